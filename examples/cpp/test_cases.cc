@@ -262,6 +262,24 @@ void TestHeatmap(matplot::figure_handle f) {
   ax->title("Heatmap");
 }
 
+void TestHeatmapMasked(matplot::figure_handle f) {
+  if (!f) return;
+  f->color({0., 1.f, 1.f, 1.f});
+  f->children(
+      std::vector<std::shared_ptr<matplot::axes_type>>{});
+  auto ax = f->add_axes();
+  std::vector<std::vector<double>> data(
+      10, std::vector<double>(10));
+  for (size_t i = 0; i < 10; ++i) {
+    for (size_t j = 0; j < 10; ++j) {
+      // A masked diagonal band next to cells at the minimum.
+      data[i][j] = (i + j) % 7 == 3 ? std::nan("") : (i * j) % 5;
+    }
+  }
+  ax->heatmap(data);
+  ax->title("Masked Heatmap");
+}
+
 void TestContour(matplot::figure_handle f) {
   if (!f) return;
   f->color({0., 1.f, 1.f, 1.f});
@@ -400,6 +418,7 @@ std::vector<TestCaseEntry> GetAllTestCases() {
       {"subplots", TestSubplot},
       {"helix_3d", Test3DLine},
       {"heatmap", TestHeatmap},
+      {"heatmap_masked", TestHeatmapMasked},
       {"stem_plot", TestStemPlot},
       {"area_plot", TestAreaPlot},
       {"scatter_labels", TestScatterLabels},
@@ -5,7 +5,8 @@
 //!
 //! Pre-renders all test cases, compares against golden references,
 //! and presents an SDL2 window with in-window status bar showing
-//! pass/fail/new status and RMSE values.
+//! pass/fail/new status and RMSE values.  The window fits the largest
+//! case; smaller cases are shown at the top-left of the content area.
 //!
 //! # Usage
 //!
@@ -15,9 +16,16 @@
 //!
 //! # Key bindings
 //!
-//! - Left/Right: navigate test cases
-//! - 1–8: jump to test case by number
-//! - Tab: cycle display mode (Live/Golden/Ref/RefDiff/Diff)
+//! - Left/Right: navigate test cases (those matching the list filter)
+//! - 1–9: jump to test case by number
+//! - /: open the case list; type to filter (substring or glob such as
+//!   `*_plot`), Up/Down/PageUp/PageDown to select, Enter to jump,
+//!   Esc to close
+//! - Tab: cycle display mode (Live/Golden/Wipe/Split/Ref/RefDiff/Diff)
+//! - Mouse: inspect the RGBA of live and golden under the cursor; in
+//!   Wipe mode the divider between live (left) and golden (right)
+//!   follows it
+//! - Z: toggle magnified live and golden loupes around the cursor
 //! - B: bless current test (save render as golden)
 //! - A: bless ALL tests
 //! - G: generate gnuplot references for all tests
@@ -25,19 +33,55 @@
 //! - S: save current render to tests/output/
 //! - Q/Esc: quit
 
-use mpl_wgpu::capture::PlotCapture;
+use mpl_wgpu::capture::{self, PlotCapture};
 use mpl_wgpu::compare;
-use mpl_wgpu::plotting::GnuplotFigure;
+use mpl_wgpu::context::RenderContext;
+use mpl_wgpu::golden;
+use mpl_wgpu::reference::{ReferenceGenerator, ReferenceOptions};
 use mpl_wgpu::test_cases;
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
-use sdl2::event::Event;
+use sdl2::event::{Event, WindowEvent};
 use sdl2::keyboard::Keycode;
 use std::path::PathBuf;
+use std::sync::Arc;
 
-const WIDTH: u32 = 800;
-const HEIGHT: u32 = 600;
 const STATUS_H: u32 = 60;
-const WIN_H: u32 = HEIGHT + STATUS_H;
+/// Smallest content area, enough for the status bar and case list.
+const MIN_WIDTH: u32 = 640;
+const MIN_HEIGHT: u32 = 400;
+/// Loupe size in pixels and magnification.
+const LOUPE: u32 = 160;
+const LOUPE_FACTOR: u32 = 8;
+/// Most entries shown at once in the case list.
+const LIST_ROWS: usize = 36;
+
+/// Window dimensions, derived from the test case sizes.
+#[derive(Clone, Copy)]
+struct Layout {
+  /// Window and content area width.
+  width: u32,
+  /// Content area height; the status bar follows below it.
+  height: u32,
+}
+
+impl Layout {
+  /// A content area holding the largest of `cases`.
+  fn new(cases: &[test_cases::TestCase]) -> Self {
+    let (w, h) = test_cases::max_size(cases);
+    Self { width: w.max(MIN_WIDTH), height: h.max(MIN_HEIGHT) }
+  }
+
+  fn win_h(self) -> u32 {
+    self.height + STATUS_H
+  }
+
+  /// Entries shown at once in the case list: [`LIST_ROWS`], or fewer
+  /// if the content area is too short.
+  fn list_rows(self) -> usize {
+    let fit = ((self.height - 30) / (FONT_H as u32 + 4)).saturating_sub(2);
+    (fit as usize).min(LIST_ROWS)
+  }
+}
 
 // -----------------------------------------------------------------
 // Embedded bitmap font (6x10, printable ASCII 0x20..=0x7E)
@@ -257,9 +301,10 @@ fn draw_char(
     return;
   }
   let glyph = &FONT[idx - 0x20];
+  let buf_h = buf.len() / 4 / buf_w as usize;
   for row in 0..FONT_H {
     let py = y as usize + row;
-    if py >= (HEIGHT + STATUS_H) as usize {
+    if py >= buf_h {
       continue;
     }
     let bits = glyph[row];
@@ -301,6 +346,10 @@ fn draw_string(
 enum DisplayMode {
   Live,
   Golden,
+  /// Live left of the cursor, golden right of it.
+  Wipe,
+  /// Live and golden at half size side by side.
+  Split,
   Reference,
   RefDiff,
   Diff,
@@ -310,7 +359,9 @@ impl DisplayMode {
   fn next(self) -> Self {
     match self {
       Self::Live => Self::Golden,
-      Self::Golden => Self::Reference,
+      Self::Golden => Self::Wipe,
+      Self::Wipe => Self::Split,
+      Self::Split => Self::Reference,
       Self::Reference => Self::RefDiff,
       Self::RefDiff => Self::Diff,
       Self::Diff => Self::Live,
@@ -321,6 +372,8 @@ impl DisplayMode {
     match self {
       Self::Live => "Live",
       Self::Golden => "Golden",
+      Self::Wipe => "Wipe",
+      Self::Split => "Split",
       Self::Reference => "Ref",
       Self::RefDiff => "RefDiff",
       Self::Diff => "Diff",
@@ -328,6 +381,50 @@ impl DisplayMode {
   }
 }
 
+// -----------------------------------------------------------------
+// Pixel inspector
+// -----------------------------------------------------------------
+
+#[derive(Default)]
+struct Inspector {
+  /// Mouse position in the content area, if it is there.
+  cursor: Option<(u32, u32)>,
+  zoom: bool,
+}
+
+impl Inspector {
+  fn set_cursor(&mut self, x: i32, y: i32, layout: Layout) {
+    self.cursor = (x >= 0 && y >= 0 && (x as u32) < layout.width
+      && (y as u32) < layout.height)
+      .then_some((x as u32, y as u32));
+  }
+
+  /// Column of the wipe divider in an image `width` wide.
+  fn wipe_x(&self, width: u32) -> u32 {
+    self.cursor.map_or(width / 2, |(x, _)| x.min(width))
+  }
+
+  /// Pixel of the live and golden images of `size` under the
+  /// cursor.  In Split mode either half maps back to full-size
+  /// coordinates.
+  fn source_pixel(
+    &self,
+    mode: DisplayMode,
+    (w, h): (u32, u32),
+  ) -> Option<(u32, u32)> {
+    let (x, y) = self.cursor?;
+    if mode != DisplayMode::Split {
+      return (x < w && y < h).then_some((x, y));
+    }
+    let (half_w, half_h) = (w / 2, h / 2);
+    let top = (h - half_h) / 2;
+    if x >= half_w * 2 || y < top || y >= top + half_h {
+      return None;
+    }
+    Some(((x % half_w) * 2, (y - top) * 2))
+  }
+}
+
 // -----------------------------------------------------------------
 // Test status
 // -----------------------------------------------------------------
@@ -362,6 +459,8 @@ impl TestStatus {
 // -----------------------------------------------------------------
 
 struct CachedTest {
+  /// Size of `pixels`, `golden` and `reference`.
+  size: (u32, u32),
   pixels: Vec<u8>,
   golden: Option<Vec<u8>>,
   reference: Option<Vec<u8>>,
@@ -392,28 +491,29 @@ fn output_dir() -> PathBuf {
     .join("output")
 }
 
-fn load_golden(name: &str) -> Option<Vec<u8>> {
+/// The golden of `name`, if it exists at `(w, h)`.
+fn load_golden(name: &str, (w, h): (u32, u32)) -> Option<Vec<u8>> {
   let path = golden_dir().join(format!("{}.png", name));
   if !path.exists() {
     return None;
   }
   let img = image::open(&path).ok()?.to_rgba8();
-  if img.width() != WIDTH || img.height() != HEIGHT {
+  if img.width() != w || img.height() != h {
     return None;
   }
   Some(img.into_raw())
 }
 
-fn load_reference(name: &str) -> Option<Vec<u8>> {
+/// The reference of `name`, resized to `(w, h)` if needed.
+fn load_reference(name: &str, (w, h): (u32, u32)) -> Option<Vec<u8>> {
   let path = reference_dir().join(format!("{}.png", name));
   if !path.exists() {
     return None;
   }
   let img = image::open(&path).ok()?.to_rgba8();
-  // Resize to match our capture dimensions if needed.
-  if img.width() != WIDTH || img.height() != HEIGHT {
+  if img.width() != w || img.height() != h {
     let resized = image::imageops::resize(
-      &img, WIDTH, HEIGHT,
+      &img, w, h,
       image::imageops::FilterType::Lanczos3,
     );
     return Some(resized.into_raw());
@@ -430,18 +530,21 @@ const DEFAULT_MAX_DIFF_PCT: f64 = 2.0;
 
 /// Renders a test case and computes its status.
 fn render_test(
+  ctx: &RenderContext,
   tc: &test_cases::TestCase,
 ) -> CachedTest {
-  let mut cap = PlotCapture::new(WIDTH, HEIGHT);
+  let (w, h) = tc.size;
+  let mut cap = PlotCapture::with_context(ctx, w, h);
   let fig = cap.figure();
   (tc.setup)(&fig);
-  let pixels = cap.render_and_capture();
-  let golden = load_golden(tc.name);
+  let pixels = cap
+    .render_and_capture()
+    .expect("read back frame");
+  let golden = load_golden(tc.name, tc.size);
 
   let (status, rmse) = match &golden {
     Some(g) => {
-      let r =
-        compare::compare_images(&pixels, g, WIDTH, HEIGHT);
+      let r = compare::compare_images(&pixels, g, w, h);
       let st = if r.rmse <= DEFAULT_MAX_RMSE
         && r.diff_pct <= DEFAULT_MAX_DIFF_PCT
       {
@@ -454,22 +557,21 @@ fn render_test(
     None => (TestStatus::New, 0.0),
   };
 
-  let reference = load_reference(tc.name);
+  let reference = load_reference(tc.name, tc.size);
   let ref_rmse = match &reference {
-    Some(r) => {
-      // Reference may differ in size; only compare if match.
-      let ref_pixels = r.len() as u32 / 4;
-      if ref_pixels == WIDTH * HEIGHT {
-        compare::compare_images(&pixels, r, WIDTH, HEIGHT)
-          .rmse
-      } else {
-        0.0
-      }
-    }
+    Some(r) => compare::compare_images(&pixels, r, w, h).rmse,
     None => 0.0,
   };
 
-  CachedTest { pixels, golden, reference, status, rmse, ref_rmse }
+  CachedTest {
+    size: tc.size,
+    pixels,
+    golden,
+    reference,
+    status,
+    rmse,
+    ref_rmse,
+  }
 }
 
 /// Refreshes cached state after bless.
@@ -482,8 +584,8 @@ fn refresh_after_bless(
   image::save_buffer(
     &path,
     &cached.pixels,
-    WIDTH,
-    HEIGHT,
+    cached.size.0,
+    cached.size.1,
     image::ColorType::Rgba8,
   )
   .expect("Failed to bless golden image");
@@ -499,130 +601,167 @@ fn generate_reference(
   tc: &test_cases::TestCase,
   cached: &mut CachedTest,
 ) {
-  let gnuplot_fig = GnuplotFigure::new();
-  let fig = gnuplot_fig.figure();
-  (tc.setup)(&fig);
-  let ref_dir = reference_dir();
-  std::fs::create_dir_all(&ref_dir).ok();
-  let path = ref_dir.join(format!("{}.png", tc.name));
-  gnuplot_fig.save(path.to_str().unwrap_or(""));
+  let generator = ReferenceGenerator::new(
+    reference_dir(),
+    ReferenceOptions::new().size(tc.size.0, tc.size.1),
+  );
+  if let Err(e) = generator.generate(tc) {
+    eprintln!("  {}: {}", tc.name, e);
+  }
   // Reload the saved PNG to get pixel data at any size,
-  // then resize to WIDTH x HEIGHT for comparison.
-  cached.reference = load_reference(tc.name);
+  // then resize to the case size for comparison.
+  let (w, h) = tc.size;
+  cached.reference = load_reference(tc.name, tc.size);
   cached.ref_rmse = match &cached.reference {
-    Some(r) => {
-      let ref_pixels = r.len() as u32 / 4;
-      if ref_pixels == WIDTH * HEIGHT {
-        compare::compare_images(
-          &cached.pixels, r, WIDTH, HEIGHT,
-        )
-        .rmse
-      } else {
-        0.0
-      }
-    }
+    Some(r) => compare::compare_images(&cached.pixels, r, w, h).rmse,
     None => 0.0,
   };
 }
 
-/// Builds the composite frame buffer (800x660) with status bar.
+/// Builds the composite frame buffer of `layout` with status bar.
 fn build_frame(
+  layout: Layout,
   cached: &CachedTest,
   mode: DisplayMode,
   idx: usize,
   total: usize,
   name: &str,
+  inspector: &Inspector,
 ) -> Vec<u8> {
-  let stride = WIDTH as usize * 4;
-  let total_pixels = (WIDTH * WIN_H) as usize * 4;
-  let mut buf = vec![0u8; total_pixels];
-
-  // Helper: blit a diff image, draw status bar, return.
-  let blit_diff = |buf: &mut Vec<u8>, diff: &[u8]| {
-    for row in 0..HEIGHT as usize {
-      let src_off = row * stride;
-      let dst_off = row * stride;
-      buf[dst_off..dst_off + stride]
-        .copy_from_slice(&diff[src_off..src_off + stride]);
-    }
-  };
+  let stride = layout.width as usize * 4;
+  let total_pixels = (layout.width * layout.win_h()) as usize * 4;
+  // Dark backdrop around cases smaller than the content area.
+  let mut buf = vec![0x20u8; total_pixels];
+  let (w, h) = cached.size;
+
+  // Gray placeholder when the compared image does not exist.
+  let gray = || vec![128; (w * h * 4) as usize];
 
-  // Content area (top 800x600).
+  // Content area, the case at the top-left.
   let content = match mode {
-    DisplayMode::Live => &cached.pixels,
+    DisplayMode::Live => cached.pixels.clone(),
     DisplayMode::Golden => {
-      if let Some(ref g) = cached.golden {
-        g
-      } else {
-        &cached.pixels
-      }
+      cached.golden.clone().unwrap_or_else(|| cached.pixels.clone())
     }
-    DisplayMode::Reference => {
-      if let Some(ref r) = cached.reference {
-        r
-      } else {
-        // Gray placeholder when no reference exists.
-        let gray =
-          vec![128; (WIDTH * HEIGHT * 4) as usize];
-        blit_diff(&mut buf, &gray);
-        draw_status_bar(
-          &mut buf, idx, total, name, cached, mode,
-        );
-        return buf;
+    DisplayMode::Wipe => match cached.golden {
+      Some(ref g) => compare::wipe(
+        &cached.pixels, g, w, inspector.wipe_x(w),
+      ),
+      None => cached.pixels.clone(),
+    },
+    DisplayMode::Split => match cached.golden {
+      Some(ref g) => {
+        compare::side_by_side(&cached.pixels, g, w, h)
       }
+      None => {
+        compare::side_by_side(&cached.pixels, &gray(), w, h)
+      }
+    },
+    DisplayMode::Reference => {
+      cached.reference.clone().unwrap_or_else(gray)
     }
-    DisplayMode::RefDiff => {
-      let diff = if let Some(ref r) = cached.reference {
-        compare::diff_pixels(&cached.pixels, r)
-      } else {
-        vec![128; (WIDTH * HEIGHT * 4) as usize]
-      };
-      blit_diff(&mut buf, &diff);
-      draw_status_bar(
-        &mut buf, idx, total, name, cached, mode,
-      );
-      return buf;
-    }
-    DisplayMode::Diff => {
-      let diff = if let Some(ref g) = cached.golden {
-        compare::diff_pixels(&cached.pixels, g)
-      } else {
-        vec![128; (WIDTH * HEIGHT * 4) as usize]
-      };
-      blit_diff(&mut buf, &diff);
-      draw_status_bar(
-        &mut buf, idx, total, name, cached, mode,
-      );
-      return buf;
-    }
+    DisplayMode::RefDiff => match cached.reference {
+      Some(ref r) => compare::diff_pixels(&cached.pixels, r),
+      None => gray(),
+    },
+    DisplayMode::Diff => match cached.golden {
+      Some(ref g) => compare::diff_pixels(&cached.pixels, g),
+      None => gray(),
+    },
   };
 
-  for row in 0..HEIGHT as usize {
-    let src_off = row * stride;
-    let dst_off = row * stride;
-    buf[dst_off..dst_off + stride]
-      .copy_from_slice(&content[src_off..src_off + stride]);
+  let row_len = w as usize * 4;
+  for (row, src) in content.chunks_exact(row_len).enumerate() {
+    buf[row * stride..row * stride + row_len].copy_from_slice(src);
+  }
+
+  if mode == DisplayMode::Wipe && cached.golden.is_some() {
+    // Yellow divider: live on the left, golden on the right.
+    let x = inspector.wipe_x(w).min(w - 1) as usize;
+    for row in 0..h as usize {
+      let p = row * stride + x * 4;
+      buf[p..p + 4].copy_from_slice(&[255, 220, 0, 255]);
+    }
   }
 
-  draw_status_bar(&mut buf, idx, total, name, cached, mode);
+  if inspector.zoom {
+    draw_loupes(&mut buf, layout, cached, mode, inspector);
+  }
+
+  draw_status_bar(
+    &mut buf, layout, idx, total, name, cached, mode, inspector,
+  );
   buf
 }
 
+/// Draws magnified live and golden neighbourhoods of the inspected
+/// pixel into the top-right corner.
+fn draw_loupes(
+  buf: &mut [u8],
+  layout: Layout,
+  cached: &CachedTest,
+  mode: DisplayMode,
+  inspector: &Inspector,
+) {
+  let Some((sx, sy)) = inspector.source_pixel(mode, cached.size)
+  else {
+    return;
+  };
+  let stride = layout.width as usize * 4;
+  let images = [
+    ("live", Some(&cached.pixels)),
+    ("golden", cached.golden.as_ref()),
+  ];
+  let x0 = layout.width - LOUPE - 10;
+  for (i, (label, img)) in images.into_iter().enumerate() {
+    let Some(img) = img else { continue };
+    let y0 = 10 + i as u32 * (LOUPE + FONT_H as u32 + 14);
+    let loupe = compare::magnify(
+      img, cached.size.0, sx, sy, LOUPE, LOUPE_FACTOR,
+    );
+    for row in 0..LOUPE as usize {
+      let dst = (y0 as usize + row) * stride + x0 as usize * 4;
+      let src = row * LOUPE as usize * 4;
+      buf[dst..dst + LOUPE as usize * 4]
+        .copy_from_slice(&loupe[src..src + LOUPE as usize * 4]);
+    }
+    // Outline the centre pixel.
+    let c = (LOUPE / LOUPE_FACTOR / 2) * LOUPE_FACTOR;
+    for k in 0..LOUPE_FACTOR {
+      for (x, y) in [
+        (c + k, c),
+        (c + k, c + LOUPE_FACTOR - 1),
+        (c, c + k),
+        (c + LOUPE_FACTOR - 1, c + k),
+      ] {
+        let p = (y0 + y) as usize * stride + (x0 + x) as usize * 4;
+        buf[p..p + 4].copy_from_slice(&[255, 0, 255, 255]);
+      }
+    }
+    draw_string(
+      buf, layout.width, x0, y0 + LOUPE + 4, label, [255, 0, 255],
+    );
+  }
+}
+
 /// Draws the 60px status bar at the bottom of the frame buffer.
+#[allow(clippy::too_many_arguments)]
 fn draw_status_bar(
   buf: &mut [u8],
+  layout: Layout,
   idx: usize,
   total: usize,
   name: &str,
   cached: &CachedTest,
   mode: DisplayMode,
+  inspector: &Inspector,
 ) {
-  let stride = WIDTH as usize * 4;
+  let stride = layout.width as usize * 4;
 
   // Fill status bar background (#333333).
-  for row in HEIGHT as usize..WIN_H as usize {
+  for row in layout.height as usize..layout.win_h() as usize {
     let off = row * stride;
-    for col in 0..WIDTH as usize {
+    for col in 0..layout.width as usize {
       let p = off + col * 4;
       buf[p] = 0x33;
       buf[p + 1] = 0x33;
@@ -633,20 +772,22 @@ fn draw_status_bar(
 
   let white = [255, 255, 255];
   let gray = [160, 160, 160];
-  let y1 = HEIGHT + 10;
-  let y2 = HEIGHT + 28;
+  let width = layout.width;
+  let y1 = layout.height + 6;
+  let y2 = layout.height + 24;
+  let y3 = layout.height + 42;
 
-  // Line 1: [idx/total] name  STATUS  RMSE=x.xx
+  // Line 1: [idx/total] name  STATUS  RMSE=x.xx  WxH
   let nav = format!("[{}/{}]", idx + 1, total);
   let mut x = 10u32;
-  draw_string(buf, WIDTH, x, y1, &nav, white);
+  draw_string(buf, width, x, y1, &nav, white);
   x += (nav.len() as u32 + 1) * FONT_W as u32;
 
-  draw_string(buf, WIDTH, x, y1, name, white);
+  draw_string(buf, width, x, y1, name, white);
   x += (name.len() as u32 + 2) * FONT_W as u32;
 
   draw_string(
-    buf, WIDTH, x, y1, cached.status.label(),
+    buf, width, x, y1, cached.status.label(),
     cached.status.color(),
   );
   x += (cached.status.label().len() as u32 + 2)
@@ -659,19 +800,222 @@ fn draw_status_bar(
     _ => cached.rmse,
   };
   let rmse_str = format!("RMSE={:.2}", shown_rmse);
-  draw_string(buf, WIDTH, x, y1, &rmse_str, white);
+  draw_string(buf, width, x, y1, &rmse_str, white);
   x += (rmse_str.len() as u32 + 2) * FONT_W as u32;
 
   let mode_str = format!("[{}]", mode.label());
-  draw_string(buf, WIDTH, x, y1, &mode_str, gray);
+  draw_string(buf, width, x, y1, &mode_str, gray);
+  x += (mode_str.len() as u32 + 2) * FONT_W as u32;
+
+  let size_str = format!("{}x{}", cached.size.0, cached.size.1);
+  draw_string(buf, width, x, y1, &size_str, gray);
 
   // Line 2: key hints.
   draw_string(
-    buf, WIDTH, 10, y2,
-    "<-/-> nav  Tab mode  B bless  A all  \
+    buf, width, 10, y2,
+    "<-/-> nav  Tab mode  / list  Z zoom  B bless  A all  \
      G gnuplot  R rerender  S save  Q quit",
     gray,
   );
+
+  // Line 3: pixel inspector.
+  if let Some((px, py)) = inspector.source_pixel(mode, cached.size) {
+    let w = cached.size.0;
+    let rgba = |img: Option<&Vec<u8>>| {
+      match img.and_then(|i| compare::pixel_at(i, w, px, py)) {
+        Some([r, g, b, a]) => format!("{:3},{:3},{:3},{:3}", r, g, b, a),
+        None => "-".to_string(),
+      }
+    };
+    let live = rgba(Some(&cached.pixels));
+    let golden = rgba(cached.golden.as_ref());
+    let color = if live == golden { white } else { [255, 120, 120] };
+    let text = format!(
+      "({:3},{:3})  live {}  golden {}",
+      px, py, live, golden,
+    );
+    draw_string(buf, width, 10, y3, &text, color);
+  }
+}
+
+// -----------------------------------------------------------------
+// Case list
+// -----------------------------------------------------------------
+
+/// Filterable overlay listing every test case.
+#[derive(Default)]
+struct CaseList {
+  /// Entries shown at once, see [`Layout::list_rows`].
+  rows: usize,
+  open: bool,
+  filter: String,
+  /// Position of the highlighted entry among the visible ones.
+  selected: usize,
+}
+
+/// What a key press in the open case list asks for.
+enum ListAction {
+  None,
+  Jump(usize),
+  Close,
+}
+
+impl CaseList {
+  fn new(layout: Layout) -> Self {
+    Self { rows: layout.list_rows(), ..Self::default() }
+  }
+
+  /// Whether `name` passes the filter: a glob (see
+  /// [`golden::matches`]) if it contains `*` or `?`, otherwise a
+  /// case-insensitive substring.
+  fn matches(&self, name: &str) -> bool {
+    if self.filter.contains(['*', '?']) {
+      golden::matches(&self.filter, name)
+    } else {
+      name.to_lowercase().contains(&self.filter.to_lowercase())
+    }
+  }
+
+  /// Indices of the cases passing the filter.
+  fn visible(&self, names: &[&str]) -> Vec<usize> {
+    (0..names.len()).filter(|&i| self.matches(names[i])).collect()
+  }
+
+  /// The case `delta` steps from `current` among those passing the
+  /// filter, wrapping around.  Steps through all cases when none
+  /// pass.
+  fn step(&self, names: &[&str], current: usize, delta: isize) -> usize {
+    let mut visible = self.visible(names);
+    if visible.is_empty() {
+      visible = (0..names.len()).collect();
+    }
+    let n = visible.len() as isize;
+    let pos = match visible.iter().position(|&i| i == current) {
+      Some(p) => p as isize + delta,
+      // Not visible: the first one in the step direction.
+      None => {
+        let after = visible.iter().position(|&i| i > current);
+        let p = after.unwrap_or(visible.len()) as isize;
+        if delta > 0 { p } else { p - 1 }
+      }
+    };
+    visible[pos.rem_euclid(n) as usize]
+  }
+
+  fn open(&mut self, names: &[&str], current: usize) {
+    self.open = true;
+    self.selected = self
+      .visible(names)
+      .iter()
+      .position(|&i| i == current)
+      .unwrap_or(0);
+  }
+
+  fn handle(&mut self, event: &Event, names: &[&str]) -> ListAction {
+    let visible = self.visible(names);
+    match event {
+      Event::TextInput { text, .. } => {
+        self.filter.push_str(text);
+        self.selected = 0;
+      }
+      Event::KeyDown { keycode: Some(kc), .. } => match *kc {
+        Keycode::Escape => return ListAction::Close,
+        Keycode::Return | Keycode::KpEnter => {
+          return match visible.get(self.selected) {
+            Some(&i) => ListAction::Jump(i),
+            None => ListAction::Close,
+          };
+        }
+        Keycode::Backspace => {
+          self.filter.pop();
+          self.selected = 0;
+        }
+        Keycode::Up => {
+          self.selected = self.selected.saturating_sub(1);
+        }
+        Keycode::Down => {
+          self.selected =
+            (self.selected + 1).min(visible.len().saturating_sub(1));
+        }
+        Keycode::PageUp => {
+          self.selected = self.selected.saturating_sub(self.rows);
+        }
+        Keycode::PageDown => {
+          self.selected = (self.selected + self.rows)
+            .min(visible.len().saturating_sub(1));
+        }
+        _ => {}
+      },
+      _ => {}
+    }
+    ListAction::None
+  }
+
+  /// Draws the list over the content area of `buf`.
+  fn draw(
+    &self,
+    buf: &mut [u8],
+    layout: Layout,
+    names: &[&str],
+    cached: &[CachedTest],
+    current: usize,
+  ) {
+    let width = layout.width;
+    let stride = width as usize * 4;
+    let row_h = FONT_H as u32 + 4;
+    let (x0, x1) = (150u32, width - 150);
+    let (y0, y1) = (20u32, 30 + (self.rows as u32 + 2) * row_h);
+
+    // Darkened panel.
+    for y in y0..y1 {
+      for x in x0..x1 {
+        let p = y as usize * stride + x as usize * 4;
+        for c in &mut buf[p..p + 3] {
+          *c /= 5;
+        }
+      }
+    }
+
+    let visible = self.visible(names);
+    let header = format!(
+      "Filter: {}_   {}/{} cases",
+      self.filter,
+      visible.len(),
+      names.len(),
+    );
+    draw_string(buf, width, x0 + 8, y0 + 6, &header, [255, 255, 255]);
+
+    // Scroll so the selected entry stays in view.
+    let first = self.selected.saturating_sub(self.rows - 1);
+    for (row, &i) in
+      visible.iter().enumerate().skip(first).take(self.rows)
+    {
+      let y = y0 + 6 + (row - first + 1) as u32 * row_h + 4;
+      if row == self.selected {
+        for yy in y - 2..y + row_h - 2 {
+          for x in x0 + 4..x1 - 4 {
+            let p = yy as usize * stride + x as usize * 4;
+            buf[p..p + 3].copy_from_slice(&[0x50, 0x50, 0x80]);
+          }
+        }
+      }
+      let status = cached[i].status;
+      let marker = if i == current { '>' } else { ' ' };
+      draw_string(
+        buf, width, x0 + 8, y,
+        &format!("{}{:3}", marker, i + 1),
+        [160, 160, 160],
+      );
+      draw_string(
+        buf, width, x0 + 8 + 6 * FONT_W as u32, y,
+        status.label(), status.color(),
+      );
+      draw_string(
+        buf, width, x0 + 8 + 12 * FONT_W as u32, y,
+        names[i], [255, 255, 255],
+      );
+    }
+  }
 }
 
 // -----------------------------------------------------------------
@@ -685,55 +1029,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     return Ok(());
   }
 
-  // Pre-render all test cases.
-  eprintln!("Pre-rendering {} test cases...", cases.len());
-  let mut cached: Vec<CachedTest> = cases
-    .iter()
-    .enumerate()
-    .map(|(i, tc)| {
-      eprintln!(
-        "  [{}/{}] {}...",
-        i + 1,
-        cases.len(),
-        tc.name
-      );
-      render_test(tc)
-    })
-    .collect();
 
-  // Print summary.
-  let n_pass =
-    cached.iter().filter(|c| c.status == TestStatus::Pass).count();
-  let n_fail =
-    cached.iter().filter(|c| c.status == TestStatus::Fail).count();
-  let n_new =
-    cached.iter().filter(|c| c.status == TestStatus::New).count();
-  let n_ref =
-    cached.iter().filter(|c| c.reference.is_some()).count();
-  eprintln!(
-    "Summary: {} pass, {} fail, {} new, {} refs",
-    n_pass, n_fail, n_new, n_ref,
-  );
-  if n_ref == 0 {
-    eprintln!(
-      "Hint: Press G to generate gnuplot references."
-    );
-  }
-
-  // Start on the first failing test, or the first test.
-  let start_idx = cached
-    .iter()
-    .position(|c| c.status == TestStatus::Fail)
-    .or_else(|| {
-      cached.iter().position(|c| c.status == TestStatus::New)
-    })
-    .unwrap_or(0);
-
-  // SDL2 init.
+  // SDL2 init, sized for the largest case.
+  let layout = Layout::new(&cases);
   let sdl = sdl2::init()?;
   let video = sdl.video()?;
   let window = video
-    .window("mpl-wgpu Visual Review", WIDTH, WIN_H)
+    .window("mpl-wgpu Visual Review", layout.width, layout.win_h())
     .position_centered()
     .build()?;
 
@@ -781,6 +1083,53 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
       None,
     ),
   )?;
+  let (device, queue) = (Arc::new(device), Arc::new(queue));
+
+  // Pre-render all test cases on the window's device.
+  let capture_ctx =
+    capture::capture_context_on(device.clone(), queue.clone())?;
+  eprintln!("Pre-rendering {} test cases...", cases.len());
+  let mut cached: Vec<CachedTest> = cases
+    .iter()
+    .enumerate()
+    .map(|(i, tc)| {
+      eprintln!(
+        "  [{}/{}] {}...",
+        i + 1,
+        cases.len(),
+        tc.name
+      );
+      render_test(&capture_ctx, tc)
+    })
+    .collect();
+
+  // Print summary.
+  let n_pass =
+    cached.iter().filter(|c| c.status == TestStatus::Pass).count();
+  let n_fail =
+    cached.iter().filter(|c| c.status == TestStatus::Fail).count();
+  let n_new =
+    cached.iter().filter(|c| c.status == TestStatus::New).count();
+  let n_ref =
+    cached.iter().filter(|c| c.reference.is_some()).count();
+  eprintln!(
+    "Summary: {} pass, {} fail, {} new, {} refs",
+    n_pass, n_fail, n_new, n_ref,
+  );
+  if n_ref == 0 {
+    eprintln!(
+      "Hint: Press G to generate gnuplot references."
+    );
+  }
+
+  // Start on the first failing test, or the first test.
+  let start_idx = cached
+    .iter()
+    .position(|c| c.status == TestStatus::Fail)
+    .or_else(|| {
+      cached.iter().position(|c| c.status == TestStatus::New)
+    })
+    .unwrap_or(0);
 
   let surface_caps = surface.get_capabilities(&adapter);
   let surface_format = surface_caps
@@ -793,8 +1142,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
   let config = wgpu::SurfaceConfiguration {
     usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
     format: surface_format,
-    width: WIDTH,
-    height: WIN_H,
+    width: layout.width,
+    height: layout.win_h(),
     present_mode: wgpu::PresentMode::Fifo,
     alpha_mode: surface_caps.alpha_modes[0],
     view_formats: vec![],
@@ -807,8 +1156,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     device.create_texture(&wgpu::TextureDescriptor {
       label: Some("BlitTexture"),
       size: wgpu::Extent3d {
-        width: WIDTH,
-        height: WIN_H,
+        width: layout.width,
+        height: layout.win_h(),
         depth_or_array_layers: 1,
       },
       mip_level_count: 1,
@@ -957,11 +1306,34 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
   let mut current_idx: usize = start_idx;
   let mut mode = DisplayMode::Live;
   let mut needs_present = true;
+  let mut inspector = Inspector::default();
+  let mut list = CaseList::new(layout);
+  let names: Vec<&str> = cases.iter().map(|tc| tc.name).collect();
+
+  // Typed text is only wanted while the case list is open.
+  let text_input = video.text_input();
+  text_input.stop();
 
   let mut event_pump = sdl.event_pump()?;
 
   'running: loop {
     for event in event_pump.poll_iter() {
+      if list.open && !matches!(event, Event::Quit { .. }) {
+        match list.handle(&event, &names) {
+          ListAction::Jump(i) => {
+            current_idx = i;
+            list.open = false;
+          }
+          ListAction::Close => list.open = false,
+          ListAction::None => {}
+        }
+        if !list.open {
+          text_input.stop();
+        }
+        needs_present = true;
+        continue;
+      }
+
       match event {
         Event::Quit { .. }
         | Event::KeyDown {
@@ -977,8 +1349,7 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
           keycode: Some(Keycode::Right),
           ..
         } => {
-          current_idx =
-            (current_idx + 1) % cases.len();
+          current_idx = list.step(&names, current_idx, 1);
           needs_present = true;
         }
 
@@ -986,40 +1357,17 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
           keycode: Some(Keycode::Left),
           ..
         } => {
-          current_idx = if current_idx == 0 {
-            cases.len() - 1
-          } else {
-            current_idx - 1
-          };
+          current_idx = list.step(&names, current_idx, -1);
           needs_present = true;
         }
 
-        // Number keys 1-8 for direct jump.
+        // Number keys 1-9 jump to the first nine cases.
         Event::KeyDown {
           keycode: Some(kc), ..
-        } if matches!(
-          kc,
-          Keycode::Num1
-            | Keycode::Num2
-            | Keycode::Num3
-            | Keycode::Num4
-            | Keycode::Num5
-            | Keycode::Num6
-            | Keycode::Num7
-            | Keycode::Num8
-        ) =>
+        } if (Keycode::Num1.into_i32()..=Keycode::Num9.into_i32())
+          .contains(&kc.into_i32()) =>
         {
-          let n = match kc {
-            Keycode::Num1 => 0,
-            Keycode::Num2 => 1,
-            Keycode::Num3 => 2,
-            Keycode::Num4 => 3,
-            Keycode::Num5 => 4,
-            Keycode::Num6 => 5,
-            Keycode::Num7 => 6,
-            Keycode::Num8 => 7,
-            _ => unreachable!(),
-          };
+          let n = (kc.into_i32() - Keycode::Num1.into_i32()) as usize;
           if n < cases.len() {
             current_idx = n;
             needs_present = true;
@@ -1034,6 +1382,38 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
           needs_present = true;
         }
 
+        // /: open the filterable case list.
+        Event::KeyDown {
+          keycode: Some(Keycode::Slash),
+          ..
+        } => {
+          list.open(&names, current_idx);
+          text_input.start();
+          needs_present = true;
+        }
+
+        // Z: toggle the magnifying loupes.
+        Event::KeyDown {
+          keycode: Some(Keycode::Z),
+          ..
+        } => {
+          inspector.zoom = !inspector.zoom;
+          needs_present = true;
+        }
+
+        Event::MouseMotion { x, y, .. } => {
+          inspector.set_cursor(x, y, layout);
+          needs_present = true;
+        }
+
+        Event::Window {
+          win_event: WindowEvent::Leave,
+          ..
+        } => {
+          inspector.cursor = None;
+          needs_present = true;
+        }
+
         // B: bless current test.
         Event::KeyDown {
           keycode: Some(Keycode::B),
@@ -1093,7 +1473,7 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
             cases[current_idx].name,
           );
           cached[current_idx] =
-            render_test(&cases[current_idx]);
+            render_test(&capture_ctx, &cases[current_idx]);
           needs_present = true;
         }
 
@@ -1109,8 +1489,8 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
           image::save_buffer(
             &path,
             &cached[current_idx].pixels,
-            WIDTH,
-            HEIGHT,
+            cached[current_idx].size.0,
+            cached[current_idx].size.1,
             image::ColorType::Rgba8,
           )
           .ok();
@@ -1124,13 +1504,20 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
     if needs_present {
       needs_present = false;
 
-      let frame_buf = build_frame(
+      let mut frame_buf = build_frame(
+        layout,
         &cached[current_idx],
         mode,
         current_idx,
         cases.len(),
         cases[current_idx].name,
+        &inspector,
       );
+      if list.open {
+        list.draw(
+          &mut frame_buf, layout, &names, &cached, current_idx,
+        );
+      }
 
       // Upload to blit texture.
       queue.write_texture(
@@ -1143,12 +1530,12 @@ fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
         &frame_buf,
         wgpu::ImageDataLayout {
           offset: 0,
-          bytes_per_row: Some(WIDTH * 4),
-          rows_per_image: Some(WIN_H),
+          bytes_per_row: Some(layout.width * 4),
+          rows_per_image: Some(layout.win_h()),
         },
         wgpu::Extent3d {
-          width: WIDTH,
-          height: WIN_H,
+          width: layout.width,
+          height: layout.win_h(),
           depth_or_array_layers: 1,
         },
       );
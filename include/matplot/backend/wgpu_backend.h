@@ -11,6 +11,7 @@
 #include <array>
 #include <memory>
 #include <string>
+#include <unordered_map>
 #include <vector>
 
 namespace matplot::backend {
@@ -313,6 +314,51 @@ class WgpuBackend : public backend_interface {
    /// @brief Disables the scissor rect.
    void disable_scissor();
 
+  /// @brief Part of an axes drawn between begin_axes() and end_axes().
+  enum class AxesPart { data, x_axis, y_axis, box };
+
+  /// @brief Tags the primitives drawn until end_axes() as `part` of
+  /// `axes`, whose box is at `position` (normalized {x, y, w, h}).
+  /// Spine offsets, tick padding and right y axes only move lines and
+  /// text tagged with their axes and part.  Rectangles, markers and
+  /// triangles drawn for a part other than data are dropped, so the
+  /// axes background is drawn once, with the data.
+  void begin_axes(const void* axes, AxesPart part, const std::array<float, 4>& position);
+
+  /// @brief Ends the part started by begin_axes().
+  void end_axes();
+
+  /// @brief Whether `axes` has a spine offset, tick padding or right y
+  /// axis, so its axis parts must be drawn separately from its data.
+  bool draws_in_parts(const void* axes) const;
+
+  /// @brief Whether the spines of `axes` are offset, so its box must be
+  /// drawn separately from its data.
+  bool despined(const void* axes) const;
+
+  /// @brief Draws the left/bottom spines of `axes` offset outward by
+  /// `offset` pixels, with their ticks and labels, and hides the
+  /// top/right spines.
+  void set_spine_offset(const void* axes, float offset);
+
+  /// @brief Removes all spine offsets.
+  void clear_spine_offsets() { spine_offsets_.clear(); }
+
+  /// @brief Moves the tick labels and axis labels of `axes` `padding`
+  /// pixels further out from the left and bottom of the box.
+  void set_tick_padding(const void* axes, float padding);
+
+  /// @brief Moves the y axis of `axes` to the right side, `offset`
+  /// pixels outside the box.  Used to stack additional y axes next to
+  /// each other.
+  void add_right_y_axis(const void* axes, float offset);
+
+  /// @brief Axes box {left, top, right, bottom} in render pixels for
+  /// the axes at `position` (normalized {x, y, w, h}).
+  std::array<float, 4> axes_box(const std::array<float, 4>& position) const {
+    return AxesBoxPixels(position);
+  }
+
   /// @brief Override to indicate this backend doesn't support gnuplot
   /// This prevents matplot++ from trying to send gnuplot commands
   void run_command(const std::string&) override {}
@@ -361,6 +407,44 @@ class WgpuBackend : public backend_interface {
 
   // Helper: Reconstruct rectangles from pending segments
   void ReconstructRectangles();
+
+  // Lines and text drawn for one part of one axes
+  struct AxesSpan {
+    const void* axes;
+    AxesPart part;
+    std::array<float, 4> position;  // normalized {x, y, w, h}
+    size_t lines_begin, lines_end;
+    size_t texts_begin, texts_end;
+  };
+  std::vector<AxesSpan> spans_;
+  // Span being drawn, and where the other primitives started
+  AxesSpan open_span_{};
+  bool span_open_ = false;
+  size_t rects_begin_ = 0, circles_begin_ = 0, triangles_begin_ = 0;
+
+  // Spans of `part` of `axes` drawn this frame
+  std::vector<const AxesSpan*> SpansOf(const void* axes, AxesPart part) const;
+
+  // Axes whose spines are offset ("despined"), in pixels
+  std::unordered_map<const void*, float> spine_offsets_;
+
+  // Helper: Move/hide spines, ticks and labels of despined axes
+  void ApplySpineOffsets();
+
+  // Axes whose y axis is drawn right of the box, pixels outside it
+  std::unordered_map<const void*, float> right_y_axes_;
+
+  // Helper: Mirror the y axes of right y axes to the right
+  void ApplyRightYAxes();
+
+  // Axes whose labels are padded away from the box, in pixels
+  std::unordered_map<const void*, float> tick_paddings_;
+
+  // Helper: Move the labels of padded axes away from their box
+  void ApplyTickPadding();
+
+  // Helper: Axes box {left, top, right, bottom} in render pixels
+  std::array<float, 4> AxesBoxPixels(const std::array<float, 4>& position) const;
 };
 
 }  // namespace matplot::backend
@@ -0,0 +1,342 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Scale bars, north arrows and axis offset labels.
+//!
+//! Image-based and spatial plots often hide the axis ticks; a
+//! [`ScaleBar`] then tells the reader how long a distance is in data
+//! units, and a [`NorthArrow`] which way is up on the map.  Both sit in
+//! a [`Corner`] of the axes box at a fixed pixel size and are laid out
+//! from the current limits at draw time, so the bar stays correct
+//! through pan and zoom.  An [`OffsetLabel`] shows the offset and
+//! multiplier taken out of large-magnitude tick labels.  Add them with
+//! [`PlotBackend::add_scale_bar`](crate::plotting::PlotBackend::add_scale_bar)
+//! [`PlotBackend::add_north_arrow`](crate::plotting::PlotBackend::add_north_arrow)
+//! and [`PlotBackend::format_ticks_with_offset`](crate::plotting::PlotBackend::format_ticks_with_offset).
+
+use crate::artist::{Artist, DataRect, DrawContext};
+use crate::ticks::{self, Auto, OffsetScale, TickFormatter};
+use glam::{Vec2, Vec4};
+
+/// Drawn above the other artists.
+const ADORNMENT_Z: i32 = 10;
+
+/// Corner of the axes box.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Corner {
+  /// Top left.
+  TopLeft,
+  /// Top right.
+  TopRight,
+  /// Bottom left.
+  BottomLeft,
+  /// Bottom right.
+  #[default]
+  BottomRight,
+}
+
+impl Corner {
+  /// Top-left of a `size` box inset by `pad` into this corner of the
+  /// pixel box `[left, top, right, bottom]`.
+  pub fn place(self, pixel_box: [f32; 4], size: Vec2, pad: f32) -> Vec2 {
+    let [left, top, right, bottom] = pixel_box;
+    let x = match self {
+      Corner::TopLeft | Corner::BottomLeft => left + pad,
+      Corner::TopRight | Corner::BottomRight => right - pad - size.x,
+    };
+    let y = match self {
+      Corner::TopLeft | Corner::TopRight => top + pad,
+      Corner::BottomLeft | Corner::BottomRight => bottom - pad - size.y,
+    };
+    Vec2::new(x, y)
+  }
+}
+
+/// Round length (1, 2 or 5 times a power of ten) closest to but not
+/// above `fraction` of `span`.
+pub fn nice_length(span: f64, fraction: f64) -> f64 {
+  let target = span.abs() * fraction;
+  if !target.is_finite() || target <= 0.0 {
+    return 0.0;
+  }
+  let mag = 10f64.powf(target.log10().floor());
+  [5.0, 2.0, 1.0]
+    .iter()
+    .map(|m| m * mag)
+    .find(|&l| l <= target)
+    .unwrap_or(mag)
+}
+
+/// A bar of known length in X data units, with a label.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScaleBar {
+  /// Length in data units, or `None` to pick a round length about a
+  /// fifth of the visible X range.
+  pub length: Option<f64>,
+  /// Unit appended to the length in the label (`"km"`, `"µm"`).
+  pub unit: String,
+  /// Corner of the axes box.
+  pub corner: Corner,
+  /// Bar and label color.
+  pub color: Vec4,
+  /// Bar thickness, in points.
+  pub width: f32,
+  /// Label font size, in pixels.
+  pub font_size: f32,
+  /// Inset from the axes box edges, in pixels.
+  pub pad: f32,
+}
+
+impl ScaleBar {
+  /// Bar `length` data units long.
+  pub fn new(length: f64, unit: &str) -> Self {
+    Self {
+      length: Some(length),
+      ..Self::auto(unit)
+    }
+  }
+
+  /// Bar with a round length picked from the visible range.
+  pub fn auto(unit: &str) -> Self {
+    Self {
+      length: None,
+      unit: unit.to_string(),
+      corner: Corner::BottomRight,
+      color: Vec4::new(0.0, 0.0, 0.0, 1.0),
+      width: 2.0,
+      font_size: 10.0,
+      pad: 10.0,
+    }
+  }
+
+  /// Sets the corner.
+  pub fn corner(mut self, corner: Corner) -> Self {
+    self.corner = corner;
+    self
+  }
+
+  /// Sets the color.
+  pub fn color(mut self, color: Vec4) -> Self {
+    self.color = color;
+    self
+  }
+
+  /// Length drawn for the X limits `(x_min, x_max)`.
+  pub fn length_for(&self, x: (f64, f64)) -> f64 {
+    self.length.unwrap_or_else(|| nice_length(x.1 - x.0, 0.2))
+  }
+
+  /// Label for a bar `length` long.
+  pub fn label(&self, length: f64) -> String {
+    let value = Auto.format(length, length);
+    if self.unit.is_empty() {
+      value
+    } else {
+      format!("{} {}", value, self.unit)
+    }
+  }
+}
+
+impl Artist for ScaleBar {
+  fn draw(&self, ctx: &mut DrawContext<'_>) {
+    let l = ctx.limits();
+    let length = self.length_for((l.x_min, l.x_max));
+    let pixels = (ctx.to_pixels(l.x_min + length, l.y_min) - ctx.to_pixels(l.x_min, l.y_min)).x.abs();
+    if length <= 0.0 || !pixels.is_finite() || pixels < 1.0 {
+      return;
+    }
+    let label = self.label(length);
+    let text_w = ctx.measure_text(&label, self.font_size);
+    let tick = 4.0;
+    let size = Vec2::new(pixels.max(text_w), self.font_size + 3.0 + tick);
+    let origin = self.corner.place(ctx.pixel_box(), size, self.pad);
+    let y = origin.y + size.y - tick * 0.5;
+    let (x0, x1) = (origin.x + (size.x - pixels) * 0.5, origin.x + (size.x + pixels) * 0.5);
+    let (a, b) = (ctx.screen(Vec2::new(x0, y)), ctx.screen(Vec2::new(x1, y)));
+    ctx.line_pixels(a, b, self.width, self.color);
+    for x in [x0, x1] {
+      let (a, b) = (ctx.screen(Vec2::new(x, y - tick)), ctx.screen(Vec2::new(x, y + tick * 0.5)));
+      ctx.line_pixels(a, b, self.width * 0.5, self.color);
+    }
+    let at = Vec2::new(origin.x + (size.x - text_w) * 0.5, origin.y);
+    ctx.text_pixels(at, &label, self.font_size, self.color);
+  }
+
+  fn extent(&self) -> Option<DataRect> {
+    None
+  }
+
+  fn z_order(&self) -> i32 {
+    ADORNMENT_Z
+  }
+}
+
+/// A compass arrow pointing north, labelled `N`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NorthArrow {
+  /// Corner of the axes box.
+  pub corner: Corner,
+  /// Direction of north in degrees clockwise from straight up.
+  pub bearing: f32,
+  /// Arrow length, in pixels.
+  pub size: f32,
+  /// Arrow and label color.
+  pub color: Vec4,
+  /// Inset from the axes box edges, in pixels.
+  pub pad: f32,
+}
+
+impl Default for NorthArrow {
+  fn default() -> Self {
+    Self {
+      corner: Corner::TopRight,
+      bearing: 0.0,
+      size: 30.0,
+      color: Vec4::new(0.0, 0.0, 0.0, 1.0),
+      pad: 10.0,
+    }
+  }
+}
+
+impl NorthArrow {
+  /// Arrow pointing straight up in the top-right corner.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the corner.
+  pub fn corner(mut self, corner: Corner) -> Self {
+    self.corner = corner;
+    self
+  }
+
+  /// Sets the direction of north, in degrees clockwise from up.
+  pub fn bearing(mut self, degrees: f32) -> Self {
+    self.bearing = degrees;
+    self
+  }
+}
+
+impl Artist for NorthArrow {
+  fn draw(&self, ctx: &mut DrawContext<'_>) {
+    let label_size = self.size * 0.4;
+    // The arrow turns inside a square box with the label above it.
+    let side = self.size + label_size + 4.0;
+    let origin = self.corner.place(ctx.pixel_box(), Vec2::splat(side), self.pad);
+    let center = origin + Vec2::new(side * 0.5, label_size + 4.0 + self.size * 0.5);
+    let (s, c) = self.bearing.to_radians().sin_cos();
+    // Unit vector towards north on the Y-down screen, and its normal.
+    let up = Vec2::new(s, -c);
+    let normal = Vec2::new(c, s);
+    let tip = center + up * self.size * 0.5;
+    let tail = center - up * self.size * 0.5;
+    let head = tip - up * self.size * 0.35;
+    let (a, b) = (ctx.screen(tail), ctx.screen(head));
+    ctx.line_pixels(a, b, 1.5, self.color);
+    let half = self.size * 0.15;
+    ctx.triangle_pixels([tip, head + normal * half, head - normal * half], self.color);
+    let w = ctx.measure_text("N", label_size);
+    let at = tip + up * (label_size * 0.5 + 2.0) - Vec2::new(w * 0.5, label_size * 0.5);
+    ctx.text_pixels(at, "N", label_size, self.color);
+  }
+
+  fn extent(&self) -> Option<DataRect> {
+    None
+  }
+
+  fn z_order(&self) -> i32 {
+    ADORNMENT_Z
+  }
+}
+
+/// Axis an [`OffsetLabel`] belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelAxis {
+  /// Shown under the right end of the X axis.
+  X,
+  /// Shown above the top of the Y axis.
+  Y,
+}
+
+/// Offset and multiplier of an axis's tick labels (`×10³ +1.7e9`),
+/// taken from the round ticks over the current limits at draw time
+/// like the labels of [`PlotBackend::format_ticks_with_offset`](crate::plotting::PlotBackend::format_ticks_with_offset).
+#[derive(Debug, Clone, PartialEq)]
+pub struct OffsetLabel {
+  /// Axis the label belongs to.
+  pub axis: LabelAxis,
+  /// Font size, in pixels.
+  pub font_size: f32,
+  /// Text color.
+  pub color: Vec4,
+}
+
+impl OffsetLabel {
+  /// Label of `axis`'s offset scale (see [`OffsetScale::label`]).
+  pub fn new(axis: LabelAxis) -> Self {
+    Self {
+      axis,
+      font_size: 10.0,
+      color: Vec4::new(0.0, 0.0, 0.0, 1.0),
+    }
+  }
+}
+
+impl Artist for OffsetLabel {
+  fn draw(&self, ctx: &mut DrawContext<'_>) {
+    let limits = ctx.limits();
+    let (min, max) = match self.axis {
+      LabelAxis::X => (limits.x_min, limits.x_max),
+      LabelAxis::Y => (limits.y_min, limits.y_max),
+    };
+    let (placed, _) = ticks::nice_ticks(min, max, ticks::DEFAULT_MAX_TICKS);
+    let text = OffsetScale::for_ticks(&placed).label();
+    if text.is_empty() {
+      return;
+    }
+    let [left, top, right, bottom] = ctx.pixel_box();
+    let at = match self.axis {
+      // Below the tick labels, right-aligned with the axis end.
+      LabelAxis::X => {
+        let w = ctx.measure_text(&text, self.font_size);
+        Vec2::new(right - w, bottom + self.font_size * 2.0 + 4.0)
+      }
+      LabelAxis::Y => Vec2::new(left, top - self.font_size - 4.0),
+    };
+    ctx.text_pixels(at, &text, self.font_size, self.color);
+  }
+
+  fn extent(&self) -> Option<DataRect> {
+    None
+  }
+
+  fn z_order(&self) -> i32 {
+    ADORNMENT_Z
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn round_lengths() {
+    assert_eq!(nice_length(100.0, 0.2), 20.0);
+    assert_eq!(nice_length(37.0, 0.2), 5.0);
+    assert_eq!(nice_length(0.9, 0.2), 0.1);
+    assert_eq!(nice_length(0.0, 0.2), 0.0);
+    assert_eq!(nice_length(f64::NAN, 0.2), 0.0);
+  }
+
+  #[test]
+  fn labels_and_placement() {
+    let bar = ScaleBar::auto("km");
+    assert_eq!(bar.length_for((0.0, 1000.0)), 200.0);
+    assert_eq!(bar.label(200.0), "200 km");
+    assert_eq!(ScaleBar::new(0.5, "").label(0.5), "0.5");
+    let bbox = [10.0, 20.0, 110.0, 220.0];
+    let size = Vec2::new(30.0, 10.0);
+    assert_eq!(Corner::TopLeft.place(bbox, size, 5.0), Vec2::new(15.0, 25.0));
+    assert_eq!(Corner::BottomRight.place(bbox, size, 5.0), Vec2::new(75.0, 205.0));
+  }
+}
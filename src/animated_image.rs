@@ -0,0 +1,178 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Animated GIF and APNG encoding.
+//!
+//! A [`FrameEncoder`] takes the RGBA frames the capture pipeline reads
+//! back, one at a time, and writes them as an animated image that loops
+//! forever.  GIF is supported everywhere but limited to 256 colors per
+//! frame and delays in hundredths of a second; APNG keeps every pixel
+//! exact and needs the frame count before the first frame.
+//!
+//! [`Animation::save`](crate::animation::Animation::save) renders and
+//! encodes a whole animation, picking the format from the extension.
+
+use crate::error::{Error, Result};
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, RgbaImage};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// GIF quantizer speed, 1 (best colors) to 30 (fastest).
+const GIF_SPEED: i32 = 10;
+
+/// Animated image format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationFormat {
+  /// Animated GIF.
+  Gif,
+  /// Animated PNG.
+  Apng,
+}
+
+impl AnimationFormat {
+  /// Format for the extension of `path`: `.gif`, or `.png` / `.apng`.
+  pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+    let ext = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+      "gif" => Some(AnimationFormat::Gif),
+      "png" | "apng" => Some(AnimationFormat::Apng),
+      _ => None,
+    }
+  }
+}
+
+enum Inner<W: Write> {
+  Gif(GifEncoder<W>),
+  Apng(png::Writer<W>),
+}
+
+/// Writes RGBA frames of one size as a looping animated image.
+pub struct FrameEncoder<W: Write> {
+  inner: Inner<W>,
+  width: u32,
+  height: u32,
+  delay: Duration,
+}
+
+impl FrameEncoder<BufWriter<File>> {
+  /// Encoder writing to the file at `path` in the format of its
+  /// extension (see [`AnimationFormat::from_path`]).
+  pub fn create(path: impl AsRef<Path>, width: u32, height: u32, fps: f32, frames: u32) -> Result<Self> {
+    let path = path.as_ref();
+    let format = AnimationFormat::from_path(path).ok_or_else(|| Error::UnsupportedFormat(path.into()))?;
+    let file = BufWriter::new(File::create(path)?);
+    Self::new(file, format, width, height, fps, frames)
+  }
+}
+
+impl<W: Write> FrameEncoder<W> {
+  /// Encoder writing `frames` frames of `width` x `height` pixels at
+  /// `fps` frames per second to `writer`.  Only APNG uses `frames`.
+  pub fn new(writer: W, format: AnimationFormat, width: u32, height: u32, fps: f32, frames: u32) -> Result<Self> {
+    let delay = frame_delay(fps);
+    let inner = match format {
+      AnimationFormat::Gif => {
+        let mut gif = GifEncoder::new_with_speed(writer, GIF_SPEED);
+        gif.set_repeat(Repeat::Infinite)?;
+        Inner::Gif(gif)
+      }
+      AnimationFormat::Apng => {
+        let mut png = png::Encoder::new(writer, width, height);
+        png.set_color(png::ColorType::Rgba);
+        png.set_depth(png::BitDepth::Eight);
+        png.set_animated(frames, 0)?;
+        let (num, den) = apng_delay(delay);
+        png.set_frame_delay(num, den)?;
+        Inner::Apng(png.write_header()?)
+      }
+    };
+    Ok(Self { inner, width, height, delay })
+  }
+
+  /// Appends a frame of tightly packed RGBA pixels.
+  pub fn push(&mut self, pixels: &[u8]) -> Result<()> {
+    match &mut self.inner {
+      Inner::Gif(gif) => {
+        let image = RgbaImage::from_raw(self.width, self.height, pixels.to_vec())
+          .ok_or(Error::FrameSize(pixels.len()))?;
+        gif.encode_frame(Frame::from_parts(image, 0, 0, Delay::from_saturating_duration(self.delay)))?;
+      }
+      Inner::Apng(png) => png.write_image_data(pixels)?,
+    }
+    Ok(())
+  }
+
+  /// Finishes the file.  An APNG fails unless exactly the announced
+  /// number of frames was pushed.
+  pub fn finish(self) -> Result<()> {
+    match self.inner {
+      // The GIF trailer is written when the encoder is dropped.
+      Inner::Gif(gif) => drop(gif),
+      Inner::Apng(png) => png.finish()?,
+    }
+    Ok(())
+  }
+}
+
+/// Time each frame is shown at `fps`; rates that are not positive and
+/// finite show frames for a second.
+fn frame_delay(fps: f32) -> Duration {
+  if fps.is_finite() && fps > 0.0 {
+    Duration::from_secs_f64(1.0 / fps as f64)
+  } else {
+    Duration::from_secs(1)
+  }
+}
+
+/// `delay` as an APNG delay fraction in milliseconds.
+fn apng_delay(delay: Duration) -> (u16, u16) {
+  (delay.as_millis().clamp(1, u16::MAX as u128) as u16, 1000)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use image::AnimationDecoder;
+
+  fn frames() -> [Vec<u8>; 2] {
+    [[255, 0, 0, 255].repeat(4), [0, 0, 255, 255].repeat(4)]
+  }
+
+  #[test]
+  fn formats_follow_the_extension() {
+    assert_eq!(AnimationFormat::from_path("a/anim.GIF"), Some(AnimationFormat::Gif));
+    assert_eq!(AnimationFormat::from_path("anim.apng"), Some(AnimationFormat::Apng));
+    assert_eq!(AnimationFormat::from_path("anim.mp4"), None);
+    assert_eq!(apng_delay(frame_delay(25.0)), (40, 1000));
+    assert_eq!(frame_delay(0.0), Duration::from_secs(1));
+  }
+
+  #[test]
+  fn encodes_looping_gifs_and_apngs() {
+    let mut gif = Vec::new();
+    let mut encoder = FrameEncoder::new(&mut gif, AnimationFormat::Gif, 2, 2, 10.0, 2).unwrap();
+    for f in frames() {
+      encoder.push(&f).unwrap();
+    }
+    assert!(matches!(encoder.push(&[0; 3]), Err(Error::FrameSize(3))));
+    encoder.finish().unwrap();
+    let decoded = image::codecs::gif::GifDecoder::new(std::io::Cursor::new(gif)).unwrap();
+    let decoded = decoded.into_frames().collect_frames().unwrap();
+    assert_eq!(decoded.len(), 2);
+    assert_eq!(decoded[1].buffer().get_pixel(0, 0).0, [0, 0, 255, 255]);
+    assert_eq!(decoded[0].delay(), Delay::from_numer_denom_ms(100, 1));
+
+    let mut apng = Vec::new();
+    let mut encoder = FrameEncoder::new(&mut apng, AnimationFormat::Apng, 2, 2, 10.0, 2).unwrap();
+    for f in frames() {
+      encoder.push(&f).unwrap();
+    }
+    encoder.finish().unwrap();
+    let decoder = png::Decoder::new(std::io::Cursor::new(apng)).read_info().unwrap();
+    let control = decoder.info().animation_control.unwrap();
+    assert_eq!((control.num_frames, control.num_plays), (2, 0));
+  }
+}
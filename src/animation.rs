@@ -0,0 +1,225 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Frame-by-frame animation.
+//!
+//! An [`Animation`] calls an update function with the frame index and
+//! the figure once per frame, like matplotlib's `FuncAnimation`.  It
+//! runs at a fixed frame rate for a fixed number of frames, or forever
+//! without one, and can be driven three ways: stepped by hand with
+//! [`step`](Animation::step), rendered off-screen frame by frame with
+//! [`capture`](Animation::capture) and
+//! [`save_frames`](Animation::save_frames), encoded to a GIF or APNG
+//! with [`save`](Animation::save), or played in a window with
+//! [`show`](Animation::show).  Off-screen frames are not paced; the
+//! frame rate only sets the time between frames in a window and the
+//! time [`frame_time`](Animation::frame_time) reports.
+//!
+//! ```rust,no_run
+//! use mpl_wgpu::animation::Animation;
+//! use mpl_wgpu::handle::FigureHandle;
+//! use mpl_wgpu::plotting::{linspace, PlotBackend};
+//!
+//! let figure = FigureHandle::new(PlotBackend::new(800, 600));
+//! let x = linspace(0.0, 6.28, 200);
+//! let mut anim = Animation::new(figure, move |frame, plot| {
+//!   let phase = frame as f64 * 0.1;
+//!   let y: Vec<f64> = x.iter().map(|v| (v + phase).sin()).collect();
+//!   plot.figure().clear();
+//!   plot.figure().current_axes().plot(&x, &y, "-");
+//! })
+//! .fps(30.0)
+//! .frames(90);
+//! anim.save_frames("frames").expect("rendered frames");
+//! ```
+
+use crate::animated_image::FrameEncoder;
+use crate::capture::HeadlessRenderer;
+use crate::error::{Error, Result};
+use crate::handle::FigureHandle;
+use crate::plotting::PlotBackend;
+#[cfg(feature = "window")]
+use crate::window::{self, WindowOptions};
+use std::path::{Path, PathBuf};
+#[cfg(feature = "window")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "window")]
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(feature = "window")]
+use std::time::Instant;
+
+/// Frame rate of a new animation.
+pub const DEFAULT_FPS: f32 = 30.0;
+
+/// A figure updated once per frame by a function of the frame index.
+pub struct Animation<F> {
+  figure: FigureHandle,
+  update: F,
+  fps: f32,
+  frames: Option<usize>,
+  next: usize,
+}
+
+impl<F: FnMut(usize, &mut PlotBackend)> Animation<F> {
+  /// Animation of `figure` calling `update(frame, plot)` for frames
+  /// 0, 1, 2, ... at [`DEFAULT_FPS`], without an end.
+  pub fn new(figure: FigureHandle, update: F) -> Self {
+    Self { figure, update, fps: DEFAULT_FPS, frames: None, next: 0 }
+  }
+
+  /// Sets the frame rate; rates that are not positive and finite are
+  /// ignored.
+  pub fn fps(mut self, fps: f32) -> Self {
+    if fps.is_finite() && fps > 0.0 {
+      self.fps = fps;
+    }
+    self
+  }
+
+  /// Stops after `count` frames.
+  pub fn frames(mut self, count: usize) -> Self {
+    self.frames = Some(count);
+    self
+  }
+
+  /// The animated figure.
+  pub fn figure(&self) -> &FigureHandle {
+    &self.figure
+  }
+
+  /// Index of the next frame.
+  pub fn frame(&self) -> usize {
+    self.next
+  }
+
+  /// Time between frames.
+  pub fn interval(&self) -> Duration {
+    Duration::from_secs_f64(1.0 / self.fps as f64)
+  }
+
+  /// Time of `frame` since the start of the animation.
+  pub fn frame_time(&self, frame: usize) -> Duration {
+    Duration::from_secs_f64(frame as f64 / self.fps as f64)
+  }
+
+  /// Whether every frame has been shown.
+  pub fn is_done(&self) -> bool {
+    self.frames.is_some_and(|n| self.next >= n)
+  }
+
+  /// Runs the update for the next frame.  Returns `false`, without
+  /// updating, once the animation is done.
+  pub fn step(&mut self) -> bool {
+    if self.is_done() {
+      return false;
+    }
+    let (frame, update) = (self.next, &mut self.update);
+    self.figure.with(|plot| update(frame, plot));
+    self.next += 1;
+    true
+  }
+
+  /// Starts over at frame 0.  The figure keeps its current state.
+  pub fn rewind(&mut self) {
+    self.next = 0;
+  }
+
+  /// Renders the remaining frames off-screen at the figure's size and
+  /// hands each frame's RGBA pixels to `sink`, e.g. to encode a video.
+  /// Returns the number of frames rendered.  Without a frame count,
+  /// runs until `sink` fails.
+  pub fn capture(&mut self, mut sink: impl FnMut(usize, &[u8]) -> Result<()>) -> Result<usize> {
+    let (width, height) = self.figure.with(|plot| plot.size());
+    let mut headless = HeadlessRenderer::new(width, height)?;
+    let mut count = 0;
+    while self.step() {
+      let pixels = headless.capture_figure(&self.figure)?;
+      sink(self.next - 1, &pixels)?;
+      count += 1;
+    }
+    Ok(count)
+  }
+
+  /// Renders the remaining frames (see [`capture`](Self::capture)) as
+  /// `frame_00000.png`, `frame_00001.png`, ... into `dir` and returns
+  /// their paths.
+  pub fn save_frames(&mut self, dir: impl AsRef<Path>) -> Result<Vec<PathBuf>> {
+    let dir = dir.as_ref();
+    std::fs::create_dir_all(dir)?;
+    let (width, height) = self.figure.with(|plot| plot.size());
+    let mut paths = Vec::new();
+    self.capture(|frame, pixels| {
+      let path = dir.join(frame_name(frame));
+      image::save_buffer(&path, pixels, width, height, image::ColorType::Rgba8)?;
+      paths.push(path);
+      Ok(())
+    })?;
+    Ok(paths)
+  }
+
+  /// Renders the remaining frames (see [`capture`](Self::capture)) into
+  /// a looping animated GIF or APNG played at `fps` frames per second.
+  /// The format follows the extension of `path` (see
+  /// [`AnimationFormat::from_path`](crate::animated_image::AnimationFormat::from_path)).
+  /// Returns the number of frames written.  Fails with
+  /// [`Error::EndlessAnimation`] without a frame count.
+  pub fn save(&mut self, path: impl AsRef<Path>, fps: f32) -> Result<usize> {
+    let Some(total) = self.frames else { return Err(Error::EndlessAnimation) };
+    let remaining = total.saturating_sub(self.next);
+    let (width, height) = self.figure.with(|plot| plot.size());
+    let mut encoder = FrameEncoder::create(path, width, height, fps, remaining as u32)?;
+    let count = self.capture(|_, pixels| encoder.push(pixels))?;
+    encoder.finish()?;
+    Ok(count)
+  }
+}
+
+#[cfg(feature = "window")]
+impl<F: FnMut(usize, &mut PlotBackend) + Send + 'static> Animation<F> {
+  /// Plays the animation in a window (see [`window::show`]) and blocks
+  /// until it is closed.  Frames are updated on a worker thread at the
+  /// frame rate; the window keeps showing the last frame once the
+  /// animation is done.
+  pub fn show(mut self, options: WindowOptions) -> Result<()> {
+    let figure = self.figure.clone();
+    let stop = Arc::new(AtomicBool::new(false));
+    let driver = {
+      let stop = stop.clone();
+      std::thread::spawn(move || {
+        let start = Instant::now();
+        let interval = self.interval();
+        while !stop.load(Ordering::Relaxed) {
+          // Frame times are fixed from the start so delays do not add up.
+          let due = start + interval.mul_f64(self.next as f64);
+          std::thread::sleep(due.saturating_duration_since(Instant::now()));
+          if !self.step() {
+            break;
+          }
+        }
+      })
+    };
+    let shown = window::show(figure, options);
+    stop.store(true, Ordering::Relaxed);
+    if let Err(panic) = driver.join() {
+      std::panic::resume_unwind(panic);
+    }
+    shown
+  }
+}
+
+/// File name of frame `frame` written by [`Animation::save_frames`].
+pub fn frame_name(frame: usize) -> String {
+  format!("frame_{:05}.png", frame)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn frame_names_sort_in_order() {
+    assert_eq!(frame_name(7), "frame_00007.png");
+    assert!(frame_name(99) < frame_name(100));
+  }
+}
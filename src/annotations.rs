@@ -0,0 +1,316 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Annotation layers.
+//!
+//! An [`AnnotationLayer`] holds the markup added on top of a plot
+//! (text notes, arrows, threshold lines) in data coordinates, separate
+//! from the plotted data.  Layers serialize to a small line-based text
+//! format, so markup can be saved, reloaded in a later session, and
+//! re-applied to a freshly rendered figure with
+//! [`Axes::apply_annotations`](crate::plotting::Axes::apply_annotations).
+//!
+//! The format is one header line followed by one tab-separated line
+//! per annotation:
+//!
+//! ```text
+//! mpl-wgpu-annotations 1
+//! note   x  y  r g b  text
+//! arrow  x0 y0 x1 y1  r g b
+//! hline  y  r g b  label
+//! vline  x  r g b  label
+//! ```
+
+use std::io::{Error, ErrorKind};
+use std::path::Path;
+
+/// First line of a serialized layer.
+const HEADER: &str = "mpl-wgpu-annotations 1";
+
+/// A single piece of markup, in data coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Annotation {
+  /// Text note anchored at a data point.
+  Note {
+    /// Anchor X.
+    x: f64,
+    /// Anchor Y.
+    y: f64,
+    /// Note text.
+    text: String,
+    /// RGB color.
+    color: [f32; 3],
+  },
+  /// Arrow from one data point to another.
+  Arrow {
+    /// Tail position.
+    from: (f64, f64),
+    /// Head position.
+    to: (f64, f64),
+    /// RGB color.
+    color: [f32; 3],
+  },
+  /// Horizontal threshold line spanning the X range.
+  HLine {
+    /// Threshold value.
+    y: f64,
+    /// Optional label (empty for none).
+    label: String,
+    /// RGB color.
+    color: [f32; 3],
+  },
+  /// Vertical marker line spanning the Y range.
+  VLine {
+    /// Marker position.
+    x: f64,
+    /// Optional label (empty for none).
+    label: String,
+    /// RGB color.
+    color: [f32; 3],
+  },
+}
+
+/// An ordered set of annotations, independent of the plotted data.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AnnotationLayer {
+  /// Annotations in the order they were added.
+  pub items: Vec<Annotation>,
+}
+
+impl AnnotationLayer {
+  /// Creates an empty layer.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Appends an annotation.
+  pub fn push(&mut self, annotation: Annotation) {
+    self.items.push(annotation);
+  }
+
+  /// Removes all annotations.
+  pub fn clear(&mut self) {
+    self.items.clear();
+  }
+
+  /// Serializes the layer to its text format.
+  pub fn to_text(&self) -> String {
+    let mut out = String::from(HEADER);
+    out.push('\n');
+    for a in &self.items {
+      let fields: Vec<String> = match a {
+        Annotation::Note { x, y, text, color } => {
+          let mut f = vec!["note".into(), num(*x), num(*y)];
+          f.extend(color.iter().map(|c| num(*c as f64)));
+          f.push(escape(text));
+          f
+        }
+        Annotation::Arrow { from, to, color } => {
+          let mut f =
+            vec!["arrow".into(), num(from.0), num(from.1), num(to.0), num(to.1)];
+          f.extend(color.iter().map(|c| num(*c as f64)));
+          f
+        }
+        Annotation::HLine { y: v, label, color }
+        | Annotation::VLine { x: v, label, color } => {
+          let kind = if matches!(a, Annotation::HLine { .. }) {
+            "hline"
+          } else {
+            "vline"
+          };
+          let mut f = vec![kind.into(), num(*v)];
+          f.extend(color.iter().map(|c| num(*c as f64)));
+          f.push(escape(label));
+          f
+        }
+      };
+      out.push_str(&fields.join("\t"));
+      out.push('\n');
+    }
+    out
+  }
+
+  /// Parses a layer from its text format.
+  ///
+  /// Blank lines are skipped.  A missing header, an unknown kind, or a
+  /// malformed number is an [`ErrorKind::InvalidData`] error naming
+  /// the offending line.
+  pub fn from_text(text: &str) -> std::io::Result<Self> {
+    let mut lines = text.lines().enumerate();
+    match lines.next() {
+      Some((_, h)) if h.trim_end() == HEADER => {}
+      _ => return Err(invalid(1, "missing annotation header")),
+    }
+    let mut layer = Self::new();
+    for (i, line) in lines {
+      let line = line.trim_end_matches('\r');
+      if line.is_empty() {
+        continue;
+      }
+      layer.push(parse_line(line).map_err(|msg| invalid(i + 1, msg))?);
+    }
+    Ok(layer)
+  }
+
+  /// Writes the layer to `path`.
+  pub fn save<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+    std::fs::write(path, self.to_text())
+  }
+
+  /// Reads a layer from `path`.
+  pub fn load<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+    Self::from_text(&std::fs::read_to_string(path)?)
+  }
+}
+
+/// Parses one annotation line.
+fn parse_line(line: &str) -> Result<Annotation, &'static str> {
+  let f: Vec<&str> = line.split('\t').collect();
+  let nums = |range: std::ops::Range<usize>| -> Result<Vec<f64>, &'static str> {
+    f.get(range)
+      .ok_or("too few fields")?
+      .iter()
+      .map(|s| s.parse::<f64>().map_err(|_| "malformed number"))
+      .collect()
+  };
+  let rgb = |v: &[f64]| [v[0] as f32, v[1] as f32, v[2] as f32];
+  let text = |i: usize| f.get(i).map(|s| unescape(s)).unwrap_or_default();
+  match f[0] {
+    "note" => {
+      let n = nums(1..6)?;
+      Ok(Annotation::Note {
+        x: n[0],
+        y: n[1],
+        text: text(6),
+        color: rgb(&n[2..]),
+      })
+    }
+    "arrow" => {
+      let n = nums(1..8)?;
+      Ok(Annotation::Arrow {
+        from: (n[0], n[1]),
+        to: (n[2], n[3]),
+        color: rgb(&n[4..]),
+      })
+    }
+    "hline" => {
+      let n = nums(1..5)?;
+      Ok(Annotation::HLine {
+        y: n[0],
+        label: text(5),
+        color: rgb(&n[1..]),
+      })
+    }
+    "vline" => {
+      let n = nums(1..5)?;
+      Ok(Annotation::VLine {
+        x: n[0],
+        label: text(5),
+        color: rgb(&n[1..]),
+      })
+    }
+    _ => Err("unknown annotation kind"),
+  }
+}
+
+fn invalid(line: usize, msg: &str) -> Error {
+  Error::new(ErrorKind::InvalidData, format!("line {}: {}", line, msg))
+}
+
+/// Formats a number so it parses back to the same value.
+fn num(v: f64) -> String {
+  format!("{:?}", v)
+}
+
+/// Escapes the field separator, newlines, and backslashes.
+fn escape(s: &str) -> String {
+  let mut out = String::with_capacity(s.len());
+  for ch in s.chars() {
+    match ch {
+      '\\' => out.push_str("\\\\"),
+      '\t' => out.push_str("\\t"),
+      '\n' => out.push_str("\\n"),
+      '\r' => out.push_str("\\r"),
+      c => out.push(c),
+    }
+  }
+  out
+}
+
+/// Reverses [`escape`]; unknown escapes are kept verbatim.
+fn unescape(s: &str) -> String {
+  let mut out = String::with_capacity(s.len());
+  let mut chars = s.chars();
+  while let Some(ch) = chars.next() {
+    if ch != '\\' {
+      out.push(ch);
+      continue;
+    }
+    match chars.next() {
+      Some('t') => out.push('\t'),
+      Some('n') => out.push('\n'),
+      Some('r') => out.push('\r'),
+      Some(c) => out.push(c),
+      None => out.push('\\'),
+    }
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample() -> AnnotationLayer {
+    let mut layer = AnnotationLayer::new();
+    layer.push(Annotation::Note {
+      x: 1.5,
+      y: -2.0,
+      text: "peak\tA\nline two \\o/".into(),
+      color: [1.0, 0.0, 0.0],
+    });
+    layer.push(Annotation::Arrow {
+      from: (0.1, 0.2),
+      to: (1.0 / 3.0, 4.0),
+      color: [0.0, 0.5, 1.0],
+    });
+    layer.push(Annotation::HLine {
+      y: 100.0,
+      label: "limit".into(),
+      color: [0.2, 0.2, 0.2],
+    });
+    layer.push(Annotation::VLine {
+      x: 1.7e9,
+      label: String::new(),
+      color: [0.0, 0.0, 0.0],
+    });
+    layer
+  }
+
+  #[test]
+  fn round_trips_through_text() {
+    let layer = sample();
+    let text = layer.to_text();
+    assert!(text.starts_with(HEADER));
+    assert_eq!(text.lines().count(), 5);
+    assert_eq!(AnnotationLayer::from_text(&text).unwrap(), layer);
+  }
+
+  #[test]
+  fn rejects_bad_input() {
+    assert!(AnnotationLayer::from_text("note\t1\t2").is_err());
+    let bad = format!("{}\nnote\t1\tx\t0\t0\t0\thi\n", HEADER);
+    let err = AnnotationLayer::from_text(&bad).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert!(err.to_string().contains("line 2"));
+    let unknown = format!("{}\ncircle\t1\n", HEADER);
+    assert!(AnnotationLayer::from_text(&unknown).is_err());
+  }
+
+  #[test]
+  fn blank_lines_are_skipped() {
+    let text = format!("{}\n\nhline\t5\t1\t0\t0\t\n", HEADER);
+    let layer = AnnotationLayer::from_text(&text).unwrap();
+    assert_eq!(layer.items.len(), 1);
+  }
+}
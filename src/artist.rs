@@ -0,0 +1,862 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Custom artists.
+//!
+//! An [`Artist`] is a chart type implemented outside matplot++.  Artists
+//! registered with
+//! [`PlotBackend::add_artist`](crate::plotting::PlotBackend::add_artist)
+//! are drawn on top of the matplot++ output in ascending
+//! [`Artist::z_order`], widen the axis limits to their
+//! [`Artist::extent`], and contribute [`Artist::legend`] entries.  They
+//! draw in data coordinates through a [`DrawContext`], which maps them
+//! into the axes box and feeds the same renderers and recordings as the
+//! matplot++ callbacks.
+//!
+//! An artist that panics while drawing does not take the frame down:
+//! its partial output is discarded, the other artists are still drawn,
+//! and the failure comes back from
+//! [`PlotBackend::render`](crate::plotting::PlotBackend::render) as a
+//! [`RenderWarning`].
+
+use crate::emoji;
+use crate::locale::Locale;
+use crate::mathtext::{self, MathLayout, Metrics};
+use crate::primitives::PrimitiveRenderer;
+use crate::recording::{RecordedPrimitive, Recording};
+use crate::text::{estimate_text_width, layout_on_path, TextMetrics, TextRenderer};
+use crate::units::SizeScale;
+use glam::{Mat4, Vec2, Vec3, Vec4};
+use image::RgbaImage;
+use std::sync::Arc;
+
+/// Axis-aligned rectangle in data coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DataRect {
+  /// Lower X bound.
+  pub x_min: f64,
+  /// Upper X bound.
+  pub x_max: f64,
+  /// Lower Y bound.
+  pub y_min: f64,
+  /// Upper Y bound.
+  pub y_max: f64,
+}
+
+impl DataRect {
+  /// Rectangle spanning `x` and `y`.
+  pub fn new(x: (f64, f64), y: (f64, f64)) -> Self {
+    Self {
+      x_min: x.0,
+      x_max: x.1,
+      y_min: y.0,
+      y_max: y.1,
+    }
+  }
+
+  /// Bounding box of the finite `(x, y)` pairs, if any.
+  pub fn from_points(x: &[f64], y: &[f64]) -> Option<Self> {
+    x.iter()
+      .zip(y)
+      .filter(|(x, y)| x.is_finite() && y.is_finite())
+      .map(|(&x, &y)| Self::new((x, x), (y, y)))
+      .reduce(|a, b| a.union(&b))
+  }
+
+  /// Smallest rectangle containing both.
+  pub fn union(&self, other: &DataRect) -> DataRect {
+    Self {
+      x_min: self.x_min.min(other.x_min),
+      x_max: self.x_max.max(other.x_max),
+      y_min: self.y_min.min(other.y_min),
+      y_max: self.y_max.max(other.y_max),
+    }
+  }
+
+  /// Whether `other` lies inside this rectangle.
+  pub fn contains(&self, other: &DataRect) -> bool {
+    other.x_min >= self.x_min
+      && other.x_max <= self.x_max
+      && other.y_min >= self.y_min
+      && other.y_max <= self.y_max
+  }
+}
+
+/// Mapping between data coordinates and an axes box in pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+  /// Axis limits shown in the box.
+  pub limits: DataRect,
+  /// Axes box in pixels, `[left, top, right, bottom]`.
+  pub pixels: [f32; 4],
+}
+
+impl Viewport {
+  /// Viewport showing `limits` in the pixel box `pixels`.
+  pub fn new(limits: DataRect, pixels: [f32; 4]) -> Self {
+    Self { limits, pixels }
+  }
+
+  /// Maps a data point to pixels (Y grows downwards).  A degenerate
+  /// axis range maps to the middle of the box.
+  pub fn to_pixels(&self, x: f64, y: f64) -> Vec2 {
+    let [left, top, right, bottom] = self.pixels;
+    let l = self.limits;
+    let fx = if l.x_max != l.x_min {
+      ((x - l.x_min) / (l.x_max - l.x_min)) as f32
+    } else {
+      0.5
+    };
+    let fy = if l.y_max != l.y_min {
+      ((y - l.y_min) / (l.y_max - l.y_min)) as f32
+    } else {
+      0.5
+    };
+    Vec2::new(left + fx * (right - left), bottom - fy * (bottom - top))
+  }
+
+  /// Maps a pixel position back to data coordinates.
+  pub fn to_data(&self, p: Vec2) -> (f64, f64) {
+    let [left, top, right, bottom] = self.pixels;
+    let l = self.limits;
+    let fx = if right != left { (p.x - left) / (right - left) } else { 0.5 };
+    let fy = if bottom != top { (bottom - p.y) / (bottom - top) } else { 0.5 };
+    (
+      l.x_min + fx as f64 * (l.x_max - l.x_min),
+      l.y_min + fy as f64 * (l.y_max - l.y_min),
+    )
+  }
+
+  /// Whether the pixel position lies in the axes box, edges included.
+  pub fn contains_pixel(&self, p: Vec2) -> bool {
+    let [left, top, right, bottom] = self.pixels;
+    p.x >= left && p.x <= right && p.y >= top && p.y <= bottom
+  }
+}
+
+/// A legend row contributed by an artist.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LegendEntry {
+  /// Row text.
+  pub label: String,
+  /// Swatch color (RGBA).
+  pub color: Vec4,
+}
+
+/// A problem met while rendering; the rest of the figure was still
+/// drawn.  Artists are numbered in the order they were added.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderWarning {
+  /// The artist panicked while drawing and was skipped.
+  ArtistPanicked {
+    /// Index of the artist.
+    artist: usize,
+    /// The panic message.
+    message: String,
+  },
+  /// The artist's extent is not finite and was left out of the axis
+  /// limits.
+  InvalidExtent {
+    /// Index of the artist.
+    artist: usize,
+  },
+  /// A batch of primitives from the matplot++ figure panicked while
+  /// being queued and was dropped; the rest of the figure was drawn.
+  CallbackPanicked {
+    /// The backend callback, e.g. `"draw_lines"`.
+    callback: &'static str,
+    /// The panic message.
+    message: String,
+  },
+  /// matplot++ threw while drawing the figure, which stopped there;
+  /// the artists were still drawn.
+  FigureFailed {
+    /// The exception message.
+    message: String,
+  },
+}
+
+impl std::fmt::Display for RenderWarning {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      RenderWarning::ArtistPanicked { artist, message } => {
+        write!(f, "artist {} panicked while drawing: {}", artist, message)
+      }
+      RenderWarning::InvalidExtent { artist } => write!(f, "artist {} has a non-finite extent", artist),
+      RenderWarning::CallbackPanicked { callback, message } => {
+        write!(f, "{} panicked on figure primitives: {}", callback, message)
+      }
+      RenderWarning::FigureFailed { message } => write!(f, "drawing the figure failed: {}", message),
+    }
+  }
+}
+
+/// A user-defined chart element.
+///
+/// Artists are `Send` so figures can be assembled on worker threads
+/// (see [`FigureHandle`](crate::handle::FigureHandle)).
+pub trait Artist: Send {
+  /// Draws the artist.
+  fn draw(&self, ctx: &mut DrawContext<'_>);
+
+  /// Data-space bounds used for autoscaling, or `None` to leave the
+  /// axis limits alone.
+  fn extent(&self) -> Option<DataRect>;
+
+  /// Drawing order among artists; higher values are drawn later.
+  fn z_order(&self) -> i32 {
+    0
+  }
+
+  /// Legend row for this artist, if it should have one.
+  fn legend(&self) -> Option<LegendEntry> {
+    None
+  }
+
+  /// Whether the artist is drawn beneath the figure's series (and the
+  /// other artists) rather than over them.
+  fn underlay(&self) -> bool {
+    false
+  }
+}
+
+/// Drawing surface handed to [`Artist::draw`].
+///
+/// Positions are in data coordinates, widths and marker radii in points
+/// (converted through the backend's [`SizeScale`]), and text sizes in
+/// pixels, matching the matplot++ callbacks.
+pub struct DrawContext<'a> {
+  prim: Option<&'a mut PrimitiveRenderer>,
+  text: Option<&'a mut TextRenderer>,
+  metrics: Option<&'a TextMetrics>,
+  locale: Option<&'a Locale>,
+  record: Option<&'a mut Recording>,
+  transform: Mat4,
+  sizes: SizeScale,
+  viewport: Viewport,
+}
+
+/// Queue lengths of a [`DrawContext`] at some point.
+#[derive(Clone, Copy)]
+struct DrawMark {
+  prim: usize,
+  images: usize,
+  text: usize,
+  record: usize,
+}
+
+impl<'a> DrawContext<'a> {
+  /// Context drawing into `viewport`.  `transform` is applied after
+  /// the data-to-pixel mapping, as in the backend callbacks.
+  pub(crate) fn new(
+    prim: Option<&'a mut PrimitiveRenderer>,
+    text: Option<&'a mut TextRenderer>,
+    record: Option<&'a mut Recording>,
+    transform: Mat4,
+    sizes: SizeScale,
+    viewport: Viewport,
+  ) -> Self {
+    Self {
+      prim,
+      text,
+      metrics: None,
+      locale: None,
+      record,
+      transform,
+      sizes,
+      viewport,
+    }
+  }
+
+  /// Measures text with `metrics` when there is no text renderer.
+  pub(crate) fn with_metrics(mut self, metrics: Option<&'a TextMetrics>) -> Self {
+    self.metrics = metrics;
+    self
+  }
+
+  /// Names and strings text is written with, see [`Locale`].
+  pub(crate) fn with_locale(mut self, locale: &'a Locale) -> Self {
+    self.locale = Some(locale);
+    self
+  }
+
+  /// The figure's locale, English unless one was set with
+  /// [`PlotBackend::set_locale`](crate::plotting::PlotBackend::set_locale).
+  pub fn locale(&self) -> &Locale {
+    self.locale.unwrap_or_else(|| crate::locale::english())
+  }
+
+  /// How much has been queued so far, to undo with [`Self::rollback`].
+  fn mark(&self) -> DrawMark {
+    DrawMark {
+      prim: self.prim.as_ref().map_or(0, |p| p.instance_count()),
+      images: self.prim.as_ref().map_or(0, |p| p.image_count()),
+      text: self.text.as_ref().map_or(0, |t| t.queued_count()),
+      record: self.record.as_ref().map_or(0, |r| r.primitives.len()),
+    }
+  }
+
+  /// Discards everything queued since `mark`.
+  fn rollback(&mut self, mark: DrawMark) {
+    if let Some(p) = self.prim.as_deref_mut() {
+      p.truncate(mark.prim);
+      p.truncate_images(mark.images);
+    }
+    if let Some(t) = self.text.as_deref_mut() {
+      t.truncate_queue(mark.text);
+    }
+    if let Some(r) = self.record.as_deref_mut() {
+      r.primitives.truncate(mark.record);
+    }
+  }
+
+  /// Axis limits currently shown.
+  pub fn limits(&self) -> DataRect {
+    self.viewport.limits
+  }
+
+  /// Axes box in pixels, `[left, top, right, bottom]`.
+  pub fn pixel_box(&self) -> [f32; 4] {
+    self.viewport.pixels
+  }
+
+  /// The data-to-pixel mapping.
+  pub fn viewport(&self) -> Viewport {
+    self.viewport
+  }
+
+  /// Maps a data point to pixels (Y grows downwards).
+  pub fn to_pixels(&self, x: f64, y: f64) -> Vec2 {
+    self.viewport.to_pixels(x, y)
+  }
+
+  /// Applies the context transform to a pixel position.
+  pub(crate) fn screen(&self, p: Vec2) -> Vec3 {
+    self.transform.transform_point3(p.extend(0.0))
+  }
+
+  /// Line segment between two data points, `width` in points.
+  pub fn line(&mut self, from: (f64, f64), to: (f64, f64), width: f32, color: Vec4) {
+    let start = self.screen(self.to_pixels(from.0, from.1));
+    let end = self.screen(self.to_pixels(to.0, to.1));
+    self.line_pixels(start, end, width, color);
+  }
+
+  /// Connected line through the data points, `width` in points.
+  /// Non-finite samples leave gaps instead of being joined through.
+  pub fn polyline(&mut self, x: &[f64], y: &[f64], width: f32, color: Vec4) {
+    for run in crate::mask::finite_runs(x, y) {
+      for i in run.start + 1..run.end {
+        self.line((x[i - 1], y[i - 1]), (x[i], y[i]), width, color);
+      }
+    }
+  }
+
+  /// Circular marker at a data point, `radius` in points.  Nothing is
+  /// drawn at non-finite points.
+  pub fn marker(&mut self, at: (f64, f64), radius: f32, color: Vec4) {
+    if !(at.0.is_finite() && at.1.is_finite()) {
+      return;
+    }
+    let center = self.screen(self.to_pixels(at.0, at.1));
+    let radius = self.sizes.points_to_pixels(radius);
+    if let Some(prim) = self.prim.as_deref_mut() {
+      prim.draw_circle(center, radius, color, 0.0, 1);
+    }
+    if let Some(record) = self.record.as_deref_mut() {
+      record.push(RecordedPrimitive::Marker {
+        center,
+        radius,
+        color,
+        marker_type: 1,
+      });
+    }
+  }
+
+  /// Filled rectangle spanning two data corners.
+  pub fn rect(&mut self, a: (f64, f64), b: (f64, f64), color: Vec4) {
+    let pa = self.to_pixels(a.0, a.1);
+    let pb = self.to_pixels(b.0, b.1);
+    self.rect_pixels(pa.min(pb), (pa - pb).abs(), color, 0.0);
+  }
+
+  /// Filled triangle through three data points.
+  pub fn triangle(&mut self, points: [(f64, f64); 3], color: Vec4) {
+    let [p0, p1, p2] = points.map(|(x, y)| self.screen(self.to_pixels(x, y)));
+    if let Some(prim) = self.prim.as_deref_mut() {
+      prim.draw_triangle(p0, p1, p2, color);
+    }
+    if let Some(record) = self.record.as_deref_mut() {
+      record.push(RecordedPrimitive::Triangle {
+        points: [p0, p1, p2],
+        color,
+      });
+    }
+  }
+
+  /// Text anchored (top-left) at a data point, `size` in pixels.
+  pub fn text(&mut self, at: (f64, f64), text: &str, size: f32, color: Vec4) {
+    let p = self.to_pixels(at.0, at.1);
+    self.text_pixels(p, text, size, color);
+  }
+
+  /// Text following the curve through the data points `path`, each
+  /// glyph centred on the curve and turned along it, starting `start`
+  /// pixels along (see [`layout_on_path`]).  Glyphs past the end of the
+  /// curve are dropped, and line breaks become spaces.  For contour and
+  /// streamline labels or curved annotations; math is drawn as plain
+  /// characters.
+  pub fn text_on_path(&mut self, path: &[(f64, f64)], text: &str, size: f32, color: Vec4, start: f32) {
+    let pixels: Vec<Vec2> = path
+      .iter()
+      .filter(|p| p.0.is_finite() && p.1.is_finite())
+      .map(|&(x, y)| self.screen(self.to_pixels(x, y)).truncate())
+      .collect();
+    let text = text.replace('\n', " ");
+    let glyphs: Vec<(usize, usize)> = text
+      .char_indices()
+      .map(|(i, c)| (i, i + c.len_utf8()))
+      .collect();
+    let mut width = 0.0;
+    let advances: Vec<f32> = glyphs
+      .iter()
+      .map(|&(_, end)| {
+        let w = self.measure_text(&text[..end], size);
+        let advance = w - width;
+        width = w;
+        advance
+      })
+      .collect();
+    let height = self.text_height(size);
+    for (&(i, end), (pos, rotation)) in glyphs.iter().zip(layout_on_path(&advances, height, &pixels, start)) {
+      let glyph = &text[i..end];
+      if let Some(record) = self.record.as_deref_mut() {
+        record.push(RecordedPrimitive::Text {
+          text: glyph.to_string(),
+          pos,
+          size,
+          color,
+          rotation,
+        });
+      } else if let Some(t) = self.text.as_deref_mut() {
+        t.draw_text_rotated(glyph, pos, size, color, rotation.round());
+      }
+    }
+  }
+
+  /// Height of a line of text in pixels at `size`.
+  fn text_height(&self, size: f32) -> f32 {
+    match (self.text.as_deref(), self.metrics) {
+      (Some(t), _) => t.measure_text("", size).y,
+      (None, Some(m)) => m.measure_text("", size).y,
+      (None, None) => size,
+    }
+  }
+
+  /// Width of `text` in pixels at `size`.
+  pub fn measure_text(&mut self, text: &str, size: f32) -> f32 {
+    match (self.text.as_deref(), self.metrics) {
+      (Some(t), _) => t.measure_text(text, size).x,
+      (None, Some(m)) => m.measure_text(text, size).x,
+      (None, None) if mathtext::has_math(text) => self.layout_math(text, size).size.x,
+      (None, None) => estimate_text_width(text, size),
+    }
+  }
+
+  /// Math layout from the text renderer, the metrics, or width estimates.
+  fn layout_math(&self, text: &str, size: f32) -> MathLayout {
+    match (self.text.as_deref(), self.metrics) {
+      (Some(t), _) => t.layout_math(text, size),
+      (None, Some(m)) => m.layout_math(text, size),
+      (None, None) => mathtext::layout(text, size, Metrics::default(), &estimate_text_width),
+    }
+  }
+
+  /// Line between two screen positions (see [`DrawContext::screen`]),
+  /// `width` in points.
+  pub(crate) fn line_pixels(&mut self, start: Vec3, end: Vec3, width: f32, color: Vec4) {
+    self.dashed_line_pixels(start, end, width, color, 0.0, 0.0);
+  }
+
+  /// Dashed line between two screen positions, `width` in points and
+  /// the dash pattern in multiples of the pixel width (0 = solid).
+  pub(crate) fn dashed_line_pixels(
+    &mut self,
+    start: Vec3,
+    end: Vec3,
+    width: f32,
+    color: Vec4,
+    dash: f32,
+    gap: f32,
+  ) {
+    let width = self.sizes.points_to_pixels(width);
+    let unit = width.max(1.0);
+    let (dash_len, gap_len) = (dash * unit, gap * unit);
+    if let Some(prim) = self.prim.as_deref_mut() {
+      prim.draw_line(start, end, width, color, dash_len, gap_len, 0.0);
+    }
+    if let Some(record) = self.record.as_deref_mut() {
+      record.push(RecordedPrimitive::Line {
+        start,
+        end,
+        width,
+        color,
+        dash_len,
+        gap_len,
+        dash_offset: 0.0,
+      });
+    }
+  }
+
+  /// Unlit filled triangle through three pixel positions.
+  pub(crate) fn triangle_pixels(&mut self, corners: [Vec2; 3], color: Vec4) {
+    let points = corners.map(|p| self.screen(p));
+    if let Some(prim) = self.prim.as_deref_mut() {
+      prim.draw_triangle_unlit(points[0], points[1], points[2], color);
+    }
+    if let Some(record) = self.record.as_deref_mut() {
+      record.push(RecordedPrimitive::Triangle { points, color });
+    }
+  }
+
+  /// `image` stretched over four pixel positions, clockwise from the
+  /// top-left, showing its part `uv` (`[u0, v0, u1, v1]`).  Only the
+  /// GPU renderer draws images; recordings have no textures, see
+  /// [`Self::recording`].
+  pub(crate) fn image_pixels(&mut self, image: &Arc<RgbaImage>, corners: [Vec2; 4], uv: [f32; 4], alpha: f32) {
+    let corners = corners.map(|p| self.screen(p));
+    if let Some(prim) = self.prim.as_deref_mut() {
+      prim.draw_image(image, corners, uv, alpha);
+    }
+  }
+
+  /// Whether primitives are being recorded.
+  pub(crate) fn recording(&self) -> bool {
+    self.record.is_some()
+  }
+
+  /// Unlit filled quad through four pixel positions, in order.
+  pub(crate) fn quad_pixels(&mut self, corners: [Vec2; 4], color: Vec4) {
+    let [a, b, c, d] = corners;
+    self.triangle_pixels([a, b, c], color);
+    self.triangle_pixels([a, c, d], color);
+  }
+
+  /// Rectangle at pixel position `pos` (top-left) with `size`.
+  pub(crate) fn rect_pixels(&mut self, pos: Vec2, size: Vec2, color: Vec4, stroke_width: f32) {
+    self.rounded_rect_pixels(pos, size, color, 0.0, stroke_width);
+  }
+
+  /// Rectangle at pixel position `pos` (top-left) with `size` and
+  /// corners rounded by `radius` pixels.
+  pub(crate) fn rounded_rect_pixels(&mut self, pos: Vec2, size: Vec2, color: Vec4, radius: f32, stroke_width: f32) {
+    let pos = self.screen(pos).truncate();
+    let stroke_width = self.sizes.points_to_pixels(stroke_width);
+    if let Some(prim) = self.prim.as_deref_mut() {
+      // The shader places rectangles by their center.
+      prim.draw_rect(pos + size * 0.5, size, color, radius, stroke_width);
+    }
+    if let Some(record) = self.record.as_deref_mut() {
+      record.push(RecordedPrimitive::Rect {
+        pos,
+        size,
+        color,
+        stroke_width,
+        corner_radius: radius,
+      });
+    }
+  }
+
+  /// Text anchored (top-left) at pixel position `pos`.
+  pub(crate) fn text_pixels(&mut self, pos: Vec2, text: &str, size: f32, color: Vec4) {
+    let pos = self.screen(pos).truncate();
+    if mathtext::has_math(text) {
+      let layout = self.layout_math(text, size);
+      mathtext::draw_math(
+        &layout,
+        pos,
+        0.0,
+        color,
+        self.prim.as_deref_mut(),
+        self.text.as_deref_mut(),
+        self.record.as_deref_mut(),
+      );
+      return;
+    }
+    let emoji = match (self.text.as_deref(), self.metrics) {
+      (Some(t), _) if t.has_color_glyphs(text) => Some(t.layout_emoji(text, size)),
+      (None, Some(m)) if m.has_color_glyphs(text) => Some(m.layout_emoji(text, size)),
+      _ => None,
+    };
+    if let Some(layout) = emoji {
+      emoji::draw_emoji(
+        &layout,
+        pos,
+        0.0,
+        color,
+        self.prim.as_deref_mut(),
+        self.text.as_deref_mut(),
+        self.record.as_deref_mut(),
+      );
+      return;
+    }
+    // While recording, the text renderer is only used for measuring.
+    if let Some(record) = self.record.as_deref_mut() {
+      record.push(RecordedPrimitive::Text {
+        text: text.to_string(),
+        pos,
+        size,
+        color,
+        rotation: 0.0,
+      });
+    } else if let Some(t) = self.text.as_deref_mut() {
+      t.draw_text(text, pos, size, color);
+    }
+  }
+}
+
+/// Union of the artists' extents.
+pub(crate) fn combined_extent(artists: &[Box<dyn Artist>], warnings: &mut Vec<RenderWarning>) -> Option<DataRect> {
+  artists
+    .iter()
+    .enumerate()
+    .filter_map(|(i, a)| a.extent().map(|r| (i, r)))
+    .filter(|(i, r)| {
+      let finite = [r.x_min, r.x_max, r.y_min, r.y_max]
+        .iter()
+        .all(|v| v.is_finite());
+      if !finite {
+        warnings.push(RenderWarning::InvalidExtent { artist: *i });
+      }
+      finite
+    })
+    .map(|(_, r)| r)
+    .reduce(|a, b| a.union(&b))
+}
+
+/// Draws `artist`, number `index`.  If it panics, what it queued is
+/// discarded and a warning is added instead.
+fn draw_guarded(index: usize, artist: &dyn Artist, ctx: &mut DrawContext<'_>, warnings: &mut Vec<RenderWarning>) -> bool {
+  let mark = ctx.mark();
+  let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| artist.draw(ctx)));
+  match result {
+    Ok(()) => true,
+    Err(payload) => {
+      ctx.rollback(mark);
+      warnings.push(RenderWarning::ArtistPanicked { artist: index, message: panic_message(&*payload) });
+      false
+    }
+  }
+}
+
+/// The message a panic was raised with.
+pub(crate) fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+  payload
+    .downcast_ref::<&str>()
+    .map(|s| s.to_string())
+    .or_else(|| payload.downcast_ref::<String>().cloned())
+    .unwrap_or_else(|| "unknown panic".into())
+}
+
+/// Draws the underlay `artists` in ascending z-order.  Call before the
+/// figure is drawn.
+pub(crate) fn draw_underlays(artists: &[Box<dyn Artist>], ctx: &mut DrawContext<'_>, warnings: &mut Vec<RenderWarning>) {
+  let mut order: Vec<(usize, &dyn Artist)> =
+    artists.iter().map(|a| a.as_ref()).enumerate().filter(|(_, a)| a.underlay()).collect();
+  order.sort_by_key(|(_, a)| a.z_order());
+  for (i, a) in order {
+    draw_guarded(i, a, ctx, warnings);
+  }
+}
+
+/// Draws the other `artists` in ascending z-order, then a legend box in
+/// the top-right corner of the axes for those that have entries.
+pub(crate) fn draw_artists(artists: &[Box<dyn Artist>], ctx: &mut DrawContext<'_>, warnings: &mut Vec<RenderWarning>) {
+  let mut order: Vec<(usize, &dyn Artist)> =
+    artists.iter().map(|a| a.as_ref()).enumerate().filter(|(_, a)| !a.underlay()).collect();
+  order.sort_by_key(|(_, a)| a.z_order());
+  // Skipped artists get no legend row either.
+  order.retain(|&(i, a)| draw_guarded(i, a, ctx, warnings));
+
+  let entries: Vec<LegendEntry> = order.iter().filter_map(|(_, a)| a.legend()).collect();
+  if entries.is_empty() {
+    return;
+  }
+  const SIZE: f32 = 12.0;
+  const PAD: f32 = 6.0;
+  const SWATCH: f32 = 10.0;
+  let row = SIZE + PAD * 0.5;
+  let text_w = entries
+    .iter()
+    .map(|e| ctx.measure_text(&e.label, SIZE))
+    .fold(0.0, f32::max);
+  let w = PAD * 3.0 + SWATCH + text_w;
+  let h = PAD * 2.0 + row * entries.len() as f32 - PAD * 0.5;
+  let [_, top, right, _] = ctx.pixel_box();
+  let origin = Vec2::new(right - w - PAD, top + PAD);
+  ctx.rect_pixels(origin, Vec2::new(w, h), Vec4::new(1.0, 1.0, 1.0, 0.9), 0.0);
+  ctx.rect_pixels(origin, Vec2::new(w, h), Vec4::new(0.0, 0.0, 0.0, 1.0), 0.75);
+  for (i, e) in entries.iter().enumerate() {
+    let y = origin.y + PAD + row * i as f32;
+    let swatch_y = y + (SIZE - SWATCH) * 0.5;
+    ctx.rect_pixels(Vec2::new(origin.x + PAD, swatch_y), Vec2::splat(SWATCH), e.color, 0.0);
+    ctx.text_pixels(Vec2::new(origin.x + PAD * 2.0 + SWATCH, y), &e.label, SIZE, Vec4::new(0.0, 0.0, 0.0, 1.0));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  struct Dots(Vec<(f64, f64)>, i32);
+
+  impl Artist for Dots {
+    fn draw(&self, ctx: &mut DrawContext<'_>) {
+      for &p in &self.0 {
+        ctx.marker(p, 3.0, Vec4::ONE);
+      }
+    }
+
+    fn extent(&self) -> Option<DataRect> {
+      let (x, y): (Vec<f64>, Vec<f64>) = self.0.iter().copied().unzip();
+      DataRect::from_points(&x, &y)
+    }
+
+    fn z_order(&self) -> i32 {
+      self.1
+    }
+
+    fn legend(&self) -> Option<LegendEntry> {
+      Some(LegendEntry {
+        label: format!("dots {}", self.1),
+        color: Vec4::ONE,
+      })
+    }
+  }
+
+  fn context(record: &mut Recording) -> DrawContext<'_> {
+    DrawContext::new(
+      None,
+      None,
+      Some(record),
+      Mat4::IDENTITY,
+      SizeScale::default(),
+      Viewport::new(
+        DataRect::new((0.0, 10.0), (0.0, 100.0)),
+        [100.0, 50.0, 300.0, 250.0],
+      ),
+    )
+  }
+
+  #[test]
+  fn data_maps_into_axes_box() {
+    let mut rec = Recording::new(400, 300);
+    let ctx = context(&mut rec);
+    assert_eq!(ctx.to_pixels(0.0, 0.0), Vec2::new(100.0, 250.0));
+    assert_eq!(ctx.to_pixels(10.0, 100.0), Vec2::new(300.0, 50.0));
+    assert_eq!(ctx.to_pixels(5.0, 50.0), Vec2::new(200.0, 150.0));
+    let vp = ctx.viewport();
+    assert_eq!(vp.to_data(Vec2::new(150.0, 200.0)), (2.5, 25.0));
+    assert!(vp.contains_pixel(Vec2::new(100.0, 50.0)));
+    assert!(!vp.contains_pixel(Vec2::new(99.0, 150.0)));
+  }
+
+  #[test]
+  fn extents_combine() {
+    let artists: Vec<Box<dyn Artist>> = vec![
+      Box::new(Dots(vec![(1.0, 2.0), (3.0, -4.0)], 0)),
+      Box::new(Dots(vec![(-5.0, f64::NAN), (0.0, 9.0)], 0)),
+      Box::new(Dots(Vec::new(), 0)),
+    ];
+    let mut warnings = Vec::new();
+    let r = combined_extent(&artists, &mut warnings).unwrap();
+    assert_eq!(r, DataRect::new((0.0, 3.0), (-4.0, 9.0)));
+    assert!(warnings.is_empty());
+  }
+
+  #[test]
+  fn artists_draw_in_z_order_with_legend() {
+    let artists: Vec<Box<dyn Artist>> = vec![
+      Box::new(Dots(vec![(9.0, 90.0)], 2)),
+      Box::new(Dots(vec![(1.0, 10.0)], -1)),
+    ];
+    let mut rec = Recording::new(400, 300);
+    draw_artists(&artists, &mut context(&mut rec), &mut Vec::new());
+    let centers: Vec<Vec3> = rec
+      .primitives
+      .iter()
+      .filter_map(|p| match p {
+        RecordedPrimitive::Marker { center, .. } => Some(*center),
+        _ => None,
+      })
+      .collect();
+    assert_eq!(centers[0], Vec3::new(120.0, 230.0, 0.0));
+    assert_eq!(centers[1], Vec3::new(280.0, 70.0, 0.0));
+    let labels: Vec<&str> = rec
+      .primitives
+      .iter()
+      .filter_map(|p| match p {
+        RecordedPrimitive::Text { text, .. } => Some(text.as_str()),
+        _ => None,
+      })
+      .collect();
+    assert_eq!(labels, vec!["dots -1", "dots 2"]);
+  }
+
+  struct Broken;
+
+  impl Artist for Broken {
+    fn draw(&self, ctx: &mut DrawContext<'_>) {
+      ctx.marker((5.0, 50.0), 3.0, Vec4::ONE);
+      panic!("bad data");
+    }
+
+    fn extent(&self) -> Option<DataRect> {
+      Some(DataRect::new((0.0, f64::NAN), (0.0, 1.0)))
+    }
+
+    fn legend(&self) -> Option<LegendEntry> {
+      Some(LegendEntry { label: "broken".into(), color: Vec4::ONE })
+    }
+  }
+
+  #[test]
+  fn panicking_artists_are_skipped_and_reported() {
+    let artists: Vec<Box<dyn Artist>> = vec![Box::new(Broken), Box::new(Dots(vec![(1.0, 10.0)], 1))];
+    let mut warnings = Vec::new();
+    assert_eq!(combined_extent(&artists, &mut warnings), Some(DataRect::new((1.0, 1.0), (10.0, 10.0))));
+    assert_eq!(warnings, vec![RenderWarning::InvalidExtent { artist: 0 }]);
+
+    let mut rec = Recording::new(400, 300);
+    let mut warnings = Vec::new();
+    draw_artists(&artists, &mut context(&mut rec), &mut warnings);
+    assert_eq!(
+      warnings,
+      vec![RenderWarning::ArtistPanicked { artist: 0, message: "bad data".into() }]
+    );
+    let markers = rec.primitives.iter().filter(|p| matches!(p, RecordedPrimitive::Marker { .. })).count();
+    assert_eq!(markers, 1);
+    assert!(!rec.primitives.iter().any(|p| matches!(p, RecordedPrimitive::Text { text, .. } if text == "broken")));
+  }
+
+  #[test]
+  fn text_follows_a_data_path() {
+    let mut rec = Recording::new(400, 300);
+    let mut ctx = context(&mut rec);
+    ctx.text_on_path(&[(0.0, 0.0), (10.0, 0.0)], "ab", 10.0, Vec4::ONE, 0.0);
+    ctx.text_on_path(&[(0.0, 0.0), (f64::NAN, 1.0), (0.0, 100.0)], "c", 10.0, Vec4::ONE, 0.0);
+    let glyphs: Vec<(&str, Vec2, f32)> = rec
+      .primitives
+      .iter()
+      .filter_map(|p| match p {
+        RecordedPrimitive::Text { text, pos, rotation, .. } => Some((text.as_str(), *pos, *rotation)),
+        _ => None,
+      })
+      .collect();
+    assert_eq!(glyphs[0], ("a", Vec2::new(100.0, 245.0), 0.0));
+    assert_eq!(glyphs[1], ("b", Vec2::new(105.0, 245.0), 0.0));
+    let (text, pos, rotation) = glyphs[2];
+    assert_eq!((text, rotation), ("c", 90.0));
+    assert!(pos.abs_diff_eq(Vec2::new(95.0, 250.0), 1e-4));
+  }
+}
@@ -0,0 +1,347 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Y autoscaling restricted to the visible X window.
+//!
+//! When zoomed into a long time series, the Y limits should fit the
+//! points that are actually on screen.  [`visible_y_range`] computes the
+//! Y range of a line inside an X window, including the interpolated
+//! values where the line crosses the window edges.
+//! [`VisibleYAutoscale`] keeps the series and recomputes the Y limits
+//! whenever the X limits change; install it with
+//! [`PlotBackend::set_visible_y_autoscale`](crate::plotting::PlotBackend::set_visible_y_autoscale).
+//!
+//! [`auto_scale`] and [`sanitize_range`] keep limits usable whatever
+//! the data: NaN and infinite values are skipped, constant data gets a
+//! non-zero range around its value, and data without a single finite
+//! value falls back to [`FALLBACK_RANGE`].
+//!
+//! [`robust_range`] fits percentiles instead of the extremes, so a few
+//! outliers do not flatten the rest of the plot, and [`Outliers`]
+//! records the points such limits leave out; see
+//! [`Axes::auto_scale_robust`](crate::plotting::Axes::auto_scale_robust).
+
+use crate::trace::trace_span;
+use std::sync::Arc;
+
+/// Default padding added above and below the data, as a fraction of
+/// the Y range.
+pub const DEFAULT_MARGIN: f64 = 0.05;
+
+/// Limits used when there is no finite data to scale to.
+pub const FALLBACK_RANGE: (f64, f64) = (0.0, 1.0);
+
+/// Range of the finite `values`, or `None` if there are none.
+pub fn finite_range(values: &[f64]) -> Option<(f64, f64)> {
+  values
+    .iter()
+    .copied()
+    .filter(|v| v.is_finite())
+    .fold(None, |acc, v| match acc {
+      None => Some((v, v)),
+      Some((a, b)) => Some((a.min(v), b.max(v))),
+    })
+}
+
+/// Limits fitting the finite `values`, padded by `margin` (see
+/// [`pad_range`]).  Without finite values, [`FALLBACK_RANGE`].
+pub fn auto_scale(values: &[f64], margin: f64) -> (f64, f64) {
+  let _span = trace_span!("auto_scale", values = values.len());
+  finite_range(values).map_or(FALLBACK_RANGE, |r| pad_range(r, margin))
+}
+
+/// The finite `values`, sorted.
+fn sorted_finite(values: &[f64]) -> Vec<f64> {
+  let mut v: Vec<f64> = values.iter().copied().filter(|v| v.is_finite()).collect();
+  v.sort_by(f64::total_cmp);
+  v
+}
+
+/// Linearly interpolated `pct` percentile (clamped to 0..=100) of the
+/// ascending `sorted` values.
+fn percentile_sorted(sorted: &[f64], pct: f64) -> f64 {
+  let rank = pct.clamp(0.0, 100.0) / 100.0 * (sorted.len() - 1) as f64;
+  let (i, frac) = (rank.floor() as usize, rank.fract());
+  match sorted.get(i + 1) {
+    Some(next) if frac > 0.0 => sorted[i] + (next - sorted[i]) * frac,
+    _ => sorted[i],
+  }
+}
+
+/// The `pct` percentile (0 to 100) of the finite `values`, linearly
+/// interpolated between neighbours, or `None` if there are none.
+pub fn percentile(values: &[f64], pct: f64) -> Option<f64> {
+  let sorted = sorted_finite(values);
+  (!sorted.is_empty()).then(|| percentile_sorted(&sorted, pct))
+}
+
+/// Range from the `lower_pct` to the `upper_pct` percentile of the
+/// finite `values` (e.g. 1 and 99), or `None` if there are none.  The
+/// percentiles may be given in either order.
+pub fn robust_range(values: &[f64], lower_pct: f64, upper_pct: f64) -> Option<(f64, f64)> {
+  let sorted = sorted_finite(values);
+  if sorted.is_empty() {
+    return None;
+  }
+  let (lo, hi) = (lower_pct.min(upper_pct), lower_pct.max(upper_pct));
+  Some((percentile_sorted(&sorted, lo), percentile_sorted(&sorted, hi)))
+}
+
+/// Points outside Y limits, by X position.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Outliers {
+  /// X of the points below the limits, ascending.
+  pub below: Vec<f64>,
+  /// X of the points above the limits, ascending.
+  pub above: Vec<f64>,
+}
+
+impl Outliers {
+  /// The points of `(x, y)` whose finite Y lies outside `ylim`.
+  pub fn find(x: &[f64], y: &[f64], ylim: (f64, f64)) -> Self {
+    let (lo, hi) = (ylim.0.min(ylim.1), ylim.0.max(ylim.1));
+    let mut out = Self::default();
+    for (&xv, &yv) in x.iter().zip(y) {
+      if !xv.is_finite() || !yv.is_finite() {
+        continue;
+      }
+      if yv < lo {
+        out.below.push(xv);
+      } else if yv > hi {
+        out.above.push(xv);
+      }
+    }
+    out.below.sort_by(f64::total_cmp);
+    out.above.sort_by(f64::total_cmp);
+    out
+  }
+
+  /// Whether every point is inside the limits.
+  pub fn is_empty(&self) -> bool {
+    self.below.is_empty() && self.above.is_empty()
+  }
+
+  /// Number of points outside the limits.
+  pub fn len(&self) -> usize {
+    self.below.len() + self.above.len()
+  }
+}
+
+/// `range` made usable as axis limits: ordered, widened by
+/// [`DEFAULT_MARGIN`] around its value if degenerate, and replaced by
+/// [`FALLBACK_RANGE`] if either bound is not finite.
+pub fn sanitize_range(range: (f64, f64)) -> (f64, f64) {
+  let (a, b) = range;
+  if !a.is_finite() || !b.is_finite() {
+    return FALLBACK_RANGE;
+  }
+  let (a, b) = (a.min(b), a.max(b));
+  if a == b {
+    pad_range((a, b), DEFAULT_MARGIN)
+  } else {
+    (a, b)
+  }
+}
+
+/// Y range of the line `(x, y)` within `[x_min, x_max]`.
+///
+/// `x` must be ascending.  Segments crossing a window edge contribute
+/// their interpolated value at the edge.  Returns `None` if no finite
+/// part of the line is visible.
+pub fn visible_y_range(x: &[f64], y: &[f64], x_min: f64, x_max: f64) -> Option<(f64, f64)> {
+  let n = x.len().min(y.len());
+  let (lo, hi) = (x_min.min(x_max), x_min.max(x_max));
+  let finite = |i: usize| x[i].is_finite() && y[i].is_finite();
+  let mut range: Option<(f64, f64)> = None;
+  let mut add = |v: f64| {
+    range = Some(match range {
+      None => (v, v),
+      Some((a, b)) => (a.min(v), b.max(v)),
+    });
+  };
+  // Only the vertices inside the window plus one on either side matter.
+  let start = x[..n].partition_point(|&v| v < lo).saturating_sub(1);
+  let end = (x[..n].partition_point(|&v| v <= hi) + 1).min(n);
+  for i in start..end {
+    if finite(i) && x[i] >= lo && x[i] <= hi {
+      add(y[i]);
+    }
+    if i + 1 < end && finite(i) && finite(i + 1) {
+      let (x0, x1) = (x[i], x[i + 1]);
+      for edge in [lo, hi] {
+        if x0 < edge && edge < x1 {
+          add(y[i] + (y[i + 1] - y[i]) * (edge - x0) / (x1 - x0));
+        }
+      }
+    }
+  }
+  range
+}
+
+/// Pads `(min, max)` by `margin` times its span on each side.  A
+/// degenerate range is padded symmetrically by `margin` times its
+/// magnitude (or by `margin` around zero), even when `margin` is 0.
+/// Non-finite bounds give [`FALLBACK_RANGE`].
+pub fn pad_range(range: (f64, f64), margin: f64) -> (f64, f64) {
+  let (a, b) = range;
+  if !a.is_finite() || !b.is_finite() {
+    return FALLBACK_RANGE;
+  }
+  let margin = if a == b && margin <= 0.0 { DEFAULT_MARGIN } else { margin };
+  let span = b - a;
+  if !span.is_finite() {
+    // Spans beyond f64::MAX cannot be padded.
+    return (a, b);
+  }
+  let pad = if span > 0.0 {
+    span * margin
+  } else if a != 0.0 {
+    a.abs() * margin
+  } else {
+    margin.max(f64::EPSILON)
+  };
+  (a - pad, b + pad)
+}
+
+/// X and Y of a series, shared with the caller.
+type SharedSeries = (Arc<[f64]>, Arc<[f64]>);
+
+/// Recomputes Y limits from the points inside the X limits.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VisibleYAutoscale {
+  series: Vec<SharedSeries>,
+  /// Padding as a fraction of the visible Y range.
+  pub margin: f64,
+  last_x: Option<(f64, f64)>,
+}
+
+impl VisibleYAutoscale {
+  /// Autoscaler without series and the default margin.
+  pub fn new() -> Self {
+    Self {
+      margin: DEFAULT_MARGIN,
+      ..Self::default()
+    }
+  }
+
+  /// Adds a series (with ascending `x`) that takes part in scaling,
+  /// copying its data.
+  pub fn add_series(&mut self, x: &[f64], y: &[f64]) {
+    self.add_series_shared(x.into(), y.into());
+  }
+
+  /// Adds a series sharing `x` and `y` instead of copying them, e.g.
+  /// with a [`DataProbe`](crate::probe::DataProbe) over the same data.
+  pub fn add_series_shared(&mut self, x: Arc<[f64]>, y: Arc<[f64]>) {
+    self.series.push((x, y));
+    self.last_x = None;
+  }
+
+  /// Removes all series.
+  pub fn clear(&mut self) {
+    self.series.clear();
+    self.last_x = None;
+  }
+
+  /// New Y limits for the X window `x`, or `None` if the window is
+  /// unchanged since the last call or shows no data.
+  pub fn update(&mut self, x: (f64, f64)) -> Option<(f64, f64)> {
+    if self.last_x == Some(x) {
+      return None;
+    }
+    self.last_x = Some(x);
+    self
+      .series
+      .iter()
+      .filter_map(|(sx, sy)| visible_y_range(sx, sy, x.0, x.1))
+      .reduce(|a, b| (a.0.min(b.0), a.1.max(b.1)))
+      .map(|r| pad_range(r, self.margin))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn only_visible_points_count() {
+    let x: Vec<f64> = (0..100).map(|i| i as f64).collect();
+    let y: Vec<f64> = x.iter().map(|v| v * v).collect();
+    assert_eq!(visible_y_range(&x, &y, 10.0, 20.0), Some((100.0, 400.0)));
+    assert_eq!(visible_y_range(&x, &y, 200.0, 300.0), None);
+  }
+
+  #[test]
+  fn edges_are_interpolated() {
+    let x = [0.0, 10.0];
+    let y = [0.0, 100.0];
+    // No vertex inside the window; the line still crosses it.
+    assert_eq!(visible_y_range(&x, &y, 2.0, 3.0), Some((20.0, 30.0)));
+    let (x, y) = ([0.0, 1.0, 2.0], [0.0, f64::NAN, 2.0]);
+    assert_eq!(visible_y_range(&x, &y, 0.2, 0.8), None);
+  }
+
+  #[test]
+  fn padding() {
+    assert_eq!(pad_range((0.0, 10.0), 0.1), (-1.0, 11.0));
+    assert_eq!(pad_range((5.0, 5.0), 0.1), (4.5, 5.5));
+    assert!(pad_range((0.0, 0.0), 0.05).0 < 0.0);
+  }
+
+  #[test]
+  fn invalid_and_constant_data() {
+    assert_eq!(finite_range(&[f64::NAN, 2.0, f64::INFINITY, -1.0]), Some((-1.0, 2.0)));
+    assert_eq!(auto_scale(&[f64::NAN, f64::NEG_INFINITY], 0.1), FALLBACK_RANGE);
+    assert_eq!(auto_scale(&[], 0.1), FALLBACK_RANGE);
+    let (lo, hi) = auto_scale(&[3.0, 3.0, f64::NAN], 0.0);
+    assert!(lo < 3.0 && hi > 3.0);
+    assert_eq!(3.0 - lo, hi - 3.0);
+    assert_eq!(sanitize_range((2.0, 1.0)), (1.0, 2.0));
+    assert_eq!(sanitize_range((f64::NAN, 1.0)), FALLBACK_RANGE);
+    assert_eq!(pad_range((f64::MIN, f64::MAX), 0.1), (f64::MIN, f64::MAX));
+  }
+
+  #[test]
+  fn recomputes_only_when_window_moves() {
+    let mut s = VisibleYAutoscale::new();
+    s.margin = 0.0;
+    s.add_series(&[0.0, 1.0, 2.0, 3.0], &[5.0, 1.0, 9.0, 2.0]);
+    s.add_series(&[0.0, 3.0], &[0.0, 0.0]);
+    assert_eq!(s.update((0.5, 1.5)), Some((0.0, 5.0)));
+    assert_eq!(s.update((0.5, 1.5)), None);
+    assert_eq!(s.update((1.5, 3.0)), Some((0.0, 9.0)));
+  }
+
+  #[test]
+  fn percentiles_ignore_outliers() {
+    let mut v: Vec<f64> = (0..=100).map(|i| i as f64).collect();
+    assert_eq!(percentile(&v, 50.0), Some(50.0));
+    assert_eq!(percentile(&[0.0, 10.0], 25.0), Some(2.5));
+    assert_eq!(percentile(&[f64::NAN], 50.0), None);
+    v.extend([1e9, -1e9, f64::NAN]);
+    let (lo, hi) = robust_range(&v, 99.0, 1.0).unwrap();
+    assert!(lo > -1.0 && lo < 2.0 && hi > 98.0 && hi < 101.0);
+    assert_eq!(robust_range(&[4.0], 1.0, 99.0), Some((4.0, 4.0)));
+  }
+
+  #[test]
+  fn outliers_by_side() {
+    let o = Outliers::find(&[3.0, 1.0, 2.0, 4.0], &[-5.0, 9.0, 0.5, f64::NAN], (0.0, 1.0));
+    assert_eq!(o.below, vec![3.0]);
+    assert_eq!(o.above, vec![1.0]);
+    assert_eq!(o.len(), 2);
+    assert!(Outliers::find(&[0.0], &[0.5], (0.0, 1.0)).is_empty());
+  }
+
+  #[test]
+  fn shared_series_are_not_copied() {
+    let x: Arc<[f64]> = Arc::from(vec![0.0, 1.0]);
+    let y: Arc<[f64]> = Arc::from(vec![2.0, 4.0]);
+    let mut s = VisibleYAutoscale::new();
+    s.margin = 0.0;
+    s.add_series_shared(x.clone(), y.clone());
+    assert_eq!(Arc::strong_count(&y), 2);
+    assert!(Arc::ptr_eq(&s.series[0].1, &y));
+    assert_eq!(s.update((0.0, 1.0)), Some((2.0, 4.0)));
+  }
+}
@@ -3,6 +3,7 @@
 
 #include "primitive_renderer.h"
 #include <algorithm>
+#include <cmath>
 #include <cstring>
 #include <fstream>
 #include <sstream>
@@ -288,10 +289,11 @@ void PrimitiveRenderer::DrawImage(const std::vector<float>& data,
     
     wgpu::Texture texture = device_.CreateTexture(&tex_desc);
 
-    // 2. Upload Data (Convert Float 0..1 to Byte 0..255)
+    // 2. Upload Data (Convert Float 0..1 to Byte 1..255, masked
+    // cells, given as negative values, to 0)
     std::vector<uint8_t> bytes(data.size());
     for(size_t i = 0; i < data.size(); ++i) {
-        bytes[i] = static_cast<uint8_t>(std::clamp(data[i] * 255.0f, 0.0f, 255.0f));
+        bytes[i] = data[i] < 0.0f ? 0 : static_cast<uint8_t>(1.0f + std::round(std::min(data[i], 1.0f) * 254.0f));
     }
     
     wgpu::ImageCopyTextureWgpu dest{}; 
@@ -335,10 +337,9 @@ void PrimitiveRenderer::DrawImage(const std::vector<float>& data,
     inst.pos_b_width[2] = 0.0f;
     inst.pos_b_width[3] = 0.0f;
     
-    // UVs are full 0..1 by default in shader if not text?
-    // Use params to indicate "It's a Text/Image"
-    // kText = 100.
-    inst.params[0] = 100.0f; // Treat as Text (Textured Rect)
+    // Textured rect like text (kText = 100), but sampled as image
+    // values (kImage = 101).
+    inst.params[0] = 101.0f;
     
     // Pass UVs (Full Texture)
     inst.pos_c_pad[0] = 0.0f;
@@ -115,7 +115,7 @@ fn vs_main(
     } else if (prim_type == 0u) { // Rect
         center = pos_a;
         size = pos_b.xy;
-    } else if (prim_type == 100u) { // Text Glyph
+    } else if (prim_type == 100u || prim_type == 101u) { // Text Glyph / Image
         center = pos_a;
         size = pos_b.xy;
         // Apply glyph rotation from params.w (radians).
@@ -174,7 +174,7 @@ fn vs_main(
     out.uv = local_pos.xy;
     
     // Text UV Mapping (pos_c_pad = u0, v0, u1, v1)
-    if (prim_type == 100u) {
+    if (prim_type == 100u || prim_type == 101u) {
         let u0 = pos_c_pad.x;
         let v0 = pos_c_pad.y;
         let u1 = pos_c_pad.z;
@@ -247,6 +247,12 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         let alpha = textureSample(font_tex, font_sampler, in.uv).r;
         if (alpha < 0.01) { discard; }
         return vec4<f32>(in.color.rgb, in.color.a * alpha);
+    } else if (prim_type == 101u) { // Image
+        // Values are stored as 1..255; 0 marks a masked cell.
+        let stored = textureSample(font_tex, font_sampler, in.uv).r * 255.0;
+        if (stored < 0.5) { discard; }
+        let value = (stored - 1.0) / 254.0;
+        return vec4<f32>(in.color.rgb * value, in.color.a);
     } else if (prim_type == 0u) { // Rect
         dist = sd_rounded_box(in.uv, in.dim, radius);
     } else if (prim_type == 1u) { // Circle
@@ -35,6 +35,75 @@ inline std::array<float, 4> FixFillColor(const std::array<float, 4>& c) {
   if (alpha > 1.0f) alpha = 1.0f;
   return {c[1], c[2], c[3], alpha};
 }
+
+// Whether all of the first n coordinates are finite; masked (NaN) cells
+// of surfaces and heatmaps are dropped rather than drawn at zero.
+inline bool AllFinite(const std::vector<double>& v, size_t n) {
+  for (size_t i = 0; i < n && i < v.size(); ++i) {
+    if (!std::isfinite(v[i])) return false;
+  }
+  return true;
+}
+
+// Image value of a masked cell.  Renderers draw images from values
+// normalised to 0..1 and leave negative cells out.
+constexpr float kMaskedCell = -1.0f;
+
+// Twice the signed area of triangle (a, b, c); positive if counter-clockwise.
+inline float Cross(float ax, float ay, float bx, float by, float cx, float cy) {
+  return (bx - ax) * (cy - ay) - (by - ay) * (cx - ax);
+}
+
+// Ear-clipping triangulation of a simple polygon.
+// Returns vertex indices, three per triangle. Works for concave polygons
+// such as confidence bands, where a triangle fan would overlap itself.
+std::vector<size_t> TriangulatePolygon(const std::vector<float>& x,
+                                       const std::vector<float>& y) {
+  size_t n = std::min(x.size(), y.size());
+  std::vector<size_t> out;
+  if (n < 3) return out;
+
+  std::vector<size_t> idx(n);
+  for (size_t i = 0; i < n; ++i) idx[i] = i;
+
+  // Orient counter-clockwise so ears have positive area.
+  float area = 0.0f;
+  for (size_t i = 0; i < n; ++i) {
+    size_t j = (i + 1) % n;
+    area += x[i] * y[j] - x[j] * y[i];
+  }
+  if (area < 0.0f) std::reverse(idx.begin(), idx.end());
+
+  size_t guard = 0;
+  while (idx.size() > 3 && guard < n * n) {
+    ++guard;
+    bool clipped = false;
+    size_t m = idx.size();
+    for (size_t i = 0; i < m; ++i) {
+      size_t a = idx[(i + m - 1) % m], b = idx[i], c = idx[(i + 1) % m];
+      if (Cross(x[a], y[a], x[b], y[b], x[c], y[c]) <= 0.0f) continue;
+      bool contains = false;
+      for (size_t k = 0; k < m && !contains; ++k) {
+        size_t p = idx[k];
+        if (p == a || p == b || p == c) continue;
+        contains = Cross(x[a], y[a], x[b], y[b], x[p], y[p]) >= 0.0f &&
+                   Cross(x[b], y[b], x[c], y[c], x[p], y[p]) >= 0.0f &&
+                   Cross(x[c], y[c], x[a], y[a], x[p], y[p]) >= 0.0f;
+      }
+      if (contains) continue;
+      out.insert(out.end(), {a, b, c});
+      idx.erase(idx.begin() + static_cast<std::ptrdiff_t>(i));
+      clipped = true;
+      break;
+    }
+    // Degenerate (collinear/self-intersecting) input: fall back to a fan.
+    if (!clipped) break;
+  }
+  for (size_t i = 1; i + 1 < idx.size(); ++i) {
+    out.insert(out.end(), {idx[0], idx[i], idx[i + 1]});
+  }
+  return out;
+}
 }
 
 WgpuBackend::WgpuBackend(std::shared_ptr<WgpuRenderer> renderer)
@@ -47,11 +116,14 @@ bool WgpuBackend::is_interactive() {
 }
 
 bool WgpuBackend::new_frame() {
+  texts_.clear();
   rects_.clear();
   lines_.clear();
   circles_.clear();
   triangles_.clear();
   pending_segments_.clear();
+  spans_.clear();
+  span_open_ = false;
   return true;
 }
 
@@ -62,6 +134,14 @@ bool WgpuBackend::render_data() {
   float h = static_cast<float>(render_height_);
 
   ReconstructRectangles();
+  end_axes();
+  ApplyRightYAxes();
+  ApplyTickPadding();
+  ApplySpineOffsets();
+  // Spines hidden by the steps above.
+  lines_.erase(std::remove_if(lines_.begin(), lines_.end(),
+                              [](const auto& l) { return l.a <= 0.0f; }),
+               lines_.end());
 
   if (!rects_.empty()) renderer_->DrawRects(rects_, w, h);
   if (!lines_.empty()) renderer_->DrawLines(lines_, w, h);
@@ -83,6 +163,7 @@ bool WgpuBackend::render_data() {
   circles_.clear();
   triangles_.clear();
   pending_segments_.clear();
+  spans_.clear();
 
   return true;
 }
@@ -134,6 +215,7 @@ void WgpuBackend::draw_background(const std::array<float, 4>& color) {
 
 // Helper to center and scale content while preserving aspect ratio
 void WgpuBackend::draw_rectangle(double x1, double x2, double y1, double y2, const std::array<float, 4>& color) {
+  if (!std::isfinite(x1) || !std::isfinite(x2) || !std::isfinite(y1) || !std::isfinite(y2)) return;
 
   float rw = static_cast<float>(render_width_);
   float rh = static_cast<float>(render_height_);
@@ -169,6 +251,7 @@ void WgpuBackend::draw_triangle(const std::vector<double>& x,
                                 const std::vector<double>& y,
                                 const std::vector<double>& z) {
   if (x.size() < 3 || y.size() < 3) return;
+  if (!AllFinite(x, 3) || !AllFinite(y, 3) || !AllFinite(z, 3)) return;
 
   float rw = static_cast<float>(render_width_);
   float rh = static_cast<float>(render_height_);
@@ -265,6 +348,10 @@ void WgpuBackend::draw_path(const std::vector<double>& x,
   float gap_len = 0.0f;
 
   for (size_t i = 0; i < n - 1; ++i) {
+    // NaN samples break the path (wrapped angles, gaps in data).
+    if (!std::isfinite(x[i]) || !std::isfinite(y[i]) ||
+        !std::isfinite(x[i + 1]) || !std::isfinite(y[i + 1])) continue;
+
     // Transform X
     float x1 = static_cast<float>(x[i]) * scale + offset_x;
     float x2 = static_cast<float>(x[i + 1]) * scale + offset_x;
@@ -304,13 +391,14 @@ void WgpuBackend::fill(const std::vector<double>& x, const std::vector<double>&
       ty[i] = (rh - offset_y) - static_cast<float>(y[i]) * scale;
   }
 
-  // Simple Triangle Fan Triangulation (Works for convex polygons like Bars/Rects)
-  // v0 is pivot. Triangles: (v0, v1, v2), (v0, v2, v3), ...
-  for (size_t i = 1; i < n - 1; ++i) {
+  // Ear-clipping handles concave outlines (bands, areas) as well as bars.
+  std::vector<size_t> tris = TriangulatePolygon(tx, ty);
+  for (size_t i = 0; i + 2 < tris.size(); i += 3) {
+      size_t a = tris[i], b = tris[i + 1], d = tris[i + 2];
       triangles_.push_back({
-          tx[0], ty[0], 0.5f, 0.0f,
-          tx[i], ty[i], 0.5f, 0.0f,
-          tx[i+1], ty[i+1], 0.5f, 0.0f,
+          tx[a], ty[a], 0.5f, 0.0f,
+          tx[b], ty[b], 0.5f, 0.0f,
+          tx[d], ty[d], 0.5f, 0.0f,
           0,0,1,0, 0,0,1,0, 0,0,1,0, // Normals (Z-up)
           c[0], c[1], c[2], c[3]
       });
@@ -354,6 +442,8 @@ void WgpuBackend::draw_markers(const std::vector<double>& x,
   }
 
   for (size_t i = 0; i < count; ++i) {
+    // No marker at NaN samples, matching the gaps in the line.
+    if (!std::isfinite(x[i]) || !std::isfinite(y[i])) continue;
     std::array<float, 4> c = marker_color_;
     float mx = static_cast<float>(x[i]) * scale + offset_x;
     float my = (rh - offset_y) - static_cast<float>(y[i]) * scale;
@@ -451,10 +541,14 @@ void WgpuBackend::draw_image(const std::vector<std::vector<double>>& x, const st
   if (!renderer_ || z.empty() || z[0].empty()) return;
   size_t img_height = z.size(), img_width = z[0].size();
   std::vector<float> data; data.reserve(img_width * img_height);
-  double min_val = z[0][0], max_val = z[0][0];
-  for (const auto& row : z) for (double val : row) { min_val = std::min(min_val, val); max_val = std::max(max_val, val); }
+  // Range over finite cells only; masked (NaN) cells upload as
+  // kMaskedCell, below the normalised range, so the image shader can
+  // leave them out without also dropping the minimum.
+  double min_val = INFINITY, max_val = -INFINITY;
+  for (const auto& row : z) for (double val : row) if (std::isfinite(val)) { min_val = std::min(min_val, val); max_val = std::max(max_val, val); }
+  if (min_val > max_val) return;
   double range = (max_val - min_val); if (range < 1e-9) range = 1.0;
-  for (const auto& row : z) for (double val : row) data.push_back(static_cast<float>((val - min_val) / range));
+  for (const auto& row : z) for (double val : row) data.push_back(std::isfinite(val) ? static_cast<float>((val - min_val) / range) : kMaskedCell);
   float sx = 0, sy = 0, sw = (float)width_, sh = (float)height_;
   if (!x.empty() && !x[0].empty() && x.size() >= 2) { sx = (float)x[0][0]; sw = (float)(x[0].back() - x[0][0]); }
   if (!y.empty() && !y[0].empty() && y.size() >= 2) { sy = (float)y[0][0]; sh = (float)(y.back()[0] - y[0][0]); }
@@ -469,6 +563,7 @@ void WgpuBackend::draw_triangle_3d(const std::vector<double>& x,
                                     const std::array<float, 4>& color,
                                     const std::vector<double>& normals) {
   if (x.size() < 3 || y.size() < 3) return;
+  if (!AllFinite(x, 3) || !AllFinite(y, 3) || !AllFinite(z, 3)) return;
   std::array<float, 4> c = FixFillColor(color);
   float z1 = z.size() > 0 ? (float)z[0] : 0.5f;
   float z2 = z.size() > 1 ? (float)z[1] : 0.5f;
@@ -539,6 +634,140 @@ void WgpuBackend::ReconstructRectangles() {
   }
 }
 
+std::array<float, 4> WgpuBackend::AxesBoxPixels(const std::array<float, 4>& position) const {
+  float rw = static_cast<float>(render_width_);
+  float rh = static_cast<float>(render_height_);
+  float lw = static_cast<float>(width_);
+  float lh = static_cast<float>(height_);
+  float scale = std::min(rw / lw, rh / lh);
+  float offset_x = (rw - lw * scale) * 0.5f;
+  float offset_y = (rh - lh * scale) * 0.5f;
+  return {position[0] * lw * scale + offset_x,
+          (rh - offset_y) - (position[1] + position[3]) * lh * scale,
+          (position[0] + position[2]) * lw * scale + offset_x,
+          (rh - offset_y) - position[1] * lh * scale};
+}
+
+void WgpuBackend::begin_axes(const void* axes, AxesPart part, const std::array<float, 4>& position) {
+  if (span_open_) end_axes();
+  open_span_ = {axes, part, position, lines_.size(), lines_.size(), texts_.size(), texts_.size()};
+  span_open_ = true;
+  rects_begin_ = rects_.size();
+  circles_begin_ = circles_.size();
+  triangles_begin_ = triangles_.size();
+}
+
+void WgpuBackend::end_axes() {
+  if (!span_open_) return;
+  span_open_ = false;
+  open_span_.lines_end = lines_.size();
+  open_span_.texts_end = texts_.size();
+  if (open_span_.part != AxesPart::data) {
+    rects_.resize(rects_begin_);
+    circles_.resize(circles_begin_);
+    triangles_.resize(triangles_begin_);
+  }
+  spans_.push_back(open_span_);
+}
+
+bool WgpuBackend::draws_in_parts(const void* axes) const {
+  return spine_offsets_.count(axes) || tick_paddings_.count(axes) || right_y_axes_.count(axes);
+}
+
+bool WgpuBackend::despined(const void* axes) const {
+  return spine_offsets_.count(axes) > 0;
+}
+
+std::vector<const WgpuBackend::AxesSpan*> WgpuBackend::SpansOf(const void* axes, AxesPart part) const {
+  std::vector<const AxesSpan*> out;
+  for (const auto& s : spans_) {
+    if (s.axes == axes && s.part == part) out.push_back(&s);
+  }
+  return out;
+}
+
+void WgpuBackend::set_spine_offset(const void* axes, float offset) {
+  spine_offsets_[axes] = offset;
+}
+
+void WgpuBackend::ApplySpineOffsets() {
+  if (spine_offsets_.empty() || width_ == 0 || height_ == 0) return;
+
+  for (const auto& [axes, offset] : spine_offsets_) {
+    for (auto part : {AxesPart::box, AxesPart::x_axis, AxesPart::y_axis}) {
+      for (const AxesSpan* s : SpansOf(axes, part)) {
+        auto [left, top, right, bottom] = AxesBoxPixels(s->position);
+        float mid_x = (left + right) * 0.5f, mid_y = (top + bottom) * 0.5f;
+        // The box part is the four spines, the axis parts their ticks
+        // and labels; the top and right ones are hidden, the bottom and
+        // left ones moved out.
+        for (size_t i = s->lines_begin; i < s->lines_end; ++i) {
+          auto& l = lines_[i];
+          bool horiz = std::abs(l.y2 - l.y1) < std::abs(l.x2 - l.x1);
+          bool bottom_side = part == AxesPart::x_axis || (part == AxesPart::box && horiz);
+          if (bottom_side) {
+            if ((l.y1 + l.y2) * 0.5f < mid_y) { l.a = 0.0f; continue; }
+            l.y1 += offset; l.y2 += offset;
+          } else {
+            if ((l.x1 + l.x2) * 0.5f > mid_x) { l.a = 0.0f; continue; }
+            l.x1 -= offset; l.x2 -= offset;
+          }
+        }
+        for (size_t i = s->texts_begin; i < s->texts_end; ++i) {
+          if (part == AxesPart::x_axis) texts_[i].y += offset;
+          if (part == AxesPart::y_axis) texts_[i].x -= offset;
+        }
+      }
+    }
+  }
+}
+
+void WgpuBackend::set_tick_padding(const void* axes, float padding) {
+  tick_paddings_[axes] = padding;
+}
+
+void WgpuBackend::ApplyTickPadding() {
+  if (tick_paddings_.empty()) return;
+
+  for (const auto& [axes, padding] : tick_paddings_) {
+    for (const AxesSpan* s : SpansOf(axes, AxesPart::x_axis)) {
+      for (size_t i = s->texts_begin; i < s->texts_end; ++i) texts_[i].y += padding;
+    }
+    for (const AxesSpan* s : SpansOf(axes, AxesPart::y_axis)) {
+      for (size_t i = s->texts_begin; i < s->texts_end; ++i) texts_[i].x -= padding;
+    }
+  }
+}
+
+void WgpuBackend::add_right_y_axis(const void* axes, float offset) {
+  right_y_axes_[axes] = offset;
+}
+
+void WgpuBackend::ApplyRightYAxes() {
+  if (right_y_axes_.empty() || width_ == 0 || height_ == 0) return;
+
+  for (const auto& [axes, offset] : right_y_axes_) {
+    for (const AxesSpan* s : SpansOf(axes, AxesPart::y_axis)) {
+      auto [left, top, right, bottom] = AxesBoxPixels(s->position);
+      float spine_x = right + offset;
+      // Mirror across the box so the left axis lands right of `spine_x`.
+      auto mirror = [&](float x) { return spine_x + (left - x); };
+      for (size_t i = s->lines_begin; i < s->lines_end; ++i) {
+        lines_[i].x1 = mirror(lines_[i].x1);
+        lines_[i].x2 = mirror(lines_[i].x2);
+      }
+      // Tick labels and the axis label sit left of the box; mirror
+      // their extent so they read left-to-right on the right side.
+      for (size_t i = s->texts_begin; i < s->texts_end; ++i) {
+        auto& t = texts_[i];
+        float w = t.rotation != 0.0f ? t.font_size
+                                     : (renderer_ ? renderer_->MeasureText(t.text, t.font_size) : 0.0f);
+        t.x = mirror(t.x + w);
+      }
+    }
+  }
+}
+
 void WgpuBackend::SetViewProjection(const float* matrix) {
   if (matrix) {
       std::memcpy(view_proj_, matrix, 16 * sizeof(float));
@@ -0,0 +1,271 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Representative benchmark scenes with per-phase timings.
+//!
+//! Each [`Scene`] configures a heavy figure (a 1M-point line, a
+//! 500x500 surface, a 10k-marker scatter).  [`run_scene`] renders it
+//! headlessly and reports where the time went, so users can measure
+//! their hardware.  [`to_baseline`] saves a run as text and [`compare`]
+//! checks a later run against it, so releases can be compared for
+//! regressions.
+
+use crate::capture::PlotCapture;
+use crate::error::Result;
+use crate::plotting::{self, linspace};
+use std::time::{Duration, Instant};
+
+/// Capture size used by [`run_all`].
+pub const DEFAULT_WIDTH: u32 = 800;
+/// Capture size used by [`run_all`].
+pub const DEFAULT_HEIGHT: u32 = 600;
+
+/// A named benchmark scene.
+pub struct Scene {
+  /// Scene name, stable across releases for comparisons.
+  pub name: &'static str,
+  /// Configures the plot on a [`plotting::Figure`].
+  pub setup: fn(&plotting::Figure<'_>),
+}
+
+/// Time spent in each phase of one scene, averaged over iterations.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Timing {
+  /// Figure configuration (data upload into matplot++), once.
+  pub setup: Duration,
+  /// matplot++ pipeline and tessellation into GPU instances.
+  pub draw: Duration,
+  /// Instance and text upload to the GPU.
+  pub prepare: Duration,
+  /// Render pass, copy, and blocking readback.
+  pub gpu: Duration,
+  /// Primitive instances produced per frame.
+  pub instances: usize,
+  /// Number of timed iterations.
+  pub iterations: u32,
+}
+
+impl Timing {
+  /// Average frame time (draw + prepare + gpu).
+  pub fn frame(&self) -> Duration {
+    self.draw + self.prepare + self.gpu
+  }
+
+  /// Per-frame phases by name, the frame total last.
+  fn phases(&self) -> [(&'static str, Duration); 4] {
+    [
+      ("draw", self.draw),
+      ("prepare", self.prepare),
+      ("gpu", self.gpu),
+      ("frame", self.frame()),
+    ]
+  }
+}
+
+/// A phase of a scene slower than in the baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+  /// Scene name.
+  pub scene: String,
+  /// `"draw"`, `"prepare"`, `"gpu"` or `"frame"`.
+  pub phase: &'static str,
+  /// Time in the baseline.
+  pub baseline: Duration,
+  /// Time in the compared run.
+  pub current: Duration,
+}
+
+/// Named timings as text for [`parse_baseline`], one scene per line:
+/// name, draw, prepare and gpu nanoseconds, instances, iterations.
+pub fn to_baseline(timings: &[(&str, Timing)]) -> String {
+  timings
+    .iter()
+    .map(|(name, t)| {
+      format!(
+        "{} {} {} {} {} {}\n",
+        name,
+        t.draw.as_nanos(),
+        t.prepare.as_nanos(),
+        t.gpu.as_nanos(),
+        t.instances,
+        t.iterations
+      )
+    })
+    .collect()
+}
+
+/// Reads timings written by [`to_baseline`], skipping malformed lines.
+pub fn parse_baseline(text: &str) -> Vec<(String, Timing)> {
+  text
+    .lines()
+    .filter_map(|line| {
+      let mut fields = line.split_whitespace();
+      let name = fields.next()?.to_string();
+      let mut nanos = || fields.next()?.parse().ok().map(Duration::from_nanos);
+      let (draw, prepare, gpu) = (nanos()?, nanos()?, nanos()?);
+      let instances = fields.next()?.parse().ok()?;
+      let iterations = fields.next()?.parse().ok()?;
+      let timing = Timing { draw, prepare, gpu, instances, iterations, ..Default::default() };
+      Some((name, timing))
+    })
+    .collect()
+}
+
+/// Phases of `timings` more than `tolerance` (a fraction: `0.1` allows
+/// 10%) slower than the same scene in `baseline`.  Scenes missing from
+/// the baseline are not compared.
+pub fn compare(
+  timings: &[(&str, Timing)],
+  baseline: &[(String, Timing)],
+  tolerance: f64,
+) -> Vec<Regression> {
+  let mut regressions = Vec::new();
+  for (name, timing) in timings {
+    let Some((_, base)) = baseline.iter().find(|(b, _)| b == name) else {
+      continue;
+    };
+    for ((phase, current), (_, before)) in timing.phases().into_iter().zip(base.phases()) {
+      if current.as_secs_f64() > before.as_secs_f64() * (1.0 + tolerance) {
+        regressions.push(Regression {
+          scene: name.to_string(),
+          phase,
+          baseline: before,
+          current,
+        });
+      }
+    }
+  }
+  regressions
+}
+
+/// Returns the standard benchmark scenes.
+pub fn scenes() -> Vec<Scene> {
+  vec![
+    Scene { name: "line_1m", setup: setup_line_1m },
+    Scene { name: "surface_500", setup: setup_surface_500 },
+    Scene { name: "scatter_10k", setup: setup_scatter_10k },
+  ]
+}
+
+/// Renders `scene` `iterations` times (after one warm-up frame) and
+/// returns averaged phase timings.  Fails without a GPU adapter.
+pub fn run_scene(
+  scene: &Scene,
+  width: u32,
+  height: u32,
+  iterations: u32,
+) -> Result<Timing> {
+  let iterations = iterations.max(1);
+  let mut cap = PlotCapture::new(width, height)?;
+
+  let start = Instant::now();
+  (scene.setup)(&cap.figure());
+  let setup = start.elapsed();
+
+  // Warm-up: first frame pays pipeline and buffer allocation.
+  cap.render_and_capture()?;
+
+  let mut timing = Timing {
+    setup,
+    iterations,
+    ..Default::default()
+  };
+  for _ in 0..iterations {
+    let t0 = Instant::now();
+    cap.draw();
+    let t1 = Instant::now();
+    timing.instances = cap.headless_mut().prim().instance_count();
+    cap.headless_mut().prepare();
+    let t2 = Instant::now();
+    cap.headless_mut().render_and_read_back()?;
+    let t3 = Instant::now();
+    timing.draw += t1 - t0;
+    timing.prepare += t2 - t1;
+    timing.gpu += t3 - t2;
+  }
+  timing.draw /= iterations;
+  timing.prepare /= iterations;
+  timing.gpu /= iterations;
+  Ok(timing)
+}
+
+/// Runs every scene at the default size and returns named timings.
+pub fn run_all(iterations: u32) -> Result<Vec<(&'static str, Timing)>> {
+  scenes()
+    .iter()
+    .map(|s| {
+      Ok((s.name, run_scene(s, DEFAULT_WIDTH, DEFAULT_HEIGHT, iterations)?))
+    })
+    .collect()
+}
+
+/// One million point line.
+fn setup_line_1m(fig: &plotting::Figure<'_>) {
+  let ax = fig.current_axes();
+  let x = linspace(0.0, 1000.0, 1_000_000);
+  let y: Vec<f64> =
+    x.iter().map(|v| v.sin() + (v * 0.013).cos()).collect();
+  ax.plot(&x, &y, "-");
+}
+
+/// 500x500 surface.
+fn setup_surface_500(fig: &plotting::Figure<'_>) {
+  let ax = fig.current_axes();
+  let n = 500usize;
+  let vals = linspace(-3.0, 3.0, n);
+  let mut x = Vec::with_capacity(n * n);
+  let mut y = Vec::with_capacity(n * n);
+  let mut z = Vec::with_capacity(n * n);
+  for &vy in &vals {
+    for &vx in &vals {
+      x.push(vx);
+      y.push(vy);
+      z.push((vx * vy).sin());
+    }
+  }
+  ax.surf(&x, &y, &z, n, n, false);
+}
+
+/// Ten thousand markers.
+fn setup_scatter_10k(fig: &plotting::Figure<'_>) {
+  let ax = fig.current_axes();
+  let x = plotting::randn(10_000);
+  let y: Vec<f64> = x.iter().enumerate()
+    .map(|(i, v)| (v * 3.0 + i as f64 * 1e-3).sin())
+    .collect();
+  ax.scatter(&x, &y, "o");
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn timing(draw_ms: u64) -> Timing {
+    Timing {
+      draw: Duration::from_millis(draw_ms),
+      prepare: Duration::from_millis(2),
+      gpu: Duration::from_millis(3),
+      instances: 10,
+      iterations: 5,
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn baseline_round_trips() {
+    let text = to_baseline(&[("line_1m", timing(4))]);
+    assert_eq!(parse_baseline(&format!("{text}garbage\n")), vec![("line_1m".to_string(), timing(4))]);
+  }
+
+  #[test]
+  fn slower_phases_beyond_the_tolerance_regress() {
+    let baseline = vec![("line_1m".to_string(), timing(10))];
+    assert!(compare(&[("line_1m", timing(11))], &baseline, 0.2).is_empty());
+    let phases: Vec<_> = compare(&[("line_1m", timing(20))], &baseline, 0.2)
+      .into_iter()
+      .map(|r| r.phase)
+      .collect();
+    assert_eq!(phases, ["draw", "frame"]);
+    assert!(compare(&[("other", timing(20))], &baseline, 0.2).is_empty());
+  }
+}
@@ -0,0 +1,219 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Layout without a GPU.
+//!
+//! A [`FigureLayout`] reports where a figure's pieces land on screen:
+//! the axes box, the bounding box of every text label and the box each
+//! artist covers.  It comes from the recording path with text measured
+//! by [`TextMetrics`], so no wgpu device is created.  Apps can hit-test
+//! labels or position HTML overlays from it, and tests can assert
+//! layout on machines without a GPU adapter.  Get one from
+//! [`PlotBackend::layout`](crate::plotting::PlotBackend::layout).
+//!
+//! Boxes are `[left, top, right, bottom]` in pixels, Y growing down.
+
+use crate::artist::{Artist, DrawContext, Viewport};
+use crate::recording::{RecordedPrimitive, Recording};
+use crate::text::{estimate_text_width, TextMetrics};
+use crate::units::SizeScale;
+use glam::{Mat4, Vec2};
+
+/// A text label and the screen box it covers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextBox {
+  /// Label contents.
+  pub text: String,
+  /// Top-left anchor in pixels.
+  pub pos: Vec2,
+  /// Font size in pixels.
+  pub size: f32,
+  /// Counter-clockwise rotation in degrees about the anchor.
+  pub rotation: f32,
+  /// Axis-aligned bounds of the (rotated) label.
+  pub bounds: [f32; 4],
+}
+
+/// Screen box covered by one artist.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArtistBox {
+  /// Index of the artist in the order it was added.
+  pub index: usize,
+  /// Bounds of everything it drew.
+  pub bounds: [f32; 4],
+}
+
+/// Screen positions of one laid out figure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FigureLayout {
+  /// Figure width in pixels.
+  pub width: u32,
+  /// Figure height in pixels.
+  pub height: u32,
+  /// Box of the current axes.
+  pub axes: [f32; 4],
+  /// Every text label, in draw order.
+  pub texts: Vec<TextBox>,
+  /// Artists that drew anything, in the order they were added.
+  pub artists: Vec<ArtistBox>,
+  /// The recorded primitives the boxes were computed from.
+  pub recording: Recording,
+}
+
+impl FigureLayout {
+  /// Topmost label under the pixel `p`.
+  pub fn text_at(&self, p: Vec2) -> Option<&TextBox> {
+    self.texts.iter().rev().find(|t| contains(t.bounds, p))
+  }
+
+  /// Index of the last added artist whose box contains `p`.
+  pub fn artist_at(&self, p: Vec2) -> Option<usize> {
+    self.artists.iter().rev().find(|a| contains(a.bounds, p)).map(|a| a.index)
+  }
+}
+
+/// Whether `bounds` contains `p`, edges included.
+pub fn contains(bounds: [f32; 4], p: Vec2) -> bool {
+  let [left, top, right, bottom] = bounds;
+  p.x >= left && p.x <= right && p.y >= top && p.y <= bottom
+}
+
+/// Smallest box holding both.
+pub fn union(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+  [a[0].min(b[0]), a[1].min(b[1]), a[2].max(b[2]), a[3].max(b[3])]
+}
+
+/// Box around `points`, grown by `pad` on every side.
+fn around(points: impl IntoIterator<Item = Vec2>, pad: f32) -> Option<[f32; 4]> {
+  let b = points
+    .into_iter()
+    .map(|p| [p.x - pad, p.y - pad, p.x + pad, p.y + pad])
+    .reduce(union)?;
+  b.iter().all(|v| v.is_finite()).then_some(b)
+}
+
+/// Bounds of a label of `extent` anchored top-left at `pos` and rotated
+/// `rotation` degrees counter-clockwise about it.
+pub fn text_bounds(pos: Vec2, extent: Vec2, rotation: f32) -> [f32; 4] {
+  let (s, c) = rotation.to_radians().sin_cos();
+  let corners = [Vec2::ZERO, Vec2::new(extent.x, 0.0), extent, Vec2::new(0.0, extent.y)];
+  // Counter-clockwise on a Y-down screen.
+  around(corners.map(|d| pos + Vec2::new(d.x * c + d.y * s, d.y * c - d.x * s)), 0.0)
+    .unwrap_or([pos.x, pos.y, pos.x, pos.y])
+}
+
+/// Extent of `text` at `size`, from `metrics` or a width estimate.
+fn measure(metrics: Option<&TextMetrics>, text: &str, size: f32) -> Vec2 {
+  match metrics {
+    Some(m) => m.measure_text(text, size),
+    None => Vec2::new(estimate_text_width(text, size), size),
+  }
+}
+
+/// Screen bounds of one primitive, `None` if it has non-finite
+/// coordinates.
+pub fn primitive_bounds(p: &RecordedPrimitive, metrics: Option<&TextMetrics>) -> Option<[f32; 4]> {
+  match p {
+    RecordedPrimitive::Rect { pos, size, stroke_width, .. } => around([*pos, *pos + *size], stroke_width * 0.5),
+    RecordedPrimitive::Line { start, end, width, .. } => around([start.truncate(), end.truncate()], width * 0.5),
+    RecordedPrimitive::Marker { center, radius, .. } => around([center.truncate()], *radius),
+    RecordedPrimitive::Triangle { points, .. } => around(points.map(|p| p.truncate()), 0.0),
+    RecordedPrimitive::Text { text, pos, size, rotation, .. } => {
+      let b = text_bounds(*pos, measure(metrics, text, *size), *rotation);
+      b.iter().all(|v| v.is_finite()).then_some(b)
+    }
+  }
+}
+
+/// Bounds of everything in `recording`.
+pub fn recording_bounds(recording: &Recording, metrics: Option<&TextMetrics>) -> Option<[f32; 4]> {
+  recording.primitives.iter().filter_map(|p| primitive_bounds(p, metrics)).reduce(union)
+}
+
+/// Text boxes of the labels in `recording`.
+pub fn text_boxes(recording: &Recording, metrics: Option<&TextMetrics>) -> Vec<TextBox> {
+  recording
+    .primitives
+    .iter()
+    .filter_map(|p| match p {
+      RecordedPrimitive::Text { text, pos, size, rotation, .. } => Some(TextBox {
+        text: text.clone(),
+        pos: *pos,
+        size: *size,
+        rotation: *rotation,
+        bounds: text_bounds(*pos, measure(metrics, text, *size), *rotation),
+      }),
+      _ => None,
+    })
+    .collect()
+}
+
+/// Boxes of `artists` drawn one at a time into `viewport`.
+pub(crate) fn artist_boxes(
+  artists: &[Box<dyn Artist>],
+  transform: Mat4,
+  sizes: SizeScale,
+  viewport: Viewport,
+  metrics: Option<&TextMetrics>,
+) -> Vec<ArtistBox> {
+  artists
+    .iter()
+    .enumerate()
+    .filter_map(|(index, a)| {
+      let mut recording = Recording::new(0, 0);
+      let mut ctx = DrawContext::new(None, None, Some(&mut recording), transform, sizes, viewport).with_metrics(metrics);
+      a.draw(&mut ctx);
+      let bounds = recording_bounds(&recording, metrics)?;
+      Some(ArtistBox { index, bounds })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use glam::{Vec3, Vec4};
+
+  #[test]
+  fn rotated_text_bounds() {
+    let extent = Vec2::new(40.0, 10.0);
+    assert_eq!(text_bounds(Vec2::new(5.0, 5.0), extent, 0.0), [5.0, 5.0, 45.0, 15.0]);
+    // Reading bottom to top, the label extends up from its anchor.
+    let b = text_bounds(Vec2::new(100.0, 100.0), extent, 90.0);
+    let expected = [100.0, 60.0, 110.0, 100.0];
+    assert!(b.iter().zip(expected).all(|(a, e)| (a - e).abs() < 1e-4), "{:?}", b);
+  }
+
+  #[test]
+  fn primitives_and_hit_tests() {
+    let mut rec = Recording::new(100, 100);
+    rec.push(RecordedPrimitive::Line {
+      start: Vec3::new(10.0, 10.0, 0.0),
+      end: Vec3::new(30.0, 20.0, 0.0),
+      width: 2.0,
+      color: Vec4::ONE,
+      dash_len: 0.0,
+      gap_len: 0.0,
+      dash_offset: 0.0,
+    });
+    rec.push(RecordedPrimitive::Text {
+      text: "ab".into(),
+      pos: Vec2::new(50.0, 50.0),
+      size: 10.0,
+      color: Vec4::ONE,
+      rotation: 0.0,
+    });
+    assert_eq!(recording_bounds(&rec, None), Some([9.0, 9.0, 62.0, 60.0]));
+    let layout = FigureLayout {
+      width: 100,
+      height: 100,
+      axes: [0.0, 0.0, 100.0, 100.0],
+      texts: text_boxes(&rec, None),
+      artists: vec![ArtistBox { index: 3, bounds: [0.0, 0.0, 20.0, 20.0] }],
+      recording: rec,
+    };
+    assert_eq!(layout.text_at(Vec2::new(55.0, 55.0)).map(|t| t.text.as_str()), Some("ab"));
+    assert!(layout.text_at(Vec2::new(5.0, 5.0)).is_none());
+    assert_eq!(layout.artist_at(Vec2::new(5.0, 5.0)), Some(3));
+  }
+}
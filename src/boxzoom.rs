@@ -0,0 +1,102 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Rubber-band zoom.
+//!
+//! As with matplotlib's zoom tool, the user drags a rectangle over the
+//! axes and the view zooms to the data under it.  Start the drag with
+//! [`PlotBackend::begin_box_zoom`](crate::plotting::PlotBackend::begin_box_zoom)
+//! at the press.  Follow the pointer with
+//! [`update_box_zoom`](crate::plotting::PlotBackend::update_box_zoom),
+//! which draws the selection over the figure, and finish with
+//! [`commit_box_zoom`](crate::plotting::PlotBackend::commit_box_zoom)
+//! at the release.  [`zoom_home`](crate::plotting::PlotBackend::zoom_home)
+//! returns to autoscaled limits.
+
+use crate::artist::{Artist, DataRect, DrawContext, Viewport};
+use glam::{Vec2, Vec4};
+
+/// Selections narrower or shorter than this many pixels are clicks,
+/// not zooms.
+pub const MIN_DRAG: f32 = 3.0;
+
+/// A rectangle being dragged out, in pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoxZoom {
+  /// Corner where the drag started.
+  pub start: Vec2,
+  /// Corner under the pointer.
+  pub end: Vec2,
+}
+
+impl BoxZoom {
+  /// Empty selection at `at`.
+  pub fn new(at: Vec2) -> Self {
+    Self { start: at, end: at }
+  }
+
+  /// The selection clamped to `pixel_box`, as
+  /// `[left, top, right, bottom]`.
+  pub fn rect(&self, pixel_box: [f32; 4]) -> [f32; 4] {
+    let [left, top, right, bottom] = pixel_box;
+    let (lo, hi) = (self.start.min(self.end), self.start.max(self.end));
+    [
+      lo.x.clamp(left, right),
+      lo.y.clamp(top, bottom),
+      hi.x.clamp(left, right),
+      hi.y.clamp(top, bottom),
+    ]
+  }
+
+  /// Limits showing the data under the selection, keeping the axis
+  /// directions of `viewport`.  `None` for selections under
+  /// [`MIN_DRAG`] pixels either way.
+  pub fn data_rect(&self, viewport: &Viewport) -> Option<DataRect> {
+    let [left, top, right, bottom] = self.rect(viewport.pixels);
+    if right - left < MIN_DRAG || bottom - top < MIN_DRAG {
+      return None;
+    }
+    let (x0, y0) = viewport.to_data(Vec2::new(left, bottom));
+    let (x1, y1) = viewport.to_data(Vec2::new(right, top));
+    Some(DataRect::new((x0, x1), (y0, y1)))
+  }
+}
+
+impl Artist for BoxZoom {
+  fn draw(&self, ctx: &mut DrawContext<'_>) {
+    let [left, top, right, bottom] = self.rect(ctx.pixel_box());
+    let (pos, size) = (Vec2::new(left, top), Vec2::new(right - left, bottom - top));
+    ctx.rect_pixels(pos, size, Vec4::new(0.2, 0.4, 0.8, 0.15), 0.0);
+    ctx.rect_pixels(pos, size, Vec4::new(0.0, 0.0, 0.0, 0.8), 0.75);
+  }
+
+  fn extent(&self) -> Option<DataRect> {
+    None
+  }
+
+  fn z_order(&self) -> i32 {
+    i32::MAX
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn selections_map_to_data_limits() {
+    // 100 x 100 pixels at (10, 10) showing 0..10 on both axes.
+    let viewport = Viewport::new(DataRect::new((0.0, 10.0), (0.0, 10.0)), [10.0, 10.0, 110.0, 110.0]);
+    let mut zoom = BoxZoom::new(Vec2::new(90.0, 30.0));
+    zoom.end = Vec2::new(30.0, 200.0);
+    assert_eq!(zoom.rect(viewport.pixels), [30.0, 30.0, 90.0, 110.0]);
+    let r = zoom.data_rect(&viewport).unwrap();
+    let got = [r.x_min, r.x_max, r.y_min, r.y_max];
+    for (got, want) in got.iter().zip([2.0, 8.0, 0.0, 8.0]) {
+      assert!((got - want).abs() < 1e-5, "{:?}", got);
+    }
+
+    zoom.end = Vec2::new(91.0, 80.0);
+    assert_eq!(zoom.data_rect(&viewport), None);
+  }
+}
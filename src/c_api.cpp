@@ -5,6 +5,13 @@
 #include "matplot/backend/wgpu_backend.h"
 #include <matplot/matplot.h>
 #include <matplot/backend/backend_registry.h>
+#include <algorithm>
+#include <atomic>
+#include <cmath>
+#include <exception>
+#include <mutex>
+#include <unordered_map>
+#include <unordered_set>
 #include <vector>
 #include <string>
 
@@ -154,6 +161,22 @@ bool mpl_figure_save(MplFigure* fig, const char* path) {
     return fig->figure->save(path);
 }
 
+bool mpl_figure_save_as(MplFigure* fig, const char* path, const char* terminal) {
+    if (!fig || !fig->figure || !path || !terminal) return false;
+    return fig->figure->save(path, terminal);
+}
+
+void mpl_figure_set_size(MplFigure* fig, unsigned int width, unsigned int height) {
+    if (!fig || !fig->figure) return;
+    fig->figure->size(width, height);
+}
+
+void mpl_figure_set_font(MplFigure* fig, const char* name, float size) {
+    if (!fig || !fig->figure) return;
+    if (name && *name) fig->figure->font(name);
+    if (size > 0.0f) fig->figure->font_size(size);
+}
+
 void mpl_figure_destroy(MplFigure* fig) {
     if (fig) {
         delete fig;
@@ -190,25 +213,358 @@ void mpl_axes_destroy(MplAxes* ax) {
     if (ax) delete ax;
 }
 
-void mpl_axes_plot(MplAxes* ax, const double* x, const double* y, size_t count, const char* style) {
-    if (!ax || !ax->axes) return;
+MplAxes* mpl_axes_clone(MplAxes* ax) {
+    if (!ax) return nullptr;
+    auto wrapper = new MplAxes();
+    wrapper->axes = ax->axes;
+    return wrapper;
+}
+
+struct MplLine {
+    std::shared_ptr<matplot::line> line;
+    uint64_t id;
+};
+
+// Series ids handed out by plot/scatter.  Entries expire with the line,
+// so a removed series is never found again; ids are not reused.
+namespace {
+std::mutex series_mutex;
+uint64_t next_series_id = 1;
+std::unordered_map<uint64_t, std::weak_ptr<matplot::line>> series_registry;
+// Group name per series id; entries go with the registry entry.
+std::unordered_map<uint64_t, std::string> series_groups;
+// Bumped by every change to which points the series show.
+std::atomic<uint64_t> series_revision{0};
+
+MplLine* register_series(std::shared_ptr<matplot::line> line) {
+    std::lock_guard<std::mutex> lock(series_mutex);
+    for (auto it = series_registry.begin(); it != series_registry.end();) {
+        if (it->second.expired()) {
+            series_groups.erase(it->first);
+            it = series_registry.erase(it);
+        } else {
+            ++it;
+        }
+    }
+    uint64_t id = next_series_id++;
+    series_registry[id] = line;
+    ++series_revision;
+    return new MplLine{std::move(line), id};
+}
+
+std::shared_ptr<matplot::line> find_series(uint64_t id) {
+    std::lock_guard<std::mutex> lock(series_mutex);
+    auto it = series_registry.find(id);
+    return it == series_registry.end() ? nullptr : it->second.lock();
+}
+
+// Host axes of the extra y axes from mpl_axes_add_y_axis.
+std::mutex overlay_mutex;
+std::unordered_map<const matplot::axes_type*, std::weak_ptr<matplot::axes_type>> overlay_hosts;
+
+bool has_child(MplAxes* ax, const std::shared_ptr<matplot::line>& line) {
+    auto children = ax->axes->children();
+    return std::any_of(children.begin(), children.end(),
+                       [&](const auto& c) { return c.get() == line.get(); });
+}
+
+void append_points(matplot::line& line, const double* x, const double* y, size_t count, size_t max_len) {
+    // Build the new window once, skipping the points a rolling window
+    // drops, and move it into the line.
+    auto window = [&](const std::vector<double>& old, const double* added) {
+        size_t total = old.size() + count;
+        size_t keep = max_len > 0 ? std::min(total, max_len) : total;
+        size_t skip = total - keep;
+        std::vector<double> v;
+        v.reserve(keep);
+        if (skip < old.size()) v.insert(v.end(), old.begin() + skip, old.end());
+        size_t skip_added = skip > old.size() ? skip - old.size() : 0;
+        v.insert(v.end(), added + skip_added, added + count);
+        return v;
+    };
+    auto vx = window(line.x_data(), x);
+    auto vy = window(line.y_data(), y);
+    line.x_data(std::move(vx));
+    line.y_data(std::move(vy));
+    ++series_revision;
+}
+} // namespace
+
+MplLine* mpl_axes_plot(MplAxes* ax, const double* x, const double* y, size_t count, const char* style) {
+    if (!ax || !ax->axes) return nullptr;
     std::vector<double> vx(x, x + count);
     std::vector<double> vy(y, y + count);
-    ax->axes->plot(vx, vy, style ? std::string(style) : "");
+    return register_series(ax->axes->plot(vx, vy, style ? std::string(style) : ""));
 }
 
-void mpl_axes_scatter(MplAxes* ax, const double* x, const double* y, size_t count, const char* style) {
-    if (!ax || !ax->axes) return;
+MplLine* mpl_axes_scatter(MplAxes* ax, const double* x, const double* y, size_t count, const char* style) {
+    if (!ax || !ax->axes) return nullptr;
     std::vector<double> vx(x, x + count);
     std::vector<double> vy(y, y + count);
     // Use plot with marker style which is more robust in this backend currently
     // or scatter if supported.
-    if (style) {
-        ax->axes->plot(vx, vy, style);
-    } else {
-        // default scatter
-        ax->axes->plot(vx, vy, "o");
+    return register_series(ax->axes->plot(vx, vy, style ? style : "o"));
+}
+
+MplLine* mpl_axes_plot3(MplAxes* ax, const double* x, const double* y, const double* z, size_t count, const char* style) {
+    if (!ax || !ax->axes) return nullptr;
+    std::vector<double> vx(x, x + count);
+    std::vector<double> vy(y, y + count);
+    std::vector<double> vz(z, z + count);
+    return register_series(ax->axes->plot3(vx, vy, vz, style ? std::string(style) : ""));
+}
+
+MplLine* mpl_axes_scatter3(MplAxes* ax, const double* x, const double* y, const double* z, size_t count, const char* style) {
+    if (!ax || !ax->axes) return nullptr;
+    std::vector<double> vx(x, x + count);
+    std::vector<double> vy(y, y + count);
+    std::vector<double> vz(z, z + count);
+    // Markers only, as mpl_axes_scatter does in 2D.
+    return register_series(ax->axes->plot3(vx, vy, vz, style ? style : "o"));
+}
+
+void mpl_line_destroy(MplLine* line) {
+    if (line) delete line;
+}
+
+void mpl_line_set_color(MplLine* line, float r, float g, float b) {
+    if (!line || !line->line) return;
+    line->line->color({0.0f, r, g, b});
+    line->line->marker_face_color({0.0f, r, g, b});
+}
+
+void mpl_line_set_width(MplLine* line, float width) {
+    if (!line || !line->line) return;
+    line->line->line_width(width);
+}
+
+void mpl_line_set_style(MplLine* line, const char* style) {
+    if (!line || !line->line || !style) return;
+    line->line->line_style(style);
+}
+
+void mpl_line_set_label(MplLine* line, const char* label) {
+    if (!line || !line->line || !label) return;
+    line->line->display_name(label);
+}
+
+void mpl_line_set_marker_size(MplLine* line, float size) {
+    if (!line || !line->line) return;
+    line->line->marker_size(size);
+}
+
+void mpl_line_append(MplLine* line, const double* x, const double* y, size_t count, size_t max_len) {
+    if (!line || !line->line) return;
+    append_points(*line->line, x, y, count, max_len);
+}
+
+void mpl_line_set_group(MplLine* line, const char* group) {
+    if (!line || !group) return;
+    std::lock_guard<std::mutex> lock(series_mutex);
+    series_groups[line->id] = group;
+}
+
+uint64_t mpl_line_id(MplLine* line) {
+    return line ? line->id : 0;
+}
+
+bool mpl_axes_update_series(MplAxes* ax, uint64_t id, const double* x, const double* y, size_t count) {
+    if (!ax || !ax->axes) return false;
+    auto line = find_series(id);
+    if (!line || !has_child(ax, line)) return false;
+    line->x_data(std::vector<double>(x, x + count));
+    line->y_data(std::vector<double>(y, y + count));
+    ++series_revision;
+    ax->axes->touch();
+    return true;
+}
+
+bool mpl_axes_append_series(MplAxes* ax, uint64_t id, const double* x, const double* y, size_t count, size_t max_len) {
+    if (!ax || !ax->axes) return false;
+    auto line = find_series(id);
+    if (!line || !has_child(ax, line)) return false;
+    append_points(*line, x, y, count, max_len);
+    ax->axes->touch();
+    return true;
+}
+
+bool mpl_axes_remove_series(MplAxes* ax, uint64_t id) {
+    if (!ax || !ax->axes) return false;
+    auto line = find_series(id);
+    if (!line) return false;
+    auto children = ax->axes->children();
+    auto it = std::find_if(children.begin(), children.end(),
+                           [&](const auto& c) { return c.get() == line.get(); });
+    if (it == children.end()) return false;
+    children.erase(it);
+    ax->axes->children(children);
+    {
+        std::lock_guard<std::mutex> lock(series_mutex);
+        series_registry.erase(id);
+        series_groups.erase(id);
     }
+    ++series_revision;
+    ax->axes->touch();
+    return true;
+}
+
+bool mpl_axes_set_series_visible(MplAxes* ax, uint64_t id, bool visible) {
+    if (!ax || !ax->axes) return false;
+    auto line = find_series(id);
+    if (!line || !has_child(ax, line)) return false;
+    line->visible(visible);
+    ++series_revision;
+    ax->axes->touch();
+    return true;
+}
+
+bool mpl_axes_series_visible(MplAxes* ax, uint64_t id, bool* visible) {
+    if (!ax || !ax->axes || !visible) return false;
+    auto line = find_series(id);
+    if (!line || !has_child(ax, line)) return false;
+    *visible = line->visible();
+    return true;
+}
+
+bool mpl_axes_set_series_group(MplAxes* ax, uint64_t id, const char* group) {
+    if (!ax || !ax->axes || !group) return false;
+    auto line = find_series(id);
+    if (!line || !has_child(ax, line)) return false;
+    std::lock_guard<std::mutex> lock(series_mutex);
+    series_groups[id] = group;
+    return true;
+}
+
+size_t mpl_axes_group_series(MplAxes* ax, const char* group, uint64_t* ids, size_t capacity) {
+    if (!ax || !ax->axes || !group) return 0;
+    std::vector<std::pair<uint64_t, std::shared_ptr<matplot::line>>> members;
+    {
+        std::lock_guard<std::mutex> lock(series_mutex);
+        for (const auto& [id, name] : series_groups) {
+            if (name != group) continue;
+            auto it = series_registry.find(id);
+            if (it == series_registry.end()) continue;
+            if (auto line = it->second.lock()) members.emplace_back(id, std::move(line));
+        }
+    }
+    std::sort(members.begin(), members.end(),
+              [](const auto& a, const auto& b) { return a.first < b.first; });
+    size_t total = 0;
+    for (const auto& [id, line] : members) {
+        if (!has_child(ax, line)) continue;
+        if (ids && total < capacity) ids[total] = id;
+        ++total;
+    }
+    return total;
+}
+
+bool mpl_axes_set_series_alpha(MplAxes* ax, uint64_t id, float alpha) {
+    if (!ax || !ax->axes) return false;
+    auto line = find_series(id);
+    if (!line) return false;
+    // matplot++ stores transparency in color[0].
+    float t = 1.0f - std::clamp(alpha, 0.0f, 1.0f);
+    auto c = line->color();
+    line->color({t, c[1], c[2], c[3]});
+    auto m = line->marker_face_color();
+    line->marker_face_color({t, m[1], m[2], m[3]});
+    ax->axes->touch();
+    return true;
+}
+
+bool mpl_axes_set_series_color(MplAxes* ax, uint64_t id, float r, float g, float b) {
+    if (!ax || !ax->axes) return false;
+    auto line = find_series(id);
+    if (!line) return false;
+    float t = line->color()[0];
+    line->color({t, r, g, b});
+    line->marker_face_color({t, r, g, b});
+    ax->axes->touch();
+    return true;
+}
+
+bool mpl_axes_set_series_width(MplAxes* ax, uint64_t id, float width) {
+    if (!ax || !ax->axes) return false;
+    auto line = find_series(id);
+    if (!line) return false;
+    line->line_width(width);
+    ax->axes->touch();
+    return true;
+}
+
+size_t mpl_axes_series_data(MplAxes* ax, double* x, double* y, size_t capacity) {
+    if (!ax || !ax->axes) return 0;
+    std::vector<std::shared_ptr<matplot::line>> series;
+    {
+        std::lock_guard<std::mutex> lock(series_mutex);
+        for (const auto& [id, weak] : series_registry) {
+            if (auto line = weak.lock()) series.push_back(std::move(line));
+        }
+    }
+    size_t total = 0;
+    for (const auto& child : ax->axes->children()) {
+        auto it = std::find_if(series.begin(), series.end(),
+                               [&](const auto& s) { return s.get() == child.get(); });
+        if (it == series.end() || !(*it)->visible()) continue;
+        const auto& xs = (*it)->x_data();
+        const auto& ys = (*it)->y_data();
+        size_t n = std::min(xs.size(), ys.size());
+        for (size_t i = 0; i < n; ++i, ++total) {
+            if (total < capacity && x && y) {
+                x[total] = xs[i];
+                y[total] = ys[i];
+            }
+        }
+    }
+    return total;
+}
+
+size_t mpl_axes_series_ids(MplAxes* ax, uint64_t* ids, size_t capacity) {
+    if (!ax || !ax->axes) return 0;
+    std::vector<std::pair<uint64_t, std::shared_ptr<matplot::line>>> series;
+    {
+        std::lock_guard<std::mutex> lock(series_mutex);
+        for (const auto& [id, weak] : series_registry) {
+            if (auto line = weak.lock()) series.emplace_back(id, std::move(line));
+        }
+    }
+    size_t total = 0;
+    for (const auto& child : ax->axes->children()) {
+        auto it = std::find_if(series.begin(), series.end(),
+                               [&](const auto& s) { return s.second.get() == child.get(); });
+        if (it == series.end() || !it->second->visible()) continue;
+        if (total < capacity && ids) ids[total] = it->first;
+        ++total;
+    }
+    return total;
+}
+
+size_t mpl_axes_series_points(MplAxes* ax, uint64_t id, double* x, double* y, size_t capacity) {
+    if (!ax || !ax->axes) return 0;
+    auto line = find_series(id);
+    if (!line || !has_child(ax, line)) return 0;
+    const auto& xs = line->x_data();
+    const auto& ys = line->y_data();
+    size_t n = std::min(xs.size(), ys.size());
+    for (size_t i = 0; i < n && i < capacity && x && y; ++i) {
+        x[i] = xs[i];
+        y[i] = ys[i];
+    }
+    return n;
+}
+
+uint64_t mpl_series_revision(void) {
+    return series_revision.load();
+}
+
+MplLine* mpl_axes_scatter_rgb(MplAxes* ax, const double* x, const double* y, size_t count, const char* style, float r, float g, float b) {
+    if (!ax || !ax->axes || count == 0) return nullptr;
+    std::vector<double> vx(x, x + count);
+    std::vector<double> vy(y, y + count);
+    auto line = ax->axes->plot(vx, vy, style ? style : "o");
+    line->color({0.0f, r, g, b});
+    line->marker_face_color({0.0f, r, g, b});
+    return register_series(std::move(line));
 }
 
 void mpl_axes_bar(MplAxes* ax, const double* values, size_t count) {
@@ -224,6 +580,13 @@ void mpl_axes_hist(MplAxes* ax, const double* values, size_t count, size_t bins)
     ax->axes->hist(v, bins);
 }
 
+void mpl_axes_hist_edges(MplAxes* ax, const double* values, size_t count, const double* edges, size_t n_edges) {
+    if (!ax || !ax->axes || n_edges < 2) return;
+    std::vector<double> v(values, values + count);
+    std::vector<double> e(edges, edges + n_edges);
+    ax->axes->hist(v, e);
+}
+
 void mpl_axes_surface(MplAxes* ax, const double* x, const double* y, const double* z, size_t rows, size_t cols, bool wireframe) {
     if (!ax || !ax->axes) return;
     
@@ -281,6 +644,55 @@ void mpl_axes_heatmap(MplAxes* ax, const double* z, size_t rows, size_t cols) {
     ax->axes->heatmap(Z);
 }
 
+void mpl_axes_set_colormap(MplAxes* ax, const float* rgb, size_t count) {
+    if (!ax || !ax->axes || count == 0) return;
+    std::vector<std::vector<double>> map(count);
+    for (size_t i = 0; i < count; ++i) {
+        map[i] = {rgb[i * 3], rgb[i * 3 + 1], rgb[i * 3 + 2]};
+    }
+    ax->axes->colormap(map);
+}
+
+MplLine* mpl_axes_plot_with_band(MplAxes* ax, const double* x, const double* y, const double* y_low, const double* y_high, size_t count, const char* style, float alpha) {
+    if (!ax || !ax->axes || count < 2) return nullptr;
+    std::vector<double> vx(x, x + count);
+    std::vector<double> vy(y, y + count);
+
+    // Closed outline: upper edge left-to-right, lower edge right-to-left.
+    // Non-finite samples are skipped so a NaN doesn't spike the band.
+    std::vector<double> px, py;
+    px.reserve(count * 2);
+    py.reserve(count * 2);
+    for (size_t i = 0; i < count; ++i) {
+        if (std::isfinite(x[i]) && std::isfinite(y_high[i])) {
+            px.push_back(x[i]);
+            py.push_back(y_high[i]);
+        }
+    }
+    for (size_t i = count; i-- > 0;) {
+        if (std::isfinite(x[i]) && std::isfinite(y_low[i])) {
+            px.push_back(x[i]);
+            py.push_back(y_low[i]);
+        }
+    }
+
+    bool replace = ax->axes->next_plot_replace();
+    auto line = ax->axes->plot(vx, vy, style ? std::string(style) : "");
+    if (px.size() >= 3) {
+        ax->axes->next_plot_replace(false);
+        // Same hue as the line; matplot++ stores transparency in color[0].
+        auto band = ax->axes->fill(px, py);
+        auto c = line->color();
+        band->color({1.0f - alpha, c[1], c[2], c[3]});
+        // The line picks its color first; draw the band behind it.
+        auto children = ax->axes->children();
+        std::iter_swap(children.end() - 2, children.end() - 1);
+        ax->axes->children(children);
+    }
+    ax->axes->next_plot_replace(replace);
+    return register_series(std::move(line));
+}
+
 void mpl_axes_set_title(MplAxes* ax, const char* title) {
     if (ax && ax->axes) ax->axes->title(title);
 }
@@ -293,10 +705,50 @@ void mpl_axes_set_ylabel(MplAxes* ax, const char* label) {
     if (ax && ax->axes) ax->axes->y_axis().label(label);
 }
 
+void mpl_axes_set_title_style(MplAxes* ax, float size, float r, float g, float b, bool bold) {
+    if (!ax || !ax->axes || size <= 0.0f) return;
+    // matplot++ sizes the title relative to the axes font size.
+    ax->axes->title_font_size_multiplier(size / ax->axes->font_size());
+    ax->axes->title_color({0.0f, r, g, b});
+    ax->axes->title_font_weight(bold ? "bold" : "normal");
+}
+
+void mpl_axes_set_label_style(MplAxes* ax, char axis, float size, float r, float g, float b, bool bold) {
+    if (!ax || !ax->axes || size <= 0.0f) return;
+    auto& a = axis == 'y' ? ax->axes->y_axis() : ax->axes->x_axis();
+    a.label_font_size(size);
+    a.label_color({0.0f, r, g, b});
+    a.label_font_weight(bold ? "bold" : "normal");
+}
+
+void mpl_axes_set_tick_label_style(MplAxes* ax, float size, float r, float g, float b, bool bold) {
+    if (!ax || !ax->axes || size <= 0.0f) return;
+    auto& axes = *ax->axes;
+    mpl_axes_set_tick_font_size(ax, size);
+    // Tick labels use the axes font weight; pin the title and axis
+    // labels to theirs.
+    std::string title_weight = axes.title_font_weight();
+    std::string x_weight = axes.x_axis().label_font_weight();
+    std::string y_weight = axes.y_axis().label_font_weight();
+    axes.font_weight(bold ? "bold" : "normal");
+    axes.title_font_weight(title_weight);
+    axes.x_axis().label_font_weight(x_weight);
+    axes.y_axis().label_font_weight(y_weight);
+    axes.x_axis().color({0.0f, r, g, b});
+    axes.y_axis().color({0.0f, r, g, b});
+}
+
 void mpl_axes_grid(MplAxes* ax, bool on) {
     if (ax && ax->axes) ax->axes->grid(on);
 }
 
+void mpl_axes_legend(MplAxes* ax, bool on) {
+    if (!ax || !ax->axes) return;
+    // legend() creates the legend from the series display names.
+    auto legend = ax->axes->legend();
+    if (legend) legend->visible(on);
+}
+
 void mpl_axes_set_xlim(MplAxes* ax, double min, double max) {
     if (ax && ax->axes) ax->axes->xlim({min, max});
 }
@@ -305,9 +757,502 @@ void mpl_axes_set_ylim(MplAxes* ax, double min, double max) {
     if (ax && ax->axes) ax->axes->ylim({min, max});
 }
 
-void mpl_figure_draw(MplFigure* fig) {
+void mpl_axes_get_xlim(MplAxes* ax, double* min, double* max) {
+    if (!ax || !ax->axes || !min || !max) return;
+    auto lim = ax->axes->xlim();
+    *min = lim[0];
+    *max = lim[1];
+}
+
+void mpl_axes_get_ylim(MplAxes* ax, double* min, double* max) {
+    if (!ax || !ax->axes || !min || !max) return;
+    auto lim = ax->axes->ylim();
+    *min = lim[0];
+    *max = lim[1];
+}
+
+void mpl_axes_set_zlim(MplAxes* ax, double min, double max) {
+    if (ax && ax->axes) ax->axes->zlim({min, max});
+}
+
+void mpl_axes_get_zlim(MplAxes* ax, double* min, double* max) {
+    if (!ax || !ax->axes || !min || !max) return;
+    auto lim = ax->axes->zlim();
+    *min = lim[0];
+    *max = lim[1];
+}
+
+void mpl_axes_set_view(MplAxes* ax, float azimuth, float elevation) {
+    if (ax && ax->axes) ax->axes->view(azimuth, elevation);
+}
+
+void mpl_axes_get_view(MplAxes* ax, float* azimuth, float* elevation) {
+    if (!ax || !ax->axes || !azimuth || !elevation) return;
+    *azimuth = ax->axes->azimuth();
+    *elevation = ax->axes->elevation();
+}
+
+bool mpl_axes_is_3d(MplAxes* ax) {
+    return ax && ax->axes && ax->axes->is_3d();
+}
+
+uintptr_t mpl_axes_key(MplAxes* ax) {
+    return ax ? reinterpret_cast<uintptr_t>(ax->axes.get()) : 0;
+}
+
+void mpl_axes_autoscale(MplAxes* ax) {
+    if (!ax || !ax->axes) return;
+    // Limits set through xlim()/ylim() switch the axes to manual mode.
+    ax->axes->x_axis().limits_mode_auto(true);
+    ax->axes->y_axis().limits_mode_auto(true);
+}
+
+void mpl_axes_get_limits_auto(MplAxes* ax, bool* x, bool* y) {
+    if (!ax || !ax->axes || !x || !y) return;
+    *x = ax->axes->x_axis().limits_mode_auto();
+    *y = ax->axes->y_axis().limits_mode_auto();
+}
+
+void mpl_axes_set_limits_auto(MplAxes* ax, bool x, bool y) {
+    if (!ax || !ax->axes) return;
+    ax->axes->x_axis().limits_mode_auto(x);
+    ax->axes->y_axis().limits_mode_auto(y);
+}
+
+// Range of the plotted objects, the one autoscaling fits the limits to.
+// Returns false if no object has finite bounds.
+bool mpl_axes_get_data_extent(MplAxes* ax, double* x_min, double* x_max, double* y_min, double* y_max) {
+    if (!ax || !ax->axes || !x_min || !x_max || !y_min || !y_max) return false;
+    bool found = false;
+    for (const auto& child : ax->axes->children()) {
+        if (!child || !child->visible()) continue;
+        double x0 = child->xmin(), x1 = child->xmax();
+        double y0 = child->ymin(), y1 = child->ymax();
+        if (!std::isfinite(x0) || !std::isfinite(x1) || !std::isfinite(y0) || !std::isfinite(y1)) continue;
+        *x_min = found ? std::min(*x_min, x0) : x0;
+        *x_max = found ? std::max(*x_max, x1) : x1;
+        *y_min = found ? std::min(*y_min, y0) : y0;
+        *y_max = found ? std::max(*y_max, y1) : y1;
+        found = true;
+    }
+    return found;
+}
+
+// Color limits are shared by every surface, heatmap and image of the
+// axes, so several surfaces map z to colors on one scale.
+void mpl_axes_set_clim(MplAxes* ax, double min, double max) {
+    if (ax && ax->axes) ax->axes->color_box_range(min, max);
+}
+
+void mpl_axes_get_clim(MplAxes* ax, double* min, double* max) {
+    if (!ax || !ax->axes || !min || !max) return;
+    auto lim = ax->axes->color_box_range();
+    *min = lim[0];
+    *max = lim[1];
+}
+
+void mpl_axes_get_pixel_extent(MplAxes* ax, float* x0, float* x1, float* figure_width) {
+    if (!ax || !ax->axes || !x0 || !x1 || !figure_width) return;
+    auto pos = ax->axes->position(); // normalized {x, y, w, h}
+    float w = ax->axes->parent() ? static_cast<float>(ax->axes->parent()->width()) : 0.0f;
+    *x0 = pos[0] * w;
+    *x1 = (pos[0] + pos[2]) * w;
+    *figure_width = w;
+}
+
+void mpl_axes_get_pixel_box(MplAxes* ax, float* left, float* top, float* right, float* bottom) {
+    if (!ax || !ax->axes || !left || !top || !right || !bottom) return;
+    auto pos = ax->axes->position(); // normalized {x, y, w, h}
+    auto fig = ax->axes->parent();
+    std::array<float, 4> box{0.0f, 0.0f, 0.0f, 0.0f};
+    auto backend = fig ? std::dynamic_pointer_cast<WgpuBackend>(fig->backend()) : nullptr;
+    if (backend) {
+        box = backend->axes_box(pos);
+    } else if (fig) {
+        float w = static_cast<float>(fig->width());
+        float h = static_cast<float>(fig->height());
+        box = {pos[0] * w, h - (pos[1] + pos[3]) * h, (pos[0] + pos[2]) * w, h - pos[1] * h};
+    }
+    *left = box[0];
+    *top = box[1];
+    *right = box[2];
+    *bottom = box[3];
+}
+
+float mpl_axes_get_font_size(MplAxes* ax) {
+    if (!ax || !ax->axes) return 0.0f;
+    return ax->axes->font_size();
+}
+
+void mpl_axes_set_tick_font_size(MplAxes* ax, float size) {
+    if (!ax || !ax->axes || size <= 0.0f) return;
+    // matplot++ draws tick labels in the axes font and sizes the title
+    // and axis labels as multiples of it; the multipliers take up the
+    // change so only the tick labels resize.
+    auto& axes = *ax->axes;
+    float scale = axes.font_size() / size;
+    axes.title_font_size_multiplier(axes.title_font_size_multiplier() * scale);
+    axes.label_font_size_multiplier(axes.label_font_size_multiplier() * scale);
+    axes.font_size(size);
+}
+
+void mpl_axes_set_xtickangle(MplAxes* ax, float degrees) {
+    if (ax && ax->axes) ax->axes->xtickangle(degrees);
+}
+
+void mpl_axes_despine(MplAxes* ax, float offset) {
+    if (!ax || !ax->axes || !ax->axes->parent()) return;
+    auto backend = std::dynamic_pointer_cast<WgpuBackend>(ax->axes->parent()->backend());
+    if (backend) backend->set_spine_offset(ax->axes.get(), offset);
+}
+
+void mpl_axes_set_axis_visible(MplAxes* ax, bool visible) {
+    if (!ax || !ax->axes) return;
+    // Ticks, tick labels, axis labels, spines and grid; the data stays.
+    ax->axes->x_axis().visible(visible);
+    ax->axes->y_axis().visible(visible);
+    ax->axes->box(visible);
+    if (!visible) ax->axes->grid(false);
+}
+
+void mpl_axes_set_position(MplAxes* ax, float x, float y, float width, float height) {
+    if (!ax || !ax->axes) return;
+    ax->axes->position({x, y, width, height});
+}
+
+void mpl_axes_set_tick_padding(MplAxes* ax, float padding) {
+    if (!ax || !ax->axes || !ax->axes->parent()) return;
+    auto backend = std::dynamic_pointer_cast<WgpuBackend>(ax->axes->parent()->backend());
+    if (backend) backend->set_tick_padding(ax->axes.get(), padding);
+}
+
+MplAxes* mpl_axes_add_y_axis(MplAxes* ax, float offset, float r, float g, float b) {
+    if (!ax || !ax->axes || !ax->axes->parent()) return nullptr;
+    auto fig = ax->axes->parent();
+    // Overlay axes sharing the host box: transparent, no box, no x axis.
+    auto extra = fig->add_axes();
+    extra->position(ax->axes->position());
+    extra->color({1.0f, 1.0f, 1.0f, 1.0f});
+    extra->box(false);
+    extra->x_axis().visible(false);
+    extra->y_axis().color({0.0f, r, g, b});
+    // The overlay shows the host's x range; mpl_figure_draw keeps it so.
+    extra->xlim(ax->axes->xlim());
+    {
+        std::lock_guard<std::mutex> lock(overlay_mutex);
+        overlay_hosts[extra.get()] = ax->axes;
+    }
+    auto backend = std::dynamic_pointer_cast<WgpuBackend>(fig->backend());
+    if (backend) backend->add_right_y_axis(extra.get(), offset);
+    // add_axes() made the overlay current; the host stays current.
+    fig->current_axes(ax->axes);
+    auto wrapper = new MplAxes();
+    wrapper->axes = extra;
+    return wrapper;
+}
+
+// matplot++ replaces the plots of an axes on each new plot unless hold
+// is on.  Returns whether it was on before.
+bool mpl_axes_hold(MplAxes* ax, bool on) {
+    if (!ax || !ax->axes) return false;
+    bool held = !ax->axes->next_plot_replace();
+    ax->axes->next_plot_replace(!on);
+    return held;
+}
+
+// Annotations are drawn on top of the existing plot, never replacing it.
+void mpl_axes_text(MplAxes* ax, double x, double y, const char* text, float r, float g, float b) {
+    if (!ax || !ax->axes || !text) return;
+    bool replace = ax->axes->next_plot_replace();
+    ax->axes->next_plot_replace(false);
+    ax->axes->text(x, y, text)->color({0.0f, r, g, b});
+    ax->axes->next_plot_replace(replace);
+}
+
+void mpl_axes_arrow(MplAxes* ax, double x0, double y0, double x1, double y1, float r, float g, float b) {
+    if (!ax || !ax->axes) return;
+    bool replace = ax->axes->next_plot_replace();
+    ax->axes->next_plot_replace(false);
+    ax->axes->arrow(x0, y0, x1, y1)->color({0.0f, r, g, b});
+    ax->axes->next_plot_replace(replace);
+}
+
+void mpl_axes_line(MplAxes* ax, double x0, double y0, double x1, double y1, float r, float g, float b) {
+    if (!ax || !ax->axes) return;
+    bool replace = ax->axes->next_plot_replace();
+    ax->axes->next_plot_replace(false);
+    ax->axes->plot(std::vector<double>{x0, x1}, std::vector<double>{y0, y1})->color({0.0f, r, g, b});
+    ax->axes->next_plot_replace(replace);
+}
+
+namespace {
+std::vector<std::string> ToStrings(const char* const* labels, size_t count) {
+    std::vector<std::string> l;
+    l.reserve(count);
+    for (size_t i = 0; i < count; ++i) l.emplace_back(labels[i] ? labels[i] : "");
+    return l;
+}
+} // namespace
+
+void mpl_axes_set_xticks(MplAxes* ax, const double* ticks, size_t count) {
+    if (!ax || !ax->axes) return;
+    ax->axes->xticks(std::vector<double>(ticks, ticks + count));
+}
+
+void mpl_axes_set_xticklabels(MplAxes* ax, const char* const* labels, size_t count) {
+    if (!ax || !ax->axes) return;
+    ax->axes->xticklabels(ToStrings(labels, count));
+}
+
+void mpl_axes_set_yticks(MplAxes* ax, const double* ticks, size_t count) {
+    if (!ax || !ax->axes) return;
+    ax->axes->yticks(std::vector<double>(ticks, ticks + count));
+}
+
+void mpl_axes_set_yticklabels(MplAxes* ax, const char* const* labels, size_t count) {
+    if (!ax || !ax->axes) return;
+    ax->axes->yticklabels(ToStrings(labels, count));
+}
+
+// Tick positions of axis 'x' or 'y' as matplot++ last placed them, and
+// whether it places them itself rather than using ticks set on it.
+size_t mpl_axes_get_ticks(MplAxes* ax, char axis, double* ticks, size_t capacity, bool* automatic) {
+    if (!ax || !ax->axes) return 0;
+    auto& a = axis == 'y' ? ax->axes->y_axis() : ax->axes->x_axis();
+    if (automatic) *automatic = a.tick_values_automatic();
+    const auto& values = a.ticks();
+    if (ticks) std::copy_n(values.begin(), std::min(capacity, values.size()), ticks);
+    return values.size();
+}
+
+// Hands the ticks of axis 'x' or 'y' back to matplot++.
+void mpl_axes_set_ticks_auto(MplAxes* ax, char axis) {
+    if (!ax || !ax->axes) return;
+    auto& a = axis == 'y' ? ax->axes->y_axis() : ax->axes->x_axis();
+    a.tick_values_automatic(true);
+}
+
+namespace {
+// Draws `ax` with its data, x axis, y axis and (despined) box as
+// separate parts, so the backend can move each one without guessing
+// which primitives belong to it.  Limits are fixed to the ones the data
+// pass settled on while the other parts are drawn.
+void draw_axes_in_parts(WgpuBackend& backend, matplot::axes_type& ax) {
+    using Part = WgpuBackend::AxesPart;
+    auto& xa = ax.x_axis();
+    auto& ya = ax.y_axis();
+    const bool x_visible = xa.visible(), y_visible = ya.visible(), box = ax.box();
+    auto draw = [&](Part part, bool x, bool y, bool b) {
+        xa.visible(x);
+        ya.visible(y);
+        ax.box(b);
+        backend.begin_axes(&ax, part, ax.position());
+        ax.draw();
+        backend.end_axes();
+    };
+    const bool despined = backend.despined(&ax);
+    draw(Part::data, false, false, box && !despined);
+
+    const bool x_auto = xa.limits_mode_auto(), y_auto = ya.limits_mode_auto();
+    const bool grid = ax.grid();
+    const std::string title = ax.title();
+    std::vector<std::pair<std::shared_ptr<matplot::axes_object>, bool>> children;
+    for (auto& child : ax.children()) {
+        children.emplace_back(child, child->visible());
+        child->visible(false);
+    }
+    ax.xlim(ax.xlim());
+    ax.ylim(ax.ylim());
+    ax.grid(false);
+    ax.title("");
+    if (x_visible) draw(Part::x_axis, true, false, false);
+    if (y_visible) draw(Part::y_axis, false, true, false);
+    if (box && despined) draw(Part::box, false, false, true);
+
+    for (auto& [child, visible] : children) child->visible(visible);
+    ax.title(title);
+    ax.grid(grid);
+    xa.limits_mode_auto(x_auto);
+    ya.limits_mode_auto(y_auto);
+    xa.visible(x_visible);
+    ya.visible(y_visible);
+    ax.box(box);
+}
+
+// Keeps the x range of extra y axes on their host's.
+void sync_overlay(matplot::axes_type& ax) {
+    std::lock_guard<std::mutex> lock(overlay_mutex);
+    auto it = overlay_hosts.find(&ax);
+    if (it == overlay_hosts.end()) return;
+    if (auto host = it->second.lock()) {
+        ax.xlim(host->xlim());
+    } else {
+        overlay_hosts.erase(it);
+    }
+}
+
+// figure_type::draw() for the wgpu backend, tagging what each axes
+// draws (see WgpuBackend::begin_axes).  Hosts come before their extra
+// y axes among the children, so their limits are settled first.
+void draw_figure(matplot::figure_type& fig, WgpuBackend& backend) {
+    if (!backend.new_frame()) return;
+    backend.draw_background(fig.color());
+    for (auto& ax : fig.children()) {
+        sync_overlay(*ax);
+        if (backend.draws_in_parts(ax.get())) {
+            draw_axes_in_parts(backend, *ax);
+        } else {
+            backend.begin_axes(ax.get(), WgpuBackend::AxesPart::data, ax->position());
+            ax->draw();
+            backend.end_axes();
+        }
+    }
+    backend.render_data();
+}
+} // namespace
+
+const char* mpl_figure_draw(MplFigure* fig) {
+    // An exception unwinding into Rust would abort the process.
+    thread_local std::string error;
+    if (!fig || !fig->figure) return nullptr;
+    try {
+        auto backend = std::dynamic_pointer_cast<WgpuBackend>(fig->figure->backend());
+        if (backend) {
+            draw_figure(*fig->figure, *backend);
+        } else {
+            fig->figure->draw();
+        }
+        return nullptr;
+    } catch (const std::exception& e) {
+        error = e.what();
+    } catch (...) {
+        error = "unknown exception";
+    }
+    return error.c_str();
+}
+
+namespace {
+// Shows only one layer of a figure while alive: the decorations
+// (backgrounds, axes, grid, titles and children that are not series)
+// if `decorations`, and the series in `chosen`.  Limits are fixed to
+// their current values so every layer lines up; the destructor puts
+// everything back.
+class LayerView {
+public:
+    LayerView(matplot::figure_type& fig, bool decorations,
+              const std::unordered_set<const matplot::axes_object*>& series,
+              const std::unordered_set<const matplot::axes_object*>& chosen)
+        : fig_(fig), fig_color_(fig.color()) {
+        if (!decorations) fig.color({1.0f, 1.0f, 1.0f, 1.0f});
+        for (auto& ax : fig.children()) {
+            auto& xa = ax->x_axis();
+            auto& ya = ax->y_axis();
+            Axes saved{ax, xa.limits_mode_auto(), ya.limits_mode_auto(), xa.visible(), ya.visible(),
+                       ax->box(), ax->grid(), ax->title(), ax->color(), {}};
+            ax->xlim(ax->xlim());
+            ax->ylim(ax->ylim());
+            for (auto& child : ax->children()) {
+                saved.children.emplace_back(child, child->visible());
+                bool shown = series.count(child.get()) ? chosen.count(child.get()) > 0 : decorations;
+                child->visible(child->visible() && shown);
+            }
+            if (!decorations) {
+                xa.visible(false);
+                ya.visible(false);
+                ax->box(false);
+                ax->grid(false);
+                ax->title("");
+                ax->color({1.0f, 1.0f, 1.0f, 1.0f});
+            }
+            axes_.push_back(std::move(saved));
+        }
+    }
+
+    ~LayerView() {
+        for (auto& a : axes_) {
+            for (auto& [child, visible] : a.children) child->visible(visible);
+            a.axes->color(a.color);
+            a.axes->title(a.title);
+            a.axes->grid(a.grid);
+            a.axes->box(a.box);
+            a.axes->x_axis().visible(a.x_visible);
+            a.axes->y_axis().visible(a.y_visible);
+            a.axes->x_axis().limits_mode_auto(a.x_auto);
+            a.axes->y_axis().limits_mode_auto(a.y_auto);
+        }
+        fig_.color(fig_color_);
+    }
+
+private:
+    struct Axes {
+        std::shared_ptr<matplot::axes_type> axes;
+        bool x_auto, y_auto, x_visible, y_visible, box, grid;
+        std::string title;
+        matplot::color_array color;
+        std::vector<std::pair<std::shared_ptr<matplot::axes_object>, bool>> children;
+    };
+    matplot::figure_type& fig_;
+    matplot::color_array fig_color_;
+    std::vector<Axes> axes_;
+};
+
+// Whether an axes of `fig` fits a limit to its data.
+bool has_automatic_limits(matplot::figure_type& fig) {
+    for (auto& ax : fig.children()) {
+        if (ax->x_axis().limits_mode_auto() || ax->y_axis().limits_mode_auto()) return true;
+    }
+    return false;
+}
+} // namespace
+
+const char* mpl_figure_draw_layer(MplFigure* fig, bool decorations, const uint64_t* ids, size_t count, bool listed) {
+    // An exception unwinding into Rust would abort the process.
+    thread_local std::string error;
+    if (!fig || !fig->figure) return nullptr;
+    auto backend = std::dynamic_pointer_cast<WgpuBackend>(fig->figure->backend());
+    if (!backend) return nullptr;
+    try {
+        std::unordered_set<const matplot::axes_object*> series, chosen;
+        {
+            std::unordered_set<uint64_t> wanted(ids, ids + count);
+            std::lock_guard<std::mutex> lock(series_mutex);
+            for (const auto& [id, weak] : series_registry) {
+                auto line = weak.lock();
+                if (!line) continue;
+                series.insert(line.get());
+                if ((wanted.count(id) > 0) == listed) chosen.insert(line.get());
+            }
+        }
+        // Automatic limits come from every series; settle them with a
+        // draw whose output is thrown away.
+        if (has_automatic_limits(*fig->figure)) {
+            backend->new_frame();
+            for (auto& ax : fig->figure->children()) {
+                sync_overlay(*ax);
+                ax->draw();
+            }
+            backend->new_frame();
+        }
+        LayerView view(*fig->figure, decorations, series, chosen);
+        draw_figure(*fig->figure, *backend);
+        return nullptr;
+    } catch (const std::exception& e) {
+        error = e.what();
+    } catch (...) {
+        error = "unknown exception";
+    }
+    return error.c_str();
+}
+
+void mpl_figure_set_background(MplFigure* fig, float r, float g, float b, float a) {
     if (fig && fig->figure) {
-        fig->figure->draw();
+        fig->figure->color({1.0f - a, r, g, b});
+    }
+}
+
+void mpl_axes_set_background(MplAxes* ax, float r, float g, float b, float a) {
+    if (ax && ax->axes) {
+        ax->axes->color({1.0f - a, r, g, b});
     }
 }
 
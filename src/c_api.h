@@ -17,6 +17,7 @@ extern "C" {
 typedef struct MplWgpuBackend MplWgpuBackend;
 typedef struct MplFigure MplFigure;
 typedef struct MplAxes MplAxes;
+typedef struct MplLine MplLine;
 
 // Axis / Figure management
 MplFigure* mpl_figure_create(MplWgpuBackend* backend);
@@ -24,28 +25,132 @@ MplFigure* mpl_figure_create_gnuplot(void);
 void mpl_figure_destroy(MplFigure* fig);
 MplAxes* mpl_figure_current_axes(MplFigure* fig);
 void mpl_axes_destroy(MplAxes* ax);
+MplAxes* mpl_axes_clone(MplAxes* ax);
 bool mpl_figure_save(MplFigure* fig, const char* path);
+bool mpl_figure_save_as(MplFigure* fig, const char* path, const char* terminal);
+void mpl_figure_set_size(MplFigure* fig, unsigned int width, unsigned int height);
+void mpl_figure_set_font(MplFigure* fig, const char* name, float size);
 
 // Plotting commands (on Axes)
-void mpl_axes_plot(MplAxes* ax, const double* x, const double* y, size_t count, const char* style);
-void mpl_axes_scatter(MplAxes* ax, const double* x, const double* y, size_t count, const char* style);
+MplLine* mpl_axes_plot(MplAxes* ax, const double* x, const double* y, size_t count, const char* style);
+MplLine* mpl_axes_scatter(MplAxes* ax, const double* x, const double* y, size_t count, const char* style);
+MplLine* mpl_axes_plot3(MplAxes* ax, const double* x, const double* y, const double* z, size_t count, const char* style);
+MplLine* mpl_axes_scatter3(MplAxes* ax, const double* x, const double* y, const double* z, size_t count, const char* style);
+
+// Series styling (on the line returned by plot/scatter)
+void mpl_line_destroy(MplLine* line);
+void mpl_line_set_color(MplLine* line, float r, float g, float b);
+void mpl_line_set_width(MplLine* line, float width);
+void mpl_line_set_style(MplLine* line, const char* style);
+void mpl_line_set_label(MplLine* line, const char* label);
+void mpl_line_set_marker_size(MplLine* line, float size);
+void mpl_line_set_group(MplLine* line, const char* group);
+void mpl_line_append(MplLine* line, const double* x, const double* y, size_t count, size_t max_len);
+uint64_t mpl_line_id(MplLine* line);
+
+// Series by id; ids stay valid for the life of the series and are never
+// reused.  Each returns false when the id names no live series.
+bool mpl_axes_update_series(MplAxes* ax, uint64_t id, const double* x, const double* y, size_t count);
+// Appends `count` points; with `max_len` > 0 only the newest `max_len`
+// points are kept.
+bool mpl_axes_append_series(MplAxes* ax, uint64_t id, const double* x, const double* y, size_t count, size_t max_len);
+bool mpl_axes_remove_series(MplAxes* ax, uint64_t id);
+bool mpl_axes_set_series_visible(MplAxes* ax, uint64_t id, bool visible);
+// Stores whether series `id` is shown in `visible`; false when it is not
+// in `ax`.
+bool mpl_axes_series_visible(MplAxes* ax, uint64_t id, bool* visible);
+// Concatenated points of the visible series of `ax`: copies up to
+// `capacity` of them into `x` and `y` and returns how many there are.
+size_t mpl_axes_series_data(MplAxes* ax, double* x, double* y, size_t capacity);
+// Ids of the visible series of `ax` in drawing order: copies up to
+// `capacity` of them into `ids` and returns how many there are.
+size_t mpl_axes_series_ids(MplAxes* ax, uint64_t* ids, size_t capacity);
+// Points of series `id` of `ax`, copied like mpl_axes_series_data.
+size_t mpl_axes_series_points(MplAxes* ax, uint64_t id, double* x, double* y, size_t capacity);
+// Counter bumped whenever a series is added or its points or visibility
+// change, so caches of series points know when to rebuild.
+uint64_t mpl_series_revision(void);
+
+// Named series groups.  A series belongs to at most one group;
+// mpl_axes_group_series copies up to `capacity` ids of the group's series
+// in `ax`, oldest first, and returns how many there are.
+bool mpl_axes_set_series_group(MplAxes* ax, uint64_t id, const char* group);
+size_t mpl_axes_group_series(MplAxes* ax, const char* group, uint64_t* ids, size_t capacity);
+bool mpl_axes_set_series_alpha(MplAxes* ax, uint64_t id, float alpha);
+bool mpl_axes_set_series_color(MplAxes* ax, uint64_t id, float r, float g, float b);
+bool mpl_axes_set_series_width(MplAxes* ax, uint64_t id, float width);
+MplLine* mpl_axes_scatter_rgb(MplAxes* ax, const double* x, const double* y, size_t count, const char* style, float r, float g, float b);
 void mpl_axes_bar(MplAxes* ax, const double* values, size_t count); // Simplified for now
 void mpl_axes_hist(MplAxes* ax, const double* values, size_t count, size_t bins);
+void mpl_axes_hist_edges(MplAxes* ax, const double* values, size_t count, const double* edges, size_t n_edges);
 void mpl_axes_surface(MplAxes* ax, const double* x, const double* y, const double* z, size_t rows, size_t cols, bool wireframe);
 void mpl_axes_pie(MplAxes* ax, const double* values, size_t count);
 void mpl_axes_boxplot(MplAxes* ax, const double* values, size_t count); // Simplified 1D
 void mpl_axes_heatmap(MplAxes* ax, const double* z, size_t rows, size_t cols);
+void mpl_axes_set_colormap(MplAxes* ax, const float* rgb, size_t count);
+MplLine* mpl_axes_plot_with_band(MplAxes* ax, const double* x, const double* y, const double* y_low, const double* y_high, size_t count, const char* style, float alpha);
 
 void mpl_axes_set_title(MplAxes* ax, const char* title);
 void mpl_axes_set_xlabel(MplAxes* ax, const char* label);
 void mpl_axes_set_ylabel(MplAxes* ax, const char* label);
+void mpl_axes_set_title_style(MplAxes* ax, float size, float r, float g, float b, bool bold);
+void mpl_axes_set_label_style(MplAxes* ax, char axis, float size, float r, float g, float b, bool bold);
+void mpl_axes_set_tick_label_style(MplAxes* ax, float size, float r, float g, float b, bool bold);
 void mpl_axes_grid(MplAxes* ax, bool on);
+void mpl_axes_legend(MplAxes* ax, bool on);
 void mpl_axes_set_xlim(MplAxes* ax, double min, double max);
 void mpl_axes_set_ylim(MplAxes* ax, double min, double max);
+void mpl_axes_get_xlim(MplAxes* ax, double* min, double* max);
+void mpl_axes_get_ylim(MplAxes* ax, double* min, double* max);
+void mpl_axes_set_zlim(MplAxes* ax, double min, double max);
+void mpl_axes_get_zlim(MplAxes* ax, double* min, double* max);
+void mpl_axes_set_view(MplAxes* ax, float azimuth, float elevation);
+void mpl_axes_get_view(MplAxes* ax, float* azimuth, float* elevation);
+bool mpl_axes_is_3d(MplAxes* ax);
+// Identity of the matplot++ axes behind `ax`, equal for every handle to
+// the same axes while one of them is alive.
+uintptr_t mpl_axes_key(MplAxes* ax);
+void mpl_axes_autoscale(MplAxes* ax);
+void mpl_axes_get_limits_auto(MplAxes* ax, bool* x, bool* y);
+void mpl_axes_set_limits_auto(MplAxes* ax, bool x, bool y);
+bool mpl_axes_get_data_extent(MplAxes* ax, double* x_min, double* x_max, double* y_min, double* y_max);
+void mpl_axes_set_clim(MplAxes* ax, double min, double max);
+void mpl_axes_get_clim(MplAxes* ax, double* min, double* max);
+void mpl_axes_get_pixel_extent(MplAxes* ax, float* x0, float* x1, float* figure_width);
+void mpl_axes_get_pixel_box(MplAxes* ax, float* left, float* top, float* right, float* bottom);
+float mpl_axes_get_font_size(MplAxes* ax);
+// Tick label font size of both axes; the title and axis labels keep
+// their size.
+void mpl_axes_set_tick_font_size(MplAxes* ax, float size);
+void mpl_axes_set_xtickangle(MplAxes* ax, float degrees);
+void mpl_axes_despine(MplAxes* ax, float offset);
+void mpl_axes_set_axis_visible(MplAxes* ax, bool visible);
+void mpl_axes_set_position(MplAxes* ax, float x, float y, float width, float height);
+void mpl_axes_set_tick_padding(MplAxes* ax, float padding);
+MplAxes* mpl_axes_add_y_axis(MplAxes* ax, float offset, float r, float g, float b);
+bool mpl_axes_hold(MplAxes* ax, bool on);
+void mpl_axes_text(MplAxes* ax, double x, double y, const char* text, float r, float g, float b);
+void mpl_axes_arrow(MplAxes* ax, double x0, double y0, double x1, double y1, float r, float g, float b);
+void mpl_axes_line(MplAxes* ax, double x0, double y0, double x1, double y1, float r, float g, float b);
+void mpl_axes_set_xticks(MplAxes* ax, const double* ticks, size_t count);
+void mpl_axes_set_xticklabels(MplAxes* ax, const char* const* labels, size_t count);
+void mpl_axes_set_yticks(MplAxes* ax, const double* ticks, size_t count);
+void mpl_axes_set_yticklabels(MplAxes* ax, const char* const* labels, size_t count);
+size_t mpl_axes_get_ticks(MplAxes* ax, char axis, double* ticks, size_t capacity, bool* automatic);
+void mpl_axes_set_ticks_auto(MplAxes* ax, char axis);
 
 // Figure level
-void mpl_figure_draw(MplFigure* fig);
+// Draws the figure through the backend callbacks.  Returns null, or the
+// message of the exception that stopped drawing, valid until the next
+// call on this thread.
+const char* mpl_figure_draw(MplFigure* fig);
+// Draws one layer of the figure like mpl_figure_draw: the decorations
+// (backgrounds, axes, grid, titles, annotations) if `decorations`, and
+// the series whose ids are (`listed`) or are not in `ids`.
+const char* mpl_figure_draw_layer(MplFigure* fig, bool decorations, const uint64_t* ids, size_t count, bool listed);
 void mpl_figure_clear(MplFigure* fig);
+void mpl_figure_set_background(MplFigure* fig, float r, float g, float b, float a);
+void mpl_axes_set_background(MplAxes* ax, float r, float g, float b, float a);
 // ===========================================================================
 
 typedef struct {
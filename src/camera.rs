@@ -0,0 +1,190 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Orbit camera for 3D plots.
+//!
+//! A [`Camera3D`] circles a target point, as the mouse rotation of
+//! matplotlib's 3D axes does: dragging [`orbit`](Camera3D::orbit)s it,
+//! the wheel [`zoom`](Camera3D::zoom)s and a modified drag
+//! [`pan`](Camera3D::pan)s.  Z is up and angles follow matplotlib, so
+//! the [`Default`] camera shows what `view_init()` does.
+//!
+//! [`view_projection`](Camera3D::view_projection) is the matrix for the
+//! renderers drawing world positions, such as
+//! [`PrimitiveRenderer::set_view_projection`](crate::primitives::PrimitiveRenderer::set_view_projection)
+//! and [`PointCloudRenderer`](crate::pointcloud::PointCloudRenderer).
+//!
+//! The 3D axes of a figure are projected by matplot++ instead.  Each
+//! one gets its own camera from
+//! [`PlotBackend::camera_mut`](crate::plotting::PlotBackend::camera_mut),
+//! whose world is the axes box normalized to the unit cube around the
+//! origin.  Renders without a target transform point the axes along
+//! its azimuth and elevation and set the limits from
+//! [`axes_limits`](Camera3D::axes_limits), so zooming and panning move
+//! through the data.
+
+use crate::watch::{AxisLimits, CameraView};
+use glam::{Mat4, Vec3};
+
+/// Elevation limit, in degrees, short of the poles where the up
+/// direction is lost.
+pub const MAX_ELEVATION: f32 = 89.0;
+/// Closest approach to the target, in world units.
+const MIN_DISTANCE: f32 = 1e-4;
+
+/// Camera circling `target` at `distance`, angles in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Camera3D {
+  /// Point looked at and orbited.
+  pub target: Vec3,
+  /// Rotation about the Z axis, from the X axis towards Y.
+  pub azimuth: f32,
+  /// Angle above the XY plane.
+  pub elevation: f32,
+  /// Distance from the target.
+  pub distance: f32,
+  /// Vertical field of view.
+  pub fov_y: f32,
+}
+
+impl Default for Camera3D {
+  /// matplotlib's default view (azimuth -37.5°, elevation 30°) of the
+  /// unit cube around the origin.
+  fn default() -> Self {
+    Self::for_bounds(Vec3::splat(-0.5), Vec3::splat(0.5))
+  }
+}
+
+impl Camera3D {
+  /// Camera at matplotlib's default angles framing the box
+  /// `[min, max]`.
+  pub fn for_bounds(min: Vec3, max: Vec3) -> Self {
+    let fov_y: f32 = 30.0;
+    let radius = ((max - min).length() * 0.5).max(f32::EPSILON);
+    Self {
+      target: (min + max) * 0.5,
+      azimuth: -37.5,
+      elevation: 30.0,
+      distance: radius / (fov_y.to_radians() * 0.5).sin(),
+      fov_y,
+    }
+  }
+
+  /// Sets the angles.
+  pub fn with_view(mut self, view: CameraView) -> Self {
+    self.azimuth = view.azimuth;
+    self.elevation = view.elevation;
+    self
+  }
+
+  /// The angles, as 3D axes take them.
+  pub fn view(&self) -> CameraView {
+    CameraView { azimuth: self.azimuth, elevation: self.elevation }
+  }
+
+  /// Rotates `dx` degrees about the Z axis and `dy` degrees up,
+  /// stopping [`MAX_ELEVATION`] short of the poles.
+  pub fn orbit(&mut self, dx: f32, dy: f32) {
+    self.azimuth = (self.azimuth + dx + 180.0).rem_euclid(360.0) - 180.0;
+    self.elevation = (self.elevation + dy).clamp(-MAX_ELEVATION, MAX_ELEVATION);
+  }
+
+  /// Moves towards the target for positive `delta` and away for
+  /// negative, by a factor of `e^delta`, so wheel steps of e.g. 0.1
+  /// zoom evenly at any distance.
+  pub fn zoom(&mut self, delta: f32) {
+    self.distance = (self.distance * (-delta).exp()).max(MIN_DISTANCE);
+  }
+
+  /// Slides the target across the view by `dx` and `dy` view heights,
+  /// Y down as on screen, so the scene follows a dragging pointer.
+  pub fn pan(&mut self, dx: f32, dy: f32) {
+    let forward = (self.target - self.eye()).normalize();
+    let right = forward.cross(Vec3::Z).normalize();
+    let up = right.cross(forward);
+    let height = 2.0 * self.distance * (self.fov_y.to_radians() * 0.5).tan();
+    self.target += (up * dy - right * dx) * height;
+  }
+
+  /// Camera position.
+  pub fn eye(&self) -> Vec3 {
+    let (az, el) = (self.azimuth.to_radians(), self.elevation.to_radians());
+    self.target + Vec3::new(el.cos() * az.cos(), el.cos() * az.sin(), el.sin()) * self.distance
+  }
+
+  /// Limits of 3D axes whose box was `home` when this camera was at
+  /// its [`Default`] framing, with the box normalized to the unit cube
+  /// around the origin: [`zoom`](Self::zoom) scales the limits about
+  /// their center and [`pan`](Self::pan) moves the center.  `None` at
+  /// the default framing, which leaves the limits to autoscaling.
+  pub fn axes_limits(&self, home: AxisLimits) -> Option<AxisLimits> {
+    let framed = Self::default();
+    if self.target == framed.target && self.distance == framed.distance {
+      return None;
+    }
+    let scale = f64::from(self.distance / framed.distance);
+    let fit = |(lo, hi): (f64, f64), t: f32| {
+      let (span, center) = (hi - lo, (lo + hi) * 0.5 + f64::from(t) * (hi - lo));
+      (center - span * 0.5 * scale, center + span * 0.5 * scale)
+    };
+    Some(AxisLimits {
+      x: fit(home.x, self.target.x),
+      y: fit(home.y, self.target.y),
+      z: fit(home.z, self.target.z),
+    })
+  }
+
+  /// World to clip space for a target `aspect` (width over height),
+  /// with wgpu's 0 to 1 depth range.
+  pub fn view_projection(&self, aspect: f32) -> Mat4 {
+    let view = Mat4::look_at_rh(self.eye(), self.target, Vec3::Z);
+    let near = self.distance * 1e-2;
+    let proj = Mat4::perspective_rh(self.fov_y.to_radians(), aspect, near, self.distance * 1e2);
+    proj * view
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn orbits_around_the_target() {
+    let mut camera = Camera3D::for_bounds(Vec3::ZERO, Vec3::new(2.0, 2.0, 2.0));
+    assert_eq!(camera.target, Vec3::ONE);
+    let clip = camera.view_projection(1.0).project_point3(camera.target);
+    assert!(clip.truncate().length() < 1e-5 && clip.z > 0.0 && clip.z < 1.0, "{:?}", clip);
+
+    let d = (camera.eye() - camera.target).length();
+    camera.orbit(200.0, 100.0);
+    assert_eq!((camera.azimuth, camera.elevation), (162.5, MAX_ELEVATION));
+    assert!(((camera.eye() - camera.target).length() - d).abs() < 1e-4);
+
+    camera.zoom(2f32.ln());
+    assert!((camera.distance - d / 2.0).abs() < 1e-4);
+
+    // Dragging right moves the target left of the view.
+    camera.elevation = 0.0;
+    camera.azimuth = -90.0;
+    camera.pan(0.5, 0.0);
+    assert!(camera.target.x < 1.0 && (camera.target.y - 1.0).abs() < 1e-5, "{:?}", camera.target);
+  }
+
+  #[test]
+  fn zoom_and_pan_move_the_axes_limits() {
+    let home = AxisLimits { x: (0.0, 10.0), y: (-1.0, 1.0), z: (0.0, 4.0) };
+    let mut camera = Camera3D::default();
+    assert_eq!(camera.axes_limits(home), None);
+
+    camera.zoom(2f32.ln());
+    let l = camera.axes_limits(home).unwrap();
+    assert!((l.x.0 - 2.5).abs() < 1e-5 && (l.x.1 - 7.5).abs() < 1e-5, "{:?}", l);
+    assert!((l.z.0 - 1.0).abs() < 1e-5 && (l.z.1 - 3.0).abs() < 1e-5, "{:?}", l);
+
+    // A quarter box along X at the same zoom.
+    camera.target.x = 0.25;
+    let l = camera.axes_limits(home).unwrap();
+    assert!((l.x.0 - 5.0).abs() < 1e-5 && (l.x.1 - 10.0).abs() < 1e-5, "{:?}", l);
+    assert!((l.y.0 + 0.5).abs() < 1e-5 && (l.y.1 - 0.5).abs() < 1e-5, "{:?}", l);
+  }
+}
@@ -0,0 +1,326 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Immediate-mode drawing with the plot renderers.
+//!
+//! A [`Canvas`] draws lines, rectangles, circles, polygons and text in
+//! screen pixels (origin top-left, Y down) on the same GPU pipelines as
+//! the plots, for overlays such as HUDs and diagrams.  Shapes take a
+//! [`Style`] with fill, stroke and dashes; the canvas turns them into
+//! renderer instances, so callers never see the instance encoding.
+//!
+//! Queue shapes every frame between [`clear`](Canvas::clear) and
+//! [`render`](Canvas::render), which uploads them and draws the shapes
+//! first and the text on top:
+//!
+//! ```rust,no_run
+//! # fn demo(ctx: &mpl_wgpu::context::RenderContext, rp: &mut wgpu::RenderPass<'_>) {
+//! use glam::{Vec2, Vec4};
+//! use mpl_wgpu::canvas::{Canvas, Style};
+//!
+//! let mut canvas = Canvas::new(ctx, 800, 600);
+//! let panel = Style::filled(Vec4::new(0.0, 0.0, 0.0, 0.6)).stroke(Vec4::ONE, 1.0);
+//! canvas.rounded_rect(Vec2::new(10.0, 10.0), Vec2::new(180.0, 40.0), 6.0, &panel);
+//! canvas.text(Vec2::new(20.0, 20.0), "FPS 60", 16.0, Vec4::ONE);
+//! # }
+//! ```
+
+use crate::context::RenderContext;
+use crate::primitives::PrimitiveRenderer;
+use crate::shapes::triangulate;
+use crate::text::TextRenderer;
+use glam::{Vec2, Vec4};
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+/// Vertices used to outline circles.
+const CIRCLE_SEGMENTS: usize = 64;
+/// Vertices used to outline each rounded corner.
+const CORNER_SEGMENTS: usize = 8;
+
+/// Fill, stroke and dash pattern of canvas shapes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Style {
+  /// Fill color, or `None` for an outline only.
+  pub fill: Option<Vec4>,
+  /// Stroke color, or `None` for no outline.
+  pub stroke: Option<Vec4>,
+  /// Stroke width in pixels.
+  pub stroke_width: f32,
+  /// Dash and gap lengths of the stroke in pixels, or `None` for a
+  /// solid stroke.
+  pub dash: Option<(f32, f32)>,
+}
+
+impl Default for Style {
+  /// Black 1px outline without fill.
+  fn default() -> Self {
+    Self {
+      fill: None,
+      stroke: Some(Vec4::new(0.0, 0.0, 0.0, 1.0)),
+      stroke_width: 1.0,
+      dash: None,
+    }
+  }
+}
+
+impl Style {
+  /// Filled without outline.
+  pub fn filled(color: Vec4) -> Self {
+    Self { fill: Some(color), stroke: None, stroke_width: 0.0, dash: None }
+  }
+
+  /// Outlined `width` pixels wide without fill.
+  pub fn stroked(color: Vec4, width: f32) -> Self {
+    Self { stroke: Some(color), stroke_width: width, ..Self::default() }
+  }
+
+  /// Sets the fill color.
+  pub fn fill(mut self, color: Vec4) -> Self {
+    self.fill = Some(color);
+    self
+  }
+
+  /// Sets the stroke color and width.
+  pub fn stroke(mut self, color: Vec4, width: f32) -> Self {
+    self.stroke = Some(color);
+    self.stroke_width = width;
+    self
+  }
+
+  /// Dashes the stroke with `dash` pixels drawn and `gap` skipped.
+  pub fn dashed(mut self, dash: f32, gap: f32) -> Self {
+    self.dash = Some((dash, gap));
+    self
+  }
+}
+
+/// Screen-space drawing surface over a [`PrimitiveRenderer`] and a
+/// [`TextRenderer`].
+pub struct Canvas {
+  prim: PrimitiveRenderer,
+  text: TextRenderer,
+  width: u32,
+  height: u32,
+}
+
+impl Canvas {
+  /// Canvas of `width` x `height` pixels drawing into targets of the
+  /// format of `ctx`, with its fonts.
+  pub fn new(ctx: &RenderContext, width: u32, height: u32) -> Self {
+    let (prim, text) = ctx.renderers(width, height);
+    Self { prim, text, width, height }
+  }
+
+  /// Canvas size in pixels.
+  pub fn size(&self) -> (u32, u32) {
+    (self.width, self.height)
+  }
+
+  /// Follows a resized target.
+  pub fn resize(&mut self, queue: &wgpu::Queue, width: u32, height: u32) {
+    self.prim.resize(queue, width, height);
+    self.text.resize(width, height);
+    self.width = width;
+    self.height = height;
+  }
+
+  /// Discards everything queued so far.
+  pub fn clear(&mut self) {
+    self.prim.clear();
+    self.text.clear();
+  }
+
+  /// Line from `from` to `to` with the stroke of `style`.
+  pub fn line(&mut self, from: Vec2, to: Vec2, style: &Style) {
+    self.polyline(&[from, to], style);
+  }
+
+  /// Open polyline through `points` with the stroke of `style`.  The
+  /// dash pattern continues across vertices.
+  pub fn polyline(&mut self, points: &[Vec2], style: &Style) {
+    let Some(color) = style.stroke else { return };
+    let (dash, gap) = style.dash.unwrap_or((0.0, 0.0));
+    let mut offset = 0.0;
+    for pair in points.windows(2) {
+      let (a, b) = (pair[0].extend(0.0), pair[1].extend(0.0));
+      self.prim.draw_line(a, b, style.stroke_width, color, dash, gap, offset);
+      offset += a.distance(b);
+    }
+  }
+
+  /// Rectangle with top-left corner `pos`.
+  pub fn rect(&mut self, pos: Vec2, size: Vec2, style: &Style) {
+    self.rounded_rect(pos, size, 0.0, style);
+  }
+
+  /// Rectangle with top-left corner `pos` and corners rounded with
+  /// `radius`.
+  pub fn rounded_rect(&mut self, pos: Vec2, size: Vec2, radius: f32, style: &Style) {
+    let radius = radius.clamp(0.0, size.x.min(size.y) * 0.5);
+    if let Some(color) = style.fill {
+      self.prim.draw_rect(pos + size * 0.5, size, color, radius, 0.0);
+    }
+    if style.stroke.is_some() {
+      self.closed(&rounded_outline(pos, size, radius), style);
+    }
+  }
+
+  /// Circle around `center`.
+  pub fn circle(&mut self, center: Vec2, radius: f32, style: &Style) {
+    let outline: Vec<Vec2> = (0..CIRCLE_SEGMENTS)
+      .map(|i| center + Vec2::from_angle(i as f32 * TAU / CIRCLE_SEGMENTS as f32) * radius)
+      .collect();
+    if let Some(color) = style.fill {
+      for i in 0..CIRCLE_SEGMENTS {
+        let next = outline[(i + 1) % CIRCLE_SEGMENTS];
+        self.triangle([center, outline[i], next], color);
+      }
+    }
+    match (style.stroke, style.dash) {
+      (Some(color), None) => {
+        self.prim.draw_circle(center.extend(0.0), radius, color, style.stroke_width, 1);
+      }
+      (Some(_), Some(_)) => self.closed(&outline, style),
+      (None, _) => {}
+    }
+  }
+
+  /// Closed polygon through `points`, filled by ear clipping so it may
+  /// be concave but not self-intersecting.
+  pub fn polygon(&mut self, points: &[Vec2], style: &Style) {
+    if let Some(color) = style.fill {
+      let vertices: Vec<(f64, f64)> = points.iter().map(|p| (p.x as f64, p.y as f64)).collect();
+      for [a, b, c] in triangulate(&vertices) {
+        self.triangle([points[a], points[b], points[c]], color);
+      }
+    }
+    self.closed(points, style);
+  }
+
+  /// Text with its top-left corner at `pos`, `size` pixels high.
+  pub fn text(&mut self, pos: Vec2, text: &str, size: f32, color: Vec4) {
+    self.text.draw_text(text, pos, size, color);
+  }
+
+  /// Like [`text`](Self::text), rotated `rotation` degrees
+  /// counter-clockwise around `pos`.
+  pub fn text_rotated(&mut self, pos: Vec2, text: &str, size: f32, color: Vec4, rotation: f32) {
+    self.text.draw_text_rotated(text, pos, size, color, rotation);
+  }
+
+  /// Width and height of `text` at `size`, for placing it.
+  pub fn measure_text(&self, text: &str, size: f32) -> Vec2 {
+    self.text.measure_text(text, size)
+  }
+
+  /// Uploads the queued shapes and text.  [`render`](Self::render)
+  /// does this itself; call it separately to upload before the render
+  /// pass is begun.
+  pub fn prepare(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+    self.prim.prepare(device, queue);
+    self.text.prepare(device, queue);
+  }
+
+  /// Uploads the queue and draws it into `rp`, shapes first and text
+  /// on top.  The queue is kept; [`clear`](Self::clear) it before the
+  /// next frame.
+  pub fn render<'a>(
+    &'a mut self,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    rp: &mut wgpu::RenderPass<'a>,
+  ) {
+    self.prepare(device, queue);
+    self.prim.render(rp);
+    self.text.render(rp);
+  }
+
+  /// The underlying renderers, for primitives the canvas does not
+  /// cover.
+  pub fn renderers(&mut self) -> (&mut PrimitiveRenderer, &mut TextRenderer) {
+    (&mut self.prim, &mut self.text)
+  }
+
+  fn triangle(&mut self, [a, b, c]: [Vec2; 3], color: Vec4) {
+    self.prim.draw_triangle_unlit(a.extend(0.0), b.extend(0.0), c.extend(0.0), color);
+  }
+
+  /// Stroke of the closed outline through `points`.
+  fn closed(&mut self, points: &[Vec2], style: &Style) {
+    if let Some(&first) = points.first() {
+      let mut ring = points.to_vec();
+      ring.push(first);
+      self.polyline(&ring, style);
+    }
+  }
+}
+
+/// Outline of a rectangle with corners rounded with `radius`,
+/// clockwise on screen from the top-left corner.
+fn rounded_outline(pos: Vec2, size: Vec2, radius: f32) -> Vec<Vec2> {
+  let corners = [
+    pos + Vec2::new(size.x, 0.0),
+    pos + size,
+    pos + Vec2::new(0.0, size.y),
+    pos,
+  ];
+  if radius <= 0.0 {
+    return [pos].into_iter().chain(corners.into_iter().take(3)).collect();
+  }
+  // Corner arc centres and the angles their quarter arcs start at,
+  // with Y down so angles grow clockwise.
+  let centres = [
+    (pos + Vec2::new(radius, radius), 2.0 * FRAC_PI_2),
+    (pos + Vec2::new(size.x - radius, radius), 3.0 * FRAC_PI_2),
+    (pos + size - Vec2::splat(radius), 0.0),
+    (pos + Vec2::new(radius, size.y - radius), FRAC_PI_2),
+  ];
+  centres
+    .iter()
+    .flat_map(|&(c, start)| {
+      (0..=CORNER_SEGMENTS).map(move |i| {
+        let angle = start + FRAC_PI_2 * i as f32 / CORNER_SEGMENTS as f32;
+        c + Vec2::from_angle(angle) * radius
+      })
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rounded_outlines_follow_the_rectangle() {
+    let square = rounded_outline(Vec2::new(10.0, 20.0), Vec2::new(30.0, 40.0), 0.0);
+    assert_eq!(square, vec![
+      Vec2::new(10.0, 20.0),
+      Vec2::new(40.0, 20.0),
+      Vec2::new(40.0, 60.0),
+      Vec2::new(10.0, 60.0),
+    ]);
+
+    let round = rounded_outline(Vec2::ZERO, Vec2::new(20.0, 10.0), 5.0);
+    assert_eq!(round.len(), 4 * (CORNER_SEGMENTS + 1));
+    // Each arc runs from one straight edge to the next.
+    let near = |a: Vec2, b: Vec2| a.distance(b) < 1e-4;
+    assert!(near(round[0], Vec2::new(0.0, 5.0)));
+    assert!(near(round[CORNER_SEGMENTS], Vec2::new(5.0, 0.0)));
+    assert!(near(round[CORNER_SEGMENTS + 1], Vec2::new(15.0, 0.0)));
+    assert!(near(round[2 * CORNER_SEGMENTS + 1], Vec2::new(20.0, 5.0)));
+    assert!(near(round[4 * CORNER_SEGMENTS + 3], Vec2::new(0.0, 5.0)));
+    for p in &round {
+      assert!((0.0..=20.0001).contains(&p.x) && (0.0..=10.0001).contains(&p.y));
+    }
+  }
+
+  #[test]
+  fn style_builders() {
+    let white = Vec4::ONE;
+    let style = Style::filled(white).stroke(Vec4::W, 2.0).dashed(4.0, 2.0);
+    assert_eq!(style.fill, Some(white));
+    assert_eq!(style.stroke, Some(Vec4::W));
+    assert_eq!((style.stroke_width, style.dash), (2.0, Some((4.0, 2.0))));
+    assert_eq!(Style::stroked(white, 3.0).fill, None);
+  }
+}
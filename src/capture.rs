@@ -6,51 +6,199 @@
 //! Provides [`HeadlessRenderer`] for rendering to an off-screen wgpu
 //! texture and reading back pixels, and [`PlotCapture`] which combines
 //! headless rendering with the matplot++ plotting pipeline.
+//!
+//! [`PlotCapture::new_scaled`] supersamples: the figure is laid out at
+//! its logical size and rasterized `scale` times larger, for HiDPI
+//! displays and print, optionally filtered back down to the logical
+//! size for antialiased edges.
+//!
+//! [`PlotCapture::with_format`] renders into an `Rgba16Float` (or, where
+//! the adapter can blend it, `Rgba32Float`) texture instead;
+//! [`render_and_capture_f32`](PlotCapture::render_and_capture_f32)
+//! returns its linear-light pixels as `f32` for tone mapping or other
+//! post-processing.
+//!
+//! [`HeadlessRenderer::capture_region`] and
+//! [`PlotCapture::render_and_capture_region`] read back only a
+//! sub-rectangle, such as the plot area or the legend; [`crop`] does
+//! the same for pixels already on the CPU.
+//!
+//! Creating either needs a GPU adapter, which CI runners and containers
+//! often lack; constructors and read-back return [`Error`] instead of
+//! panicking so callers can skip rendering there.
 
+use crate::animated_image::{AnimationFormat, FrameEncoder};
+use crate::animation::frame_name;
+use crate::context::RenderContext;
+use crate::error::{Error, Result};
+use crate::handle::FigureHandle;
 use crate::plotting::PlotBackend;
 use crate::primitives::PrimitiveRenderer;
 use crate::text::TextRenderer;
-use std::path::Path;
+use crate::trace::trace_span;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
 
 /// wgpu's required row alignment for buffer-to-texture copies.
 const COPY_BYTES_PER_ROW_ALIGNMENT: u32 = 256;
 
-/// Default font path on Linux systems.
+/// Default font path on Linux systems, used without the
+/// `embedded-font` feature.
+#[cfg(not(feature = "embedded-font"))]
 const DEFAULT_FONT_PATH: &str =
   "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf";
 
 /// Off-screen texture format used for capture.
-const CAPTURE_FORMAT: wgpu::TextureFormat =
+pub const CAPTURE_FORMAT: wgpu::TextureFormat =
   wgpu::TextureFormat::Rgba8UnormSrgb;
 
-/// Computes the padded bytes-per-row for a given width.
-fn padded_bytes_per_row(width: u32) -> u32 {
-  let unpadded = width * 4;
+/// Formats headless rendering can read back: [`CAPTURE_FORMAT`] and
+/// the float formats of [`PlotCapture::with_format`].
+const CAPTURE_FORMATS: [wgpu::TextureFormat; 3] = [
+  CAPTURE_FORMAT,
+  wgpu::TextureFormat::Rgba16Float,
+  wgpu::TextureFormat::Rgba32Float,
+];
+
+/// Bytes per pixel of the capture `format`.
+fn bytes_per_pixel(format: wgpu::TextureFormat) -> u32 {
+  match format {
+    wgpu::TextureFormat::Rgba16Float => 8,
+    wgpu::TextureFormat::Rgba32Float => 16,
+    _ => 4,
+  }
+}
+
+/// Computes the padded bytes-per-row for a given row size.
+fn padded_bytes_per_row(unpadded: u32) -> u32 {
   let align = COPY_BYTES_PER_ROW_ALIGNMENT;
   (unpadded + align - 1) / align * align
 }
 
+/// The embedded default font.
+#[cfg(feature = "embedded-font")]
+pub(crate) fn load_default_font() -> Result<Vec<u8>> {
+  Ok(crate::text::EMBEDDED_FONT.to_vec())
+}
+
 /// Loads font data from the default system path.
-fn load_default_font() -> Vec<u8> {
-  std::fs::read(DEFAULT_FONT_PATH).unwrap_or_else(|e| {
-    panic!(
-      "Failed to load font from {}: {}",
-      DEFAULT_FONT_PATH, e
-    );
+#[cfg(not(feature = "embedded-font"))]
+pub(crate) fn load_default_font() -> Result<Vec<u8>> {
+  std::fs::read(DEFAULT_FONT_PATH).map_err(|source| Error::FontFile {
+    path: DEFAULT_FONT_PATH.into(),
+    source,
   })
 }
 
+/// Creates a [`RenderContext`] for headless capture on a new adapter
+/// and device without a surface, using the default font.
+///
+/// Most callers want the process-wide [`shared_context`] instead; a
+/// separate context is useful to isolate a device, for example per
+/// worker thread.
+///
+/// Fails with [`Error::AdapterNotFound`] on machines without a usable
+/// GPU or software adapter.
+pub fn capture_context() -> Result<RenderContext> {
+  capture_context_with_format(CAPTURE_FORMAT)
+}
+
+/// Like [`capture_context`], for rendering into `format`:
+/// [`CAPTURE_FORMAT`], `Rgba16Float` or `Rgba32Float`.
+///
+/// Fails with [`Error::CaptureFormat`] for other formats and for those
+/// the adapter cannot render into with blending; `Rgba32Float` is not
+/// blendable without a native extension.
+pub fn capture_context_with_format(format: wgpu::TextureFormat) -> Result<RenderContext> {
+  let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+    backends: wgpu::Backends::all(),
+    ..Default::default()
+  });
+
+  let adapter = pollster::block_on(
+    instance.request_adapter(&wgpu::RequestAdapterOptions {
+      power_preference: wgpu::PowerPreference::default(),
+      compatible_surface: None,
+      force_fallback_adapter: false,
+    }),
+  )
+  .ok_or(Error::AdapterNotFound)?;
+
+  let features = adapter.get_texture_format_features(format);
+  let usages = wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC;
+  if !CAPTURE_FORMATS.contains(&format)
+    || !features.allowed_usages.contains(usages)
+    || !features.flags.contains(wgpu::TextureFormatFeatureFlags::BLENDABLE)
+  {
+    return Err(Error::CaptureFormat(format));
+  }
+
+  let (device, queue) = pollster::block_on(
+    adapter.request_device(
+      &wgpu::DeviceDescriptor {
+        label: Some("HeadlessDevice"),
+        required_features: wgpu::Features::empty(),
+        required_limits: wgpu::Limits::default(),
+      },
+      None,
+    ),
+  )?;
+
+  RenderContext::new(
+    Arc::new(device),
+    Arc::new(queue),
+    format,
+    &load_default_font()?,
+  )
+}
+
+/// [`RenderContext`] for headless capture into [`CAPTURE_FORMAT`] on
+/// an existing `device` and `queue`, such as the ones driving an
+/// application's window, using the default font.
+///
+/// Compiles the pipelines once; hand the context to
+/// [`HeadlessRenderer::with_context`] or [`PlotCapture::with_context`]
+/// for every figure rendered on that device.
+pub fn capture_context_on(device: Arc<wgpu::Device>, queue: Arc<wgpu::Queue>) -> Result<RenderContext> {
+  RenderContext::new(device, queue, CAPTURE_FORMAT, &load_default_font()?)
+}
+
+/// The process-wide headless [`RenderContext`], created by the first
+/// call.  Batch jobs can call this up front to pay the adapter request
+/// and shader compilation before the first figure is rendered.
+///
+/// A failed creation is not cached; the next call tries again.
+pub fn shared_context() -> Result<&'static RenderContext> {
+  shared_context_with_format(CAPTURE_FORMAT)
+}
+
+/// Like [`shared_context`], one per format of
+/// [`capture_context_with_format`].
+pub fn shared_context_with_format(format: wgpu::TextureFormat) -> Result<&'static RenderContext> {
+  static CONTEXTS: [OnceLock<RenderContext>; 3] = [OnceLock::new(), OnceLock::new(), OnceLock::new()];
+  let slot = CAPTURE_FORMATS.iter().position(|&f| f == format).ok_or(Error::CaptureFormat(format))?;
+  if let Some(ctx) = CONTEXTS[slot].get() {
+    return Ok(ctx);
+  }
+  let ctx = capture_context_with_format(format)?;
+  // A racing thread may have won; its context is kept and ours dropped.
+  Ok(CONTEXTS[slot].get_or_init(|| ctx))
+}
+
 /// Headless wgpu renderer for off-screen capture.
 ///
-/// Creates its own adapter, device, and queue without a surface,
-/// renders to an off-screen texture, and reads back RGBA pixels.
+/// Renders to an off-screen texture on the device of a
+/// [`RenderContext`] and reads back RGBA pixels.
 pub struct HeadlessRenderer {
-  device: wgpu::Device,
-  queue: wgpu::Queue,
+  device: Arc<wgpu::Device>,
+  queue: Arc<wgpu::Queue>,
   texture: wgpu::Texture,
   staging_buffer: wgpu::Buffer,
   prim: PrimitiveRenderer,
   text: TextRenderer,
+  format: wgpu::TextureFormat,
   width: u32,
   height: u32,
 }
@@ -58,35 +206,53 @@ pub struct HeadlessRenderer {
 impl HeadlessRenderer {
   /// Creates a new headless renderer with the given dimensions.
   ///
-  /// Requests a wgpu adapter without a compatible surface, creates
-  /// the device/queue, off-screen texture, staging buffer, and both
-  /// primitive and text renderers.
-  pub fn new(width: u32, height: u32) -> Self {
-    let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
-      backends: wgpu::Backends::all(),
-      ..Default::default()
-    });
+  /// Uses the process-wide [`shared_context`], so only the first
+  /// renderer pays for adapter and device creation and pipeline
+  /// compilation; each renderer then creates just its off-screen
+  /// texture, staging buffer, and the buffers of its primitive and text
+  /// renderers.  Text uses the embedded font with the `embedded-font`
+  /// feature and DejaVu Sans from the system font directory otherwise.
+  pub fn new(width: u32, height: u32) -> Result<Self> {
+    Ok(Self::with_context(shared_context()?, width, height))
+  }
 
-    let adapter = pollster::block_on(
-      instance.request_adapter(&wgpu::RequestAdapterOptions {
-        power_preference: wgpu::PowerPreference::default(),
-        compatible_surface: None,
-        force_fallback_adapter: false,
-      }),
-    )
-    .expect("Failed to find a suitable GPU adapter");
-
-    let (device, queue) = pollster::block_on(
-      adapter.request_device(
-        &wgpu::DeviceDescriptor {
-          label: Some("HeadlessDevice"),
-          required_features: wgpu::Features::empty(),
-          required_limits: wgpu::Limits::default(),
-        },
-        None,
-      ),
-    )
-    .expect("Failed to create device");
+  /// Like [`HeadlessRenderer::new`], rendering text with the TrueType
+  /// or OpenType font in `font_data`.
+  pub fn with_font(
+    width: u32,
+    height: u32,
+    font_data: &[u8],
+  ) -> Result<Self> {
+    let ctx = shared_context()?.with_font(font_data)?;
+    Ok(Self::with_context(&ctx, width, height))
+  }
+
+  /// Like [`HeadlessRenderer::new`], on an existing `device` and
+  /// `queue` instead of the shared context.  Builds a new
+  /// [`RenderContext`] each call; to render many figures on one device
+  /// create it once with [`capture_context_on`] and use
+  /// [`with_context`](Self::with_context).
+  pub fn with_device(
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    width: u32,
+    height: u32,
+  ) -> Result<Self> {
+    let ctx = capture_context_on(device, queue)?;
+    Ok(Self::with_context(&ctx, width, height))
+  }
+
+  /// Headless renderer on the device, pipelines and fonts of `ctx`,
+  /// which must target [`CAPTURE_FORMAT`] or a float format (see
+  /// [`capture_context_with_format`]).
+  pub fn with_context(ctx: &RenderContext, width: u32, height: u32) -> Self {
+    let format = ctx.format();
+    assert!(
+      CAPTURE_FORMATS.contains(&format),
+      "headless rendering needs a context for a capture format"
+    );
+    let device = ctx.device().clone();
+    let queue = ctx.queue().clone();
 
     let texture = device.create_texture(&wgpu::TextureDescriptor {
       label: Some("CaptureTexture"),
@@ -98,13 +264,13 @@ impl HeadlessRenderer {
       mip_level_count: 1,
       sample_count: 1,
       dimension: wgpu::TextureDimension::D2,
-      format: CAPTURE_FORMAT,
+      format,
       usage: wgpu::TextureUsages::RENDER_ATTACHMENT
         | wgpu::TextureUsages::COPY_SRC,
       view_formats: &[],
     });
 
-    let padded_row = padded_bytes_per_row(width);
+    let padded_row = padded_bytes_per_row(width * bytes_per_pixel(format));
     let staging_buffer =
       device.create_buffer(&wgpu::BufferDescriptor {
         label: Some("StagingBuffer"),
@@ -114,17 +280,7 @@ impl HeadlessRenderer {
         mapped_at_creation: false,
       });
 
-    let prim =
-      PrimitiveRenderer::new(&device, CAPTURE_FORMAT, width, height);
-
-    let font_data = load_default_font();
-    let text = TextRenderer::new(
-      &device,
-      CAPTURE_FORMAT,
-      width,
-      height,
-      &font_data,
-    );
+    let (prim, text) = ctx.renderers(width, height);
 
     Self {
       device,
@@ -133,6 +289,7 @@ impl HeadlessRenderer {
       staging_buffer,
       prim,
       text,
+      format,
       width,
       height,
     }
@@ -168,14 +325,88 @@ impl HeadlessRenderer {
     self.height
   }
 
+  /// Returns the format of the off-screen texture.
+  pub fn format(&self) -> wgpu::TextureFormat {
+    self.format
+  }
+
   /// Renders the current primitive and text state to the off-screen
   /// texture, copies to the staging buffer, maps it, and returns
-  /// tightly-packed RGBA pixel data (width * height * 4 bytes).
-  pub fn capture(&mut self) -> Vec<u8> {
-    // Prepare GPU data.
+  /// tightly-packed RGBA pixel data (width * height * 4 bytes).  Float
+  /// targets are encoded to sRGB.
+  pub fn capture(&mut self) -> Result<Vec<u8>> {
+    self.prepare();
+    self.render_and_read_back()
+  }
+
+  /// Like [`capture`](Self::capture), returning linear-light RGBA
+  /// values (width * height * 4 floats) at the precision of the
+  /// target format.
+  pub fn capture_f32(&mut self) -> Result<Vec<f32>> {
+    self.prepare();
+    let data = self.render_and_read_back_raw(self.full_region())?;
+    Ok(decode_linear(self.format, &data))
+  }
+
+  /// Like [`capture`](Self::capture), copying only the `w` x `h`
+  /// pixels whose top-left corner is (`x`, `y`) out of the texture,
+  /// such as the plot area or the legend.  The region is clipped to
+  /// the texture (see [`clip_region`]); the result is tightly packed at
+  /// the clipped size.
+  pub fn capture_region(
+    &mut self,
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+  ) -> Result<Vec<u8>> {
+    self.prepare();
+    let region = clip_region((self.width, self.height), [x, y, w, h]);
+    let data = self.render_and_read_back_raw(region)?;
+    Ok(self.to_srgb8(data))
+  }
+
+  /// Clears the renderers, draws the figure behind `figure` into them
+  /// and captures the result (see [`capture`](Self::capture)).
+  pub fn capture_figure(&mut self, figure: &FigureHandle) -> Result<Vec<u8>> {
+    self.prim.clear();
+    self.text.clear();
+    figure.render(&mut self.prim, &mut self.text, None);
+    self.capture()
+  }
+
+  /// Uploads queued primitives and text to the GPU.
+  pub(crate) fn prepare(&mut self) {
     self.prim.prepare(&self.device, &self.queue);
     self.text.prepare(&self.device, &self.queue);
+  }
+
+  /// Draws the prepared state to the off-screen texture and reads the
+  /// pixels back as sRGB RGBA, blocking until the GPU is done.
+  pub(crate) fn render_and_read_back(&mut self) -> Result<Vec<u8>> {
+    let data = self.render_and_read_back_raw(self.full_region())?;
+    Ok(self.to_srgb8(data))
+  }
 
+  /// The whole texture as `[x, y, w, h]`.
+  fn full_region(&self) -> [u32; 4] {
+    [0, 0, self.width, self.height]
+  }
+
+  /// sRGB RGBA bytes of texels read back in the target format.
+  fn to_srgb8(&self, data: Vec<u8>) -> Vec<u8> {
+    if self.format == CAPTURE_FORMAT {
+      return data;
+    }
+    encode_srgb(&decode_linear(self.format, &data))
+  }
+
+  /// Like [`render_and_read_back`](Self::render_and_read_back),
+  /// returning the texels of `[x, y, w, h]`, which must lie within the
+  /// texture, in the target format.
+  fn render_and_read_back_raw(&mut self, region: [u32; 4]) -> Result<Vec<u8>> {
+    let [_, _, width, height] = region;
+    let _span = trace_span!("capture_readback", width = width, height = height);
     let view = self
       .texture
       .create_view(&wgpu::TextureViewDescriptor::default());
@@ -214,13 +445,21 @@ impl HeadlessRenderer {
       self.text.render(&mut rp);
     }
 
-    // Copy texture to staging buffer.
-    let padded_row = padded_bytes_per_row(self.width);
+    self.copy_region(&mut encoder, region);
+    self.queue.submit(std::iter::once(encoder.finish()));
+    self.map_region(region)
+  }
+
+  /// Records copying the texels of `region` to the staging buffer.
+  fn copy_region(&self, encoder: &mut wgpu::CommandEncoder, region: [u32; 4]) {
+    let [x, y, width, height] = region;
+    let unpadded_row = width * bytes_per_pixel(self.format);
+    let padded_row = padded_bytes_per_row(unpadded_row);
     encoder.copy_texture_to_buffer(
       wgpu::ImageCopyTexture {
         texture: &self.texture,
         mip_level: 0,
-        origin: wgpu::Origin3d::ZERO,
+        origin: wgpu::Origin3d { x, y, z: 0 },
         aspect: wgpu::TextureAspect::All,
       },
       wgpu::ImageCopyBuffer {
@@ -228,37 +467,43 @@ impl HeadlessRenderer {
         layout: wgpu::ImageDataLayout {
           offset: 0,
           bytes_per_row: Some(padded_row),
-          rows_per_image: Some(self.height),
+          rows_per_image: Some(height),
         },
       },
       wgpu::Extent3d {
-        width: self.width,
-        height: self.height,
+        width,
+        height,
         depth_or_array_layers: 1,
       },
     );
+  }
 
-    self.queue.submit(std::iter::once(encoder.finish()));
+  /// Maps the staging buffer after a submitted
+  /// [`copy_region`](Self::copy_region) and returns the texels of
+  /// `region`, tightly packed.
+  fn map_region(&self, region: [u32; 4]) -> Result<Vec<u8>> {
+    let [_, _, width, height] = region;
+    let unpadded_row = width * bytes_per_pixel(self.format);
+    let padded_row = padded_bytes_per_row(unpadded_row);
 
     // Map and read back.
     let buffer_slice = self.staging_buffer.slice(..);
     let (tx, rx) = std::sync::mpsc::channel();
     buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
-      tx.send(result).unwrap();
+      // The receiver outlives the poll below.
+      let _ = tx.send(result);
     });
     self.device.poll(wgpu::Maintain::Wait);
-    rx.recv()
-      .expect("GPU channel closed")
-      .expect("Failed to map staging buffer");
+    rx.recv().map_err(|_| Error::DeviceLost)??;
 
     let data = buffer_slice.get_mapped_range();
-    let unpadded_row = (self.width * 4) as usize;
+    let unpadded_row = unpadded_row as usize;
     let padded_row = padded_row as usize;
 
-    // Strip padding to produce tightly-packed pixels.
+    // Strip padding to produce tightly-packed texels.
     let mut pixels =
-      Vec::with_capacity(unpadded_row * self.height as usize);
-    for row in 0..self.height as usize {
+      Vec::with_capacity(unpadded_row * height as usize);
+    for row in 0..height as usize {
       let start = row * padded_row;
       pixels
         .extend_from_slice(&data[start..start + unpadded_row]);
@@ -267,20 +512,41 @@ impl HeadlessRenderer {
     drop(data);
     self.staging_buffer.unmap();
 
-    pixels
+    Ok(pixels)
+  }
+
+  /// Reads the whole texture back as drawn by `draw`, which records
+  /// into an encoder against the texture's view, for renderers that do
+  /// not go through [`prim`](Self::prim) or [`text`](Self::text).
+  #[cfg(test)]
+  pub(crate) fn read_back_with(
+    &self,
+    draw: impl FnOnce(&mut wgpu::CommandEncoder, &wgpu::TextureView),
+  ) -> Result<Vec<u8>> {
+    let view = self
+      .texture
+      .create_view(&wgpu::TextureViewDescriptor::default());
+    let mut encoder = self.device.create_command_encoder(
+      &wgpu::CommandEncoderDescriptor { label: Some("CaptureEncoder") },
+    );
+    draw(&mut encoder, &view);
+    let region = self.full_region();
+    self.copy_region(&mut encoder, region);
+    self.queue.submit(std::iter::once(encoder.finish()));
+    Ok(self.to_srgb8(self.map_region(region)?))
   }
 
   /// Captures the current frame and saves it as a PNG file.
-  pub fn save_png<P: AsRef<Path>>(&mut self, path: P) {
-    let pixels = self.capture();
+  pub fn save_png<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+    let pixels = self.capture()?;
     image::save_buffer(
       path,
       &pixels,
       self.width,
       self.height,
       image::ColorType::Rgba8,
-    )
-    .expect("Failed to save PNG");
+    )?;
+    Ok(())
   }
 }
 
@@ -289,37 +555,185 @@ impl HeadlessRenderer {
 pub struct PlotCapture {
   headless: HeadlessRenderer,
   plot_backend: PlotBackend,
+  scale: f32,
+  downsample: bool,
 }
 
 impl PlotCapture {
   /// Creates a new plot capture context with the given dimensions.
-  pub fn new(width: u32, height: u32) -> Self {
-    let headless = HeadlessRenderer::new(width, height);
-    let plot_backend = PlotBackend::new(width, height);
+  pub fn new(width: u32, height: u32) -> Result<Self> {
+    Ok(Self::from_headless(HeadlessRenderer::new(width, height)?))
+  }
+
+  /// Like [`PlotCapture::new`], rendering text with the TrueType or
+  /// OpenType font in `font_data`.
+  pub fn with_font(
+    width: u32,
+    height: u32,
+    font_data: &[u8],
+  ) -> Result<Self> {
+    let headless = HeadlessRenderer::with_font(width, height, font_data)?;
+    Ok(Self::from_headless(headless))
+  }
+
+  /// Like [`PlotCapture::new`], on the device and fonts of `ctx` (see
+  /// [`HeadlessRenderer::with_context`]).
+  pub fn with_context(ctx: &RenderContext, width: u32, height: u32) -> Self {
+    Self::from_headless(HeadlessRenderer::with_context(ctx, width, height))
+  }
+
+  /// Like [`PlotCapture::new`], on an existing `device` and `queue`
+  /// (see [`HeadlessRenderer::with_device`]).
+  pub fn with_device(
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    width: u32,
+    height: u32,
+  ) -> Result<Self> {
+    let headless = HeadlessRenderer::with_device(device, queue, width, height)?;
+    Ok(Self::from_headless(headless))
+  }
+
+  /// Like [`PlotCapture::new`], rendering into a texture of `format`,
+  /// typically `Rgba16Float` for
+  /// [`render_and_capture_f32`](Self::render_and_capture_f32).  Fails
+  /// with [`Error::CaptureFormat`] if the adapter cannot render into
+  /// it (see [`capture_context_with_format`]).
+  pub fn with_format(width: u32, height: u32, format: wgpu::TextureFormat) -> Result<Self> {
+    let ctx = shared_context_with_format(format)?;
+    Ok(Self::with_context(ctx, width, height))
+  }
+
+  /// Capture of a `width` x `height` figure rendered at `scale` times
+  /// the resolution: layout, line widths and font sizes are those of
+  /// the logical size, and everything is rasterized into a
+  /// `scale * width` x `scale * height` image.  See
+  /// [`set_downsample`](Self::set_downsample) to get the logical size
+  /// back, antialiased.
+  pub fn new_scaled(width: u32, height: u32, scale: f32) -> Result<Self> {
+    Ok(Self::with_context_scaled(shared_context()?, width, height, scale))
+  }
+
+  /// Like [`PlotCapture::new_scaled`], on the device and fonts of
+  /// `ctx`.
+  pub fn with_context_scaled(ctx: &RenderContext, width: u32, height: u32, scale: f32) -> Self {
+    let scale = if scale.is_finite() && scale > 0.0 { scale } else { 1.0 };
+    let physical = |n: u32| ((n as f32 * scale).round() as u32).max(1);
+    let mut headless = HeadlessRenderer::with_context(ctx, physical(width), physical(height));
+    // Primitives are placed in logical pixels and stretched over the
+    // larger target; text is scaled when it is rasterized.
+    headless.prim.resize(&headless.queue, width, height);
+    headless.text.set_pixel_scale(scale);
+    Self {
+      headless,
+      plot_backend: PlotBackend::new(width, height),
+      scale,
+      downsample: false,
+    }
+  }
+
+  /// Capture of an already configured `backend` at its size, on the
+  /// device and fonts of `ctx`.
+  pub fn from_backend(ctx: &RenderContext, backend: PlotBackend) -> Self {
+    let (width, height) = backend.size();
+    Self {
+      headless: HeadlessRenderer::with_context(ctx, width, height),
+      plot_backend: backend,
+      scale: 1.0,
+      downsample: false,
+    }
+  }
+
+  fn from_headless(headless: HeadlessRenderer) -> Self {
+    let plot_backend = PlotBackend::new(headless.width(), headless.height());
     Self {
       headless,
       plot_backend,
+      scale: 1.0,
+      downsample: false,
     }
   }
 
+  /// Device pixels per logical pixel.
+  pub fn scale(&self) -> f32 {
+    self.scale
+  }
+
+  /// Filters supersampled captures back down to the logical size.  Off
+  /// by default; has no effect at scale 1.
+  pub fn set_downsample(&mut self, downsample: bool) {
+    self.downsample = downsample;
+  }
+
+  /// Whether the supersampled output is downsampled.
+  fn downsampling(&self) -> bool {
+    self.downsample && self.scale != 1.0
+  }
+
   /// Returns the matplot++ figure for configuring plots.
-  pub fn figure(&self) -> crate::plotting::Figure {
+  pub fn figure(&self) -> crate::plotting::Figure<'_> {
     self.plot_backend.figure()
   }
 
-  /// Returns the capture width.
+  /// Returns the width of captured images.
   pub fn width(&self) -> u32 {
-    self.headless.width()
+    if self.downsampling() { self.plot_backend.size().0 } else { self.headless.width() }
   }
 
-  /// Returns the capture height.
+  /// Returns the height of captured images.
   pub fn height(&self) -> u32 {
-    self.headless.height()
+    if self.downsampling() { self.plot_backend.size().1 } else { self.headless.height() }
   }
 
   /// Clears renderers, runs the matplot++ render pipeline through
   /// the FFI callbacks, then captures the result as RGBA pixels.
-  pub fn render_and_capture(&mut self) -> Vec<u8> {
+  pub fn render_and_capture(&mut self) -> Result<Vec<u8>> {
+    self.draw();
+    let pixels = self.headless.capture()?;
+    if !self.downsampling() {
+      return Ok(pixels);
+    }
+    let (w, h) = self.plot_backend.size();
+    Ok(downsample(&pixels, (self.headless.width, self.headless.height), (w, h)))
+  }
+
+  /// Like [`render_and_capture`](Self::render_and_capture), keeping
+  /// only the `w` x `h` pixels at (`x`, `y`) of the captured image,
+  /// clipped to it (see [`clip_region`]).  Without downsampling only
+  /// the region is read back from the GPU.
+  pub fn render_and_capture_region(
+    &mut self,
+    x: u32,
+    y: u32,
+    w: u32,
+    h: u32,
+  ) -> Result<Vec<u8>> {
+    if self.downsampling() {
+      let pixels = self.render_and_capture()?;
+      let size = (self.width(), self.height());
+      return Ok(crop(&pixels, size, [x, y, w, h]));
+    }
+    self.draw();
+    self.headless.capture_region(x, y, w, h)
+  }
+
+  /// Like [`render_and_capture`](Self::render_and_capture), returning
+  /// linear-light RGBA floats without quantizing them to 8 bits.  The
+  /// values keep the precision of the capture format (see
+  /// [`with_format`](Self::with_format)).
+  pub fn render_and_capture_f32(&mut self) -> Result<Vec<f32>> {
+    self.draw();
+    let values = self.headless.capture_f32()?;
+    if !self.downsampling() {
+      return Ok(values);
+    }
+    let (w, h) = self.plot_backend.size();
+    Ok(downsample_linear(&values, (self.headless.width, self.headless.height), (w, h)))
+  }
+
+  /// Clears renderers and runs the matplot++ pipeline, queueing
+  /// primitives and text without touching the GPU.
+  pub(crate) fn draw(&mut self) {
     self.headless.prim.clear();
     self.headless.text.clear();
     self.plot_backend.render(
@@ -327,19 +741,326 @@ impl PlotCapture {
       &mut self.headless.text,
       None,
     );
-    self.headless.capture()
+  }
+
+  /// Returns the underlying headless renderer.
+  #[cfg(feature = "bench")]
+  pub(crate) fn headless_mut(&mut self) -> &mut HeadlessRenderer {
+    &mut self.headless
+  }
+
+  /// Records the plot and saves it as a self-contained interactive
+  /// HTML file with tooltips in the current axes' data coordinates.
+  /// Text is measured with the capture's font so the layout matches
+  /// the PNG output.
+  pub fn save_html<P: AsRef<Path>>(
+    &mut self,
+    path: P,
+  ) -> std::io::Result<()> {
+    let recording =
+      self.plot_backend.record(Some(&mut self.headless.text));
+    let viewport = self.plot_backend.figure().current_axes().viewport();
+    crate::html::save_html(&recording, Some(&viewport), path)
+  }
+
+  /// Records the plot and saves it as an SVG file (see
+  /// [`crate::svg`]).  Text is measured, and outlined for
+  /// [`SvgText::Paths`](crate::svg::SvgText::Paths), with the capture's
+  /// fonts so the layout matches the PNG output.
+  pub fn save_svg<P: AsRef<Path>>(
+    &mut self,
+    path: P,
+    options: &crate::svg::SvgOptions,
+  ) -> std::io::Result<()> {
+    let recording =
+      self.plot_backend.record(Some(&mut self.headless.text));
+    let metrics = self.headless.text.metrics();
+    crate::svg::save_svg(&recording, path, options, Some(&metrics))
+  }
+
+  /// Renders the plot and encodes it for display in a terminal.
+  ///
+  /// `columns` is the preview width in text cells (half blocks) or
+  /// pixels (sixel); see [`crate::term::render`].
+  #[cfg(feature = "term")]
+  pub fn to_term(
+    &mut self,
+    format: crate::term::TermFormat,
+    columns: u32,
+  ) -> Result<String> {
+    let pixels = self.render_and_capture()?;
+    Ok(crate::term::render(
+      &pixels,
+      self.width(),
+      self.height(),
+      format,
+      columns,
+    ))
+  }
+
+  /// Renders a movie of `n_frames` frames played at `fps` frames per
+  /// second, calling `update(frame, figure)` before each frame.
+  ///
+  /// A `path` without an extension is a directory that receives
+  /// numbered PNG frames (see [`frame_name`]).  `.gif`, `.png` and
+  /// `.apng` are encoded as looping animated images (see
+  /// [`animated_image`](crate::animated_image)); other extensions are
+  /// piped into ffmpeg with the `ffmpeg` feature (see
+  /// [`crate::ffmpeg`]) and rejected with [`Error::UnsupportedFormat`]
+  /// without it.
+  pub fn record<P: AsRef<Path>>(
+    &mut self,
+    n_frames: usize,
+    fps: f32,
+    path: P,
+    mut update: impl FnMut(usize, &crate::plotting::Figure<'_>),
+  ) -> Result<()> {
+    let path = path.as_ref();
+    let (width, height) = (self.width(), self.height());
+    let mut sink = FrameSink::open(path, width, height, fps, n_frames)?;
+    for frame in 0..n_frames {
+      update(frame, &self.figure());
+      let pixels = self.render_and_capture()?;
+      sink.push(frame, &pixels, width, height)?;
+    }
+    sink.finish()
   }
 
   /// Renders and saves the result as a PNG file.
-  pub fn save_png<P: AsRef<Path>>(&mut self, path: P) {
-    let pixels = self.render_and_capture();
+  pub fn save_png<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+    let pixels = self.render_and_capture()?;
     image::save_buffer(
       path,
       &pixels,
-      self.headless.width,
-      self.headless.height,
+      self.width(),
+      self.height(),
       image::ColorType::Rgba8,
-    )
-    .expect("Failed to save PNG");
+    )?;
+    Ok(())
+  }
+}
+
+/// `[x, y, w, h]` clipped to an image of `size`: the corner is moved
+/// inside and the extent shortened to end at the image edge.
+pub fn clip_region(size: (u32, u32), region: [u32; 4]) -> [u32; 4] {
+  let [x, y, w, h] = region;
+  let (x, y) = (x.min(size.0), y.min(size.1));
+  [x, y, w.min(size.0 - x), h.min(size.1 - y)]
+}
+
+/// The pixels of `[x, y, w, h]` (clipped, see [`clip_region`]) of
+/// tightly packed RGBA `pixels` of `size`.
+pub fn crop(pixels: &[u8], size: (u32, u32), region: [u32; 4]) -> Vec<u8> {
+  let [x, y, w, h] = clip_region(size, region);
+  let stride = size.0 as usize * 4;
+  let (start, len) = (x as usize * 4, w as usize * 4);
+  let rows = pixels.chunks_exact(stride).skip(y as usize).take(h as usize);
+  rows.flat_map(|row| &row[start..start + len]).copied().collect()
+}
+
+/// Box-filters tightly packed sRGB `pixels` of size `from` down to
+/// `to`, averaging in linear light.  Source pixels straddling a target
+/// pixel's edge count by their overlap, so any ratio works.
+pub fn downsample(pixels: &[u8], from: (u32, u32), to: (u32, u32)) -> Vec<u8> {
+  encode_srgb(&downsample_linear(&decode_linear(CAPTURE_FORMAT, pixels), from, to))
+}
+
+/// Like [`downsample`], for linear-light RGBA floats such as those of
+/// [`PlotCapture::render_and_capture_f32`].
+pub fn downsample_linear(values: &[f32], from: (u32, u32), to: (u32, u32)) -> Vec<f32> {
+  let (cols, rows) = (box_weights(from.0, to.0), box_weights(from.1, to.1));
+  let row_len = from.0 as usize * 4;
+  // Horizontal pass over every source row, then vertical.
+  let mut wide = vec![0.0f32; to.0 as usize * 4 * from.1 as usize];
+  for y in 0..from.1 as usize {
+    let src = &values[y * row_len..(y + 1) * row_len];
+    for (x, taps) in cols.iter().enumerate() {
+      let out = &mut wide[(y * to.0 as usize + x) * 4..][..4];
+      for &(i, w) in taps {
+        for c in 0..4 {
+          out[c] += w * src[i * 4 + c];
+        }
+      }
+    }
+  }
+  let mut out = Vec::with_capacity(to.0 as usize * to.1 as usize * 4);
+  for taps in &rows {
+    for x in 0..to.0 as usize {
+      for c in 0..4 {
+        out.push(taps.iter().map(|&(j, w)| w * wide[(j * to.0 as usize + x) * 4 + c]).sum());
+      }
+    }
+  }
+  out
+}
+
+/// Source indices and weights (summing to 1) of each of `to` target
+/// samples covering `from` source samples.
+fn box_weights(from: u32, to: u32) -> Vec<Vec<(usize, f32)>> {
+  let ratio = from as f32 / to.max(1) as f32;
+  (0..to)
+    .map(|i| {
+      let (lo, hi) = (i as f32 * ratio, (i + 1) as f32 * ratio);
+      let taps: Vec<(usize, f32)> = (lo.floor() as usize..(hi.ceil() as usize).min(from as usize))
+        .map(|j| (j, (hi.min(j as f32 + 1.0) - lo.max(j as f32)) / ratio))
+        .filter(|&(_, w)| w > 0.0)
+        .collect();
+      taps
+    })
+    .collect()
+}
+
+/// Linear-light RGBA of tightly packed texels `data` in the capture
+/// `format`.
+fn decode_linear(format: wgpu::TextureFormat, data: &[u8]) -> Vec<f32> {
+  match format {
+    wgpu::TextureFormat::Rgba16Float => {
+      data.chunks_exact(2).map(|b| f16_to_f32(u16::from_le_bytes([b[0], b[1]]))).collect()
+    }
+    wgpu::TextureFormat::Rgba32Float => {
+      data.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect()
+    }
+    _ => {
+      let decode: Vec<f32> = (0..=255u8).map(|v| srgb_to_linear(v as f32 / 255.0)).collect();
+      data
+        .chunks_exact(4)
+        .flat_map(|p| [decode[p[0] as usize], decode[p[1] as usize], decode[p[2] as usize], p[3] as f32 / 255.0])
+        .collect()
+    }
+  }
+}
+
+/// sRGB RGBA bytes of linear-light `values`, clamped to [0, 1].
+fn encode_srgb(values: &[f32]) -> Vec<u8> {
+  values
+    .chunks_exact(4)
+    .flat_map(|p| {
+      let byte = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+      [byte(linear_to_srgb(p[0])), byte(linear_to_srgb(p[1])), byte(linear_to_srgb(p[2])), byte(p[3])]
+    })
+    .collect()
+}
+
+/// Widens an IEEE 754 half-precision float.
+fn f16_to_f32(bits: u16) -> f32 {
+  let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+  let exp = (bits >> 10) & 0x1f;
+  let mantissa = (bits & 0x3ff) as f32;
+  sign
+    * match exp {
+      0 => mantissa * 2f32.powi(-24),
+      0x1f if mantissa == 0.0 => f32::INFINITY,
+      0x1f => f32::NAN,
+      _ => (1.0 + mantissa / 1024.0) * 2f32.powi(exp as i32 - 15),
+    }
+}
+
+fn srgb_to_linear(v: f32) -> f32 {
+  if v <= 0.04045 { v / 12.92 } else { ((v + 0.055) / 1.055).powf(2.4) }
+}
+
+fn linear_to_srgb(v: f32) -> f32 {
+  if v <= 0.0031308 { v * 12.92 } else { 1.055 * v.powf(1.0 / 2.4) - 0.055 }
+}
+
+/// Destination of the frames of [`PlotCapture::record`].
+enum FrameSink {
+  Frames(PathBuf),
+  Animated(FrameEncoder<BufWriter<File>>),
+  #[cfg(feature = "ffmpeg")]
+  Ffmpeg(crate::ffmpeg::FfmpegEncoder),
+}
+
+impl FrameSink {
+  fn open(path: &Path, width: u32, height: u32, fps: f32, frames: usize) -> Result<Self> {
+    if path.extension().is_none() {
+      std::fs::create_dir_all(path)?;
+      return Ok(FrameSink::Frames(path.into()));
+    }
+    if AnimationFormat::from_path(path).is_some() {
+      return Ok(FrameSink::Animated(FrameEncoder::create(path, width, height, fps, frames as u32)?));
+    }
+    Self::video(path, width, height, fps)
+  }
+
+  #[cfg(feature = "ffmpeg")]
+  fn video(path: &Path, width: u32, height: u32, fps: f32) -> Result<Self> {
+    Ok(FrameSink::Ffmpeg(crate::ffmpeg::FfmpegEncoder::spawn(path, width, height, fps)?))
+  }
+
+  #[cfg(not(feature = "ffmpeg"))]
+  fn video(path: &Path, _width: u32, _height: u32, _fps: f32) -> Result<Self> {
+    Err(Error::UnsupportedFormat(path.into()))
+  }
+
+  fn push(&mut self, frame: usize, pixels: &[u8], width: u32, height: u32) -> Result<()> {
+    match self {
+      FrameSink::Frames(dir) => {
+        image::save_buffer(dir.join(frame_name(frame)), pixels, width, height, image::ColorType::Rgba8)?
+      }
+      FrameSink::Animated(encoder) => encoder.push(pixels)?,
+      #[cfg(feature = "ffmpeg")]
+      FrameSink::Ffmpeg(encoder) => encoder.push(pixels)?,
+    }
+    Ok(())
+  }
+
+  fn finish(self) -> Result<()> {
+    match self {
+      FrameSink::Frames(_) => Ok(()),
+      FrameSink::Animated(encoder) => encoder.finish(),
+      #[cfg(feature = "ffmpeg")]
+      FrameSink::Ffmpeg(encoder) => encoder.finish(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn downsampling_averages_in_linear_light() {
+    // 2x2 black and white checker into one pixel, 4x2 into 3x1.
+    let checker = [0, 0, 0, 255, 255, 255, 255, 255, 255, 255, 255, 255, 0, 0, 0, 255];
+    assert_eq!(downsample(&checker, (2, 2), (1, 1)), vec![188, 188, 188, 255]);
+    let weights = box_weights(4, 3);
+    assert_eq!(weights[1].iter().map(|t| t.0).collect::<Vec<_>>(), vec![1, 2]);
+    for taps in &weights {
+      assert!((taps.iter().map(|t| t.1).sum::<f32>() - 1.0).abs() < 1e-6);
+    }
+    assert_eq!(downsample(&[7; 4 * 4 * 2], (4, 2), (3, 1)), vec![7; 12]);
+  }
+
+  #[test]
+  fn regions_are_clipped_and_cropped() {
+    assert_eq!(clip_region((10, 8), [2, 3, 4, 4]), [2, 3, 4, 4]);
+    assert_eq!(clip_region((10, 8), [8, 6, 5, 5]), [8, 6, 2, 2]);
+    assert_eq!(clip_region((10, 8), [12, 9, 5, 5]), [10, 8, 0, 0]);
+
+    // 3x2 image whose pixels hold their own index.
+    let pixels: Vec<u8> = (0..6u8).flat_map(|i| [i; 4]).collect();
+    let cropped = crop(&pixels, (3, 2), [1, 0, 2, 2]);
+    assert_eq!(cropped, [[1; 4], [2; 4], [4; 4], [5; 4]].concat());
+    assert_eq!(crop(&pixels, (3, 2), [2, 1, 9, 9]), [5; 4]);
+    assert!(crop(&pixels, (3, 2), [3, 0, 1, 1]).is_empty());
+  }
+
+  #[test]
+  fn float_texels_decode_to_linear_values() {
+    assert_eq!(f16_to_f32(0x3c00), 1.0);
+    assert_eq!(f16_to_f32(0xc000), -2.0);
+    assert_eq!(f16_to_f32(0x3555), 0.333_251_95);
+    assert_eq!(f16_to_f32(0x0001), 2f32.powi(-24));
+    assert_eq!(f16_to_f32(0x7c00), f32::INFINITY);
+    assert!(f16_to_f32(0x7e00).is_nan());
+
+    let half: Vec<u8> = [0x3800u16, 0x0000, 0x3c00, 0x3c00].iter().flat_map(|h| h.to_le_bytes()).collect();
+    assert_eq!(decode_linear(wgpu::TextureFormat::Rgba16Float, &half), vec![0.5, 0.0, 1.0, 1.0]);
+    let full: Vec<u8> = [0.25f32, 2.0, 0.0, 1.0].iter().flat_map(|v| v.to_le_bytes()).collect();
+    assert_eq!(decode_linear(wgpu::TextureFormat::Rgba32Float, &full), vec![0.25, 2.0, 0.0, 1.0]);
+    // Out-of-range values clamp when encoded to 8-bit sRGB.
+    assert_eq!(encode_srgb(&[0.25, 2.0, -1.0, 1.0]), vec![137, 255, 0, 255]);
+    assert_eq!(decode_linear(CAPTURE_FORMAT, &[255, 0, 0, 51]), vec![1.0, 0.0, 0.0, 0.2]);
   }
 }
@@ -0,0 +1,229 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Clustered scatter plots.
+//!
+//! A [`ClusteredScatter`] draws every point while few are visible.  Past
+//! its [`threshold`](ClusteredScatter::threshold) of visible points it
+//! merges the points falling into each square of a pixel grid into one
+//! marker at their centroid, sized and colored by how many points it
+//! holds, so millions of points cost a few thousand markers.  Clusters
+//! are recomputed from the current limits on every draw: zooming in
+//! splits them until the individual points show again.  Add one with
+//! [`PlotBackend::add_clustered_scatter`](crate::plotting::PlotBackend::add_clustered_scatter).
+
+use crate::artist::{Artist, DataRect, DrawContext, LegendEntry, Viewport};
+use crate::colormaps::{self, Colormap};
+use glam::{Vec2, Vec4};
+use std::collections::BTreeMap;
+
+/// Points merged into one marker.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cluster {
+  /// Centroid X, in data coordinates.
+  pub x: f64,
+  /// Centroid Y, in data coordinates.
+  pub y: f64,
+  /// Number of points.
+  pub count: usize,
+}
+
+/// Groups the points inside the axes box of `viewport` by the
+/// `cell`-pixel grid square they fall into, row by row from the top
+/// left.  Non-finite points are skipped.
+pub fn cluster(x: &[f64], y: &[f64], viewport: &Viewport, cell: f32) -> Vec<Cluster> {
+  let [left, top, _, _] = viewport.pixels;
+  let cell = cell.max(1.0);
+  let mut cells: BTreeMap<(i64, i64), (f64, f64, usize)> = BTreeMap::new();
+  for (&px, &py) in x.iter().zip(y) {
+    let Some(p) = visible_pixel(viewport, px, py) else { continue };
+    let key = (((p.y - top) / cell) as i64, ((p.x - left) / cell) as i64);
+    let acc = cells.entry(key).or_insert((0.0, 0.0, 0));
+    acc.0 += px;
+    acc.1 += py;
+    acc.2 += 1;
+  }
+  cells
+    .into_values()
+    .map(|(sx, sy, count)| Cluster {
+      x: sx / count as f64,
+      y: sy / count as f64,
+      count,
+    })
+    .collect()
+}
+
+/// Pixel position of a finite data point inside the axes box.
+fn visible_pixel(viewport: &Viewport, x: f64, y: f64) -> Option<Vec2> {
+  if !(x.is_finite() && y.is_finite()) {
+    return None;
+  }
+  let p = viewport.to_pixels(x, y);
+  viewport.contains_pixel(p).then_some(p)
+}
+
+/// Scatter plot that clusters dense regions.
+pub struct ClusteredScatter {
+  x: Vec<f64>,
+  y: Vec<f64>,
+  /// Color of single points.
+  pub color: Vec4,
+  /// Marker radius of single points, in points.
+  pub radius: f32,
+  /// Largest cluster marker radius, in points.
+  pub max_radius: f32,
+  /// Grid square size, in pixels.
+  pub cell: f32,
+  /// Visible points drawn individually before clustering starts.
+  pub threshold: usize,
+  map: Box<dyn Colormap>,
+  label: Option<String>,
+}
+
+impl ClusteredScatter {
+  /// Scatter of the `(x, y)` pairs, truncated to the shorter slice:
+  /// 3pt blue points, clustered on a 12 pixel grid past 5000 visible
+  /// points into markers of up to 12pt colored with viridis.
+  pub fn new(x: &[f64], y: &[f64]) -> Self {
+    let n = x.len().min(y.len());
+    Self {
+      x: x[..n].to_vec(),
+      y: y[..n].to_vec(),
+      color: Vec4::new(0.12, 0.47, 0.71, 1.0),
+      radius: 3.0,
+      max_radius: 12.0,
+      cell: 12.0,
+      threshold: 5000,
+      map: Box::new(colormaps::VIRIDIS),
+      label: None,
+    }
+  }
+
+  /// Sets the color of single points.
+  pub fn color(mut self, color: Vec4) -> Self {
+    self.color = color;
+    self
+  }
+
+  /// Sets the single point radius and the largest cluster radius, in
+  /// points.
+  pub fn radius(mut self, radius: f32, max_radius: f32) -> Self {
+    self.radius = radius;
+    self.max_radius = max_radius;
+    self
+  }
+
+  /// Sets the grid square size, in pixels.
+  pub fn cell(mut self, cell: f32) -> Self {
+    self.cell = cell;
+    self
+  }
+
+  /// Sets how many visible points are drawn before clustering.
+  pub fn threshold(mut self, threshold: usize) -> Self {
+    self.threshold = threshold;
+    self
+  }
+
+  /// Colors clusters with `map`, from two points (low end) to the
+  /// largest cluster in view (high end) on a log scale.
+  pub fn colormap(mut self, map: Box<dyn Colormap>) -> Self {
+    self.map = map;
+    self
+  }
+
+  /// Adds a legend row.
+  pub fn label(mut self, label: &str) -> Self {
+    self.label = Some(label.into());
+    self
+  }
+
+  /// Radius in points and color of a cluster of `count` points, the
+  /// largest in view holding `max_count`.  Area grows with the count.
+  pub fn cluster_style(&self, count: usize, max_count: usize) -> (f32, Vec4) {
+    if count <= 1 {
+      return (self.radius, self.color);
+    }
+    let radius = (self.radius * (count as f32).sqrt()).min(self.max_radius.max(self.radius));
+    let t = if max_count > 2 {
+      ((count as f32).ln() - 2f32.ln()) / ((max_count as f32).ln() - 2f32.ln())
+    } else {
+      0.0
+    };
+    let [r, g, b] = self.map.eval(t);
+    (radius, Vec4::new(r, g, b, 1.0))
+  }
+}
+
+impl Artist for ClusteredScatter {
+  fn draw(&self, ctx: &mut DrawContext<'_>) {
+    let viewport = ctx.viewport();
+    let visible = self
+      .x
+      .iter()
+      .zip(&self.y)
+      .filter(|(&x, &y)| visible_pixel(&viewport, x, y).is_some())
+      .count();
+    if visible <= self.threshold {
+      for (&x, &y) in self.x.iter().zip(&self.y) {
+        if visible_pixel(&viewport, x, y).is_some() {
+          ctx.marker((x, y), self.radius, self.color);
+        }
+      }
+      return;
+    }
+    let clusters = cluster(&self.x, &self.y, &viewport, self.cell);
+    let max_count = clusters.iter().map(|c| c.count).max().unwrap_or(0);
+    for c in &clusters {
+      let (radius, color) = self.cluster_style(c.count, max_count);
+      ctx.marker((c.x, c.y), radius, color);
+    }
+  }
+
+  fn extent(&self) -> Option<DataRect> {
+    DataRect::from_points(&self.x, &self.y)
+  }
+
+  fn legend(&self) -> Option<LegendEntry> {
+    let label = self.label.clone()?;
+    Some(LegendEntry { label, color: self.color })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn viewport() -> Viewport {
+    // 100 x 100 pixels showing 0..10 on both axes.
+    Viewport::new(DataRect::new((0.0, 10.0), (0.0, 10.0)), [0.0, 0.0, 100.0, 100.0])
+  }
+
+  #[test]
+  fn points_in_a_cell_merge_at_their_centroid() {
+    let x = [1.2, 1.6, 8.0, 20.0, f64::NAN];
+    let y = [8.8, 8.4, 2.0, 5.0, 5.0];
+    let clusters = cluster(&x, &y, &viewport(), 10.0);
+    assert_eq!(clusters.len(), 2);
+    assert_eq!(clusters[0].count, 2);
+    assert!((clusters[0].x - 1.4).abs() < 1e-9 && (clusters[0].y - 8.6).abs() < 1e-9);
+    assert_eq!(clusters[1], Cluster { x: 8.0, y: 2.0, count: 1 });
+
+    // A finer grid splits the pair.
+    assert_eq!(cluster(&x, &y, &viewport(), 2.0).len(), 3);
+  }
+
+  #[test]
+  fn clusters_grow_with_their_count() {
+    let scatter = ClusteredScatter::new(&[], &[]).radius(2.0, 10.0);
+    assert_eq!(scatter.cluster_style(1, 100), (2.0, scatter.color));
+    let (small, low) = scatter.cluster_style(2, 100);
+    let (big, high) = scatter.cluster_style(100, 100);
+    assert_eq!(small, 2.0 * 2f32.sqrt());
+    assert_eq!(big, 10.0);
+    let [r, g, b] = colormaps::VIRIDIS.eval(0.0);
+    assert_eq!(low, Vec4::new(r, g, b, 1.0));
+    let [r, g, b] = colormaps::VIRIDIS.eval(1.0);
+    assert_eq!(high, Vec4::new(r, g, b, 1.0));
+  }
+}
@@ -0,0 +1,155 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Colorbars.
+//!
+//! A [`Colorbar`] draws a colormap as a vertical bar to the right of
+//! the axes box, with ticks and labels in data units.  Under a
+//! logarithmic [`Norm`] (such as [`LogNorm`](crate::colormaps::LogNorm))
+//! the bar gets a major tick per decade, labelled `10ⁿ`, and unlabelled
+//! minor ticks at the 2..9 multiples; otherwise it gets round linear
+//! ticks.  Add one with
+//! [`PlotBackend::colorbar`](crate::plotting::PlotBackend::colorbar).
+
+use crate::artist::{Artist, DataRect, DrawContext};
+use crate::colormaps::{Colormap, Norm};
+use crate::ticks::{self, Auto, Scientific, TickFormatter};
+use glam::{Vec2, Vec4};
+
+/// Gradient slices drawn along the bar.
+const GRADIENT_STEPS: usize = 64;
+/// Major and minor tick lengths, in pixels.
+const MAJOR_TICK: f32 = 5.0;
+const MINOR_TICK: f32 = 3.0;
+/// Tick label font size, in pixels.
+const LABEL_SIZE: f32 = 10.0;
+
+/// Ticks along a colorbar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorbarTicks {
+  /// Labelled tick values.
+  pub major: Vec<f64>,
+  /// Unlabelled tick values.
+  pub minor: Vec<f64>,
+  /// Labels of the major ticks.
+  pub labels: Vec<String>,
+}
+
+/// Ticks for the value range of `norm`: decades and 2..9 minor ticks
+/// with scientific labels for logarithmic norms, round linear ticks
+/// otherwise.
+pub fn colorbar_ticks(norm: &dyn Norm, max_ticks: usize) -> ColorbarTicks {
+  let (lo, hi) = norm.range();
+  if norm.is_logarithmic() {
+    let (major, minor) = ticks::log_ticks(lo, hi, max_ticks);
+    let fmt = Scientific { decimals: 1 };
+    let labels = major.iter().map(|&v| fmt.format(v, 0.0)).collect();
+    ColorbarTicks { major, minor, labels }
+  } else {
+    let (major, step) = ticks::nice_ticks(lo, hi, max_ticks);
+    let labels = ticks::labels(&major, step, &Auto);
+    ColorbarTicks {
+      major,
+      minor: Vec::new(),
+      labels,
+    }
+  }
+}
+
+/// A colormap scale beside the axes.
+pub struct Colorbar {
+  map: Box<dyn Colormap>,
+  norm: Box<dyn Norm>,
+  /// Bar width, in pixels.
+  pub width: f32,
+  /// Gap between the axes box and the bar, in pixels.
+  pub pad: f32,
+  /// Maximum number of major ticks.
+  pub max_ticks: usize,
+}
+
+impl Colorbar {
+  /// Colorbar for `map` over the value range of `norm`.
+  pub fn new(map: Box<dyn Colormap>, norm: Box<dyn Norm>) -> Self {
+    Self {
+      map,
+      norm,
+      width: 15.0,
+      pad: 10.0,
+      max_ticks: ticks::DEFAULT_MAX_TICKS,
+    }
+  }
+
+  /// Ticks drawn along the bar.
+  pub fn ticks(&self) -> ColorbarTicks {
+    colorbar_ticks(self.norm.as_ref(), self.max_ticks)
+  }
+}
+
+impl Artist for Colorbar {
+  fn draw(&self, ctx: &mut DrawContext<'_>) {
+    let [_, top, right, bottom] = ctx.pixel_box();
+    let (x, h) = (right + self.pad, bottom - top);
+    let slice = h / GRADIENT_STEPS as f32;
+    for i in 0..GRADIENT_STEPS {
+      let t = (i as f32 + 0.5) / GRADIENT_STEPS as f32;
+      let [r, g, b] = self.map.eval(t);
+      // Slices run bottom (0) to top (1), overlapping by a pixel so no
+      // seams show.
+      let y = bottom - slice * (i + 1) as f32;
+      ctx.rect_pixels(Vec2::new(x, y), Vec2::new(self.width, slice + 1.0), Vec4::new(r, g, b, 1.0), 0.0);
+    }
+    let black = Vec4::new(0.0, 0.0, 0.0, 1.0);
+    ctx.rect_pixels(Vec2::new(x, top), Vec2::new(self.width, h), black, 0.75);
+
+    let edge = x + self.width;
+    let ticks = self.ticks();
+    let y_of = |v: f64| {
+      let t = self.norm.apply(v);
+      t.is_finite().then_some(bottom - h * t as f32)
+    };
+    for &v in &ticks.minor {
+      if let Some(y) = y_of(v) {
+        let (a, b) = (ctx.screen(Vec2::new(edge, y)), ctx.screen(Vec2::new(edge + MINOR_TICK, y)));
+        ctx.line_pixels(a, b, 0.5, black);
+      }
+    }
+    for (&v, label) in ticks.major.iter().zip(&ticks.labels) {
+      if let Some(y) = y_of(v) {
+        let (a, b) = (ctx.screen(Vec2::new(edge, y)), ctx.screen(Vec2::new(edge + MAJOR_TICK, y)));
+        ctx.line_pixels(a, b, 0.75, black);
+        let pos = Vec2::new(edge + MAJOR_TICK + 3.0, y - LABEL_SIZE * 0.5);
+        ctx.text_pixels(pos, label, LABEL_SIZE, black);
+      }
+    }
+  }
+
+  fn extent(&self) -> Option<DataRect> {
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::colormaps::{LogNorm, Normalize, GREYS};
+
+  #[test]
+  fn log_norm_gets_decades() {
+    let t = colorbar_ticks(&LogNorm::new(1.0, 1e4), 8);
+    assert_eq!(t.major, vec![1.0, 10.0, 100.0, 1e3, 1e4]);
+    assert_eq!(t.labels, vec!["10\u{2070}", "10\u{b9}", "10\u{b2}", "10\u{b3}", "10\u{2074}"]);
+    assert_eq!(t.minor.len(), 8 * 4);
+  }
+
+  #[test]
+  fn linear_norm_gets_round_ticks() {
+    let t = colorbar_ticks(&Normalize::new(0.0, 1.0), 6);
+    assert_eq!(t.major.first(), Some(&0.0));
+    assert_eq!(t.major.last(), Some(&1.0));
+    assert_eq!(t.labels.len(), t.major.len());
+    assert!(t.minor.is_empty());
+    let bar = Colorbar::new(Box::new(GREYS), Box::new(Normalize::new(0.0, 1.0)));
+    assert!(bar.extent().is_none());
+  }
+}
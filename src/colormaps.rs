@@ -0,0 +1,366 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Colormaps.
+//!
+//! A [`Colormap`] maps a scalar in `[0, 1]` to an RGB color.  The
+//! built-in maps ([`VIRIDIS`], [`PLASMA`], [`INFERNO`], [`MAGMA`],
+//! [`TURBO`], [`COOLWARM`], [`JET`], [`GREYS`]) are [`Listed`] maps:
+//! evenly spaced control points sampled from the reference maps, with
+//! linear interpolation in between.  [`Colormap::reversed`] flips any
+//! map, and [`Colormap::lut`] bakes it into a lookup table for upload.
+//! A [`Norm`] maps data values into the `[0, 1]` colormap domain:
+//! [`Normalize`] linearly, [`LogNorm`] by decade.
+
+/// Maps normalized scalars to colors.
+pub trait Colormap: Send + Sync {
+  /// Color at `t`, clamped to `[0, 1]`.  NaN maps to the low end.
+  fn eval(&self, t: f32) -> [f32; 3];
+
+  /// Map name, e.g. `"viridis"`.
+  fn name(&self) -> String;
+
+  /// `n` evenly spaced samples from the low to the high end.
+  fn lut(&self, n: usize) -> Vec<[f32; 3]> {
+    match n {
+      0 => Vec::new(),
+      1 => vec![self.eval(0.0)],
+      _ => (0..n)
+        .map(|i| self.eval(i as f32 / (n - 1) as f32))
+        .collect(),
+    }
+  }
+
+  /// The same map running from high to low.
+  fn reversed(self) -> Reversed<Self>
+  where
+    Self: Sized,
+  {
+    Reversed(self)
+  }
+}
+
+/// Piecewise-linear map through evenly spaced `0xRRGGBB` stops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Listed {
+  /// Map name.
+  pub name: &'static str,
+  /// Control points, low end first.  Needs at least one entry.
+  pub stops: &'static [u32],
+}
+
+impl Colormap for Listed {
+  fn eval(&self, t: f32) -> [f32; 3] {
+    let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+    let last = self.stops.len() - 1;
+    let x = t * last as f32;
+    let i = (x.floor() as usize).min(last.saturating_sub(1));
+    let f = x - i as f32;
+    let a = rgb(self.stops[i]);
+    let b = rgb(self.stops[(i + 1).min(last)]);
+    [
+      a[0] + (b[0] - a[0]) * f,
+      a[1] + (b[1] - a[1]) * f,
+      a[2] + (b[2] - a[2]) * f,
+    ]
+  }
+
+  fn name(&self) -> String {
+    self.name.to_string()
+  }
+}
+
+/// A colormap evaluated back to front; named `<name>_r`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reversed<C>(pub C);
+
+impl<C: Colormap> Colormap for Reversed<C> {
+  fn eval(&self, t: f32) -> [f32; 3] {
+    let t = if t.is_nan() { 0.0 } else { t };
+    self.0.eval(1.0 - t)
+  }
+
+  fn name(&self) -> String {
+    format!("{}_r", self.0.name())
+  }
+}
+
+/// Perceptually uniform blue-green-yellow.
+pub const VIRIDIS: Listed = Listed {
+  name: "viridis",
+  stops: &[
+    0x440154, 0x482475, 0x414487, 0x355f8d, 0x2a788e, 0x21918c, 0x22a884,
+    0x44bf70, 0x7ad151, 0xbddf26, 0xfde725,
+  ],
+};
+
+/// Perceptually uniform blue-purple-yellow.
+pub const PLASMA: Listed = Listed {
+  name: "plasma",
+  stops: &[
+    0x0d0887, 0x41049d, 0x6a00a8, 0x8f0da4, 0xb12a90, 0xcc4778, 0xe16462,
+    0xf2844b, 0xfca636, 0xfcce25, 0xf0f921,
+  ],
+};
+
+/// Perceptually uniform black-red-yellow.
+pub const INFERNO: Listed = Listed {
+  name: "inferno",
+  stops: &[
+    0x000004, 0x160b39, 0x420a68, 0x6a176e, 0x932667, 0xbc3754, 0xdd513a,
+    0xf37819, 0xfca50a, 0xf6d746, 0xfcffa4,
+  ],
+};
+
+/// Perceptually uniform black-purple-cream.
+pub const MAGMA: Listed = Listed {
+  name: "magma",
+  stops: &[
+    0x000004, 0x140e36, 0x3b0f70, 0x641a80, 0x8c2981, 0xb73779, 0xde4968,
+    0xf7705c, 0xfe9f6d, 0xfecf92, 0xfcfdbf,
+  ],
+};
+
+/// Improved rainbow map.
+pub const TURBO: Listed = Listed {
+  name: "turbo",
+  stops: &[
+    0x30123b, 0x4662d7, 0x36aaf9, 0x1ae4b6, 0x72fe5e, 0xc7ef34, 0xfaba39,
+    0xf66b19, 0xcb2a04, 0x7a0403,
+  ],
+};
+
+/// Diverging blue-grey-red.
+pub const COOLWARM: Listed = Listed {
+  name: "coolwarm",
+  stops: &[0x3b4cc0, 0x8db0fe, 0xdddddd, 0xf49a7b, 0xb40426],
+};
+
+/// Classic rainbow map.
+pub const JET: Listed = Listed {
+  name: "jet",
+  stops: &[
+    0x00007f, 0x0000ff, 0x007fff, 0x00ffff, 0x7fff7f, 0xffff00, 0xff7f00,
+    0xff0000, 0x7f0000,
+  ],
+};
+
+/// White to black.
+pub const GREYS: Listed = Listed {
+  name: "greys",
+  stops: &[0xffffff, 0x000000],
+};
+
+/// Mapping of data values onto the `[0, 1]` colormap domain.
+pub trait Norm: Send + Sync {
+  /// Maps `value` into `[0, 1]`; NaN marks values that get no color.
+  fn apply(&self, value: f64) -> f64;
+
+  /// Values mapped to 0 and 1.
+  fn range(&self) -> (f64, f64);
+
+  /// Whether the mapping is logarithmic, so colorbars use decade ticks.
+  fn is_logarithmic(&self) -> bool {
+    false
+  }
+}
+
+/// Linear mapping of data values onto `[0, 1]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Normalize {
+  /// Value mapped to 0.
+  pub vmin: f64,
+  /// Value mapped to 1.
+  pub vmax: f64,
+}
+
+impl Normalize {
+  /// Normalization from `vmin` to `vmax`.
+  pub fn new(vmin: f64, vmax: f64) -> Self {
+    Self { vmin, vmax }
+  }
+
+  /// Normalization spanning the finite `values`, or `None` if there
+  /// are none.
+  pub fn autoscale(values: &[f64]) -> Option<Self> {
+    values
+      .iter()
+      .filter(|v| v.is_finite())
+      .fold(None, |acc, &v| match acc {
+        None => Some(Self::new(v, v)),
+        Some(n) => Some(Self::new(n.vmin.min(v), n.vmax.max(v))),
+      })
+  }
+
+  /// Maps `value` into `[0, 1]`, clamping values outside the range.
+  /// A degenerate range maps everything to 0.5; NaN stays NaN.
+  pub fn apply(&self, value: f64) -> f64 {
+    if value.is_nan() {
+      return f64::NAN;
+    }
+    let span = self.vmax - self.vmin;
+    if span == 0.0 || !span.is_finite() {
+      return 0.5;
+    }
+    ((value - self.vmin) / span).clamp(0.0, 1.0)
+  }
+}
+
+impl Norm for Normalize {
+  fn apply(&self, value: f64) -> f64 {
+    Normalize::apply(self, value)
+  }
+
+  fn range(&self) -> (f64, f64) {
+    (self.vmin, self.vmax)
+  }
+}
+
+/// Logarithmic mapping of positive data values onto `[0, 1]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogNorm {
+  /// Value mapped to 0; must be positive.
+  pub vmin: f64,
+  /// Value mapped to 1.
+  pub vmax: f64,
+}
+
+impl LogNorm {
+  /// Normalization from `vmin` to `vmax`.
+  pub fn new(vmin: f64, vmax: f64) -> Self {
+    Self { vmin, vmax }
+  }
+
+  /// Normalization spanning the finite positive `values`, or `None` if
+  /// there are none.
+  pub fn autoscale(values: &[f64]) -> Option<Self> {
+    let positive: Vec<f64> = values.iter().copied().filter(|&v| v > 0.0).collect();
+    Normalize::autoscale(&positive).map(|n| Self::new(n.vmin, n.vmax))
+  }
+
+  /// Maps `value` into `[0, 1]` by its decade, clamping values outside
+  /// the range.  Non-positive values and NaN map to NaN; a degenerate
+  /// range maps everything else to 0.5.
+  pub fn apply(&self, value: f64) -> f64 {
+    if value.is_nan() || value <= 0.0 {
+      return f64::NAN;
+    }
+    Normalize::new(self.vmin.log10(), self.vmax.log10()).apply(value.log10())
+  }
+}
+
+impl Norm for LogNorm {
+  fn apply(&self, value: f64) -> f64 {
+    LogNorm::apply(self, value)
+  }
+
+  fn range(&self) -> (f64, f64) {
+    (self.vmin, self.vmax)
+  }
+
+  fn is_logarithmic(&self) -> bool {
+    true
+  }
+}
+
+/// All built-in maps.
+pub const BUILTIN: [Listed; 8] =
+  [VIRIDIS, PLASMA, INFERNO, MAGMA, TURBO, COOLWARM, JET, GREYS];
+
+/// Looks up a built-in map by name; a `_r` suffix selects the reversed
+/// map.  Names are case-insensitive.
+pub fn by_name(name: &str) -> Option<Box<dyn Colormap>> {
+  let lower = name.to_ascii_lowercase();
+  let (base, reversed) = match lower.strip_suffix("_r") {
+    Some(base) => (base, true),
+    None => (lower.as_str(), false),
+  };
+  let map = BUILTIN.iter().copied().find(|m| m.name == base)?;
+  Some(if reversed {
+    Box::new(map.reversed())
+  } else {
+    Box::new(map)
+  })
+}
+
+/// Splits `0xRRGGBB` into channels in `[0, 1]`.
+fn rgb(hex: u32) -> [f32; 3] {
+  [
+    ((hex >> 16) & 0xff) as f32 / 255.0,
+    ((hex >> 8) & 0xff) as f32 / 255.0,
+    (hex & 0xff) as f32 / 255.0,
+  ]
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn close(a: [f32; 3], b: [f32; 3]) -> bool {
+    a.iter().zip(&b).all(|(x, y)| (x - y).abs() < 1e-5)
+  }
+
+  #[test]
+  fn endpoints_and_interpolation() {
+    assert!(close(GREYS.eval(0.0), [1.0, 1.0, 1.0]));
+    assert!(close(GREYS.eval(1.0), [0.0, 0.0, 0.0]));
+    assert!(close(GREYS.eval(0.25), [0.75, 0.75, 0.75]));
+    assert!(close(VIRIDIS.eval(-3.0), rgb(0x440154)));
+    assert!(close(VIRIDIS.eval(f32::NAN), rgb(0x440154)));
+    assert!(close(VIRIDIS.eval(7.0), rgb(0xfde725)));
+    assert!(close(VIRIDIS.eval(0.5), rgb(0x21918c)));
+  }
+
+  #[test]
+  fn reversed_flips_and_renames() {
+    let r = MAGMA.reversed();
+    assert_eq!(r.name(), "magma_r");
+    assert!(close(r.eval(0.0), MAGMA.eval(1.0)));
+    assert!(close(r.eval(0.3), MAGMA.eval(0.7)));
+  }
+
+  #[test]
+  fn lut_samples_evenly() {
+    let lut = JET.lut(9);
+    assert_eq!(lut.len(), 9);
+    for (i, c) in lut.iter().enumerate() {
+      assert!(close(*c, rgb(JET.stops[i])));
+    }
+    assert!(GREYS.lut(0).is_empty());
+    assert_eq!(GREYS.lut(1).len(), 1);
+  }
+
+  #[test]
+  fn normalize_clamps_and_autoscales() {
+    let n = Normalize::autoscale(&[3.0, f64::NAN, -1.0, 7.0]).unwrap();
+    assert_eq!(n, Normalize::new(-1.0, 7.0));
+    assert_eq!(n.apply(3.0), 0.5);
+    assert_eq!(n.apply(-10.0), 0.0);
+    assert_eq!(n.apply(70.0), 1.0);
+    assert!(n.apply(f64::NAN).is_nan());
+    assert_eq!(Normalize::new(2.0, 2.0).apply(2.0), 0.5);
+    assert!(Normalize::autoscale(&[f64::NAN]).is_none());
+  }
+
+  #[test]
+  fn log_norm_maps_decades() {
+    let n = LogNorm::new(1.0, 1000.0);
+    assert!((n.apply(10.0) - 1.0 / 3.0).abs() < 1e-12);
+    assert!((n.apply(100.0) - 2.0 / 3.0).abs() < 1e-12);
+    assert_eq!(n.apply(1e6), 1.0);
+    assert_eq!(n.apply(0.5), 0.0);
+    assert!(n.apply(0.0).is_nan() && n.apply(-1.0).is_nan());
+    assert_eq!(LogNorm::autoscale(&[-5.0, 0.0, 2.0, 50.0]), Some(LogNorm::new(2.0, 50.0)));
+    assert!(LogNorm::autoscale(&[0.0, -1.0]).is_none());
+  }
+
+  #[test]
+  fn lookup_by_name() {
+    assert_eq!(by_name("Turbo").unwrap().name(), "turbo");
+    assert_eq!(by_name("coolwarm_r").unwrap().name(), "coolwarm_r");
+    assert!(by_name("rainbow").is_none());
+    for m in BUILTIN {
+      assert!(m.stops.len() >= 2);
+    }
+  }
+}
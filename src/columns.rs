@@ -0,0 +1,117 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Plot data from columnar sources.
+//!
+//! Data engineering pipelines hand over Arrow arrays or Polars data
+//! frames: nullable, often split into chunks, and typed as integers or
+//! timestamps as much as floats.  The helpers here flatten such a
+//! column into the `f64` slice the plotting calls take.  Nulls become
+//! NaN, which breaks the line instead of drawing a spike to zero, and
+//! timestamps become Unix seconds as used by
+//! [`temporal`](crate::temporal).
+//!
+//! The Arrow integration is behind the `arrow` feature
+//! ([`Axes::plot_arrow`](crate::plotting::Axes::plot_arrow)), the
+//! Polars one behind `polars`
+//! ([`Axes::plot_df`](crate::plotting::Axes::plot_df)).
+
+/// Values with nulls replaced by NaN.
+pub fn null_masked<I: IntoIterator<Item = Option<f64>>>(values: I) -> Vec<f64> {
+  values.into_iter().map(|v| v.unwrap_or(f64::NAN)).collect()
+}
+
+/// Seconds per tick of a timestamp stored in units of `10^-exponent`
+/// seconds (0 for seconds, 3 for milliseconds, ...).
+pub fn seconds_per_tick(exponent: i32) -> f64 {
+  10f64.powi(-exponent)
+}
+
+/// Arrow arrays flattened to `f64`.
+#[cfg(feature = "arrow")]
+pub mod arrow {
+  use super::{null_masked, seconds_per_tick};
+  use crate::error::{Error, Result};
+  use arrow_array::cast::AsArray;
+  use arrow_array::types::*;
+  use arrow_array::{Array, ArrayRef};
+  use arrow_schema::DataType;
+
+  /// Values of one array: floats and integers as is, timestamps in
+  /// Unix seconds, nulls as NaN.
+  pub fn values(array: &dyn Array) -> Result<Vec<f64>> {
+    macro_rules! numeric {
+      ($t:ty) => {
+        null_masked(array.as_primitive::<$t>().iter().map(|v| v.map(|v| v as f64)))
+      };
+      ($t:ty, $exp:expr) => {
+        null_masked(array.as_primitive::<$t>().iter().map(|v| v.map(|v| v as f64 * seconds_per_tick($exp))))
+      };
+    }
+    use arrow_schema::TimeUnit::*;
+    Ok(match array.data_type() {
+      DataType::Float64 => numeric!(Float64Type),
+      DataType::Float32 => numeric!(Float32Type),
+      DataType::Int64 => numeric!(Int64Type),
+      DataType::Int32 => numeric!(Int32Type),
+      DataType::Int16 => numeric!(Int16Type),
+      DataType::Int8 => numeric!(Int8Type),
+      DataType::UInt64 => numeric!(UInt64Type),
+      DataType::UInt32 => numeric!(UInt32Type),
+      DataType::UInt16 => numeric!(UInt16Type),
+      DataType::UInt8 => numeric!(UInt8Type),
+      DataType::Timestamp(Second, _) => numeric!(TimestampSecondType, 0),
+      DataType::Timestamp(Millisecond, _) => numeric!(TimestampMillisecondType, 3),
+      DataType::Timestamp(Microsecond, _) => numeric!(TimestampMicrosecondType, 6),
+      DataType::Timestamp(Nanosecond, _) => numeric!(TimestampNanosecondType, 9),
+      other => return Err(Error::UnsupportedColumn(other.to_string())),
+    })
+  }
+
+  /// Values of a chunked column, chunks concatenated in order.
+  pub fn chunked_values(chunks: &[ArrayRef]) -> Result<Vec<f64>> {
+    let mut out = Vec::with_capacity(chunks.iter().map(|c| c.len()).sum());
+    for chunk in chunks {
+      out.extend(values(chunk.as_ref())?);
+    }
+    Ok(out)
+  }
+}
+
+/// Polars columns flattened to `f64`.
+#[cfg(feature = "polars")]
+pub mod polars {
+  use super::{null_masked, seconds_per_tick};
+  use crate::error::{Error, Result};
+  use ::polars::prelude::{DataFrame, DataType, TimeUnit};
+
+  /// Values of column `name` of `df`: numbers cast to `f64`, datetimes
+  /// in Unix seconds, nulls as NaN.  All chunks are read.
+  pub fn values(df: &DataFrame, name: &str) -> Result<Vec<f64>> {
+    let column = df.column(name).map_err(|_| Error::UnknownData(name.into()))?;
+    let scale = match column.dtype() {
+      DataType::Datetime(TimeUnit::Milliseconds, _) => seconds_per_tick(3),
+      DataType::Datetime(TimeUnit::Microseconds, _) => seconds_per_tick(6),
+      DataType::Datetime(TimeUnit::Nanoseconds, _) => seconds_per_tick(9),
+      _ => 1.0,
+    };
+    let unsupported = || Error::UnsupportedColumn(format!("{}: {}", name, column.dtype()));
+    let cast = column.cast(&DataType::Float64).map_err(|_| unsupported())?;
+    let floats = cast.f64().map_err(|_| unsupported())?;
+    Ok(null_masked(floats.into_iter().map(|v| v.map(|v| v * scale))))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn nulls_become_gaps() {
+    let v = null_masked([Some(1.0), None, Some(3.0)]);
+    assert_eq!(v[0], 1.0);
+    assert!(v[1].is_nan());
+    assert_eq!(v[2], 3.0);
+    assert!((1_700_000_000_123.0 * seconds_per_tick(3) - 1_700_000_000.123).abs() < 1e-6);
+  }
+}
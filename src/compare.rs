@@ -4,10 +4,17 @@
 //! Pixel-level image comparison utilities.
 //!
 //! Used by both the automated visual regression tests and the
-//! interactive review GUI.
+//! interactive review GUI, which also uses the inspection helpers
+//! ([`pixel_at`], [`wipe`], [`side_by_side`], [`magnify`]) to look at
+//! live and golden images together.  [`compare_sequences`] extends
+//! the comparison to the frames of an animation.
 
 /// Per-channel soft threshold for "different enough to count".
 const SOFT_THRESHOLD: u8 = 5;
+/// Largest tile width of a sequence contact sheet, in pixels.
+const SHEET_TILE_WIDTH: u32 = 160;
+/// Gap between contact sheet tiles, in pixels.
+const SHEET_GAP: u32 = 2;
 
 /// Result of comparing two RGBA images pixel-by-pixel.
 pub struct CompareResult {
@@ -71,6 +78,138 @@ pub fn compare_images(
   }
 }
 
+/// Result of comparing two frame sequences.
+pub struct SequenceResult {
+  /// Comparison of each frame present in both sequences, in order.
+  pub frames: Vec<CompareResult>,
+  /// Number of actual frames.
+  pub actual_frames: usize,
+  /// Number of expected frames.
+  pub expected_frames: usize,
+  /// Root mean square error across all channels of all compared
+  /// frames.
+  pub rmse: f64,
+  /// Maximum channel difference over all compared frames.
+  pub max_diff: u8,
+  /// Fraction of pixels of all compared frames exceeding the soft
+  /// threshold (percent).
+  pub diff_pct: f64,
+  /// Index of the frame with the largest RMSE.
+  pub worst_frame: Option<usize>,
+  /// Diff heatmaps of every frame in a grid (see
+  /// [`compare_sequences`]).
+  pub contact_sheet: Vec<u8>,
+  /// Width and height of [`contact_sheet`](Self::contact_sheet).
+  pub sheet_size: (u32, u32),
+}
+
+impl SequenceResult {
+  /// Whether both sequences have the same number of frames.
+  pub fn same_length(&self) -> bool {
+    self.actual_frames == self.expected_frames
+  }
+}
+
+/// Compares two sequences of `width` x `height` RGBA frames, such as
+/// those written by
+/// [`PlotCapture::record`](crate::capture::PlotCapture::record), frame
+/// by frame.
+///
+/// Frames present in both sequences are compared with
+/// [`compare_images`] and pooled into aggregate metrics.  The contact
+/// sheet shows each frame's [`diff_pixels`] heatmap, shrunk to at
+/// most 160 pixels wide keeping the largest difference of each block
+/// so small changes stay visible, in a near-square grid on a gray
+/// background.  Frames missing from either sequence are black tiles.
+pub fn compare_sequences<A, E>(
+  actual: &[A],
+  expected: &[E],
+  width: u32,
+  height: u32,
+) -> SequenceResult
+where
+  A: AsRef<[u8]>,
+  E: AsRef<[u8]>,
+{
+  let pairs = actual.iter().zip(expected);
+  let frames: Vec<CompareResult> = pairs
+    .clone()
+    .map(|(a, e)| compare_images(a.as_ref(), e.as_ref(), width, height))
+    .collect();
+
+  let compared = frames.len() as f64;
+  let pixel_count = (width * height) as f64 * compared;
+  let mean_sq =
+    frames.iter().map(|f| f.rmse * f.rmse).sum::<f64>() / compared;
+  let diff_count: usize = frames.iter().map(|f| f.diff_count).sum();
+  let worst_frame = (0..frames.len())
+    .max_by(|&i, &j| frames[i].rmse.total_cmp(&frames[j].rmse));
+
+  // Contact sheet of shrunk diff heatmaps.
+  let count = actual.len().max(expected.len()) as u32;
+  let factor = width.div_ceil(SHEET_TILE_WIDTH).max(1);
+  let (tile_w, tile_h) =
+    (width.div_ceil(factor), height.div_ceil(factor));
+  let columns = (count as f64).sqrt().ceil().max(1.0) as u32;
+  let rows = count.div_ceil(columns);
+  let sheet_w = columns * (tile_w + SHEET_GAP) + SHEET_GAP;
+  let sheet_h = rows * (tile_h + SHEET_GAP) + SHEET_GAP;
+  let mut sheet = vec![128u8; (sheet_w * sheet_h) as usize * 4];
+  let diffs = pairs.map(|(a, e)| diff_pixels(a.as_ref(), e.as_ref()));
+  let tiles = diffs.map(Some).chain(std::iter::repeat(None));
+  for (i, diff) in tiles.take(count as usize).enumerate() {
+    let i = i as u32;
+    let x0 = SHEET_GAP + (i % columns) * (tile_w + SHEET_GAP);
+    let y0 = SHEET_GAP + (i / columns) * (tile_h + SHEET_GAP);
+    for ty in 0..tile_h {
+      for tx in 0..tile_w {
+        let p = match &diff {
+          Some(d) => max_pool(d, width, height, tx, ty, factor),
+          None => [0, 0, 0, 255],
+        };
+        let dst = (((y0 + ty) * sheet_w + x0 + tx) * 4) as usize;
+        sheet[dst..dst + 4].copy_from_slice(&p);
+      }
+    }
+  }
+
+  SequenceResult {
+    actual_frames: actual.len(),
+    expected_frames: expected.len(),
+    rmse: if frames.is_empty() { 0.0 } else { mean_sq.sqrt() },
+    max_diff: frames.iter().map(|f| f.max_diff).max().unwrap_or(0),
+    diff_pct: if frames.is_empty() {
+      0.0
+    } else {
+      diff_count as f64 / pixel_count * 100.0
+    },
+    worst_frame,
+    frames,
+    contact_sheet: sheet,
+    sheet_size: (sheet_w, sheet_h),
+  }
+}
+
+/// The most different pixel of the `factor` x `factor` block of a
+/// [`diff_pixels`] heatmap at tile position (`tx`, `ty`): red if any
+/// pixel differs, green otherwise.
+fn max_pool(
+  diff: &[u8],
+  width: u32,
+  height: u32,
+  tx: u32,
+  ty: u32,
+  factor: u32,
+) -> [u8; 4] {
+  let mut red = 0;
+  for y in ty * factor..((ty + 1) * factor).min(height) {
+    for x in tx * factor..((tx + 1) * factor).min(width) {
+      red = red.max(diff[((y * width + x) * 4) as usize]);
+    }
+  }
+  if red > 0 { [red, 0, 0, 255] } else { [0, 128, 0, 255] }
+}
+
 /// Generates a diff heatmap as RGBA pixels.
 ///
 /// Green = match (within soft threshold), red = differs (amplified
@@ -104,6 +243,97 @@ pub fn diff_pixels(actual: &[u8], expected: &[u8]) -> Vec<u8> {
   out
 }
 
+/// RGBA value of pixel (`x`, `y`) in a `width`-wide buffer, or
+/// `None` outside the buffer.
+pub fn pixel_at(
+  pixels: &[u8],
+  width: u32,
+  x: u32,
+  y: u32,
+) -> Option<[u8; 4]> {
+  if x >= width {
+    return None;
+  }
+  let off = (y as usize * width as usize + x as usize) * 4;
+  let p = pixels.get(off..off + 4)?;
+  Some([p[0], p[1], p[2], p[3]])
+}
+
+/// Wipe composite of two same-sized RGBA images: columns left of
+/// `split` come from `left`, the rest from `right`.
+pub fn wipe(
+  left: &[u8],
+  right: &[u8],
+  width: u32,
+  split: u32,
+) -> Vec<u8> {
+  assert_eq!(left.len(), right.len());
+  let stride = width as usize * 4;
+  let cut = split.min(width) as usize * 4;
+  let mut out = right.to_vec();
+  let rows = out.chunks_exact_mut(stride).zip(left.chunks_exact(stride));
+  for (dst, src) in rows {
+    dst[..cut].copy_from_slice(&src[..cut]);
+  }
+  out
+}
+
+/// Both images at half size next to each other, `left` in the left
+/// half and `right` in the right, vertically centred on a gray
+/// background.  Downsampling keeps every other pixel so single-pixel
+/// lines may vanish; use [`magnify`] for detail.
+pub fn side_by_side(
+  left: &[u8],
+  right: &[u8],
+  width: u32,
+  height: u32,
+) -> Vec<u8> {
+  let (w, h) = (width as usize, height as usize);
+  let mut out = vec![128u8; w * h * 4];
+  let (half_w, half_h) = (w / 2, h / 2);
+  let top = (h - half_h) / 2;
+  for (img, x0) in [(left, 0), (right, half_w)] {
+    for y in 0..half_h {
+      for x in 0..half_w {
+        let src = ((y * 2) * w + x * 2) * 4;
+        let dst = ((top + y) * w + x0 + x) * 4;
+        out[dst..dst + 4].copy_from_slice(&img[src..src + 4]);
+      }
+    }
+  }
+  out
+}
+
+/// Square loupe of `size` x `size` output pixels showing the
+/// neighbourhood of (`cx`, `cy`) enlarged `factor` times.  Pixels
+/// outside the image are transparent black.
+pub fn magnify(
+  pixels: &[u8],
+  width: u32,
+  cx: u32,
+  cy: u32,
+  size: u32,
+  factor: u32,
+) -> Vec<u8> {
+  let factor = factor.max(1);
+  let radius = (size / factor / 2) as i64;
+  let mut out = vec![0u8; (size * size) as usize * 4];
+  for y in 0..size {
+    for x in 0..size {
+      let sx = cx as i64 - radius + (x / factor) as i64;
+      let sy = cy as i64 - radius + (y / factor) as i64;
+      if sx < 0 || sy < 0 {
+        continue;
+      }
+      if let Some(p) = pixel_at(pixels, width, sx as u32, sy as u32) {
+        let dst = ((y * size + x) * 4) as usize;
+        out[dst..dst + 4].copy_from_slice(&p);
+      }
+    }
+  }
+  out
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -222,4 +452,64 @@ mod tests {
     // and diff_count cannot exceed total pixels.
     assert!(result.diff_count <= (w * h) as usize);
   }
+
+  #[test]
+  fn inspection_helpers() {
+    let a = solid(4, 2, 10, 0, 0, 255);
+    let b = solid(4, 2, 0, 20, 0, 255);
+    assert_eq!(pixel_at(&a, 4, 3, 1), Some([10, 0, 0, 255]));
+    assert_eq!(pixel_at(&a, 4, 4, 0), None);
+    assert_eq!(pixel_at(&a, 4, 0, 2), None);
+
+    let w = wipe(&a, &b, 4, 1);
+    assert_eq!(pixel_at(&w, 4, 0, 1), Some([10, 0, 0, 255]));
+    assert_eq!(pixel_at(&w, 4, 1, 1), Some([0, 20, 0, 255]));
+
+    let s = side_by_side(&a, &b, 4, 2);
+    assert_eq!(pixel_at(&s, 4, 1, 0), Some([10, 0, 0, 255]));
+    assert_eq!(pixel_at(&s, 4, 2, 0), Some([0, 20, 0, 255]));
+    assert_eq!(pixel_at(&s, 4, 0, 1), Some([128; 4]));
+
+    let m = magnify(&a, 4, 0, 0, 4, 2);
+    assert_eq!(pixel_at(&m, 4, 0, 0), Some([0; 4]));
+    assert_eq!(pixel_at(&m, 4, 2, 2), Some([10, 0, 0, 255]));
+  }
+
+  #[test]
+  fn sequences_compare_frame_by_frame() {
+    let gray = solid(2, 2, 100, 100, 100, 255);
+    let mut spot = gray.clone();
+    spot[0] = 200;
+    let actual = [gray.clone(), spot, gray.clone()];
+    let expected = [gray.clone(), gray.clone()];
+    let result = compare_sequences(&actual, &expected, 2, 2);
+
+    assert!(!result.same_length());
+    assert_eq!(result.frames.len(), 2);
+    assert_eq!(result.frames[0].rmse, 0.0);
+    assert_eq!(result.worst_frame, Some(1));
+    assert_eq!(result.max_diff, 100);
+    assert_eq!(result.diff_pct, 12.5);
+    // One channel of 32 differs by 100.
+    assert!((result.rmse - (10_000.0f64 / 32.0).sqrt()).abs() < 1e-9);
+
+    // Three 2x2 tiles in a 2x2 grid with 2px gaps.
+    let (w, h) = result.sheet_size;
+    assert_eq!((w, h), (10, 10));
+    let sheet = &result.contact_sheet;
+    assert_eq!(pixel_at(sheet, w, 2, 2), Some([0, 128, 0, 255]));
+    assert_eq!(pixel_at(sheet, w, 6, 2), Some([255, 0, 0, 255]));
+    assert_eq!(pixel_at(sheet, w, 2, 6), Some([0, 0, 0, 255]));
+    assert_eq!(pixel_at(sheet, w, 6, 6), Some([128; 4]));
+
+    // Wide frames shrink, keeping the largest difference per block.
+    let wide = solid(320, 1, 0, 0, 0, 255);
+    let mut dot = wide.clone();
+    dot[4 * 5] = 255;
+    let result = compare_sequences(&[dot], &[wide], 320, 1);
+    assert_eq!(result.sheet_size, (164, 5));
+    let sheet = &result.contact_sheet;
+    assert_eq!(pixel_at(sheet, 164, 4, 2), Some([255, 0, 0, 255]));
+    assert_eq!(pixel_at(sheet, 164, 5, 2), Some([0, 128, 0, 255]));
+  }
 }
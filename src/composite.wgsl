@@ -0,0 +1,19 @@
+// Layer Compositing Shader
+//
+// Copies a cached layer texture (premultiplied alpha) onto the target
+// with a single full-screen triangle.
+
+@group(0) @binding(0) var layer_tex: texture_2d<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) i: u32) -> @builtin(position) vec4<f32> {
+    // (0,0), (2,0), (0,2) in UV space covers the whole viewport.
+    let uv = vec2<f32>(f32((i << 1u) & 2u), f32(i & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) pos: vec4<f32>) -> @location(0) vec4<f32> {
+    // Layers match the target size, so texels map 1:1 to pixels.
+    return textureLoad(layer_tex, vec2<i32>(pos.xy), 0);
+}
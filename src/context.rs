@@ -0,0 +1,181 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! GPU resources shared by many plots.
+//!
+//! Creating a [`PrimitiveRenderer`] compiles its shader and builds two
+//! render pipelines, and creating a [`TextRenderer`] parses its fonts,
+//! builds its pipeline and rasterizes every glyph it draws into its own
+//! atlas.  An application showing dozens of small plots pays that cost
+//! once per plot.  A [`RenderContext`] holds the device, queue,
+//! pipelines, parsed fonts and one glyph atlas; the renderers it hands
+//! out only allocate their own uniform and instance buffers, and a
+//! glyph rasterized for one plot is reused by all of them.
+//!
+//! ```rust,no_run
+//! # fn demo(device: std::sync::Arc<wgpu::Device>, queue: std::sync::Arc<wgpu::Queue>, font: &[u8]) {
+//! use mpl_wgpu::context::RenderContext;
+//!
+//! let ctx = RenderContext::new(device, queue, wgpu::TextureFormat::Bgra8UnormSrgb, font)
+//!   .expect("valid font");
+//! let plots: Vec<_> = (0..24).map(|_| ctx.renderers(200, 150)).collect();
+//! # }
+//! ```
+//!
+//! Plots drawn into the same target can go further and share a single
+//! pair of renderers, each plot passing its own target transform to
+//! [`PlotBackend::render`](crate::plotting::PlotBackend::render).
+//! Plots keeping their own renderers can instead have their passes
+//! encoded on several threads with [`encode_parallel`](crate::encode::encode_parallel).
+
+use crate::error::Result;
+use crate::glyphs::GlyphCache;
+use crate::primitives::{PrimitivePipelines, PrimitiveRenderer};
+use crate::text::TextRenderer;
+use std::sync::Arc;
+use wgpu_text::glyph_brush::ab_glyph::FontArc;
+
+/// Device, queue, pipelines, fonts and glyph atlas shared by the
+/// renderers of many plots drawing into targets of one format.
+///
+/// Cloning is cheap and shares everything.
+#[derive(Clone)]
+pub struct RenderContext {
+  device: Arc<wgpu::Device>,
+  queue: Arc<wgpu::Queue>,
+  format: wgpu::TextureFormat,
+  samples: u32,
+  pipelines: Arc<PrimitivePipelines>,
+  glyphs: Arc<GlyphCache>,
+  fonts: Vec<FontArc>,
+}
+
+impl RenderContext {
+  /// Compiles the primitive and text pipelines for `format` and parses
+  /// `font_data` as the primary font.
+  pub fn new(
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    format: wgpu::TextureFormat,
+    font_data: &[u8],
+  ) -> Result<Self> {
+    let font = FontArc::try_from_vec(font_data.to_vec())?;
+    let pipelines = Arc::new(PrimitivePipelines::new(&device, format, 1));
+    let glyphs = Arc::new(GlyphCache::new(&device, format, 1));
+    Ok(Self {
+      device,
+      queue,
+      format,
+      samples: 1,
+      pipelines,
+      glyphs,
+      fonts: vec![font],
+    })
+  }
+
+  /// Context using the embedded DejaVu Sans font.
+  #[cfg(feature = "embedded-font")]
+  pub fn with_embedded_font(
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    format: wgpu::TextureFormat,
+  ) -> Self {
+    Self::new(device, queue, format, crate::text::EMBEDDED_FONT)
+      .expect("embedded font is valid")
+  }
+
+  /// Copy of this context sharing device, queue, pipelines and glyph
+  /// atlas, with `font_data` as the only font.
+  pub fn with_font(&self, font_data: &[u8]) -> Result<Self> {
+    let font = FontArc::try_from_vec(font_data.to_vec())?;
+    Ok(Self {
+      fonts: vec![font],
+      ..self.clone()
+    })
+  }
+
+  /// Copy of this context sharing device, queue and fonts, whose
+  /// renderers draw into `samples`-sampled (multisampled) targets.
+  /// Recompiles the pipelines, with a new glyph atlas, unless the count
+  /// is unchanged.
+  pub fn with_sample_count(&self, samples: u32) -> Self {
+    if samples == self.samples {
+      return self.clone();
+    }
+    Self {
+      samples,
+      pipelines: Arc::new(PrimitivePipelines::new(&self.device, self.format, samples)),
+      glyphs: Arc::new(GlyphCache::new(&self.device, self.format, samples)),
+      ..self.clone()
+    }
+  }
+
+  /// Appends a fallback font for text renderers created afterwards (see
+  /// [`TextRenderer::add_fallback_font`]).
+  pub fn add_fallback_font(
+    &mut self,
+    font_data: &[u8],
+  ) -> Result<()> {
+    self.fonts.push(FontArc::try_from_vec(font_data.to_vec())?);
+    Ok(())
+  }
+
+  /// The shared device.
+  pub fn device(&self) -> &Arc<wgpu::Device> {
+    &self.device
+  }
+
+  /// The shared queue.
+  pub fn queue(&self) -> &Arc<wgpu::Queue> {
+    &self.queue
+  }
+
+  /// Target format of the pipelines.
+  pub fn format(&self) -> wgpu::TextureFormat {
+    self.format
+  }
+
+  /// Sample count of the targets the renderers draw into.
+  pub fn sample_count(&self) -> u32 {
+    self.samples
+  }
+
+  /// Primitive renderer for a `width` x `height` target, using the
+  /// shared pipelines.
+  pub fn primitive_renderer(
+    &self,
+    width: u32,
+    height: u32,
+  ) -> PrimitiveRenderer {
+    PrimitiveRenderer::with_pipelines(
+      &self.device,
+      self.pipelines.clone(),
+      width,
+      height,
+    )
+  }
+
+  /// Text renderer for a `width` x `height` target, using the shared
+  /// fonts, text pipeline and glyph atlas.
+  pub fn text_renderer(&self, width: u32, height: u32) -> TextRenderer {
+    TextRenderer::with_cache(
+      &self.device,
+      self.glyphs.clone(),
+      width,
+      height,
+      self.fonts.clone(),
+    )
+  }
+
+  /// Both renderers for one plot.
+  pub fn renderers(
+    &self,
+    width: u32,
+    height: u32,
+  ) -> (PrimitiveRenderer, TextRenderer) {
+    (
+      self.primitive_renderer(width, height),
+      self.text_renderer(width, height),
+    )
+  }
+}
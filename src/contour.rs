@@ -0,0 +1,364 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Contour lines with inline level labels.
+//!
+//! [`contour_lines`] traces the iso-lines of a grid with marching
+//! squares and joins the cell segments into polylines.  A [`Contour`]
+//! artist draws them and, with [`Contour::clabel`], places level labels
+//! along each line with a gap cut in the line under the text, like
+//! matplotlib's `clabel`.  Label placement works in pixels, so the
+//! density stays constant while zooming.  Add contours with
+//! [`PlotBackend::add_artist`](crate::plotting::PlotBackend::add_artist).
+
+use crate::artist::{Artist, DataRect, DrawContext};
+use crate::colormaps::Colormap;
+use crate::ticks::{self, Auto, TickFormatter};
+use glam::{Vec2, Vec4};
+use std::collections::HashMap;
+
+/// Iso-lines of `z` at `level`.
+///
+/// `z` is row-major with `x.len()` columns and `y.len()` rows
+/// (`z[j * x.len() + i]` is the value at `(x[i], y[j])`).  Cells with a
+/// non-finite corner are skipped; saddle cells are resolved by the mean
+/// of their corners.  Closed lines repeat their first point at the end.
+pub fn contour_lines(x: &[f64], y: &[f64], z: &[f64], level: f64) -> Vec<Vec<(f64, f64)>> {
+  let (nx, ny) = (x.len(), y.len());
+  if nx < 2 || ny < 2 || z.len() < nx * ny {
+    return Vec::new();
+  }
+  let at = |i: usize, j: usize| z[j * nx + i];
+  // Edge ids: 2 * corner for the edge to the right, 2 * corner + 1 for
+  // the edge upwards.
+  let h = |i: usize, j: usize| 2 * (j * nx + i);
+  let v = |i: usize, j: usize| 2 * (j * nx + i) + 1;
+  let mut points: HashMap<usize, (f64, f64)> = HashMap::new();
+  let mut segments: Vec<(usize, usize)> = Vec::new();
+  for j in 0..ny - 1 {
+    for i in 0..nx - 1 {
+      let c = [at(i, j), at(i + 1, j), at(i + 1, j + 1), at(i, j + 1)];
+      if c.iter().any(|v| !v.is_finite()) {
+        continue;
+      }
+      let high = c.map(|v| v >= level);
+      // Bottom, right, top, left edges with their corner pairs.
+      let edges = [
+        (h(i, j), (i, j), (i + 1, j), c[0], c[1]),
+        (v(i + 1, j), (i + 1, j), (i + 1, j + 1), c[1], c[2]),
+        (h(i, j + 1), (i, j + 1), (i + 1, j + 1), c[3], c[2]),
+        (v(i, j), (i, j), (i, j + 1), c[0], c[3]),
+      ];
+      let crossed: Vec<usize> = (0..4).filter(|&k| high[k] != high[(k + 1) % 4]).collect();
+      for &k in &crossed {
+        let (id, (i0, j0), (i1, j1), z0, z1) = edges[k];
+        points.entry(id).or_insert_with(|| {
+          let t = (level - z0) / (z1 - z0);
+          (x[i0] + (x[i1] - x[i0]) * t, y[j0] + (y[j1] - y[j0]) * t)
+        });
+      }
+      match crossed[..] {
+        [a, b] => segments.push((edges[a].0, edges[b].0)),
+        [_, _, _, _] => {
+          // Saddle: cut off the two corners on the other side of the
+          // center from the rest.
+          let center_high = c.iter().sum::<f64>() / 4.0 >= level;
+          for k in (0..4).filter(|&k| high[k] != center_high) {
+            // Corner k touches the edge before it and the edge after it.
+            segments.push((edges[(k + 3) % 4].0, edges[k].0));
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+  join_segments(&segments, &points)
+}
+
+/// Chains segments sharing edge points into polylines, open lines first.
+fn join_segments(segments: &[(usize, usize)], points: &HashMap<usize, (f64, f64)>) -> Vec<Vec<(f64, f64)>> {
+  let mut adjacent: HashMap<usize, Vec<usize>> = HashMap::new();
+  for (s, &(a, b)) in segments.iter().enumerate() {
+    adjacent.entry(a).or_default().push(s);
+    adjacent.entry(b).or_default().push(s);
+  }
+  let mut used = vec![false; segments.len()];
+  let mut ends: Vec<usize> = segments
+    .iter()
+    .flat_map(|&(a, b)| [a, b])
+    .filter(|e| adjacent[e].len() == 1)
+    .collect();
+  ends.extend(segments.iter().map(|&(a, _)| a));
+  let mut lines = Vec::new();
+  for start in ends {
+    let mut line = vec![points[&start]];
+    let mut edge = start;
+    while let Some(&s) = adjacent[&edge].iter().find(|&&s| !used[s]) {
+      used[s] = true;
+      let (a, b) = segments[s];
+      edge = if a == edge { b } else { a };
+      line.push(points[&edge]);
+    }
+    if line.len() > 1 {
+      lines.push(line);
+    }
+  }
+  lines
+}
+
+/// Arc-length positions for labels `label_width` wide along a pixel
+/// path, about `spacing` apart and centred in their share of the path.
+/// Paths shorter than 1.5 label widths get none.
+pub fn label_positions(path: &[Vec2], label_width: f32, spacing: f32) -> Vec<f32> {
+  let length = path_length(path);
+  if length < label_width * 1.5 || spacing <= 0.0 {
+    return Vec::new();
+  }
+  let n = ((length / spacing).floor() as usize).max(1);
+  (0..n).map(|k| (k as f32 + 0.5) * length / n as f32).collect()
+}
+
+/// Total length of a pixel path.
+fn path_length(path: &[Vec2]) -> f32 {
+  path.windows(2).map(|w| w[0].distance(w[1])).sum()
+}
+
+/// Point at arc length `s` along a pixel path.
+pub fn point_along(path: &[Vec2], s: f32) -> Vec2 {
+  let mut left = s;
+  for w in path.windows(2) {
+    let d = w[0].distance(w[1]);
+    if left <= d && d > 0.0 {
+      return w[0].lerp(w[1], left / d);
+    }
+    left -= d;
+  }
+  path.last().copied().unwrap_or(Vec2::ZERO)
+}
+
+/// Pieces of a pixel path left after removing the arc-length
+/// intervals in `gaps` (sorted, non-overlapping).
+pub fn cut_gaps(path: &[Vec2], gaps: &[(f32, f32)]) -> Vec<Vec<Vec2>> {
+  let in_gap = |s: f32| gaps.iter().any(|&(a, b)| s > a && s < b);
+  let mut pieces: Vec<Vec<Vec2>> = Vec::new();
+  let mut open = false;
+  let mut s = 0.0;
+  for w in path.windows(2) {
+    let d = w[0].distance(w[1]);
+    // Split the segment at the gap bounds inside it.
+    let mut stops: Vec<f32> = gaps
+      .iter()
+      .flat_map(|&(a, b)| [a, b])
+      .filter(|&c| c > s && c < s + d)
+      .collect();
+    stops.push(s);
+    stops.push(s + d);
+    stops.sort_by(f32::total_cmp);
+    for span in stops.windows(2) {
+      let (u, v) = (span[0], span[1]);
+      if in_gap((u + v) * 0.5) || d == 0.0 {
+        open = false;
+        continue;
+      }
+      let at = |c: f32| w[0].lerp(w[1], (c - s) / d);
+      match pieces.last_mut() {
+        Some(piece) if open => piece.push(at(v)),
+        _ => pieces.push(vec![at(u), at(v)]),
+      }
+      open = true;
+    }
+    s += d;
+  }
+  pieces
+}
+
+/// Inline level label settings.
+pub struct ContourLabels {
+  /// Font size, in pixels.
+  pub size: f32,
+  /// Target distance between labels on a line, in pixels.
+  pub spacing: f32,
+  /// Extra line gap on either side of the text, in pixels.
+  pub inline_spacing: f32,
+  /// Label text for a level.
+  pub format: Box<dyn TickFormatter + Send>,
+}
+
+impl Default for ContourLabels {
+  fn default() -> Self {
+    Self {
+      size: 10.0,
+      spacing: 300.0,
+      inline_spacing: 5.0,
+      format: Box::new(Auto),
+    }
+  }
+}
+
+/// Iso-lines of a grid, optionally labelled.
+pub struct Contour {
+  x: Vec<f64>,
+  y: Vec<f64>,
+  z: Vec<f64>,
+  levels: Vec<f64>,
+  colors: Vec<Vec4>,
+  /// Line width, in points.
+  pub width: f32,
+  labels: Option<ContourLabels>,
+}
+
+impl Contour {
+  /// Black contours of `z` (see [`contour_lines`] for the layout) at
+  /// round levels inside its range.
+  pub fn new(x: &[f64], y: &[f64], z: &[f64]) -> Self {
+    let finite = z.iter().copied().filter(|v| v.is_finite());
+    let (lo, hi) = finite.fold((f64::INFINITY, f64::NEG_INFINITY), |(a, b), v| (a.min(v), b.max(v)));
+    let (levels, _) = ticks::nice_ticks(lo, hi, ticks::DEFAULT_MAX_TICKS);
+    Self {
+      x: x.to_vec(),
+      y: y.to_vec(),
+      z: z.to_vec(),
+      levels: levels.into_iter().filter(|&l| l > lo && l < hi).collect(),
+      colors: vec![Vec4::new(0.0, 0.0, 0.0, 1.0)],
+      width: 1.0,
+      labels: None,
+    }
+  }
+
+  /// Sets the contour levels.
+  pub fn levels(mut self, levels: &[f64]) -> Self {
+    self.levels = levels.to_vec();
+    self
+  }
+
+  /// Draws every level in `color`.
+  pub fn color(mut self, color: Vec4) -> Self {
+    self.colors = vec![color];
+    self
+  }
+
+  /// Colors the levels from low to high through `map`.
+  pub fn colormap(mut self, map: &dyn Colormap) -> Self {
+    let n = self.levels.len();
+    self.colors = (0..n)
+      .map(|k| {
+        let t = if n > 1 { k as f32 / (n - 1) as f32 } else { 0.5 };
+        let [r, g, b] = map.eval(t);
+        Vec4::new(r, g, b, 1.0)
+      })
+      .collect();
+    self
+  }
+
+  /// Labels the lines inline with their level.
+  pub fn clabel(mut self, labels: ContourLabels) -> Self {
+    self.labels = Some(labels);
+    self
+  }
+
+  /// Spacing between neighbouring levels, for label precision.
+  fn level_step(&self) -> f64 {
+    self.levels.windows(2).map(|w| (w[1] - w[0]).abs()).fold(f64::INFINITY, f64::min)
+  }
+}
+
+impl Artist for Contour {
+  fn draw(&self, ctx: &mut DrawContext<'_>) {
+    let step = self.level_step();
+    for (k, &level) in self.levels.iter().enumerate() {
+      let color = self.colors[k % self.colors.len().max(1)];
+      for line in contour_lines(&self.x, &self.y, &self.z, level) {
+        let path: Vec<Vec2> = line.iter().map(|&(x, y)| ctx.to_pixels(x, y)).collect();
+        let mut pieces = vec![path.clone()];
+        let mut texts = Vec::new();
+        if let Some(labels) = &self.labels {
+          let text = labels.format.format(level, step);
+          let w = ctx.measure_text(&text, labels.size);
+          let half = w * 0.5 + labels.inline_spacing;
+          let at = label_positions(&path, w + 2.0 * labels.inline_spacing, labels.spacing);
+          if !at.is_empty() {
+            let gaps: Vec<(f32, f32)> = at.iter().map(|&s| (s - half, s + half)).collect();
+            pieces = cut_gaps(&path, &gaps);
+            for s in at {
+              let p = point_along(&path, s);
+              texts.push((p - Vec2::new(w * 0.5, labels.size * 0.5), text.clone(), labels.size));
+            }
+          }
+        }
+        for piece in pieces {
+          for w in piece.windows(2) {
+            let (a, b) = (ctx.screen(w[0]), ctx.screen(w[1]));
+            ctx.line_pixels(a, b, self.width, color);
+          }
+        }
+        for (pos, text, size) in texts {
+          ctx.text_pixels(pos, &text, size, color);
+        }
+      }
+    }
+  }
+
+  fn extent(&self) -> Option<DataRect> {
+    // Opposite grid corners bound the grid.
+    let xs = [*self.x.first()?, *self.x.last()?];
+    let ys = [*self.y.first()?, *self.y.last()?];
+    DataRect::from_points(&xs, &ys)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// z = x on a 3x3 grid.
+  fn ramp() -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let x = vec![0.0, 1.0, 2.0];
+    let y = vec![0.0, 1.0, 2.0];
+    let z = (0..9).map(|k| (k % 3) as f64).collect();
+    (x, y, z)
+  }
+
+  #[test]
+  fn traces_a_straight_iso_line() {
+    let (x, y, z) = ramp();
+    let lines = contour_lines(&x, &y, &z, 0.5);
+    assert_eq!(lines.len(), 1);
+    let mut line = lines[0].clone();
+    line.sort_by(|a, b| a.1.total_cmp(&b.1));
+    assert_eq!(line, vec![(0.5, 0.0), (0.5, 1.0), (0.5, 2.0)]);
+  }
+
+  #[test]
+  fn closed_loop_around_a_peak() {
+    let x = [0.0, 1.0, 2.0];
+    let z = [0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+    let lines = contour_lines(&x, &x, &z, 0.5);
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].len(), 5);
+    assert_eq!(lines[0].first(), lines[0].last());
+  }
+
+  #[test]
+  fn labels_are_spread_and_cut_the_line() {
+    let path = [Vec2::new(0.0, 0.0), Vec2::new(100.0, 0.0)];
+    assert_eq!(label_positions(&path, 20.0, 50.0), vec![25.0, 75.0]);
+    assert!(label_positions(&path, 80.0, 50.0).is_empty());
+    assert_eq!(point_along(&path, 25.0), Vec2::new(25.0, 0.0));
+    let pieces = cut_gaps(&path, &[(15.0, 35.0), (65.0, 85.0)]);
+    let ends: Vec<(f32, f32)> = pieces.iter().map(|p| (p[0].x, p[p.len() - 1].x)).collect();
+    let expected = [(0.0, 15.0), (35.0, 65.0), (85.0, 100.0)];
+    assert_eq!(ends.len(), 3);
+    for (a, b) in ends.iter().zip(expected) {
+      assert!((a.0 - b.0).abs() < 1e-4 && (a.1 - b.1).abs() < 1e-4);
+    }
+  }
+
+  #[test]
+  fn default_levels_are_interior() {
+    let (x, y, z) = ramp();
+    let c = Contour::new(&x, &y, &z);
+    assert!(c.levels.iter().all(|&l| l > 0.0 && l < 2.0));
+    assert!(!c.levels.is_empty());
+    assert_eq!(c.extent(), Some(DataRect::new((0.0, 2.0), (0.0, 2.0))));
+  }
+}
@@ -0,0 +1,456 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Density images of very large point sets, aggregated on the GPU.
+//!
+//! Past a few million points, markers overlap into a solid blob and
+//! cost more than the screen can show.  A [`DensityRenderer`] instead
+//! counts how many points fall on each pixel of the axes box in a
+//! compute pass, then shades the counts through a colormap on a log
+//! scale, as datashader does.  The points stay on the GPU as 8-byte
+//! `[x, y]` pairs in data coordinates, uploaded in chunks of
+//! [`CHUNK_POINTS`] like a [`PointCloudRenderer`](crate::pointcloud::PointCloudRenderer)
+//! cloud, so hundreds of millions of points fit without any one
+//! buffer nearing the device limits.
+//!
+//! Each frame, [`aggregate`](DensityRenderer::aggregate) records the
+//! counting pass into an encoder, and [`render`](DensityRenderer::render)
+//! draws the image into a later render pass over the axes box set with
+//! [`set_viewport`](DensityRenderer::set_viewport).
+
+use crate::artist::{DataRect, Viewport};
+use crate::colormaps::{self, Colormap};
+
+/// Points per storage buffer (32 MiB).
+pub const CHUNK_POINTS: usize = 1 << 22;
+
+/// Threads per counting workgroup; matches the shader.
+const WORKGROUP_SIZE: usize = 256;
+
+/// Colormap entries.
+const LUT_SIZE: usize = 256;
+
+/// Size of the parameter block: limits, pixel box, screen and grid
+/// size.
+const PARAMS_SIZE: u64 = 48;
+
+/// Count grid covering `pixel_box` (`[left, top, right, bottom]`), one
+/// cell per pixel and at least one cell each way.
+pub fn grid_size(pixel_box: [f32; 4]) -> [u32; 2] {
+  let [left, top, right, bottom] = pixel_box;
+  [
+    (right - left).round().max(1.0) as u32,
+    (bottom - top).round().max(1.0) as u32,
+  ]
+}
+
+/// Opaque colormap table as laid out in the shader.
+fn lut_entries(map: &dyn Colormap) -> Vec<[f32; 4]> {
+  map.lut(LUT_SIZE).into_iter().map(|[r, g, b]| [r, g, b, 1.0]).collect()
+}
+
+/// Workgroups counting `points` points.
+fn workgroups(points: usize) -> u32 {
+  points.div_ceil(WORKGROUP_SIZE) as u32
+}
+
+/// A chunk of uploaded points.
+struct Chunk {
+  points: wgpu::Buffer,
+  /// Points in use, for the shader.
+  len: wgpu::Buffer,
+  bind_group: wgpu::BindGroup,
+}
+
+/// Counts points per pixel and shades the counts.
+pub struct DensityRenderer {
+  count_pipeline: wgpu::ComputePipeline,
+  shade_pipeline: wgpu::RenderPipeline,
+  params: wgpu::Buffer,
+  lut: wgpu::Buffer,
+  peak: wgpu::Buffer,
+  counts: wgpu::Buffer,
+  shade_bind_group: wgpu::BindGroup,
+  chunks: Vec<Chunk>,
+  len: usize,
+  viewport: Viewport,
+  screen_size: [f32; 2],
+  grid: [u32; 2],
+}
+
+impl DensityRenderer {
+  /// Renderer for a `width` x `height` target of `format`, shading
+  /// with viridis.  Set the viewport with
+  /// [`set_viewport`](Self::set_viewport) before the first
+  /// [`aggregate`](Self::aggregate).
+  pub fn new(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    width: u32,
+    height: u32,
+  ) -> Self {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+      label: Some("DensityShader"),
+      source: wgpu::ShaderSource::Wgsl(include_str!("density.wgsl").into()),
+    });
+
+    let count_pipeline =
+      device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+        label: Some("DensityCountPipeline"),
+        layout: None,
+        module: &shader,
+        entry_point: "count_points",
+        compilation_options: wgpu::PipelineCompilationOptions::default(),
+      });
+
+    let shade_pipeline =
+      device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("DensityShadePipeline"),
+        layout: None,
+        vertex: wgpu::VertexState {
+          module: &shader,
+          entry_point: "vs_box",
+          buffers: &[],
+          compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+          module: &shader,
+          entry_point: "fs_shade",
+          targets: &[Some(wgpu::ColorTargetState {
+            format,
+            blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+            write_mask: wgpu::ColorWrites::ALL,
+          })],
+          compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+      });
+
+    let params = device.create_buffer(&wgpu::BufferDescriptor {
+      label: Some("DensityParams"),
+      size: PARAMS_SIZE,
+      usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+      mapped_at_creation: false,
+    });
+    let lut = device.create_buffer(&wgpu::BufferDescriptor {
+      label: Some("DensityColormap"),
+      size: (LUT_SIZE * 16) as u64,
+      usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+      mapped_at_creation: true,
+    });
+    lut
+      .slice(..)
+      .get_mapped_range_mut()
+      .copy_from_slice(bytemuck::cast_slice(&lut_entries(&colormaps::VIRIDIS)));
+    lut.unmap();
+    let peak = device.create_buffer(&wgpu::BufferDescriptor {
+      label: Some("DensityPeak"),
+      size: 4,
+      usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+      mapped_at_creation: false,
+    });
+
+    let viewport = Viewport::new(
+      DataRect::new((0.0, 1.0), (0.0, 1.0)),
+      [0.0, 0.0, width as f32, height as f32],
+    );
+    let grid = grid_size(viewport.pixels);
+    let counts = Self::count_buffer(device, grid);
+    let shade_bind_group =
+      Self::shade_bind_group(device, &shade_pipeline, &params, &counts, &peak, &lut);
+
+    Self {
+      count_pipeline,
+      shade_pipeline,
+      params,
+      lut,
+      peak,
+      counts,
+      shade_bind_group,
+      chunks: Vec::new(),
+      len: 0,
+      viewport,
+      screen_size: [width as f32, height as f32],
+      grid,
+    }
+  }
+
+  fn count_buffer(device: &wgpu::Device, grid: [u32; 2]) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+      label: Some("DensityCounts"),
+      size: grid[0] as u64 * grid[1] as u64 * 4,
+      usage: wgpu::BufferUsages::STORAGE
+        | wgpu::BufferUsages::COPY_DST
+        | wgpu::BufferUsages::COPY_SRC,
+      mapped_at_creation: false,
+    })
+  }
+
+  fn shade_bind_group(
+    device: &wgpu::Device,
+    pipeline: &wgpu::RenderPipeline,
+    params: &wgpu::Buffer,
+    counts: &wgpu::Buffer,
+    peak: &wgpu::Buffer,
+    lut: &wgpu::Buffer,
+  ) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+      label: None,
+      layout: &pipeline.get_bind_group_layout(0),
+      entries: &[
+        wgpu::BindGroupEntry { binding: 0, resource: params.as_entire_binding() },
+        wgpu::BindGroupEntry { binding: 5, resource: counts.as_entire_binding() },
+        wgpu::BindGroupEntry { binding: 6, resource: peak.as_entire_binding() },
+        wgpu::BindGroupEntry { binding: 7, resource: lut.as_entire_binding() },
+      ],
+    })
+  }
+
+  fn chunk_bind_group(
+    &self,
+    device: &wgpu::Device,
+    points: &wgpu::Buffer,
+    len: &wgpu::Buffer,
+  ) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+      label: None,
+      layout: &self.count_pipeline.get_bind_group_layout(0),
+      entries: &[
+        wgpu::BindGroupEntry { binding: 0, resource: self.params.as_entire_binding() },
+        wgpu::BindGroupEntry { binding: 1, resource: points.as_entire_binding() },
+        wgpu::BindGroupEntry { binding: 2, resource: self.counts.as_entire_binding() },
+        wgpu::BindGroupEntry { binding: 3, resource: self.peak.as_entire_binding() },
+        wgpu::BindGroupEntry { binding: 4, resource: len.as_entire_binding() },
+      ],
+    })
+  }
+
+  /// Updates the target size.
+  pub fn resize(&mut self, queue: &wgpu::Queue, width: u32, height: u32) {
+    self.screen_size = [width as f32, height as f32];
+    self.update_params(queue);
+  }
+
+  /// Aggregates over the limits of `viewport` into one count per pixel
+  /// of its box, e.g. [`Axes::viewport`](crate::plotting::Axes::viewport)
+  /// after every zoom.  The counts are reallocated when the box
+  /// changes size.
+  pub fn set_viewport(
+    &mut self,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    viewport: &Viewport,
+  ) {
+    self.viewport = *viewport;
+    let grid = grid_size(viewport.pixels);
+    if grid != self.grid {
+      self.grid = grid;
+      self.counts = Self::count_buffer(device, grid);
+      self.shade_bind_group = Self::shade_bind_group(
+        device,
+        &self.shade_pipeline,
+        &self.params,
+        &self.counts,
+        &self.peak,
+        &self.lut,
+      );
+      for i in 0..self.chunks.len() {
+        let bind_group =
+          self.chunk_bind_group(device, &self.chunks[i].points, &self.chunks[i].len);
+        self.chunks[i].bind_group = bind_group;
+      }
+    }
+    self.update_params(queue);
+  }
+
+  /// Shades counts with `map`, from one point (low end) to the fullest
+  /// pixel (high end).
+  pub fn set_colormap(&mut self, queue: &wgpu::Queue, map: &dyn Colormap) {
+    queue.write_buffer(&self.lut, 0, bytemuck::cast_slice(&lut_entries(map)));
+  }
+
+  fn update_params(&self, queue: &wgpu::Queue) {
+    let l = self.viewport.limits;
+    let [left, top, right, bottom] = self.viewport.pixels;
+    let floats = [
+      l.x_min as f32,
+      l.x_max as f32,
+      l.y_min as f32,
+      l.y_max as f32,
+      left,
+      top,
+      right - left,
+      bottom - top,
+      self.screen_size[0],
+      self.screen_size[1],
+    ];
+    let mut data = [0u32; 12];
+    for (d, f) in data.iter_mut().zip(floats) {
+      *d = f.to_bits();
+    }
+    data[10] = self.grid[0];
+    data[11] = self.grid[1];
+    queue.write_buffer(&self.params, 0, bytemuck::cast_slice(&data));
+  }
+
+  /// Replaces the points with `points` (`[x, y]` in data
+  /// coordinates), reusing the chunk buffers.
+  pub fn set_points(
+    &mut self,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    points: &[[f32; 2]],
+  ) {
+    self.clear();
+    self.extend(device, queue, points);
+    self.chunks.truncate(self.len.div_ceil(CHUNK_POINTS));
+  }
+
+  /// Appends `points`, uploading only them and allocating chunks as
+  /// needed.
+  pub fn extend(
+    &mut self,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    points: &[[f32; 2]],
+  ) {
+    let point_bytes = std::mem::size_of::<[f32; 2]>();
+    let mut rest = points;
+    while !rest.is_empty() {
+      let (chunk, offset) = (self.len / CHUNK_POINTS, self.len % CHUNK_POINTS);
+      if chunk == self.chunks.len() {
+        let points = device.create_buffer(&wgpu::BufferDescriptor {
+          label: Some("DensityChunk"),
+          size: (CHUNK_POINTS * point_bytes) as u64,
+          usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+          mapped_at_creation: false,
+        });
+        let len = device.create_buffer(&wgpu::BufferDescriptor {
+          label: Some("DensityChunkLen"),
+          size: 16,
+          usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+          mapped_at_creation: false,
+        });
+        let bind_group = self.chunk_bind_group(device, &points, &len);
+        self.chunks.push(Chunk { points, len, bind_group });
+      }
+      let n = (CHUNK_POINTS - offset).min(rest.len());
+      queue.write_buffer(
+        &self.chunks[chunk].points,
+        (offset * point_bytes) as u64,
+        bytemuck::cast_slice(&rest[..n]),
+      );
+      let used = [(offset + n) as u32, 0, 0, 0];
+      queue.write_buffer(&self.chunks[chunk].len, 0, bytemuck::cast_slice(&used));
+      self.len += n;
+      rest = &rest[n..];
+    }
+  }
+
+  /// Removes all points; the chunk buffers are kept for reuse.
+  pub fn clear(&mut self) {
+    self.len = 0;
+  }
+
+  /// Number of points uploaded.
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Whether no points are uploaded.
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Number of chunk buffers allocated.
+  pub fn chunk_count(&self) -> usize {
+    self.chunks.len()
+  }
+
+  /// Records the counting pass into `encoder`; call after changing the
+  /// points or viewport and before the pass drawing
+  /// [`render`](Self::render).
+  pub fn aggregate(&self, encoder: &mut wgpu::CommandEncoder) {
+    encoder.clear_buffer(&self.counts, 0, None);
+    encoder.clear_buffer(&self.peak, 0, None);
+    if self.len == 0 {
+      return;
+    }
+    let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+      label: Some("DensityCountPass"),
+      timestamp_writes: None,
+    });
+    pass.set_pipeline(&self.count_pipeline);
+    for (i, chunk) in self.chunks.iter().enumerate() {
+      let n = (self.len - (i * CHUNK_POINTS).min(self.len)).min(CHUNK_POINTS);
+      if n == 0 {
+        break;
+      }
+      pass.set_bind_group(0, &chunk.bind_group, &[]);
+      pass.dispatch_workgroups(workgroups(n), 1, 1);
+    }
+  }
+
+  /// Draws the aggregated image over the axes box into `rp`.  Empty
+  /// pixels are left untouched.
+  pub fn render<'a>(&'a self, rp: &mut wgpu::RenderPass<'a>) {
+    rp.set_pipeline(&self.shade_pipeline);
+    rp.set_bind_group(0, &self.shade_bind_group, &[]);
+    rp.draw(0..6, 0..1);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::capture::{shared_context, CAPTURE_FORMAT};
+
+  /// Counts of `renderer` after aggregating, row by row.
+  fn read_counts(device: &wgpu::Device, queue: &wgpu::Queue, renderer: &DensityRenderer) -> Vec<u32> {
+    let size = renderer.counts.size();
+    let staging = device.create_buffer(&wgpu::BufferDescriptor {
+      label: None,
+      size,
+      usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+      mapped_at_creation: false,
+    });
+    let mut encoder = device.create_command_encoder(&Default::default());
+    renderer.aggregate(&mut encoder);
+    encoder.copy_buffer_to_buffer(&renderer.counts, 0, &staging, 0, size);
+    queue.submit([encoder.finish()]);
+    let slice = staging.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |result| result.unwrap());
+    device.poll(wgpu::Maintain::Wait);
+    let counts = bytemuck::cast_slice(&slice.get_mapped_range()).to_vec();
+    counts
+  }
+
+  #[test]
+  fn counts_points_per_pixel() {
+    let ctx = shared_context().expect("headless GPU context");
+    let (device, queue) = (ctx.device(), ctx.queue());
+    let mut renderer = DensityRenderer::new(device, CAPTURE_FORMAT, 8, 8);
+    // A 4 x 2 box over x in [0, 4) and y in [0, 2).
+    let viewport = Viewport::new(DataRect::new((0.0, 4.0), (0.0, 2.0)), [2.0, 2.0, 6.0, 4.0]);
+    renderer.set_viewport(device, queue, &viewport);
+    renderer.set_points(
+      device,
+      queue,
+      &[[0.5, 1.5], [0.5, 1.5], [0.5, 1.5], [3.5, 0.5], [9.0, 0.5], [f32::NAN, 1.0]],
+    );
+    // Row 0 is the top of the box; the last two points are outside.
+    assert_eq!(read_counts(device, queue, &renderer), [3, 0, 0, 0, 0, 0, 0, 1]);
+  }
+
+  #[test]
+  fn one_cell_per_pixel_of_the_box() {
+    assert_eq!(grid_size([10.0, 20.0, 410.0, 320.4]), [400, 300]);
+    assert_eq!(grid_size([5.0, 5.0, 5.0, 4.0]), [1, 1]);
+    assert_eq!(workgroups(CHUNK_POINTS), (CHUNK_POINTS / WORKGROUP_SIZE) as u32);
+    assert_eq!(workgroups(257), 2);
+    assert!(workgroups(CHUNK_POINTS) <= 65535);
+  }
+}
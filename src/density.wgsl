@@ -0,0 +1,72 @@
+// Density shader: points are counted per pixel by a compute pass, then
+// the counts are shaded through a colormap on a log scale.
+
+struct Params {
+    // Data limits: x_min, x_max, y_min, y_max.
+    limits: vec4<f32>,
+    // Axes box in target pixels: left, top, width, height.
+    pixel_box: vec4<f32>,
+    screen_size: vec2<f32>,
+    // Count grid size, one cell per pixel of the box.
+    grid: vec2<u32>,
+};
+@group(0) @binding(0) var<uniform> params: Params;
+
+// Counting.
+@group(0) @binding(1) var<storage, read> points: array<vec2<f32>>;
+@group(0) @binding(2) var<storage, read_write> counts: array<atomic<u32>>;
+@group(0) @binding(3) var<storage, read_write> peak: atomic<u32>;
+// Points in use in this chunk, in x.
+@group(0) @binding(4) var<uniform> chunk_len: vec4<u32>;
+
+// Shading.
+@group(0) @binding(5) var<storage, read> cell_counts: array<u32>;
+@group(0) @binding(6) var<storage, read> peak_count: u32;
+@group(0) @binding(7) var<uniform> lut: array<vec4<f32>, 256>;
+
+@compute @workgroup_size(256)
+fn count_points(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (id.x >= chunk_len.x) {
+        return;
+    }
+    let p = points[id.x];
+    let l = params.limits;
+    let fx = (p.x - l.x) / (l.y - l.x);
+    // Row 0 is the top of the box.
+    let fy = (l.w - p.y) / (l.w - l.z);
+    // Also rejects NaN.
+    if (!(fx >= 0.0 && fx < 1.0 && fy >= 0.0 && fy < 1.0)) {
+        return;
+    }
+    let gx = min(u32(fx * f32(params.grid.x)), params.grid.x - 1u);
+    let gy = min(u32(fy * f32(params.grid.y)), params.grid.y - 1u);
+    let n = atomicAdd(&counts[gy * params.grid.x + gx], 1u) + 1u;
+    // Skip the shared maximum unless it can grow.
+    if (n > atomicLoad(&peak)) {
+        atomicMax(&peak, n);
+    }
+}
+
+@vertex
+fn vs_box(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let idx = vertex_index % 6u;
+    var corner = vec2<f32>(0.0, 0.0);
+    if (idx == 1u || idx == 2u || idx == 4u) { corner.x = 1.0; }
+    if (idx == 2u || idx == 4u || idx == 5u) { corner.y = 1.0; }
+    let px = params.pixel_box.xy + corner * params.pixel_box.zw;
+    let ndc = px / params.screen_size * 2.0 - 1.0;
+    return vec4<f32>(ndc.x, -ndc.y, 0.0, 1.0);
+}
+
+@fragment
+fn fs_shade(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let local = max(position.xy - params.pixel_box.xy, vec2<f32>(0.0, 0.0));
+    let gx = min(u32(local.x), params.grid.x - 1u);
+    let gy = min(u32(local.y), params.grid.y - 1u);
+    let n = cell_counts[gy * params.grid.x + gx];
+    if (n == 0u) {
+        discard;
+    }
+    let t = log(f32(n) + 1.0) / log(f32(max(peak_count, 1u)) + 1.0);
+    return lut[min(u32(round(t * 255.0)), 255u)];
+}
@@ -0,0 +1,377 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Color emoji in labels.
+//!
+//! Color emoji fonts such as Noto Color Emoji (CBDT) or Apple Color
+//! Emoji (sbix) store glyphs as bitmaps instead of outlines, and the
+//! glyph atlas only rasterizes outlines, so status markers like ✅ in a
+//! title would disappear.  Text containing such glyphs is laid out by
+//! [`layout`] into plain text runs and [`ColorBitmap`]s, which the text
+//! callbacks draw in place of the raw string: the runs as text, the
+//! bitmaps resampled to about one cell per screen pixel and drawn as
+//! quads, like [image underlays](crate::underlay).  PNG and BGRA
+//! bitmaps are supported.
+//!
+//! Add the emoji font as a fallback with
+//! [`TextRenderer::add_fallback_font`](crate::text::TextRenderer::add_fallback_font).
+
+use crate::primitives::PrimitiveRenderer;
+use crate::recording::{RecordedPrimitive, Recording};
+use crate::text::{font_runs, measure_with_fonts, TextRenderer};
+use glam::{Vec2, Vec4};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use wgpu_text::glyph_brush::ab_glyph::{Font, GlyphId, GlyphImageFormat, PxScale, ScaleFont};
+
+/// Straight-alpha RGBA image of a color glyph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorBitmap {
+  /// Width in pixels.
+  pub width: usize,
+  /// Height in pixels.
+  pub height: usize,
+  /// Pixels row by row, top row first.
+  pub pixels: Vec<[u8; 4]>,
+}
+
+impl ColorBitmap {
+  /// Decodes glyph image `data` in `format`; `width` and `height` are
+  /// only used by raw bitmaps.  Monochrome and grayscale formats are
+  /// not color glyphs and give `None`.
+  pub fn decode(data: &[u8], format: &GlyphImageFormat, width: usize, height: usize) -> Option<Self> {
+    match format {
+      GlyphImageFormat::Png => {
+        let image = image::load_from_memory_with_format(data, image::ImageFormat::Png).ok()?.to_rgba8();
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        let pixels = image.pixels().map(|p| p.0).collect();
+        Some(Self { width, height, pixels })
+      }
+      GlyphImageFormat::BitmapPremulBgra32 if data.len() >= width * height * 4 => {
+        let pixels = data.chunks_exact(4).take(width * height).map(|p| {
+          let unpremul = |v: u8| if p[3] == 0 { 0 } else { (v as u32 * 255 / p[3] as u32).min(255) as u8 };
+          [unpremul(p[2]), unpremul(p[1]), unpremul(p[0]), p[3]]
+        });
+        Some(Self { width, height, pixels: pixels.collect() })
+      }
+      _ => None,
+    }
+  }
+
+  /// Box-filtered `cols` x `rows` resampling, straight-alpha RGBA in
+  /// 0..1, row by row.
+  pub fn resample(&self, cols: usize, rows: usize) -> Vec<Vec4> {
+    let (cols, rows) = (cols.max(1), rows.max(1));
+    let span = |i: usize, n: usize, len: usize| (i * len / n)..((i + 1) * len / n).max(i * len / n + 1).min(len);
+    let mut out = Vec::with_capacity(cols * rows);
+    for row in 0..rows {
+      for col in 0..cols {
+        // Average premultiplied, so transparent pixels do not darken edges.
+        let mut sum = Vec4::ZERO;
+        let mut n = 0.0;
+        for y in span(row, rows, self.height) {
+          for x in span(col, cols, self.width) {
+            let p = Vec4::from_array(self.pixels[y * self.width + x].map(|v| v as f32 / 255.0));
+            sum += (p.truncate() * p.w).extend(p.w);
+            n += 1.0;
+          }
+        }
+        let avg = if n > 0.0 { sum / n } else { Vec4::ZERO };
+        let rgb = if avg.w > 0.0 { avg.truncate() / avg.w } else { avg.truncate() };
+        out.push(rgb.extend(avg.w));
+      }
+    }
+    out
+  }
+}
+
+/// Plain text drawn with the text renderer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextRun {
+  /// The text.
+  pub text: String,
+  /// Top-left offset from the label anchor.
+  pub offset: Vec2,
+}
+
+/// A color glyph placed in a label.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlacedBitmap {
+  /// The glyph image, shared with the [`BitmapCache`] it came from.
+  pub bitmap: Arc<ColorBitmap>,
+  /// Top-left offset from the label anchor.
+  pub offset: Vec2,
+  /// Drawn size in pixels.
+  pub size: Vec2,
+}
+
+/// Label split into text runs and color glyphs.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EmojiLayout {
+  /// Text runs.
+  pub runs: Vec<TextRun>,
+  /// Color glyphs.
+  pub bitmaps: Vec<PlacedBitmap>,
+  /// Font size of the text runs in pixels.
+  pub font_size: f32,
+  /// Extent of the whole label.
+  pub size: Vec2,
+}
+
+/// Variation selectors asking for text or emoji presentation; they
+/// select nothing here and are dropped.
+fn is_variation_selector(c: char) -> bool {
+  matches!(c, '\u{FE0E}' | '\u{FE0F}')
+}
+
+/// Pixels per em of `font` at `size` pixels.
+fn pixels_per_em<F: Font>(font: &F, size: f32) -> f32 {
+  font.as_scaled(PxScale::from(size)).h_scale_factor() * font.units_per_em().unwrap_or(1000.0)
+}
+
+/// Whether the font file `data` has bitmap glyph tables (`CBDT` or
+/// `sbix`).  Collections are judged by their first font.
+pub fn has_color_tables(data: &[u8]) -> bool {
+  let u32_at = |at: usize| data.get(at..at + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+  let start = match data.get(0..4) {
+    Some(b"ttcf") => u32_at(12).unwrap_or(0) as usize,
+    _ => 0,
+  };
+  let Some(tables) = data.get(start + 4..start + 6).map(|b| u16::from_be_bytes([b[0], b[1]])) else { return false };
+  (0..tables as usize)
+    .filter_map(|i| data.get(start + 12 + i * 16..start + 16 + i * 16))
+    .any(|tag| tag == b"CBDT" || tag == b"sbix")
+}
+
+/// Whole pixels per em of the glyph image asked for at `ppem`.
+fn image_ppem(ppem: f32) -> u16 {
+  ppem.round().clamp(1.0, u16::MAX as f32) as u16
+}
+
+/// Whether glyph `id` is a color glyph without an outline, judged by
+/// the presence of its image without decoding it.
+fn is_color_glyph<F: Font>(font: &F, id: GlyphId, ppem: f32) -> bool {
+  has_color_tables(font.font_data())
+    && font.outline(id).is_none()
+    && font.glyph_raster_image2(id, image_ppem(ppem)).is_some()
+}
+
+/// Decoded color glyphs by font index, glyph id and pixels per em, so
+/// labels redrawn every frame decode each glyph once.
+///
+/// The font index refers to the font list the cache is used with;
+/// [`clear`](Self::clear) it when that list changes other than by
+/// appending.
+#[derive(Debug, Default)]
+pub struct BitmapCache {
+  bitmaps: Mutex<HashMap<(usize, u16, u16), Option<(Arc<ColorBitmap>, f32)>>>,
+}
+
+impl Clone for BitmapCache {
+  fn clone(&self) -> Self {
+    Self { bitmaps: Mutex::new(self.bitmaps.lock().unwrap().clone()) }
+  }
+}
+
+impl BitmapCache {
+  /// Forgets every decoded glyph.
+  pub fn clear(&self) {
+    self.bitmaps.lock().unwrap().clear();
+  }
+
+  /// Bitmap of glyph `id` of `fonts[font]` for `ppem` pixels per em
+  /// and its scale to `ppem`, if it is a color glyph without an
+  /// outline.
+  fn color_glyph<F: Font>(&self, fonts: &[F], font: usize, id: GlyphId, ppem: f32) -> Option<(Arc<ColorBitmap>, f32)> {
+    if !has_color_tables(fonts[font].font_data()) {
+      return None;
+    }
+    let key = (font, id.0, image_ppem(ppem));
+    if let Some(cached) = self.bitmaps.lock().unwrap().get(&key) {
+      return cached.clone();
+    }
+    let decoded = (|| {
+      let font = &fonts[font];
+      if font.outline(id).is_some() {
+        return None;
+      }
+      let image = font.glyph_raster_image2(id, key.2)?;
+      let bitmap = ColorBitmap::decode(image.data, &image.format, image.width as usize, image.height as usize)?;
+      Some((Arc::new(bitmap), ppem / image.pixels_per_em.max(1) as f32))
+    })();
+    self.bitmaps.lock().unwrap().insert(key, decoded.clone());
+    decoded
+  }
+
+  /// Number of glyphs of color fonts looked up so far, color or not.
+  pub fn len(&self) -> usize {
+    self.bitmaps.lock().unwrap().len()
+  }
+
+  /// Whether no glyph was looked up yet.
+  pub fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+}
+
+/// Whether any character of `text` is drawn from a color glyph of
+/// `fonts` (see [`font_runs`]).  Only checks that the glyph images
+/// exist; nothing is decoded.
+pub fn has_color_glyphs<F: Font>(fonts: &[F], text: &str) -> bool {
+  font_runs(fonts, text).into_iter().any(|(font, run)| {
+    let font = &fonts[font];
+    if !has_color_tables(font.font_data()) {
+      return false;
+    }
+    let ppem = pixels_per_em(font, 16.0);
+    run.chars().any(|c| !c.is_whitespace() && is_color_glyph(font, font.glyph_id(c), ppem))
+  })
+}
+
+/// Lays out `text` at `size` pixels into text runs and color glyphs,
+/// each glyph scaled to the font size, centred in its advance and on
+/// the line box.  Decoded glyphs are taken from and added to `cache`.
+pub fn layout<F: Font>(fonts: &[F], text: &str, size: f32, cache: &BitmapCache) -> EmojiLayout {
+  let mut out = EmojiLayout { font_size: size, ..EmojiLayout::default() };
+  let Some(primary) = fonts.first() else { return out };
+  let scaled = primary.as_scaled(PxScale::from(size));
+  let (line_box, line_height) = (scaled.height(), scaled.height() + scaled.line_gap());
+  let mut top = 0.0;
+  for line in text.split('\n') {
+    let mut x = 0.0;
+    let mut pending = String::new();
+    let flush = |pending: &mut String, x: &mut f32, runs: &mut Vec<TextRun>| {
+      if !pending.is_empty() {
+        let text = std::mem::take(pending);
+        let width = measure_with_fonts(fonts, &text, size).x;
+        runs.push(TextRun { text, offset: Vec2::new(*x, top) });
+        *x += width;
+      }
+    };
+    for (index, run) in font_runs(fonts, line) {
+      let font = &fonts[index];
+      let ppem = pixels_per_em(font, size);
+      for c in run.chars().filter(|&c| !is_variation_selector(c)) {
+        let id = font.glyph_id(c);
+        let glyph = if c.is_whitespace() { None } else { cache.color_glyph(fonts, index, id, ppem) };
+        match glyph {
+          Some((bitmap, scale)) => {
+            flush(&mut pending, &mut x, &mut out.runs);
+            let advance = font.as_scaled(PxScale::from(size)).h_advance(id);
+            let drawn = Vec2::new(bitmap.width as f32, bitmap.height as f32) * scale;
+            let offset = Vec2::new(x + (advance - drawn.x) / 2.0, top + (line_box - drawn.y) / 2.0);
+            out.bitmaps.push(PlacedBitmap { bitmap, offset, size: drawn });
+            x += advance;
+          }
+          None => pending.push(c),
+        }
+      }
+    }
+    flush(&mut pending, &mut x, &mut out.runs);
+    out.size.x = out.size.x.max(x);
+    top += line_height;
+  }
+  out.size.y = top - scaled.line_gap();
+  out
+}
+
+/// Screen offset of layout offset `v` for text rotated `degrees`
+/// counter-clockwise on a Y-down screen.
+fn rotate(v: Vec2, degrees: f32) -> Vec2 {
+  let (s, c) = degrees.to_radians().sin_cos();
+  Vec2::new(c * v.x + s * v.y, -s * v.x + c * v.y)
+}
+
+/// Draws `layout` with its top-left at `pos`, rotated `rotation`
+/// degrees about `pos`, into whichever targets are present.  The text
+/// color's alpha fades the color glyphs too.
+pub(crate) fn draw_emoji(
+  layout: &EmojiLayout,
+  pos: Vec2,
+  rotation: f32,
+  color: Vec4,
+  mut prim: Option<&mut PrimitiveRenderer>,
+  text: Option<&mut TextRenderer>,
+  mut record: Option<&mut Recording>,
+) {
+  // While recording, the text renderer is only used for measuring.
+  if let Some(record) = record.as_deref_mut() {
+    for r in &layout.runs {
+      record.push(RecordedPrimitive::Text {
+        text: r.text.clone(),
+        pos: pos + rotate(r.offset, rotation),
+        size: layout.font_size,
+        color,
+        rotation,
+      });
+    }
+  } else if let Some(text) = text {
+    for r in &layout.runs {
+      text.draw_text_rotated(&r.text, pos + rotate(r.offset, rotation), layout.font_size, color, rotation);
+    }
+  }
+  for b in &layout.bitmaps {
+    let (cols, rows) = (b.size.x.ceil().max(1.0) as usize, b.size.y.ceil().max(1.0) as usize);
+    let cell = b.size / Vec2::new(cols as f32, rows as f32);
+    for (i, c) in b.bitmap.resample(cols, rows).into_iter().enumerate() {
+      if c.w <= 0.0 {
+        continue;
+      }
+      let corner = b.offset + cell * Vec2::new((i % cols) as f32, (i / cols) as f32);
+      let [p0, p1, p2, p3] = [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y]
+        .map(|d| (pos + rotate(corner + cell * d, rotation)).extend(0.0));
+      let color = c.truncate().extend(c.w * color.w);
+      for points in [[p0, p1, p2], [p0, p2, p3]] {
+        if let Some(record) = record.as_deref_mut() {
+          record.push(RecordedPrimitive::Triangle { points, color });
+        } else if let Some(prim) = prim.as_deref_mut() {
+          prim.draw_triangle_unlit(points[0], points[1], points[2], color);
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn decodes_and_resamples_bitmaps() {
+    // Half-transparent green, premultiplied BGRA.
+    let b = ColorBitmap::decode(&[0, 128, 0, 128].repeat(4), &GlyphImageFormat::BitmapPremulBgra32, 2, 2).unwrap();
+    assert_eq!(b.pixels[0], [0, 255, 0, 128]);
+    assert!(ColorBitmap::decode(&[0; 4], &GlyphImageFormat::BitmapGray8, 2, 2).is_none());
+
+    let mut font = vec![0u8; 44];
+    font[4..6].copy_from_slice(&2u16.to_be_bytes());
+    font[12..16].copy_from_slice(b"glyf");
+    assert!(!has_color_tables(&font));
+    font[28..32].copy_from_slice(b"CBDT");
+    assert!(has_color_tables(&font));
+    assert!(!has_color_tables(&font[..20]));
+
+    let b = ColorBitmap { width: 2, height: 1, pixels: vec![[255, 0, 0, 255], [0, 0, 255, 0]] };
+    let cells = b.resample(1, 1);
+    // The transparent pixel halves coverage without tinting the color.
+    assert_eq!(cells, vec![Vec4::new(1.0, 0.0, 0.0, 0.5)]);
+    assert_eq!(b.resample(4, 2).len(), 8);
+  }
+
+  #[cfg(feature = "embedded-font")]
+  #[test]
+  fn outline_fonts_have_no_color_glyphs() {
+    use crate::text::EMBEDDED_FONT;
+    use wgpu_text::glyph_brush::ab_glyph::FontArc;
+    let fonts = [FontArc::try_from_slice(EMBEDDED_FONT).unwrap()];
+    assert!(!has_color_glyphs(&fonts, "OK \u{26a0}\u{fe0f}"));
+    let cache = BitmapCache::default();
+    let l = layout(&fonts, "ab\ncd", 20.0, &cache);
+    assert_eq!(l.runs.len(), 2);
+    // Outline fonts are not looked up glyph by glyph.
+    assert!(cache.is_empty());
+    assert!(l.bitmaps.is_empty());
+    assert_eq!(l.runs[1].offset.x, 0.0);
+    assert!((l.size - measure_with_fonts(&fonts, "ab\ncd", 20.0)).abs().max_element() < 1e-3);
+  }
+}
@@ -0,0 +1,132 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Encoding render passes on several threads.
+//!
+//! A dashboard with dozens of subplots, or a figure plus its
+//! thumbnails, spends much of its CPU frame time uploading instances
+//! and recording draw calls.  wgpu devices, queues and command encoders
+//! can be used from any thread, so [`encode_parallel`] splits the
+//! passes over scoped worker threads, each preparing its renderers and
+//! recording into its own command encoder.  The command buffers come
+//! back in pass order and [`submit_parallel`] submits them together, so
+//! passes drawing into the same target still land in order.
+
+use crate::primitives::PrimitiveRenderer;
+use crate::text::TextRenderer;
+use std::ops::Range;
+
+/// One render pass: a plot's renderers drawn into `view`.
+pub struct PassTarget<'a> {
+  /// Queued primitives.
+  pub prim: &'a mut PrimitiveRenderer,
+  /// Queued text.
+  pub text: &'a mut TextRenderer,
+  /// Texture drawn into.
+  pub view: &'a wgpu::TextureView,
+  /// Clear color, or `None` to keep the contents, e.g. for a subplot
+  /// sharing a target cleared by an earlier pass.
+  pub clear: Option<wgpu::Color>,
+}
+
+impl<'a> PassTarget<'a> {
+  /// Pass drawing over the existing contents of `view`.
+  pub fn new(prim: &'a mut PrimitiveRenderer, text: &'a mut TextRenderer, view: &'a wgpu::TextureView) -> Self {
+    Self { prim, text, view, clear: None }
+  }
+
+  /// Clears the target to `color` first.
+  pub fn clear(mut self, color: wgpu::Color) -> Self {
+    self.clear = Some(color);
+    self
+  }
+
+  /// Uploads the queued geometry and records the pass into `encoder`.
+  fn encode(self, device: &wgpu::Device, queue: &wgpu::Queue, encoder: &mut wgpu::CommandEncoder) {
+    self.prim.prepare(device, queue);
+    self.text.prepare(device, queue);
+    let mut rp = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+      label: Some("ParallelPass"),
+      color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+        view: self.view,
+        resolve_target: None,
+        ops: wgpu::Operations {
+          load: self.clear.map_or(wgpu::LoadOp::Load, wgpu::LoadOp::Clear),
+          store: wgpu::StoreOp::Store,
+        },
+      })],
+      depth_stencil_attachment: None,
+      ..Default::default()
+    });
+    self.prim.render(&mut rp);
+    self.text.render(&mut rp);
+  }
+}
+
+/// Splits `n` passes into at most `threads` contiguous, nearly equal
+/// ranges.
+pub fn split(n: usize, threads: usize) -> Vec<Range<usize>> {
+  let parts = threads.clamp(1, n.max(1));
+  (0..parts).map(|i| i * n / parts..(i + 1) * n / parts).filter(|r| !r.is_empty()).collect()
+}
+
+/// Encodes `passes` on up to `threads` threads (`0` for the available
+/// parallelism), one command buffer per thread, returned in pass order.
+pub fn encode_parallel(
+  device: &wgpu::Device,
+  queue: &wgpu::Queue,
+  passes: Vec<PassTarget<'_>>,
+  threads: usize,
+) -> Vec<wgpu::CommandBuffer> {
+  let threads = match threads {
+    0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+    n => n,
+  };
+  let ranges = split(passes.len(), threads);
+  let mut passes = passes.into_iter();
+  let chunks: Vec<Vec<PassTarget<'_>>> = ranges.iter().map(|r| passes.by_ref().take(r.len()).collect()).collect();
+
+  let encode = |chunk: Vec<PassTarget<'_>>| {
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+      label: Some("ParallelEncoder"),
+    });
+    for pass in chunk {
+      pass.encode(device, queue, &mut encoder);
+    }
+    encoder.finish()
+  };
+  if chunks.len() <= 1 {
+    return chunks.into_iter().map(encode).collect();
+  }
+  std::thread::scope(|s| {
+    let workers: Vec<_> = chunks.into_iter().map(|chunk| s.spawn(move || encode(chunk))).collect();
+    workers
+      .into_iter()
+      .map(|w| w.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+      .collect()
+  })
+}
+
+/// Encodes `passes` with [`encode_parallel`] and submits all command
+/// buffers at once.
+pub fn submit_parallel(
+  device: &wgpu::Device,
+  queue: &wgpu::Queue,
+  passes: Vec<PassTarget<'_>>,
+  threads: usize,
+) -> wgpu::SubmissionIndex {
+  queue.submit(encode_parallel(device, queue, passes, threads))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn split_covers_every_pass_in_order() {
+    assert_eq!(split(10, 3), vec![0..3, 3..6, 6..10]);
+    assert_eq!(split(2, 8), vec![0..1, 1..2]);
+    assert_eq!(split(5, 0), vec![0..5]);
+    assert!(split(0, 4).is_empty());
+  }
+}
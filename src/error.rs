@@ -0,0 +1,197 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Crate-level error type.
+//!
+//! Creating a headless device, parsing a font, reading rendered pixels
+//! back, writing image files and opening a window can all fail at
+//! runtime: a CI runner or container may have no GPU adapter or display
+//! at all.  The fallible entry points
+//! ([`HeadlessRenderer::new`](crate::capture::HeadlessRenderer::new),
+//! [`PlotCapture::save_png`](crate::capture::PlotCapture::save_png),
+//! [`RenderContext::new`](crate::context::RenderContext::new), ...)
+//! return [`Result`] so callers can skip or degrade instead of
+//! panicking.
+
+use std::fmt;
+use std::path::PathBuf;
+use wgpu_text::glyph_brush::ab_glyph::InvalidFont;
+
+/// Errors returned by this crate.
+#[derive(Debug)]
+pub enum Error {
+  /// No GPU adapter (hardware or software fallback) is available.
+  AdapterNotFound,
+  /// The adapter refused to create a device.
+  RequestDevice(wgpu::RequestDeviceError),
+  /// Font data is not a valid TrueType or OpenType font.
+  InvalidFont,
+  /// A font file could not be read.
+  FontFile {
+    /// Path of the font file.
+    path: PathBuf,
+    /// Underlying read error.
+    source: std::io::Error,
+  },
+  /// Mapping a read-back buffer failed.
+  BufferMap(wgpu::BufferAsyncError),
+  /// The device was lost before a read-back completed.
+  DeviceLost,
+  /// Encoding or writing an image failed.
+  Image(image::ImageError),
+  /// Other I/O failure.
+  Io(std::io::Error),
+  /// The windowing event loop could not be created or failed.
+  #[cfg(feature = "window")]
+  EventLoop(winit::error::EventLoopError),
+  /// A window could not be created.
+  #[cfg(feature = "window")]
+  Window(winit::error::OsError),
+  /// A window surface could not be created or configured.
+  Surface(wgpu::CreateSurfaceError),
+  /// A plot spec or data frame has no column of this name.
+  UnknownData(String),
+  /// A data column has a type that cannot be plotted as numbers.
+  UnsupportedColumn(String),
+  /// Gnuplot did not write the reference render at this path.
+  Gnuplot(PathBuf),
+  /// No animated image format matches the extension of this path.
+  UnsupportedFormat(PathBuf),
+  /// A frame or image had this many bytes instead of its RGBA size.
+  FrameSize(usize),
+  /// Encoding an animated PNG failed.
+  Png(png::EncodingError),
+  /// An animation without a frame count cannot be saved to a file.
+  EndlessAnimation,
+  /// ffmpeg exited unsuccessfully while encoding a video.
+  Ffmpeg(std::process::ExitStatus),
+  /// The adapter cannot render into and read back this texture format
+  /// with blending.
+  CaptureFormat(wgpu::TextureFormat),
+}
+
+/// `Result` with this crate's [`Error`].
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Error::AdapterNotFound => write!(f, "no suitable GPU adapter found"),
+      Error::RequestDevice(e) => write!(f, "failed to create device: {}", e),
+      Error::InvalidFont => write!(f, "invalid font data"),
+      Error::FontFile { path, source } => {
+        write!(f, "failed to load font from {}: {}", path.display(), source)
+      }
+      Error::BufferMap(e) => write!(f, "failed to map read-back buffer: {}", e),
+      Error::DeviceLost => write!(f, "device lost during read-back"),
+      Error::Image(e) => write!(f, "failed to save image: {}", e),
+      Error::Io(e) => write!(f, "{}", e),
+      #[cfg(feature = "window")]
+      Error::EventLoop(e) => write!(f, "event loop failed: {}", e),
+      #[cfg(feature = "window")]
+      Error::Window(e) => write!(f, "failed to create window: {}", e),
+      Error::Surface(e) => write!(f, "failed to create surface: {}", e),
+      Error::UnknownData(name) => write!(f, "unknown data column \"{}\"", name),
+      Error::UnsupportedColumn(ty) => write!(f, "unsupported column type {}", ty),
+      Error::Gnuplot(path) => write!(f, "gnuplot failed to write {}", path.display()),
+      Error::UnsupportedFormat(path) => write!(f, "no animation format for {}", path.display()),
+      Error::FrameSize(len) => write!(f, "frame of {} bytes does not match the image size", len),
+      Error::Png(e) => write!(f, "failed to encode PNG: {}", e),
+      Error::EndlessAnimation => write!(f, "animation has no frame count"),
+      Error::Ffmpeg(status) => write!(f, "ffmpeg failed: {}", status),
+      Error::CaptureFormat(format) => write!(f, "cannot capture into {:?} textures", format),
+    }
+  }
+}
+
+impl std::error::Error for Error {
+  fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+    match self {
+      Error::RequestDevice(e) => Some(e),
+      Error::FontFile { source, .. } => Some(source),
+      Error::BufferMap(e) => Some(e),
+      Error::Image(e) => Some(e),
+      Error::Io(e) => Some(e),
+      #[cfg(feature = "window")]
+      Error::EventLoop(e) => Some(e),
+      #[cfg(feature = "window")]
+      Error::Window(e) => Some(e),
+      Error::Surface(e) => Some(e),
+      Error::Png(e) => Some(e),
+      _ => None,
+    }
+  }
+}
+
+impl From<wgpu::RequestDeviceError> for Error {
+  fn from(e: wgpu::RequestDeviceError) -> Self {
+    Error::RequestDevice(e)
+  }
+}
+
+impl From<InvalidFont> for Error {
+  fn from(_: InvalidFont) -> Self {
+    Error::InvalidFont
+  }
+}
+
+impl From<wgpu::BufferAsyncError> for Error {
+  fn from(e: wgpu::BufferAsyncError) -> Self {
+    Error::BufferMap(e)
+  }
+}
+
+impl From<image::ImageError> for Error {
+  fn from(e: image::ImageError) -> Self {
+    Error::Image(e)
+  }
+}
+
+impl From<std::io::Error> for Error {
+  fn from(e: std::io::Error) -> Self {
+    Error::Io(e)
+  }
+}
+
+#[cfg(feature = "window")]
+impl From<winit::error::EventLoopError> for Error {
+  fn from(e: winit::error::EventLoopError) -> Self {
+    Error::EventLoop(e)
+  }
+}
+
+#[cfg(feature = "window")]
+impl From<winit::error::OsError> for Error {
+  fn from(e: winit::error::OsError) -> Self {
+    Error::Window(e)
+  }
+}
+
+impl From<wgpu::CreateSurfaceError> for Error {
+  fn from(e: wgpu::CreateSurfaceError) -> Self {
+    Error::Surface(e)
+  }
+}
+
+impl From<png::EncodingError> for Error {
+  fn from(e: png::EncodingError) -> Self {
+    Error::Png(e)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn messages_and_sources() {
+    let e = Error::FontFile {
+      path: PathBuf::from("/no/font.ttf"),
+      source: std::io::Error::from(std::io::ErrorKind::NotFound),
+    };
+    assert!(e.to_string().starts_with("failed to load font from /no/font.ttf"));
+    assert!(std::error::Error::source(&e).is_some());
+    assert_eq!(Error::from(InvalidFont).to_string(), "invalid font data");
+    assert!(std::error::Error::source(&Error::AdapterNotFound).is_none());
+  }
+}
@@ -0,0 +1,108 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Interaction events.
+//!
+//! Embedding applications keeping their own UI in step with a plot
+//! (a linked table highlighting the picked row, a zoom history, a
+//! legend panel) would otherwise poll the backend after every input.
+//! [`PlotBackend::subscribe`](crate::plotting::PlotBackend::subscribe)
+//! hands out a channel receiver of [`PlotEvent`]s instead, sent as the
+//! interactions happen:
+//!
+//! - [`LimitsChanged`](PlotEvent::LimitsChanged) by a render that found
+//!   the current axes' limits moved, whatever moved them;
+//! - [`SeriesPicked`](PlotEvent::SeriesPicked) by a
+//!   [`pick`](crate::plotting::PlotBackend::pick) that hit a point;
+//! - [`TooltipShown`](PlotEvent::TooltipShown) by
+//!   [`set_tooltip`](crate::plotting::PlotBackend::set_tooltip);
+//! - [`LegendToggled`](PlotEvent::LegendToggled) by
+//!   [`toggle_series`](crate::plotting::PlotBackend::toggle_series).
+//!
+//! Receivers can move to other threads, and dropping one unsubscribes
+//! it.  For the limits or camera alone, see [`watch`](crate::watch).
+
+use crate::picking::PickResult;
+use crate::plotting::SeriesId;
+use crate::tooltip::Tooltip;
+use crate::watch::AxisLimits;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+/// Something the user did to the plot.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlotEvent {
+  /// The current axes' limits changed to these.
+  LimitsChanged(AxisLimits),
+  /// A pick hit this point.
+  SeriesPicked(PickResult),
+  /// This tooltip was shown, or `None` when the tooltip was hidden.
+  TooltipShown(Option<Tooltip>),
+  /// A series was shown or hidden from its legend entry.
+  LegendToggled {
+    /// The series.
+    id: SeriesId,
+    /// Whether it is now shown.
+    visible: bool,
+  },
+}
+
+/// Subscribers to [`PlotEvent`]s.
+#[derive(Default)]
+pub(crate) struct Events {
+  senders: Vec<Sender<PlotEvent>>,
+  limits: Option<AxisLimits>,
+}
+
+impl Events {
+  /// New subscriber, sent the events from now on.
+  pub(crate) fn subscribe(&mut self) -> Receiver<PlotEvent> {
+    let (tx, rx) = mpsc::channel();
+    self.senders.push(tx);
+    rx
+  }
+
+  /// Whether anyone is subscribed, to skip building events otherwise.
+  pub(crate) fn is_watched(&self) -> bool {
+    !self.senders.is_empty()
+  }
+
+  /// Sends `event` to every subscriber, dropping the subscribers whose
+  /// receiver is gone.
+  pub(crate) fn emit(&mut self, event: PlotEvent) {
+    self.senders.retain(|tx| tx.send(event.clone()).is_ok());
+  }
+
+  /// Sends [`PlotEvent::LimitsChanged`] if `limits` differ from the
+  /// last ones seen.  The first limits seen are not a change.
+  pub(crate) fn limits(&mut self, limits: AxisLimits) {
+    match self.limits.replace(limits) {
+      Some(last) if last != limits => self.emit(PlotEvent::LimitsChanged(limits)),
+      _ => {}
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn only_limit_changes_are_sent() {
+    let mut events = Events::default();
+    let rx = events.subscribe();
+    let a = AxisLimits { x: (0.0, 1.0), y: (0.0, 1.0), z: (0.0, 1.0) };
+    let b = AxisLimits { x: (0.0, 2.0), ..a };
+    events.limits(a);
+    events.limits(a);
+    events.limits(b);
+    events.emit(PlotEvent::TooltipShown(None));
+    assert_eq!(
+      rx.try_iter().collect::<Vec<_>>(),
+      vec![PlotEvent::LimitsChanged(b), PlotEvent::TooltipShown(None)]
+    );
+
+    drop(rx);
+    events.emit(PlotEvent::TooltipShown(None));
+    assert!(!events.is_watched());
+  }
+}
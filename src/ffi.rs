@@ -20,6 +20,9 @@ pub struct MplFigure { _private: [u8; 0] }
 #[repr(C)]
 pub struct MplAxes { _private: [u8; 0] }
 
+#[repr(C)]
+pub struct MplLine { _private: [u8; 0] }
+
 // Primitive Structures matching C API
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -97,29 +100,103 @@ extern "C" {
     pub fn mpl_figure_create(backend: *mut MplWgpuBackend) -> *mut MplFigure;
     pub fn mpl_figure_create_gnuplot() -> *mut MplFigure;
     pub fn mpl_figure_destroy(fig: *mut MplFigure);
-    pub fn mpl_figure_draw(fig: *mut MplFigure);
+    pub fn mpl_figure_draw(fig: *mut MplFigure) -> *const c_char;
+    pub fn mpl_figure_draw_layer(fig: *mut MplFigure, decorations: bool, ids: *const u64, count: usize, listed: bool) -> *const c_char;
     pub fn mpl_figure_current_axes(fig: *mut MplFigure) -> *mut MplAxes;
     pub fn mpl_figure_clear(fig: *mut MplFigure);
+    pub fn mpl_figure_set_background(fig: *mut MplFigure, r: c_float, g: c_float, b: c_float, a: c_float);
+    pub fn mpl_axes_set_background(ax: *mut MplAxes, r: c_float, g: c_float, b: c_float, a: c_float);
     pub fn mpl_figure_save(
       fig: *mut MplFigure, path: *const c_char,
     ) -> bool;
+    pub fn mpl_figure_save_as(fig: *mut MplFigure, path: *const c_char, terminal: *const c_char) -> bool;
+    pub fn mpl_figure_set_size(fig: *mut MplFigure, width: u32, height: u32);
+    pub fn mpl_figure_set_font(fig: *mut MplFigure, name: *const c_char, size: c_float);
 
     pub fn mpl_axes_destroy(ax: *mut MplAxes);
-    pub fn mpl_axes_plot(ax: *mut MplAxes, x: *const c_double, y: *const c_double, count: usize, style: *const c_char);
-    pub fn mpl_axes_scatter(ax: *mut MplAxes, x: *const c_double, y: *const c_double, count: usize, style: *const c_char);
+    pub fn mpl_axes_clone(ax: *mut MplAxes) -> *mut MplAxes;
+    pub fn mpl_axes_plot(ax: *mut MplAxes, x: *const c_double, y: *const c_double, count: usize, style: *const c_char) -> *mut MplLine;
+    pub fn mpl_axes_scatter(ax: *mut MplAxes, x: *const c_double, y: *const c_double, count: usize, style: *const c_char) -> *mut MplLine;
+    pub fn mpl_axes_plot3(ax: *mut MplAxes, x: *const c_double, y: *const c_double, z: *const c_double, count: usize, style: *const c_char) -> *mut MplLine;
+    pub fn mpl_axes_scatter3(ax: *mut MplAxes, x: *const c_double, y: *const c_double, z: *const c_double, count: usize, style: *const c_char) -> *mut MplLine;
+    pub fn mpl_line_destroy(line: *mut MplLine);
+    pub fn mpl_line_set_color(line: *mut MplLine, r: c_float, g: c_float, b: c_float);
+    pub fn mpl_line_set_width(line: *mut MplLine, width: c_float);
+    pub fn mpl_line_set_style(line: *mut MplLine, style: *const c_char);
+    pub fn mpl_line_set_label(line: *mut MplLine, label: *const c_char);
+    pub fn mpl_line_set_marker_size(line: *mut MplLine, size: c_float);
+    pub fn mpl_line_set_group(line: *mut MplLine, group: *const c_char);
+    pub fn mpl_line_append(line: *mut MplLine, x: *const c_double, y: *const c_double, count: usize, max_len: usize);
+    pub fn mpl_line_id(line: *mut MplLine) -> u64;
+    pub fn mpl_axes_update_series(ax: *mut MplAxes, id: u64, x: *const c_double, y: *const c_double, count: usize) -> bool;
+    pub fn mpl_axes_append_series(ax: *mut MplAxes, id: u64, x: *const c_double, y: *const c_double, count: usize, max_len: usize) -> bool;
+    pub fn mpl_axes_remove_series(ax: *mut MplAxes, id: u64) -> bool;
+    pub fn mpl_axes_set_series_visible(ax: *mut MplAxes, id: u64, visible: bool) -> bool;
+    pub fn mpl_axes_series_visible(ax: *mut MplAxes, id: u64, visible: *mut bool) -> bool;
+    pub fn mpl_axes_series_data(ax: *mut MplAxes, x: *mut c_double, y: *mut c_double, capacity: usize) -> usize;
+    pub fn mpl_axes_series_ids(ax: *mut MplAxes, ids: *mut u64, capacity: usize) -> usize;
+    pub fn mpl_axes_series_points(ax: *mut MplAxes, id: u64, x: *mut c_double, y: *mut c_double, capacity: usize) -> usize;
+    pub fn mpl_series_revision() -> u64;
+    pub fn mpl_axes_set_series_group(ax: *mut MplAxes, id: u64, group: *const c_char) -> bool;
+    pub fn mpl_axes_group_series(ax: *mut MplAxes, group: *const c_char, ids: *mut u64, capacity: usize) -> usize;
+    pub fn mpl_axes_set_series_alpha(ax: *mut MplAxes, id: u64, alpha: c_float) -> bool;
+    pub fn mpl_axes_set_series_color(ax: *mut MplAxes, id: u64, r: c_float, g: c_float, b: c_float) -> bool;
+    pub fn mpl_axes_set_series_width(ax: *mut MplAxes, id: u64, width: c_float) -> bool;
+    pub fn mpl_axes_scatter_rgb(ax: *mut MplAxes, x: *const c_double, y: *const c_double, count: usize, style: *const c_char, r: c_float, g: c_float, b: c_float) -> *mut MplLine;
     pub fn mpl_axes_bar(ax: *mut MplAxes, values: *const c_double, count: usize);
     pub fn mpl_axes_hist(ax: *mut MplAxes, values: *const c_double, count: usize, bins: usize);
+    pub fn mpl_axes_hist_edges(ax: *mut MplAxes, values: *const c_double, count: usize, edges: *const c_double, n_edges: usize);
     pub fn mpl_axes_surface(ax: *mut MplAxes, x: *const c_double, y: *const c_double, z: *const c_double, rows: usize, cols: usize, wireframe: bool);
     pub fn mpl_axes_pie(ax: *mut MplAxes, values: *const c_double, count: usize);
     pub fn mpl_axes_boxplot(ax: *mut MplAxes, values: *const c_double, count: usize);
     pub fn mpl_axes_heatmap(ax: *mut MplAxes, z: *const c_double, rows: usize, cols: usize);
+    pub fn mpl_axes_set_colormap(ax: *mut MplAxes, rgb: *const c_float, count: usize);
+    pub fn mpl_axes_plot_with_band(ax: *mut MplAxes, x: *const c_double, y: *const c_double, y_low: *const c_double, y_high: *const c_double, count: usize, style: *const c_char, alpha: c_float) -> *mut MplLine;
     
     pub fn mpl_axes_set_title(ax: *mut MplAxes, title: *const c_char);
     pub fn mpl_axes_set_xlabel(ax: *mut MplAxes, label: *const c_char);
     pub fn mpl_axes_set_ylabel(ax: *mut MplAxes, label: *const c_char);
+    pub fn mpl_axes_set_title_style(ax: *mut MplAxes, size: c_float, r: c_float, g: c_float, b: c_float, bold: bool);
+    pub fn mpl_axes_set_label_style(ax: *mut MplAxes, axis: c_char, size: c_float, r: c_float, g: c_float, b: c_float, bold: bool);
+    pub fn mpl_axes_set_tick_label_style(ax: *mut MplAxes, size: c_float, r: c_float, g: c_float, b: c_float, bold: bool);
     pub fn mpl_axes_grid(ax: *mut MplAxes, on: bool);
+    pub fn mpl_axes_legend(ax: *mut MplAxes, on: bool);
     pub fn mpl_axes_set_xlim(ax: *mut MplAxes, min: c_double, max: c_double);
     pub fn mpl_axes_set_ylim(ax: *mut MplAxes, min: c_double, max: c_double);
+    pub fn mpl_axes_get_xlim(ax: *mut MplAxes, min: *mut c_double, max: *mut c_double);
+    pub fn mpl_axes_get_ylim(ax: *mut MplAxes, min: *mut c_double, max: *mut c_double);
+    pub fn mpl_axes_set_zlim(ax: *mut MplAxes, min: c_double, max: c_double);
+    pub fn mpl_axes_get_zlim(ax: *mut MplAxes, min: *mut c_double, max: *mut c_double);
+    pub fn mpl_axes_set_view(ax: *mut MplAxes, azimuth: c_float, elevation: c_float);
+    pub fn mpl_axes_get_view(ax: *mut MplAxes, azimuth: *mut c_float, elevation: *mut c_float);
+    pub fn mpl_axes_is_3d(ax: *mut MplAxes) -> bool;
+    pub fn mpl_axes_key(ax: *mut MplAxes) -> usize;
+    pub fn mpl_axes_autoscale(ax: *mut MplAxes);
+    pub fn mpl_axes_get_limits_auto(ax: *mut MplAxes, x: *mut bool, y: *mut bool);
+    pub fn mpl_axes_set_limits_auto(ax: *mut MplAxes, x: bool, y: bool);
+    pub fn mpl_axes_get_data_extent(ax: *mut MplAxes, x_min: *mut c_double, x_max: *mut c_double, y_min: *mut c_double, y_max: *mut c_double) -> bool;
+    pub fn mpl_axes_set_clim(ax: *mut MplAxes, min: c_double, max: c_double);
+    pub fn mpl_axes_get_clim(ax: *mut MplAxes, min: *mut c_double, max: *mut c_double);
+    pub fn mpl_axes_get_pixel_extent(ax: *mut MplAxes, x0: *mut c_float, x1: *mut c_float, figure_width: *mut c_float);
+    pub fn mpl_axes_get_pixel_box(ax: *mut MplAxes, left: *mut c_float, top: *mut c_float, right: *mut c_float, bottom: *mut c_float);
+    pub fn mpl_axes_get_font_size(ax: *mut MplAxes) -> c_float;
+    pub fn mpl_axes_set_tick_font_size(ax: *mut MplAxes, size: c_float);
+    pub fn mpl_axes_set_xtickangle(ax: *mut MplAxes, degrees: c_float);
+    pub fn mpl_axes_despine(ax: *mut MplAxes, offset: c_float);
+    pub fn mpl_axes_set_axis_visible(ax: *mut MplAxes, visible: bool);
+    pub fn mpl_axes_set_position(ax: *mut MplAxes, x: c_float, y: c_float, width: c_float, height: c_float);
+    pub fn mpl_axes_set_tick_padding(ax: *mut MplAxes, padding: c_float);
+    pub fn mpl_axes_add_y_axis(ax: *mut MplAxes, offset: c_float, r: c_float, g: c_float, b: c_float) -> *mut MplAxes;
+    pub fn mpl_axes_hold(ax: *mut MplAxes, on: bool) -> bool;
+    pub fn mpl_axes_text(ax: *mut MplAxes, x: c_double, y: c_double, text: *const c_char, r: c_float, g: c_float, b: c_float);
+    pub fn mpl_axes_arrow(ax: *mut MplAxes, x0: c_double, y0: c_double, x1: c_double, y1: c_double, r: c_float, g: c_float, b: c_float);
+    pub fn mpl_axes_line(ax: *mut MplAxes, x0: c_double, y0: c_double, x1: c_double, y1: c_double, r: c_float, g: c_float, b: c_float);
+    pub fn mpl_axes_set_xticks(ax: *mut MplAxes, ticks: *const c_double, count: usize);
+    pub fn mpl_axes_set_xticklabels(ax: *mut MplAxes, labels: *const *const c_char, count: usize);
+    pub fn mpl_axes_set_yticks(ax: *mut MplAxes, ticks: *const c_double, count: usize);
+    pub fn mpl_axes_set_yticklabels(ax: *mut MplAxes, labels: *const *const c_char, count: usize);
+    pub fn mpl_axes_get_ticks(ax: *mut MplAxes, axis: c_char, ticks: *mut c_double, capacity: usize, automatic: *mut bool) -> usize;
+    pub fn mpl_axes_set_ticks_auto(ax: *mut MplAxes, axis: c_char);
 }
 
 // Re-export c_uint for convenience
@@ -0,0 +1,86 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Video encoding through an external `ffmpeg`.
+//!
+//! An [`FfmpegEncoder`] starts `ffmpeg` reading raw RGBA frames from
+//! its standard input and encoding them into the output file, so any
+//! container and codec ffmpeg picks for the extension (`.mp4`, `.webm`,
+//! `.mov`, ...) works without linking a codec library.  Frames are
+//! converted to YUV 4:2:0, padded to even dimensions, for players that
+//! accept nothing else.  Encoding needs `ffmpeg` on the `PATH`.
+//!
+//! [`PlotCapture::record`](crate::capture::PlotCapture::record) renders
+//! a movie of an evolving plot with it.
+
+use crate::error::{Error, Result};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+
+/// Running `ffmpeg` encoding frames piped into it.
+pub struct FfmpegEncoder {
+  child: Child,
+  stdin: ChildStdin,
+}
+
+impl FfmpegEncoder {
+  /// Starts `ffmpeg` writing `width` x `height` frames at `fps` frames
+  /// per second to `path`, overwriting it.
+  pub fn spawn(path: impl AsRef<Path>, width: u32, height: u32, fps: f32) -> Result<Self> {
+    let mut child = Command::new("ffmpeg")
+      .args(args(path.as_ref(), width, height, fps))
+      .stdin(Stdio::piped())
+      .stdout(Stdio::null())
+      .stderr(Stdio::null())
+      .spawn()?;
+    let stdin = child.stdin.take().expect("stdin is piped");
+    Ok(Self { child, stdin })
+  }
+
+  /// Sends a frame of tightly packed RGBA pixels.
+  pub fn push(&mut self, pixels: &[u8]) -> Result<()> {
+    self.stdin.write_all(pixels)?;
+    Ok(())
+  }
+
+  /// Closes the input and waits for `ffmpeg` to finish the file.  Fails
+  /// with [`Error::Ffmpeg`] if it exits unsuccessfully.
+  pub fn finish(mut self) -> Result<()> {
+    drop(self.stdin);
+    let status = self.child.wait()?;
+    if !status.success() {
+      return Err(Error::Ffmpeg(status));
+    }
+    Ok(())
+  }
+}
+
+/// Command line arguments encoding raw RGBA frames from standard input
+/// to `path`.
+pub fn args(path: &Path, width: u32, height: u32, fps: f32) -> Vec<String> {
+  let size = format!("{}x{}", width, height);
+  let rate = fps.to_string();
+  let path = path.to_string_lossy();
+  [
+    "-y", "-loglevel", "error",
+    "-f", "rawvideo", "-pix_fmt", "rgba", "-s", &size, "-r", &rate, "-i", "-",
+    "-vf", "pad=ceil(iw/2)*2:ceil(ih/2)*2", "-pix_fmt", "yuv420p", &path,
+  ]
+  .iter()
+  .map(|s| s.to_string())
+  .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn reads_raw_frames_from_stdin() {
+    let args = args(Path::new("out/movie.mp4"), 640, 480, 24.0);
+    let after = |flag: &str| args[args.iter().position(|a| a == flag).unwrap() + 1].as_str();
+    assert_eq!((after("-s"), after("-r"), after("-i")), ("640x480", "24", "-"));
+    assert_eq!(args.last().unwrap(), "out/movie.mp4");
+  }
+}
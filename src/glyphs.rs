@@ -0,0 +1,432 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Glyph atlas and text pipeline shared by text renderers.
+//!
+//! A [`TextRenderer`](crate::text::TextRenderer) lays out its text on
+//! the CPU and draws one quad per glyph, sampled from a coverage atlas.
+//! The atlas and the pipeline live in a [`GlyphCache`], which every
+//! renderer handed out by a [`RenderContext`](crate::context::RenderContext)
+//! shares: a glyph rasterized for one plot is reused by all the others.
+//!
+//! Glyphs never move once placed.  A full atlas is copied into one
+//! twice the size, and past the largest size a fresh atlas is started.
+//! Renderers draw from the atlas texture they were prepared with until
+//! their next prepare, so neither disturbs text queued elsewhere.
+
+use bytemuck::{Pod, Zeroable};
+use glam::Vec2;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use wgpu_text::glyph_brush::ab_glyph::{Font, FontArc, Glyph};
+
+/// Side of a new atlas, in texels.
+const INITIAL_SIZE: u32 = 512;
+
+/// Largest atlas side, unless the device allows less.
+const MAX_SIZE: u32 = 4096;
+
+/// Empty texels right of and below each glyph, so sampling rotated
+/// glyphs does not bleed into their neighbours.
+const PADDING: u32 = 1;
+
+/// One glyph quad, as the shader takes it.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+pub(crate) struct GlyphInstance {
+  /// Top-left corner, in clip space.
+  pub origin: [f32; 2],
+  /// Top edge, left to right, in clip space.
+  pub axis_x: [f32; 2],
+  /// Left edge, top to bottom, in clip space.
+  pub axis_y: [f32; 2],
+  /// Atlas texels: left, top, width, height.
+  pub uv: [f32; 4],
+  pub color: [f32; 4],
+}
+
+/// A rasterized glyph in the atlas.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct AtlasGlyph {
+  /// Atlas texels: left, top, width, height.
+  pub uv: [f32; 4],
+  /// Top-left corner of the raster from the glyph's whole-pixel
+  /// origin.
+  pub offset: Vec2,
+}
+
+/// Glyph identity in the atlas: font, glyph, scale and subpixel
+/// offset in sixteenths of a pixel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct GlyphKey {
+  font: usize,
+  id: u16,
+  scale: [u32; 2],
+  offset: [u8; 2],
+}
+
+/// Atlas texture and what is in it.
+struct Atlas {
+  texture: wgpu::Texture,
+  bind_group: Arc<wgpu::BindGroup>,
+  size: u32,
+  /// Rows of glyphs: top, height and used width.
+  shelves: Vec<[u32; 3]>,
+  /// Top of the space below the shelves.
+  bottom: u32,
+  /// Placed glyphs, `None` for blank ones.
+  glyphs: HashMap<GlyphKey, Option<AtlasGlyph>>,
+  /// Fonts with glyphs here.  Holding them keeps their data alive, so
+  /// its address identifies the font.
+  fonts: Vec<FontArc>,
+}
+
+impl Atlas {
+  /// Index of `font` in `fonts`, added if new.
+  fn font_index(&mut self, font: &FontArc) -> usize {
+    let data = font.font_data();
+    let same = |f: &FontArc| {
+      std::ptr::eq(f.font_data().as_ptr(), data.as_ptr()) && f.font_data().len() == data.len()
+    };
+    match self.fonts.iter().position(same) {
+      Some(i) => i,
+      None => {
+        self.fonts.push(font.clone());
+        self.fonts.len() - 1
+      }
+    }
+  }
+
+  /// Top-left texel of a free `width` x `height` area, or `None` if
+  /// the atlas has no room for it.
+  fn allocate(&mut self, width: u32, height: u32) -> Option<[u32; 2]> {
+    let (w, h) = (width + PADDING, height + PADDING);
+    // A shelf at most twice as tall as the glyph, so short glyphs do
+    // not waste tall rows.
+    let size = self.size;
+    if let Some(shelf) =
+      self.shelves.iter_mut().find(|s| s[1] >= h && s[1] <= 2 * h && s[2] + w <= size)
+    {
+      let at = [shelf[2], shelf[0]];
+      shelf[2] += w;
+      return Some(at);
+    }
+    if w > size || self.bottom + h > size {
+      return None;
+    }
+    self.shelves.push([self.bottom, h, w]);
+    self.bottom += h;
+    Some([0, self.bottom - h])
+  }
+}
+
+/// Text pipeline and glyph atlas for targets of one format and sample
+/// count.
+pub(crate) struct GlyphCache {
+  pipeline: wgpu::RenderPipeline,
+  bind_group_layout: wgpu::BindGroupLayout,
+  sampler: wgpu::Sampler,
+  max_size: u32,
+  atlas: Mutex<Atlas>,
+}
+
+impl GlyphCache {
+  pub(crate) fn new(device: &wgpu::Device, format: wgpu::TextureFormat, samples: u32) -> Self {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+      label: Some("GlyphShader"),
+      source: wgpu::ShaderSource::Wgsl(include_str!("glyphs.wgsl").into()),
+    });
+
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+      label: Some("GlyphAtlas"),
+      entries: &[
+        wgpu::BindGroupLayoutEntry {
+          binding: 0,
+          visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+          ty: wgpu::BindingType::Texture {
+            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+            view_dimension: wgpu::TextureViewDimension::D2,
+            multisampled: false,
+          },
+          count: None,
+        },
+        wgpu::BindGroupLayoutEntry {
+          binding: 1,
+          visibility: wgpu::ShaderStages::FRAGMENT,
+          ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+          count: None,
+        },
+      ],
+    });
+
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+      label: None,
+      bind_group_layouts: &[&bind_group_layout],
+      push_constant_ranges: &[],
+    });
+
+    let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+      label: Some("GlyphPipeline"),
+      layout: Some(&layout),
+      vertex: wgpu::VertexState {
+        module: &shader,
+        entry_point: "vs_main",
+        buffers: &[wgpu::VertexBufferLayout {
+          array_stride: std::mem::size_of::<GlyphInstance>() as wgpu::BufferAddress,
+          step_mode: wgpu::VertexStepMode::Instance,
+          attributes: &wgpu::vertex_attr_array![
+            0 => Float32x2, // origin
+            1 => Float32x2, // axis_x
+            2 => Float32x2, // axis_y
+            3 => Float32x4, // uv
+            4 => Float32x4, // color
+          ],
+        }],
+        compilation_options: wgpu::PipelineCompilationOptions::default(),
+      },
+      fragment: Some(wgpu::FragmentState {
+        module: &shader,
+        entry_point: "fs_main",
+        targets: &[Some(wgpu::ColorTargetState {
+          format,
+          blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+          write_mask: wgpu::ColorWrites::ALL,
+        })],
+        compilation_options: wgpu::PipelineCompilationOptions::default(),
+      }),
+      primitive: wgpu::PrimitiveState::default(),
+      depth_stencil: None,
+      multisample: wgpu::MultisampleState { count: samples, ..Default::default() },
+      multiview: None,
+    });
+
+    let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+      label: Some("GlyphSampler"),
+      mag_filter: wgpu::FilterMode::Linear,
+      min_filter: wgpu::FilterMode::Linear,
+      ..Default::default()
+    });
+
+    let max_size = MAX_SIZE.min(device.limits().max_texture_dimension_2d);
+    let atlas = Mutex::new(Self::atlas(device, &bind_group_layout, &sampler, INITIAL_SIZE));
+    Self { pipeline, bind_group_layout, sampler, max_size, atlas }
+  }
+
+  /// Empty `size` x `size` atlas.
+  fn atlas(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    sampler: &wgpu::Sampler,
+    size: u32,
+  ) -> Atlas {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+      label: Some("GlyphAtlas"),
+      size: wgpu::Extent3d { width: size, height: size, depth_or_array_layers: 1 },
+      mip_level_count: 1,
+      sample_count: 1,
+      dimension: wgpu::TextureDimension::D2,
+      format: wgpu::TextureFormat::R8Unorm,
+      usage: wgpu::TextureUsages::TEXTURE_BINDING
+        | wgpu::TextureUsages::COPY_DST
+        | wgpu::TextureUsages::COPY_SRC,
+      view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+      label: None,
+      layout,
+      entries: &[
+        wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&view) },
+        wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(sampler) },
+      ],
+    });
+    Atlas {
+      texture,
+      bind_group: Arc::new(bind_group),
+      size,
+      shelves: Vec::new(),
+      bottom: 0,
+      glyphs: HashMap::new(),
+      fonts: Vec::new(),
+    }
+  }
+
+  /// The text pipeline.
+  pub(crate) fn pipeline(&self) -> &wgpu::RenderPipeline {
+    &self.pipeline
+  }
+
+  /// Number of glyphs in the atlas, blank ones included.
+  pub(crate) fn len(&self) -> usize {
+    self.atlas.lock().unwrap().glyphs.len()
+  }
+
+  /// Side of the atlas, in texels.
+  pub(crate) fn size(&self) -> u32 {
+    self.atlas.lock().unwrap().size
+  }
+
+  /// Places `glyphs` in the atlas, each a font and a glyph positioned at
+  /// its subpixel offset within `[0, 1)`, rasterizing those not placed
+  /// before.  Returns where each is, `None` for blank glyphs, and the
+  /// bind group of the atlas texture holding them.
+  ///
+  /// If the atlas fills up even at its largest size, a fresh one is
+  /// started for these glyphs; glyphs that do not fit one alone are
+  /// left out.
+  pub(crate) fn place(
+    &self,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    glyphs: &[(&FontArc, Glyph)],
+  ) -> (Vec<Option<AtlasGlyph>>, Arc<wgpu::BindGroup>) {
+    let mut atlas = self.atlas.lock().unwrap();
+    let fresh = atlas.glyphs.is_empty();
+    let (mut placed, complete) = self.place_in(&mut atlas, device, queue, glyphs);
+    if !complete && !fresh {
+      *atlas = Self::atlas(device, &self.bind_group_layout, &self.sampler, INITIAL_SIZE);
+      placed = self.place_in(&mut atlas, device, queue, glyphs).0;
+    }
+    (placed, atlas.bind_group.clone())
+  }
+
+  /// [`place`](Self::place) into `atlas`, and whether every glyph fit.
+  fn place_in(
+    &self,
+    atlas: &mut Atlas,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    glyphs: &[(&FontArc, Glyph)],
+  ) -> (Vec<Option<AtlasGlyph>>, bool) {
+    let mut complete = true;
+    let placed = glyphs
+      .iter()
+      .map(|&(font, ref glyph)| {
+        let key = GlyphKey {
+          font: atlas.font_index(font),
+          id: glyph.id.0,
+          scale: [glyph.scale.x.to_bits(), glyph.scale.y.to_bits()],
+          offset: [
+            (glyph.position.x * 16.0).round() as u8,
+            (glyph.position.y * 16.0).round() as u8,
+          ],
+        };
+        if let Some(&placed) = atlas.glyphs.get(&key) {
+          return placed;
+        }
+        let Some(outlined) = font.outline_glyph(glyph.clone()) else {
+          atlas.glyphs.insert(key, None);
+          return None;
+        };
+        let bounds = outlined.px_bounds();
+        let (width, height) = (bounds.width() as u32, bounds.height() as u32);
+        if width == 0 || height == 0 {
+          atlas.glyphs.insert(key, None);
+          return None;
+        }
+        let at = loop {
+          if let Some(at) = atlas.allocate(width, height) {
+            break Some(at);
+          }
+          if !self.grow(atlas, device, queue) {
+            break None;
+          }
+        };
+        let Some([x, y]) = at else {
+          complete = false;
+          return None;
+        };
+        let mut coverage = vec![0u8; (width * height) as usize];
+        outlined.draw(|cx, cy, c| {
+          coverage[(cy * width + cx) as usize] = (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        });
+        queue.write_texture(
+          wgpu::ImageCopyTexture {
+            texture: &atlas.texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d { x, y, z: 0 },
+            aspect: wgpu::TextureAspect::All,
+          },
+          &coverage,
+          wgpu::ImageDataLayout { offset: 0, bytes_per_row: Some(width), rows_per_image: Some(height) },
+          wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+        );
+        let placed = AtlasGlyph {
+          uv: [x as f32, y as f32, width as f32, height as f32],
+          offset: Vec2::new(bounds.min.x, bounds.min.y),
+        };
+        atlas.glyphs.insert(key, Some(placed));
+        Some(placed)
+      })
+      .collect();
+    (placed, complete)
+  }
+
+  /// Copies `atlas` into a texture twice the size, keeping every glyph
+  /// where it is.  Returns `false` at the largest size.
+  fn grow(&self, atlas: &mut Atlas, device: &wgpu::Device, queue: &wgpu::Queue) -> bool {
+    let size = atlas.size * 2;
+    if size > self.max_size {
+      return false;
+    }
+    let grown = Self::atlas(device, &self.bind_group_layout, &self.sampler, size);
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+      label: Some("GlyphAtlasGrow"),
+    });
+    encoder.copy_texture_to_texture(
+      atlas.texture.as_image_copy(),
+      grown.texture.as_image_copy(),
+      wgpu::Extent3d { width: atlas.size, height: atlas.size, depth_or_array_layers: 1 },
+    );
+    // Glyphs written before are uploaded ahead of the copy.
+    queue.submit([encoder.finish()]);
+    atlas.texture = grown.texture;
+    atlas.bind_group = grown.bind_group;
+    atlas.size = size;
+    true
+  }
+}
+
+#[cfg(all(test, feature = "embedded-font"))]
+mod tests {
+  use super::*;
+  use crate::capture::{shared_context, CAPTURE_FORMAT};
+  use crate::text::EMBEDDED_FONT;
+  use wgpu_text::glyph_brush::ab_glyph::{point, PxScale};
+
+  #[test]
+  fn shelves_pack_left_to_right_then_down() {
+    let ctx = shared_context().expect("headless GPU context");
+    let cache = GlyphCache::new(ctx.device(), CAPTURE_FORMAT, 1);
+    let mut atlas = cache.atlas.lock().unwrap();
+    assert_eq!(atlas.allocate(10, 20), Some([0, 0]));
+    assert_eq!(atlas.allocate(10, 15), Some([11, 0]));
+    // Too short for the first shelf.
+    assert_eq!(atlas.allocate(10, 5), Some([0, 21]));
+    assert_eq!(atlas.allocate(INITIAL_SIZE + 1, 5), None);
+  }
+
+  #[test]
+  fn glyphs_stay_put_as_the_atlas_grows() {
+    let ctx = shared_context().expect("headless GPU context");
+    let (device, queue) = (ctx.device(), ctx.queue());
+    let cache = GlyphCache::new(device, CAPTURE_FORMAT, 1);
+    let font = FontArc::try_from_slice(EMBEDDED_FONT).unwrap();
+    let glyph = |c: char, size: f32| {
+      let mut glyph = font.glyph_id(c).with_scale(PxScale::from(size));
+      glyph.position = point(0.25, 0.0);
+      glyph
+    };
+
+    let (first, _) = cache.place(device, queue, &[(&font, glyph('A', 12.0))]);
+    assert!(first[0].is_some());
+    // Blank glyphs take no room.
+    assert_eq!(cache.place(device, queue, &[(&font, glyph(' ', 12.0))]).0, [None]);
+
+    let many: Vec<_> = (0..200).map(|i| (&font, glyph('W', 20.0 + i as f32 * 0.5))).collect();
+    cache.place(device, queue, &many);
+    assert!(cache.size() > INITIAL_SIZE);
+    assert_eq!(cache.place(device, queue, &[(&font, glyph('A', 12.0))]).0, first);
+    assert_eq!(cache.len(), 202);
+  }
+}
@@ -0,0 +1,44 @@
+// Text shader: one instanced quad per glyph, its coverage sampled from
+// the glyph atlas.
+
+@group(0) @binding(0) var atlas: texture_2d<f32>;
+@group(0) @binding(1) var atlas_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    // Top-left corner and the top and left edges, in clip space.
+    @location(0) origin: vec2<f32>,
+    @location(1) axis_x: vec2<f32>,
+    @location(2) axis_y: vec2<f32>,
+    // Atlas texels: left, top, width, height.
+    @location(3) uv: vec4<f32>,
+    @location(4) color: vec4<f32>,
+) -> VertexOutput {
+    let idx = vertex_index % 6u;
+    var corner = vec2<f32>(0.0, 0.0);
+    if (idx == 1u || idx == 2u || idx == 4u) { corner.x = 1.0; }
+    if (idx == 2u || idx == 4u || idx == 5u) { corner.y = 1.0; }
+
+    var out: VertexOutput;
+    let p = origin + corner.x * axis_x + corner.y * axis_y;
+    out.position = vec4<f32>(p, 0.0, 1.0);
+    out.uv = (uv.xy + corner * uv.zw) / vec2<f32>(textureDimensions(atlas));
+    out.color = color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let coverage = textureSample(atlas, atlas_sampler, in.uv).r;
+    if (coverage <= 0.0) {
+        discard;
+    }
+    return vec4<f32>(in.color.rgb, in.color.a * coverage);
+}
@@ -0,0 +1,284 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Golden image management.
+//!
+//! Visual regression tests compare renders against golden PNGs named
+//! after their test case.  Over time goldens outlive renamed or deleted
+//! cases, new cases lack goldens, and a change to the capture size
+//! leaves every golden at the old dimensions.  [`Goldens`] finds all
+//! three from code: [`audit`](Goldens::audit) lists goldens without a
+//! case and cases without a golden, [`prune`](Goldens::prune) deletes
+//! the former, [`check_sizes`](Goldens::check_sizes) and
+//! [`check_case_sizes`](Goldens::check_case_sizes) report goldens whose
+//! dimensions differ from the test size, and
+//! [`bless`](Goldens::bless) re-renders the cases whose names match a
+//! pattern such as `"*_plot"`.
+
+use crate::capture::PlotCapture;
+use crate::error::Result;
+use crate::test_cases::{self, TestCase};
+use std::path::{Path, PathBuf};
+
+/// Cases of the C++ harness in `examples/cpp/test_cases.cc`, whose
+/// goldens share [`Goldens::repo`] with the Rust ones.
+pub const CPP_CASES: [&str; 24] = [
+  "line_plot",
+  "scatter_plot",
+  "bar_chart",
+  "multi_line",
+  "histogram",
+  "subplots",
+  "helix_3d",
+  "heatmap",
+  "heatmap_masked",
+  "stem_plot",
+  "area_plot",
+  "scatter_labels",
+  "stairs_plot",
+  "log_log",
+  "error_bar",
+  "polar_plot",
+  "pie_chart",
+  "contour",
+  "box_plot",
+  "quiver",
+  "scatter_3d",
+  "stem_3d",
+  "semi_log_x",
+  "semi_log_y",
+];
+
+/// Names of every case with a golden in [`Goldens::repo`]: the Rust
+/// [`test_cases::all`] followed by the [`CPP_CASES`] not among them.
+/// Audit or prune the repo's goldens against these, never the Rust
+/// cases alone.
+pub fn repo_case_names() -> Vec<&'static str> {
+  let mut names: Vec<&str> = test_cases::all().iter().map(|tc| tc.name).collect();
+  for name in CPP_CASES {
+    if !names.contains(&name) {
+      names.push(name);
+    }
+  }
+  names
+}
+
+/// Goldens without a test case and test cases without a golden.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GoldenAudit {
+  /// Golden names no test case uses, sorted.
+  pub orphaned: Vec<String>,
+  /// Test case names without a golden, in the given order.
+  pub missing: Vec<String>,
+}
+
+impl GoldenAudit {
+  /// Whether goldens and cases match one to one.
+  pub fn is_clean(&self) -> bool {
+    self.orphaned.is_empty() && self.missing.is_empty()
+  }
+}
+
+/// A golden whose dimensions differ from the test size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeMismatch {
+  /// Golden name.
+  pub name: String,
+  /// Golden width and height in pixels.
+  pub actual: (u32, u32),
+  /// Width and height the test renders at.
+  pub expected: (u32, u32),
+}
+
+/// A directory of golden PNGs, one `<name>.png` per test case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Goldens {
+  dir: PathBuf,
+}
+
+impl Goldens {
+  /// Goldens stored in `dir`.
+  pub fn new(dir: impl Into<PathBuf>) -> Self {
+    Self { dir: dir.into() }
+  }
+
+  /// This crate's own goldens in `tests/golden`, used by both the Rust
+  /// and the C++ harness (see [`repo_case_names`]).
+  pub fn repo() -> Self {
+    Self::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden"))
+  }
+
+  /// The golden directory.
+  pub fn dir(&self) -> &Path {
+    &self.dir
+  }
+
+  /// Path of the golden for `name`.
+  pub fn path(&self, name: &str) -> PathBuf {
+    self.dir.join(format!("{}.png", name))
+  }
+
+  /// Names of all goldens, sorted.  A missing directory has none.
+  pub fn list(&self) -> Result<Vec<String>> {
+    let entries = match std::fs::read_dir(&self.dir) {
+      Ok(entries) => entries,
+      Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+      Err(e) => return Err(e.into()),
+    };
+    let mut names = Vec::new();
+    for entry in entries {
+      let path = entry?.path();
+      if path.extension().is_some_and(|e| e == "png") {
+        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+          names.push(stem.to_string());
+        }
+      }
+    }
+    names.sort();
+    Ok(names)
+  }
+
+  /// Compares the goldens with the test case `names`.
+  pub fn audit(&self, names: &[&str]) -> Result<GoldenAudit> {
+    let goldens = self.list()?;
+    Ok(GoldenAudit {
+      orphaned: goldens.iter().filter(|g| !names.contains(&g.as_str())).cloned().collect(),
+      missing: names.iter().filter(|n| !goldens.iter().any(|g| g == *n)).map(|n| n.to_string()).collect(),
+    })
+  }
+
+  /// Deletes goldens no name in `names` uses and returns their names.
+  /// `names` must cover every harness rendering into the directory; for
+  /// [`Goldens::repo`] pass [`repo_case_names`].
+  pub fn prune(&self, names: &[&str]) -> Result<Vec<String>> {
+    let orphaned = self.audit(names)?.orphaned;
+    for name in &orphaned {
+      std::fs::remove_file(self.path(name))?;
+    }
+    Ok(orphaned)
+  }
+
+  /// Goldens among `names` whose dimensions are not `width` x `height`.
+  /// Names without a golden are skipped.
+  pub fn check_sizes(&self, names: &[&str], width: u32, height: u32) -> Result<Vec<SizeMismatch>> {
+    self.sizes_differing(names.iter().map(|name| (*name, (width, height))))
+  }
+
+  /// Goldens of `cases` whose dimensions differ from their case's
+  /// [`size`](TestCase::size).  Cases without a golden are skipped.
+  pub fn check_case_sizes(&self, cases: &[TestCase]) -> Result<Vec<SizeMismatch>> {
+    self.sizes_differing(cases.iter().map(|tc| (tc.name, tc.size)))
+  }
+
+  fn sizes_differing<'n>(&self, expected: impl Iterator<Item = (&'n str, (u32, u32))>) -> Result<Vec<SizeMismatch>> {
+    let mut mismatches = Vec::new();
+    for (name, expected) in expected {
+      let path = self.path(name);
+      if !path.exists() {
+        continue;
+      }
+      let actual = image::image_dimensions(&path)?;
+      if actual != expected {
+        mismatches.push(SizeMismatch { name: name.to_string(), actual, expected });
+      }
+    }
+    Ok(mismatches)
+  }
+
+  /// Renders the `cases` whose names match `pattern` (see [`matches`])
+  /// at their size and saves them as their goldens.  Returns the
+  /// blessed names.
+  pub fn bless(&self, cases: &[TestCase], pattern: &str) -> Result<Vec<String>> {
+    std::fs::create_dir_all(&self.dir)?;
+    let mut blessed = Vec::new();
+    for tc in cases.iter().filter(|tc| matches(pattern, tc.name)) {
+      let mut cap = PlotCapture::new(tc.size.0, tc.size.1)?;
+      (tc.setup)(&cap.figure());
+      cap.save_png(self.path(tc.name))?;
+      blessed.push(tc.name.to_string());
+    }
+    Ok(blessed)
+  }
+}
+
+/// Whether `name` matches the glob `pattern`, where `*` matches any run
+/// of characters and `?` any single character.
+pub fn matches(pattern: &str, name: &str) -> bool {
+  let (p, n): (Vec<char>, Vec<char>) = (pattern.chars().collect(), name.chars().collect());
+  // Position after the last `*` and the name position it resumes from.
+  let (mut pi, mut ni, mut star) = (0, 0, None);
+  while ni < n.len() {
+    match p.get(pi) {
+      Some('*') => {
+        star = Some((pi + 1, ni));
+        pi += 1;
+      }
+      Some(&c) if c == '?' || c == n[ni] => {
+        pi += 1;
+        ni += 1;
+      }
+      _ => match star {
+        Some((sp, sn)) => {
+          pi = sp;
+          ni = sn + 1;
+          star = Some((sp, sn + 1));
+        }
+        None => return false,
+      },
+    }
+  }
+  p[pi..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn glob_patterns() {
+    assert!(matches("*", "line_plot"));
+    assert!(matches("*_plot", "line_plot"));
+    assert!(matches("line_*", "line_plot"));
+    assert!(matches("l?ne*t", "line_plot"));
+    assert!(matches("line_plot", "line_plot"));
+    assert!(!matches("*_chart", "line_plot"));
+    assert!(!matches("line", "line_plot"));
+    assert!(matches("*a*b*", "xaybz"));
+    assert!(!matches("", "x"));
+  }
+
+  #[test]
+  fn audit_prune_and_sizes() {
+    let dir = std::env::temp_dir().join(format!("mpl_wgpu_goldens_{}", std::process::id()));
+    let goldens = Goldens::new(&dir);
+    assert_eq!(goldens.list().unwrap(), Vec::<String>::new());
+
+    std::fs::create_dir_all(&dir).unwrap();
+    for (name, w) in [("kept", 4), ("old", 4), ("small", 2)] {
+      image::save_buffer(goldens.path(name), &vec![255; w * 3 * 4], w as u32, 3, image::ColorType::Rgba8).unwrap();
+    }
+    std::fs::write(dir.join("notes.txt"), "").unwrap();
+
+    let audit = goldens.audit(&["kept", "small", "new"]).unwrap();
+    assert_eq!(audit.orphaned, vec!["old"]);
+    assert_eq!(audit.missing, vec!["new"]);
+    assert!(!audit.is_clean());
+
+    let sizes = goldens.check_sizes(&["kept", "small", "new"], 4, 3).unwrap();
+    assert_eq!(sizes, vec![SizeMismatch { name: "small".into(), actual: (2, 3), expected: (4, 3) }]);
+    let cases = [TestCase::new("kept", |_| {}).sized(4, 3), TestCase::new("small", |_| {}).sized(2, 3)];
+    assert_eq!(goldens.check_case_sizes(&cases).unwrap(), vec![]);
+
+    assert_eq!(goldens.prune(&["kept", "small"]).unwrap(), vec!["old"]);
+    assert_eq!(goldens.list().unwrap(), vec!["kept", "small"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+  }
+
+  #[test]
+  fn repo_goldens_all_belong_to_a_case() {
+    let names = repo_case_names();
+    let audit = Goldens::repo().audit(&names).unwrap();
+    assert_eq!(audit.orphaned, Vec::<String>::new());
+    assert!(names.contains(&"helix_3d") && names.contains(&"surface_3d"));
+  }
+}
@@ -0,0 +1,92 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Figures shared between threads.
+//!
+//! A [`PlotBackend`] is `Send` but not `Sync`, so one thread at a time
+//! owns it.  That covers building a figure on a worker and moving it to
+//! the GPU thread.  Live producers, such as a sampling thread appending
+//! to a series while the UI thread renders, need shared access instead.
+//! A [`FigureHandle`] wraps the backend in a mutex: clones go to the
+//! producers, each of which edits the figure inside [`FigureHandle::with`],
+//! and the render thread draws it with [`FigureHandle::render`].
+//!
+//! The backend is not `Sync`: its matplot++ handles
+//! ([`Figure`](crate::plotting::Figure), [`Axes`](crate::plotting::Axes),
+//! ...) edit the figure through a shared borrow, so two threads holding
+//! `&PlotBackend` could edit it at once.  The handles borrow the
+//! backend, so those taken inside the closure cannot outlive it.
+
+use crate::plotting::PlotBackend;
+use crate::primitives::PrimitiveRenderer;
+use crate::recording::Recording;
+use crate::text::TextRenderer;
+use glam::Mat4;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Cloneable, `Send + Sync` handle to a [`PlotBackend`].
+#[derive(Clone)]
+pub struct FigureHandle {
+  inner: Arc<Mutex<PlotBackend>>,
+  generation: Arc<AtomicU64>,
+}
+
+impl FigureHandle {
+  /// Shares `backend`.
+  pub fn new(backend: PlotBackend) -> Self {
+    Self { inner: Arc::new(Mutex::new(backend)), generation: Arc::new(AtomicU64::new(0)) }
+  }
+
+  /// Locks the backend.  A panic in another holder does not leave the
+  /// figure unusable, so a poisoned lock is recovered.
+  fn lock(&self) -> MutexGuard<'_, PlotBackend> {
+    self.inner.lock().unwrap_or_else(|e| e.into_inner())
+  }
+
+  /// Runs `f` with exclusive access to the backend and returns its
+  /// result.  Counts as a change (see [`generation`](Self::generation)).
+  pub fn with<R>(&self, f: impl FnOnce(&mut PlotBackend) -> R) -> R {
+    let r = f(&mut self.lock());
+    self.generation.fetch_add(1, Ordering::Release);
+    r
+  }
+
+  /// Number of [`with`](Self::with) calls so far; renderers that only
+  /// redraw on change compare it with the value of their last frame.
+  pub fn generation(&self) -> u64 {
+    self.generation.load(Ordering::Acquire)
+  }
+
+  /// Renders the figure; see [`PlotBackend::render`].
+  pub fn render(&self, prim: &mut PrimitiveRenderer, text: &mut TextRenderer, target: Option<Mat4>) {
+    self.lock().render(prim, text, target);
+  }
+
+  /// Records the figure; see [`PlotBackend::record`].
+  pub fn record(&self, text: Option<&mut TextRenderer>) -> Recording {
+    self.lock().record(text)
+  }
+
+  /// The backend, if this is the last handle to it.
+  pub fn into_inner(self) -> Result<PlotBackend, Self> {
+    match Arc::try_unwrap(self.inner) {
+      Ok(m) => Ok(m.into_inner().unwrap_or_else(|e| e.into_inner())),
+      Err(inner) => Err(Self { inner, generation: self.generation }),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn send<T: Send>() {}
+  fn send_sync<T: Send + Sync>() {}
+
+  #[test]
+  fn thread_safety() {
+    send::<PlotBackend>();
+    send_sync::<FigureHandle>();
+  }
+}
@@ -0,0 +1,306 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Self-contained interactive HTML export.
+//!
+//! Embeds a [`Recording`] as JSON together with a small canvas
+//! viewer into a single HTML file.  The viewer supports pan (drag),
+//! zoom (mouse wheel), reset (double-click), and hover tooltips for
+//! markers and lines, so figures can be shared without a server.  Line
+//! tooltips report the interpolated point on the segment under the
+//! cursor.  Given the [`Viewport`] of the axes the figure was recorded
+//! with, tooltips show data coordinates; pixel coordinates otherwise.
+
+use crate::artist::Viewport;
+use crate::recording::Recording;
+use std::path::Path;
+
+/// Viewer script.  Reads the figure from `window.MPL_FIGURE` and the
+/// axes viewport, if any, from `window.MPL_AXES`.
+const VIEWER_JS: &str = r##"
+(function () {
+  var fig = window.MPL_FIGURE;
+  var axes = window.MPL_AXES;
+  var canvas = document.getElementById("mpl-canvas");
+  var tip = document.getElementById("mpl-tip");
+  var ctx = canvas.getContext("2d");
+  var dpr = window.devicePixelRatio || 1;
+  canvas.width = fig.width * dpr;
+  canvas.height = fig.height * dpr;
+  canvas.style.width = fig.width + "px";
+  canvas.style.height = fig.height + "px";
+  var view = { x: 0, y: 0, k: 1 };
+
+  function rgba(p, i) {
+    return "rgba(" + Math.round(p[i] * 255) + "," +
+      Math.round(p[i + 1] * 255) + "," +
+      Math.round(p[i + 2] * 255) + "," + p[i + 3] + ")";
+  }
+
+  function marker(p) {
+    var x = p[1], y = p[2], r = p[3], t = p[4];
+    ctx.beginPath();
+    if (t === 10) {
+      ctx.rect(x - r, y - r, 2 * r, 2 * r);
+    } else if (t === 11) {
+      ctx.moveTo(x, y - r); ctx.lineTo(x + r, y);
+      ctx.lineTo(x, y + r); ctx.lineTo(x - r, y);
+      ctx.closePath();
+    } else if (t === 14 || t === 15) {
+      var s = t === 14 ? -1 : 1;
+      ctx.moveTo(x, y + s * r); ctx.lineTo(x + r, y - s * r);
+      ctx.lineTo(x - r, y - s * r); ctx.closePath();
+    } else {
+      ctx.arc(x, y, r, 0, 2 * Math.PI);
+    }
+    ctx.fillStyle = rgba(p, 5);
+    ctx.fill();
+  }
+
+  function draw() {
+    ctx.setTransform(dpr, 0, 0, dpr, 0, 0);
+    ctx.fillStyle = "#fff";
+    ctx.fillRect(0, 0, fig.width, fig.height);
+    ctx.setTransform(dpr * view.k, 0, 0, dpr * view.k,
+      dpr * view.x, dpr * view.y);
+    var prims = fig.primitives;
+    // Faces first, matching PrimitiveRenderer ordering.
+    prims.forEach(function (p) {
+      if (p[0] !== "t") return;
+      ctx.beginPath();
+      ctx.moveTo(p[1], p[2]); ctx.lineTo(p[3], p[4]);
+      ctx.lineTo(p[5], p[6]); ctx.closePath();
+      ctx.fillStyle = rgba(p, 7);
+      ctx.fill();
+    });
+    prims.forEach(function (p) {
+      if (p[0] === "r") {
+        if (p[5] > 0) {
+          ctx.lineWidth = p[5];
+          ctx.strokeStyle = rgba(p, 7);
+          ctx.strokeRect(p[1], p[2], p[3], p[4]);
+        } else {
+          ctx.fillStyle = rgba(p, 7);
+          ctx.fillRect(p[1], p[2], p[3], p[4]);
+        }
+      } else if (p[0] === "l") {
+        ctx.beginPath();
+        ctx.setLineDash(p[6] > 0 ? [p[6], p[7]] : []);
+        ctx.lineDashOffset = p[8];
+        ctx.lineWidth = p[5];
+        ctx.strokeStyle = rgba(p, 9);
+        ctx.moveTo(p[1], p[2]); ctx.lineTo(p[3], p[4]);
+        ctx.stroke();
+      } else if (p[0] === "m") {
+        marker(p);
+      }
+    });
+    ctx.setLineDash([]);
+    ctx.textBaseline = "top";
+    prims.forEach(function (p) {
+      if (p[0] !== "s") return;
+      ctx.font = p[3] + "px sans-serif";
+      ctx.fillStyle = rgba(p, 4);
+      ctx.save();
+      ctx.translate(p[1], p[2]);
+      ctx.rotate(-(p[9] || 0) * Math.PI / 180);
+      ctx.fillText(p[8], 0, 0);
+      ctx.restore();
+    });
+  }
+
+  function toFigure(ev) {
+    var b = canvas.getBoundingClientRect();
+    return {
+      x: (ev.clientX - b.left - view.x) / view.k,
+      y: (ev.clientY - b.top - view.y) / view.k
+    };
+  }
+
+  function nearestMarker(pt) {
+    var best = null, bestD = 8 / view.k;
+    fig.primitives.forEach(function (p, i) {
+      if (p[0] !== "m") return;
+      var d = Math.hypot(p[1] - pt.x, p[2] - pt.y);
+      if (d <= Math.max(bestD, p[3])) { best = { p: p, i: i }; bestD = d; }
+    });
+    return best;
+  }
+
+  // Closest point on any line segment; the reach grows with the width.
+  function nearestLine(pt) {
+    var best = null, bestD = Infinity;
+    fig.primitives.forEach(function (p) {
+      if (p[0] !== "l") return;
+      var ax = p[1], ay = p[2], dx = p[3] - ax, dy = p[4] - ay;
+      var len2 = dx * dx + dy * dy;
+      var t = len2 > 0 ? ((pt.x - ax) * dx + (pt.y - ay) * dy) / len2 : 0;
+      t = Math.max(0, Math.min(1, t));
+      var x = ax + dx * t, y = ay + dy * t;
+      var d = Math.hypot(pt.x - x, pt.y - y);
+      if (d <= 5 / view.k + p[5] * 0.5 && d < bestD) {
+        best = { x: x, y: y }; bestD = d;
+      }
+    });
+    return best;
+  }
+
+  // Data coordinates of the figure pixel (x, y), inverting the axes
+  // box; a degenerate box maps to the middle of the limits.
+  function toData(x, y) {
+    var l = axes.limits, b = axes.pixels;
+    var fx = b[2] !== b[0] ? (x - b[0]) / (b[2] - b[0]) : 0.5;
+    var fy = b[3] !== b[1] ? (b[3] - y) / (b[3] - b[1]) : 0.5;
+    return { x: l[0] + fx * (l[1] - l[0]), y: l[2] + fy * (l[3] - l[2]) };
+  }
+
+  function num(v) {
+    return String(Number(v.toPrecision(6)));
+  }
+
+  function describe(x, y) {
+    if (!axes) return "(" + x.toFixed(1) + ", " + y.toFixed(1) + ")";
+    var d = toData(x, y);
+    return "(" + num(d.x) + ", " + num(d.y) + ")";
+  }
+
+  var drag = null;
+  canvas.addEventListener("mousedown", function (ev) {
+    drag = { x: ev.clientX, y: ev.clientY, vx: view.x, vy: view.y };
+  });
+  window.addEventListener("mouseup", function () { drag = null; });
+  canvas.addEventListener("mousemove", function (ev) {
+    if (drag) {
+      view.x = drag.vx + ev.clientX - drag.x;
+      view.y = drag.vy + ev.clientY - drag.y;
+      draw();
+    }
+    var pt = toFigure(ev);
+    var hit = nearestMarker(pt);
+    var line = hit ? null : nearestLine(pt);
+    tip.style.left = (ev.pageX + 12) + "px";
+    tip.style.top = (ev.pageY + 12) + "px";
+    tip.textContent = hit
+      ? "marker #" + hit.i + " at " + describe(hit.p[1], hit.p[2])
+      : line
+      ? "line at " + describe(line.x, line.y)
+      : describe(pt.x, pt.y);
+    tip.style.display = "block";
+  });
+  canvas.addEventListener("mouseleave", function () {
+    tip.style.display = "none";
+  });
+  canvas.addEventListener("wheel", function (ev) {
+    ev.preventDefault();
+    var b = canvas.getBoundingClientRect();
+    var mx = ev.clientX - b.left, my = ev.clientY - b.top;
+    var f = Math.exp(-ev.deltaY * 0.0015);
+    view.x = mx - (mx - view.x) * f;
+    view.y = my - (my - view.y) * f;
+    view.k *= f;
+    draw();
+  }, { passive: false });
+  canvas.addEventListener("dblclick", function () {
+    view = { x: 0, y: 0, k: 1 };
+    draw();
+  });
+  draw();
+})();
+"##;
+
+/// Renders a recording into a complete HTML document, with tooltips in
+/// the data coordinates of `viewport` if given.
+pub fn to_html(recording: &Recording, viewport: Option<&Viewport>, title: &str) -> String {
+  format!(
+    "<!DOCTYPE html>\n\
+     <html>\n<head>\n<meta charset=\"utf-8\">\n\
+     <title>{title}</title>\n\
+     <style>\n\
+     body {{ margin: 0; background: #eee; font-family: sans-serif; }}\n\
+     #mpl-canvas {{ display: block; margin: 16px auto; \
+     background: #fff; cursor: grab; }}\n\
+     #mpl-tip {{ position: absolute; display: none; \
+     pointer-events: none; background: rgba(0,0,0,0.75); \
+     color: #fff; padding: 2px 6px; border-radius: 3px; \
+     font-size: 12px; }}\n\
+     </style>\n</head>\n<body>\n\
+     <canvas id=\"mpl-canvas\"></canvas>\n\
+     <div id=\"mpl-tip\"></div>\n\
+     <script>window.MPL_FIGURE = {json};\n\
+     window.MPL_AXES = {axes};</script>\n\
+     <script>{js}</script>\n\
+     </body>\n</html>\n",
+    title = escape_html(title),
+    json = recording.to_json(),
+    axes = viewport.map_or_else(|| "null".to_string(), viewport_json),
+    js = VIEWER_JS,
+  )
+}
+
+/// Writes a recording as a self-contained HTML file (see [`to_html`]).
+pub fn save_html<P: AsRef<Path>>(
+  recording: &Recording,
+  viewport: Option<&Viewport>,
+  path: P,
+) -> std::io::Result<()> {
+  let title = path
+    .as_ref()
+    .file_stem()
+    .and_then(|s| s.to_str())
+    .unwrap_or("mpl-wgpu figure")
+    .to_string();
+  std::fs::write(path, to_html(recording, viewport, &title))
+}
+
+/// `viewport` as the viewer reads it: limits `[x_min, x_max, y_min,
+/// y_max]` and the pixel box `[left, top, right, bottom]`, or `null`
+/// if a value is not finite.
+fn viewport_json(viewport: &Viewport) -> String {
+  let l = viewport.limits;
+  let limits = [l.x_min, l.x_max, l.y_min, l.y_max];
+  let pixels = viewport.pixels.map(f64::from);
+  if !limits.iter().chain(&pixels).all(|v| v.is_finite()) {
+    return "null".to_string();
+  }
+  let list = |v: &[f64]| v.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(",");
+  format!("{{\"limits\":[{}],\"pixels\":[{}]}}", list(&limits[..]), list(&pixels[..]))
+}
+
+/// Escapes text for use inside HTML element content.
+fn escape_html(s: &str) -> String {
+  s.replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::artist::DataRect;
+
+  #[test]
+  fn html_embeds_figure_and_viewer() {
+    let rec = Recording::new(320, 240);
+    let html = to_html(&rec, None, "demo");
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>demo</title>"));
+    assert!(html.contains(&rec.to_json()));
+    assert!(html.contains("getElementById(\"mpl-canvas\")"));
+    assert!(html.contains("window.MPL_AXES = null;"));
+  }
+
+  #[test]
+  fn axes_viewport_is_embedded() {
+    let viewport = Viewport::new(DataRect::new((0.0, 10.0), (-1.0, 1.0)), [40.0, 20.0, 300.0, 220.0]);
+    let html = to_html(&Recording::new(320, 240), Some(&viewport), "demo");
+    assert!(html.contains(r#"window.MPL_AXES = {"limits":[0,10,-1,1],"pixels":[40,20,300,220]};"#));
+    let nan = Viewport::new(DataRect::new((f64::NAN, 1.0), (0.0, 1.0)), [0.0; 4]);
+    assert_eq!(viewport_json(&nan), "null");
+  }
+
+  #[test]
+  fn title_is_escaped() {
+    let html = to_html(&Recording::new(1, 1), None, "<a&b>");
+    assert!(html.contains("<title>&lt;a&amp;b&gt;</title>"));
+  }
+}
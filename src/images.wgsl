@@ -0,0 +1,58 @@
+// Textured quads for images drawn beneath the primitives.
+
+struct GlobalUniforms {
+    view_proj: mat4x4<f32>,
+    screen_size: vec2<f32>,
+    _padding: vec2<f32>,
+    camera_pos: vec3<f32>,
+};
+@group(0) @binding(0) var<uniform> globals: GlobalUniforms;
+@group(1) @binding(0) var image_texture: texture_2d<f32>;
+@group(1) @binding(1) var image_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) tint: vec4<f32>,
+};
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @location(0) c0: vec4<f32>,
+    @location(1) c1: vec4<f32>,
+    @location(2) c2: vec4<f32>,
+    @location(3) c3: vec4<f32>,
+    @location(4) uv: vec4<f32>,
+    @location(5) tint: vec4<f32>,
+) -> VertexOutput {
+    // Corners 0-1-2 and 0-2-3, clockwise from the top-left.
+    var corners = array<vec3<f32>, 4>(c0.xyz, c1.xyz, c2.xyz, c3.xyz);
+    var uvs = array<vec2<f32>, 4>(uv.xy, uv.zy, uv.zw, uv.xw);
+    var order = array<u32, 6>(0u, 1u, 2u, 0u, 2u, 3u);
+    let i = order[vertex_index % 6u];
+    let p = corners[i];
+
+    // Same 2D test as the primitives: pixel coordinates, Y down.
+    let is_2d = abs(globals.view_proj[0][0] - 1.0) < 0.0001 &&
+                abs(globals.view_proj[1][1] - 1.0) < 0.0001 &&
+                abs(globals.view_proj[3][3] - 1.0) < 0.0001 &&
+                abs(globals.view_proj[0][1]) < 0.0001 &&
+                abs(globals.view_proj[1][0]) < 0.0001;
+
+    var out: VertexOutput;
+    if (is_2d) {
+        let ndc = (p.xy / globals.screen_size) * 2.0 - 1.0;
+        out.position = vec4<f32>(ndc.x, -ndc.y, p.z, 1.0);
+    } else {
+        out.position = globals.view_proj * vec4<f32>(p, 1.0);
+    }
+    out.uv = uvs[i];
+    out.tint = tint;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(image_texture, image_sampler, in.uv) * in.tint;
+}
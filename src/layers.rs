@@ -0,0 +1,387 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Cached render layers.
+//!
+//! A [`LayerCompositor`] splits a frame into [`Layer`]s (background and
+//! grid, static series, dynamic series, overlay), each with its own
+//! primitive and text renderer and an intermediate texture.  A layer is
+//! only re-rendered into its texture after it was touched; every frame
+//! then composites the cached textures onto the target in layer order.
+//! Redrawing a cursor in the overlay thus leaves a multi-million point
+//! static layer untouched.
+
+use crate::error::Result;
+use crate::glyphs::GlyphCache;
+use crate::primitives::{PrimitivePipelines, PrimitiveRenderer};
+use crate::text::TextRenderer;
+use std::sync::Arc;
+use wgpu_text::glyph_brush::ab_glyph::FontArc;
+
+/// Render layers, back to front.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Layer {
+  /// Figure background, axes, and grid.
+  Background,
+  /// Series that rarely change.
+  Static,
+  /// Series updated frequently (streaming data).
+  Dynamic,
+  /// Cursors, crosshairs, selections, and tooltips.
+  Overlay,
+}
+
+impl Layer {
+  /// All layers in compositing order.
+  pub const ALL: [Layer; 4] =
+    [Layer::Background, Layer::Static, Layer::Dynamic, Layer::Overlay];
+
+  fn index(self) -> usize {
+    self as usize
+  }
+}
+
+/// One layer's renderers and cached texture.
+struct LayerTarget {
+  prim: PrimitiveRenderer,
+  text: TextRenderer,
+  view: wgpu::TextureView,
+  bind_group: wgpu::BindGroup,
+  dirty: bool,
+}
+
+/// Renders layers into cached textures and composites them.
+pub struct LayerCompositor {
+  layers: Vec<LayerTarget>,
+  pipeline: wgpu::RenderPipeline,
+  bind_group_layout: wgpu::BindGroupLayout,
+  format: wgpu::TextureFormat,
+  width: u32,
+  height: u32,
+}
+
+impl LayerCompositor {
+  /// Creates the compositor for a `width` x `height` target of
+  /// `format`.  `font_data` is used by every layer's text renderer.
+  pub fn new(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    width: u32,
+    height: u32,
+    font_data: &[u8],
+  ) -> Result<Self> {
+    let shader =
+      device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("CompositeShader"),
+        source: wgpu::ShaderSource::Wgsl(
+          include_str!("composite.wgsl").into(),
+        ),
+      });
+
+    let bind_group_layout =
+      device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("CompositeBindGroup"),
+        entries: &[wgpu::BindGroupLayoutEntry {
+          binding: 0,
+          visibility: wgpu::ShaderStages::FRAGMENT,
+          ty: wgpu::BindingType::Texture {
+            sample_type: wgpu::TextureSampleType::Float {
+              filterable: false,
+            },
+            view_dimension: wgpu::TextureViewDimension::D2,
+            multisampled: false,
+          },
+          count: None,
+        }],
+      });
+
+    let pipeline_layout =
+      device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: None,
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+      });
+
+    // Layers are rendered with alpha blending onto transparent black,
+    // which leaves premultiplied color in the texture.
+    let pipeline =
+      device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("CompositePipeline"),
+        layout: Some(&pipeline_layout),
+        vertex: wgpu::VertexState {
+          module: &shader,
+          entry_point: "vs_main",
+          buffers: &[],
+          compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+          module: &shader,
+          entry_point: "fs_main",
+          targets: &[Some(wgpu::ColorTargetState {
+            format,
+            blend: Some(wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+            write_mask: wgpu::ColorWrites::ALL,
+          })],
+          compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+      });
+
+    // All layers draw with the same pipelines, glyph atlas and parsed
+    // font.
+    let pipelines = Arc::new(PrimitivePipelines::new(device, format, 1));
+    let glyphs = Arc::new(GlyphCache::new(device, format, 1));
+    let font = FontArc::try_from_vec(font_data.to_vec())?;
+    let layers = Layer::ALL
+      .iter()
+      .map(|_| {
+        let (view, bind_group) = create_layer_texture(
+          device,
+          &bind_group_layout,
+          format,
+          width,
+          height,
+        );
+        LayerTarget {
+          prim: PrimitiveRenderer::with_pipelines(
+            device,
+            pipelines.clone(),
+            width,
+            height,
+          ),
+          text: TextRenderer::with_cache(
+            device,
+            glyphs.clone(),
+            width,
+            height,
+            vec![font.clone()],
+          ),
+          view,
+          bind_group,
+          dirty: true,
+        }
+      })
+      .collect();
+
+    Ok(Self {
+      layers,
+      pipeline,
+      bind_group_layout,
+      format,
+      width,
+      height,
+    })
+  }
+
+  /// Clears `layer`'s queued primitives and text and marks it for
+  /// re-rendering.  Call before redrawing the layer's contents.
+  pub fn begin(&mut self, layer: Layer) {
+    let l = &mut self.layers[layer.index()];
+    l.prim.clear();
+    l.text.clear();
+    l.dirty = true;
+  }
+
+  /// The primitive renderer of `layer`.  Marks the layer dirty.
+  pub fn prim(&mut self, layer: Layer) -> &mut PrimitiveRenderer {
+    let l = &mut self.layers[layer.index()];
+    l.dirty = true;
+    &mut l.prim
+  }
+
+  /// The text renderer of `layer`.  Marks the layer dirty.
+  pub fn text(&mut self, layer: Layer) -> &mut TextRenderer {
+    let l = &mut self.layers[layer.index()];
+    l.dirty = true;
+    &mut l.text
+  }
+
+  /// Both renderers of `layer`, e.g. for
+  /// [`PlotBackend::render`](crate::plotting::PlotBackend::render).
+  /// Marks the layer dirty.
+  pub fn renderers(
+    &mut self,
+    layer: Layer,
+  ) -> (&mut PrimitiveRenderer, &mut TextRenderer) {
+    let l = &mut self.layers[layer.index()];
+    l.dirty = true;
+    (&mut l.prim, &mut l.text)
+  }
+
+  /// Forces `layer` to be re-rendered on the next frame without
+  /// changing its contents (e.g. after a view-projection change).
+  pub fn invalidate(&mut self, layer: Layer) {
+    self.layers[layer.index()].dirty = true;
+  }
+
+  /// Whether `layer` will be re-rendered on the next frame.
+  pub fn is_dirty(&self, layer: Layer) -> bool {
+    self.layers[layer.index()].dirty
+  }
+
+  /// Resizes every layer; all layers are re-rendered next frame.
+  pub fn resize(
+    &mut self,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    width: u32,
+    height: u32,
+  ) {
+    self.width = width;
+    self.height = height;
+    for l in &mut self.layers {
+      let (view, bind_group) = create_layer_texture(
+        device,
+        &self.bind_group_layout,
+        self.format,
+        width,
+        height,
+      );
+      l.view = view;
+      l.bind_group = bind_group;
+      l.prim.resize(queue, width, height);
+      l.text.resize(width, height);
+      l.dirty = true;
+    }
+  }
+
+  /// Re-renders dirty layers into their textures, then composites all
+  /// layers onto `target`, which must match the compositor's size and
+  /// format.  `target` is loaded, not cleared, so clear it beforehand
+  /// or let the background layer cover it.
+  ///
+  /// Returns the number of layers that were re-rendered.
+  pub fn render(
+    &mut self,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    encoder: &mut wgpu::CommandEncoder,
+    target: &wgpu::TextureView,
+  ) -> usize {
+    let mut redrawn = 0;
+    for l in self.layers.iter_mut().filter(|l| l.dirty) {
+      l.prim.prepare(device, queue);
+      l.text.prepare(device, queue);
+      let mut rp = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("LayerPass"),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+          view: &l.view,
+          resolve_target: None,
+          ops: wgpu::Operations {
+            load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+            store: wgpu::StoreOp::Store,
+          },
+        })],
+        depth_stencil_attachment: None,
+        ..Default::default()
+      });
+      l.prim.render(&mut rp);
+      l.text.render(&mut rp);
+      drop(rp);
+      l.dirty = false;
+      redrawn += 1;
+    }
+
+    let mut rp = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+      label: Some("CompositePass"),
+      color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+        view: target,
+        resolve_target: None,
+        ops: wgpu::Operations {
+          load: wgpu::LoadOp::Load,
+          store: wgpu::StoreOp::Store,
+        },
+      })],
+      depth_stencil_attachment: None,
+      ..Default::default()
+    });
+    rp.set_pipeline(&self.pipeline);
+    for l in &self.layers {
+      rp.set_bind_group(0, &l.bind_group, &[]);
+      rp.draw(0..3, 0..1);
+    }
+    redrawn
+  }
+}
+
+/// Creates a layer texture and the bind group sampling it.
+fn create_layer_texture(
+  device: &wgpu::Device,
+  layout: &wgpu::BindGroupLayout,
+  format: wgpu::TextureFormat,
+  width: u32,
+  height: u32,
+) -> (wgpu::TextureView, wgpu::BindGroup) {
+  let texture = device.create_texture(&wgpu::TextureDescriptor {
+    label: Some("LayerTexture"),
+    size: wgpu::Extent3d {
+      width: width.max(1),
+      height: height.max(1),
+      depth_or_array_layers: 1,
+    },
+    mip_level_count: 1,
+    sample_count: 1,
+    dimension: wgpu::TextureDimension::D2,
+    format,
+    usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+      | wgpu::TextureUsages::TEXTURE_BINDING,
+    view_formats: &[],
+  });
+  let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+  let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+    label: Some("LayerBindGroup"),
+    layout,
+    entries: &[wgpu::BindGroupEntry {
+      binding: 0,
+      resource: wgpu::BindingResource::TextureView(&view),
+    }],
+  });
+  (view, bind_group)
+}
+
+#[cfg(all(test, feature = "embedded-font"))]
+mod tests {
+  use super::*;
+  use crate::capture::{shared_context, CAPTURE_FORMAT};
+  use crate::plotting::PlotBackend;
+  use crate::text::EMBEDDED_FONT;
+
+  #[test]
+  fn layers_hold_only_their_primitives() {
+    let ctx = shared_context().expect("headless GPU context");
+    let mut layers =
+      LayerCompositor::new(ctx.device(), CAPTURE_FORMAT, 400, 300, EMBEDDED_FONT)
+        .unwrap();
+    let mut backend = PlotBackend::new(400, 300);
+    let id = {
+      let ax = backend.figure().current_axes();
+      ax.hold(true);
+      let x: Vec<f64> = (0..100).map(f64::from).collect();
+      ax.plot(&x, &x, "-");
+      ax.plot(&x[..10], &x[..10], "-").id()
+    };
+    backend.set_series_dynamic(id, true);
+
+    let mut queued = |layer: Layer| {
+      backend.render_layer(&mut layers, layer, None);
+      (
+        layers.prim(layer).instance_count(),
+        layers.text(layer).queued_count(),
+      )
+    };
+    let background = queued(Layer::Background);
+    let fixed = queued(Layer::Static);
+    let dynamic = queued(Layer::Dynamic);
+    let overlay = queued(Layer::Overlay);
+
+    // Tick labels and the like are only in the background.
+    assert!(background.1 > 0);
+    assert_eq!((fixed.1, dynamic.1), (0, 0));
+    // The long series is static, the short one dynamic.
+    assert!(dynamic.0 > 0 && fixed.0 > dynamic.0);
+    assert_eq!(overlay, (0, 0));
+  }
+}
@@ -0,0 +1,207 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Tick label collision detection.
+//!
+//! [`fit_tick_labels`] checks horizontal tick labels for overlaps with
+//! their neighbours and for overrunning the figure edges, then picks
+//! the least invasive fix: shrink the font slightly, rotate the labels
+//! 45 degrees, or thin them to every n-th tick.  The returned
+//! [`LabelLayout`] reports which strategy was used.
+
+/// Minimum gap between neighbouring labels, in pixels.
+const LABEL_PADDING: f32 = 4.0;
+/// Smallest font scale tried before rotating.
+const MIN_SHRINK: f32 = 0.75;
+/// Font scale decrement per shrink step.
+const SHRINK_STEP: f32 = 0.05;
+/// Rotation used when labels don't fit horizontally, in degrees.
+const ROTATION_DEG: f32 = 45.0;
+
+/// How overlapping tick labels were resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelStrategy {
+  /// Labels fit as-is.
+  Fits,
+  /// The font was shrunk.
+  Shrink,
+  /// Labels were rotated.
+  Rotate,
+  /// Only every n-th label is kept.
+  Thin,
+}
+
+/// Result of the tick label layout pass.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelLayout {
+  /// Chosen strategy.
+  pub strategy: LabelStrategy,
+  /// Indices of the ticks that keep their labels.
+  pub keep: Vec<usize>,
+  /// Label rotation in degrees (counter-clockwise).
+  pub rotation: f32,
+  /// Font size to draw the labels with.
+  pub font_size: f32,
+  /// Overlapping neighbour pairs before the fix.
+  pub collisions: usize,
+  /// Labels overrunning the figure edges before the fix.
+  pub overruns: usize,
+}
+
+/// Lays out horizontal tick labels centered on `positions`.
+///
+/// `positions` are tick centers in pixels, `bounds` the horizontal
+/// figure extent, and `measure(text, font_size)` returns a label's
+/// width in pixels.
+pub fn fit_tick_labels<S, M>(
+  positions: &[f32],
+  labels: &[S],
+  font_size: f32,
+  bounds: (f32, f32),
+  measure: M,
+) -> LabelLayout
+where
+  S: AsRef<str>,
+  M: Fn(&str, f32) -> f32,
+{
+  let n = positions.len().min(labels.len());
+  let all: Vec<usize> = (0..n).collect();
+  let widths =
+    |fs: f32| -> Vec<f32> { labels[..n].iter().map(|l| measure(l.as_ref(), fs)).collect() };
+
+  let base = widths(font_size);
+  let collisions = count_collisions(positions, &base, &all);
+  let overruns = count_overruns(positions, &base, &all, bounds);
+  let layout = |strategy, keep, rotation, font_size| LabelLayout {
+    strategy,
+    keep,
+    rotation,
+    font_size,
+    collisions,
+    overruns,
+  };
+  if collisions == 0 && overruns == 0 {
+    return layout(LabelStrategy::Fits, all, 0.0, font_size);
+  }
+
+  let mut scale = 1.0 - SHRINK_STEP;
+  while scale >= MIN_SHRINK - 1e-6 {
+    let w = widths(font_size * scale);
+    if count_collisions(positions, &w, &all) == 0
+      && count_overruns(positions, &w, &all, bounds) == 0
+    {
+      return layout(LabelStrategy::Shrink, all, 0.0, font_size * scale);
+    }
+    scale -= SHRINK_STEP;
+  }
+
+  if rotated_fits(positions, &base, font_size, bounds) {
+    return layout(LabelStrategy::Rotate, all, ROTATION_DEG, font_size);
+  }
+
+  for step in 2..n.max(2) {
+    let keep: Vec<usize> = (0..n)
+      .step_by(step)
+      .filter(|&i| count_overruns(positions, &base, &[i], bounds) == 0)
+      .collect();
+    if count_collisions(positions, &base, &keep) == 0 {
+      return layout(LabelStrategy::Thin, keep, 0.0, font_size);
+    }
+  }
+  // A single centered label is the last resort.
+  layout(LabelStrategy::Thin, vec![n / 2], 0.0, font_size)
+}
+
+/// Overlapping pairs among consecutive kept labels.
+fn count_collisions(positions: &[f32], widths: &[f32], keep: &[usize]) -> usize {
+  keep
+    .windows(2)
+    .filter(|w| {
+      let (a, b) = (w[0], w[1]);
+      let gap = (positions[b] - positions[a]).abs();
+      gap < (widths[a] + widths[b]) * 0.5 + LABEL_PADDING
+    })
+    .count()
+}
+
+/// Kept labels extending past the figure edges.
+fn count_overruns(
+  positions: &[f32],
+  widths: &[f32],
+  keep: &[usize],
+  bounds: (f32, f32),
+) -> usize {
+  keep
+    .iter()
+    .filter(|&&i| {
+      let half = widths[i] * 0.5;
+      positions[i] - half < bounds.0 || positions[i] + half > bounds.1
+    })
+    .count()
+}
+
+/// Whether labels rotated by [`ROTATION_DEG`] and right-aligned at
+/// their tick clear each other and the left figure edge.
+fn rotated_fits(
+  positions: &[f32],
+  widths: &[f32],
+  font_size: f32,
+  bounds: (f32, f32),
+) -> bool {
+  let (sin, cos) = ROTATION_DEG.to_radians().sin_cos();
+  // Parallel rotated labels are separated by spacing * sin.
+  let spaced = positions
+    .windows(2)
+    .all(|p| (p[1] - p[0]).abs() * sin >= font_size + LABEL_PADDING * 0.5);
+  let inside = positions
+    .iter()
+    .zip(widths)
+    .all(|(p, w)| p - w * cos >= bounds.0 && *p <= bounds.1);
+  spaced && inside
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn measure(s: &str, fs: f32) -> f32 {
+    s.len() as f32 * fs * 0.5
+  }
+
+  #[test]
+  fn spacious_labels_fit() {
+    let l = fit_tick_labels(&[100.0, 200.0, 300.0], &["a", "b", "c"], 10.0, (0.0, 400.0), measure);
+    assert_eq!(l.strategy, LabelStrategy::Fits);
+    assert_eq!(l.keep, vec![0, 1, 2]);
+  }
+
+  #[test]
+  fn slight_overlap_shrinks() {
+    // 8 chars at 10px = 40px wide, ticks 42px apart.
+    let labels = ["abcdefgh"; 3];
+    let l = fit_tick_labels(&[100.0, 142.0, 184.0], &labels, 10.0, (0.0, 400.0), measure);
+    assert_eq!(l.strategy, LabelStrategy::Shrink);
+    assert!(l.font_size < 10.0 && l.font_size >= 7.5);
+    assert_eq!(l.collisions, 2);
+  }
+
+  #[test]
+  fn long_labels_rotate() {
+    let labels = ["2024-01-01 00:00"; 4];
+    let pos = [200.0, 240.0, 280.0, 320.0];
+    let l = fit_tick_labels(&pos, &labels, 10.0, (0.0, 400.0), measure);
+    assert_eq!(l.strategy, LabelStrategy::Rotate);
+    assert_eq!(l.rotation, 45.0);
+  }
+
+  #[test]
+  fn dense_labels_thin_and_respect_edges() {
+    let pos: Vec<f32> = (0..20).map(|i| i as f32 * 10.0).collect();
+    let labels: Vec<String> = (0..20).map(|i| format!("{:03}", i)).collect();
+    let l = fit_tick_labels(&pos, &labels, 10.0, (0.0, 190.0), measure);
+    assert_eq!(l.strategy, LabelStrategy::Thin);
+    // Tick 0 overruns the left edge; survivors don't collide.
+    assert!(!l.keep.contains(&0));
+    assert_eq!(count_collisions(&pos, &[15.0; 20], &l.keep), 0);
+  }
+}
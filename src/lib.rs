@@ -26,16 +26,80 @@
 #![warn(missing_docs)]
 #![warn(rust_2018_idioms)]
 
+pub mod adornments;
+pub mod animated_image;
+pub mod animation;
+pub mod annotations;
+pub mod artist;
+pub mod autoscale;
 pub mod backend;
+pub mod bounds;
+pub mod boxzoom;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod camera;
+pub mod canvas;
 pub mod capture;
+pub mod cluster;
+pub mod colorbar;
+pub mod colormaps;
+pub mod columns;
 pub mod compare;
+pub mod context;
+pub mod contour;
+pub mod density;
+pub mod emoji;
+pub mod encode;
+pub mod error;
+pub mod events;
 pub mod ffi;
+#[cfg(feature = "ffmpeg")]
+pub mod ffmpeg;
+mod glyphs;
+pub mod golden;
+pub mod handle;
+pub mod html;
+pub mod layers;
+pub mod layout;
+pub mod locale;
+pub mod mask;
+pub mod mathtext;
+pub mod origin;
+pub mod outline;
+pub mod periodic;
+pub mod picking;
+pub mod pointcloud;
 pub mod primitives;
 pub mod plotting;
+pub mod probe;
+pub mod quick;
+pub mod quickplot;
+pub mod recording;
+pub mod reference;
+pub mod refline;
+pub mod screen;
+pub mod shapes;
+pub mod sparkline;
+pub mod spec;
+pub mod stream;
+pub mod svg;
+pub mod temporal;
+#[cfg(feature = "term")]
+pub mod term;
 pub mod test_cases;
 pub mod text;
+pub mod ticks;
+pub mod tooltip;
+mod trace;
+pub mod underlay;
+pub mod units;
+pub mod walls;
+pub mod watch;
+#[cfg(feature = "window")]
+pub mod window;
 
 pub use backend::{Figure, WgpuBackend};
+pub use error::{Error, Result};
 
 // Re-export rendering components
 pub use primitives::{Instance, PrimitiveRenderer};
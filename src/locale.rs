@@ -0,0 +1,193 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Localized text for a figure.
+//!
+//! A [`Locale`] bundles what a figure needs to be shown in another
+//! language: month and weekday names with the date patterns of the
+//! temporal axis, and replacements for the few words the crate writes
+//! itself, looked up by key (see [`keys`]).  Install one with
+//! [`PlotBackend::set_locale`](crate::plotting::PlotBackend::set_locale);
+//! artists read it through
+//! [`DrawContext::locale`](crate::artist::DrawContext::locale).
+//!
+//! ```rust
+//! use mpl_wgpu::locale::{keys, Locale};
+//!
+//! let de = Locale::english()
+//!   .short_months(["Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez"])
+//!   .date_format("{d}. {b}")
+//!   .string(keys::MISSING, "k. A.");
+//! assert_eq!(de.text(keys::MISSING), "k. A.");
+//! ```
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Keys of the built-in strings.
+pub mod keys {
+  /// Placeholder for a value that is missing, e.g. a probe reading
+  /// outside a series.  English: `-`.
+  pub const MISSING: &str = "missing";
+}
+
+/// English text of the built-in string `key`, or `None` for unknown
+/// keys.
+fn english_text(key: &str) -> Option<&'static str> {
+  match key {
+    keys::MISSING => Some("-"),
+    _ => None,
+  }
+}
+
+/// Names, date patterns and strings of one language.
+///
+/// Date patterns use `{Y}` (year), `{m}` (month number), `{d}` (day of
+/// month), `{b}`/`{B}` (short/full month name) and `{a}`/`{A}`
+/// (short/full weekday name).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Locale {
+  /// Full month names, January first.
+  pub months: [String; 12],
+  /// Abbreviated month names, January first.
+  pub short_months: [String; 12],
+  /// Full weekday names, Monday first.
+  pub weekdays: [String; 7],
+  /// Abbreviated weekday names, Monday first.
+  pub short_weekdays: [String; 7],
+  /// Pattern of daily ticks.
+  pub day_format: String,
+  /// Pattern of weekly ticks and of the dates starting a new day
+  /// among hourly ticks.
+  pub date_format: String,
+  /// Pattern of monthly ticks.
+  pub month_format: String,
+  /// Replacements of built-in strings, by key.
+  pub strings: HashMap<String, String>,
+}
+
+impl Default for Locale {
+  fn default() -> Self {
+    let names = |s: &[&str]| s.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+    let months = names(&[
+      "January", "February", "March", "April", "May", "June",
+      "July", "August", "September", "October", "November", "December",
+    ]);
+    let weekdays = names(&["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"]);
+    Self {
+      short_months: std::array::from_fn(|i| months[i][..3].to_string()),
+      months: std::array::from_fn(|i| months[i].clone()),
+      short_weekdays: std::array::from_fn(|i| weekdays[i][..3].to_string()),
+      weekdays: std::array::from_fn(|i| weekdays[i].clone()),
+      day_format: "{a} {d} {b}".into(),
+      date_format: "{d} {b}".into(),
+      month_format: "{b} {Y}".into(),
+      strings: HashMap::new(),
+    }
+  }
+}
+
+impl Locale {
+  /// English names and strings.
+  pub fn english() -> Self {
+    Self::default()
+  }
+
+  /// Sets the full month names, January first.
+  pub fn months(mut self, names: [&str; 12]) -> Self {
+    self.months = names.map(String::from);
+    self
+  }
+
+  /// Sets the abbreviated month names, January first.
+  pub fn short_months(mut self, names: [&str; 12]) -> Self {
+    self.short_months = names.map(String::from);
+    self
+  }
+
+  /// Sets the full weekday names, Monday first.
+  pub fn weekdays(mut self, names: [&str; 7]) -> Self {
+    self.weekdays = names.map(String::from);
+    self
+  }
+
+  /// Sets the abbreviated weekday names, Monday first.
+  pub fn short_weekdays(mut self, names: [&str; 7]) -> Self {
+    self.short_weekdays = names.map(String::from);
+    self
+  }
+
+  /// Sets the pattern of daily ticks.
+  pub fn day_format(mut self, pattern: &str) -> Self {
+    self.day_format = pattern.into();
+    self
+  }
+
+  /// Sets the pattern of weekly ticks and new days among hourly ticks.
+  pub fn date_format(mut self, pattern: &str) -> Self {
+    self.date_format = pattern.into();
+    self
+  }
+
+  /// Sets the pattern of monthly ticks.
+  pub fn month_format(mut self, pattern: &str) -> Self {
+    self.month_format = pattern.into();
+    self
+  }
+
+  /// Replaces the built-in string `key` (see [`keys`]), or adds a
+  /// string for an artist of your own.
+  pub fn string(mut self, key: &str, text: &str) -> Self {
+    self.strings.insert(key.into(), text.into());
+    self
+  }
+
+  /// The string for `key`: the replacement if set, else the English
+  /// built-in text, else the key itself.
+  pub fn text<'s>(&'s self, key: &'s str) -> &'s str {
+    self.strings.get(key).map(String::as_str).or_else(|| english_text(key)).unwrap_or(key)
+  }
+
+  /// Formats a date with `pattern`.  `month` counts from 1 and
+  /// `weekday` from 0 for Monday.
+  pub fn format_date(&self, pattern: &str, year: i64, month: u32, day: u32, weekday: u32) -> String {
+    let (m, w) = ((month as usize).clamp(1, 12) - 1, weekday as usize % 7);
+    pattern
+      .replace("{Y}", &format!("{:04}", year))
+      .replace("{m}", &format!("{:02}", month))
+      .replace("{d}", &day.to_string())
+      .replace("{B}", &self.months[m])
+      .replace("{b}", &self.short_months[m])
+      .replace("{A}", &self.weekdays[w])
+      .replace("{a}", &self.short_weekdays[w])
+  }
+}
+
+/// Shared English locale, for contexts without one.
+pub(crate) fn english() -> &'static Locale {
+  static ENGLISH: OnceLock<Locale> = OnceLock::new();
+  ENGLISH.get_or_init(Locale::english)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn strings_fall_back_to_english() {
+    assert_eq!(Locale::english().text(keys::MISSING), "-");
+    let locale = Locale::english().string(keys::MISSING, "n. v.").string("unit", "Stück");
+    assert_eq!(locale.text(keys::MISSING), "n. v.");
+    assert_eq!(locale.text("unit"), "Stück");
+    assert_eq!(locale.text("unknown"), "unknown");
+  }
+
+  #[test]
+  fn patterns_use_the_names() {
+    let en = Locale::english();
+    assert_eq!(en.format_date(&en.day_format, 2024, 1, 1, 0), "Mon 1 Jan");
+    assert_eq!(en.format_date("{A}, {B} {d} {Y} ({m})", 2024, 9, 5, 3), "Thursday, September 5 2024 (09)");
+    let fr = en.short_months(["janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.", "déc."]);
+    assert_eq!(fr.format_date(&fr.month_format, 2024, 8, 1, 3), "août 2024");
+  }
+}
@@ -0,0 +1,82 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Validity masks for gridded data.
+//!
+//! A mask flags cells that hold no valid data, following numpy's
+//! masked arrays: `true` means masked.  [`apply_mask`] turns masked
+//! cells into NaN, which the backend leaves out (holes in surfaces,
+//! transparent heatmap cells, gaps in contours) instead of plotting them
+//! as zeros.  [`Axes::surf_masked`](crate::plotting::Axes::surf_masked)
+//! and [`Axes::heatmap_masked`](crate::plotting::Axes::heatmap_masked)
+//! take a mask directly.  For lines, [`finite_runs`] splits a series
+//! at its NaN samples.
+
+use std::ops::Range;
+
+/// `values` with every masked entry replaced by NaN.  Entries past the
+/// end of `mask` are unmasked.
+pub fn apply_mask(values: &[f64], mask: &[bool]) -> Vec<f64> {
+  values
+    .iter()
+    .enumerate()
+    .map(|(i, &v)| if mask.get(i).copied().unwrap_or(false) { f64::NAN } else { v })
+    .collect()
+}
+
+/// Mask of the non-finite entries of `values` (numpy's
+/// `masked_invalid`).
+pub fn mask_invalid(values: &[f64]) -> Vec<bool> {
+  values.iter().map(|v| !v.is_finite()).collect()
+}
+
+/// Index ranges of the maximal runs of points of `(x, y)` with both
+/// coordinates finite.  A line is drawn as one polyline per run, so a
+/// NaN sample leaves a gap as in matplotlib.
+pub fn finite_runs(x: &[f64], y: &[f64]) -> Vec<Range<usize>> {
+  let n = x.len().min(y.len());
+  let mut runs = Vec::new();
+  let mut start = None;
+  for i in 0..n {
+    match (x[i].is_finite() && y[i].is_finite(), start) {
+      (true, None) => start = Some(i),
+      (false, Some(s)) => {
+        runs.push(s..i);
+        start = None;
+      }
+      _ => {}
+    }
+  }
+  if let Some(s) = start {
+    runs.push(s..n);
+  }
+  runs
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn masked_cells_become_nan() {
+    let z = apply_mask(&[1.0, 2.0, 3.0, 4.0], &[false, true, false]);
+    assert_eq!(z[0], 1.0);
+    assert!(z[1].is_nan());
+    assert_eq!(&z[2..], &[3.0, 4.0]);
+  }
+
+  #[test]
+  fn lines_break_at_nan() {
+    let x = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    let y = [0.0, 1.0, f64::NAN, 3.0, 4.0, f64::INFINITY];
+    assert_eq!(finite_runs(&x, &y), vec![0..2, 3..5]);
+    assert_eq!(finite_runs(&x[..2], &y[..2]), vec![0..2]);
+    assert!(finite_runs(&[f64::NAN], &[1.0]).is_empty());
+  }
+
+  #[test]
+  fn invalid_values_are_masked() {
+    let mask = mask_invalid(&[0.0, f64::NAN, f64::INFINITY, -1.0]);
+    assert_eq!(mask, vec![false, true, true, false]);
+  }
+}
@@ -0,0 +1,597 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! A small TeX-like math syntax for titles, labels and annotations.
+//!
+//! Text between unescaped `$` signs is math: `^` and `_` raise and
+//! lower the following character or `{group}`, `\frac{a}{b}` stacks a
+//! fraction, and commands such as `\alpha`, `\Omega`, `\times` or
+//! `\infty` insert symbols.  `\$` is a literal dollar sign.  A label
+//! like `"Flux ($10^{-3}\,\mathrm{W}/m^2$)"` is laid out by [`layout`]
+//! into glyph runs with their own offsets and sizes, plus fraction
+//! rules, which the text callbacks draw in place of the raw string.
+//! Glyphs missing from the primary font are picked from the fallback
+//! fonts like any other text.
+
+use crate::primitives::PrimitiveRenderer;
+use crate::recording::{RecordedPrimitive, Recording};
+use crate::text::TextRenderer;
+use glam::{Vec2, Vec4};
+
+/// Script size relative to its base.
+const SCRIPT_SCALE: f32 = 0.7;
+/// Fraction numerator and denominator size relative to the fraction.
+const FRAC_SCALE: f32 = 0.8;
+/// Smallest size scripts shrink to, relative to the label size.
+const MIN_SCALE: f32 = 0.5;
+
+/// Vertical font metrics as fractions of the font size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Metrics {
+  /// Baseline to top of the line box.
+  pub ascent: f32,
+  /// Baseline to bottom of the line box.
+  pub descent: f32,
+}
+
+impl Default for Metrics {
+  fn default() -> Self {
+    Self {
+      ascent: 0.8,
+      descent: 0.2,
+    }
+  }
+}
+
+/// A run of glyphs in a [`MathLayout`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MathGlyph {
+  /// Text of the run.
+  pub text: String,
+  /// Top-left of the run relative to the layout's top-left.
+  pub offset: Vec2,
+  /// Font size in pixels.
+  pub size: f32,
+}
+
+/// A fraction bar in a [`MathLayout`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rule {
+  /// Left end, relative to the layout's top-left.
+  pub start: Vec2,
+  /// Right end.
+  pub end: Vec2,
+  /// Thickness in pixels.
+  pub thickness: f32,
+}
+
+/// Positioned glyph runs and rules of a label.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MathLayout {
+  /// Glyph runs.
+  pub glyphs: Vec<MathGlyph>,
+  /// Fraction bars.
+  pub rules: Vec<Rule>,
+  /// Extent of the whole label.
+  pub size: Vec2,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+  Text(String),
+  Group(Vec<Node>),
+  Script {
+    base: Box<Node>,
+    sup: Option<Box<Node>>,
+    sub: Option<Box<Node>>,
+  },
+  Frac(Box<Node>, Box<Node>),
+}
+
+/// Whether `text` contains a `$...$` math span.
+pub fn has_math(text: &str) -> bool {
+  split_math(text).iter().any(|(math, _)| *math)
+}
+
+/// Splits `text` into plain and math segments.  An unmatched `$` is
+/// kept as a literal, and `\$` becomes `$` in plain text.
+fn split_math(text: &str) -> Vec<(bool, String)> {
+  let mut segments = Vec::new();
+  let mut current = String::new();
+  let mut math = false;
+  let mut chars = text.chars().peekable();
+  while let Some(c) = chars.next() {
+    match c {
+      '\\' if chars.peek() == Some(&'$') => {
+        chars.next();
+        if math {
+          current.push_str("\\$");
+        } else {
+          current.push('$');
+        }
+      }
+      '$' => {
+        segments.push((math, std::mem::take(&mut current)));
+        math = !math;
+      }
+      _ => current.push(c),
+    }
+  }
+  if math {
+    // Unclosed: the opening `$` was literal.
+    let open = segments.pop().map(|(_, s)| s).unwrap_or_default();
+    current = format!("{}${}", open, current);
+    math = false;
+  }
+  segments.push((math, current));
+  segments.retain(|(_, s)| !s.is_empty());
+  segments
+}
+
+/// Unicode for a symbol command, without the backslash.
+fn symbol(name: &str) -> Option<&'static str> {
+  Some(match name {
+    "alpha" => "\u{3b1}",
+    "beta" => "\u{3b2}",
+    "gamma" => "\u{3b3}",
+    "delta" => "\u{3b4}",
+    "epsilon" => "\u{3f5}",
+    "varepsilon" => "\u{3b5}",
+    "zeta" => "\u{3b6}",
+    "eta" => "\u{3b7}",
+    "theta" => "\u{3b8}",
+    "vartheta" => "\u{3d1}",
+    "iota" => "\u{3b9}",
+    "kappa" => "\u{3ba}",
+    "lambda" => "\u{3bb}",
+    "mu" => "\u{3bc}",
+    "nu" => "\u{3bd}",
+    "xi" => "\u{3be}",
+    "pi" => "\u{3c0}",
+    "rho" => "\u{3c1}",
+    "sigma" => "\u{3c3}",
+    "tau" => "\u{3c4}",
+    "upsilon" => "\u{3c5}",
+    "phi" => "\u{3d5}",
+    "varphi" => "\u{3c6}",
+    "chi" => "\u{3c7}",
+    "psi" => "\u{3c8}",
+    "omega" => "\u{3c9}",
+    "Gamma" => "\u{393}",
+    "Delta" => "\u{394}",
+    "Theta" => "\u{398}",
+    "Lambda" => "\u{39b}",
+    "Xi" => "\u{39e}",
+    "Pi" => "\u{3a0}",
+    "Sigma" => "\u{3a3}",
+    "Upsilon" => "\u{3a5}",
+    "Phi" => "\u{3a6}",
+    "Psi" => "\u{3a8}",
+    "Omega" => "\u{3a9}",
+    "times" => "\u{d7}",
+    "cdot" => "\u{b7}",
+    "div" => "\u{f7}",
+    "pm" => "\u{b1}",
+    "mp" => "\u{2213}",
+    "leq" | "le" => "\u{2264}",
+    "geq" | "ge" => "\u{2265}",
+    "neq" | "ne" => "\u{2260}",
+    "approx" => "\u{2248}",
+    "sim" => "\u{223c}",
+    "propto" => "\u{221d}",
+    "infty" => "\u{221e}",
+    "partial" => "\u{2202}",
+    "nabla" => "\u{2207}",
+    "sum" => "\u{2211}",
+    "prod" => "\u{220f}",
+    "int" => "\u{222b}",
+    "sqrt" => "\u{221a}",
+    "in" => "\u{2208}",
+    "circ" => "\u{2218}",
+    "degree" => "\u{b0}",
+    "prime" => "\u{2032}",
+    "ell" => "\u{2113}",
+    "hbar" => "\u{210f}",
+    "AA" => "\u{c5}",
+    "to" | "rightarrow" => "\u{2192}",
+    "leftarrow" => "\u{2190}",
+    "ldots" => "\u{2026}",
+    "cdots" => "\u{22ef}",
+    "langle" => "\u{27e8}",
+    "rangle" => "\u{27e9}",
+    _ => return None,
+  })
+}
+
+struct Parser<'a> {
+  chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl Parser<'_> {
+  /// Nodes up to the end of input or an unmatched `}`.
+  fn list(&mut self) -> Vec<Node> {
+    let mut nodes: Vec<Node> = Vec::new();
+    while let Some(&c) = self.chars.peek() {
+      match c {
+        '}' => {
+          self.chars.next();
+          break;
+        }
+        '^' | '_' => {
+          self.chars.next();
+          let arg = self.atom().unwrap_or(Node::Text(String::new()));
+          let base = take_base(&mut nodes);
+          let (base, mut sup, mut sub) = match base {
+            Node::Script { base, sup, sub } => (base, sup, sub),
+            other => (Box::new(other), None, None),
+          };
+          if c == '^' {
+            sup = Some(Box::new(arg));
+          } else {
+            sub = Some(Box::new(arg));
+          }
+          nodes.push(Node::Script { base, sup, sub });
+        }
+        c if c.is_whitespace() => {
+          // Spaces are ignored in math, as in TeX.
+          self.chars.next();
+        }
+        _ => {
+          if let Some(node) = self.atom() {
+            match (nodes.last_mut(), node) {
+              (Some(Node::Text(prev)), Node::Text(t)) => prev.push_str(&t),
+              (_, node) => nodes.push(node),
+            }
+          }
+        }
+      }
+    }
+    nodes
+  }
+
+  /// A single character, command or `{group}`.
+  fn atom(&mut self) -> Option<Node> {
+    while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+      self.chars.next();
+    }
+    let c = self.chars.next()?;
+    Some(match c {
+      '{' => Node::Group(self.list()),
+      '\\' => self.command(),
+      c => Node::Text(c.to_string()),
+    })
+  }
+
+  fn command(&mut self) -> Node {
+    let mut name = String::new();
+    while let Some(&c) = self.chars.peek() {
+      if !c.is_ascii_alphabetic() {
+        break;
+      }
+      name.push(c);
+      self.chars.next();
+    }
+    if name.is_empty() {
+      return Node::Text(match self.chars.next() {
+        Some(',') => "\u{2009}".to_string(),
+        Some(';') | Some(' ') => " ".to_string(),
+        Some(c) => c.to_string(),
+        None => "\\".to_string(),
+      });
+    }
+    match name.as_str() {
+      "frac" => {
+        let num = self.atom().unwrap_or(Node::Group(Vec::new()));
+        let den = self.atom().unwrap_or(Node::Group(Vec::new()));
+        Node::Frac(Box::new(num), Box::new(den))
+      }
+      // No upright or text font switching; the argument is kept as is.
+      "mathrm" | "text" | "mathit" | "mathbf" => {
+        self.atom().unwrap_or(Node::Group(Vec::new()))
+      }
+      _ => match symbol(&name) {
+        Some(s) => Node::Text(s.to_string()),
+        None => Node::Text(format!("\\{}", name)),
+      },
+    }
+  }
+}
+
+/// Removes and returns the base of a script: the last character of a
+/// trailing text node or the last node.
+fn take_base(nodes: &mut Vec<Node>) -> Node {
+  match nodes.last_mut() {
+    Some(Node::Text(t)) if t.chars().count() > 1 => {
+      let last = t.pop().unwrap();
+      Node::Text(last.to_string())
+    }
+    Some(_) => nodes.pop().unwrap(),
+    None => Node::Text(String::new()),
+  }
+}
+
+fn parse(math: &str) -> Vec<Node> {
+  Parser {
+    chars: math.chars().peekable(),
+  }
+  .list()
+}
+
+/// Laid out node relative to its baseline origin (Y down).
+#[derive(Default)]
+struct Boxed {
+  glyphs: Vec<MathGlyph>,
+  rules: Vec<Rule>,
+  width: f32,
+  ascent: f32,
+  descent: f32,
+}
+
+impl Boxed {
+  /// Appends `other` with its origin at `(x, baseline)`.
+  fn place(&mut self, other: Boxed, x: f32, baseline: f32) {
+    let d = Vec2::new(x, baseline);
+    self.glyphs.extend(other.glyphs.into_iter().map(|mut g| {
+      g.offset += d;
+      g
+    }));
+    self.rules.extend(other.rules.into_iter().map(|mut r| {
+      r.start += d;
+      r.end += d;
+      r
+    }));
+    self.width = self.width.max(x + other.width);
+    self.ascent = self.ascent.max(other.ascent - baseline);
+    self.descent = self.descent.max(other.descent + baseline);
+  }
+
+  /// Appends `other` after the current content on the same baseline.
+  fn append(&mut self, other: Boxed) {
+    let x = self.width;
+    self.place(other, x, 0.0);
+  }
+}
+
+struct Layouter<'a> {
+  width: &'a dyn Fn(&str, f32) -> f32,
+  metrics: Metrics,
+  min_size: f32,
+}
+
+impl Layouter<'_> {
+  fn text(&self, text: &str, size: f32) -> Boxed {
+    let mut b = Boxed {
+      width: (self.width)(text, size),
+      ascent: self.metrics.ascent * size,
+      descent: self.metrics.descent * size,
+      ..Boxed::default()
+    };
+    if !text.is_empty() {
+      b.glyphs.push(MathGlyph {
+        text: text.to_string(),
+        offset: Vec2::new(0.0, -b.ascent),
+        size,
+      });
+    }
+    b
+  }
+
+  fn nodes(&self, nodes: &[Node], size: f32) -> Boxed {
+    let mut b = Boxed::default();
+    for node in nodes {
+      b.append(self.node(node, size));
+    }
+    b
+  }
+
+  fn node(&self, node: &Node, size: f32) -> Boxed {
+    match node {
+      Node::Text(t) => self.text(t, size),
+      Node::Group(nodes) => self.nodes(nodes, size),
+      Node::Script { base, sup, sub } => {
+        let mut b = self.node(base, size);
+        let x = b.width;
+        let small = (size * SCRIPT_SCALE).max(self.min_size);
+        if let Some(sup) = sup {
+          b.place(self.node(sup, small), x, -0.45 * size);
+        }
+        if let Some(sub) = sub {
+          b.place(self.node(sub, small), x, 0.2 * size);
+        }
+        b
+      }
+      Node::Frac(num, den) => {
+        let small = (size * FRAC_SCALE).max(self.min_size);
+        let (num, den) = (self.node(num, small), self.node(den, small));
+        let axis = -0.3 * size;
+        let thickness = (size * 0.06).max(1.0);
+        let gap = 0.1 * size;
+        let pad = 0.1 * size;
+        let width = num.width.max(den.width) + 2.0 * pad;
+        let mut b = Boxed {
+          width,
+          ..Boxed::default()
+        };
+        let num_base = axis - thickness * 0.5 - gap - num.descent;
+        let den_base = axis + thickness * 0.5 + gap + den.ascent;
+        let (num_x, den_x) = ((width - num.width) * 0.5, (width - den.width) * 0.5);
+        b.place(num, num_x, num_base);
+        b.place(den, den_x, den_base);
+        b.rules.push(Rule {
+          start: Vec2::new(pad * 0.5, axis),
+          end: Vec2::new(width - pad * 0.5, axis),
+          thickness,
+        });
+        b
+      }
+    }
+  }
+}
+
+/// Lays out `text` at `size` pixels.  Plain segments and `$...$` math
+/// share one baseline; `width` measures a run of text at a size, and
+/// `metrics` gives the font's vertical extent.
+pub fn layout(
+  text: &str,
+  size: f32,
+  metrics: Metrics,
+  width: &dyn Fn(&str, f32) -> f32,
+) -> MathLayout {
+  let layouter = Layouter {
+    width,
+    metrics,
+    min_size: size * MIN_SCALE,
+  };
+  let mut b = Boxed::default();
+  for (math, segment) in split_math(text) {
+    if math {
+      b.append(layouter.nodes(&parse(&segment), size));
+    } else {
+      b.append(layouter.text(&segment, size));
+    }
+  }
+  let top = Vec2::new(0.0, b.ascent);
+  MathLayout {
+    glyphs: b
+      .glyphs
+      .into_iter()
+      .map(|mut g| {
+        g.offset += top;
+        g
+      })
+      .collect(),
+    rules: b
+      .rules
+      .into_iter()
+      .map(|mut r| {
+        r.start += top;
+        r.end += top;
+        r
+      })
+      .collect(),
+    size: Vec2::new(b.width, b.ascent + b.descent),
+  }
+}
+
+/// Screen offset of layout offset `v` for text rotated `degrees`
+/// counter-clockwise on a Y-down screen.
+fn rotate(v: Vec2, degrees: f32) -> Vec2 {
+  let (s, c) = degrees.to_radians().sin_cos();
+  Vec2::new(c * v.x + s * v.y, -s * v.x + c * v.y)
+}
+
+/// Draws `layout` with its top-left at `pos`, rotated `rotation`
+/// degrees about `pos`, into whichever targets are present.
+pub(crate) fn draw_math(
+  layout: &MathLayout,
+  pos: Vec2,
+  rotation: f32,
+  color: Vec4,
+  prim: Option<&mut PrimitiveRenderer>,
+  text: Option<&mut TextRenderer>,
+  record: Option<&mut Recording>,
+) {
+  // While recording, the text renderer is only used for measuring.
+  if let Some(record) = record {
+    for g in &layout.glyphs {
+      record.push(RecordedPrimitive::Text {
+        text: g.text.clone(),
+        pos: pos + rotate(g.offset, rotation),
+        size: g.size,
+        color,
+        rotation,
+      });
+    }
+    for r in &layout.rules {
+      record.push(RecordedPrimitive::Line {
+        start: (pos + rotate(r.start, rotation)).extend(0.0),
+        end: (pos + rotate(r.end, rotation)).extend(0.0),
+        width: r.thickness,
+        color,
+        dash_len: 0.0,
+        gap_len: 0.0,
+        dash_offset: 0.0,
+      });
+    }
+    return;
+  }
+  if let Some(text) = text {
+    for g in &layout.glyphs {
+      let at = pos + rotate(g.offset, rotation);
+      text.draw_text_rotated(&g.text, at, g.size, color, rotation);
+    }
+  }
+  if let Some(prim) = prim {
+    for r in &layout.rules {
+      let start = (pos + rotate(r.start, rotation)).extend(0.0);
+      let end = (pos + rotate(r.end, rotation)).extend(0.0);
+      prim.draw_line(start, end, r.thickness, color, 0.0, 0.0, 0.0);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Monospace measure: every character is half the size wide.
+  fn mono(text: &str, size: f32) -> f32 {
+    text.chars().count() as f32 * size * 0.5
+  }
+
+  fn lay(text: &str) -> MathLayout {
+    layout(text, 10.0, Metrics::default(), &mono)
+  }
+
+  #[test]
+  fn splits_math_segments() {
+    assert!(has_math("a $x$ b"));
+    assert!(!has_math("costs $5"));
+    assert!(!has_math("\\$x\\$"));
+    assert_eq!(
+      split_math("a $x$ b"),
+      vec![(false, "a ".into()), (true, "x".into()), (false, " b".into())]
+    );
+    assert_eq!(split_math("costs $5"), vec![(false, "costs $5".into())]);
+  }
+
+  #[test]
+  fn symbols_and_scripts() {
+    let l = lay("$\\alpha^2$");
+    assert_eq!(l.glyphs.len(), 2);
+    assert_eq!(l.glyphs[0].text, "\u{3b1}");
+    assert_eq!(l.glyphs[1].text, "2");
+    assert_eq!(l.glyphs[1].size, 7.0);
+    // The superscript sits to the right of and above the base.
+    assert_eq!(l.glyphs[1].offset.x, 5.0);
+    assert!(l.glyphs[1].offset.y < l.glyphs[0].offset.y);
+    assert!(l.size.y > 10.0);
+
+    // Only the last character is the base; `_` lowers.
+    let l = lay("$xy_{ij}$");
+    assert_eq!(l.glyphs[0].text, "x");
+    assert_eq!(l.glyphs[1].text, "y");
+    assert_eq!(l.glyphs[2].text, "ij");
+    assert!(l.glyphs[2].offset.y > l.glyphs[1].offset.y);
+    assert_eq!(l.size.x, 10.0 + 7.0);
+  }
+
+  #[test]
+  fn fractions_stack_around_a_rule() {
+    let l = lay("$\\frac{1}{2}$");
+    assert_eq!(l.rules.len(), 1);
+    let rule = l.rules[0];
+    let (num, den) = (&l.glyphs[0], &l.glyphs[1]);
+    assert!(num.offset.y + num.size * 0.8 < rule.start.y);
+    assert!(den.offset.y > rule.start.y);
+    assert_eq!(num.offset.x, den.offset.x);
+  }
+
+  #[test]
+  fn plain_text_and_unknown_commands_pass_through() {
+    let l = lay("E = $mc^2$ in \\$");
+    assert_eq!(l.glyphs[0].text, "E = ");
+    assert_eq!(l.glyphs.last().unwrap().text, " in $");
+    assert_eq!(lay("$\\foo$").glyphs[0].text, "\\foo");
+  }
+}
@@ -0,0 +1,109 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Floating origin for large-magnitude world coordinates.
+//!
+//! Under a view projection, world positions go to the GPU as `f32` in
+//! every [`Instance`](crate::primitives::Instance).  `f32` keeps about
+//! seven significant digits, so GPS timestamps (1.7e9 s) or UTM
+//! northings (5e6 m) lose everything below a few hundred units and
+//! lines jitter when zoomed in.  A [`FloatingOrigin`] subtracts a
+//! per-axis origin near the data in `f64` before the cast, and folds
+//! the same translation into the view projection, again in `f64`, so
+//! the GPU only sees small relative coordinates.  Set it with
+//! [`PrimitiveRenderer::set_origin`](crate::primitives::PrimitiveRenderer::set_origin).
+
+use glam::{DMat4, DVec3, Mat4, Vec3};
+
+/// Ratio of magnitude to span above which an axis gets an origin.
+/// Below it, `f32` still resolves about 1/1000 of the span.
+pub const MAGNITUDE_RATIO: f64 = 1e4;
+
+/// Origin for an axis showing `[min, max]`: `0` when the values are
+/// small compared to their span, else `min` rounded down to a power of
+/// ten below the span, so the origin reads well in offset labels.
+pub fn axis_origin(min: f64, max: f64) -> f64 {
+  if !(min.is_finite() && max.is_finite()) {
+    return 0.0;
+  }
+  let (lo, hi) = (min.min(max), min.max(max));
+  let span = hi - lo;
+  let magnitude = lo.abs().max(hi.abs());
+  if !span.is_finite() || magnitude <= span.max(f64::MIN_POSITIVE) * MAGNITUDE_RATIO {
+    return 0.0;
+  }
+  let step = if span > 0.0 { 10f64.powf(span.log10().floor()) } else { 10f64.powf(magnitude.log10().floor() - 6.0) };
+  (lo / step).floor() * step
+}
+
+/// Per-axis world origin subtracted before positions become `f32`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FloatingOrigin {
+  /// World position mapped to the local zero.
+  pub origin: DVec3,
+}
+
+impl FloatingOrigin {
+  /// Origin at `origin`.
+  pub fn new(origin: DVec3) -> Self {
+    Self { origin }
+  }
+
+  /// Origin picked per axis with [`axis_origin`] for data in the box
+  /// `[min, max]`.
+  pub fn for_bounds(min: DVec3, max: DVec3) -> Self {
+    Self::new(DVec3::new(
+      axis_origin(min.x, max.x),
+      axis_origin(min.y, max.y),
+      axis_origin(min.z, max.z),
+    ))
+  }
+
+  /// `p` relative to the origin, as sent to the GPU.
+  pub fn local(&self, p: DVec3) -> Vec3 {
+    (p - self.origin).as_vec3()
+  }
+
+  /// World position of the local point `p`.
+  pub fn world(&self, p: Vec3) -> DVec3 {
+    p.as_dvec3() + self.origin
+  }
+
+  /// `view_proj` for local coordinates: the world view projection
+  /// with the origin translation folded in before rounding to `f32`.
+  pub fn view_projection(&self, view_proj: DMat4) -> Mat4 {
+    (view_proj * DMat4::from_translation(self.origin)).as_mat4()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn origins_only_for_large_magnitudes() {
+    assert_eq!(axis_origin(0.0, 10.0), 0.0);
+    assert_eq!(axis_origin(-5.0, 5.0), 0.0);
+    assert_eq!(axis_origin(1_700_000_123.0, 1_700_000_987.0), 1_700_000_100.0);
+    assert_eq!(axis_origin(5_412_345.6, 5_412_398.2), 5_412_340.0);
+    assert_eq!(axis_origin(f64::NAN, 1.0), 0.0);
+  }
+
+  #[test]
+  fn local_coordinates_keep_precision() {
+    let t = DVec3::new(1_700_000_000.25, 5_412_345.5, 0.0);
+    let naive = t.as_vec3();
+    assert_ne!(naive.as_dvec3(), t);
+
+    let origin = FloatingOrigin::for_bounds(t, t + DVec3::new(100.0, 100.0, 0.0));
+    assert_eq!(origin.world(origin.local(t)), t);
+
+    // The folded view projection maps local points where the world
+    // projection maps world points.
+    let view = DMat4::from_scale(DVec3::splat(0.01)) * DMat4::from_translation(-t);
+    let p = t + DVec3::new(12.5, -3.0, 0.0);
+    let expected = view.transform_point3(p).as_vec3();
+    let got = origin.view_projection(view).transform_point3(origin.local(p));
+    assert!((got - expected).abs().max_element() < 1e-3, "{:?} vs {:?}", got, expected);
+  }
+}
@@ -0,0 +1,153 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Glyph outlines for vector export of text.
+//!
+//! Vector output that names a font only looks right where that font is
+//! installed.  Converting text to its glyph outlines, read from the
+//! font's `glyf` or `CFF` tables by ttf-parser through ab_glyph, draws
+//! the same shapes everywhere at the cost of selectable text.  The
+//! outlines are laid out like [`TextRenderer`](crate::text::TextRenderer)
+//! lays out text: anchored top-left, Y down, with kerning, fallback
+//! fonts and line breaks, so a path drawn at the text anchor covers the
+//! rasterized glyphs.
+//!
+//! Outline text with
+//! [`TextMetrics::outline_text`](crate::text::TextMetrics::outline_text)
+//! and write the result with [`svg_path_data`].
+
+use crate::text::font_runs;
+use glam::Vec2;
+use wgpu_text::glyph_brush::ab_glyph::{Font, OutlineCurve, Point, PxScale, ScaleFont};
+
+/// One drawing command of an outline, in pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathCommand {
+  /// Starts a contour.
+  MoveTo(Vec2),
+  /// Straight line to the point.
+  LineTo(Vec2),
+  /// Quadratic Bézier curve through the control to the end point.
+  QuadTo(Vec2, Vec2),
+  /// Cubic Bézier curve through both controls to the end point.
+  CubicTo(Vec2, Vec2, Vec2),
+  /// Closes the contour.
+  Close,
+}
+
+/// Outlines of `text` at `size` pixels with its top-left corner at
+/// `pos`, with characters missing from the first of `fonts` taken from
+/// the fallbacks (see [`font_runs`]).  Whitespace and glyphs without an
+/// outline add no commands.
+pub fn outline_text<F: Font>(fonts: &[F], text: &str, size: f32, pos: Vec2) -> Vec<PathCommand> {
+  let mut out = Vec::new();
+  let Some(primary) = fonts.first() else { return out };
+  let scale = PxScale::from(size);
+  let primary = primary.as_scaled(scale);
+  let mut baseline = pos.y + primary.ascent();
+  for line in text.split('\n') {
+    let mut x = pos.x;
+    for (font, run) in font_runs(fonts, line) {
+      let scaled = fonts[font].as_scaled(scale);
+      let mut prev = None;
+      for c in run.chars() {
+        let id = scaled.glyph_id(c);
+        if let Some(p) = prev {
+          x += scaled.kern(p, id);
+        }
+        if let Some(outline) = fonts[font].outline(id) {
+          let (sx, sy) = (scaled.h_scale_factor(), scaled.v_scale_factor());
+          let map = |p: Point| Vec2::new(x + p.x * sx, baseline - p.y * sy);
+          push_curves(&mut out, outline.curves.iter().map(|c| match *c {
+            OutlineCurve::Line(a, b) => (map(a), PathCommand::LineTo(map(b))),
+            OutlineCurve::Quad(a, b, c) => (map(a), PathCommand::QuadTo(map(b), map(c))),
+            OutlineCurve::Cubic(a, b, c, d) => (map(a), PathCommand::CubicTo(map(b), map(c), map(d))),
+          }));
+        }
+        x += scaled.h_advance(id);
+        prev = Some(id);
+      }
+    }
+    baseline += primary.height() + primary.line_gap();
+  }
+  out
+}
+
+/// Appends curves given as start point and command, starting a new
+/// contour wherever a curve does not continue the previous one.
+fn push_curves(out: &mut Vec<PathCommand>, curves: impl Iterator<Item = (Vec2, PathCommand)>) {
+  let mut current = None;
+  for (start, command) in curves {
+    if current != Some(start) {
+      if current.is_some() {
+        out.push(PathCommand::Close);
+      }
+      out.push(PathCommand::MoveTo(start));
+    }
+    current = Some(end_point(command));
+    out.push(command);
+  }
+  if current.is_some() {
+    out.push(PathCommand::Close);
+  }
+}
+
+fn end_point(command: PathCommand) -> Vec2 {
+  match command {
+    PathCommand::MoveTo(p) | PathCommand::LineTo(p) | PathCommand::QuadTo(_, p) | PathCommand::CubicTo(_, _, p) => p,
+    PathCommand::Close => Vec2::NAN,
+  }
+}
+
+/// `commands` as the `d` attribute of an SVG `<path>`, coordinates
+/// rounded to 1/100 pixel.
+pub fn svg_path_data(commands: &[PathCommand]) -> String {
+  let point = |p: Vec2| format!("{} {}", round(p.x), round(p.y));
+  let parts: Vec<String> = commands
+    .iter()
+    .map(|command| match *command {
+      PathCommand::MoveTo(p) => format!("M {}", point(p)),
+      PathCommand::LineTo(p) => format!("L {}", point(p)),
+      PathCommand::QuadTo(c, p) => format!("Q {} {}", point(c), point(p)),
+      PathCommand::CubicTo(c1, c2, p) => format!("C {} {} {}", point(c1), point(c2), point(p)),
+      PathCommand::Close => "Z".to_string(),
+    })
+    .collect();
+  parts.join(" ")
+}
+
+fn round(v: f32) -> f32 {
+  (v * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn curves_split_into_closed_contours() {
+    let (a, b, c) = (Vec2::ZERO, Vec2::X, Vec2::Y);
+    let mut out = Vec::new();
+    push_curves(&mut out, [(a, PathCommand::LineTo(b)), (b, PathCommand::QuadTo(c, a)), (c, PathCommand::LineTo(b))].into_iter());
+    assert_eq!(out.len(), 7);
+    assert_eq!(out[3], PathCommand::Close);
+    assert_eq!(out[4], PathCommand::MoveTo(c));
+    assert_eq!(svg_path_data(&out), "M 0 0 L 1 0 Q 0 1 0 0 Z M 0 1 L 1 0 Z");
+  }
+
+  #[cfg(feature = "embedded-font")]
+  #[test]
+  fn outlines_cover_the_measured_text() {
+    use crate::text::{measure_with_font, EMBEDDED_FONT};
+    use wgpu_text::glyph_brush::ab_glyph::FontArc;
+    let font = FontArc::try_from_slice(EMBEDDED_FONT).unwrap();
+    let pos = Vec2::new(10.0, 20.0);
+    let commands = outline_text(std::slice::from_ref(&font), "Hg\nx", 20.0, pos);
+    assert!(matches!(commands[0], PathCommand::MoveTo(_)));
+    let size = measure_with_font(&font, "Hg\nx", 20.0);
+    for p in commands.iter().map(|&c| end_point(c)).filter(|p| p.is_finite()) {
+      assert!(p.cmpge(pos).all() && p.cmple(pos + size).all(), "{} outside", p);
+    }
+    assert!(outline_text(&[font], " ", 20.0, pos).is_empty());
+  }
+}
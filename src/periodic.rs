@@ -0,0 +1,185 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Wrapped axes for angular data.
+//!
+//! Angles live on a circle: a heading going from 359° to 1° moves by
+//! two degrees, not by 358.  A [`Period`] describes the wrapped range
+//! (`0..360`, `-π..π`, ...).  [`split_wrapped`] cuts a line where it
+//! crosses the wrap, ending it at one edge and resuming it at the other
+//! instead of drawing a jump across the axes, and [`angle_ticks`] picks
+//! ticks labelled in degrees (`90°`) or fractions of π (`π/2`).  Plot
+//! wrapped data with
+//! [`Axes::plot_wrapped`](crate::plotting::Axes::plot_wrapped).
+
+use std::f64::consts::PI;
+
+/// Unit of an angular axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AngleUnit {
+  /// Degrees, labelled `90°`.
+  Degrees,
+  /// Radians, labelled as fractions of π.
+  Radians,
+}
+
+/// A wrapped value range `[min, max)`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Period {
+  /// Lower bound, included.
+  pub min: f64,
+  /// Upper bound, identified with `min`.
+  pub max: f64,
+  /// Unit used for tick labels.
+  pub unit: AngleUnit,
+}
+
+impl Period {
+  /// `0..360` degrees.
+  pub const DEGREES: Period = Period { min: 0.0, max: 360.0, unit: AngleUnit::Degrees };
+  /// `-180..180` degrees.
+  pub const SIGNED_DEGREES: Period = Period { min: -180.0, max: 180.0, unit: AngleUnit::Degrees };
+  /// `0..2π` radians.
+  pub const RADIANS: Period = Period { min: 0.0, max: 2.0 * PI, unit: AngleUnit::Radians };
+  /// `-π..π` radians.
+  pub const SIGNED_RADIANS: Period = Period { min: -PI, max: PI, unit: AngleUnit::Radians };
+
+  /// Length of the period.
+  pub fn span(&self) -> f64 {
+    self.max - self.min
+  }
+
+  /// `v` wrapped into `[min, max)`.  Non-finite values are returned
+  /// unchanged.
+  pub fn wrap(&self, v: f64) -> f64 {
+    if !v.is_finite() {
+      return v;
+    }
+    self.min + (v - self.min).rem_euclid(self.span())
+  }
+}
+
+/// Wraps `y` into `period` and breaks the line `(x, y)` where it
+/// crosses the wrap.
+///
+/// Consecutive samples whose shortest angular path crosses the wrap get
+/// interpolated end points on both edges with a NaN gap in between, so
+/// the line leaves the axes at one edge and re-enters at the other.
+pub fn split_wrapped(x: &[f64], y: &[f64], period: Period) -> (Vec<f64>, Vec<f64>) {
+  let n = x.len().min(y.len());
+  let span = period.span();
+  let (mut ox, mut oy) = (Vec::with_capacity(n), Vec::with_capacity(n));
+  for i in 0..n {
+    let cur = period.wrap(y[i]);
+    if i > 0 {
+      let prev = period.wrap(y[i - 1]);
+      if prev.is_finite() && cur.is_finite() && x[i - 1].is_finite() && x[i].is_finite() {
+        // Shortest signed step from prev to cur.
+        let step = (cur - prev + span * 0.5).rem_euclid(span) - span * 0.5;
+        let unwrapped = prev + step;
+        let edge = if unwrapped >= period.max {
+          Some((period.max, period.min))
+        } else if unwrapped < period.min {
+          Some((period.min, period.max))
+        } else {
+          None
+        };
+        if let Some((leave, enter)) = edge {
+          let t = if step != 0.0 { (leave - prev) / step } else { 0.0 };
+          let xc = x[i - 1] + (x[i] - x[i - 1]) * t;
+          ox.extend([xc, xc, xc]);
+          oy.extend([leave, f64::NAN, enter]);
+        }
+      }
+    }
+    ox.push(x[i]);
+    oy.push(cur);
+  }
+  (ox, oy)
+}
+
+/// Tick positions and labels spanning `period`, at most `max_ticks`
+/// intervals: multiples of 30, 45, 90 or 180 degrees, or of π/6, π/4,
+/// π/2 or π.
+pub fn angle_ticks(period: Period, max_ticks: usize) -> (Vec<f64>, Vec<String>) {
+  let (unit, steps): (f64, [f64; 4]) = match period.unit {
+    AngleUnit::Degrees => (1.0, [30.0, 45.0, 90.0, 180.0]),
+    AngleUnit::Radians => (PI, [1.0 / 6.0, 0.25, 0.5, 1.0]),
+  };
+  let span = period.span() / unit;
+  let step = steps
+    .iter()
+    .copied()
+    .find(|s| span / s <= max_ticks.max(1) as f64 + 1e-9)
+    .unwrap_or(steps[3]);
+  let first = (period.min / unit / step - 1e-9).ceil() as i64;
+  let last = (period.max / unit / step + 1e-9).floor() as i64;
+  let mut ticks = Vec::new();
+  let mut labels = Vec::new();
+  for k in first..=last {
+    let v = k as f64 * step;
+    ticks.push(v * unit);
+    labels.push(match period.unit {
+      AngleUnit::Degrees => format!("{}\u{b0}", v.round() as i64),
+      AngleUnit::Radians => pi_fraction(v),
+    });
+  }
+  (ticks, labels)
+}
+
+/// `v`·π written as a reduced fraction with denominator up to 12
+/// (`0.5` -> `π/2`, `-0.75` -> `-3π/4`).
+pub fn pi_fraction(v: f64) -> String {
+  let den = (1..=12)
+    .find(|&d| (v * d as f64 - (v * d as f64).round()).abs() < 1e-9)
+    .unwrap_or(12) as i64;
+  let num = (v * den as f64).round() as i64;
+  let sign = if num < 0 { "-" } else { "" };
+  match (num.abs(), den) {
+    (0, _) => "0".to_string(),
+    (1, 1) => format!("{}\u{3c0}", sign),
+    (n, 1) => format!("{}{}\u{3c0}", sign, n),
+    (1, d) => format!("{}\u{3c0}/{}", sign, d),
+    (n, d) => format!("{}{}\u{3c0}/{}", sign, n, d),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn wraps_into_range() {
+    assert_eq!(Period::DEGREES.wrap(370.0), 10.0);
+    assert_eq!(Period::DEGREES.wrap(-10.0), 350.0);
+    assert_eq!(Period::SIGNED_DEGREES.wrap(180.0), -180.0);
+    assert!(Period::DEGREES.wrap(f64::NAN).is_nan());
+  }
+
+  #[test]
+  fn crossing_the_wrap_breaks_the_line() {
+    let (x, y) = split_wrapped(&[0.0, 1.0, 2.0], &[350.0, 10.0, 20.0], Period::DEGREES);
+    assert_eq!(x, vec![0.0, 0.5, 0.5, 0.5, 1.0, 2.0]);
+    assert_eq!(&y[..2], &[350.0, 360.0]);
+    assert!(y[2].is_nan());
+    assert_eq!(&y[3..], &[0.0, 10.0, 20.0]);
+    // Going down through zero.
+    let (_, y) = split_wrapped(&[0.0, 1.0], &[5.0, 355.0], Period::DEGREES);
+    assert_eq!((y[1], y[3]), (0.0, 360.0));
+    // No crossing, no break.
+    let (x, _) = split_wrapped(&[0.0, 1.0], &[100.0, 250.0], Period::DEGREES);
+    assert_eq!(x.len(), 2);
+  }
+
+  #[test]
+  fn tick_labels() {
+    let (ticks, labels) = angle_ticks(Period::DEGREES, 8);
+    assert_eq!(ticks, vec![0.0, 45.0, 90.0, 135.0, 180.0, 225.0, 270.0, 315.0, 360.0]);
+    assert_eq!(labels[2], "90\u{b0}");
+    let (ticks, labels) = angle_ticks(Period::SIGNED_RADIANS, 4);
+    assert_eq!(ticks.len(), 5);
+    assert_eq!(labels, vec!["-\u{3c0}", "-\u{3c0}/2", "0", "\u{3c0}/2", "\u{3c0}"]);
+    assert_eq!(pi_fraction(-0.75), "-3\u{3c0}/4");
+    assert_eq!(pi_fraction(2.0), "2\u{3c0}");
+  }
+}
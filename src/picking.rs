@@ -0,0 +1,287 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Hit testing for line series.
+//!
+//! [`pick_line`] finds the segment of a polyline closest to the cursor
+//! and returns the interpolated on-curve position, not just the nearest
+//! vertex, so tooltips on sparse lines report the value under the
+//! cursor.  Distances are measured in pixels through a [`Viewport`],
+//! and the hit tolerance grows with the drawn line width.
+//!
+//! For hover over many points, a [`PointIndex`] buckets the points of
+//! every series by pixel cell once, so each query only looks at the
+//! cells around the cursor.  [`PlotBackend::pick`](crate::plotting::PlotBackend::pick)
+//! keeps one for the current axes between renders.
+
+use crate::artist::Viewport;
+use crate::plotting::SeriesId;
+use glam::Vec2;
+use std::collections::HashMap;
+
+/// Default pick tolerance beyond the line's half width, in pixels.
+pub const DEFAULT_HIT_TOLERANCE: f32 = 5.0;
+
+/// A hit on a line series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineHit {
+  /// Index of the segment's first vertex.
+  pub segment: usize,
+  /// Position along the segment, 0 at its first vertex and 1 at the
+  /// next.
+  pub t: f64,
+  /// Interpolated X on the curve.
+  pub x: f64,
+  /// Interpolated Y on the curve.
+  pub y: f64,
+  /// Index of the vertex nearest to the hit.
+  pub nearest_vertex: usize,
+  /// Distance from the cursor to the curve, in pixels.
+  pub distance: f32,
+}
+
+/// Picks the point of the polyline `(x, y)` closest to `cursor` (in
+/// pixels).
+///
+/// A hit needs the cursor within `tolerance + line_width / 2` pixels of
+/// the line.  Non-finite points break the line; an isolated point is
+/// hit like a zero-length segment.
+pub fn pick_line(
+  x: &[f64],
+  y: &[f64],
+  viewport: &Viewport,
+  cursor: Vec2,
+  tolerance: f32,
+  line_width: f32,
+) -> Option<LineHit> {
+  let n = x.len().min(y.len());
+  let reach = tolerance + line_width * 0.5;
+  let finite = |i: usize| x[i].is_finite() && y[i].is_finite();
+  let mut best: Option<LineHit> = None;
+  for i in 0..n {
+    if !finite(i) {
+      continue;
+    }
+    // Segment i..i+1, or the lone point i if the line breaks here.
+    let j = if i + 1 < n && finite(i + 1) { i + 1 } else { i };
+    let isolated = j == i && (i == 0 || !finite(i - 1));
+    if j == i && !isolated {
+      continue;
+    }
+    let a = viewport.to_pixels(x[i], y[i]);
+    let b = viewport.to_pixels(x[j], y[j]);
+    let ab = b - a;
+    let len2 = ab.length_squared();
+    let t = if len2 > 0.0 {
+      ((cursor - a).dot(ab) / len2).clamp(0.0, 1.0)
+    } else {
+      0.0
+    };
+    let distance = cursor.distance(a + ab * t);
+    if distance > reach || best.is_some_and(|b| b.distance <= distance) {
+      continue;
+    }
+    let t = t as f64;
+    best = Some(LineHit {
+      segment: i,
+      t,
+      x: x[i] + (x[j] - x[i]) * t,
+      y: y[i] + (y[j] - y[i]) * t,
+      nearest_vertex: if t <= 0.5 { i } else { j },
+      distance,
+    });
+  }
+  best
+}
+
+/// Picks across several series; returns the index of the closest
+/// series and its hit.
+pub fn pick_lines(
+  series: &[(&[f64], &[f64])],
+  viewport: &Viewport,
+  cursor: Vec2,
+  tolerance: f32,
+  line_width: f32,
+) -> Option<(usize, LineHit)> {
+  series
+    .iter()
+    .enumerate()
+    .filter_map(|(k, (x, y))| {
+      pick_line(x, y, viewport, cursor, tolerance, line_width).map(|h| (k, h))
+    })
+    .min_by(|a, b| a.1.distance.total_cmp(&b.1.distance))
+}
+
+/// Side of a [`PointIndex`] cell, in pixels.
+pub const INDEX_CELL: f32 = 16.0;
+
+/// The series point nearest to a picked pixel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PickResult {
+  /// Series the point belongs to.
+  pub series_id: SeriesId,
+  /// Index of the point in the series.
+  pub index: usize,
+  /// The point, in data coordinates.
+  pub data_xy: (f64, f64),
+  /// Distance from the picked pixel, in pixels.
+  pub distance: f32,
+}
+
+/// Series points inside an axes box, bucketed by pixel cell.
+pub struct PointIndex {
+  viewport: Viewport,
+  points: Vec<(PickResult, Vec2)>,
+  cells: HashMap<(i32, i32), Vec<usize>>,
+}
+
+impl PointIndex {
+  /// Indexes the finite points of `series` that fall inside the axes
+  /// box of `viewport`.
+  pub fn new(series: &[(SeriesId, &[f64], &[f64])], viewport: Viewport) -> Self {
+    let mut index = Self {
+      viewport,
+      points: Vec::new(),
+      cells: HashMap::new(),
+    };
+    for &(series_id, x, y) in series {
+      for (i, (&px, &py)) in x.iter().zip(y).enumerate() {
+        if !(px.is_finite() && py.is_finite()) {
+          continue;
+        }
+        let p = viewport.to_pixels(px, py);
+        if !viewport.contains_pixel(p) {
+          continue;
+        }
+        let hit = PickResult { series_id, index: i, data_xy: (px, py), distance: 0.0 };
+        index.cells.entry(cell_of(p)).or_default().push(index.points.len());
+        index.points.push((hit, p));
+      }
+    }
+    index
+  }
+
+  /// The viewport the points were placed with.
+  pub fn viewport(&self) -> &Viewport {
+    &self.viewport
+  }
+
+  /// Number of indexed points.
+  pub fn len(&self) -> usize {
+    self.points.len()
+  }
+
+  /// Whether no points are indexed.
+  pub fn is_empty(&self) -> bool {
+    self.points.is_empty()
+  }
+
+  /// The point nearest to `at` within `radius` pixels.  Ties go to the
+  /// series drawn last, which is on top.
+  pub fn nearest(&self, at: Vec2, radius: f32) -> Option<PickResult> {
+    if radius.is_nan() || radius < 0.0 || !at.is_finite() {
+      return None;
+    }
+    // Every point is inside the axes box, so a huge or infinite radius
+    // only searches the cells of the box.
+    let [left, top, right, bottom] = self.viewport.pixels;
+    let (box_lo, box_hi) = (
+      cell_of(Vec2::new(left.min(right), top.min(bottom))),
+      cell_of(Vec2::new(left.max(right), top.max(bottom))),
+    );
+    let (lo, hi) = (cell_of(at - Vec2::splat(radius)), cell_of(at + Vec2::splat(radius)));
+    let (lo, hi) = ((lo.0.max(box_lo.0), lo.1.max(box_lo.1)), (hi.0.min(box_hi.0), hi.1.min(box_hi.1)));
+    let mut best: Option<(usize, f32)> = None;
+    for cx in lo.0..=hi.0 {
+      for cy in lo.1..=hi.1 {
+        for &k in self.cells.get(&(cx, cy)).into_iter().flatten() {
+          let d = at.distance(self.points[k].1);
+          if d <= radius && best.is_none_or(|(b, bd)| d < bd || (d == bd && k > b)) {
+            best = Some((k, d));
+          }
+        }
+      }
+    }
+    best.map(|(k, distance)| PickResult { distance, ..self.points[k].0 })
+  }
+}
+
+fn cell_of(p: Vec2) -> (i32, i32) {
+  ((p.x / INDEX_CELL).floor() as i32, (p.y / INDEX_CELL).floor() as i32)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::artist::DataRect;
+
+  /// 1 data unit = 10 pixels, origin at pixel (0, 100).
+  fn viewport() -> Viewport {
+    Viewport::new(
+      DataRect::new((0.0, 10.0), (0.0, 10.0)),
+      [0.0, 0.0, 100.0, 100.0],
+    )
+  }
+
+  #[test]
+  fn interpolates_on_sparse_segment() {
+    let (x, y) = ([0.0, 10.0], [0.0, 10.0]);
+    // Cursor 2px off the diagonal near data (3, 3).
+    let cursor = Vec2::new(30.0 + 1.414, 70.0 + 1.414);
+    let hit = pick_line(&x, &y, &viewport(), cursor, 5.0, 0.0).unwrap();
+    assert_eq!(hit.segment, 0);
+    assert!((hit.x - 3.0).abs() < 1e-3 && (hit.y - 3.0).abs() < 1e-3);
+    assert!((hit.t - 0.3).abs() < 1e-3);
+    assert_eq!(hit.nearest_vertex, 0);
+    assert!((hit.distance - 2.0).abs() < 1e-2);
+  }
+
+  #[test]
+  fn tolerance_grows_with_line_width() {
+    let (x, y) = ([0.0, 10.0], [5.0, 5.0]);
+    let cursor = Vec2::new(50.0, 42.0); // 8px above the line
+    assert!(pick_line(&x, &y, &viewport(), cursor, 5.0, 2.0).is_none());
+    let hit = pick_line(&x, &y, &viewport(), cursor, 5.0, 8.0).unwrap();
+    assert_eq!((hit.x, hit.y), (5.0, 5.0));
+  }
+
+  #[test]
+  fn gaps_break_the_line() {
+    let x = [0.0, 4.0, f64::NAN, 6.0, 10.0];
+    let y = [5.0, 5.0, f64::NAN, 5.0, 5.0];
+    // Over the gap between x = 4 and x = 6.
+    assert!(pick_line(&x, &y, &viewport(), Vec2::new(50.0, 50.0), 5.0, 0.0).is_none());
+    let hit = pick_line(&x, &y, &viewport(), Vec2::new(85.0, 50.0), 5.0, 0.0).unwrap();
+    assert_eq!(hit.segment, 3);
+    assert_eq!(hit.nearest_vertex, 4);
+  }
+
+  #[test]
+  fn closest_series_wins() {
+    let (x, y1, y2) = ([0.0, 10.0], [5.0, 5.0], [6.0, 6.0]);
+    let series: [(&[f64], &[f64]); 2] = [(&x, &y1), (&x, &y2)];
+    let (k, hit) =
+      pick_lines(&series, &viewport(), Vec2::new(20.0, 41.0), 5.0, 0.0).unwrap();
+    assert_eq!(k, 1);
+    assert_eq!(hit.y, 6.0);
+  }
+
+  #[test]
+  fn index_finds_the_nearest_point_within_the_radius() {
+    let (a, b) = (SeriesId(1), SeriesId(2));
+    let (x, y1, y2) = ([1.0, 2.0, 3.0, 20.0], [5.0, 5.0, 5.0, 5.0], [6.0, 5.0, f64::NAN, 6.0]);
+    let index = PointIndex::new(&[(a, &x, &y1), (b, &x, &y2)], viewport());
+    // The point outside the box and the NaN are left out.
+    assert_eq!(index.len(), 5);
+
+    let hit = index.nearest(Vec2::new(31.0, 48.0), 5.0).unwrap();
+    assert_eq!((hit.series_id, hit.index, hit.data_xy), (a, 2, (3.0, 5.0)));
+    assert!((hit.distance - 5f32.sqrt()).abs() < 1e-4);
+    // Coincident points: the later series wins.
+    assert_eq!(index.nearest(Vec2::new(20.0, 50.0), 1.0).unwrap().series_id, b);
+    assert_eq!(index.nearest(Vec2::new(60.0, 50.0), 5.0), None);
+    // An unbounded radius searches the box, not the whole plane.
+    assert_eq!(index.nearest(Vec2::new(60.0, 50.0), f32::INFINITY).unwrap().data_xy, (3.0, 5.0));
+    assert_eq!(index.nearest(Vec2::new(60.0, 50.0), f32::NAN), None);
+  }
+}
@@ -3,14 +3,53 @@
 
 //! High-level plotting API for mpl-wgpu
 
+use crate::adornments::{LabelAxis, NorthArrow, OffsetLabel, ScaleBar};
+use crate::annotations::{Annotation, AnnotationLayer};
+use crate::artist::{self, Artist, DataRect, DrawContext, RenderWarning, Viewport};
+use crate::autoscale::{self, VisibleYAutoscale};
+use crate::boxzoom::BoxZoom;
+use crate::cluster::ClusteredScatter;
+use crate::bounds::{self, FigureLayout};
+use crate::camera::Camera3D;
+use crate::colorbar::Colorbar;
+use crate::colormaps::{Colormap, Norm, Normalize};
+use crate::emoji;
+use crate::events::{Events, PlotEvent};
 use crate::ffi;
+use crate::layers::{Layer, LayerCompositor};
+use crate::layout::{self, LabelLayout};
+use crate::locale::Locale;
+use crate::mask;
+use crate::mathtext::{self, Metrics};
+use crate::periodic::{self, Period};
+use crate::picking::{PickResult, PointIndex};
 use crate::primitives::PrimitiveRenderer;
-use crate::text::TextRenderer;
+use crate::probe::{DataProbe, ProbeReading};
+use crate::recording::{RecordedPrimitive, Recording};
+use crate::refline::{LineStyle, RefLine};
+use crate::shapes::{Shape, ShapePatch, ShapeStyle};
+use crate::spec::PlotSpec;
+use crate::stream::Stream;
+use crate::temporal;
+use crate::ticks::{self, OffsetScale, TickAdjuster, TickAxis, TickFormatter};
+use crate::tooltip::Tooltip;
+use crate::trace::{trace_event, trace_span};
+use crate::underlay::ImageUnderlay;
+use crate::units::SizeScale;
+use crate::walls::{self, WallProjection};
+use crate::watch::{AxisLimits, CameraView, Watch};
+use crate::text::{self, TextMetrics, TextRenderer};
 use std::ffi::{CString, CStr};
 use std::os::raw::{c_void, c_char};
 use glam::{Mat4, Vec2, Vec3, Vec4};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::path::Path;
+use std::sync::mpsc::Receiver;
 
 // Re-exports
+pub use crate::reference::GnuplotFigure;
 pub use std::f64::consts::PI;
 
 pub fn linspace(start: f64, end: f64, n: usize) -> Vec<f64> {
@@ -31,27 +70,236 @@ pub fn randn(n: usize) -> Vec<f64> {
     v
 }
 
+/// Converts labels to C strings, returning the owned strings together
+/// with a pointer array that borrows from them.
+fn c_strings<S: AsRef<str>>(labels: &[S]) -> (Vec<CString>, Vec<*const c_char>) {
+    let owned: Vec<CString> = labels
+        .iter()
+        .map(|l| CString::new(l.as_ref()).unwrap_or_default())
+        .collect();
+    let ptrs = owned.iter().map(|c| c.as_ptr()).collect();
+    (owned, ptrs)
+}
+
 // ----------------------------------------------------------------------------
 // Axes
 // ----------------------------------------------------------------------------
 
-pub struct Axes {
+/// Opacity of the shaded band drawn by [`Axes::plot_with_band`].
+const BAND_ALPHA: f32 = 0.25;
+
+/// Entries in the lookup table uploaded by [`Axes::set_colormap`].
+const COLORMAP_LUT_SIZE: usize = 256;
+
+/// Size, color and weight of a text element (title, axis labels, tick
+/// labels).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FontStyle {
+    /// Font size in points.
+    pub size: f32,
+    /// RGB color.
+    pub color: [f32; 3],
+    /// Bold weight.
+    pub bold: bool,
+}
+
+impl Default for FontStyle {
+    fn default() -> Self {
+        Self { size: 11.0, color: [0.0, 0.0, 0.0], bold: false }
+    }
+}
+
+impl FontStyle {
+    /// Black regular text at `size`.
+    pub fn new(size: f32) -> Self {
+        Self { size, ..Self::default() }
+    }
+
+    /// Sets the color.
+    pub fn color(mut self, r: f32, g: f32, b: f32) -> Self {
+        self.color = [r, g, b];
+        self
+    }
+
+    /// Sets bold weight.
+    pub fn bold(mut self, bold: bool) -> Self {
+        self.bold = bold;
+        self
+    }
+}
+
+pub struct Axes<'a> {
     ptr: *mut ffi::MplAxes,
+    /// The figure's locale, if one was set.
+    locale: Option<&'a Locale>,
+    /// Tick labels the backend re-places on every render.
+    tick_labels: Option<&'a RefCell<Vec<AxesTicks>>>,
+    owner: PhantomData<&'a ()>,
 }
 
-impl Axes {
-    pub fn plot(&self, x: &[f64], y: &[f64], style: &str) {
+impl<'a> Axes<'a> {
+    /// Plots a line and returns its [`Series`] for styling.
+    ///
+    /// `x` and `y` are only borrowed: matplot++ takes its single copy,
+    /// and the caller keeps its data, e.g. an `Arc<[f64]>` also handed
+    /// to [`DataProbe::add_series_shared`](crate::probe::DataProbe::add_series_shared).
+    pub fn plot(&self, x: &[f64], y: &[f64], style: &str) -> Series<'a> {
         let c_style = CString::new(style).unwrap_or_default();
-        unsafe {
-            ffi::mpl_axes_plot(self.ptr, x.as_ptr(), y.as_ptr(), x.len().min(y.len()), c_style.as_ptr());
+        Series {
+            ptr: unsafe { ffi::mpl_axes_plot(self.ptr, x.as_ptr(), y.as_ptr(), x.len().min(y.len()), c_style.as_ptr()) },
+            window: None,
+            owner: PhantomData,
         }
     }
 
-    pub fn scatter(&self, x: &[f64], y: &[f64], style: &str) {
+    /// Plots markers and returns their [`Series`] for styling.
+    pub fn scatter(&self, x: &[f64], y: &[f64], style: &str) -> Series<'a> {
         let c_style = CString::new(style).unwrap_or_default();
-        unsafe {
-            ffi::mpl_axes_scatter(self.ptr, x.as_ptr(), y.as_ptr(), x.len().min(y.len()), c_style.as_ptr());
+        Series {
+            ptr: unsafe { ffi::mpl_axes_scatter(self.ptr, x.as_ptr(), y.as_ptr(), x.len().min(y.len()), c_style.as_ptr()) },
+            window: None,
+            owner: PhantomData,
+        }
+    }
+
+    /// Plots a line through 3D points and returns its [`Series`].
+    pub fn plot3(&self, x: &[f64], y: &[f64], z: &[f64], style: &str) -> Series<'a> {
+        let n = x.len().min(y.len()).min(z.len());
+        let c_style = CString::new(style).unwrap_or_default();
+        Series {
+            ptr: unsafe { ffi::mpl_axes_plot3(self.ptr, x.as_ptr(), y.as_ptr(), z.as_ptr(), n, c_style.as_ptr()) },
+            window: None,
+            owner: PhantomData,
+        }
+    }
+
+    /// Plots markers at 3D points and returns their [`Series`].
+    pub fn scatter3(&self, x: &[f64], y: &[f64], z: &[f64], style: &str) -> Series<'a> {
+        let n = x.len().min(y.len()).min(z.len());
+        let c_style = CString::new(style).unwrap_or_default();
+        Series {
+            ptr: unsafe { ffi::mpl_axes_scatter3(self.ptr, x.as_ptr(), y.as_ptr(), z.as_ptr(), n, c_style.as_ptr()) },
+            window: None,
+            owner: PhantomData,
+        }
+    }
+
+    /// Draws the shadows of the 3D series `(x, y, z)` on the walls of
+    /// the box chosen by `projection`, in `style` (see [`walls`]).
+    ///
+    /// Call after plotting the data: the walls are placed at the
+    /// current limits, which are then fixed so the box stays where the
+    /// shadows are.
+    pub fn project_onto_walls(&self, x: &[f64], y: &[f64], z: &[f64], style: &str, projection: &WallProjection) -> Vec<Series<'a>> {
+        let (x_lim, y_lim, z_lim) = (self.xlim(), self.ylim(), self.zlim());
+        self.set_xlim(x_lim.0, x_lim.1);
+        self.set_ylim(y_lim.0, y_lim.1);
+        self.set_zlim(z_lim.0, z_lim.1);
+        let [r, g, b] = projection.color;
+        self.holding(|| {
+            projection
+                .walls
+                .iter()
+                .map(|&wall| {
+                    let [px, py, pz] = walls::project(wall, x, y, z, [x_lim, y_lim, z_lim]);
+                    self.plot3(&px, &py, &pz, style).color(r, g, b).width(projection.width)
+                })
+                .collect()
+        })
+    }
+
+    /// Replaces the data of series `id`.  Returns `false` if it is not
+    /// in these axes.
+    pub fn update_series(&self, id: SeriesId, x: &[f64], y: &[f64]) -> bool {
+        unsafe { ffi::mpl_axes_update_series(self.ptr, id.0, x.as_ptr(), y.as_ptr(), x.len().min(y.len())) }
+    }
+
+    /// Appends points to series `id`, keeping only the newest `window`
+    /// points if given.  Returns `false` if it is not in these axes.
+    pub fn append_series(&self, id: SeriesId, x: &[f64], y: &[f64], window: Option<usize>) -> bool {
+        let n = x.len().min(y.len());
+        unsafe { ffi::mpl_axes_append_series(self.ptr, id.0, x.as_ptr(), y.as_ptr(), n, window.unwrap_or(0)) }
+    }
+
+    /// Removes series `id` from these axes.  Returns `false` if it is
+    /// not in them.
+    pub fn remove_series(&self, id: SeriesId) -> bool {
+        unsafe { ffi::mpl_axes_remove_series(self.ptr, id.0) }
+    }
+
+    /// Shows or hides series `id` without dropping its data.  Returns
+    /// `false` if it is not in these axes.
+    pub fn set_visible(&self, id: SeriesId, visible: bool) -> bool {
+        unsafe { ffi::mpl_axes_set_series_visible(self.ptr, id.0, visible) }
+    }
+
+    /// Whether series `id` is shown, or `None` if it is not in these
+    /// axes.
+    pub fn is_visible(&self, id: SeriesId) -> Option<bool> {
+        let mut visible = false;
+        unsafe { ffi::mpl_axes_series_visible(self.ptr, id.0, &mut visible) }.then_some(visible)
+    }
+
+    /// The series of this axes in group `name`, for styling or toggling
+    /// them together:
+    ///
+    /// ```rust,no_run
+    /// # fn demo(ax: &mpl_wgpu::plotting::Axes) {
+    /// ax.group("sensors").set_alpha(0.3).set_visible(false);
+    /// # }
+    /// ```
+    ///
+    /// Series join a group with [`Series::group`] or
+    /// [`SeriesGroup::add`].
+    pub fn group(&self, name: &str) -> SeriesGroup<'_> {
+        SeriesGroup { ax: self, name: CString::new(name).unwrap_or_default() }
+    }
+
+    /// Scatter plot coloring each marker by its value in `c` through
+    /// `map`.
+    ///
+    /// `norm` maps values onto the colormap (for example a
+    /// [`LogNorm`](crate::colormaps::LogNorm)); `None` spans the finite
+    /// values of `c` linearly.  Points the norm maps to NaN are skipped.
+    /// The axes colormap is set to `map` so a colorbar matches the
+    /// markers.
+    ///
+    /// Returns the ids of the marker series, one per color in use.
+    pub fn scatter_mapped(&self, x: &[f64], y: &[f64], c: &[f64], style: &str, map: &dyn Colormap, norm: Option<&dyn Norm>) -> Vec<SeriesId> {
+        let n = x.len().min(y.len()).min(c.len());
+        let auto = Normalize::autoscale(&c[..n]);
+        let Some(norm) = norm.or(auto.as_ref().map(|a| a as &dyn Norm)) else { return Vec::new() };
+        // One marker series per lookup table entry in use.
+        let lut = map.lut(COLORMAP_LUT_SIZE);
+        let mut groups = vec![(Vec::new(), Vec::new()); COLORMAP_LUT_SIZE];
+        for i in 0..n {
+            let t = norm.apply(c[i]);
+            if t.is_nan() {
+                continue;
+            }
+            // Norms may map values outside their range past [0, 1].
+            let k = (t.clamp(0.0, 1.0) * (COLORMAP_LUT_SIZE - 1) as f64).round() as usize;
+            groups[k].0.push(x[i]);
+            groups[k].1.push(y[i]);
         }
+        let c_style = CString::new(style).unwrap_or_default();
+        let ids = self.holding(|| {
+            groups
+                .iter()
+                .zip(lut)
+                .filter(|((gx, _), _)| !gx.is_empty())
+                .map(|((gx, gy), [r, g, b])| {
+                    let series = Series {
+                        ptr: unsafe { ffi::mpl_axes_scatter_rgb(self.ptr, gx.as_ptr(), gy.as_ptr(), gx.len(), c_style.as_ptr(), r, g, b) },
+                        window: None,
+                        owner: PhantomData,
+                    };
+                    series.id()
+                })
+                .collect()
+        });
+        self.set_colormap(map);
+        ids
     }
     
     pub fn bar(&self, values: &[f64]) {
@@ -70,6 +318,13 @@ impl Axes {
         }
     }
 
+    /// Surface plot with holes where `mask` is `true` (see
+    /// [`mask::apply_mask`]), drawn as a wireframe if `wireframe` is
+    /// set, like [`surf`](Self::surf).
+    pub fn surf_masked(&self, x: &[f64], y: &[f64], z: &[f64], rows: usize, cols: usize, wireframe: bool, mask: &[bool]) {
+        self.surf(x, y, &mask::apply_mask(z, mask), rows, cols, wireframe);
+    }
+
     pub fn pie(&self, values: &[f64]) {
         unsafe { ffi::mpl_axes_pie(self.ptr, values.as_ptr(), values.len()); }
     }
@@ -82,6 +337,46 @@ impl Axes {
         unsafe { ffi::mpl_axes_heatmap(self.ptr, z.as_ptr(), rows, cols); }
     }
 
+    /// Heatmap with transparent cells where `mask` is `true` (see
+    /// [`mask::apply_mask`]).
+    pub fn heatmap_masked(&self, z: &[f64], rows: usize, cols: usize, mask: &[bool]) {
+        self.heatmap(&mask::apply_mask(z, mask), rows, cols);
+    }
+
+    /// Colormap for surfaces, heatmaps, and other scalar-mapped plots,
+    /// uploaded as a 256-entry lookup table.
+    pub fn set_colormap(&self, map: &dyn Colormap) {
+        let lut: Vec<f32> = map.lut(COLORMAP_LUT_SIZE).into_iter().flatten().collect();
+        unsafe { ffi::mpl_axes_set_colormap(self.ptr, lut.as_ptr(), COLORMAP_LUT_SIZE); }
+    }
+
+    /// Line plot with a shaded band between `y_low` and `y_high`.
+    ///
+    /// The band is filled as a single polygon in the line's color at
+    /// reduced opacity and renders behind the line, e.g. for confidence
+    /// intervals.  All slices are truncated to the shortest length.
+    /// Returns the [`Series`] of the line.
+    pub fn plot_with_band(&self, x: &[f64], y: &[f64], y_low: &[f64], y_high: &[f64], style: &str) -> Series<'a> {
+        let n = x.len().min(y.len()).min(y_low.len()).min(y_high.len());
+        let c_style = CString::new(style).unwrap_or_default();
+        Series {
+            ptr: unsafe {
+                ffi::mpl_axes_plot_with_band(
+                    self.ptr,
+                    x.as_ptr(),
+                    y.as_ptr(),
+                    y_low.as_ptr(),
+                    y_high.as_ptr(),
+                    n,
+                    c_style.as_ptr(),
+                    BAND_ALPHA,
+                )
+            },
+            window: None,
+            owner: PhantomData,
+        }
+    }
+
     pub fn set_title(&self, text: &str) {
         let c_text = CString::new(text).unwrap_or_default();
         unsafe { ffi::mpl_axes_set_title(self.ptr, c_text.as_ptr()); }
@@ -96,11 +391,148 @@ impl Axes {
         let c_text = CString::new(text).unwrap_or_default();
         unsafe { ffi::mpl_axes_set_ylabel(self.ptr, c_text.as_ptr()); }
     }
+
+    /// Sets the fill of the axes box, `[r, g, b, a]`.
+    pub fn set_background(&self, color: [f32; 4]) {
+        let [r, g, b, a] = color;
+        unsafe { ffi::mpl_axes_set_background(self.ptr, r, g, b, a); }
+    }
+
+    /// Styles the title.
+    pub fn set_title_style(&self, style: &FontStyle) {
+        let [r, g, b] = style.color;
+        unsafe { ffi::mpl_axes_set_title_style(self.ptr, style.size, r, g, b, style.bold); }
+    }
+
+    /// Styles the X axis label.
+    pub fn set_xlabel_style(&self, style: &FontStyle) {
+        self.set_label_style(b'x', style);
+    }
+
+    /// Styles the Y axis label.
+    pub fn set_ylabel_style(&self, style: &FontStyle) {
+        self.set_label_style(b'y', style);
+    }
+
+    fn set_label_style(&self, axis: u8, style: &FontStyle) {
+        let [r, g, b] = style.color;
+        unsafe { ffi::mpl_axes_set_label_style(self.ptr, axis as c_char, style.size, r, g, b, style.bold); }
+    }
+
+    /// Styles the tick labels of both axes.  The color also applies to
+    /// the axis lines and ticks; the title and axis labels keep their
+    /// size and weight.
+    pub fn set_tick_label_style(&self, style: &FontStyle) {
+        let [r, g, b] = style.color;
+        unsafe { ffi::mpl_axes_set_tick_label_style(self.ptr, style.size, r, g, b, style.bold); }
+    }
     
     pub fn grid(&self, on: bool) {
         unsafe { ffi::mpl_axes_grid(self.ptr, on); }
     }
+
+    /// Shows or hides a legend of the series labels (see
+    /// [`Series::label`]).
+    pub fn legend(&self, on: bool) {
+        unsafe { ffi::mpl_axes_legend(self.ptr, on); }
+    }
     
+    /// Seaborn-style despine: hides the top and right spines and draws
+    /// the left and bottom spines, with their ticks and labels, `offset`
+    /// pixels outward from the data area.  An offset of 0 only hides the
+    /// top and right spines.
+    pub fn despine(&self, offset: f32) {
+        unsafe { ffi::mpl_axes_despine(self.ptr, offset); }
+    }
+
+    /// Hides the ticks, tick labels, axis labels, spines and grid,
+    /// leaving only the data (matplotlib's `axis("off")`).  Combine
+    /// with [`set_position`](Self::set_position) to drop the margins
+    /// for sparkline- or image-like output.
+    pub fn axis_off(&self) {
+        unsafe { ffi::mpl_axes_set_axis_visible(self.ptr, false); }
+    }
+
+    /// Shows the axis decorations hidden by [`axis_off`](Self::axis_off).
+    /// The grid stays off until re-enabled with [`grid`](Self::grid).
+    pub fn axis_on(&self) {
+        unsafe { ffi::mpl_axes_set_axis_visible(self.ptr, true); }
+    }
+
+    /// Places the axes box at `[left, bottom, width, height]` in
+    /// fractions of the figure; `[0.0, 0.0, 1.0, 1.0]` fills the figure
+    /// without margins.
+    pub fn set_position(&self, position: [f32; 4]) {
+        let [x, y, w, h] = position;
+        unsafe { ffi::mpl_axes_set_position(self.ptr, x, y, w, h); }
+    }
+
+    /// Moves the tick labels and axis labels `padding` pixels further
+    /// out from the left and bottom of the axes box, for long Y tick
+    /// labels crowding the spine.  Negative values pull them closer.
+    pub fn set_tick_padding(&self, padding: f32) {
+        unsafe { ffi::mpl_axes_set_tick_padding(self.ptr, padding); }
+    }
+
+    /// Adds another Y axis sharing this axes' box, drawn on the right
+    /// `offset` pixels outside the data area in `color` (RGB).
+    ///
+    /// Plot the series that belong to the new axis on the returned
+    /// [`Axes`]; its ticks and labels use `color`, and its x range
+    /// follows this axes' at every draw.  Stack several axes with
+    /// increasing offsets.  These axes stay the figure's current axes.
+    /// Returns `None` for axes outside a figure.
+    pub fn add_y_axis(&self, offset: f32, color: [f32; 3]) -> Option<Axes<'a>> {
+        let ptr = unsafe { ffi::mpl_axes_add_y_axis(self.ptr, offset, color[0], color[1], color[2]) };
+        if ptr.is_null() {
+            return None;
+        }
+        Some(Axes { ptr, locale: self.locale, tick_labels: self.tick_labels, owner: PhantomData })
+    }
+
+    /// Text at data coordinates `(x, y)` in `color` (RGB).
+    pub fn text(&self, x: f64, y: f64, text: &str, color: [f32; 3]) {
+        let c_text = CString::new(text).unwrap_or_default();
+        unsafe { ffi::mpl_axes_text(self.ptr, x, y, c_text.as_ptr(), color[0], color[1], color[2]); }
+    }
+
+    /// Arrow from `from` to `to`, in data coordinates.
+    pub fn arrow(&self, from: (f64, f64), to: (f64, f64), color: [f32; 3]) {
+        unsafe { ffi::mpl_axes_arrow(self.ptr, from.0, from.1, to.0, to.1, color[0], color[1], color[2]); }
+    }
+
+    /// Straight line segment from `from` to `to`, in data coordinates.
+    pub fn line(&self, from: (f64, f64), to: (f64, f64), color: [f32; 3]) {
+        unsafe { ffi::mpl_axes_line(self.ptr, from.0, from.1, to.0, to.1, color[0], color[1], color[2]); }
+    }
+
+    /// Draws every annotation of `layer` on top of the current plot.
+    ///
+    /// Threshold lines span the current axis limits, so apply the layer
+    /// after the data has been plotted.
+    pub fn apply_annotations(&self, layer: &AnnotationLayer) {
+        for a in &layer.items {
+            match a {
+                Annotation::Note { x, y, text, color } => self.text(*x, *y, text, *color),
+                Annotation::Arrow { from, to, color } => self.arrow(*from, *to, *color),
+                Annotation::HLine { y, label, color } => {
+                    let (x0, x1) = self.xlim();
+                    self.line((x0, *y), (x1, *y), *color);
+                    if !label.is_empty() {
+                        self.text(x1, *y, label, *color);
+                    }
+                }
+                Annotation::VLine { x, label, color } => {
+                    let (y0, y1) = self.ylim();
+                    self.line((*x, y0), (*x, y1), *color);
+                    if !label.is_empty() {
+                        self.text(*x, y1, label, *color);
+                    }
+                }
+            }
+        }
+    }
+
     pub fn set_xlim(&self, min: f64, max: f64) {
         unsafe { ffi::mpl_axes_set_xlim(self.ptr, min, max); }
     }
@@ -108,265 +540,2142 @@ impl Axes {
     pub fn set_ylim(&self, min: f64, max: f64) {
         unsafe { ffi::mpl_axes_set_ylim(self.ptr, min, max); }
     }
-}
 
-impl Drop for Axes {
-    fn drop(&mut self) {
-        unsafe { ffi::mpl_axes_destroy(self.ptr); }
+    /// Lets matplot++ fit the X and Y limits to the data again after
+    /// they were set.
+    pub fn autoscale(&self) {
+        unsafe { ffi::mpl_axes_autoscale(self.ptr); }
     }
-}
 
-// ----------------------------------------------------------------------------
-// Figure
-// ----------------------------------------------------------------------------
+    /// Whether matplot++ fits the X and Y limits to the data, i.e. they
+    /// were not set since the last [`autoscale`](Self::autoscale).
+    pub(crate) fn limits_auto(&self) -> (bool, bool) {
+        let (mut x, mut y) = (false, false);
+        unsafe { ffi::mpl_axes_get_limits_auto(self.ptr, &mut x, &mut y); }
+        (x, y)
+    }
 
-pub struct Figure {
-    ptr: *mut ffi::MplFigure,
-}
+    /// Sets whether matplot++ fits the X and Y limits to the data,
+    /// keeping the current limits where it does not.
+    pub(crate) fn set_limits_auto(&self, x: bool, y: bool) {
+        unsafe { ffi::mpl_axes_set_limits_auto(self.ptr, x, y); }
+    }
 
-impl Figure {
-    pub fn current_axes(&self) -> Axes {
-        let ptr = unsafe { ffi::mpl_figure_current_axes(self.ptr) };
-        Axes { ptr }
+    /// Bounds of the visible plots, which autoscaling fits the limits
+    /// to; `None` without finite data.
+    pub(crate) fn data_extent(&self) -> Option<DataRect> {
+        let (mut x0, mut x1, mut y0, mut y1) = (0.0, 0.0, 0.0, 0.0);
+        let found = unsafe { ffi::mpl_axes_get_data_extent(self.ptr, &mut x0, &mut x1, &mut y0, &mut y1) };
+        found.then(|| DataRect::new((x0, x1), (y0, y1)))
     }
-    
-    pub fn clear(&self) {
-        unsafe { ffi::mpl_figure_clear(self.ptr); }
+
+    /// Current X axis limits.
+    pub fn xlim(&self) -> (f64, f64) {
+        let (mut min, mut max) = (0.0, 0.0);
+        unsafe { ffi::mpl_axes_get_xlim(self.ptr, &mut min, &mut max); }
+        (min, max)
     }
-}
 
-// ----------------------------------------------------------------------------
-// GnuplotFigure
-// ----------------------------------------------------------------------------
+    /// Current Y axis limits.
+    pub fn ylim(&self) -> (f64, f64) {
+        let (mut min, mut max) = (0.0, 0.0);
+        unsafe { ffi::mpl_axes_get_ylim(self.ptr, &mut min, &mut max); }
+        (min, max)
+    }
 
-/// Owning wrapper for a gnuplot-backed matplot++ figure.
-///
-/// Uses the default gnuplot backend (no wgpu renderer). Call
-/// [`save`] to render the plot to a PNG via gnuplot.
-pub struct GnuplotFigure {
-  ptr: *mut ffi::MplFigure,
-}
+    /// Sets the Z axis limits of 3D axes.
+    pub fn set_zlim(&self, min: f64, max: f64) {
+        unsafe { ffi::mpl_axes_set_zlim(self.ptr, min, max); }
+    }
 
-impl GnuplotFigure {
-  /// Creates a new gnuplot-backed figure.
-  pub fn new() -> Self {
-    let ptr = unsafe { ffi::mpl_figure_create_gnuplot() };
-    assert!(!ptr.is_null(), "Failed to create gnuplot figure");
-    Self { ptr }
-  }
+    /// Current Z axis limits.
+    pub fn zlim(&self) -> (f64, f64) {
+        let (mut min, mut max) = (0.0, 0.0);
+        unsafe { ffi::mpl_axes_get_zlim(self.ptr, &mut min, &mut max); }
+        (min, max)
+    }
 
-  /// Returns a non-owning [`Figure`] handle for plot setup.
-  pub fn figure(&self) -> Figure {
-    Figure { ptr: self.ptr }
-  }
+    /// Current X, Y and Z limits.
+    pub fn limits(&self) -> AxisLimits {
+        AxisLimits { x: self.xlim(), y: self.ylim(), z: self.zlim() }
+    }
 
-  /// Saves the figure to a file via gnuplot.
-  ///
-  /// The output format is inferred from the file extension
-  /// (e.g. `.png`, `.svg`).
-  pub fn save(&self, path: &str) -> bool {
-    let c_path = CString::new(path).unwrap_or_default();
-    unsafe { ffi::mpl_figure_save(self.ptr, c_path.as_ptr()) }
-  }
-}
+    /// Points the camera of 3D axes `azimuth` degrees about the Z axis
+    /// and `elevation` degrees above the XY plane.
+    pub fn set_view(&self, azimuth: f32, elevation: f32) {
+        unsafe { ffi::mpl_axes_set_view(self.ptr, azimuth, elevation); }
+    }
 
-impl Drop for GnuplotFigure {
-  fn drop(&mut self) {
-    unsafe { ffi::mpl_figure_destroy(self.ptr); }
-  }
-}
+    /// Current camera of 3D axes.
+    pub fn view(&self) -> CameraView {
+        let (mut azimuth, mut elevation) = (0.0, 0.0);
+        unsafe { ffi::mpl_axes_get_view(self.ptr, &mut azimuth, &mut elevation); }
+        CameraView { azimuth, elevation }
+    }
 
-// ----------------------------------------------------------------------------
-// PlotBackend
-// ----------------------------------------------------------------------------
+    /// Whether these are 3D axes, e.g. after [`plot3`](Self::plot3).
+    pub fn is_3d(&self) -> bool {
+        unsafe { ffi::mpl_axes_is_3d(self.ptr) }
+    }
 
-struct BackendContext {
-    prim: *mut PrimitiveRenderer,
-    text: *mut TextRenderer,
-    transform: Mat4,
-}
+    /// Identity of the axes, shared by every handle to them.
+    pub(crate) fn key(&self) -> usize {
+        unsafe { ffi::mpl_axes_key(self.ptr) }
+    }
 
-extern "C" fn draw_rects_cb(user_data: *mut c_void, rects: *const ffi::MplWgpuRect, count: usize) {
-    let ctx = unsafe { &mut *(user_data as *mut BackendContext) };
-    if ctx.prim.is_null() { return; }
-    let prim = unsafe { &mut *ctx.prim };
-    let rects_slice = unsafe { std::slice::from_raw_parts(rects, count) };
-    for r in rects_slice {
-        let pos = ctx.transform.transform_point3(Vec3::new(r.x, r.y, 0.0));
-        prim.draw_rect(
-            Vec2::new(pos.x, pos.y),
-            Vec2::new(r.width, r.height),
-            Vec4::new(r.r, r.g, r.b, r.a),
-            r.corner_radius,
-            r.stroke_width
-        );
+    /// Keeps the plots already on the axes when adding more, like
+    /// matplotlib's `hold`.  Off by default: each plot replaces the
+    /// previous ones.
+    pub fn hold(&self, on: bool) {
+        unsafe { ffi::mpl_axes_hold(self.ptr, on); }
     }
-}
 
-extern "C" fn draw_lines_cb(user_data: *mut c_void, lines: *const ffi::MplWgpuLine, count: usize) {
-    let ctx = unsafe { &mut *(user_data as *mut BackendContext) };
-    if ctx.prim.is_null() { return; }
-    let prim = unsafe { &mut *ctx.prim };
-    let lines_slice = unsafe { std::slice::from_raw_parts(lines, count) };
-    for l in lines_slice {
-        let p1 = ctx.transform.transform_point3(Vec3::new(l.x1, l.y1, l.z1));
-        let p2 = ctx.transform.transform_point3(Vec3::new(l.x2, l.y2, l.z2));
-        prim.draw_line(
-            p1,
-            p2,
-            l.width,
-            Vec4::new(l.r, l.g, l.b, l.a),
-            l.dash_len,
-            l.gap_len,
-            l.dash_offset
-        );
+    /// Runs `f` with [`hold`](Self::hold) on, then restores it.
+    pub(crate) fn holding<R>(&self, f: impl FnOnce() -> R) -> R {
+        let held = unsafe { ffi::mpl_axes_hold(self.ptr, true) };
+        let result = f();
+        unsafe { ffi::mpl_axes_hold(self.ptr, held); }
+        result
     }
-}
 
-extern "C" fn draw_circles_cb(user_data: *mut c_void, circles: *const ffi::MplWgpuCircle, count: usize) {
-    let ctx = unsafe { &mut *(user_data as *mut BackendContext) };
-    if ctx.prim.is_null() { return; }
-    let prim = unsafe { &mut *ctx.prim };
-    let slice = unsafe { std::slice::from_raw_parts(circles, count) };
-    for c in slice {
-        let center = ctx.transform.transform_point3(Vec3::new(c.cx, c.cy, c.cz));
-        prim.draw_circle(
-            center,
-            c.radius,
-            Vec4::new(c.r, c.g, c.b, c.a),
-            0.0, c.type_ as u32
-        );
+    /// Fixes the values mapped to the ends of the colormap for every
+    /// surface, heatmap and image of the axes, matplotlib's `clim`.
+    /// Without it each plot is colored over its own range, so two
+    /// surfaces are not comparable; plot both with [`hold`](Self::hold)
+    /// on, pass the range covering both, and
+    /// [`Normalize::new(vmin, vmax)`](Normalize::new) to
+    /// [`PlotBackend::colorbar`] to match.
+    pub fn set_clim(&self, vmin: f64, vmax: f64) {
+        unsafe { ffi::mpl_axes_set_clim(self.ptr, vmin, vmax); }
     }
-}
 
-extern "C" fn draw_triangles_cb(user_data: *mut c_void, tris: *const ffi::MplWgpuTriangle, count: usize) {
-    let ctx = unsafe { &mut *(user_data as *mut BackendContext) };
-    if ctx.prim.is_null() { return; }
-    let prim = unsafe { &mut *ctx.prim };
-    let slice = unsafe { std::slice::from_raw_parts(tris, count) };
-    for t in slice {
-        let p1 = ctx.transform.transform_point3(Vec3::new(t.x1, t.y1, t.z1));
-        let p2 = ctx.transform.transform_point3(Vec3::new(t.x2, t.y2, t.z2));
-        let p3 = ctx.transform.transform_point3(Vec3::new(t.x3, t.y3, t.z3));
-        prim.draw_triangle(
-            p1, p2, p3,
-            Vec4::new(t.r, t.g, t.b, t.a)
-        );
-    }
-}
-
-extern "C" fn draw_text_cb(user_data: *mut c_void, text: *const c_char, x: f32, y: f32, size: f32, r: f32, g: f32, b: f32, a: f32, _rot: f32) {
-    let ctx = unsafe { &mut *(user_data as *mut BackendContext) };
-    if ctx.text.is_null() { return; }
-    let text_renderer = unsafe { &mut *ctx.text };
-    if text.is_null() { return; }
-    let c_str = unsafe { CStr::from_ptr(text) };
-    
-    let pos = ctx.transform.transform_point3(Vec3::new(x, y, 0.0));
-    
-    if let Ok(s) = c_str.to_str() {
-       text_renderer.draw_text(s, Vec2::new(pos.x, pos.y), size, Vec4::new(r, g, b, a));
+    /// Current color limits.
+    pub fn clim(&self) -> (f64, f64) {
+        let (mut min, mut max) = (0.0, 0.0);
+        unsafe { ffi::mpl_axes_get_clim(self.ptr, &mut min, &mut max); }
+        (min, max)
     }
-}
 
-extern "C" fn measure_text_cb(user_data: *mut c_void, text: *const c_char, size: f32) -> f32 {
-    if text.is_null() { return 0.0; }
-    let c_str = unsafe { CStr::from_ptr(text) };
-    let ctx = unsafe { &mut *(user_data as *mut BackendContext) };
-    if !ctx.text.is_null() {
-        let text_renderer = unsafe { &mut *ctx.text };
-        if let Ok(s) = c_str.to_str() {
-            return text_renderer.measure_text(s, size).x;
+    /// Ids of the visible series, in drawing order.
+    pub fn series_ids(&self) -> Vec<SeriesId> {
+        let n = unsafe { ffi::mpl_axes_series_ids(self.ptr, std::ptr::null_mut(), 0) };
+        let mut ids = vec![0u64; n];
+        let n = unsafe { ffi::mpl_axes_series_ids(self.ptr, ids.as_mut_ptr(), n) }.min(ids.len());
+        ids.truncate(n);
+        ids.into_iter().map(SeriesId).collect()
+    }
+
+    /// X and Y values of series `id`, empty if it is not in these axes.
+    pub fn series_points(&self, id: SeriesId) -> (Vec<f64>, Vec<f64>) {
+        let n = unsafe { ffi::mpl_axes_series_points(self.ptr, id.0, std::ptr::null_mut(), std::ptr::null_mut(), 0) };
+        let (mut x, mut y) = (vec![0.0; n], vec![0.0; n]);
+        let n = unsafe { ffi::mpl_axes_series_points(self.ptr, id.0, x.as_mut_ptr(), y.as_mut_ptr(), n) }.min(x.len());
+        x.truncate(n);
+        y.truncate(n);
+        (x, y)
+    }
+
+    /// X and Y values of all visible series, concatenated.
+    pub fn series_data(&self) -> (Vec<f64>, Vec<f64>) {
+        let n = unsafe { ffi::mpl_axes_series_data(self.ptr, std::ptr::null_mut(), std::ptr::null_mut(), 0) };
+        let (mut x, mut y) = (vec![0.0; n], vec![0.0; n]);
+        let n = unsafe { ffi::mpl_axes_series_data(self.ptr, x.as_mut_ptr(), y.as_mut_ptr(), n) }.min(x.len());
+        x.truncate(n);
+        y.truncate(n);
+        (x, y)
+    }
+
+    /// Sets the Y limits from the `lower_pct` to the `upper_pct`
+    /// percentile of the visible series (e.g. 1 and 99) instead of
+    /// their extremes, padded by [`autoscale::DEFAULT_MARGIN`], so a few
+    /// outliers do not flatten the interesting part of the plot.
+    /// Returns the points left outside; [`mark_outliers`](Self::mark_outliers)
+    /// draws them as edge arrows.  Without finite data the limits are
+    /// unchanged.
+    pub fn auto_scale_robust(&self, lower_pct: f64, upper_pct: f64) -> autoscale::Outliers {
+        let (x, y) = self.series_data();
+        let Some(range) = autoscale::robust_range(&y, lower_pct, upper_pct) else {
+            return autoscale::Outliers::default();
+        };
+        let (lo, hi) = autoscale::pad_range(range, autoscale::DEFAULT_MARGIN);
+        self.set_ylim(lo, hi);
+        autoscale::Outliers::find(&x, &y, (lo, hi))
+    }
+
+    /// Draws an arrow at the bottom or top edge for each outlier, at its
+    /// X position and pointing towards it.  Outliers closer than 1% of
+    /// the X range share an arrow.
+    pub fn mark_outliers(&self, outliers: &autoscale::Outliers, color: [f32; 3]) {
+        let (x0, x1) = self.xlim();
+        let (y0, y1) = self.ylim();
+        let (gap, len) = ((x1 - x0).abs() * 0.01, (y1 - y0) * 0.06);
+        for (xs, tip, tail) in [(&outliers.below, y0, y0 + len), (&outliers.above, y1, y1 - len)] {
+            let mut last = f64::NEG_INFINITY;
+            for &x in xs.iter().filter(|&&x| x >= x0.min(x1) && x <= x0.max(x1)) {
+                if x - last >= gap {
+                    self.arrow((x, tail), (x, tip), color);
+                    last = x;
+                }
+            }
         }
     }
-    // Fallback
-    c_str.to_bytes().len() as f32 * size * 0.6
-}
 
-extern "C" fn clear_cb(_user_data: *mut c_void, _r: f32, _g: f32, _b: f32, _a: f32) { }
+    /// Axes box in render pixels, `[left, top, right, bottom]`.
+    pub fn pixel_box(&self) -> [f32; 4] {
+        let mut b = [0.0f32; 4];
+        unsafe {
+            let [l, t, r, bo] = &mut b;
+            ffi::mpl_axes_get_pixel_box(self.ptr, l, t, r, bo);
+        }
+        b
+    }
 
-extern "C" fn draw_image_cb(_user_data: *mut c_void, _data: *const f32, _w: usize, _h: usize, _x: f32, _y: f32, _dw: f32, _dh: f32) { }
+    /// Current data-to-pixel mapping of the axes box.
+    pub fn viewport(&self) -> Viewport {
+        Viewport::new(DataRect::new(self.xlim(), self.ylim()), self.pixel_box())
+    }
 
-static VTABLE: ffi::MplWgpuVTable = ffi::MplWgpuVTable {
-    draw_rects: Some(draw_rects_cb),
-    draw_lines: Some(draw_lines_cb),
-    draw_circles: Some(draw_circles_cb),
-    draw_triangles: Some(draw_triangles_cb),
-    draw_text: Some(draw_text_cb),
-    measure_text: Some(measure_text_cb),
-    clear: Some(clear_cb),
-    draw_image: Some(draw_image_cb),
-};
+    /// Places round X ticks over the current limits and labels them
+    /// with `formatter`.
+    ///
+    /// On axes from [`PlotBackend::figure`] the ticks are placed and
+    /// labelled again on every render once the limits are final, so
+    /// they follow zoom, pan, streaming and autoscale, until ticks or
+    /// labels are set explicitly.  Elsewhere they are computed once
+    /// from [`xlim`](Self::xlim).
+    pub fn format_xticks(&self, formatter: impl TickFormatter + Send + 'static) {
+        self.format_ticks(0, TickLabels::Format { formatter: Box::new(formatter), offset: false });
+    }
 
-pub struct PlotBackend {
-    width: u32,
-    height: u32,
-    backend_ptr: *mut ffi::MplWgpuBackend,
-    figure_ptr: *mut ffi::MplFigure,
-    ctx_ptr: *mut BackendContext,
-}
+    /// Places round Y ticks over the current limits and labels them
+    /// with `formatter`.  See [`format_xticks`](Self::format_xticks).
+    pub fn format_yticks(&self, formatter: impl TickFormatter + Send + 'static) {
+        self.format_ticks(1, TickLabels::Format { formatter: Box::new(formatter), offset: false });
+    }
 
-impl PlotBackend {
-    pub fn new(width: u32, height: u32) -> Self {
-        let ctx = Box::new(BackendContext {
-            prim: std::ptr::null_mut(),
-            text: std::ptr::null_mut(),
-            transform: Mat4::IDENTITY,
-        });
-        
-        let ctx_ptr = Box::into_raw(ctx);
-        
-        let backend_ptr = unsafe { 
-            ffi::mpl_wgpu_backend_create(&VTABLE, ctx_ptr as *mut c_void) 
+    /// Like [`format_xticks`](Self::format_xticks), labelling the ticks
+    /// relative to the offset and multiplier picked by
+    /// [`OffsetScale::for_ticks`] so large-magnitude values stay short.
+    /// Returns them for the current limits; an [`OffsetLabel`] shows
+    /// them near the axis as the limits change (see
+    /// [`PlotBackend::format_ticks_with_offset`]).
+    pub fn format_xticks_offset(&self, formatter: impl TickFormatter + Send + 'static) -> OffsetScale {
+        self.format_ticks(0, TickLabels::Format { formatter: Box::new(formatter), offset: true })
+    }
+
+    /// Y axis version of [`format_xticks_offset`](Self::format_xticks_offset).
+    pub fn format_yticks_offset(&self, formatter: impl TickFormatter + Send + 'static) -> OffsetScale {
+        self.format_ticks(1, TickLabels::Format { formatter: Box::new(formatter), offset: true })
+    }
+
+    /// Places and labels the ticks of axis `i` (0 for X, 1 for Y) with
+    /// `labels` for the current limits, and keeps `labels` to do so on
+    /// every render.  Returns the offset scale of the placed ticks.
+    fn format_ticks(&self, i: usize, labels: TickLabels) -> OffsetScale {
+        let limits = if i == 0 { self.xlim() } else { self.ylim() };
+        let scale = labels.place(self, i, limits, self.locale);
+        self.keep_tick_labels(i, Some(labels));
+        scale
+    }
+
+    /// Sets (or with `None` forgets) the tick labels the backend
+    /// re-places on axis `i` of these axes.
+    fn keep_tick_labels(&self, i: usize, labels: Option<TickLabels>) {
+        let Some(kept) = self.tick_labels else { return };
+        let mut kept = kept.borrow_mut();
+        let key = self.key();
+        match kept.iter_mut().find(|t| t.key == key) {
+            Some(t) => {
+                t.labels[i] = labels;
+                t.placed[i] = None;
+            }
+            None => {
+                if let Some(labels) = labels {
+                    kept.push(AxesTicks::new(self, i, labels));
+                }
+            }
+        }
+        kept.retain(|t| t.labels.iter().any(Option::is_some));
+    }
+
+    /// Sets X ticks and labels, resolving label collisions.
+    ///
+    /// Labels that overlap their neighbours or overrun the figure edge
+    /// are fixed by shrinking the tick label font (which the Y tick
+    /// labels share; the title and axis labels keep their size),
+    /// rotating the labels, or keeping only every n-th label; the
+    /// returned [`LabelLayout`] reports the strategy used.  Call after
+    /// the X limits are final.
+    pub fn set_xticklabels_fitted<S: AsRef<str>>(&self, ticks: &[f64], labels: &[S]) -> LabelLayout {
+        self.keep_tick_labels(0, None);
+        self.fit_xticklabels(ticks, labels)
+    }
+
+    /// [`set_xticklabels_fitted`](Self::set_xticklabels_fitted) keeping
+    /// the tick labels the backend re-places.
+    fn fit_xticklabels<S: AsRef<str>>(&self, ticks: &[f64], labels: &[S]) -> LabelLayout {
+        let (min, max) = self.xlim();
+        let (mut x0, mut x1, mut fig_w) = (0.0, 0.0, 0.0);
+        let font_size = unsafe {
+            ffi::mpl_axes_get_pixel_extent(self.ptr, &mut x0, &mut x1, &mut fig_w);
+            ffi::mpl_axes_get_font_size(self.ptr)
         };
-        
+        let span = if max != min { max - min } else { 1.0 };
+        let positions: Vec<f32> = ticks
+            .iter()
+            .map(|t| x0 + ((t - min) / span) as f32 * (x1 - x0))
+            .collect();
+        let fit = layout::fit_tick_labels(&positions, labels, font_size, (0.0, fig_w), text::default_text_width);
+
+        let kept_ticks: Vec<f64> = fit.keep.iter().map(|&i| ticks[i]).collect();
+        let kept_labels: Vec<&str> = fit.keep.iter().map(|&i| labels[i].as_ref()).collect();
+        self.place_ticks(b'x', &kept_ticks, &kept_labels);
         unsafe {
-            ffi::mpl_wgpu_backend_set_size(backend_ptr, width, height);
+            ffi::mpl_axes_set_xtickangle(self.ptr, fit.rotation);
+            if fit.font_size != font_size {
+                ffi::mpl_axes_set_tick_font_size(self.ptr, fit.font_size);
+            }
         }
-        
-        let figure_ptr = unsafe {
-            ffi::mpl_figure_create(backend_ptr)
-        };
-        
-        Self {
-            width,
-            height,
-            backend_ptr,
-            figure_ptr,
-            ctx_ptr,
+        fit
+    }
+
+    /// Sets the ticks and labels of axis `b'x'` or `b'y'` without
+    /// touching the tick labels the backend re-places.
+    fn place_ticks<S: AsRef<str>>(&self, axis: u8, ticks: &[f64], labels: &[S]) {
+        let (_owned, ptrs) = c_strings(labels);
+        unsafe {
+            if axis == b'x' {
+                ffi::mpl_axes_set_xticks(self.ptr, ticks.as_ptr(), ticks.len());
+                ffi::mpl_axes_set_xticklabels(self.ptr, ptrs.as_ptr(), ptrs.len());
+            } else {
+                ffi::mpl_axes_set_yticks(self.ptr, ticks.as_ptr(), ticks.len());
+                ffi::mpl_axes_set_yticklabels(self.ptr, ptrs.as_ptr(), ptrs.len());
+            }
         }
     }
-    
-    // Provide access to the figure
-    // Note: In C++, backend doesn't usually own figure, but here we kind of do to keep it alive.
-    // Or we view PlotBackend as the "Canvas + Window" which holds the figure logic.
-    pub fn figure(&self) -> Figure {
-        Figure { ptr: self.figure_ptr }
+
+    /// Sets explicit X tick positions, replacing
+    /// [`format_xticks`](Self::format_xticks).
+    pub fn set_xticks(&self, ticks: &[f64]) {
+        self.keep_tick_labels(0, None);
+        unsafe { ffi::mpl_axes_set_xticks(self.ptr, ticks.as_ptr(), ticks.len()); }
     }
-    
-    pub fn resize(&mut self, width: u32, height: u32) {
-        self.width = width;
-        self.height = height;
-        unsafe { ffi::mpl_wgpu_backend_set_size(self.backend_ptr, width, height); }
+
+    /// Replaces the X tick labels with strings, one per tick, e.g.
+    /// category names for bar charts and boxplots.
+    pub fn set_xticklabels<S: AsRef<str>>(&self, labels: &[S]) {
+        self.keep_tick_labels(0, None);
+        let (_owned, ptrs) = c_strings(labels);
+        unsafe { ffi::mpl_axes_set_xticklabels(self.ptr, ptrs.as_ptr(), ptrs.len()); }
+    }
+
+    /// Sets explicit Y tick positions, replacing
+    /// [`format_yticks`](Self::format_yticks).
+    pub fn set_yticks(&self, ticks: &[f64]) {
+        self.keep_tick_labels(1, None);
+        unsafe { ffi::mpl_axes_set_yticks(self.ptr, ticks.as_ptr(), ticks.len()); }
+    }
+
+    /// Replaces the Y tick labels with strings, one per tick.
+    pub fn set_yticklabels<S: AsRef<str>>(&self, labels: &[S]) {
+        self.keep_tick_labels(1, None);
+        let (_owned, ptrs) = c_strings(labels);
+        unsafe { ffi::mpl_axes_set_yticklabels(self.ptr, ptrs.as_ptr(), ptrs.len()); }
+    }
+
+    /// Tick positions of axis `b'x'` or `b'y'` as matplot++ last placed
+    /// them, and whether it places them itself: `false` once ticks were
+    /// set, e.g. by [`set_xaxis_temporal`](Self::set_xaxis_temporal) or
+    /// [`format_xticks`](Self::format_xticks).
+    pub(crate) fn ticks(&self, axis: u8) -> (Vec<f64>, bool) {
+        let mut automatic = false;
+        let n = unsafe { ffi::mpl_axes_get_ticks(self.ptr, axis as c_char, std::ptr::null_mut(), 0, &mut automatic) };
+        let mut ticks = vec![0.0; n];
+        let n = unsafe { ffi::mpl_axes_get_ticks(self.ptr, axis as c_char, ticks.as_mut_ptr(), n, &mut automatic) }.min(ticks.len());
+        ticks.truncate(n);
+        (ticks, automatic)
+    }
+
+    /// Lets matplot++ place the ticks of axis `b'x'` or `b'y'` again.
+    pub(crate) fn set_ticks_auto(&self, axis: u8) {
+        unsafe { ffi::mpl_axes_set_ticks_auto(self.ptr, axis as c_char); }
     }
 
-    pub fn set_scale_factor(&mut self, _scale: f32) {}
+    /// Bar chart with one named category per bar.
+    ///
+    /// Bars sit at x = 1..=n (matplot++'s default), so the ticks are
+    /// placed there and labelled with `labels`.
+    pub fn bar_categorical<S: AsRef<str>>(&self, labels: &[S], values: &[f64]) {
+        let n = labels.len().min(values.len());
+        self.bar(&values[..n]);
+        let ticks: Vec<f64> = (1..=n).map(|i| i as f64).collect();
+        self.set_xticks(&ticks);
+        self.set_xticklabels(&labels[..n]);
+    }
+
+    /// Switches the X axis to temporal mode over `[t_min, t_max]`.
+    ///
+    /// Values are Unix timestamps in seconds (UTC).  Ticks land on
+    /// round calendar boundaries and are labelled as times or dates
+    /// depending on the span; see [`temporal::ticks`].
+    pub fn set_xaxis_temporal(&self, t_min: f64, t_max: f64) {
+        self.set_xaxis_temporal_in(t_min, t_max, temporal::Utc);
+    }
+
+    /// Temporal X axis labelled in the display time zone `zone`.
+    ///
+    /// Data stays in Unix timestamps; only tick placement and labels
+    /// follow `zone`'s wall clock, DST transitions included.  Dates are
+    /// written in the figure's locale if one was set with
+    /// [`PlotBackend::set_locale`], and as ISO dates otherwise.  Like
+    /// [`format_xticks`](Self::format_xticks), the ticks follow the X
+    /// limits on every render on axes from [`PlotBackend::figure`].
+    pub fn set_xaxis_temporal_in(&self, t_min: f64, t_max: f64, zone: impl temporal::DisplayZone + Send + 'static) {
+        self.set_xlim(t_min, t_max);
+        self.format_ticks(0, TickLabels::Temporal { zone: Box::new(zone), locale: None });
+    }
+
+    /// Temporal X axis in `zone` with dates written in `locale`; see
+    /// [`temporal::ticks_localized`].
+    pub fn set_xaxis_temporal_localized(
+        &self,
+        t_min: f64,
+        t_max: f64,
+        zone: impl temporal::DisplayZone + Send + 'static,
+        locale: &Locale,
+    ) {
+        self.set_xlim(t_min, t_max);
+        self.format_ticks(0, TickLabels::Temporal { zone: Box::new(zone), locale: Some(locale.clone()) });
+    }
+
+    /// Line plot against Unix timestamps, with a temporal X axis
+    /// spanning the data.
+    pub fn plot_time(&self, t: &[f64], y: &[f64], style: &str) -> Series<'a> {
+        let series = self.plot(t, y, style);
+        let n = t.len().min(y.len());
+        let (lo, hi) = t[..n]
+            .iter()
+            .filter(|v| v.is_finite())
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+        if lo < hi {
+            self.set_xaxis_temporal(lo, hi);
+        }
+        series
+    }
+
+    /// Line plot of angles `y` on a wrapped Y axis spanning `period`.
+    /// The line is cut where it crosses the wrap instead of jumping
+    /// across the axes, and ticks are labelled in degrees or fractions
+    /// of π.
+    pub fn plot_wrapped(&self, x: &[f64], y: &[f64], style: &str, period: Period) -> Series<'a> {
+        let (wx, wy) = periodic::split_wrapped(x, y, period);
+        let series = self.plot(&wx, &wy, style);
+        self.set_ylim(period.min, period.max);
+        let (ticks, labels) = periodic::angle_ticks(period, ticks::DEFAULT_MAX_TICKS);
+        self.set_yticks(&ticks);
+        self.set_yticklabels(&labels);
+        series
+    }
 
-    pub fn render(&mut self, prim: &mut PrimitiveRenderer, text: &mut TextRenderer, target: Option<Mat4>) {
+    /// Histogram of Unix timestamps with calendar-aligned bins (per
+    /// hour, day, week, month, ...) in UTC, with a temporal X axis.
+    pub fn hist_time(&self, t: &[f64], bin: temporal::TimeStep) {
+        self.hist_time_in(t, bin, temporal::Utc);
+    }
+
+    /// Like [`Axes::hist_time`], with bin boundaries on `zone`'s wall
+    /// clock.
+    pub fn hist_time_in(&self, t: &[f64], bin: temporal::TimeStep, zone: impl temporal::DisplayZone + Send + 'static) {
+        let (edges, _) = temporal::histogram(t, bin, &zone);
+        if edges.len() < 2 {
+            return;
+        }
         unsafe {
-            (*self.ctx_ptr).prim = prim as *mut _;
-            (*self.ctx_ptr).text = text as *mut _;
-            (*self.ctx_ptr).transform = target.unwrap_or(Mat4::IDENTITY);
-            // draw() triggers the full matplotplusplus pipeline:
-            //   new_frame() -> send_draw_commands() -> render_data()
-            // which populates primitives and flushes them via callbacks.
-            ffi::mpl_figure_draw(self.figure_ptr);
-            (*self.ctx_ptr).prim = std::ptr::null_mut();
-            (*self.ctx_ptr).text = std::ptr::null_mut();
+            ffi::mpl_axes_hist_edges(self.ptr, t.as_ptr(), t.len(), edges.as_ptr(), edges.len());
         }
+        self.set_xaxis_temporal_in(edges[0], edges[edges.len() - 1], zone);
     }
+
+    /// Line plot against `chrono` datetimes, with a temporal X axis.
+    #[cfg(feature = "chrono")]
+    pub fn plot_datetime<Tz: chrono::TimeZone>(&self, t: &[chrono::DateTime<Tz>], y: &[f64], style: &str) -> Series<'a> {
+        self.plot_time(&temporal::from_datetimes(t), y, style)
+    }
+
+    /// Line plot of chunked Arrow columns; nulls leave gaps and
+    /// timestamps are plotted as Unix seconds (see [`columns`](crate::columns)).
+    #[cfg(feature = "arrow")]
+    pub fn plot_arrow(&self, x: &[arrow_array::ArrayRef], y: &[arrow_array::ArrayRef], style: &str) -> crate::Result<Series<'a>> {
+        let x = crate::columns::arrow::chunked_values(x)?;
+        let y = crate::columns::arrow::chunked_values(y)?;
+        Ok(self.plot(&x, &y, style))
+    }
+
+    /// Line plot of columns `x` and `y` of a Polars data frame; nulls
+    /// leave gaps and datetimes are plotted as Unix seconds (see
+    /// [`columns`](crate::columns)).
+    #[cfg(feature = "polars")]
+    pub fn plot_df(&self, df: &polars::prelude::DataFrame, x: &str, y: &str) -> crate::Result<Series<'a>> {
+        let x = crate::columns::polars::values(df, x)?;
+        let y = crate::columns::polars::values(df, y)?;
+        Ok(self.plot(&x, &y, "-"))
+    }
+}
+
+impl Drop for Axes<'_> {
+    fn drop(&mut self) {
+        unsafe { ffi::mpl_axes_destroy(self.ptr); }
+    }
+}
+
+/// Handle to a line or scatter series just added with [`Axes::plot`] or
+/// [`Axes::scatter`], styled by chaining:
+///
+/// ```rust,no_run
+/// # fn demo(ax: &mpl_wgpu::plotting::Axes, x: &[f64], y: &[f64]) {
+/// ax.plot(x, y, "-").color(0.8, 0.1, 0.1).width(2.0).label("error").dashed();
+/// # }
+/// ```
+///
+/// The series stays in the axes when the handle is dropped; keep its
+/// [`id`](Self::id) to update or remove it later.
+pub struct Series<'a> {
+    ptr: *mut ffi::MplLine,
+    window: Option<usize>,
+    owner: PhantomData<&'a ()>,
+}
+
+/// Stable identifier of a plotted series.
+///
+/// Ids stay valid while the series is in its axes and are never reused,
+/// so a stale id is simply ignored.  Unlike [`Series`], an id is `Send`
+/// and can be stored anywhere.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SeriesId(pub(crate) u64);
+
+impl Series<'_> {
+    /// Identifier for [`Axes::update_series`], [`Axes::remove_series`]
+    /// and [`Axes::set_visible`].
+    pub fn id(&self) -> SeriesId {
+        SeriesId(unsafe { ffi::mpl_line_id(self.ptr) })
+    }
+
+    /// Sets the line and marker color.
+    pub fn color(self, r: f32, g: f32, b: f32) -> Self {
+        unsafe { ffi::mpl_line_set_color(self.ptr, r, g, b); }
+        self
+    }
+
+    /// Sets the line width, in points.
+    pub fn width(self, width: f32) -> Self {
+        unsafe { ffi::mpl_line_set_width(self.ptr, width); }
+        self
+    }
+
+    /// Sets the line style from a matplotlib-style spec (`"-"`, `"--"`,
+    /// `":"`, `"-."`).
+    pub fn style(self, style: &str) -> Self {
+        let c_style = CString::new(style).unwrap_or_default();
+        unsafe { ffi::mpl_line_set_style(self.ptr, c_style.as_ptr()); }
+        self
+    }
+
+    /// Dashed line.
+    pub fn dashed(self) -> Self {
+        self.style("--")
+    }
+
+    /// Dotted line.
+    pub fn dotted(self) -> Self {
+        self.style(":")
+    }
+
+    /// Sets the legend label.
+    pub fn label(self, label: &str) -> Self {
+        let c_label = CString::new(label).unwrap_or_default();
+        unsafe { ffi::mpl_line_set_label(self.ptr, c_label.as_ptr()); }
+        self
+    }
+
+    /// Sets the marker size, in points.
+    pub fn marker_size(self, size: f32) -> Self {
+        unsafe { ffi::mpl_line_set_marker_size(self.ptr, size); }
+        self
+    }
+
+    /// Keeps only the newest `len` points when pushing (see
+    /// [`push`](Self::push)), as a rolling window.
+    pub fn rolling(mut self, len: usize) -> Self {
+        self.window = Some(len);
+        self
+    }
+
+    /// Appends the point `(x, y)`.  Only the new point crosses the C
+    /// API; with [`rolling`](Self::rolling) the oldest points drop out.
+    pub fn push(&self, x: f64, y: f64) {
+        self.extend(&[x], &[y]);
+    }
+
+    /// Appends the points `(x[i], y[i])`.
+    pub fn extend(&self, x: &[f64], y: &[f64]) {
+        let n = x.len().min(y.len());
+        unsafe { ffi::mpl_line_append(self.ptr, x.as_ptr(), y.as_ptr(), n, self.window.unwrap_or(0)); }
+    }
+
+    /// Puts the series in group `name` of its axes (see
+    /// [`Axes::group`]), leaving any previous group.
+    pub fn group(self, name: &str) -> Self {
+        let c_name = CString::new(name).unwrap_or_default();
+        unsafe { ffi::mpl_line_set_group(self.ptr, c_name.as_ptr()); }
+        self
+    }
+}
+
+impl Drop for Series<'_> {
+    fn drop(&mut self) {
+        unsafe { ffi::mpl_line_destroy(self.ptr); }
+    }
+}
+
+/// Named group of series in one axes, from [`Axes::group`].
+///
+/// Setters apply to every current member and chain.  Series added to
+/// the group later are not restyled.
+pub struct SeriesGroup<'a> {
+    ax: &'a Axes<'a>,
+    name: CString,
+}
+
+impl SeriesGroup<'_> {
+    /// The group name.
+    pub fn name(&self) -> &str {
+        self.name.to_str().unwrap_or_default()
+    }
+
+    /// Ids of the members, oldest first.
+    pub fn ids(&self) -> Vec<SeriesId> {
+        let n = unsafe { ffi::mpl_axes_group_series(self.ax.ptr, self.name.as_ptr(), std::ptr::null_mut(), 0) };
+        let mut ids = vec![0u64; n];
+        let n = unsafe { ffi::mpl_axes_group_series(self.ax.ptr, self.name.as_ptr(), ids.as_mut_ptr(), n) };
+        ids.truncate(n);
+        ids.into_iter().map(SeriesId).collect()
+    }
+
+    /// Number of members.
+    pub fn len(&self) -> usize {
+        unsafe { ffi::mpl_axes_group_series(self.ax.ptr, self.name.as_ptr(), std::ptr::null_mut(), 0) }
+    }
+
+    /// Whether the group has no members.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Moves series `id` of the axes into this group.  Unknown ids are
+    /// ignored.
+    pub fn add(&self, id: SeriesId) -> &Self {
+        unsafe { ffi::mpl_axes_set_series_group(self.ax.ptr, id.0, self.name.as_ptr()); }
+        self
+    }
+
+    /// Sets the opacity of lines and markers, 0 (invisible) to 1.
+    pub fn set_alpha(&self, alpha: f32) -> &Self {
+        self.each(|ax, id| unsafe { ffi::mpl_axes_set_series_alpha(ax, id, alpha) })
+    }
+
+    /// Sets the line and marker color, keeping the opacity.
+    pub fn set_color(&self, r: f32, g: f32, b: f32) -> &Self {
+        self.each(|ax, id| unsafe { ffi::mpl_axes_set_series_color(ax, id, r, g, b) })
+    }
+
+    /// Sets the line width, in points.
+    pub fn set_width(&self, width: f32) -> &Self {
+        self.each(|ax, id| unsafe { ffi::mpl_axes_set_series_width(ax, id, width) })
+    }
+
+    /// Shows or hides all members.
+    pub fn set_visible(&self, visible: bool) -> &Self {
+        self.each(|ax, id| unsafe { ffi::mpl_axes_set_series_visible(ax, id, visible) })
+    }
+
+    /// Removes all members from the axes and returns how many there
+    /// were.
+    pub fn remove(&self) -> usize {
+        let ids = self.ids();
+        ids.iter().filter(|id| self.ax.remove_series(**id)).count()
+    }
+
+    fn each(&self, f: impl Fn(*mut ffi::MplAxes, u64) -> bool) -> &Self {
+        for id in self.ids() {
+            f(self.ax.ptr, id.0);
+        }
+        self
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Figure
+// ----------------------------------------------------------------------------
+
+pub struct Figure<'a> {
+    ptr: *mut ffi::MplFigure,
+    locale: Option<&'a Locale>,
+    tick_labels: Option<&'a RefCell<Vec<AxesTicks>>>,
+    owner: PhantomData<&'a ()>,
+}
+
+impl<'a> Figure<'a> {
+    /// Non-owning handle to `ptr`, valid while its owner is borrowed
+    /// for `'a`.
+    pub(crate) fn from_raw(ptr: *mut ffi::MplFigure) -> Self {
+        Figure { ptr, locale: None, tick_labels: None, owner: PhantomData }
+    }
+
+    pub fn current_axes(&self) -> Axes<'a> {
+        let ptr = unsafe { ffi::mpl_figure_current_axes(self.ptr) };
+        Axes { ptr, locale: self.locale, tick_labels: self.tick_labels, owner: PhantomData }
+    }
+    
+    pub fn clear(&self) {
+        unsafe { ffi::mpl_figure_clear(self.ptr); }
+    }
+
+    /// Sets the fill behind the whole figure, `[r, g, b, a]`.
+    pub fn set_background(&self, color: [f32; 4]) {
+        let [r, g, b, a] = color;
+        unsafe { ffi::mpl_figure_set_background(self.ptr, r, g, b, a); }
+    }
+}
+
+// ----------------------------------------------------------------------------
+// PlotBackend
+// ----------------------------------------------------------------------------
+
+struct BackendContext {
+    prim: *mut PrimitiveRenderer,
+    text: *mut TextRenderer,
+    /// Measures text while recording without a text renderer.
+    metrics: *const TextMetrics,
+    record: *mut Recording,
+    transform: Mat4,
+    sizes: SizeScale,
+    /// Panics caught in callbacks since the last draw.
+    warnings: Vec<RenderWarning>,
+}
+
+/// Runs the body of a backend callback.  Unwinding into matplot++
+/// would abort, so a panic is caught instead: what the batch queued is
+/// discarded and a [`RenderWarning::CallbackPanicked`] is kept for the
+/// render.
+fn guard_callback<R: Default>(user_data: *mut c_void, callback: &'static str, body: impl FnOnce(&mut BackendContext) -> R) -> R {
+    let ctx = user_data as *mut BackendContext;
+    let mark = unsafe {
+        (
+            (*ctx).prim.as_ref().map_or(0, |p| p.instance_count()),
+            (*ctx).text.as_ref().map_or(0, |t| t.queued_count()),
+            (*ctx).record.as_ref().map_or(0, |r| r.primitives.len()),
+        )
+    };
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| body(unsafe { &mut *ctx }))) {
+        Ok(r) => r,
+        Err(payload) => {
+            let ctx = unsafe { &mut *ctx };
+            if let Some(p) = unsafe { ctx.prim.as_mut() } {
+                p.truncate(mark.0);
+            }
+            if let Some(t) = unsafe { ctx.text.as_mut() } {
+                t.truncate_queue(mark.1);
+            }
+            if let Some(r) = unsafe { ctx.record.as_mut() } {
+                r.primitives.truncate(mark.2);
+            }
+            ctx.warnings.push(RenderWarning::CallbackPanicked { callback, message: artist::panic_message(&*payload) });
+            R::default()
+        }
+    }
+}
+
+extern "C" fn draw_rects_cb(user_data: *mut c_void, rects: *const ffi::MplWgpuRect, count: usize) {
+    guard_callback(user_data, "draw_rects", |ctx| {
+        let mut prim = unsafe { ctx.prim.as_mut() };
+        let mut record = unsafe { ctx.record.as_mut() };
+        if prim.is_none() && record.is_none() { return; }
+        let rects_slice = unsafe { std::slice::from_raw_parts(rects, count) };
+        for r in rects_slice {
+            let pos = ctx.transform.transform_point3(Vec3::new(r.x, r.y, 0.0));
+            let pos = Vec2::new(pos.x, pos.y);
+            let size = Vec2::new(r.width, r.height);
+            let color = Vec4::new(r.r, r.g, r.b, r.a);
+            let stroke_width = ctx.sizes.points_to_pixels(r.stroke_width);
+            if let Some(prim) = prim.as_deref_mut() {
+                prim.draw_rect(pos, size, color, r.corner_radius, stroke_width);
+            }
+            if let Some(record) = record.as_deref_mut() {
+                record.push(RecordedPrimitive::Rect {
+                    pos,
+                    size,
+                    color,
+                    stroke_width,
+                    corner_radius: r.corner_radius,
+                });
+            }
+        }
+    })
+}
+
+extern "C" fn draw_lines_cb(user_data: *mut c_void, lines: *const ffi::MplWgpuLine, count: usize) {
+    guard_callback(user_data, "draw_lines", |ctx| {
+        let mut prim = unsafe { ctx.prim.as_mut() };
+        let mut record = unsafe { ctx.record.as_mut() };
+        if prim.is_none() && record.is_none() { return; }
+        let lines_slice = unsafe { std::slice::from_raw_parts(lines, count) };
+        for l in lines_slice {
+            let p1 = ctx.transform.transform_point3(Vec3::new(l.x1, l.y1, l.z1));
+            let p2 = ctx.transform.transform_point3(Vec3::new(l.x2, l.y2, l.z2));
+            let color = Vec4::new(l.r, l.g, l.b, l.a);
+            let width = ctx.sizes.points_to_pixels(l.width);
+            if let Some(prim) = prim.as_deref_mut() {
+                prim.draw_line(
+                    p1,
+                    p2,
+                    width,
+                    color,
+                    l.dash_len,
+                    l.gap_len,
+                    l.dash_offset
+                );
+            }
+            if let Some(record) = record.as_deref_mut() {
+                record.push(RecordedPrimitive::Line {
+                    start: p1,
+                    end: p2,
+                    width,
+                    color,
+                    dash_len: l.dash_len,
+                    gap_len: l.gap_len,
+                    dash_offset: l.dash_offset,
+                });
+            }
+        }
+    })
+}
+
+extern "C" fn draw_circles_cb(user_data: *mut c_void, circles: *const ffi::MplWgpuCircle, count: usize) {
+    guard_callback(user_data, "draw_circles", |ctx| {
+        let mut prim = unsafe { ctx.prim.as_mut() };
+        let mut record = unsafe { ctx.record.as_mut() };
+        if prim.is_none() && record.is_none() { return; }
+        let slice = unsafe { std::slice::from_raw_parts(circles, count) };
+        for c in slice {
+            let center = ctx.transform.transform_point3(Vec3::new(c.cx, c.cy, c.cz));
+            let color = Vec4::new(c.r, c.g, c.b, c.a);
+            let radius = ctx.sizes.points_to_pixels(c.radius);
+            if let Some(prim) = prim.as_deref_mut() {
+                prim.draw_circle(center, radius, color, 0.0, c.type_ as u32);
+            }
+            if let Some(record) = record.as_deref_mut() {
+                record.push(RecordedPrimitive::Marker {
+                    center,
+                    radius,
+                    color,
+                    marker_type: c.type_ as u32,
+                });
+            }
+        }
+    })
+}
+
+extern "C" fn draw_triangles_cb(user_data: *mut c_void, tris: *const ffi::MplWgpuTriangle, count: usize) {
+    guard_callback(user_data, "draw_triangles", |ctx| {
+        let mut prim = unsafe { ctx.prim.as_mut() };
+        let mut record = unsafe { ctx.record.as_mut() };
+        if prim.is_none() && record.is_none() { return; }
+        let slice = unsafe { std::slice::from_raw_parts(tris, count) };
+        for t in slice {
+            let p1 = ctx.transform.transform_point3(Vec3::new(t.x1, t.y1, t.z1));
+            let p2 = ctx.transform.transform_point3(Vec3::new(t.x2, t.y2, t.z2));
+            let p3 = ctx.transform.transform_point3(Vec3::new(t.x3, t.y3, t.z3));
+            let color = Vec4::new(t.r, t.g, t.b, t.a);
+            if let Some(prim) = prim.as_deref_mut() {
+                prim.draw_triangle(p1, p2, p3, color);
+            }
+            if let Some(record) = record.as_deref_mut() {
+                record.push(RecordedPrimitive::Triangle {
+                    points: [p1, p2, p3],
+                    color,
+                });
+            }
+        }
+    })
+}
+
+extern "C" fn draw_text_cb(user_data: *mut c_void, text: *const c_char, x: f32, y: f32, size: f32, r: f32, g: f32, b: f32, a: f32, rotation: f32) {
+    guard_callback(user_data, "draw_text", |ctx| {
+        let text_renderer = unsafe { ctx.text.as_mut() };
+        let record = unsafe { ctx.record.as_mut() };
+        if text_renderer.is_none() && record.is_none() { return; }
+        if text.is_null() { return; }
+        let c_str = unsafe { CStr::from_ptr(text) };
+    
+        let pos = ctx.transform.transform_point3(Vec3::new(x, y, 0.0));
+        let pos = Vec2::new(pos.x, pos.y);
+        let color = Vec4::new(r, g, b, a);
+    
+        if let Ok(s) = c_str.to_str() {
+            if mathtext::has_math(s) {
+                let metrics = unsafe { ctx.metrics.as_ref() };
+                let layout = match (text_renderer.as_deref(), metrics) {
+                    (Some(t), _) => t.layout_math(s, size),
+                    (None, Some(m)) => m.layout_math(s, size),
+                    (None, None) => mathtext::layout(s, size, Metrics::default(), &text::estimate_text_width),
+                };
+                let prim = unsafe { ctx.prim.as_mut() };
+                mathtext::draw_math(&layout, pos, rotation, color, prim, text_renderer, record);
+                return;
+            }
+            let metrics = unsafe { ctx.metrics.as_ref() };
+            let emoji = match (text_renderer.as_deref(), metrics) {
+                (Some(t), _) if t.has_color_glyphs(s) => Some(t.layout_emoji(s, size)),
+                (None, Some(m)) if m.has_color_glyphs(s) => Some(m.layout_emoji(s, size)),
+                _ => None,
+            };
+            if let Some(layout) = emoji {
+                let prim = unsafe { ctx.prim.as_mut() };
+                emoji::draw_emoji(&layout, pos, rotation, color, prim, text_renderer, record);
+                return;
+            }
+            // While recording, the text renderer is only used for measuring.
+            if let Some(record) = record {
+                record.push(RecordedPrimitive::Text {
+                    text: s.to_string(),
+                    pos,
+                    size,
+                    color,
+                    rotation,
+                });
+            } else if let Some(text_renderer) = text_renderer {
+                text_renderer.draw_text_rotated(s, pos, size, color, rotation);
+            }
+        }
+    })
+}
+
+extern "C" fn measure_text_cb(user_data: *mut c_void, text: *const c_char, size: f32) -> f32 {
+    if text.is_null() { return 0.0; }
+    let c_str = unsafe { CStr::from_ptr(text) };
+    guard_callback(user_data, "measure_text", |ctx| {
+        if !ctx.text.is_null() {
+            let text_renderer = unsafe { &mut *ctx.text };
+            if let Ok(s) = c_str.to_str() {
+                return text_renderer.measure_text(s, size).x;
+            }
+        }
+        if let (Some(metrics), Ok(s)) = (unsafe { ctx.metrics.as_ref() }, c_str.to_str()) {
+            return metrics.measure_text(s, size).x;
+        }
+        text::estimate_text_width(&c_str.to_string_lossy(), size)
+    })
+}
+
+extern "C" fn clear_cb(_user_data: *mut c_void, _r: f32, _g: f32, _b: f32, _a: f32) { }
+
+extern "C" fn draw_image_cb(_user_data: *mut c_void, _data: *const f32, _w: usize, _h: usize, _x: f32, _y: f32, _dw: f32, _dh: f32) { }
+
+static VTABLE: ffi::MplWgpuVTable = ffi::MplWgpuVTable {
+    draw_rects: Some(draw_rects_cb),
+    draw_lines: Some(draw_lines_cb),
+    draw_circles: Some(draw_circles_cb),
+    draw_triangles: Some(draw_triangles_cb),
+    draw_text: Some(draw_text_cb),
+    measure_text: Some(measure_text_cb),
+    clear: Some(clear_cb),
+    draw_image: Some(draw_image_cb),
+};
+
+/// Owns a matplot++ figure and the artists drawn over it.
+///
+/// A `PlotBackend` is `Send`: it can be built on a worker thread and
+/// moved to the thread that owns the GPU renderers.  Renderers are only
+/// borrowed for the duration of [`render`](Self::render) and
+/// [`record`](Self::record), never stored.  The [`Figure`], [`Axes`]
+/// and [`Series`] handles it hands out are not `Send`; drop them before
+/// moving the backend.  Share one between threads with a
+/// [`FigureHandle`](crate::handle::FigureHandle).
+pub struct PlotBackend {
+    width: u32,
+    height: u32,
+    backend_ptr: *mut ffi::MplWgpuBackend,
+    figure_ptr: *mut ffi::MplFigure,
+    ctx_ptr: *mut BackendContext,
+    artists: Vec<Box<dyn Artist>>,
+    probe: Option<DataProbe>,
+    /// Series revision the probe last read its plotted series at.
+    probe_revision: Option<u64>,
+    box_zoom: Option<BoxZoom>,
+    tooltip: Option<Tooltip>,
+    y_autoscale: Option<VisibleYAutoscale>,
+    /// Streams by [`Axes::key`] of their axes.
+    streams: HashMap<usize, HashMap<SeriesId, Stream>>,
+    /// Series moved into or out of [`Layer::Dynamic`] explicitly.
+    dynamic_series: HashMap<SeriesId, bool>,
+    stream_autoscale: bool,
+    /// Set with [`set_locale`](Self::set_locale); English otherwise.
+    locale: Option<Locale>,
+    warnings: Vec<RenderWarning>,
+    margins: Vec<AxesMargins>,
+    tick_adjuster: Option<Box<dyn TickAdjuster>>,
+    /// Ticks last adjusted on the X and Y axis.
+    tick_memos: [Option<TickMemo>; 2],
+    /// Axes labelled with [`Axes::format_xticks`] and the like.
+    tick_labels: RefCell<Vec<AxesTicks>>,
+    /// Index for [`pick`](Self::pick) and the series revision it saw.
+    pick_index: Option<(PointIndex, u64)>,
+    /// Series and point index of the last pick's hit, so moving over
+    /// the same point reports it once.
+    picked: Option<(SeriesId, usize)>,
+    limit_watch: Watch<AxisLimits>,
+    camera_watch: Watch<CameraView>,
+    events: Events,
+    cameras: Vec<AxesCamera>,
+}
+
+impl PlotBackend {
+    pub fn new(width: u32, height: u32) -> Self {
+        let ctx = Box::new(BackendContext {
+            prim: std::ptr::null_mut(),
+            text: std::ptr::null_mut(),
+            metrics: std::ptr::null(),
+            record: std::ptr::null_mut(),
+            transform: Mat4::IDENTITY,
+            sizes: SizeScale::default(),
+            warnings: Vec::new(),
+        });
+        
+        let ctx_ptr = Box::into_raw(ctx);
+        
+        let backend_ptr = unsafe { 
+            ffi::mpl_wgpu_backend_create(&VTABLE, ctx_ptr as *mut c_void) 
+        };
+        
+        unsafe {
+            ffi::mpl_wgpu_backend_set_size(backend_ptr, width, height);
+        }
+        
+        let figure_ptr = unsafe {
+            ffi::mpl_figure_create(backend_ptr)
+        };
+        
+        Self {
+            width,
+            height,
+            backend_ptr,
+            figure_ptr,
+            ctx_ptr,
+            artists: Vec::new(),
+            probe: None,
+            probe_revision: None,
+            box_zoom: None,
+            tooltip: None,
+            y_autoscale: None,
+            streams: HashMap::new(),
+            dynamic_series: HashMap::new(),
+            stream_autoscale: true,
+            locale: None,
+            warnings: Vec::new(),
+            margins: Vec::new(),
+            tick_adjuster: None,
+            tick_memos: [None, None],
+            tick_labels: RefCell::new(Vec::new()),
+            pick_index: None,
+            picked: None,
+            limit_watch: Watch::default(),
+            camera_watch: Watch::default(),
+            events: Events::default(),
+            cameras: Vec::new(),
+        }
+    }
+    
+    /// Builds a figure from a [`PlotSpec`], failing with
+    /// [`Error::UnknownData`](crate::Error::UnknownData) if it
+    /// references a column it does not define.
+    pub fn from_spec(spec: &PlotSpec) -> crate::Result<Self> {
+        spec.validate()?;
+        let backend = Self::new(spec.width, spec.height);
+        spec.draw(&backend.figure().current_axes())?;
+        Ok(backend)
+    }
+
+    /// Width and height in pixels.
+    pub fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Opens an 800 x 600 window showing the figure and blocks until
+    /// it is closed; the figure follows the window size.  Must be
+    /// called on the main thread on platforms that require it.  See
+    /// [`window::show`](crate::window::show) to keep editing the figure
+    /// from other threads.
+    #[cfg(feature = "window")]
+    pub fn show(self) -> crate::Result<()> {
+        self.show_with(crate::window::WindowOptions::new())
+    }
+
+    /// Like [`show`](Self::show), with the window title, size and frame
+    /// pacing of `options`.
+    #[cfg(feature = "window")]
+    pub fn show_with(self, options: crate::window::WindowOptions) -> crate::Result<()> {
+        crate::window::show(crate::handle::FigureHandle::new(self), options)
+    }
+
+    // Provide access to the figure
+    // Note: In C++, backend doesn't usually own figure, but here we kind of do to keep it alive.
+    // Or we view PlotBackend as the "Canvas + Window" which holds the figure logic.
+    /// The figure drawn by this backend.
+    ///
+    /// The figure and the handles taken from it borrow the backend, so
+    /// it cannot move to another thread while they are alive:
+    ///
+    /// ```rust,compile_fail
+    /// let backend = mpl_wgpu::plotting::PlotBackend::new(640, 480);
+    /// let ax = backend.figure().current_axes();
+    /// std::thread::spawn(move || drop(backend));
+    /// ax.grid(true);
+    /// ```
+    pub fn figure(&self) -> Figure<'_> {
+        Figure {
+            locale: self.locale.as_ref(),
+            tick_labels: Some(&self.tick_labels),
+            ..Figure::from_raw(self.figure_ptr)
+        }
+    }
+    
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+        unsafe { ffi::mpl_wgpu_backend_set_size(self.backend_ptr, width, height); }
+        self.apply_margins();
+    }
+
+    /// Places the current axes box `left`, `right`, `top` and `bottom`
+    /// pixels from the figure edges, kept across
+    /// [`resize`](Self::resize).  Use small margins for sparklines and
+    /// small multiples, wider ones for long tick labels.  Each axes
+    /// keeps its own margins.
+    pub fn set_margins(&mut self, left: f32, right: f32, top: f32, bottom: f32) {
+        let ax = Figure::from_raw(self.figure_ptr).current_axes();
+        let key = ax.key();
+        self.margins.retain(|m| m.key != key);
+        self.margins.push(AxesMargins::new(ax, [left, right, top, bottom]));
+        self.apply_margins();
+    }
+
+    /// The pixel margins of the current axes from
+    /// [`set_margins`](Self::set_margins), as `[left, right, top,
+    /// bottom]`.
+    pub fn margins(&self) -> Option<[f32; 4]> {
+        let key = self.figure().current_axes().key();
+        self.margins.iter().find(|m| m.key == key).map(|m| m.margins)
+    }
+
+    /// Moves the current axes' tick labels and axis labels `padding`
+    /// pixels further from the box (see [`Axes::set_tick_padding`]).
+    pub fn set_tick_padding(&mut self, padding: f32) {
+        self.figure().current_axes().set_tick_padding(padding);
+    }
+
+    /// Places every axes with margins at them for the current size.
+    fn apply_margins(&self) {
+        let (w, h) = (self.width.max(1) as f32, self.height.max(1) as f32);
+        for m in &self.margins {
+            let [left, right, top, bottom] = m.margins;
+            let width = ((w - left - right) / w).max(0.0);
+            let height = ((h - top - bottom) / h).max(0.0);
+            m.axes().set_position([left / w, bottom / h, width, height]);
+        }
+    }
+
+    /// Sets the display scale factor applied to line widths, marker
+    /// sizes, and strokes.
+    pub fn set_scale_factor(&mut self, scale: f32) {
+        unsafe { (*self.ctx_ptr).sizes.scale_factor = scale; }
+    }
+
+    /// Passes the current axes' tick positions through `adjuster` on
+    /// every render, after the limits are final.  The ticks start as
+    /// round positions over the limits (see [`ticks::nice_ticks`]) and
+    /// replace any set with [`Axes::set_xticks`]; labels follow the
+    /// adjusted positions unless set explicitly.
+    pub fn set_tick_adjuster(&mut self, adjuster: impl TickAdjuster + 'static) {
+        self.tick_adjuster = Some(Box::new(adjuster));
+    }
+
+    /// Removes the tick adjuster, leaving the ticks to matplot++.
+    pub fn clear_tick_adjuster(&mut self) {
+        self.tick_adjuster = None;
+    }
+
+    /// Sets the locale of the figure's built-in text, read by artists
+    /// through [`DrawContext::locale`].  Temporal X axes write their
+    /// dates in it too, from the next render on.
+    pub fn set_locale(&mut self, locale: Locale) {
+        self.locale = Some(locale);
+        for t in self.tick_labels.get_mut() {
+            t.placed = [None, None];
+        }
+    }
+
+    /// The figure's locale, English by default.
+    pub fn locale(&self) -> &Locale {
+        self.locale.as_ref().unwrap_or_else(|| crate::locale::english())
+    }
+
+    /// Sets the figure DPI used to convert point sizes to pixels.
+    pub fn set_dpi(&mut self, dpi: f32) {
+        unsafe { (*self.ctx_ptr).sizes.dpi = dpi; }
+    }
+
+    /// Current point-to-pixel conversion.
+    pub fn size_scale(&self) -> SizeScale {
+        unsafe { (*self.ctx_ptr).sizes }
+    }
+
+    /// Registers a custom artist, drawn over the current axes on every
+    /// render and record.
+    pub fn add_artist(&mut self, artist: Box<dyn Artist>) {
+        self.artists.push(artist);
+    }
+
+    /// Adds a horizontal reference line at `y` spanning the axes box
+    /// regardless of the data limits, `width` in points.
+    pub fn axhline(&mut self, y: f64, color: Vec4, width: f32, style: LineStyle) {
+        let line = RefLine::horizontal(y).color(color).width(width).style(style);
+        self.add_artist(Box::new(line));
+    }
+
+    /// Adds a vertical reference line at `x` spanning the axes box
+    /// regardless of the data limits, `width` in points.
+    pub fn axvline(&mut self, x: f64, color: Vec4, width: f32, style: LineStyle) {
+        let line = RefLine::vertical(x).color(color).width(width).style(style);
+        self.add_artist(Box::new(line));
+    }
+
+    /// Adds reference geometry (circle, ellipse, rectangle, polygon) in
+    /// data coordinates, clipped to the axes box.
+    pub fn add_shape(&mut self, shape: Shape, style: ShapeStyle) {
+        self.add_artist(Box::new(ShapePatch::new(shape, style)));
+    }
+
+    /// Draws the rectangle spanning `x0..x1` and `y0..y1` in data
+    /// coordinates, clipped to the axes box and widening the limits if
+    /// `style.autoscale` is set.  A building block for Gantt bars, heat
+    /// tiles and other custom charts; see [`ShapeStyle::tile`].
+    pub fn draw_rect_data(&mut self, x0: f64, x1: f64, y0: f64, y1: f64, style: ShapeStyle) {
+        self.add_shape(Shape::rect(x0, x1, y0, y1), style);
+    }
+
+    /// Adds a colorbar for `map` over the value range of `norm` beside
+    /// the axes box, with decade ticks under a logarithmic norm.
+    pub fn colorbar(&mut self, map: Box<dyn Colormap>, norm: Box<dyn Norm>) {
+        self.add_artist(Box::new(Colorbar::new(map, norm)));
+    }
+
+    /// Adds a scatter plot that merges dense regions into count-sized
+    /// markers, re-clustered as the view zooms (see
+    /// [`cluster`](crate::cluster)).
+    pub fn add_clustered_scatter(&mut self, scatter: ClusteredScatter) {
+        self.add_artist(Box::new(scatter));
+    }
+
+    /// Adds a scale bar in a corner of the axes box.
+    pub fn add_scale_bar(&mut self, bar: ScaleBar) {
+        self.add_artist(Box::new(bar));
+    }
+
+    /// Adds a north arrow in a corner of the axes box.
+    pub fn add_north_arrow(&mut self, arrow: NorthArrow) {
+        self.add_artist(Box::new(arrow));
+    }
+
+    /// Labels the current axes' ticks relative to an offset and
+    /// multiplier per axis (see [`Axes::format_xticks_offset`]) and
+    /// shows them near the axes, matplotlib style: the X one under the
+    /// right end of the X axis, the Y one above the Y axis.  Both
+    /// follow the limits on every render.  Returns the X and Y scales
+    /// for the current limits.
+    pub fn format_ticks_with_offset(
+        &mut self,
+        formatter: impl TickFormatter + Clone + Send + 'static,
+    ) -> (OffsetScale, OffsetScale) {
+        let (x, y) = {
+            let ax = self.figure().current_axes();
+            (ax.format_xticks_offset(formatter.clone()), ax.format_yticks_offset(formatter))
+        };
+        self.add_artist(Box::new(OffsetLabel::new(LabelAxis::X)));
+        self.add_artist(Box::new(OffsetLabel::new(LabelAxis::Y)));
+        (x, y)
+    }
+
+    /// Adds a raster image under the data, anchored to its data
+    /// extent.  The figure and axes backgrounds are made transparent so
+    /// the image shows through; the render target's clear color fills
+    /// the rest.
+    pub fn add_image_underlay(&mut self, image: ImageUnderlay) {
+        let fig = self.figure();
+        fig.set_background([1.0, 1.0, 1.0, 0.0]);
+        fig.current_axes().set_background([1.0, 1.0, 1.0, 0.0]);
+        self.add_artist(Box::new(image));
+    }
+
+    /// Removes all custom artists.
+    pub fn clear_artists(&mut self) {
+        self.artists.clear();
+    }
+
+    /// Installs (or with `None` removes) the vertical data probe drawn
+    /// over the current axes.  Its plotted series are read from the
+    /// current axes whenever series data changes.
+    pub fn set_probe(&mut self, probe: Option<DataProbe>) {
+        self.probe = probe;
+        self.probe_revision = None;
+    }
+
+    /// The installed data probe.
+    pub fn probe_mut(&mut self) -> Option<&mut DataProbe> {
+        self.probe_revision = None;
+        self.probe.as_mut()
+    }
+
+    /// Rereads the probe's plotted series if any series changed since
+    /// the last read.
+    fn read_probe_series(&mut self) {
+        let revision = unsafe { ffi::mpl_series_revision() };
+        let Some(probe) = self.probe.as_mut() else { return };
+        if self.probe_revision != Some(revision) {
+            probe.read_series(&Figure::from_raw(self.figure_ptr).current_axes());
+            self.probe_revision = Some(revision);
+        }
+    }
+
+    /// Moves the probe to the data X under the horizontal pixel
+    /// position `cursor_x`, or hides it when the cursor leaves the axes
+    /// box.  Returns the readings at the new position.
+    pub fn move_probe(&mut self, cursor_x: f32) -> Vec<ProbeReading> {
+        self.read_probe_series();
+        let viewport = self.figure().current_axes().viewport();
+        let Some(probe) = self.probe.as_mut() else { return Vec::new() };
+        let [left, _, right, _] = viewport.pixels;
+        probe.x = if cursor_x >= left && cursor_x <= right {
+            Some(viewport.to_data(Vec2::new(cursor_x, 0.0)).0)
+        } else {
+            None
+        };
+        probe.readings()
+    }
+
+    /// Receives the current axes' limits now and after every render
+    /// that changed them (see [`watch`](crate::watch)).
+    pub fn watch_limits(&mut self) -> Receiver<AxisLimits> {
+        let limits = self.figure().current_axes().limits();
+        self.limit_watch.subscribe(limits)
+    }
+
+    /// Receives the current axes' 3D camera now and after every render
+    /// that changed it.
+    pub fn watch_camera(&mut self) -> Receiver<CameraView> {
+        let view = self.figure().current_axes().view();
+        self.camera_watch.subscribe(view)
+    }
+
+    /// Receives the interaction events from now on (see
+    /// [`events`](crate::events)).
+    pub fn subscribe(&mut self) -> Receiver<PlotEvent> {
+        self.events.subscribe()
+    }
+
+    /// Installs (or with `None` removes) the orbit camera of the current
+    /// 3D axes (see [`camera`](crate::camera)), framing the limits they
+    /// have now at its [`Default`] distance and target.  Renders without
+    /// a target transform point the axes along the camera and zoom and
+    /// pan their limits with it.
+    pub fn set_camera(&mut self, camera: Option<Camera3D>) {
+        let ax = Figure::from_raw(self.figure_ptr).current_axes();
+        let key = ax.key();
+        self.cameras.retain(|c| c.key != key);
+        if let Some(camera) = camera {
+            self.cameras.push(AxesCamera::new(ax, camera));
+        }
+    }
+
+    /// The orbit camera of the current axes, installed at their view
+    /// and limits on first use, to [`orbit`](Camera3D::orbit),
+    /// [`zoom`](Camera3D::zoom) and [`pan`](Camera3D::pan) from pointer
+    /// input.  Each axes keeps its own camera.
+    pub fn camera_mut(&mut self) -> &mut Camera3D {
+        let ax = Figure::from_raw(self.figure_ptr).current_axes();
+        let key = ax.key();
+        let i = match self.cameras.iter().position(|c| c.key == key) {
+            Some(i) => i,
+            None => {
+                let camera = Camera3D::default().with_view(ax.view());
+                self.cameras.push(AxesCamera::new(ax, camera));
+                self.cameras.len() - 1
+            }
+        };
+        &mut self.cameras[i].camera
+    }
+
+    /// Pixel position of the data point `(x, y)` in the current axes.
+    pub fn data_to_screen(&self, x: f64, y: f64) -> Vec2 {
+        self.figure().current_axes().viewport().to_pixels(x, y)
+    }
+
+    /// Data point of the current axes under the pixel position `px`,
+    /// or `None` outside the axes box.  The inverse of
+    /// [`data_to_screen`](Self::data_to_screen), for turning pointer
+    /// positions into data for cursors, picking and annotations.
+    pub fn screen_to_data(&self, px: Vec2) -> Option<(f64, f64)> {
+        let viewport = self.figure().current_axes().viewport();
+        viewport.contains_pixel(px).then(|| viewport.to_data(px))
+    }
+
+    /// The point of a visible series of the current axes nearest to the
+    /// pixel position `px`, within `radius` pixels, for hover tooltips
+    /// and click-to-inspect.  Subscribers get a
+    /// [`SeriesPicked`](PlotEvent::SeriesPicked) event when the hit
+    /// moves to another point, not on every pick of the same one.
+    ///
+    /// The points are indexed by pixel cell on the first pick after a
+    /// render, so repeated picks are cheap (see [`PointIndex`]).  The
+    /// index is rebuilt when the limits move or any series is added,
+    /// edited, streamed into, hidden or removed.
+    pub fn pick(&mut self, px: Vec2, radius: f32) -> Option<PickResult> {
+        let ax = Figure::from_raw(self.figure_ptr).current_axes();
+        let viewport = ax.viewport();
+        let revision = unsafe { ffi::mpl_series_revision() };
+        if self.pick_index.as_ref().is_none_or(|(index, seen)| *index.viewport() != viewport || *seen != revision) {
+            let data: Vec<_> = ax.series_ids().into_iter().map(|id| (id, ax.series_points(id))).collect();
+            let series: Vec<_> = data.iter().map(|(id, (x, y))| (*id, x.as_slice(), y.as_slice())).collect();
+            self.pick_index = Some((PointIndex::new(&series, viewport), revision));
+        }
+        let hit = self.pick_index.as_ref()?.0.nearest(px, radius);
+        let point = hit.map(|h| (h.series_id, h.index));
+        if let Some(hit) = hit.filter(|_| point != self.picked) {
+            if self.events.is_watched() {
+                self.events.emit(PlotEvent::SeriesPicked(hit));
+            }
+        }
+        self.picked = point;
+        hit
+    }
+
+    /// Shows (or with `None` hides) a tooltip over the current axes,
+    /// drawn above everything else (see [`tooltip`](crate::tooltip)).
+    pub fn set_tooltip(&mut self, tooltip: Option<Tooltip>) {
+        if self.events.is_watched() && tooltip != self.tooltip {
+            self.events.emit(PlotEvent::TooltipShown(tooltip.clone()));
+        }
+        self.tooltip = tooltip;
+    }
+
+    /// The tooltip shown, if any.
+    pub fn tooltip(&self) -> Option<&Tooltip> {
+        self.tooltip.as_ref()
+    }
+
+    /// Shows series `id` of the current axes if hidden and hides it
+    /// otherwise, as a click on its legend entry does in matplotlib.
+    /// Returns whether it is now shown, or `None` if it is not in the
+    /// current axes.
+    pub fn toggle_series(&mut self, id: SeriesId) -> Option<bool> {
+        let ax = Figure::from_raw(self.figure_ptr).current_axes();
+        let visible = !ax.is_visible(id)?;
+        ax.set_visible(id, visible);
+        if self.events.is_watched() {
+            self.events.emit(PlotEvent::LegendToggled { id, visible });
+        }
+        Some(visible)
+    }
+
+    /// Starts a rubber-band zoom at pixel `at`, e.g. on a mouse press
+    /// (see [`boxzoom`](crate::boxzoom)).
+    pub fn begin_box_zoom(&mut self, at: Vec2) {
+        self.box_zoom = Some(BoxZoom::new(at));
+    }
+
+    /// Moves the dragged corner of the rubber band to pixel `at`; the
+    /// selection is drawn over the figure until committed or cancelled.
+    pub fn update_box_zoom(&mut self, at: Vec2) {
+        if let Some(zoom) = self.box_zoom.as_mut() {
+            zoom.end = at;
+        }
+    }
+
+    /// Ends the rubber-band zoom, setting the current axes' limits to
+    /// the data under the selection.  Returns `false`, leaving the
+    /// limits alone, when no zoom was started or the selection is too
+    /// small to be a drag (see [`MIN_DRAG`](crate::boxzoom::MIN_DRAG)).
+    pub fn commit_box_zoom(&mut self) -> bool {
+        let Some(zoom) = self.box_zoom.take() else { return false };
+        let ax = self.figure().current_axes();
+        let Some(r) = zoom.data_rect(&ax.viewport()) else { return false };
+        ax.set_xlim(r.x_min, r.x_max);
+        ax.set_ylim(r.y_min, r.y_max);
+        true
+    }
+
+    /// Drops the rubber band without zooming.
+    pub fn cancel_box_zoom(&mut self) {
+        self.box_zoom = None;
+    }
+
+    /// The rubber band being dragged, if any.
+    pub fn box_zoom(&self) -> Option<&BoxZoom> {
+        self.box_zoom.as_ref()
+    }
+
+    /// Returns the current axes to limits fitted to the data, undoing
+    /// box zooms and other explicit limits.
+    pub fn zoom_home(&mut self) {
+        self.box_zoom = None;
+        self.figure().current_axes().autoscale();
+    }
+
+    /// Fits the current axes' Y limits to the points of `autoscale`'s
+    /// series inside the X limits, recomputed whenever the X limits
+    /// change (zoom, pan, follow mode).  `None` turns it off.
+    pub fn set_visible_y_autoscale(&mut self, autoscale: Option<VisibleYAutoscale>) {
+        self.y_autoscale = autoscale;
+    }
+
+    /// The installed visible-window Y autoscaler.
+    pub fn visible_y_autoscale_mut(&mut self) -> Option<&mut VisibleYAutoscale> {
+        self.y_autoscale.as_mut()
+    }
+
+    /// Streams into series `id` of the current axes keeping only the
+    /// newest `window` points, or all for `None`.  Resets the limits
+    /// tracked for it by [`append`](Self::append).
+    pub fn set_stream_window(&mut self, id: SeriesId, window: Option<usize>) {
+        let key = self.figure().current_axes().key();
+        self.streams.entry(key).or_default().insert(id, Stream::new(window));
+    }
+
+    /// Appends `points` to series `id` of the current axes, within its
+    /// window from [`set_stream_window`](Self::set_stream_window).
+    /// Only the new points are sent, and with stream autoscale on (the
+    /// default) the X and Y limits are fitted to the points appended to
+    /// the streams of the current axes, from ranges kept up to date per
+    /// point instead of
+    /// rescanning the series.  Returns `false` if the series no longer
+    /// exists.
+    ///
+    /// ```rust,no_run
+    /// # fn demo(backend: &mut mpl_wgpu::plotting::PlotBackend, t: f64, v: f64) {
+    /// let id = backend.figure().current_axes().plot(&[], &[], "-").id();
+    /// backend.set_stream_window(id, Some(600));
+    /// // Once per frame:
+    /// backend.append(id, &[(t, v)]);
+    /// # }
+    /// ```
+    pub fn append(&mut self, id: SeriesId, points: &[(f64, f64)]) -> bool {
+        let ax = Figure::from_raw(self.figure_ptr).current_axes();
+        let streams = self.streams.entry(ax.key()).or_default();
+        let stream = streams.entry(id).or_default();
+        let (x, y): (Vec<f64>, Vec<f64>) = points.iter().copied().unzip();
+        if !ax.append_series(id, &x, &y, stream.window()) {
+            streams.remove(&id);
+            return false;
+        }
+        stream.push(points);
+        if self.stream_autoscale {
+            self.fit_streams(&ax);
+        }
+        true
+    }
+
+    /// Turns fitting the limits to streamed points on or off.
+    pub fn set_stream_autoscale(&mut self, on: bool) {
+        self.stream_autoscale = on;
+    }
+
+    /// Fits `ax` to the windows of its streams: tight in X so the
+    /// newest point sits at the right edge, padded in Y.
+    fn fit_streams(&self, ax: &Axes<'_>) {
+        let Some(streams) = self.streams.get(&ax.key()) else { return };
+        let union = |a: Option<(f64, f64)>, b: Option<(f64, f64)>| match (a, b) {
+            (Some(a), Some(b)) => Some((a.0.min(b.0), a.1.max(b.1))),
+            (a, b) => a.or(b),
+        };
+        let x = streams.values().fold(None, |acc, s| union(acc, s.x.range()));
+        let y = streams.values().fold(None, |acc, s| union(acc, s.y.range()));
+        if let Some(x) = x {
+            let (x0, x1) = autoscale::sanitize_range(x);
+            ax.set_xlim(x0, x1);
+        }
+        if let Some(y) = y {
+            let (y0, y1) = autoscale::pad_range(y, autoscale::DEFAULT_MARGIN);
+            ax.set_ylim(y0, y1);
+        }
+    }
+
+    /// Places and labels the ticks of every axes labelled with
+    /// [`Axes::format_xticks`] and the like for their final limits,
+    /// where the limits or the axes box moved since the last render.
+    fn relabel_ticks(&mut self) {
+        let locale = self.locale.as_ref();
+        for t in self.tick_labels.get_mut() {
+            let ax = t.axes();
+            let pixels = ax.pixel_box();
+            let mut placed = t.placed;
+            for (i, limits) in [ax.xlim(), ax.ylim()].into_iter().enumerate() {
+                let Some(labels) = &t.labels[i] else { continue };
+                if placed[i] != Some((limits, pixels)) {
+                    labels.place(&ax, i, limits, locale);
+                    placed[i] = Some((limits, pixels));
+                }
+            }
+            t.placed = placed;
+        }
+    }
+
+    /// Passes the ticks matplot++ places on the current axes through
+    /// the tick adjuster, if any, for one frame.  Axes whose ticks were
+    /// set, such as temporal or formatted ones, keep them.  Returns
+    /// which of the X and Y ticks were adjusted, to hand back to
+    /// matplot++ with [`release_ticks`](Self::release_ticks) once the
+    /// frame is drawn.
+    fn adjust_ticks(&mut self) -> [bool; 2] {
+        let Some(adjuster) = self.tick_adjuster.as_deref_mut() else { return [false; 2] };
+        let ax = Figure::from_raw(self.figure_ptr).current_axes();
+        let mut adjusted = [false; 2];
+        let axes = [(TickAxis::X, b'x', ax.xlim()), (TickAxis::Y, b'y', ax.ylim())];
+        for (i, (axis, name, limits)) in axes.into_iter().enumerate() {
+            let (placed, automatic) = ax.ticks(name);
+            if !automatic {
+                continue;
+            }
+            // Ticks left over from the last adjustment are not
+            // matplot++'s; start from what it placed for these limits.
+            let memo = &mut self.tick_memos[i];
+            let base = match memo {
+                Some(m) if placed == m.adjusted && m.limits == limits => m.placed.clone(),
+                Some(m) if placed == m.adjusted => Vec::new(),
+                _ => placed,
+            };
+            let ticks = ticks::adjust_placed_ticks(axis, limits, base.clone(), ticks::DEFAULT_MAX_TICKS, adjuster);
+            match name {
+                b'x' => ax.set_xticks(&ticks),
+                _ => ax.set_yticks(&ticks),
+            }
+            *memo = Some(TickMemo { limits, placed: base, adjusted: ticks });
+            adjusted[i] = true;
+        }
+        adjusted
+    }
+
+    /// Hands the ticks set by [`adjust_ticks`](Self::adjust_ticks) back
+    /// to matplot++.
+    fn release_ticks(&self, adjusted: [bool; 2]) {
+        let ax = self.figure().current_axes();
+        for (name, adjusted) in [b'x', b'y'].into_iter().zip(adjusted) {
+            if adjusted {
+                ax.set_ticks_auto(name);
+            }
+        }
+    }
+
+    /// Sends the current axes' limits and camera to their watchers, and
+    /// limit changes to the event subscribers, if they changed.
+    fn publish_view(&mut self) {
+        if !(self.limit_watch.is_watched() || self.camera_watch.is_watched() || self.events.is_watched()) {
+            return;
+        }
+        let ax = Figure::from_raw(self.figure_ptr).current_axes();
+        if self.limit_watch.is_watched() || self.events.is_watched() {
+            let limits = ax.limits();
+            if self.limit_watch.is_watched() {
+                self.limit_watch.publish(limits);
+            }
+            if self.events.is_watched() {
+                self.events.limits(limits);
+            }
+        }
+        if self.camera_watch.is_watched() {
+            self.camera_watch.publish(ax.view());
+        }
+    }
+
+    /// Points each 3D axes with a camera along it and sets its limits
+    /// to what the camera frames.
+    fn apply_cameras(&self) {
+        for c in &self.cameras {
+            let ax = c.axes();
+            if !ax.is_3d() {
+                continue;
+            }
+            if ax.view() != c.camera.view() {
+                ax.set_view(c.camera.azimuth, c.camera.elevation);
+            }
+            if let Some(limits) = c.camera.axes_limits(c.home) {
+                if ax.limits() != limits {
+                    ax.set_xlim(limits.x.0, limits.x.1);
+                    ax.set_ylim(limits.y.0, limits.y.1);
+                    ax.set_zlim(limits.z.0, limits.z.1);
+                }
+            }
+        }
+    }
+
+    /// Applies the visible-window Y autoscale if the X limits moved.
+    fn fit_visible_y(&mut self) {
+        let Some(autoscale) = self.y_autoscale.as_mut() else { return };
+        let _span = trace_span!("auto_scale");
+        let ax = Figure::from_raw(self.figure_ptr).current_axes();
+        if let Some((lo, hi)) = autoscale.update(ax.xlim()) {
+            ax.set_ylim(lo, hi);
+        }
+    }
+
+    /// Widens the current axes' autoscaled limits to cover every
+    /// artist's extent for one frame.  Limits set by the user are left
+    /// alone.  Returns which of the X and Y limits were fixed, to hand
+    /// back to autoscaling with [`release_artist_fit`](Self::release_artist_fit)
+    /// once the frame is drawn.
+    fn fit_artists(&self, warnings: &mut Vec<RenderWarning>) -> (bool, bool) {
+        let Some(extent) = artist::combined_extent(&self.artists, warnings) else { return (false, false) };
+        let ax = self.figure().current_axes();
+        let (x_auto, y_auto) = ax.limits_auto();
+        let data = ax.data_extent();
+        if !(x_auto || y_auto) || data.is_some_and(|d| d.contains(&extent)) {
+            return (false, false);
+        }
+        let fit = data.map_or(extent, |d| d.union(&extent));
+        if x_auto {
+            let (x0, x1) = autoscale::sanitize_range((fit.x_min, fit.x_max));
+            ax.set_xlim(x0, x1);
+        }
+        if y_auto {
+            let (y0, y1) = autoscale::sanitize_range((fit.y_min, fit.y_max));
+            ax.set_ylim(y0, y1);
+        }
+        (x_auto, y_auto)
+    }
+
+    /// Returns the limits fixed by [`fit_artists`](Self::fit_artists)
+    /// to autoscaling.
+    fn release_artist_fit(&self, (x, y): (bool, bool)) {
+        if !(x || y) {
+            return;
+        }
+        let ax = self.figure().current_axes();
+        let (x_auto, y_auto) = ax.limits_auto();
+        ax.set_limits_auto(x_auto || x, y_auto || y);
+    }
+
+    /// Draws the underlay artists; call before drawing the figure.
+    fn draw_underlays(&self, prim: Option<&mut PrimitiveRenderer>, record: Option<&mut Recording>, warnings: &mut Vec<RenderWarning>) {
+        if !self.artists.iter().any(|a| a.underlay()) {
+            return;
+        }
+        let viewport = self.figure().current_axes().viewport();
+        let (transform, sizes, metrics) = unsafe { ((*self.ctx_ptr).transform, (*self.ctx_ptr).sizes, (*self.ctx_ptr).metrics.as_ref()) };
+        let mut ctx = DrawContext::new(prim, None, record, transform, sizes, viewport)
+            .with_metrics(metrics)
+            .with_locale(self.locale());
+        artist::draw_underlays(&self.artists, &mut ctx, warnings);
+    }
+
+    /// Draws the artists over the current axes.
+    fn draw_artists(
+        &self,
+        prim: Option<&mut PrimitiveRenderer>,
+        text: Option<&mut TextRenderer>,
+        record: Option<&mut Recording>,
+        warnings: &mut Vec<RenderWarning>,
+    ) {
+        if self.artists.is_empty() && self.probe.is_none() && self.box_zoom.is_none() && self.tooltip.is_none() {
+            return;
+        }
+        let viewport = self.figure().current_axes().viewport();
+        let (transform, sizes, metrics) = unsafe { ((*self.ctx_ptr).transform, (*self.ctx_ptr).sizes, (*self.ctx_ptr).metrics.as_ref()) };
+        let mut ctx = DrawContext::new(prim, text, record, transform, sizes, viewport)
+            .with_metrics(metrics)
+            .with_locale(self.locale());
+        artist::draw_artists(&self.artists, &mut ctx, warnings);
+        if let Some(probe) = &self.probe {
+            probe.draw(&mut ctx);
+        }
+        if let Some(zoom) = &self.box_zoom {
+            zoom.draw(&mut ctx);
+        }
+        if let Some(tooltip) = &self.tooltip {
+            tooltip.draw(&mut ctx);
+        }
+    }
+
+    /// Queues the figure into `prim` and `text`, optionally transformed
+    /// by `target`.
+    ///
+    /// Without a target, 3D axes are viewed from their
+    /// [`camera_mut`](Self::camera_mut), or else at matplotlib's default
+    /// angles.
+    ///
+    /// Artists that panic while drawing are skipped, and artists with a
+    /// non-finite extent are left out of the axis limits.  Batches of
+    /// figure primitives that panic while being queued are dropped, and
+    /// an exception in matplot++ ends the figure where it was thrown.
+    /// The rest is still drawn and the problems are returned (see also
+    /// [`PlotBackend::warnings`]).
+    pub fn render(&mut self, prim: &mut PrimitiveRenderer, text: &mut TextRenderer, target: Option<Mat4>) -> Vec<RenderWarning> {
+        self.render_into(prim, text, target, None)
+    }
+
+    /// [`render`](Self::render), limited to the primitives of `layer`
+    /// if given.
+    fn render_into(&mut self, prim: &mut PrimitiveRenderer, text: &mut TextRenderer, target: Option<Mat4>, layer: Option<Layer>) -> Vec<RenderWarning> {
+        let mut warnings = Vec::new();
+        self.fit_visible_y();
+        let fitted = self.fit_artists(&mut warnings);
+        if target.is_none() {
+            self.apply_cameras();
+        }
+        self.relabel_ticks();
+        let adjusted = self.adjust_ticks();
+        self.publish_view();
+        self.pick_index = None;
+        self.read_probe_series();
+        let shows = |l: Layer| layer.is_none_or(|layer| layer == l);
+        unsafe { (*self.ctx_ptr).transform = target.unwrap_or(Mat4::IDENTITY); }
+        if shows(Layer::Background) {
+            self.draw_underlays(Some(&mut *prim), None, &mut warnings);
+        }
+        if layer != Some(Layer::Overlay) {
+            unsafe {
+                (*self.ctx_ptr).prim = prim as *mut _;
+                (*self.ctx_ptr).text = text as *mut _;
+                // draw() triggers the full matplotplusplus pipeline:
+                //   new_frame() -> send_draw_commands() -> render_data()
+                // which populates primitives and flushes them via callbacks.
+                let _span = trace_span!("tessellate");
+                match layer {
+                    Some(layer) => self.draw_figure_layer(layer, &mut warnings),
+                    None => self.draw_figure(&mut warnings),
+                }
+                trace_event!(instances = prim.instance_count(), texts = text.queued_count());
+                (*self.ctx_ptr).prim = std::ptr::null_mut();
+                (*self.ctx_ptr).text = std::ptr::null_mut();
+            }
+        }
+        if shows(Layer::Overlay) {
+            self.draw_artists(Some(prim), Some(text), None, &mut warnings);
+        }
+        self.release_artist_fit(fitted);
+        self.release_ticks(adjusted);
+        self.warnings = warnings.clone();
+        warnings
+    }
+
+    /// Runs the matplot++ pipeline into the renderers set in the
+    /// callback context, adding the panics caught in callbacks and any
+    /// exception that stopped the figure to `warnings`.
+    fn draw_figure(&mut self, warnings: &mut Vec<RenderWarning>) {
+        let error = unsafe { ffi::mpl_figure_draw(self.figure_ptr) };
+        warnings.append(unsafe { &mut (*self.ctx_ptr).warnings });
+        if !error.is_null() {
+            let message = unsafe { CStr::from_ptr(error) }.to_string_lossy().into_owned();
+            warnings.push(RenderWarning::FigureFailed { message });
+        }
+    }
+
+    /// Runs the matplot++ pipeline for the figure primitives of `layer`:
+    /// backgrounds, axes, grid and annotations for
+    /// [`Layer::Background`], and the series of the other layers (see
+    /// [`set_series_dynamic`](Self::set_series_dynamic)).
+    fn draw_figure_layer(&mut self, layer: Layer, warnings: &mut Vec<RenderWarning>) {
+        let dynamic: Vec<u64> = self.dynamic_series().into_iter().map(|id| id.0).collect();
+        let (decorations, listed) = match layer {
+            Layer::Background => (true, true),
+            Layer::Static => (false, false),
+            Layer::Dynamic => (false, true),
+            Layer::Overlay => return,
+        };
+        // The background layer lists no series, so it draws none.
+        let ids = if layer == Layer::Background { &[][..] } else { &dynamic[..] };
+        let error = unsafe { ffi::mpl_figure_draw_layer(self.figure_ptr, decorations, ids.as_ptr(), ids.len(), listed) };
+        warnings.append(unsafe { &mut (*self.ctx_ptr).warnings });
+        if !error.is_null() {
+            let message = unsafe { CStr::from_ptr(error) }.to_string_lossy().into_owned();
+            warnings.push(RenderWarning::FigureFailed { message });
+        }
+    }
+
+    /// Series in [`Layer::Dynamic`]: the streamed ones (see
+    /// [`append`](Self::append)) and those moved there, less those
+    /// moved out.
+    fn dynamic_series(&self) -> Vec<SeriesId> {
+        let streamed = self.streams.values().flat_map(|s| s.keys().copied());
+        let mut ids: Vec<SeriesId> = streamed.filter(|id| self.dynamic_series.get(id) != Some(&false)).collect();
+        ids.extend(self.dynamic_series.iter().filter(|(_, &dynamic)| dynamic).map(|(&id, _)| id));
+        ids.sort_by_key(|id| id.0);
+        ids.dedup();
+        ids
+    }
+
+    /// Moves series `id` into (or with `false` out of)
+    /// [`Layer::Dynamic`] for [`render_layer`](Self::render_layer).
+    /// Series start in [`Layer::Static`], except those fed through
+    /// [`append`](Self::append), which start dynamic.
+    pub fn set_series_dynamic(&mut self, id: SeriesId, dynamic: bool) {
+        self.dynamic_series.insert(id, dynamic);
+    }
+
+    /// Problems met by the last render or recording of the figure.
+    pub fn warnings(&self) -> &[RenderWarning] {
+        &self.warnings
+    }
+
+    /// Re-renders the part of the figure in `layer` into a
+    /// [`LayerCompositor`], replacing the layer's previous contents.
+    /// Other layers keep their cached textures.
+    ///
+    /// [`Layer::Background`] holds the underlays, backgrounds, axes,
+    /// grid and annotations, [`Layer::Static`] and [`Layer::Dynamic`]
+    /// the series (see [`set_series_dynamic`](Self::set_series_dynamic)),
+    /// and [`Layer::Overlay`] the artists, probe, box zoom and tooltip.
+    /// Axes with automatic limits draw every series once to settle them;
+    /// fix the limits to redraw a layer without touching the others.
+    pub fn render_layer(&mut self, layers: &mut LayerCompositor, layer: Layer, target: Option<Mat4>) -> Vec<RenderWarning> {
+        layers.begin(layer);
+        let (prim, text) = layers.renderers(layer);
+        self.render_into(prim, text, target, Some(layer))
+    }
+
+    /// Runs the matplot++ pipeline without a GPU renderer and returns
+    /// the emitted screen-space primitives.
+    ///
+    /// Pass the [`TextRenderer`] used for on-screen rendering so text
+    /// is measured the same way; without one, a width estimate is used.
+    pub fn record(&mut self, text: Option<&mut TextRenderer>) -> Recording {
+        self.record_with(text, None)
+    }
+
+    /// Lays the figure out without a GPU: where the axes box, every
+    /// text label and every artist end up on screen (see [`bounds`]).
+    ///
+    /// Text is measured with `metrics`, which should hold the fonts
+    /// used for rendering; without it, widths are estimated.
+    pub fn layout(&mut self, metrics: Option<&TextMetrics>) -> FigureLayout {
+        let recording = self.record_with(None, metrics);
+        let ax = self.figure().current_axes();
+        let sizes = unsafe { (*self.ctx_ptr).sizes };
+        let artists = bounds::artist_boxes(&self.artists, Mat4::IDENTITY, sizes, ax.viewport(), metrics);
+        FigureLayout {
+            width: self.width,
+            height: self.height,
+            axes: ax.pixel_box(),
+            texts: bounds::text_boxes(&recording, metrics),
+            artists,
+            recording,
+        }
+    }
+
+    fn record_with(&mut self, mut text: Option<&mut TextRenderer>, metrics: Option<&TextMetrics>) -> Recording {
+        let mut warnings = Vec::new();
+        self.fit_visible_y();
+        let fitted = self.fit_artists(&mut warnings);
+        self.apply_cameras();
+        self.relabel_ticks();
+        let adjusted = self.adjust_ticks();
+        self.publish_view();
+        self.pick_index = None;
+        self.read_probe_series();
+        let mut recording = Recording::new(self.width, self.height);
+        unsafe {
+            (*self.ctx_ptr).transform = Mat4::IDENTITY;
+            (*self.ctx_ptr).metrics = metrics.map_or(std::ptr::null(), |m| m as *const _);
+        }
+        self.draw_underlays(None, Some(&mut recording), &mut warnings);
+        unsafe {
+            (*self.ctx_ptr).text = text.as_deref_mut().map_or(std::ptr::null_mut(), |t| t as *mut _);
+            (*self.ctx_ptr).record = &mut recording as *mut _;
+            (*self.ctx_ptr).transform = Mat4::IDENTITY;
+            let _span = trace_span!("tessellate");
+            self.draw_figure(&mut warnings);
+            trace_event!(primitives = recording.primitives.len());
+            (*self.ctx_ptr).text = std::ptr::null_mut();
+            (*self.ctx_ptr).record = std::ptr::null_mut();
+        }
+        self.draw_artists(None, text, Some(&mut recording), &mut warnings);
+        self.release_artist_fit(fitted);
+        self.release_ticks(adjusted);
+        unsafe { (*self.ctx_ptr).metrics = std::ptr::null(); }
+        self.warnings = warnings;
+        recording
+    }
+
+    /// Records the figure and writes it as a self-contained
+    /// interactive HTML file with pan/zoom and tooltips in the current
+    /// axes' data coordinates.
+    pub fn save_html<P: AsRef<Path>>(&mut self, path: P) -> std::io::Result<()> {
+        let recording = self.record(None);
+        let viewport = self.figure().current_axes().viewport();
+        crate::html::save_html(&recording, Some(&viewport), path)
+    }
+}
+
+/// Ticks of one axis before and after the tick adjuster, and the
+/// limits they were placed for.
+struct TickMemo {
+    limits: (f64, f64),
+    placed: Vec<f64>,
+    adjusted: Vec<f64>,
 }
 
+/// Orbit camera of one axes, holding a handle that keeps them alive.
+struct AxesCamera {
+    axes: *mut ffi::MplAxes,
+    key: usize,
+    camera: Camera3D,
+    /// Limits framed at the camera's default distance and target.
+    home: AxisLimits,
+}
+
+impl AxesCamera {
+    fn new(ax: Axes<'_>, camera: Camera3D) -> Self {
+        let (key, home) = (ax.key(), ax.limits());
+        let axes = ax.ptr;
+        std::mem::forget(ax);
+        Self { axes, key, camera, home }
+    }
+
+    /// Borrowed handle to the axes.
+    fn axes(&self) -> std::mem::ManuallyDrop<Axes<'_>> {
+        std::mem::ManuallyDrop::new(Axes { ptr: self.axes, locale: None, tick_labels: None, owner: PhantomData })
+    }
+}
+
+impl Drop for AxesCamera {
+    fn drop(&mut self) {
+        unsafe { ffi::mpl_axes_destroy(self.axes); }
+    }
+}
+
+/// Pixel margins of one axes, held like [`AxesCamera`].
+struct AxesMargins {
+    axes: *mut ffi::MplAxes,
+    key: usize,
+    /// `[left, right, top, bottom]` in pixels.
+    margins: [f32; 4],
+}
+
+impl AxesMargins {
+    fn new(ax: Axes<'_>, margins: [f32; 4]) -> Self {
+        let key = ax.key();
+        let axes = ax.ptr;
+        std::mem::forget(ax);
+        Self { axes, key, margins }
+    }
+
+    /// Borrowed handle to the axes.
+    fn axes(&self) -> std::mem::ManuallyDrop<Axes<'_>> {
+        std::mem::ManuallyDrop::new(Axes { ptr: self.axes, locale: None, tick_labels: None, owner: PhantomData })
+    }
+}
+
+impl Drop for AxesMargins {
+    fn drop(&mut self) {
+        unsafe { ffi::mpl_axes_destroy(self.axes); }
+    }
+}
+
+/// How the ticks of one axis are placed and labelled on every render.
+enum TickLabels {
+    /// Round ticks labelled with `formatter`, relative to their
+    /// [`OffsetScale`] if `offset` is set.
+    Format { formatter: Box<dyn TickFormatter + Send>, offset: bool },
+    /// Calendar ticks on `zone`'s wall clock, with dates written in
+    /// `locale`, or else in the figure's locale if one was set.
+    Temporal { zone: Box<dyn temporal::DisplayZone + Send>, locale: Option<Locale> },
+}
+
+impl TickLabels {
+    /// Places and labels ticks over `limits` on axis `i` (0 for X, 1
+    /// for Y) of `ax`, in a figure with `locale`.  Returns the offset
+    /// scale of the labels.
+    fn place(&self, ax: &Axes<'_>, i: usize, limits: (f64, f64), locale: Option<&Locale>) -> OffsetScale {
+        match self {
+            TickLabels::Format { formatter, offset } => {
+                let (ticks, step) = ticks::nice_ticks(limits.0, limits.1, ticks::DEFAULT_MAX_TICKS);
+                let (scale, labels) = if *offset {
+                    let scale = OffsetScale::for_ticks(&ticks);
+                    (scale, scale.labels(&ticks, step, formatter.as_ref()))
+                } else {
+                    (OffsetScale::default(), ticks::labels(&ticks, step, formatter.as_ref()))
+                };
+                if i == 0 {
+                    ax.fit_xticklabels(&ticks, &labels);
+                } else {
+                    ax.place_ticks(b'y', &ticks, &labels);
+                }
+                scale
+            }
+            TickLabels::Temporal { zone, locale: own } => {
+                let (min, max, n) = (limits.0, limits.1, temporal::DEFAULT_MAX_TICKS);
+                let (ticks, labels) = match own.as_ref().or(locale) {
+                    Some(locale) => temporal::ticks_localized(min, max, n, zone.as_ref(), locale),
+                    None => temporal::ticks_in(min, max, n, zone.as_ref()),
+                };
+                ax.fit_xticklabels(&ticks, &labels);
+                OffsetScale::default()
+            }
+        }
+    }
+}
+
+/// Tick labels of one axes, held like [`AxesCamera`].
+struct AxesTicks {
+    axes: *mut ffi::MplAxes,
+    key: usize,
+    /// X and Y labels.
+    labels: [Option<TickLabels>; 2],
+    /// Limits and pixel box each axis was last placed for.
+    placed: [Option<((f64, f64), [f32; 4])>; 2],
+}
+
+impl AxesTicks {
+    fn new(ax: &Axes<'_>, i: usize, labels: TickLabels) -> Self {
+        let mut ticks = Self {
+            axes: unsafe { ffi::mpl_axes_clone(ax.ptr) },
+            key: ax.key(),
+            labels: [None, None],
+            placed: [None, None],
+        };
+        ticks.labels[i] = Some(labels);
+        ticks
+    }
+
+    /// Borrowed handle to the axes.
+    fn axes(&self) -> std::mem::ManuallyDrop<Axes<'_>> {
+        std::mem::ManuallyDrop::new(Axes { ptr: self.axes, locale: None, tick_labels: None, owner: PhantomData })
+    }
+}
+
+impl Drop for AxesTicks {
+    fn drop(&mut self) {
+        unsafe { ffi::mpl_axes_destroy(self.axes); }
+    }
+}
+
+// SAFETY: the figure, the backend and the callback context are owned
+// exclusively by this value and only touched through it; the renderer
+// pointers in the context are null outside `render` and `record`.
+// Handles into the figure (`Figure`, `Axes`, `Series`) are `!Send`
+// and borrow the backend, so none is alive once it has moved to
+// another thread.  `Sync` is not implemented: `figure()` takes `&self`
+// and its handles mutate the figure, so threads sharing a
+// `&PlotBackend` would race; `FigureHandle` serializes them instead.
+unsafe impl Send for PlotBackend {}
+
 impl Drop for PlotBackend {
     fn drop(&mut self) {
         unsafe {
@@ -0,0 +1,336 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Point clouds.
+//!
+//! Drawing a 3D scatter of millions of points through
+//! [`PrimitiveRenderer`](crate::primitives::PrimitiveRenderer) costs an
+//! 80-byte [`Instance`](crate::primitives::Instance) per point, re-sorted
+//! and re-uploaded every frame.  A [`PointCloudRenderer`] keeps the
+//! points on the GPU instead, as 16-byte [`CloudPoint`]s (position and
+//! RGBA8 color), and draws each as a screen-aligned square or round
+//! sprite of a fixed pixel size.
+//!
+//! Points are uploaded in chunks of [`CHUNK_POINTS`], each its own
+//! vertex buffer: [`extend`](PointCloudRenderer::extend) only writes the
+//! new points, so a cloud can be streamed in without re-uploading what
+//! is already there, and no single buffer approaches the device's size
+//! limit.  Draw the cloud into an existing pass with
+//! [`render`](PointCloudRenderer::render).
+
+use bytemuck::{Pod, Zeroable};
+use glam::{Mat4, Vec3, Vec4};
+
+/// Points per vertex buffer (4 MiB).
+pub const CHUNK_POINTS: usize = 1 << 18;
+
+/// Size of the uniform block: view projection, screen size, point
+/// size and sprite shape.
+const UNIFORM_SIZE: u64 = 64 + 16;
+
+/// One point: position and packed RGBA8 color, 16 bytes.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Pod, Zeroable)]
+pub struct CloudPoint {
+  /// Position, in pixels under an identity view projection and world
+  /// units otherwise.
+  pub position: [f32; 3],
+  /// Color packed by [`pack_color`].
+  pub color: u32,
+}
+
+impl CloudPoint {
+  /// Point at `position` in `color` (RGBA in `[0, 1]`).
+  pub fn new(position: Vec3, color: Vec4) -> Self {
+    Self {
+      position: position.to_array(),
+      color: pack_color(color),
+    }
+  }
+}
+
+/// Packs RGBA in `[0, 1]` into bytes `r, g, b, a` in memory order, read
+/// back by the shader as normalized values.
+pub fn pack_color(color: Vec4) -> u32 {
+  let [r, g, b, a] = color
+    .to_array()
+    .map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+  u32::from_le_bytes([r, g, b, a])
+}
+
+/// Draws large point clouds from chunked GPU buffers.
+pub struct PointCloudRenderer {
+  pipeline: wgpu::RenderPipeline,
+  bind_group: wgpu::BindGroup,
+  uniform_buffer: wgpu::Buffer,
+  chunks: Vec<wgpu::Buffer>,
+  len: usize,
+  view_proj: Mat4,
+  screen_size: [f32; 2],
+  point_size: f32,
+  round: bool,
+}
+
+impl PointCloudRenderer {
+  /// Renderer for a `width` x `height` target of `format`, drawing
+  /// 2 px round points in pixel coordinates until a view projection is
+  /// set.
+  pub fn new(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    format: wgpu::TextureFormat,
+    width: u32,
+    height: u32,
+  ) -> Self {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+      label: Some("PointCloudShader"),
+      source: wgpu::ShaderSource::Wgsl(include_str!("pointcloud.wgsl").into()),
+    });
+
+    let bind_group_layout =
+      device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("PointCloudBindGroup"),
+        entries: &[wgpu::BindGroupLayoutEntry {
+          binding: 0,
+          visibility: wgpu::ShaderStages::VERTEX_FRAGMENT,
+          ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+          },
+          count: None,
+        }],
+      });
+
+    let pipeline_layout =
+      device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: None,
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+      });
+
+    let pipeline =
+      device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("PointCloudPipeline"),
+        layout: Some(&pipeline_layout),
+        vertex: wgpu::VertexState {
+          module: &shader,
+          entry_point: "vs_main",
+          buffers: &[wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<CloudPoint>()
+              as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &wgpu::vertex_attr_array![
+              0 => Float32x3, // position
+              1 => Unorm8x4   // color
+            ],
+          }],
+          compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+          module: &shader,
+          entry_point: "fs_main",
+          targets: &[Some(wgpu::ColorTargetState {
+            format,
+            blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+            write_mask: wgpu::ColorWrites::ALL,
+          })],
+          compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+      });
+
+    let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+      label: Some("PointCloudUniforms"),
+      size: UNIFORM_SIZE,
+      usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+      mapped_at_creation: false,
+    });
+
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+      label: None,
+      layout: &bind_group_layout,
+      entries: &[wgpu::BindGroupEntry {
+        binding: 0,
+        resource: uniform_buffer.as_entire_binding(),
+      }],
+    });
+
+    let renderer = Self {
+      pipeline,
+      bind_group,
+      uniform_buffer,
+      chunks: Vec::new(),
+      len: 0,
+      view_proj: Mat4::IDENTITY,
+      screen_size: [width as f32, height as f32],
+      point_size: 2.0,
+      round: true,
+    };
+    renderer.update_uniforms(queue);
+    renderer
+  }
+
+  /// Updates the target size.
+  pub fn resize(&mut self, queue: &wgpu::Queue, width: u32, height: u32) {
+    self.screen_size = [width as f32, height as f32];
+    self.update_uniforms(queue);
+  }
+
+  /// Sets the view projection; identity draws in pixel coordinates.
+  pub fn set_view_projection(&mut self, queue: &wgpu::Queue, matrix: Mat4) {
+    self.view_proj = matrix;
+    self.update_uniforms(queue);
+  }
+
+  /// Sets the point diameter in pixels, the same at every depth.
+  pub fn set_point_size(&mut self, queue: &wgpu::Queue, size: f32) {
+    self.point_size = size;
+    self.update_uniforms(queue);
+  }
+
+  /// Draws round sprites (the default) or squares, which are cheaper to
+  /// shade.
+  pub fn set_round(&mut self, queue: &wgpu::Queue, round: bool) {
+    self.round = round;
+    self.update_uniforms(queue);
+  }
+
+  fn update_uniforms(&self, queue: &wgpu::Queue) {
+    let mut data = [0.0f32; 16 + 4];
+    data[0..16].copy_from_slice(self.view_proj.as_ref());
+    data[16] = self.screen_size[0];
+    data[17] = self.screen_size[1];
+    data[18] = self.point_size;
+    data[19] = if self.round { 1.0 } else { 0.0 };
+    queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&data));
+  }
+
+  /// Replaces the cloud with `points`, reusing the chunk buffers.
+  pub fn set_points(
+    &mut self,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    points: &[CloudPoint],
+  ) {
+    self.clear();
+    self.extend(device, queue, points);
+    self.chunks.truncate(self.len.div_ceil(CHUNK_POINTS));
+  }
+
+  /// Appends `points`, uploading only them and allocating chunks as
+  /// needed.
+  pub fn extend(
+    &mut self,
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    points: &[CloudPoint],
+  ) {
+    let point_bytes = std::mem::size_of::<CloudPoint>();
+    let mut rest = points;
+    while !rest.is_empty() {
+      let (chunk, offset) = (self.len / CHUNK_POINTS, self.len % CHUNK_POINTS);
+      if chunk == self.chunks.len() {
+        self.chunks.push(device.create_buffer(&wgpu::BufferDescriptor {
+          label: Some("PointCloudChunk"),
+          size: (CHUNK_POINTS * point_bytes) as u64,
+          usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+          mapped_at_creation: false,
+        }));
+      }
+      let n = (CHUNK_POINTS - offset).min(rest.len());
+      queue.write_buffer(
+        &self.chunks[chunk],
+        (offset * point_bytes) as u64,
+        bytemuck::cast_slice(&rest[..n]),
+      );
+      self.len += n;
+      rest = &rest[n..];
+    }
+  }
+
+  /// Removes all points; the chunk buffers are kept for reuse.
+  pub fn clear(&mut self) {
+    self.len = 0;
+  }
+
+  /// Number of points uploaded.
+  pub fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Whether no points are uploaded.
+  pub fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Number of chunk buffers allocated.
+  pub fn chunk_count(&self) -> usize {
+    self.chunks.len()
+  }
+
+  /// Draws the cloud into `rp`.
+  pub fn render<'a>(&'a self, rp: &mut wgpu::RenderPass<'a>) {
+    if self.len == 0 {
+      return;
+    }
+    rp.set_pipeline(&self.pipeline);
+    rp.set_bind_group(0, &self.bind_group, &[]);
+    for (i, chunk) in self.chunks.iter().enumerate() {
+      let n = (self.len - (i * CHUNK_POINTS).min(self.len)).min(CHUNK_POINTS);
+      if n == 0 {
+        break;
+      }
+      rp.set_vertex_buffer(0, chunk.slice(..));
+      rp.draw(0..6, 0..n as u32);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::capture::{HeadlessRenderer, CAPTURE_FORMAT};
+
+  #[test]
+  fn points_pack_into_sixteen_bytes() {
+    assert_eq!(std::mem::size_of::<CloudPoint>(), 16);
+    let p = CloudPoint::new(Vec3::new(1.0, 2.0, 3.0), Vec4::new(1.0, 0.5, 0.0, 2.0));
+    assert_eq!(p.position, [1.0, 2.0, 3.0]);
+    assert_eq!(p.color.to_le_bytes(), [255, 128, 0, 255]);
+    assert_eq!(bytemuck::bytes_of(&p)[12..], [255, 128, 0, 255]);
+  }
+
+  #[test]
+  fn draws_points_in_pixel_coordinates_from_the_start() {
+    let headless = HeadlessRenderer::new(64, 48).expect("headless GPU context");
+    let (device, queue) = (headless.device(), headless.queue());
+    let mut cloud = PointCloudRenderer::new(device, queue, CAPTURE_FORMAT, 64, 48);
+    let red = Vec4::new(1.0, 0.0, 0.0, 1.0);
+    cloud.set_points(device, queue, &[CloudPoint::new(Vec3::new(16.5, 12.5, 0.0), red)]);
+    let pixels = headless
+      .read_back_with(|encoder, view| {
+        let mut rp = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+          label: None,
+          color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+              load: wgpu::LoadOp::Clear(wgpu::Color::WHITE),
+              store: wgpu::StoreOp::Store,
+            },
+          })],
+          ..Default::default()
+        });
+        cloud.render(&mut rp);
+      })
+      .expect("read back frame");
+    let at = |x: usize, y: usize| &pixels[(y * 64 + x) * 4..][..3];
+    // Only the uniforms written at creation place the 2 px point.
+    assert_eq!(at(16, 12), [255, 0, 0]);
+    assert_eq!(at(40, 30), [255, 255, 255]);
+  }
+}
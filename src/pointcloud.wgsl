@@ -0,0 +1,73 @@
+// Point cloud shader: one screen-aligned quad per point.
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    screen_size: vec2<f32>,
+    // Point diameter in pixels.
+    point_size: f32,
+    // 1 for round sprites, 0 for squares.
+    round: f32,
+};
+@group(0) @binding(0) var<uniform> globals: Uniforms;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    // Corner of the sprite in [-1, 1].
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    @location(0) position: vec3<f32>,
+    @location(1) color: vec4<f32>,
+) -> VertexOutput {
+    var out: VertexOutput;
+
+    let idx = vertex_index % 6u;
+    var corner = vec2<f32>(-1.0, -1.0);
+    if (idx == 1u || idx == 2u || idx == 4u) { corner.x = 1.0; }
+    if (idx == 2u || idx == 4u || idx == 5u) { corner.y = 1.0; }
+
+    // An identity view projection means pixel coordinates (Y down), as
+    // in the primitive shader.
+    let is_2d = abs(globals.view_proj[0][0] - 1.0) < 0.0001 &&
+                abs(globals.view_proj[1][1] - 1.0) < 0.0001 &&
+                abs(globals.view_proj[3][3] - 1.0) < 0.0001 &&
+                abs(globals.view_proj[0][1]) < 0.0001 &&
+                abs(globals.view_proj[1][0]) < 0.0001;
+
+    var clip: vec4<f32>;
+    if (is_2d) {
+        let ndc = (position.xy / globals.screen_size) * 2.0 - 1.0;
+        clip = vec4<f32>(ndc.x, -ndc.y, position.z, 1.0);
+    } else {
+        clip = globals.view_proj * vec4<f32>(position, 1.0);
+    }
+
+    // Half a pixel of margin for the antialiased edge.
+    let half_px = globals.point_size * 0.5 + 0.5;
+    let offset = corner * half_px / globals.screen_size * 2.0;
+    out.position = vec4<f32>(clip.xy + offset * clip.w, clip.z, clip.w);
+    out.uv = corner * half_px / max(globals.point_size * 0.5, 0.5);
+    out.color = color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let radius_px = max(globals.point_size * 0.5, 0.5);
+    var d: f32;
+    if (globals.round > 0.5) {
+        d = length(in.uv);
+    } else {
+        d = max(abs(in.uv.x), abs(in.uv.y));
+    }
+    // Coverage of the pixel by the sprite edge.
+    let alpha = clamp((1.0 - d) * radius_px + 0.5, 0.0, 1.0);
+    if (alpha <= 0.0) {
+        discard;
+    }
+    return vec4<f32>(in.color.rgb, in.color.a * alpha);
+}
@@ -1,4 +1,11 @@
-use glam::{Vec2, Vec3, Vec4};
+use crate::origin::FloatingOrigin;
+use crate::trace::trace_span;
+use crate::units::SizeScale;
+use glam::{DMat4, DVec3, Vec2, Vec3, Vec4};
+use image::RgbaImage;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
@@ -7,40 +14,68 @@ pub struct Instance {
     pub pos_b_width: [f32; 4],
     pub color: [f32; 4],
     pub params: [f32; 4], // prim_type, dash_len, gap_len, dash_offset
-    pub pos_c_pad: [f32; 4],
+    pub pos_c_pad: [f32; 4], // triangle vertex c; w = 1 for pixel widths in 3D
 }
 
-pub struct PrimitiveRenderer {
+/// Image quad queued with [`PrimitiveRenderer::draw_image`].
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct ImageQuad {
+    corners: [[f32; 4]; 4], // clockwise from the top-left; w unused
+    uv: [f32; 4],           // u0, v0, u1, v1
+    tint: [f32; 4],
+}
+
+/// Units of line widths and marker radii under a 3D view projection.
+///
+/// In 2D (identity view projection) coordinates are already pixels and
+/// both variants behave the same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WidthUnits {
+    /// World-space size: scales with zoom and distance.
+    #[default]
+    World,
+    /// Screen-space size in pixels, constant regardless of zoom.
+    Pixels,
+    /// Screen-space size in points, converted with the renderer's
+    /// [`SizeScale`].
+    Points,
+}
+
+/// Order in which queued instances are drawn.
+///
+/// All orders are stable: instances that compare equal keep their
+/// submission order, so blending is identical from frame to frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DrawOrder {
+    /// Exactly as submitted.
+    Submission,
+    /// Faces (triangles) first, then everything else.
+    #[default]
+    ByType,
+    /// Back to front by clip-space depth of each instance's center.
+    ByDepth,
+}
+
+/// Shader and pipelines of [`PrimitiveRenderer`], shared by all renderers
+/// for one device, target format and sample count (see
+/// [`RenderContext`](crate::context::RenderContext)).
+pub(crate) struct PrimitivePipelines {
     pipeline_depth_write: wgpu::RenderPipeline,
     pipeline_lines: wgpu::RenderPipeline,
-    bind_group: wgpu::BindGroup,
-    uniform_buffer: wgpu::Buffer,
-    instance_buffer: wgpu::Buffer,
-    instances: Vec<Instance>,
-    screen_size: Vec2,
-    view_proj: glam::Mat4,
-    capacity: usize,
+    bind_group_layout: wgpu::BindGroupLayout,
+    pipeline_images: wgpu::RenderPipeline,
+    image_layout: wgpu::BindGroupLayout,
+    image_sampler: wgpu::Sampler,
 }
 
-impl PrimitiveRenderer {
-    pub fn new(
-        device: &wgpu::Device,
-        format: wgpu::TextureFormat,
-        width: u32,
-        height: u32,
-    ) -> Self {
+impl PrimitivePipelines {
+    pub(crate) fn new(device: &wgpu::Device, format: wgpu::TextureFormat, samples: u32) -> Self {
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("PrimitiveShader"),
             source: wgpu::ShaderSource::Wgsl(include_str!("primitives.wgsl").into()),
         });
 
-        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some("PrimitiveUniforms"),
-            size: 64 + 16 + 16, // Mat4 + Vec2 + CameraPos + padding
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-            mapped_at_creation: false,
-        });
-
         let bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 label: Some("PrimitiveBindGroup"),
@@ -56,15 +91,6 @@ impl PrimitiveRenderer {
                 }],
             });
 
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            layout: &bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: uniform_buffer.as_entire_binding(),
-            }],
-            label: None,
-        });
-
         let pipeline_layout =
             device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
                 bind_group_layouts: &[&bind_group_layout],
@@ -114,7 +140,7 @@ impl PrimitiveRenderer {
                 },
                 depth_stencil: None,
                 multisample: wgpu::MultisampleState {
-                    count: 1, 
+                    count: samples,
                     mask: !0,
                     alpha_to_coverage_enabled: false,
                 },
@@ -133,13 +159,216 @@ impl PrimitiveRenderer {
                 },
                 depth_stencil: None,
                 multisample: wgpu::MultisampleState {
-                    count: 1, 
+                    count: samples,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                multiview: None,
+            });
+
+        let image_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("ImageShader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("images.wgsl").into()),
+        });
+
+        let image_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("ImageBindGroup"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
+            });
+
+        let image_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                bind_group_layouts: &[&bind_group_layout, &image_layout],
+                push_constant_ranges: &[],
+                label: None,
+            });
+
+        let pipeline_images =
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some("PrimitivePipeline_Images"),
+                layout: Some(&image_pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &image_shader,
+                    entry_point: "vs_main",
+                    buffers: &[wgpu::VertexBufferLayout {
+                        array_stride: std::mem::size_of::<ImageQuad>()
+                            as wgpu::BufferAddress,
+                        step_mode: wgpu::VertexStepMode::Instance,
+                        attributes: &wgpu::vertex_attr_array![
+                            0 => Float32x4, // top-left
+                            1 => Float32x4, // top-right
+                            2 => Float32x4, // bottom-right
+                            3 => Float32x4, // bottom-left
+                            4 => Float32x4, // uv rectangle
+                            5 => Float32x4  // tint
+                        ],
+                    }],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &image_shader,
+                    entry_point: "fs_main",
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: wgpu::PipelineCompilationOptions::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    cull_mode: None,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState {
+                    count: samples,
                     mask: !0,
                     alpha_to_coverage_enabled: false,
                 },
                 multiview: None,
             });
 
+        // Image pixels stay sharp when zoomed in.
+        let image_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("ImageSampler"),
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            pipeline_depth_write,
+            pipeline_lines,
+            bind_group_layout,
+            pipeline_images,
+            image_layout,
+            image_sampler,
+        }
+    }
+
+    /// Uploads `image` to a texture and binds it for the image pipeline.
+    /// Images past the device's texture size limit are sampled down to
+    /// fit.
+    fn upload_image(&self, device: &wgpu::Device, queue: &wgpu::Queue, image: &RgbaImage) -> wgpu::BindGroup {
+        let limit = device.limits().max_texture_dimension_2d;
+        let step = image.width().max(image.height()).div_ceil(limit).max(1);
+        let (width, height) = (image.width().div_ceil(step), image.height().div_ceil(step));
+        let pixels: Cow<[u8]> = if step == 1 {
+            Cow::Borrowed(image.as_raw())
+        } else {
+            Cow::Owned(
+                (0..height)
+                    .flat_map(|y| (0..width).flat_map(move |x| image.get_pixel(x * step, y * step).0))
+                    .collect(),
+            )
+        };
+        let size = wgpu::Extent3d { width, height, depth_or_array_layers: 1 };
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("ImageTexture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            &pixels,
+            wgpu::ImageDataLayout { offset: 0, bytes_per_row: Some(width * 4), rows_per_image: Some(height) },
+            size,
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("ImageBindGroup"),
+            layout: &self.image_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&view) },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(&self.image_sampler) },
+            ],
+        })
+    }
+}
+
+pub struct PrimitiveRenderer {
+    pipelines: Arc<PrimitivePipelines>,
+    bind_group: wgpu::BindGroup,
+    uniform_buffer: wgpu::Buffer,
+    instance_buffer: wgpu::Buffer,
+    instances: Vec<Instance>,
+    screen_size: Vec2,
+    view_proj: glam::Mat4,
+    origin: FloatingOrigin,
+    capacity: usize,
+    draw_order: DrawOrder,
+    sizes: SizeScale,
+    crisp: bool,
+    /// Images queued for the frame, drawn beneath the instances.
+    images: Vec<(Arc<RgbaImage>, ImageQuad)>,
+    image_buffer: Option<wgpu::Buffer>,
+    /// Uploaded images by address, dropped once their image is.
+    textures: HashMap<usize, (Weak<RgbaImage>, wgpu::BindGroup)>,
+}
+
+impl PrimitiveRenderer {
+    pub fn new(
+        device: &wgpu::Device,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let pipelines = Arc::new(PrimitivePipelines::new(device, format, 1));
+        Self::with_pipelines(device, pipelines, width, height)
+    }
+
+    /// Renderer drawing with existing `pipelines`; only its uniform and
+    /// instance buffers are created.
+    pub(crate) fn with_pipelines(
+        device: &wgpu::Device,
+        pipelines: Arc<PrimitivePipelines>,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("PrimitiveUniforms"),
+            size: 64 + 16 + 16, // Mat4 + Vec2 + CameraPos + padding
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &pipelines.bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+            label: None,
+        });
+
         let initial_capacity = 1024;
         let instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("InstanceBuffer"),
@@ -149,15 +378,21 @@ impl PrimitiveRenderer {
         });
 
         Self {
-            pipeline_depth_write,
-            pipeline_lines,
+            pipelines,
             bind_group,
             uniform_buffer,
             instance_buffer,
             instances: Vec::with_capacity(initial_capacity),
             screen_size: Vec2::new(width as f32, height as f32),
             view_proj: glam::Mat4::IDENTITY,
+            origin: FloatingOrigin::default(),
             capacity: initial_capacity,
+            draw_order: DrawOrder::default(),
+            sizes: SizeScale::default(),
+            crisp: false,
+            images: Vec::new(),
+            image_buffer: None,
+            textures: HashMap::new(),
         }
     }
 
@@ -171,6 +406,68 @@ impl PrimitiveRenderer {
         self.update_uniforms(queue);
     }
 
+    /// Sets the origin subtracted from world positions by the `*_world`
+    /// draw calls and [`set_world_view_projection`](Self::set_world_view_projection).
+    /// Positions passed to the `f32` calls must already be relative to it
+    /// (see [`local`](Self::local)), including the camera position.
+    pub fn set_origin(&mut self, origin: FloatingOrigin) {
+        self.origin = origin;
+    }
+
+    /// The current floating origin.
+    pub fn origin(&self) -> FloatingOrigin {
+        self.origin
+    }
+
+    /// World position `p` relative to the origin.
+    pub fn local(&self, p: DVec3) -> Vec3 {
+        self.origin.local(p)
+    }
+
+    /// Sets a world-space view projection in `f64`, folding in the
+    /// origin before it is rounded to `f32`.
+    pub fn set_world_view_projection(&mut self, queue: &wgpu::Queue, matrix: DMat4) {
+        self.set_view_projection(queue, self.origin.view_projection(matrix));
+    }
+
+    /// Sets the order used by [`prepare`](Self::prepare).
+    pub fn set_draw_order(&mut self, order: DrawOrder) {
+        self.draw_order = order;
+    }
+
+    /// Sets the point-to-pixel conversion for [`WidthUnits::Points`].
+    pub fn set_size_scale(&mut self, sizes: SizeScale) {
+        self.sizes = sizes;
+    }
+
+    /// Converts `size` in `units` to the value and shader flag stored in
+    /// an instance.
+    fn resolve_size(&self, size: f32, units: WidthUnits) -> (f32, f32) {
+        match units {
+            WidthUnits::World => (size, 0.0),
+            WidthUnits::Pixels => (size, 1.0),
+            WidthUnits::Points => (self.sizes.points_to_pixels(size), 1.0),
+        }
+    }
+
+    /// Current draw order.
+    pub fn draw_order(&self) -> DrawOrder {
+        self.draw_order
+    }
+
+    /// Snaps thin horizontal and vertical lines to the pixel grid in 2D
+    /// (identity view projection) so 1 px grid and border lines stay
+    /// sharp instead of blurring across two pixel rows.  Off by default,
+    /// since snapping moves geometry by up to half a pixel.
+    pub fn set_crisp_lines(&mut self, crisp: bool) {
+        self.crisp = crisp;
+    }
+
+    /// Whether thin lines are snapped to the pixel grid.
+    pub fn crisp_lines(&self) -> bool {
+        self.crisp
+    }
+
     pub fn set_camera_pos(&mut self, queue: &wgpu::Queue, pos: glam::Vec3) {
         let mut data = [0.0f32; 16 + 4 + 4];
         data[0..16].copy_from_slice(self.view_proj.as_ref());
@@ -268,6 +565,11 @@ impl PrimitiveRenderer {
         gap_len: f32,
         dash_offset: f32,
     ) {
+        let (start, end, thickness) = if self.crisp && self.view_proj == glam::Mat4::IDENTITY {
+            snap_line(start, end, thickness)
+        } else {
+            (start, end, thickness)
+        };
         self.instances.push(Instance {
             pos_a_radius: [start.x, start.y, start.z, thickness * 0.5],
             pos_b_width: [end.x, end.y, end.z, 0.0],
@@ -277,6 +579,68 @@ impl PrimitiveRenderer {
         });
     }
 
+    /// Solid 3D line whose `width` is interpreted in `units`.
+    pub fn draw_line_3d(
+        &mut self,
+        start: Vec3,
+        end: Vec3,
+        width: f32,
+        units: WidthUnits,
+        color: Vec4,
+    ) {
+        let (width, flag) = self.resolve_size(width, units);
+        self.instances.push(Instance {
+            pos_a_radius: [start.x, start.y, start.z, width * 0.5],
+            pos_b_width: [end.x, end.y, end.z, 0.0],
+            color: [color.x, color.y, color.z, color.w],
+            params: [2.0, 0.0, 0.0, 0.0],
+            pos_c_pad: [0.0, 0.0, 0.0, flag],
+        });
+    }
+
+    /// Like [`draw_line_3d`](Self::draw_line_3d) with `f64` world
+    /// positions, made relative to the origin before the `f32` cast.
+    pub fn draw_line_world(
+        &mut self,
+        start: DVec3,
+        end: DVec3,
+        width: f32,
+        units: WidthUnits,
+        color: Vec4,
+    ) {
+        self.draw_line_3d(self.local(start), self.local(end), width, units, color);
+    }
+
+    /// Like [`draw_point_3d`](Self::draw_point_3d) with an `f64` world
+    /// position.
+    pub fn draw_point_world(
+        &mut self,
+        center: DVec3,
+        radius: f32,
+        units: WidthUnits,
+        color: Vec4,
+    ) {
+        self.draw_point_3d(self.local(center), radius, units, color);
+    }
+
+    /// 3D circle marker whose `radius` is interpreted in `units`.
+    pub fn draw_point_3d(
+        &mut self,
+        center: Vec3,
+        radius: f32,
+        units: WidthUnits,
+        color: Vec4,
+    ) {
+        let (radius, flag) = self.resolve_size(radius, units);
+        self.instances.push(Instance {
+            pos_a_radius: [center.x, center.y, center.z, radius],
+            pos_b_width: [0.0, 0.0, 0.0, 0.0],
+            color: [color.x, color.y, color.z, color.w],
+            params: [1.0, 0.0, 0.0, 0.0],
+            pos_c_pad: [0.0, 0.0, 0.0, flag],
+        });
+    }
+
     pub fn draw_triangle_unlit(
         &mut self,
         p0: Vec3,
@@ -309,18 +673,32 @@ impl PrimitiveRenderer {
         });
     }
 
+    /// Queues `image` stretched over four corners, clockwise from the
+    /// top-left, showing its part `uv` (`[u0, v0, u1, v1]`) with the
+    /// alpha multiplied by `alpha`.  Images are drawn beneath all
+    /// instances.  Each image is uploaded to a texture once and kept
+    /// while the `Arc` is alive.
+    pub fn draw_image(&mut self, image: &Arc<RgbaImage>, corners: [Vec3; 4], uv: [f32; 4], alpha: f32) {
+        self.images.push((
+            image.clone(),
+            ImageQuad {
+                corners: corners.map(|c| c.extend(0.0).to_array()),
+                uv,
+                tint: [1.0, 1.0, 1.0, alpha],
+            },
+        ));
+    }
+
     pub fn prepare(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        let _span = trace_span!("prepare_primitives", instances = self.instances.len());
         self.update_uniforms(queue);
+        self.prepare_images(device, queue);
 
         if self.instances.is_empty() {
             return;
         }
 
-        // Sort: Faces (30, 31) first.
-        self.instances.sort_by_key(|i| {
-            let t = i.params[0] as u32;
-            t != 30 && t != 31
-        });
+        sort_instances(&mut self.instances, self.draw_order, self.view_proj);
 
         let size = (self.instances.len() * std::mem::size_of::<Instance>()) as u64;
         if size > self.instance_buffer.size() {
@@ -340,35 +718,231 @@ impl PrimitiveRenderer {
         );
     }
 
+    /// Uploads the queued images that have no texture yet and writes
+    /// their quads.
+    fn prepare_images(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        self.textures.retain(|_, (image, _)| image.strong_count() > 0);
+        for (image, _) in &self.images {
+            let key = Arc::as_ptr(image) as usize;
+            if !self.textures.contains_key(&key) {
+                let bind_group = self.pipelines.upload_image(device, queue, image);
+                self.textures.insert(key, (Arc::downgrade(image), bind_group));
+            }
+        }
+        if self.images.is_empty() {
+            return;
+        }
+        let quads: Vec<ImageQuad> = self.images.iter().map(|(_, quad)| *quad).collect();
+        let size = std::mem::size_of_val(quads.as_slice()) as u64;
+        if self.image_buffer.as_ref().is_none_or(|b| b.size() < size) {
+            self.image_buffer = Some(device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("ImageBuffer"),
+                size,
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }));
+        }
+        if let Some(buffer) = &self.image_buffer {
+            queue.write_buffer(buffer, 0, bytemuck::cast_slice(&quads));
+        }
+    }
+
     pub fn render<'a>(&'a self, rp: &mut wgpu::RenderPass<'a>) {
+        rp.set_bind_group(0, &self.bind_group, &[]);
+        if let Some(buffer) = self.image_buffer.as_ref().filter(|_| !self.images.is_empty()) {
+            rp.set_pipeline(&self.pipelines.pipeline_images);
+            rp.set_vertex_buffer(0, buffer.slice(..));
+            for (i, (image, _)) in self.images.iter().enumerate() {
+                let Some((_, texture)) = self.textures.get(&(Arc::as_ptr(image) as usize)) else { continue };
+                rp.set_bind_group(1, texture, &[]);
+                rp.draw(0..6, i as u32..i as u32 + 1);
+            }
+        }
+
         if self.instances.is_empty() {
             return;
         }
 
-        rp.set_bind_group(0, &self.bind_group, &[]);
         rp.set_vertex_buffer(0, self.instance_buffer.slice(..));
 
-        // Find split point between Faces (type 30, 31) and everything else.
-        let split_idx = self.instances.partition_point(|i| {
-            let t = i.params[0] as u32;
-            t == 30 || t == 31
-        });
+        // Draw runs of faces and non-faces with their pipelines.  With
+        // DrawOrder::ByType this is one run of each.
+        let mut start = 0;
+        while start < self.instances.len() {
+            let face = is_face(&self.instances[start]);
+            let len = self.instances[start..]
+                .iter()
+                .take_while(|i| is_face(i) == face)
+                .count();
+            let end = start + len;
+            rp.set_pipeline(if face {
+                &self.pipelines.pipeline_depth_write
+            } else {
+                &self.pipelines.pipeline_lines
+            });
+            rp.draw(0..6, start as u32..end as u32);
+            start = end;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.instances.clear();
+        self.images.clear();
+    }
+
+    /// Drops the instances queued after the first `n`.
+    pub(crate) fn truncate(&mut self, n: usize) {
+        self.instances.truncate(n);
+    }
+
+    /// Drops the images queued after the first `n`.
+    pub(crate) fn truncate_images(&mut self, n: usize) {
+        self.images.truncate(n);
+    }
 
-        if split_idx > 0 {
-            rp.set_pipeline(&self.pipeline_depth_write);
-            rp.draw(0..6, 0..split_idx as u32);
+    /// Number of images queued for the current frame.
+    pub fn image_count(&self) -> usize {
+        self.images.len()
+    }
+
+    /// Number of primitive instances queued for the current frame.
+    pub fn instance_count(&self) -> usize {
+        self.instances.len()
+    }
+}
+
+/// Widest stroke, in pixels, that [`snap_line`] snaps.
+const CRISP_MAX_WIDTH: f32 = 3.0;
+
+/// Aligns a thin horizontal or vertical pixel-space line with the pixel
+/// grid: the width is rounded to whole pixels and the line centered on
+/// a pixel center (odd widths) or pixel edge (even widths), so it
+/// covers whole pixel rows.  Other lines are returned unchanged.
+pub fn snap_line(start: Vec3, end: Vec3, width: f32) -> (Vec3, Vec3, f32) {
+    if !(0.5..=CRISP_MAX_WIDTH).contains(&width) {
+        return (start, end, width);
+    }
+    let w = width.round().max(1.0);
+    let snap = |c: f32| {
+        if w as u32 % 2 == 1 {
+            c.floor() + 0.5
+        } else {
+            c.round()
         }
+    };
+    let (mut a, mut b) = (start, end);
+    if (a.y - b.y).abs() < 1e-3 {
+        let y = snap((a.y + b.y) * 0.5);
+        a.y = y;
+        b.y = y;
+    } else if (a.x - b.x).abs() < 1e-3 {
+        let x = snap((a.x + b.x) * 0.5);
+        a.x = x;
+        b.x = x;
+    } else {
+        return (start, end, width);
+    }
+    (a, b, w)
+}
+
+/// Faces (types 30, 31) use the depth-writing pipeline.
+fn is_face(i: &Instance) -> bool {
+    let t = i.params[0] as u32;
+    t == 30 || t == 31
+}
 
-        if split_idx < self.instances.len() {
-            rp.set_pipeline(&self.pipeline_lines);
-            rp.draw(
-                0..6,
-                split_idx as u32..self.instances.len() as u32,
-            );
+/// Clip-space depth of an instance's center.
+fn instance_depth(i: &Instance, view_proj: glam::Mat4) -> f32 {
+    let a = Vec3::from_slice(&i.pos_a_radius[..3]);
+    let center = match i.params[0] as u32 {
+        // Rects and markers are anchored at pos_a.
+        0 | 1 | 10..=29 => a,
+        2 => (a + Vec3::from_slice(&i.pos_b_width[..3])) * 0.5,
+        _ => {
+            (a + Vec3::from_slice(&i.pos_b_width[..3])
+                + Vec3::from_slice(&i.pos_c_pad[..3]))
+                / 3.0
         }
+    };
+    let clip = view_proj * center.extend(1.0);
+    if clip.w.abs() > f32::EPSILON {
+        clip.z / clip.w
+    } else {
+        clip.z
     }
+}
 
-    pub fn clear(&mut self) {
-        self.instances.clear();
+/// Stable sort of instances for `order`; ties keep submission order.
+fn sort_instances(instances: &mut [Instance], order: DrawOrder, view_proj: glam::Mat4) {
+    match order {
+        DrawOrder::Submission => {}
+        DrawOrder::ByType => instances.sort_by_key(|i| !is_face(i)),
+        DrawOrder::ByDepth => instances.sort_by(|a, b| {
+            instance_depth(b, view_proj).total_cmp(&instance_depth(a, view_proj))
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inst(prim_type: f32, z: f32, tag: f32) -> Instance {
+        Instance {
+            pos_a_radius: [0.0, 0.0, z, 0.0],
+            pos_b_width: [0.0, 0.0, z, 0.0],
+            color: [tag, 0.0, 0.0, 1.0],
+            params: [prim_type, 0.0, 0.0, 0.0],
+            pos_c_pad: [0.0, 0.0, z, 0.0],
+        }
+    }
+
+    fn tags(v: &[Instance]) -> Vec<f32> {
+        v.iter().map(|i| i.color[0]).collect()
+    }
+
+    #[test]
+    fn by_type_is_stable_within_buckets() {
+        let mut v = vec![
+            inst(2.0, 0.0, 0.0),
+            inst(30.0, 0.0, 1.0),
+            inst(1.0, 0.0, 2.0),
+            inst(31.0, 0.0, 3.0),
+            inst(2.0, 0.0, 4.0),
+        ];
+        sort_instances(&mut v, DrawOrder::ByType, glam::Mat4::IDENTITY);
+        assert_eq!(tags(&v), vec![1.0, 3.0, 0.0, 2.0, 4.0]);
+    }
+
+    #[test]
+    fn submission_keeps_order() {
+        let mut v = vec![inst(2.0, 0.0, 0.0), inst(30.0, 0.0, 1.0)];
+        sort_instances(&mut v, DrawOrder::Submission, glam::Mat4::IDENTITY);
+        assert_eq!(tags(&v), vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn by_depth_is_back_to_front_and_stable() {
+        let mut v = vec![
+            inst(2.0, 0.1, 0.0),
+            inst(30.0, 0.9, 1.0),
+            inst(1.0, 0.1, 2.0),
+            inst(31.0, 0.5, 3.0),
+        ];
+        sort_instances(&mut v, DrawOrder::ByDepth, glam::Mat4::IDENTITY);
+        assert_eq!(tags(&v), vec![1.0, 3.0, 0.0, 2.0]);
+    }
+
+    #[test]
+    fn snaps_thin_axis_aligned_lines() {
+        let (a, b, w) = snap_line(Vec3::new(0.0, 10.3, 0.0), Vec3::new(50.0, 10.3, 0.0), 0.8);
+        assert_eq!((a.y, b.y, w), (10.5, 10.5, 1.0));
+        let (a, _, w) = snap_line(Vec3::new(4.2, 0.0, 0.0), Vec3::new(4.2, 9.0, 0.0), 2.2);
+        assert_eq!((a.x, w), (4.0, 2.0));
+        // Diagonal and thick lines are left alone.
+        let diag = (Vec3::ZERO, Vec3::new(3.0, 4.0, 0.0), 1.0);
+        assert_eq!(snap_line(diag.0, diag.1, diag.2), diag);
+        let thick = (Vec3::new(0.0, 1.2, 0.0), Vec3::new(9.0, 1.2, 0.0), 6.0);
+        assert_eq!(snap_line(thick.0, thick.1, thick.2), thick);
     }
 }
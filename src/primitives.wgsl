@@ -17,6 +17,16 @@ struct GlobalUniforms {
 };
 @group(0) @binding(0) var<uniform> globals: GlobalUniforms;
 
+// World-space length of one pixel at `p`, measured along `axis`.
+fn world_per_pixel(p: vec3<f32>, axis: vec3<f32>) -> f32 {
+    let c0 = globals.view_proj * vec4<f32>(p, 1.0);
+    let c1 = globals.view_proj * vec4<f32>(p + axis, 1.0);
+    let d = (c1.xy / c1.w - c0.xy / c0.w) * globals.screen_size * 0.5;
+    let px = length(d);
+    if (px < 1e-6) { return 0.0; }
+    return 1.0 / px;
+}
+
 @vertex
 fn vs_main(
     @builtin(vertex_index) vertex_index: u32,
@@ -29,7 +39,9 @@ fn vs_main(
     var out: VertexOutput;
     
     let pos_a = pos_a_radius.xyz;
-    let radius = pos_a_radius.w;
+    var radius = pos_a_radius.w;
+    // pos_c_pad.w = 1: radius is in pixels, converted to world units below
+    let pixel_units = pos_c_pad.w > 0.5;
     let pos_b = pos_b_stroke.xyz;
     let stroke_width = pos_b_stroke.w;
     let pos_c = pos_c_pad.xyz;
@@ -112,6 +124,7 @@ fn vs_main(
                 else { perp = cross(dir, vec3<f32>(0.0, 1.0, 0.0)); }
             }
             perp = normalize(perp);
+            if (pixel_units) { radius = radius * world_per_pixel(pos_a + delta * 0.5, perp); }
             let actual_up = cross(perp, dir);
             rotation = mat3x3<f32>(dir, perp, actual_up);
         }
@@ -124,7 +137,6 @@ fn vs_main(
         size = pos_b.xy;
     } else { // Circles/Markers
         center = pos_a;
-        size = vec2<f32>(radius * 2.0, radius * 2.0);
         if (!is_2d) {
             // 3D Billboarding for points/circles
             var view_dir = globals.camera_pos - center;
@@ -134,10 +146,12 @@ fn vs_main(
             var right = cross(vec3<f32>(0.0, 0.0, 1.0), view_dir);
             if (length(right) < 1e-6) { right = vec3<f32>(1.0, 0.0, 0.0); }
             right = normalize(right);
+            if (pixel_units) { radius = radius * world_per_pixel(center, right); }
             
             let up = cross(view_dir, right);
             rotation = mat3x3<f32>(right, up, view_dir);
         }
+        size = vec2<f32>(radius * 2.0, radius * 2.0);
     }
 
     var effective_radius = radius;
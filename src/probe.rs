@@ -0,0 +1,271 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Vertical data probes.
+//!
+//! A [`DataProbe`] draws a vertical line at the probed X and a stacked
+//! readout box listing the linearly interpolated Y of every visible
+//! series at that X, as in oscilloscope and telemetry UIs.  It is an
+//! [`Artist`], installed with
+//! [`PlotBackend::set_probe`](crate::plotting::PlotBackend::set_probe)
+//! and moved with
+//! [`PlotBackend::move_probe`](crate::plotting::PlotBackend::move_probe).
+//!
+//! Plotted series are read from their axes with
+//! [`Axes::series_points`], so streamed and updated data is probed
+//! without handing it to the probe again.
+
+use crate::artist::{Artist, DataRect, DrawContext};
+use crate::locale::keys;
+use crate::plotting::{Axes, SeriesId};
+use glam::{Vec2, Vec4};
+use std::sync::Arc;
+
+/// Readout font size, in pixels.
+const READOUT_SIZE: f32 = 12.0;
+/// Readout box padding, in pixels.
+const READOUT_PAD: f32 = 6.0;
+
+/// A series sampled by the probe.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeSeries {
+  /// Readout label.
+  pub label: String,
+  /// X values, ascending.  Shared, so probing the data already held
+  /// elsewhere does not copy it.
+  pub x: Arc<[f64]>,
+  /// Y values.
+  pub y: Arc<[f64]>,
+  /// Marker and label color (RGBA).
+  pub color: Vec4,
+  /// Hidden series are not read out.
+  pub visible: bool,
+  /// Plotted series the data and visibility are read from, or `None`
+  /// for data added directly.
+  pub id: Option<SeriesId>,
+}
+
+/// One row of the probe readout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProbeReading {
+  /// Series label.
+  pub label: String,
+  /// Interpolated Y, or `None` outside the series' X range or in a gap.
+  pub value: Option<f64>,
+  /// Series color.
+  pub color: Vec4,
+}
+
+/// Linearly interpolated Y of the polyline `(x, y)` at `at`.
+///
+/// `x` must be ascending.  Returns `None` outside the X range and
+/// across segments with a non-finite end.
+pub fn interpolate_at(x: &[f64], y: &[f64], at: f64) -> Option<f64> {
+  let n = x.len().min(y.len());
+  if n == 0 || !at.is_finite() {
+    return None;
+  }
+  // First vertex with x >= at.
+  let i = x[..n].partition_point(|&v| v < at);
+  if i == n {
+    return None;
+  }
+  if x[i] == at {
+    return y[i].is_finite().then_some(y[i]);
+  }
+  if i == 0 {
+    return None;
+  }
+  let (x0, x1, y0, y1) = (x[i - 1], x[i], y[i - 1], y[i]);
+  if ![x0, x1, y0, y1].iter().all(|v| v.is_finite()) {
+    return None;
+  }
+  Some(y0 + (y1 - y0) * (at - x0) / (x1 - x0))
+}
+
+/// Vertical slice readout across several series.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataProbe {
+  /// Probed series, listed in this order.
+  pub series: Vec<ProbeSeries>,
+  /// Probed X, or `None` to hide the probe.
+  pub x: Option<f64>,
+  /// Decimals shown in the readout.
+  pub decimals: usize,
+}
+
+impl Default for DataProbe {
+  fn default() -> Self {
+    Self {
+      series: Vec::new(),
+      x: None,
+      decimals: 3,
+    }
+  }
+}
+
+impl DataProbe {
+  /// An empty, hidden probe.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Adds a visible series, copying its data.
+  pub fn add_series(&mut self, label: &str, x: &[f64], y: &[f64], color: Vec4) {
+    self.add_series_shared(label, x.into(), y.into(), color);
+  }
+
+  /// Adds a visible series sharing `x` and `y` instead of copying them.
+  pub fn add_series_shared(&mut self, label: &str, x: Arc<[f64]>, y: Arc<[f64]>, color: Vec4) {
+    self.series.push(ProbeSeries {
+      label: label.to_string(),
+      x,
+      y,
+      color,
+      visible: true,
+      id: None,
+    });
+  }
+
+  /// Adds the plotted series `id`, read from its axes with
+  /// [`read_series`](Self::read_series).
+  pub fn add_plotted(&mut self, label: &str, id: SeriesId, color: Vec4) {
+    self.series.push(ProbeSeries {
+      label: label.to_string(),
+      x: Vec::new().into(),
+      y: Vec::new().into(),
+      color,
+      visible: true,
+      id: Some(id),
+    });
+  }
+
+  /// Refreshes the data and visibility of the plotted series from
+  /// `ax`.  Series no longer in `ax` are hidden.
+  pub fn read_series(&mut self, ax: &Axes<'_>) {
+    for s in &mut self.series {
+      let Some(id) = s.id else { continue };
+      let (x, y) = ax.series_points(id);
+      s.x = x.into();
+      s.y = y.into();
+      s.visible = ax.is_visible(id).unwrap_or(false);
+    }
+  }
+
+  /// Readings of the visible series at the probed X.
+  pub fn readings(&self) -> Vec<ProbeReading> {
+    let Some(at) = self.x else { return Vec::new() };
+    self
+      .series
+      .iter()
+      .filter(|s| s.visible)
+      .map(|s| ProbeReading {
+        label: s.label.clone(),
+        value: interpolate_at(&s.x, &s.y, at),
+        color: s.color,
+      })
+      .collect()
+  }
+}
+
+impl Artist for DataProbe {
+  fn draw(&self, ctx: &mut DrawContext<'_>) {
+    let Some(at) = self.x else { return };
+    let limits = ctx.limits();
+    if at < limits.x_min.min(limits.x_max) || at > limits.x_min.max(limits.x_max) {
+      return;
+    }
+    let [left, top, right, bottom] = ctx.pixel_box();
+    let px = ctx.to_pixels(at, limits.y_min).x;
+    let black = Vec4::new(0.0, 0.0, 0.0, 1.0);
+    let (a, b) = (ctx.screen(Vec2::new(px, top)), ctx.screen(Vec2::new(px, bottom)));
+    ctx.line_pixels(a, b, 1.0, black);
+
+    let readings = self.readings();
+    let rows: Vec<String> = readings
+      .iter()
+      .map(|r| match r.value {
+        Some(v) => format!("{}: {:.*}", r.label, self.decimals, v),
+        None => format!("{}: {}", r.label, ctx.locale().text(keys::MISSING)),
+      })
+      .collect();
+    for r in &readings {
+      if let Some(v) = r.value {
+        ctx.marker((at, v), 3.0, r.color);
+      }
+    }
+    if rows.is_empty() {
+      return;
+    }
+
+    // Readout to the right of the line, flipped left near the edge.
+    let line_h = READOUT_SIZE + READOUT_PAD * 0.5;
+    let w = rows
+      .iter()
+      .map(|s| ctx.measure_text(s, READOUT_SIZE))
+      .fold(0.0, f32::max)
+      + READOUT_PAD * 2.0;
+    let h = line_h * rows.len() as f32 + READOUT_PAD * 1.5;
+    let x = if px + READOUT_PAD + w <= right {
+      px + READOUT_PAD
+    } else {
+      (px - READOUT_PAD - w).max(left)
+    };
+    let origin = Vec2::new(x, top + READOUT_PAD);
+    ctx.rect_pixels(origin, Vec2::new(w, h), Vec4::new(1.0, 1.0, 1.0, 0.9), 0.0);
+    ctx.rect_pixels(origin, Vec2::new(w, h), black, 0.75);
+    for (i, (row, r)) in rows.iter().zip(&readings).enumerate() {
+      let pos = origin + Vec2::new(READOUT_PAD, READOUT_PAD + line_h * i as f32);
+      ctx.text_pixels(pos, row, READOUT_SIZE, r.color);
+    }
+  }
+
+  fn extent(&self) -> Option<DataRect> {
+    None
+  }
+
+  fn z_order(&self) -> i32 {
+    i32::MAX
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn interpolates_between_samples() {
+    let x = [0.0, 1.0, 3.0];
+    let y = [0.0, 10.0, 30.0];
+    assert_eq!(interpolate_at(&x, &y, 0.5), Some(5.0));
+    assert_eq!(interpolate_at(&x, &y, 2.0), Some(20.0));
+    assert_eq!(interpolate_at(&x, &y, 3.0), Some(30.0));
+    assert_eq!(interpolate_at(&x, &y, 0.0), Some(0.0));
+    assert_eq!(interpolate_at(&x, &y, -0.1), None);
+    assert_eq!(interpolate_at(&x, &y, 3.1), None);
+    assert_eq!(interpolate_at(&[], &[], 1.0), None);
+  }
+
+  #[test]
+  fn gaps_have_no_value() {
+    let x = [0.0, 1.0, 2.0];
+    let y = [0.0, f64::NAN, 2.0];
+    assert_eq!(interpolate_at(&x, &y, 0.5), None);
+    assert_eq!(interpolate_at(&x, &y, 2.0), Some(2.0));
+  }
+
+  #[test]
+  fn readings_skip_hidden_series() {
+    let mut probe = DataProbe::new();
+    probe.add_series("a", &[0.0, 2.0], &[0.0, 4.0], Vec4::ONE);
+    probe.add_series("b", &[0.0, 2.0], &[1.0, 1.0], Vec4::ONE);
+    probe.add_series("c", &[5.0, 6.0], &[1.0, 1.0], Vec4::ONE);
+    assert!(probe.readings().is_empty());
+    probe.x = Some(1.0);
+    probe.series[1].visible = false;
+    let r = probe.readings();
+    assert_eq!(r.len(), 2);
+    assert_eq!((r[0].label.as_str(), r[0].value), ("a", Some(2.0)));
+    assert_eq!((r[1].label.as_str(), r[1].value), ("c", None));
+  }
+}
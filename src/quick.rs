@@ -0,0 +1,136 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! One line to a picture.
+//!
+//! [`plot`] and [`scatter`] create a figure of [`SIZE`] with the data
+//! drawn on gridded, autoscaled axes, ready to be saved or shown:
+//!
+//! ```rust,no_run
+//! let x: Vec<f64> = (0..50).map(|i| i as f64 * 0.2).collect();
+//! let y: Vec<f64> = x.iter().map(|x| x.sin()).collect();
+//! mpl_wgpu::quick::plot(&x, &y).title("sin").save("sin.png")?;
+//! # Ok::<(), mpl_wgpu::error::Error>(())
+//! ```
+//!
+//! The GPU device for saving is created on first use and shared (see
+//! [`capture::shared_context`]).  Anything beyond titles and labels is
+//! configured through [`QuickPlot::backend`].
+
+use crate::capture::{self, PlotCapture};
+use crate::error::Result;
+use crate::plotting::PlotBackend;
+use std::path::Path;
+
+pub use crate::quickplot::SIZE;
+
+/// A figure made by [`plot`] or [`scatter`].
+pub struct QuickPlot {
+  backend: PlotBackend,
+}
+
+/// Line plot of `y` against `x`.
+pub fn plot(x: &[f64], y: &[f64]) -> QuickPlot {
+  QuickPlot::with(|backend| {
+    backend.figure().current_axes().plot(x, y, "-");
+  })
+}
+
+/// Markers at the `(x, y)` pairs.
+pub fn scatter(x: &[f64], y: &[f64]) -> QuickPlot {
+  QuickPlot::with(|backend| {
+    backend.figure().current_axes().scatter(x, y, "o");
+  })
+}
+
+/// Shows a line plot of `y` against `x` in a window and returns when
+/// it is closed.
+#[cfg(feature = "window")]
+pub fn show(x: &[f64], y: &[f64]) -> Result<()> {
+  plot(x, y).show()
+}
+
+/// File types [`QuickPlot::save`] writes, by extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Output {
+  Png,
+  Svg,
+  Html,
+}
+
+impl Output {
+  /// SVG and HTML for their extensions, PNG otherwise.
+  fn for_path(path: &Path) -> Self {
+    let ext = path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase);
+    match ext.as_deref() {
+      Some("svg") => Output::Svg,
+      Some("html" | "htm") => Output::Html,
+      _ => Output::Png,
+    }
+  }
+}
+
+impl QuickPlot {
+  /// Figure of [`SIZE`] with a grid, drawn into by `draw`.
+  fn with(draw: impl FnOnce(&mut PlotBackend)) -> Self {
+    let mut backend = PlotBackend::new(SIZE.0, SIZE.1);
+    backend.figure().current_axes().grid(true);
+    draw(&mut backend);
+    Self { backend }
+  }
+
+  /// Sets the title.
+  pub fn title(self, text: &str) -> Self {
+    self.backend.figure().current_axes().set_title(text);
+    self
+  }
+
+  /// Sets the X axis label.
+  pub fn xlabel(self, text: &str) -> Self {
+    self.backend.figure().current_axes().set_xlabel(text);
+    self
+  }
+
+  /// Sets the Y axis label.
+  pub fn ylabel(self, text: &str) -> Self {
+    self.backend.figure().current_axes().set_ylabel(text);
+    self
+  }
+
+  /// The figure, for anything else.
+  pub fn backend(&mut self) -> &mut PlotBackend {
+    &mut self.backend
+  }
+
+  /// Renders the figure to `path`: SVG or HTML for those extensions,
+  /// PNG otherwise.
+  pub fn save(self, path: impl AsRef<Path>) -> Result<()> {
+    let path = path.as_ref();
+    let mut capture = PlotCapture::from_backend(capture::shared_context()?, self.backend);
+    match Output::for_path(path) {
+      Output::Png => capture.save_png(path)?,
+      Output::Svg => capture.save_svg(path, &Default::default())?,
+      Output::Html => capture.save_html(path)?,
+    }
+    Ok(())
+  }
+
+  /// Shows the figure in a window and returns when it is closed.
+  #[cfg(feature = "window")]
+  pub fn show(self) -> Result<()> {
+    self.backend.show()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn output_follows_the_extension() {
+    assert_eq!(Output::for_path(Path::new("out.png")), Output::Png);
+    assert_eq!(Output::for_path(Path::new("out.SVG")), Output::Svg);
+    assert_eq!(Output::for_path(Path::new("dir/out.htm")), Output::Html);
+    assert_eq!(Output::for_path(Path::new("out")), Output::Png);
+  }
+}
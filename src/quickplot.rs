@@ -0,0 +1,155 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! One-call plots of CSV files.
+//!
+//! [`plot_csv`] reads a CSV file with a header row, plots the named
+//! Y columns against the X column as labelled lines and turns the
+//! legend on.  Cells are coerced to numbers; empty or non-numeric cells
+//! become NaN and leave gaps.  The delimiter is guessed from the header
+//! (comma, semicolon or tab) and double-quoted fields may contain it.
+//!
+//! ```rust,no_run
+//! let mut plot = mpl_wgpu::quickplot::plot_csv("log.csv", "time", &["cpu", "memory"])
+//!   .expect("readable CSV with these columns");
+//! let recording = plot.record(None);
+//! ```
+
+use crate::error::{Error, Result};
+use crate::plotting::{Axes, PlotBackend};
+use std::path::Path;
+
+/// Size of the figure [`plot_csv`] creates.
+pub const SIZE: (u32, u32) = (800, 600);
+
+/// Numeric columns of a CSV file.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Table {
+  /// Column names from the header row.
+  pub headers: Vec<String>,
+  /// One vector per column, all as long as the number of data rows.
+  pub columns: Vec<Vec<f64>>,
+}
+
+impl Table {
+  /// Parses CSV text whose first line is the header.
+  pub fn parse(text: &str) -> Self {
+    let mut lines = text.lines().filter(|l| !l.trim().is_empty());
+    let Some(header) = lines.next() else { return Self::default() };
+    let delimiter = guess_delimiter(header);
+    let headers: Vec<String> = split_row(header, delimiter).into_iter().map(|h| h.trim().to_string()).collect();
+    let mut columns = vec![Vec::new(); headers.len()];
+    for line in lines {
+      let cells = split_row(line, delimiter);
+      for (i, column) in columns.iter_mut().enumerate() {
+        column.push(cells.get(i).map_or(f64::NAN, |c| coerce(c)));
+      }
+    }
+    Self { headers, columns }
+  }
+
+  /// Reads and parses the CSV file at `path`.
+  pub fn read(path: impl AsRef<Path>) -> Result<Self> {
+    Ok(Self::parse(&std::fs::read_to_string(path)?))
+  }
+
+  /// The column named `name`.
+  pub fn column(&self, name: &str) -> Result<&[f64]> {
+    self
+      .headers
+      .iter()
+      .position(|h| h == name)
+      .map(|i| self.columns[i].as_slice())
+      .ok_or_else(|| Error::UnknownData(name.into()))
+  }
+
+  /// Plots `y_cols` against `x_col` into `ax` as lines labelled with
+  /// their column names, with a legend.  Nothing is drawn if a column
+  /// is missing.
+  pub fn plot(&self, ax: &Axes<'_>, x_col: &str, y_cols: &[&str]) -> Result<()> {
+    let x = self.column(x_col)?;
+    let ys = y_cols.iter().map(|c| self.column(c)).collect::<Result<Vec<_>>>()?;
+    ax.holding(|| {
+      for (name, y) in y_cols.iter().zip(ys) {
+        ax.plot(x, y, "-").label(name);
+      }
+    });
+    ax.set_xlabel(x_col);
+    ax.legend(true);
+    Ok(())
+  }
+}
+
+/// Reads the CSV file at `path` and plots its `y_cols` against `x_col`
+/// (see [`Table::plot`]) in a new figure.
+pub fn plot_csv(path: impl AsRef<Path>, x_col: &str, y_cols: &[&str]) -> Result<PlotBackend> {
+  let table = Table::read(path)?;
+  // Check the columns before creating the figure.
+  table.column(x_col)?;
+  for c in y_cols {
+    table.column(c)?;
+  }
+  let backend = PlotBackend::new(SIZE.0, SIZE.1);
+  table.plot(&backend.figure().current_axes(), x_col, y_cols)?;
+  Ok(backend)
+}
+
+/// The delimiter occurring most often in `header` outside quotes,
+/// comma on a tie.
+fn guess_delimiter(header: &str) -> char {
+  let count = |d: char| split_row(header, d).len();
+  [',', ';', '\t'].into_iter().rev().max_by_key(|&d| count(d)).unwrap_or(',')
+}
+
+/// Fields of one row; quoted fields may contain the delimiter and
+/// `""` for a quote.
+fn split_row(line: &str, delimiter: char) -> Vec<String> {
+  let mut fields = Vec::new();
+  let mut field = String::new();
+  let mut quoted = false;
+  let mut chars = line.chars().peekable();
+  while let Some(c) = chars.next() {
+    match c {
+      '"' if quoted && chars.peek() == Some(&'"') => {
+        field.push('"');
+        chars.next();
+      }
+      '"' => quoted = !quoted,
+      c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+      c => field.push(c),
+    }
+  }
+  fields.push(field);
+  fields
+}
+
+/// `cell` as a number, NaN if it is empty or not numeric.
+fn coerce(cell: &str) -> f64 {
+  cell.trim().parse().unwrap_or(f64::NAN)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parses_headers_quotes_and_gaps() {
+    let t = Table::parse("time,\"cpu, %\",mem\n0,10,1.5\n1,,2\n2,n/a\n\n3,\"40\",4\n");
+    assert_eq!(t.headers, vec!["time", "cpu, %", "mem"]);
+    assert_eq!(t.column("time").unwrap(), &[0.0, 1.0, 2.0, 3.0]);
+    let cpu = t.column("cpu, %").unwrap();
+    assert_eq!((cpu[0], cpu[3]), (10.0, 40.0));
+    assert!(cpu[1].is_nan() && cpu[2].is_nan());
+    assert!(t.column("mem").unwrap()[2].is_nan());
+    assert!(matches!(t.column("disk"), Err(Error::UnknownData(_))));
+  }
+
+  #[test]
+  fn guesses_the_delimiter() {
+    let t = Table::parse("a;b\n1,5;2\n");
+    assert_eq!(t.headers, vec!["a", "b"]);
+    assert!(t.column("a").unwrap()[0].is_nan());
+    assert_eq!(Table::parse("a\tb\n1\t2").column("b").unwrap(), &[2.0]);
+    assert_eq!(split_row("\"say \"\"hi\"\"\",x", ','), vec!["say \"hi\"", "x"]);
+  }
+}
@@ -0,0 +1,315 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Recorded draw-call streams.
+//!
+//! A [`Recording`] captures the screen-space primitives that the
+//! matplot++ pipeline emits through the FFI callbacks, independent of
+//! any GPU renderer.  Recordings can be serialized to JSON and are the
+//! input for the interactive HTML and the SVG export.
+
+use glam::{Vec2, Vec3, Vec4};
+
+/// A single screen-space primitive captured from the draw callbacks.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordedPrimitive {
+  /// Axis-aligned rectangle (top-left corner and size).
+  Rect {
+    /// Top-left corner in pixels.
+    pos: Vec2,
+    /// Width and height in pixels.
+    size: Vec2,
+    /// RGBA color.
+    color: Vec4,
+    /// Stroke width (0 = filled).
+    stroke_width: f32,
+    /// Corner radius in pixels.
+    corner_radius: f32,
+  },
+  /// Line segment, optionally dashed.
+  Line {
+    /// Start point.
+    start: Vec3,
+    /// End point.
+    end: Vec3,
+    /// Stroke width in pixels.
+    width: f32,
+    /// RGBA color.
+    color: Vec4,
+    /// Dash length in pixels (0 = solid).
+    dash_len: f32,
+    /// Gap length in pixels.
+    gap_len: f32,
+    /// Dash pattern offset in pixels.
+    dash_offset: f32,
+  },
+  /// Point marker.
+  Marker {
+    /// Marker center.
+    center: Vec3,
+    /// Marker radius in pixels.
+    radius: f32,
+    /// RGBA color.
+    color: Vec4,
+    /// Shader marker type (1 = circle, 10+ = shaped markers).
+    marker_type: u32,
+  },
+  /// Filled triangle.
+  Triangle {
+    /// The three vertices.
+    points: [Vec3; 3],
+    /// RGBA color.
+    color: Vec4,
+  },
+  /// Text label anchored at its top-left corner.
+  Text {
+    /// Label contents.
+    text: String,
+    /// Anchor position in pixels.
+    pos: Vec2,
+    /// Font size in pixels.
+    size: f32,
+    /// RGBA color.
+    color: Vec4,
+    /// Counter-clockwise rotation in degrees about the anchor.
+    rotation: f32,
+  },
+}
+
+/// An ordered list of primitives for one rendered frame.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Recording {
+  /// Frame width in pixels.
+  pub width: u32,
+  /// Frame height in pixels.
+  pub height: u32,
+  /// Primitives in submission order.
+  pub primitives: Vec<RecordedPrimitive>,
+}
+
+impl Recording {
+  /// Creates an empty recording for a frame of the given size.
+  pub fn new(width: u32, height: u32) -> Self {
+    Self {
+      width,
+      height,
+      primitives: Vec::new(),
+    }
+  }
+
+  /// Appends a primitive.
+  pub fn push(&mut self, primitive: RecordedPrimitive) {
+    self.primitives.push(primitive);
+  }
+
+  /// Serializes the recording to compact JSON.
+  ///
+  /// Each primitive is an array whose first element is a kind tag
+  /// (`"r"`, `"l"`, `"m"`, `"t"`, `"s"`) followed by its numeric
+  /// fields; text entries end with the label string.
+  pub fn to_json(&self) -> String {
+    let mut out = String::with_capacity(64 + self.primitives.len() * 48);
+    out.push_str(&format!(
+      "{{\"width\":{},\"height\":{},\"primitives\":[",
+      self.width, self.height
+    ));
+    for (i, p) in self.primitives.iter().enumerate() {
+      if i > 0 {
+        out.push(',');
+      }
+      match p {
+        RecordedPrimitive::Rect {
+          pos,
+          size,
+          color,
+          stroke_width,
+          corner_radius,
+        } => {
+          out.push_str("[\"r\"");
+          push_nums(
+            &mut out,
+            &[pos.x, pos.y, size.x, size.y, *stroke_width, *corner_radius],
+          );
+          push_color(&mut out, *color);
+          out.push(']');
+        }
+        RecordedPrimitive::Line {
+          start,
+          end,
+          width,
+          color,
+          dash_len,
+          gap_len,
+          dash_offset,
+        } => {
+          out.push_str("[\"l\"");
+          push_nums(
+            &mut out,
+            &[
+              start.x,
+              start.y,
+              end.x,
+              end.y,
+              *width,
+              *dash_len,
+              *gap_len,
+              *dash_offset,
+            ],
+          );
+          push_color(&mut out, *color);
+          out.push(']');
+        }
+        RecordedPrimitive::Marker {
+          center,
+          radius,
+          color,
+          marker_type,
+        } => {
+          out.push_str("[\"m\"");
+          push_nums(
+            &mut out,
+            &[center.x, center.y, *radius, *marker_type as f32],
+          );
+          push_color(&mut out, *color);
+          out.push(']');
+        }
+        RecordedPrimitive::Triangle { points, color } => {
+          out.push_str("[\"t\"");
+          push_nums(
+            &mut out,
+            &[
+              points[0].x,
+              points[0].y,
+              points[1].x,
+              points[1].y,
+              points[2].x,
+              points[2].y,
+            ],
+          );
+          push_color(&mut out, *color);
+          out.push(']');
+        }
+        RecordedPrimitive::Text {
+          text,
+          pos,
+          size,
+          color,
+          rotation,
+        } => {
+          out.push_str("[\"s\"");
+          push_nums(&mut out, &[pos.x, pos.y, *size]);
+          push_color(&mut out, *color);
+          out.push(',');
+          push_json_string(&mut out, text);
+          push_nums(&mut out, &[*rotation]);
+          out.push(']');
+        }
+      }
+    }
+    out.push_str("]}");
+    out
+  }
+}
+
+/// Appends `,n0,n1,...` to `out`, writing non-finite values as 0.
+fn push_nums(out: &mut String, nums: &[f32]) {
+  for n in nums {
+    out.push(',');
+    if n.is_finite() {
+      out.push_str(&format!("{}", n));
+    } else {
+      out.push('0');
+    }
+  }
+}
+
+/// Appends the four color channels as numbers.
+fn push_color(out: &mut String, c: Vec4) {
+  push_nums(out, &[c.x, c.y, c.z, c.w]);
+}
+
+/// Appends `s` as a quoted, escaped JSON string.
+fn push_json_string(out: &mut String, s: &str) {
+  out.push('"');
+  for ch in s.chars() {
+    match ch {
+      '"' => out.push_str("\\\""),
+      '\\' => out.push_str("\\\\"),
+      '\n' => out.push_str("\\n"),
+      '\r' => out.push_str("\\r"),
+      '\t' => out.push_str("\\t"),
+      // Keep "</script>" from terminating an embedding <script>.
+      '<' => out.push_str("\\u003c"),
+      c if (c as u32) < 0x20 => {
+        out.push_str(&format!("\\u{:04x}", c as u32));
+      }
+      c => out.push(c),
+    }
+  }
+  out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn empty_recording_json() {
+    let rec = Recording::new(800, 600);
+    assert_eq!(
+      rec.to_json(),
+      "{\"width\":800,\"height\":600,\"primitives\":[]}"
+    );
+  }
+
+  #[test]
+  fn rect_and_line_json() {
+    let mut rec = Recording::new(10, 10);
+    rec.push(RecordedPrimitive::Rect {
+      pos: Vec2::new(1.0, 2.0),
+      size: Vec2::new(3.0, 4.0),
+      color: Vec4::new(1.0, 0.0, 0.0, 1.0),
+      stroke_width: 0.0,
+      corner_radius: 0.0,
+    });
+    rec.push(RecordedPrimitive::Line {
+      start: Vec3::new(0.0, 0.0, 0.0),
+      end: Vec3::new(5.0, 5.0, 0.0),
+      width: 1.5,
+      color: Vec4::ONE,
+      dash_len: 0.0,
+      gap_len: 0.0,
+      dash_offset: 0.0,
+    });
+    let json = rec.to_json();
+    assert!(json.contains("[\"r\",1,2,3,4,0,0,1,0,0,1]"));
+    assert!(json.contains("[\"l\",0,0,5,5,1.5,0,0,0,1,1,1,1]"));
+  }
+
+  #[test]
+  fn text_is_escaped() {
+    let mut rec = Recording::new(10, 10);
+    rec.push(RecordedPrimitive::Text {
+      text: "a\"b</script>".to_string(),
+      pos: Vec2::ZERO,
+      size: 12.0,
+      color: Vec4::ONE,
+      rotation: 90.0,
+    });
+    let json = rec.to_json();
+    assert!(json.contains("\"a\\\"b\\u003c/script>\",90]"));
+    assert!(!json.contains("</script>"));
+  }
+
+  #[test]
+  fn non_finite_values_become_zero() {
+    let mut rec = Recording::new(10, 10);
+    rec.push(RecordedPrimitive::Marker {
+      center: Vec3::new(f32::NAN, 1.0, 0.0),
+      radius: f32::INFINITY,
+      color: Vec4::ONE,
+      marker_type: 1,
+    });
+    assert!(rec.to_json().contains("[\"m\",0,1,0,1,1,1,1,1]"));
+  }
+}
@@ -0,0 +1,255 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Gnuplot reference renders.
+//!
+//! matplot++ ships a gnuplot backend.  Rendering the same figure setup
+//! through it gives an independent reference image to validate the
+//! wgpu renderer against: a [`GnuplotFigure`] is a figure on that
+//! backend, and a [`ReferenceGenerator`] renders whole sets of
+//! [`TestCase`]s into a reference directory.  [`ReferenceOptions`]
+//! choose the output size, the gnuplot terminal and how fonts map onto
+//! gnuplot's.  Rendering needs `gnuplot` on the `PATH`.
+//!
+//! ```rust,no_run
+//! use mpl_wgpu::reference::{ReferenceGenerator, ReferenceOptions, Terminal};
+//! use mpl_wgpu::test_cases;
+//!
+//! let options = ReferenceOptions::new().size(1024, 768).terminal(Terminal::Svg);
+//! let generator = ReferenceGenerator::new("refs", options);
+//! generator.generate_matching(&test_cases::all(), "*_plot").expect("gnuplot references");
+//! ```
+
+use crate::error::{Error, Result};
+use crate::ffi;
+use crate::golden;
+use crate::plotting::Figure;
+use crate::test_cases::{self, TestCase};
+use std::ffi::CString;
+use std::path::{Path, PathBuf};
+
+/// Gnuplot output terminal.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Terminal {
+  /// Anti-aliased PNG through cairo, closest to the wgpu output.
+  #[default]
+  PngCairo,
+  /// PNG through libgd.
+  Png,
+  /// Scalable SVG.
+  Svg,
+}
+
+impl Terminal {
+  /// Gnuplot's name for the terminal.
+  pub fn name(self) -> &'static str {
+    match self {
+      Terminal::PngCairo => "pngcairo",
+      Terminal::Png => "png",
+      Terminal::Svg => "svg",
+    }
+  }
+
+  /// File extension of the output.
+  pub fn extension(self) -> &'static str {
+    match self {
+      Terminal::PngCairo | Terminal::Png => "png",
+      Terminal::Svg => "svg",
+    }
+  }
+}
+
+/// How text styles map onto gnuplot.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StyleMap {
+  /// Font family passed to gnuplot, or `None` for its default.
+  pub font: Option<String>,
+  /// Base font size in points, or `None` for matplot++'s default.
+  pub font_size: Option<f32>,
+}
+
+/// Size, terminal and styles of reference renders.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferenceOptions {
+  /// Output width in pixels.
+  pub width: u32,
+  /// Output height in pixels.
+  pub height: u32,
+  /// Gnuplot terminal.
+  pub terminal: Terminal,
+  /// Font mapping.
+  pub style: StyleMap,
+}
+
+impl Default for ReferenceOptions {
+  fn default() -> Self {
+    Self {
+      width: test_cases::WIDTH,
+      height: test_cases::HEIGHT,
+      terminal: Terminal::default(),
+      style: StyleMap::default(),
+    }
+  }
+}
+
+impl ReferenceOptions {
+  /// PNG references at the visual test size.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the output size in pixels.
+  pub fn size(mut self, width: u32, height: u32) -> Self {
+    self.width = width;
+    self.height = height;
+    self
+  }
+
+  /// Sets the gnuplot terminal.
+  pub fn terminal(mut self, terminal: Terminal) -> Self {
+    self.terminal = terminal;
+    self
+  }
+
+  /// Sets the font family.
+  pub fn font(mut self, font: &str) -> Self {
+    self.style.font = Some(font.into());
+    self
+  }
+
+  /// Sets the base font size in points.
+  pub fn font_size(mut self, size: f32) -> Self {
+    self.style.font_size = Some(size);
+    self
+  }
+}
+
+/// Owning wrapper for a gnuplot-backed matplot++ figure.
+///
+/// Uses the default gnuplot backend (no wgpu renderer). Call
+/// [`save`](Self::save) to render the plot via gnuplot.
+pub struct GnuplotFigure {
+  ptr: *mut ffi::MplFigure,
+  terminal: Option<Terminal>,
+}
+
+impl GnuplotFigure {
+  /// Creates a new gnuplot-backed figure.
+  pub fn new() -> Self {
+    let ptr = unsafe { ffi::mpl_figure_create_gnuplot() };
+    assert!(!ptr.is_null(), "Failed to create gnuplot figure");
+    Self { ptr, terminal: None }
+  }
+
+  /// Figure sized and styled by `options`, saving with its terminal.
+  pub fn with_options(options: &ReferenceOptions) -> Self {
+    let mut fig = Self::new();
+    fig.terminal = Some(options.terminal);
+    let font = CString::new(options.style.font.as_deref().unwrap_or("")).unwrap_or_default();
+    unsafe {
+      ffi::mpl_figure_set_size(fig.ptr, options.width, options.height);
+      ffi::mpl_figure_set_font(fig.ptr, font.as_ptr(), options.style.font_size.unwrap_or(0.0));
+    }
+    fig
+  }
+
+  /// Returns a non-owning [`Figure`] handle for plot setup.
+  pub fn figure(&self) -> Figure<'_> {
+    Figure::from_raw(self.ptr)
+  }
+
+  /// Saves the figure to a file via gnuplot.
+  ///
+  /// Without a terminal from [`with_options`](Self::with_options), the
+  /// output format is inferred from the file extension (e.g. `.png`,
+  /// `.svg`).
+  pub fn save(&self, path: &str) -> bool {
+    let c_path = CString::new(path).unwrap_or_default();
+    match self.terminal {
+      Some(t) => {
+        let c_term = CString::new(t.name()).unwrap_or_default();
+        unsafe { ffi::mpl_figure_save_as(self.ptr, c_path.as_ptr(), c_term.as_ptr()) }
+      }
+      None => unsafe { ffi::mpl_figure_save(self.ptr, c_path.as_ptr()) },
+    }
+  }
+}
+
+impl Default for GnuplotFigure {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Drop for GnuplotFigure {
+  fn drop(&mut self) {
+    unsafe { ffi::mpl_figure_destroy(self.ptr); }
+  }
+}
+
+/// Renders test cases through gnuplot into a directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferenceGenerator {
+  dir: PathBuf,
+  options: ReferenceOptions,
+}
+
+impl ReferenceGenerator {
+  /// Writes references rendered with `options` into `dir`.
+  pub fn new(dir: impl Into<PathBuf>, options: ReferenceOptions) -> Self {
+    Self { dir: dir.into(), options }
+  }
+
+  /// This crate's own references in `tests/reference`.
+  pub fn repo(options: ReferenceOptions) -> Self {
+    Self::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("reference"), options)
+  }
+
+  /// The output directory.
+  pub fn dir(&self) -> &Path {
+    &self.dir
+  }
+
+  /// The render options.
+  pub fn options(&self) -> &ReferenceOptions {
+    &self.options
+  }
+
+  /// Output path of the reference for `name`.
+  pub fn path(&self, name: &str) -> PathBuf {
+    self.dir.join(format!("{}.{}", name, self.options.terminal.extension()))
+  }
+
+  /// Renders `tc` and returns the written path.  Fails with
+  /// [`Error::Gnuplot`] if gnuplot did not write it.
+  pub fn generate(&self, tc: &TestCase) -> Result<PathBuf> {
+    std::fs::create_dir_all(&self.dir)?;
+    let path = self.path(tc.name);
+    let fig = GnuplotFigure::with_options(&self.options);
+    (tc.setup)(&fig.figure());
+    if !fig.save(&path.to_string_lossy()) {
+      return Err(Error::Gnuplot(path));
+    }
+    Ok(path)
+  }
+
+  /// Renders the `cases` whose names match `pattern` (see
+  /// [`golden::matches`]) and returns the written paths.
+  pub fn generate_matching(&self, cases: &[TestCase], pattern: &str) -> Result<Vec<PathBuf>> {
+    cases.iter().filter(|tc| golden::matches(pattern, tc.name)).map(|tc| self.generate(tc)).collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn output_paths_follow_the_terminal() {
+    let svg = ReferenceGenerator::new("refs", ReferenceOptions::new().terminal(Terminal::Svg));
+    assert_eq!(svg.path("line_plot"), Path::new("refs/line_plot.svg"));
+    let png = ReferenceGenerator::new("refs", ReferenceOptions::new());
+    assert_eq!(png.path("line_plot"), Path::new("refs/line_plot.png"));
+    assert_eq!(png.options().width, test_cases::WIDTH);
+  }
+}
@@ -0,0 +1,212 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Reference lines.
+//!
+//! A [`RefLine`] is a horizontal or vertical line at a fixed data value
+//! spanning the whole axes box, whatever the data limits (matplotlib's
+//! `axhline` / `axvline`).  It is drawn at render time from the current
+//! limits and never widens them, so thresholds and zero lines stay put
+//! while the view pans and zooms.  Add them with
+//! [`PlotBackend::axhline`](crate::plotting::PlotBackend::axhline) and
+//! [`PlotBackend::axvline`](crate::plotting::PlotBackend::axvline).
+
+use crate::artist::{Artist, DataRect, DrawContext};
+use glam::{Vec2, Vec4};
+
+/// Dash pattern of a line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LineStyle {
+  /// Continuous line (`"-"`).
+  #[default]
+  Solid,
+  /// Long dashes (`"--"`).
+  Dashed,
+  /// Dots (`":"`).
+  Dotted,
+}
+
+impl LineStyle {
+  /// Parses a matplotlib style string (`"-"`, `"--"`, `":"`).
+  pub fn parse(s: &str) -> Option<Self> {
+    match s {
+      "-" | "" => Some(LineStyle::Solid),
+      "--" => Some(LineStyle::Dashed),
+      ":" => Some(LineStyle::Dotted),
+      _ => None,
+    }
+  }
+
+  /// Dash and gap lengths in multiples of the line width.
+  fn pattern(self) -> (f32, f32) {
+    match self {
+      LineStyle::Solid => (0.0, 0.0),
+      LineStyle::Dashed => (3.7, 1.6),
+      LineStyle::Dotted => (1.0, 1.65),
+    }
+  }
+}
+
+/// Orientation of a [`RefLine`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+  /// Constant Y, spanning the X range.
+  Horizontal,
+  /// Constant X, spanning the Y range.
+  Vertical,
+}
+
+/// An infinite horizontal or vertical line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RefLine {
+  /// Which axis the line is perpendicular to.
+  pub orientation: Orientation,
+  /// Data value (Y for horizontal, X for vertical lines).
+  pub value: f64,
+  /// RGBA color.
+  pub color: Vec4,
+  /// Width in points.
+  pub width: f32,
+  /// Dash pattern.
+  pub style: LineStyle,
+}
+
+impl RefLine {
+  /// Solid black 1pt horizontal line at `y`.
+  pub fn horizontal(y: f64) -> Self {
+    Self::new(Orientation::Horizontal, y)
+  }
+
+  /// Solid black 1pt vertical line at `x`.
+  pub fn vertical(x: f64) -> Self {
+    Self::new(Orientation::Vertical, x)
+  }
+
+  fn new(orientation: Orientation, value: f64) -> Self {
+    Self {
+      orientation,
+      value,
+      color: Vec4::new(0.0, 0.0, 0.0, 1.0),
+      width: 1.0,
+      style: LineStyle::Solid,
+    }
+  }
+
+  /// Sets the color.
+  pub fn color(mut self, color: Vec4) -> Self {
+    self.color = color;
+    self
+  }
+
+  /// Sets the width in points.
+  pub fn width(mut self, width: f32) -> Self {
+    self.width = width;
+    self
+  }
+
+  /// Sets the dash pattern.
+  pub fn style(mut self, style: LineStyle) -> Self {
+    self.style = style;
+    self
+  }
+
+  /// End points in pixels across the axes box of `ctx`, or `None` if
+  /// the value is outside the current limits.
+  fn span(&self, ctx: &DrawContext<'_>) -> Option<(Vec2, Vec2)> {
+    let l = ctx.limits();
+    let [left, top, right, bottom] = ctx.pixel_box();
+    let (lo, hi) = match self.orientation {
+      Orientation::Horizontal => (l.y_min.min(l.y_max), l.y_min.max(l.y_max)),
+      Orientation::Vertical => (l.x_min.min(l.x_max), l.x_min.max(l.x_max)),
+    };
+    if !(lo..=hi).contains(&self.value) {
+      return None;
+    }
+    Some(match self.orientation {
+      Orientation::Horizontal => {
+        let y = ctx.to_pixels(l.x_min, self.value).y;
+        (Vec2::new(left, y), Vec2::new(right, y))
+      }
+      Orientation::Vertical => {
+        let x = ctx.to_pixels(self.value, l.y_min).x;
+        (Vec2::new(x, top), Vec2::new(x, bottom))
+      }
+    })
+  }
+}
+
+impl Artist for RefLine {
+  fn draw(&self, ctx: &mut DrawContext<'_>) {
+    let Some((a, b)) = self.span(ctx) else { return };
+    let (dash, gap) = self.style.pattern();
+    let (a, b) = (ctx.screen(a), ctx.screen(b));
+    ctx.dashed_line_pixels(a, b, self.width, self.color, dash, gap);
+  }
+
+  fn extent(&self) -> Option<DataRect> {
+    None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::artist::Viewport;
+  use crate::recording::{RecordedPrimitive, Recording};
+  use crate::units::SizeScale;
+  use glam::{Mat4, Vec3};
+
+  fn draw(line: &RefLine) -> Vec<RecordedPrimitive> {
+    let mut rec = Recording::new(200, 200);
+    let viewport = Viewport::new(
+      DataRect::new((0.0, 10.0), (-5.0, 5.0)),
+      [20.0, 10.0, 180.0, 190.0],
+    );
+    let mut ctx = DrawContext::new(
+      None,
+      None,
+      Some(&mut rec),
+      Mat4::IDENTITY,
+      SizeScale::default(),
+      viewport,
+    );
+    line.draw(&mut ctx);
+    rec.primitives
+  }
+
+  #[test]
+  fn horizontal_line_spans_axes_box() {
+    let prims = draw(&RefLine::horizontal(0.0).width(2.0).style(LineStyle::Dashed));
+    match &prims[..] {
+      [RecordedPrimitive::Line { start, end, width, dash_len, .. }] => {
+        assert_eq!(*start, Vec3::new(20.0, 100.0, 0.0));
+        assert_eq!(*end, Vec3::new(180.0, 100.0, 0.0));
+        assert_eq!(*width, 2.0);
+        assert!((*dash_len - 7.4).abs() < 1e-5);
+      }
+      other => panic!("unexpected primitives: {:?}", other),
+    }
+  }
+
+  #[test]
+  fn vertical_line_and_out_of_range() {
+    let prims = draw(&RefLine::vertical(5.0));
+    match &prims[..] {
+      [RecordedPrimitive::Line { start, end, .. }] => {
+        assert_eq!(*start, Vec3::new(100.0, 10.0, 0.0));
+        assert_eq!(*end, Vec3::new(100.0, 190.0, 0.0));
+      }
+      other => panic!("unexpected primitives: {:?}", other),
+    }
+    assert!(draw(&RefLine::horizontal(6.0)).is_empty());
+    assert!(RefLine::vertical(1e9).extent().is_none());
+  }
+
+  #[test]
+  fn style_strings() {
+    assert_eq!(LineStyle::parse("--"), Some(LineStyle::Dashed));
+    assert_eq!(LineStyle::parse(":"), Some(LineStyle::Dotted));
+    assert_eq!(LineStyle::parse("-"), Some(LineStyle::Solid));
+    assert_eq!(LineStyle::parse("-."), None);
+  }
+}
@@ -0,0 +1,181 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Drawing plots into an application's own swapchain.
+//!
+//! Applications that already own a wgpu device and surface otherwise
+//! have to clear and prepare both renderers, open a render pass and, for
+//! smooth edges, manage a multisampled texture and its resolve.  A
+//! [`ScreenRenderer`] does all of that: create it from a
+//! [`RenderContext`] on the application's device, keep it in sync with
+//! the surface through [`resize`](ScreenRenderer::resize), and record
+//! each frame with [`draw`](ScreenRenderer::draw) into the application's
+//! encoder.
+//!
+//! ```rust,no_run
+//! # fn demo(ctx: mpl_wgpu::context::RenderContext, surface: wgpu::Surface, plot: &mut mpl_wgpu::plotting::PlotBackend) {
+//! use mpl_wgpu::screen::ScreenRenderer;
+//!
+//! let mut screen = ScreenRenderer::new(&ctx, 800, 600).with_msaa(4);
+//! let frame = surface.get_current_texture().unwrap();
+//! let view = frame.texture.create_view(&Default::default());
+//! let mut encoder = ctx.device().create_command_encoder(&Default::default());
+//! screen.draw(&mut encoder, &view, plot);
+//! ctx.queue().submit([encoder.finish()]);
+//! frame.present();
+//! # }
+//! ```
+
+use crate::artist::RenderWarning;
+use crate::context::RenderContext;
+use crate::plotting::PlotBackend;
+use crate::primitives::PrimitiveRenderer;
+use crate::text::TextRenderer;
+
+/// Primitive and text renderers drawing plots into a surface.
+pub struct ScreenRenderer {
+  ctx: RenderContext,
+  prim: PrimitiveRenderer,
+  text: TextRenderer,
+  /// Multisampled color target resolved into the frame, when sampling.
+  msaa: Option<wgpu::TextureView>,
+  width: u32,
+  height: u32,
+  clear: Option<wgpu::Color>,
+}
+
+impl ScreenRenderer {
+  /// Renderer for a `width` x `height` surface of the context's format,
+  /// clearing to white and without multisampling.
+  pub fn new(ctx: &RenderContext, width: u32, height: u32) -> Self {
+    let (width, height) = (width.max(1), height.max(1));
+    let (prim, text) = ctx.renderers(width, height);
+    let mut screen = Self {
+      ctx: ctx.clone(),
+      prim,
+      text,
+      msaa: None,
+      width,
+      height,
+      clear: Some(wgpu::Color::WHITE),
+    };
+    screen.msaa = screen.msaa_target();
+    screen
+  }
+
+  /// Draws with `samples` samples per pixel, resolved into the frame;
+  /// 1 turns multisampling off.  Rebuilds the renderers.
+  pub fn with_msaa(mut self, samples: u32) -> Self {
+    self.ctx = self.ctx.with_sample_count(samples.max(1));
+    (self.prim, self.text) = self.ctx.renderers(self.width, self.height);
+    self.msaa = self.msaa_target();
+    self
+  }
+
+  /// Sets the color the frame is cleared to, or `None` to draw over its
+  /// contents.  Multisampled drawing always clears, to white for `None`.
+  pub fn set_clear_color(&mut self, color: Option<wgpu::Color>) {
+    self.clear = color;
+  }
+
+  /// Samples per pixel.
+  pub fn sample_count(&self) -> u32 {
+    self.ctx.sample_count()
+  }
+
+  /// Surface size in pixels.
+  pub fn size(&self) -> (u32, u32) {
+    (self.width, self.height)
+  }
+
+  /// Follows a surface reconfigured to `width` x `height`.  The plot is
+  /// resized on the next [`draw`](Self::draw).
+  pub fn resize(&mut self, width: u32, height: u32) {
+    let (width, height) = (width.max(1), height.max(1));
+    if (width, height) == (self.width, self.height) {
+      return;
+    }
+    self.width = width;
+    self.height = height;
+    self.prim.resize(self.ctx.queue(), width, height);
+    self.text.resize(width, height);
+    self.msaa = self.msaa_target();
+  }
+
+  /// The primitive renderer, for drawing extra primitives after
+  /// [`draw`](Self::draw) queued the plot.
+  pub fn primitive_renderer(&mut self) -> &mut PrimitiveRenderer {
+    &mut self.prim
+  }
+
+  /// The text renderer.
+  pub fn text_renderer(&mut self) -> &mut TextRenderer {
+    &mut self.text
+  }
+
+  /// Records a pass drawing `plot` into `view`, resizing the plot to the
+  /// surface first.  Submitting `encoder` is left to the caller.
+  pub fn draw(
+    &mut self,
+    encoder: &mut wgpu::CommandEncoder,
+    view: &wgpu::TextureView,
+    plot: &mut PlotBackend,
+  ) -> Vec<RenderWarning> {
+    if plot.size() != (self.width, self.height) {
+      plot.resize(self.width, self.height);
+    }
+    self.prim.clear();
+    self.text.clear();
+    let warnings = plot.render(&mut self.prim, &mut self.text, None);
+    self.prim.prepare(self.ctx.device(), self.ctx.queue());
+    self.text.prepare(self.ctx.device(), self.ctx.queue());
+
+    let (target, resolve_target, load, store) = match &self.msaa {
+      // The samples are only needed until they are resolved.
+      Some(msaa) => (
+        msaa,
+        Some(view),
+        wgpu::LoadOp::Clear(self.clear.unwrap_or(wgpu::Color::WHITE)),
+        wgpu::StoreOp::Discard,
+      ),
+      None => (
+        view,
+        None,
+        self.clear.map_or(wgpu::LoadOp::Load, wgpu::LoadOp::Clear),
+        wgpu::StoreOp::Store,
+      ),
+    };
+    let mut rp = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+      label: Some("ScreenPass"),
+      color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+        view: target,
+        resolve_target,
+        ops: wgpu::Operations { load, store },
+      })],
+      depth_stencil_attachment: None,
+      ..Default::default()
+    });
+    self.prim.render(&mut rp);
+    self.text.render(&mut rp);
+    warnings
+  }
+
+  /// Multisampled texture matching the surface, if sampling.
+  fn msaa_target(&self) -> Option<wgpu::TextureView> {
+    let samples = self.ctx.sample_count();
+    if samples <= 1 {
+      return None;
+    }
+    let texture = self.ctx.device().create_texture(&wgpu::TextureDescriptor {
+      label: Some("ScreenMsaa"),
+      size: wgpu::Extent3d { width: self.width, height: self.height, depth_or_array_layers: 1 },
+      mip_level_count: 1,
+      sample_count: samples,
+      dimension: wgpu::TextureDimension::D2,
+      format: self.ctx.format(),
+      usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+      view_formats: &[],
+    });
+    Some(texture.create_view(&wgpu::TextureViewDescriptor::default()))
+  }
+}
@@ -0,0 +1,361 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Reference geometry in data coordinates.
+//!
+//! A [`Shape`] is a circle, ellipse, rectangle or polygon given in data
+//! units, such as a unit circle behind a Nyquist plot or a tolerance
+//! box.  [`ShapePatch`] draws it with a [`ShapeStyle`] (fill, stroke or
+//! both), clipped to the axes box, and optionally widens the axis limits
+//! to include it.  Add shapes with
+//! [`PlotBackend::add_shape`](crate::plotting::PlotBackend::add_shape).
+
+use crate::artist::{Artist, DataRect, DrawContext};
+use glam::Vec4;
+use std::f64::consts::TAU;
+
+/// Vertices used to outline circles and ellipses.
+const CURVE_SEGMENTS: usize = 96;
+
+/// A closed outline in data coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+  /// Circle around `center`.
+  Circle {
+    /// Center point.
+    center: (f64, f64),
+    /// Radius in data units.
+    radius: f64,
+  },
+  /// Ellipse around `center`, rotated `angle` degrees counter-clockwise.
+  Ellipse {
+    /// Center point.
+    center: (f64, f64),
+    /// Full width along the unrotated X axis.
+    width: f64,
+    /// Full height along the unrotated Y axis.
+    height: f64,
+    /// Rotation in degrees.
+    angle: f64,
+  },
+  /// Axis-aligned rectangle.
+  Rectangle {
+    /// Lower-left corner.
+    corner: (f64, f64),
+    /// Width in data units.
+    width: f64,
+    /// Height in data units.
+    height: f64,
+  },
+  /// Simple polygon; the last point connects back to the first.
+  Polygon {
+    /// Vertices in order.
+    points: Vec<(f64, f64)>,
+  },
+}
+
+impl Shape {
+  /// Rectangle spanning `x0..x1` and `y0..y1`, in either order.
+  pub fn rect(x0: f64, x1: f64, y0: f64, y1: f64) -> Self {
+    Shape::Rectangle {
+      corner: (x0.min(x1), y0.min(y1)),
+      width: (x1 - x0).abs(),
+      height: (y1 - y0).abs(),
+    }
+  }
+
+  /// Outline vertices, without repeating the first point.
+  pub fn outline(&self) -> Vec<(f64, f64)> {
+    match self {
+      Shape::Circle { center, radius } => ellipse_points(*center, *radius, *radius, 0.0),
+      Shape::Ellipse { center, width, height, angle } => {
+        ellipse_points(*center, width * 0.5, height * 0.5, angle.to_radians())
+      }
+      Shape::Rectangle { corner: (x, y), width, height } => vec![
+        (*x, *y),
+        (x + width, *y),
+        (x + width, y + height),
+        (*x, y + height),
+      ],
+      Shape::Polygon { points } => points.clone(),
+    }
+  }
+}
+
+fn ellipse_points(center: (f64, f64), rx: f64, ry: f64, angle: f64) -> Vec<(f64, f64)> {
+  let (s, c) = angle.sin_cos();
+  (0..CURVE_SEGMENTS)
+    .map(|i| {
+      let t = TAU * i as f64 / CURVE_SEGMENTS as f64;
+      let (x, y) = (rx * t.cos(), ry * t.sin());
+      (center.0 + x * c - y * s, center.1 + x * s + y * c)
+    })
+    .collect()
+}
+
+/// Fill and stroke of a shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeStyle {
+  /// Fill color, or `None` for an outline only.
+  pub fill: Option<Vec4>,
+  /// Stroke color, or `None` for no outline.
+  pub stroke: Option<Vec4>,
+  /// Stroke width in points.
+  pub stroke_width: f32,
+  /// Whether the shape widens the axis limits.
+  pub autoscale: bool,
+}
+
+impl Default for ShapeStyle {
+  /// Black 1pt outline without fill, not autoscaled.
+  fn default() -> Self {
+    Self {
+      fill: None,
+      stroke: Some(Vec4::new(0.0, 0.0, 0.0, 1.0)),
+      stroke_width: 1.0,
+      autoscale: false,
+    }
+  }
+}
+
+impl ShapeStyle {
+  /// Filled without outline and widening the axis limits, as for heat
+  /// tiles and Gantt bars.
+  pub fn tile(color: Vec4) -> Self {
+    Self { fill: Some(color), stroke: None, stroke_width: 0.0, autoscale: true }
+  }
+
+  /// Sets the fill color.
+  pub fn fill(mut self, color: Vec4) -> Self {
+    self.fill = Some(color);
+    self
+  }
+
+  /// Sets the stroke color and width, or removes the outline with
+  /// `None`.
+  pub fn stroke(mut self, color: Option<Vec4>, width: f32) -> Self {
+    self.stroke = color;
+    self.stroke_width = width;
+    self
+  }
+
+  /// Sets whether the shape widens the axis limits.
+  pub fn autoscale(mut self, autoscale: bool) -> Self {
+    self.autoscale = autoscale;
+    self
+  }
+}
+
+/// A styled [`Shape`] drawn as an [`Artist`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapePatch {
+  /// Geometry.
+  pub shape: Shape,
+  /// Fill and stroke.
+  pub style: ShapeStyle,
+}
+
+impl ShapePatch {
+  /// `shape` drawn with `style`.
+  pub fn new(shape: Shape, style: ShapeStyle) -> Self {
+    Self { shape, style }
+  }
+}
+
+impl Artist for ShapePatch {
+  fn draw(&self, ctx: &mut DrawContext<'_>) {
+    let outline = self.shape.outline();
+    let limits = ctx.limits();
+    if let Some(color) = self.style.fill {
+      let clipped = clip_polygon(&outline, &limits);
+      for [a, b, c] in triangulate(&clipped) {
+        ctx.triangle([clipped[a], clipped[b], clipped[c]], color);
+      }
+    }
+    if let Some(color) = self.style.stroke {
+      let n = outline.len();
+      for i in 0..n {
+        if let Some((a, b)) = clip_segment(outline[i], outline[(i + 1) % n], &limits) {
+          ctx.line(a, b, self.style.stroke_width, color);
+        }
+      }
+    }
+  }
+
+  fn extent(&self) -> Option<DataRect> {
+    if !self.style.autoscale {
+      return None;
+    }
+    let (x, y): (Vec<f64>, Vec<f64>) = self.shape.outline().into_iter().unzip();
+    DataRect::from_points(&x, &y)
+  }
+}
+
+/// Ordered bounds of `r` as `(x_min, x_max, y_min, y_max)`.
+fn bounds(r: &DataRect) -> (f64, f64, f64, f64) {
+  (r.x_min.min(r.x_max), r.x_min.max(r.x_max), r.y_min.min(r.y_max), r.y_min.max(r.y_max))
+}
+
+/// The part of the segment `a`-`b` inside `rect` (Liang-Barsky), if any.
+pub fn clip_segment(a: (f64, f64), b: (f64, f64), rect: &DataRect) -> Option<((f64, f64), (f64, f64))> {
+  let (x0, x1, y0, y1) = bounds(rect);
+  let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+  let (mut t0, mut t1) = (0.0f64, 1.0f64);
+  for (p, q) in [(-dx, a.0 - x0), (dx, x1 - a.0), (-dy, a.1 - y0), (dy, y1 - a.1)] {
+    if p == 0.0 {
+      if q < 0.0 {
+        return None;
+      }
+    } else {
+      let t = q / p;
+      if p < 0.0 {
+        t0 = t0.max(t);
+      } else {
+        t1 = t1.min(t);
+      }
+    }
+  }
+  (t0 <= t1).then_some(((a.0 + dx * t0, a.1 + dy * t0), (a.0 + dx * t1, a.1 + dy * t1)))
+}
+
+/// `polygon` clipped to `rect` (Sutherland-Hodgman).
+pub fn clip_polygon(polygon: &[(f64, f64)], rect: &DataRect) -> Vec<(f64, f64)> {
+  let (x0, x1, y0, y1) = bounds(rect);
+  // Each edge: inside test and intersection with the boundary line.
+  type Inside = fn((f64, f64), f64) -> bool;
+  let edges: [(Inside, f64, bool); 4] = [
+    (|p, v| p.0 >= v, x0, true),
+    (|p, v| p.0 <= v, x1, true),
+    (|p, v| p.1 >= v, y0, false),
+    (|p, v| p.1 <= v, y1, false),
+  ];
+  let mut out = polygon.to_vec();
+  for (inside, v, vertical) in edges {
+    let input = std::mem::take(&mut out);
+    let n = input.len();
+    for i in 0..n {
+      let (p, q) = (input[i], input[(i + 1) % n]);
+      let cross = |p: (f64, f64), q: (f64, f64)| {
+        if vertical {
+          let t = (v - p.0) / (q.0 - p.0);
+          (v, p.1 + (q.1 - p.1) * t)
+        } else {
+          let t = (v - p.1) / (q.1 - p.1);
+          (p.0 + (q.0 - p.0) * t, v)
+        }
+      };
+      match (inside(p, v), inside(q, v)) {
+        (true, true) => out.push(q),
+        (true, false) => out.push(cross(p, q)),
+        (false, true) => {
+          out.push(cross(p, q));
+          out.push(q);
+        }
+        (false, false) => {}
+      }
+    }
+  }
+  out
+}
+
+/// Twice the signed area of triangle `(a, b, c)`; positive if
+/// counter-clockwise.
+fn cross(a: (f64, f64), b: (f64, f64), c: (f64, f64)) -> f64 {
+  (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+/// Ear-clipping triangulation of a simple polygon, as vertex index
+/// triples.  Degenerate input falls back to a fan.
+pub fn triangulate(points: &[(f64, f64)]) -> Vec<[usize; 3]> {
+  let n = points.len();
+  let mut out = Vec::new();
+  if n < 3 {
+    return out;
+  }
+  let mut idx: Vec<usize> = (0..n).collect();
+  let area: f64 = (0..n)
+    .map(|i| {
+      let (p, q) = (points[i], points[(i + 1) % n]);
+      p.0 * q.1 - q.0 * p.1
+    })
+    .sum();
+  if area < 0.0 {
+    idx.reverse();
+  }
+  while idx.len() > 3 {
+    let m = idx.len();
+    let ear = (0..m).find(|&i| {
+      let (a, b, c) = (idx[(i + m - 1) % m], idx[i], idx[(i + 1) % m]);
+      let (pa, pb, pc) = (points[a], points[b], points[c]);
+      cross(pa, pb, pc) > 0.0
+        && !idx.iter().any(|&k| {
+          k != a
+            && k != b
+            && k != c
+            && cross(pa, pb, points[k]) >= 0.0
+            && cross(pb, pc, points[k]) >= 0.0
+            && cross(pc, pa, points[k]) >= 0.0
+        })
+    });
+    let Some(i) = ear else { break };
+    out.push([idx[(i + m - 1) % m], idx[i], idx[(i + 1) % m]]);
+    idx.remove(i);
+  }
+  for i in 1..idx.len().saturating_sub(1) {
+    out.push([idx[0], idx[i], idx[i + 1]]);
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn outlines() {
+    let circle = Shape::Circle { center: (1.0, 2.0), radius: 1.0 }.outline();
+    assert_eq!(circle.len(), CURVE_SEGMENTS);
+    assert!(circle.iter().all(|p| ((p.0 - 1.0).hypot(p.1 - 2.0) - 1.0).abs() < 1e-12));
+    let rect = Shape::Rectangle { corner: (0.0, 0.0), width: 2.0, height: 1.0 }.outline();
+    assert_eq!(rect, vec![(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (0.0, 1.0)]);
+    let e = Shape::Ellipse { center: (0.0, 0.0), width: 4.0, height: 2.0, angle: 90.0 }.outline();
+    assert!(e[0].0.abs() < 1e-12 && (e[0].1 - 2.0).abs() < 1e-12);
+  }
+
+  #[test]
+  fn concave_polygon_triangulates() {
+    // L shape, area 3.
+    let l = [(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0), (1.0, 2.0), (0.0, 2.0)];
+    let tris = triangulate(&l);
+    assert_eq!(tris.len(), 4);
+    let area: f64 = tris.iter().map(|t| cross(l[t[0]], l[t[1]], l[t[2]]) * 0.5).sum();
+    assert!((area - 3.0).abs() < 1e-12);
+  }
+
+  #[test]
+  fn clipping_to_the_axes() {
+    let r = DataRect::new((0.0, 1.0), (0.0, 1.0));
+    assert_eq!(clip_segment((-1.0, 0.5), (2.0, 0.5), &r), Some(((0.0, 0.5), (1.0, 0.5))));
+    assert_eq!(clip_segment((-1.0, 2.0), (2.0, 2.0), &r), None);
+    let square = [(0.5, 0.5), (1.5, 0.5), (1.5, 1.5), (0.5, 1.5)];
+    let clipped = clip_polygon(&square, &r);
+    let (x, y): (Vec<f64>, Vec<f64>) = clipped.into_iter().unzip();
+    assert_eq!(DataRect::from_points(&x, &y), Some(DataRect::new((0.5, 1.0), (0.5, 1.0))));
+  }
+
+  #[test]
+  fn autoscale_is_opt_in() {
+    let shape = Shape::Circle { center: (0.0, 0.0), radius: 1.0 };
+    assert!(ShapePatch::new(shape.clone(), ShapeStyle::default()).extent().is_none());
+    let ext = ShapePatch::new(shape, ShapeStyle::default().autoscale(true)).extent().unwrap();
+    assert!((ext.x_max - 1.0).abs() < 1e-12 && (ext.y_min + 1.0).abs() < 1e-12);
+  }
+
+  #[test]
+  fn tiles_from_spans() {
+    let tile = ShapePatch::new(Shape::rect(3.0, 1.0, 2.0, 5.0), ShapeStyle::tile(Vec4::ONE));
+    assert_eq!(tile.shape, Shape::Rectangle { corner: (1.0, 2.0), width: 2.0, height: 3.0 });
+    assert_eq!(tile.extent(), Some(DataRect::new((1.0, 3.0), (2.0, 5.0))));
+    assert!(tile.style.stroke.is_none());
+  }
+}
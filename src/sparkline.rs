@@ -0,0 +1,230 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Word-sized line charts.
+//!
+//! A [`Sparkline`] draws a series straight into a pixel rectangle of a
+//! [`PrimitiveRenderer`], without a figure, margins, axes or ticks, so a
+//! dashboard table can show hundreds of them from one pair of renderers
+//! (see [`crate::context`]).  Long series are reduced to the minimum
+//! and maximum of each pixel column before drawing, so the instance
+//! count follows the width, not the data.  Optional dots mark the
+//! minimum and maximum, and the last value can be printed to the right.
+
+use crate::autoscale::{finite_range, sanitize_range};
+use crate::primitives::PrimitiveRenderer;
+use crate::text::TextRenderer;
+use crate::ticks::{Auto, TickFormatter};
+use glam::{Vec2, Vec4};
+
+/// Gap between the line and the last-value label, in pixels.
+const LABEL_GAP: f32 = 3.0;
+
+/// Style of a sparkline; one instance can draw any number of series.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sparkline {
+  /// Line color, also used for the last-value label.
+  pub color: Vec4,
+  /// Line width, in pixels.
+  pub line_width: f32,
+  /// Color of the minimum and maximum dots, or `None` for no dots.
+  pub extrema: Option<Vec4>,
+  /// Dot radius, in pixels.
+  pub dot_radius: f32,
+  /// Whether the last finite value is printed right of the line.
+  pub last_value: bool,
+  /// Label font size, in pixels.
+  pub font_size: f32,
+  /// Fixed value range, so rows of a table share one scale; `None`
+  /// fits each series.
+  pub range: Option<(f64, f64)>,
+}
+
+impl Default for Sparkline {
+  fn default() -> Self {
+    Self {
+      color: Vec4::new(0.12, 0.47, 0.71, 1.0),
+      line_width: 1.0,
+      extrema: None,
+      dot_radius: 1.5,
+      last_value: false,
+      font_size: 10.0,
+      range: None,
+    }
+  }
+}
+
+impl Sparkline {
+  /// Thin blue line without dots or label.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the line color.
+  pub fn color(mut self, color: Vec4) -> Self {
+    self.color = color;
+    self
+  }
+
+  /// Sets the line width, in pixels.
+  pub fn line_width(mut self, width: f32) -> Self {
+    self.line_width = width;
+    self
+  }
+
+  /// Marks the minimum and maximum with dots of `color`.
+  pub fn extrema(mut self, color: Vec4) -> Self {
+    self.extrema = Some(color);
+    self
+  }
+
+  /// Sets whether the last value is printed.
+  pub fn last_value(mut self, show: bool) -> Self {
+    self.last_value = show;
+    self
+  }
+
+  /// Fixes the value range instead of fitting each series.
+  pub fn range(mut self, lo: f64, hi: f64) -> Self {
+    self.range = Some((lo, hi));
+    self
+  }
+
+  /// Queues `values` drawn into the `size` pixel rectangle at `pos`
+  /// (top-left).  Samples are evenly spaced; non-finite ones leave
+  /// gaps.  `text` is only needed for the last-value label.
+  pub fn draw(
+    &self,
+    values: &[f64],
+    pos: Vec2,
+    size: Vec2,
+    prim: &mut PrimitiveRenderer,
+    text: Option<&mut TextRenderer>,
+  ) {
+    let Some(range) = self.range.or_else(|| finite_range(values)) else {
+      return;
+    };
+    let (lo, hi) = sanitize_range(range);
+
+    let mut width = size.x;
+    let label = match (self.last_value, text) {
+      (true, Some(text)) => values.iter().rev().find(|v| v.is_finite()).map(|&v| {
+        let label = Auto.format(v, v);
+        let w = text.measure_text(&label, self.font_size).x;
+        width = (width - w - LABEL_GAP).max(0.0);
+        (text, label)
+      }),
+      _ => None,
+    };
+
+    // Inset so dots and thick lines stay inside the rectangle.
+    let dot = if self.extrema.is_some() { self.dot_radius } else { 0.0 };
+    let pad = (self.line_width * 0.5).max(dot);
+    let plot = Vec2::new((width - 2.0 * pad).max(0.0), (size.y - 2.0 * pad).max(0.0));
+    let origin = pos + Vec2::splat(pad);
+    let last = values.len().saturating_sub(1).max(1) as f32;
+    let at = |i: usize| {
+      let t = ((values[i] - lo) / (hi - lo)) as f32;
+      let x = if values.len() == 1 { 0.5 } else { i as f32 / last };
+      origin + Vec2::new(x * plot.x, (1.0 - t) * plot.y)
+    };
+
+    let kept = decimate(values, plot.x.ceil() as usize);
+    for pair in kept.windows(2) {
+      let (a, b) = (pair[0], pair[1]);
+      if values[a].is_finite() && values[b].is_finite() {
+        prim.draw_line(at(a).extend(0.0), at(b).extend(0.0), self.line_width, self.color, 0.0, 0.0, 0.0);
+      }
+    }
+    if let [i] = kept[..] {
+      if values[i].is_finite() {
+        prim.draw_circle(at(i).extend(0.0), self.line_width, self.color, 0.0, 1);
+      }
+    }
+
+    if let Some(color) = self.extrema {
+      if let Some((min, max)) = extrema(values) {
+        for i in [min, max] {
+          prim.draw_circle(at(i).extend(0.0), self.dot_radius, color, 0.0, 1);
+        }
+      }
+    }
+
+    if let Some((text, label)) = label {
+      let i = values.iter().rposition(|v| v.is_finite()).unwrap_or(0);
+      let y = (at(i).y - self.font_size * 0.5).clamp(pos.y, (pos.y + size.y - self.font_size).max(pos.y));
+      text.draw_text(&label, Vec2::new(pos.x + width + LABEL_GAP, y), self.font_size, self.color);
+    }
+  }
+}
+
+/// Indices of `values` worth drawing in `columns` pixel columns: all of
+/// them when they fit, else the first, minimum, maximum and last sample
+/// of each column, in order.  A column without finite samples keeps one
+/// non-finite index so the line breaks there.
+pub fn decimate(values: &[f64], columns: usize) -> Vec<usize> {
+  let n = values.len();
+  if n <= columns.max(1) * 4 {
+    return (0..n).collect();
+  }
+  let mut kept = Vec::with_capacity(columns * 4);
+  for c in 0..columns {
+    let bucket = c * n / columns..(c + 1) * n / columns;
+    let Some((min, max)) = extrema(&values[bucket.clone()]) else {
+      kept.push(bucket.start);
+      continue;
+    };
+    let finite = |i: &usize| values[*i].is_finite();
+    let first = bucket.clone().find(finite).unwrap_or(bucket.start);
+    let last = bucket.clone().rev().find(finite).unwrap_or(bucket.start);
+    let (min, max) = (bucket.start + min, bucket.start + max);
+    let mut four = [first, min, max, last];
+    four.sort_unstable();
+    for i in four {
+      if kept.last() != Some(&i) {
+        kept.push(i);
+      }
+    }
+  }
+  kept
+}
+
+/// Indices of the first minimum and first maximum finite values.
+pub fn extrema(values: &[f64]) -> Option<(usize, usize)> {
+  let mut finite = values.iter().enumerate().filter(|(_, v)| v.is_finite());
+  let (first, _) = finite.next()?;
+  Some(finite.fold((first, first), |(min, max), (i, &v)| {
+    (if v < values[min] { i } else { min }, if v > values[max] { i } else { max })
+  }))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn decimation_keeps_peaks() {
+    let short = [1.0, 2.0, 3.0];
+    assert_eq!(decimate(&short, 10), vec![0, 1, 2]);
+
+    let mut long = vec![0.0; 1000];
+    long[437] = 5.0;
+    long[612] = -5.0;
+    let kept = decimate(&long, 10);
+    assert!(kept.len() <= 40);
+    assert!(kept.contains(&437) && kept.contains(&612));
+    assert!(kept.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!((kept[0], *kept.last().unwrap()), (0, 999));
+
+    let mut gap = vec![1.0; 1000];
+    gap[100..200].fill(f64::NAN);
+    assert!(decimate(&gap, 10).contains(&100));
+  }
+
+  #[test]
+  fn extrema_skip_non_finite() {
+    assert_eq!(extrema(&[f64::NAN, 3.0, 1.0, 4.0, 1.0]), Some((2, 3)));
+    assert_eq!(extrema(&[f64::NAN]), None);
+    assert_eq!(extrema(&[]), None);
+  }
+}
@@ -0,0 +1,390 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Plain-data description of a figure.
+//!
+//! The [`plotting`](crate::plotting) handles point into live matplot++
+//! objects and cannot be stored.  A [`PlotSpec`] holds the same figure
+//! as data: axis settings, line and scatter series, shaded areas and
+//! surfaces.  With the `serde` feature every type here implements
+//! `Serialize` and `Deserialize`, so a figure can be written to JSON or
+//! TOML, reloaded, or sent to another process, and drawn there with
+//! [`PlotSpec::draw`] or built with
+//! [`PlotBackend::from_spec`](crate::plotting::PlotBackend::from_spec).
+//!
+//! Data is given inline or by name.  A [`Data::Ref`] names a column of
+//! [`PlotSpec::data`], so tools can write one table and reference its
+//! columns from several series; in serialized form a column is an array
+//! of numbers and a reference is its name:
+//!
+//! ```json
+//! {
+//!   "data": { "t": [0, 1, 2, 3], "temp": [20.5, 21.0, 21.8, 21.2] },
+//!   "axes": { "title": "Sensor", "xlabel": "t [s]", "grid": true },
+//!   "series": [{ "kind": "line", "x": "t", "y": "temp", "style": "-o", "color": [0.8, 0.1, 0.1] }]
+//! }
+//! ```
+
+use crate::error::{Error, Result};
+use crate::plotting::Axes;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Named data columns of a [`PlotSpec`].
+pub type DataTable = BTreeMap<String, Vec<f64>>;
+
+/// Values given inline or as the name of a [`DataTable`] column.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum Data {
+  /// Inline values.
+  Values(Vec<f64>),
+  /// Name of a column.
+  Ref(String),
+}
+
+impl Default for Data {
+  fn default() -> Self {
+    Data::Values(Vec::new())
+  }
+}
+
+impl From<Vec<f64>> for Data {
+  fn from(values: Vec<f64>) -> Self {
+    Data::Values(values)
+  }
+}
+
+impl From<&[f64]> for Data {
+  fn from(values: &[f64]) -> Self {
+    Data::Values(values.to_vec())
+  }
+}
+
+impl From<&str> for Data {
+  fn from(name: &str) -> Self {
+    Data::Ref(name.into())
+  }
+}
+
+impl Data {
+  /// The values, looking references up in `table`.  Fails with
+  /// [`Error::UnknownData`] for a name `table` does not have.
+  pub fn resolve<'a>(&'a self, table: &'a DataTable) -> Result<&'a [f64]> {
+    match self {
+      Data::Values(v) => Ok(v),
+      Data::Ref(name) => table.get(name).map(Vec::as_slice).ok_or_else(|| Error::UnknownData(name.clone())),
+    }
+  }
+}
+
+/// Titles, labels, limits and grid of one axes.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct AxisConfig {
+  /// Axes title.
+  pub title: Option<String>,
+  /// X axis label.
+  pub xlabel: Option<String>,
+  /// Y axis label.
+  pub ylabel: Option<String>,
+  /// Fixed X limits; `None` autoscales.
+  pub xlim: Option<(f64, f64)>,
+  /// Fixed Y limits; `None` autoscales.
+  pub ylim: Option<(f64, f64)>,
+  /// Fixed color limits of the surfaces; `None` autoscales.
+  pub clim: Option<(f64, f64)>,
+  /// Whether grid lines are shown.
+  pub grid: bool,
+}
+
+impl AxisConfig {
+  /// Applies the settings to `ax`.
+  pub fn apply(&self, ax: &Axes<'_>) {
+    if let Some(title) = &self.title {
+      ax.set_title(title);
+    }
+    if let Some(label) = &self.xlabel {
+      ax.set_xlabel(label);
+    }
+    if let Some(label) = &self.ylabel {
+      ax.set_ylabel(label);
+    }
+    if let Some((lo, hi)) = self.xlim {
+      ax.set_xlim(lo, hi);
+    }
+    if let Some((lo, hi)) = self.ylim {
+      ax.set_ylim(lo, hi);
+    }
+    if let Some((lo, hi)) = self.clim {
+      ax.set_clim(lo, hi);
+    }
+    ax.grid(self.grid);
+  }
+}
+
+/// How a [`Series`] is drawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum SeriesKind {
+  /// Connected line ([`Axes::plot`]).
+  #[default]
+  Line,
+  /// Markers only ([`Axes::scatter`]).
+  Scatter,
+  /// One bar per `y` value ([`Axes::bar`]); `x` is unused.
+  Bar,
+  /// Histogram of the `y` values ([`Axes::hist`]) with
+  /// [`Series::bins`] bins; `x` is unused.
+  Hist,
+}
+
+/// Bins of a [`SeriesKind::Hist`] series without [`Series::bins`].
+pub const DEFAULT_BINS: usize = 10;
+
+/// Data and style of one line, scatter, bar or histogram series.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Series {
+  /// Plot type.
+  pub kind: SeriesKind,
+  /// X values.
+  pub x: Data,
+  /// Y values.
+  pub y: Data,
+  /// Matplotlib-style format string (`"-o"`, `"--"`); empty for the
+  /// default of `kind`.
+  pub style: String,
+  /// RGB color in `[0, 1]`.
+  pub color: Option<[f32; 3]>,
+  /// Line width, in points.
+  pub width: Option<f32>,
+  /// Marker size, in points.
+  pub marker_size: Option<f32>,
+  /// Legend label.
+  pub label: Option<String>,
+  /// Histogram bins.
+  pub bins: Option<usize>,
+}
+
+impl Series {
+  /// Line through `x`, `y`.
+  pub fn line(x: impl Into<Data>, y: impl Into<Data>) -> Self {
+    Self { x: x.into(), y: y.into(), ..Self::default() }
+  }
+
+  /// Markers at `x`, `y`.
+  pub fn scatter(x: impl Into<Data>, y: impl Into<Data>) -> Self {
+    Self { kind: SeriesKind::Scatter, ..Self::line(x, y) }
+  }
+
+  /// References to columns missing from `table`.
+  fn check(&self, table: &DataTable) -> Result<()> {
+    self.y.resolve(table)?;
+    if matches!(self.kind, SeriesKind::Line | SeriesKind::Scatter) {
+      self.x.resolve(table)?;
+    }
+    Ok(())
+  }
+
+  /// Plots the series into `ax`, looking column references up in
+  /// `table`.
+  pub fn draw(&self, ax: &Axes<'_>, table: &DataTable) -> Result<()> {
+    self.check(table)?;
+    let y = self.y.resolve(table)?;
+    let x = || self.x.resolve(table);
+    let mut s = match (self.kind, self.style.as_str()) {
+      (SeriesKind::Line, style) => ax.plot(x()?, y, style),
+      (SeriesKind::Scatter, "") => ax.scatter(x()?, y, "o"),
+      (SeriesKind::Scatter, style) => ax.scatter(x()?, y, style),
+      (SeriesKind::Bar, _) => {
+        ax.bar(y);
+        return Ok(());
+      }
+      (SeriesKind::Hist, _) => {
+        ax.hist(y, self.bins.unwrap_or(DEFAULT_BINS));
+        return Ok(());
+      }
+    };
+    if let Some([r, g, b]) = self.color {
+      s = s.color(r, g, b);
+    }
+    if let Some(width) = self.width {
+      s = s.width(width);
+    }
+    if let Some(size) = self.marker_size {
+      s = s.marker_size(size);
+    }
+    if let Some(label) = &self.label {
+      s.label(label);
+    }
+    Ok(())
+  }
+}
+
+/// Line with a shaded band between `y_low` and `y_high`.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct AreaSeries {
+  /// X values.
+  pub x: Data,
+  /// Center line.
+  pub y: Data,
+  /// Lower band edge.
+  pub y_low: Data,
+  /// Upper band edge.
+  pub y_high: Data,
+  /// Format string of the center line.
+  pub style: String,
+}
+
+impl AreaSeries {
+  /// Plots the band and its line into `ax`, looking column references
+  /// up in `table`.
+  pub fn draw(&self, ax: &Axes<'_>, table: &DataTable) -> Result<()> {
+    let [x, y, lo, hi] = [&self.x, &self.y, &self.y_low, &self.y_high].map(|d| d.resolve(table));
+    ax.plot_with_band(x?, y?, lo?, hi?, &self.style);
+    Ok(())
+  }
+}
+
+/// Surface over a `rows` x `cols` grid, stored row-major.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Surface {
+  /// X coordinate of every grid point.
+  pub x: Data,
+  /// Y coordinate of every grid point.
+  pub y: Data,
+  /// Height of every grid point.
+  pub z: Data,
+  /// Grid rows.
+  pub rows: usize,
+  /// Grid columns.
+  pub cols: usize,
+  /// Draw the mesh only.
+  pub wireframe: bool,
+}
+
+impl Surface {
+  /// Plots the surface into `ax`, looking column references up in
+  /// `table`.  Grids whose arrays are shorter than `rows * cols` are
+  /// skipped.
+  pub fn draw(&self, ax: &Axes<'_>, table: &DataTable) -> Result<()> {
+    let [x, y, z] = [&self.x, &self.y, &self.z].map(|d| d.resolve(table));
+    let (x, y, z) = (x?, y?, z?);
+    let n = self.rows * self.cols;
+    if n > 0 && x.len() >= n && y.len() >= n && z.len() >= n {
+      ax.surf(x, y, z, self.rows, self.cols, self.wireframe);
+    }
+    Ok(())
+  }
+}
+
+/// A whole single-axes figure as data.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PlotSpec {
+  /// Figure width in pixels.
+  pub width: u32,
+  /// Figure height in pixels.
+  pub height: u32,
+  /// Named columns that [`Data::Ref`]s point into.
+  pub data: DataTable,
+  /// Axis settings.
+  pub axes: AxisConfig,
+  /// Line and scatter series, in draw order.
+  pub series: Vec<Series>,
+  /// Shaded areas, drawn before the series.
+  pub areas: Vec<AreaSeries>,
+  /// Surfaces, drawn before the areas.
+  pub surfaces: Vec<Surface>,
+}
+
+impl Default for PlotSpec {
+  fn default() -> Self {
+    Self {
+      width: 800,
+      height: 600,
+      data: DataTable::new(),
+      axes: AxisConfig::default(),
+      series: Vec::new(),
+      areas: Vec::new(),
+      surfaces: Vec::new(),
+    }
+  }
+}
+
+impl PlotSpec {
+  /// Empty 800 x 600 figure.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Checks that every column reference names a column of
+  /// [`data`](Self::data).
+  pub fn validate(&self) -> Result<()> {
+    let t = &self.data;
+    for s in &self.surfaces {
+      for d in [&s.x, &s.y, &s.z] {
+        d.resolve(t)?;
+      }
+    }
+    for a in &self.areas {
+      for d in [&a.x, &a.y, &a.y_low, &a.y_high] {
+        d.resolve(t)?;
+      }
+    }
+    self.series.iter().try_for_each(|s| s.check(t))
+  }
+
+  /// Draws everything into `ax` and applies the axis settings last, so
+  /// fixed limits win over autoscaling.  Nothing is drawn if the spec
+  /// does not [`validate`](Self::validate).
+  pub fn draw(&self, ax: &Axes<'_>) -> Result<()> {
+    self.validate()?;
+    ax.holding(|| -> Result<()> {
+      for surface in &self.surfaces {
+        surface.draw(ax, &self.data)?;
+      }
+      for area in &self.areas {
+        area.draw(ax, &self.data)?;
+      }
+      for series in &self.series {
+        series.draw(ax, &self.data)?;
+      }
+      Ok(())
+    })?;
+    self.axes.apply(ax);
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn references_resolve_against_the_table() {
+    let mut spec = PlotSpec::new();
+    spec.data.insert("t".into(), vec![0.0, 1.0, 2.0]);
+    spec.series.push(Series::line("t", vec![1.0, 4.0, 9.0]));
+    assert!(spec.validate().is_ok());
+    assert_eq!(spec.series[0].x.resolve(&spec.data).unwrap(), &[0.0, 1.0, 2.0]);
+
+    spec.areas.push(AreaSeries { y_high: "hi".into(), ..AreaSeries::default() });
+    assert!(matches!(spec.validate(), Err(Error::UnknownData(name)) if name == "hi"));
+
+    // Bars and histograms only read `y`.
+    let bars = Series { kind: SeriesKind::Bar, x: "unused".into(), y: "t".into(), ..Series::default() };
+    assert!(bars.check(&spec.data).is_ok());
+  }
+}
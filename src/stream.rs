@@ -0,0 +1,145 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Streaming data into a series.
+//!
+//! Real-time telemetry adds a few points per frame to a series that may
+//! already hold many thousands.  Appending sends only the new points
+//! across the C API, and an optional rolling window keeps the newest
+//! `window` points and drops the rest.  Rescanning the whole series for
+//! its range each frame would defeat that, so a [`Stream`] tracks the
+//! range of its window with [`RollingExtent`]s in amortized constant
+//! time per point.
+//!
+//! Append through [`PlotBackend::append`](crate::plotting::PlotBackend::append),
+//! which also fits the axis limits to all streams, or through
+//! [`Series::push`](crate::plotting::Series::push) on a series handle.
+
+use std::collections::VecDeque;
+
+/// Minimum and maximum of the last `window` values pushed.
+///
+/// Non-finite values take up a place in the window but never count
+/// towards the range, so NaN gaps age out like any other point.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RollingExtent {
+  window: Option<usize>,
+  pushed: u64,
+  // Candidates for the minimum (ascending) and maximum (descending),
+  // with the index they were pushed at.
+  min: VecDeque<(u64, f64)>,
+  max: VecDeque<(u64, f64)>,
+}
+
+impl RollingExtent {
+  /// Extent over the last `window` values, or all values for `None`.
+  pub fn new(window: Option<usize>) -> Self {
+    Self { window, ..Self::default() }
+  }
+
+  /// The window length.
+  pub fn window(&self) -> Option<usize> {
+    self.window
+  }
+
+  /// Adds `value` as the newest value.
+  pub fn push(&mut self, value: f64) {
+    let i = self.pushed;
+    self.pushed += 1;
+    if value.is_finite() {
+      while self.min.back().is_some_and(|&(_, v)| v >= value) {
+        self.min.pop_back();
+      }
+      self.min.push_back((i, value));
+      while self.max.back().is_some_and(|&(_, v)| v <= value) {
+        self.max.pop_back();
+      }
+      self.max.push_back((i, value));
+    }
+    if let Some(w) = self.window {
+      let oldest = self.pushed.saturating_sub(w as u64);
+      while self.min.front().is_some_and(|&(j, _)| j < oldest) {
+        self.min.pop_front();
+      }
+      while self.max.front().is_some_and(|&(j, _)| j < oldest) {
+        self.max.pop_front();
+      }
+    }
+  }
+
+  /// `(min, max)` of the finite values in the window, or `None` if
+  /// there are none.
+  pub fn range(&self) -> Option<(f64, f64)> {
+    Some((self.min.front()?.1, self.max.front()?.1))
+  }
+
+  /// Forgets all values.
+  pub fn clear(&mut self) {
+    *self = Self::new(self.window);
+  }
+}
+
+/// Rolling window and running X and Y ranges of a streamed series.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Stream {
+  /// X range of the window.
+  pub x: RollingExtent,
+  /// Y range of the window.
+  pub y: RollingExtent,
+}
+
+impl Stream {
+  /// Stream keeping the newest `window` points, or all for `None`.
+  pub fn new(window: Option<usize>) -> Self {
+    Self { x: RollingExtent::new(window), y: RollingExtent::new(window) }
+  }
+
+  /// The window length.
+  pub fn window(&self) -> Option<usize> {
+    self.x.window()
+  }
+
+  /// Records appended points.
+  pub fn push(&mut self, points: &[(f64, f64)]) {
+    for &(x, y) in points {
+      self.x.push(x);
+      self.y.push(y);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn extent_follows_the_window() {
+    let mut e = RollingExtent::new(Some(3));
+    assert_eq!(e.range(), None);
+    for v in [5.0, 1.0, 3.0] {
+      e.push(v);
+    }
+    assert_eq!(e.range(), Some((1.0, 5.0)));
+    e.push(2.0);
+    assert_eq!(e.range(), Some((1.0, 3.0)));
+    e.push(f64::NAN);
+    e.push(f64::NAN);
+    assert_eq!(e.range(), Some((2.0, 2.0)));
+    e.push(f64::NAN);
+    assert_eq!(e.range(), None);
+  }
+
+  #[test]
+  fn unbounded_extent_and_streams() {
+    let mut s = Stream::new(None);
+    s.push(&[(0.0, 4.0), (1.0, -2.0), (2.0, 7.0)]);
+    assert_eq!(s.x.range(), Some((0.0, 2.0)));
+    assert_eq!(s.y.range(), Some((-2.0, 7.0)));
+    let mut w = Stream::new(Some(2));
+    w.push(&[(0.0, 4.0), (1.0, -2.0), (2.0, 7.0)]);
+    assert_eq!(w.y.range(), Some((-2.0, 7.0)));
+    assert_eq!(w.x.range(), Some((1.0, 2.0)));
+    w.y.clear();
+    assert_eq!((w.y.range(), w.window()), (None, Some(2)));
+  }
+}
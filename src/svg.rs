@@ -0,0 +1,301 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! SVG vector export.
+//!
+//! Replays a [`Recording`], the same draw calls the GPU renderer
+//! receives, as SVG elements: rectangles, round-capped lines with their
+//! dash patterns, marker shapes, triangles and text.  The result scales
+//! to any resolution for papers and web pages.
+//!
+//! Text is written either as `<text>` elements, which stay selectable
+//! but need the font wherever the file is shown, or as glyph outlines
+//! (see [`outline`](crate::outline)), which look the same everywhere.
+//!
+//! ```rust,no_run
+//! use mpl_wgpu::svg::{SvgOptions, SvgText};
+//!
+//! let mut plot = mpl_wgpu::plotting::PlotBackend::new(800, 600);
+//! let recording = plot.record(None);
+//! let svg = mpl_wgpu::svg::to_svg(&recording, &SvgOptions::new().text(SvgText::Text), None);
+//! ```
+
+use crate::recording::{RecordedPrimitive, Recording};
+use crate::text::TextMetrics;
+use glam::{Vec2, Vec4};
+use std::fmt::Write;
+use std::path::Path;
+
+/// Line spacing of multi-line `<text>` labels, in multiples of the
+/// font size.
+const LINE_SPACING: f32 = 1.2;
+
+/// How text is written.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum SvgText {
+  /// `<text>` elements in the font family, selectable and searchable.
+  #[default]
+  Text,
+  /// Glyph outlines as `<path>` elements, independent of installed
+  /// fonts.  Needs [`TextMetrics`]; without them text is written as
+  /// [`SvgText::Text`].
+  Paths,
+}
+
+/// Options of the SVG export.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+  /// How text is written.
+  pub text: SvgText,
+  /// CSS font family of `<text>` elements.
+  pub font_family: String,
+  /// Color filling the page before the primitives, or `None` for a
+  /// transparent page.
+  pub background: Option<Vec4>,
+}
+
+impl Default for SvgOptions {
+  fn default() -> Self {
+    Self {
+      text: SvgText::default(),
+      font_family: "DejaVu Sans, sans-serif".into(),
+      background: Some(Vec4::ONE),
+    }
+  }
+}
+
+impl SvgOptions {
+  /// `<text>` labels in DejaVu Sans on a white page.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets how text is written.
+  pub fn text(mut self, text: SvgText) -> Self {
+    self.text = text;
+    self
+  }
+
+  /// Sets the font family of `<text>` elements.
+  pub fn font_family(mut self, family: &str) -> Self {
+    self.font_family = family.into();
+    self
+  }
+
+  /// Sets the page background.
+  pub fn background(mut self, color: Option<Vec4>) -> Self {
+    self.background = color;
+    self
+  }
+}
+
+/// Renders `recording` as an SVG document.  `metrics` outline text for
+/// [`SvgText::Paths`] and should hold the fonts used for rendering.
+pub fn to_svg(recording: &Recording, options: &SvgOptions, metrics: Option<&TextMetrics>) -> String {
+  let (w, h) = (recording.width, recording.height);
+  let mut out = format!(
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n"
+  );
+  if let Some(bg) = options.background {
+    let _ = writeln!(out, "<rect width=\"{}\" height=\"{}\" {}/>", w, h, fill(bg));
+  }
+  for p in &recording.primitives {
+    write_primitive(&mut out, p, options, metrics);
+  }
+  out.push_str("</svg>\n");
+  out
+}
+
+/// Writes `recording` as an SVG file (see [`to_svg`]).
+pub fn save_svg<P: AsRef<Path>>(
+  recording: &Recording,
+  path: P,
+  options: &SvgOptions,
+  metrics: Option<&TextMetrics>,
+) -> std::io::Result<()> {
+  std::fs::write(path, to_svg(recording, options, metrics))
+}
+
+fn write_primitive(out: &mut String, p: &RecordedPrimitive, options: &SvgOptions, metrics: Option<&TextMetrics>) {
+  let _ = match p {
+    RecordedPrimitive::Rect { pos, size, color, stroke_width, corner_radius } => {
+      let paint = if *stroke_width > 0.0 {
+        format!("fill=\"none\" {} stroke-width=\"{}\"", stroke(*color), num(*stroke_width))
+      } else {
+        fill(*color)
+      };
+      writeln!(
+        out,
+        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\" {}/>",
+        num(pos.x), num(pos.y), num(size.x), num(size.y), num(*corner_radius), paint
+      )
+    }
+    RecordedPrimitive::Line { start, end, width, color, dash_len, gap_len, dash_offset } => {
+      let dashes = if *dash_len > 0.0 {
+        format!(
+          " stroke-dasharray=\"{} {}\" stroke-dashoffset=\"{}\"",
+          num(*dash_len), num(*gap_len), num(*dash_offset)
+        )
+      } else {
+        " stroke-linecap=\"round\"".to_string()
+      };
+      writeln!(
+        out,
+        "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {} stroke-width=\"{}\"{}/>",
+        num(start.x), num(start.y), num(end.x), num(end.y), stroke(*color), num(*width), dashes
+      )
+    }
+    RecordedPrimitive::Marker { center, radius, color, marker_type } => {
+      let c = center.truncate();
+      writeln!(out, "{}", marker(c, *radius, *marker_type, *color))
+    }
+    RecordedPrimitive::Triangle { points, color } => {
+      let pts: Vec<String> = points.iter().map(|p| format!("{},{}", num(p.x), num(p.y))).collect();
+      writeln!(out, "<polygon points=\"{}\" {}/>", pts.join(" "), fill(*color))
+    }
+    RecordedPrimitive::Text { text, pos, size, color, rotation } => {
+      let transform = if *rotation != 0.0 {
+        format!(" transform=\"rotate({} {} {})\"", num(-rotation), num(pos.x), num(pos.y))
+      } else {
+        String::new()
+      };
+      match (&options.text, metrics) {
+        (SvgText::Paths, Some(m)) => {
+          let d = crate::outline::svg_path_data(&m.outline_text(text, *size, *pos));
+          if d.is_empty() {
+            return;
+          }
+          writeln!(out, "<path d=\"{}\" {}{}/>", d, fill(*color), transform)
+        }
+        _ => {
+          let lines: Vec<String> = text
+            .split('\n')
+            .enumerate()
+            .map(|(i, line)| {
+              let dy = if i == 0 { 0.0 } else { size * LINE_SPACING };
+              format!("<tspan x=\"{}\" dy=\"{}\">{}</tspan>", num(pos.x), num(dy), escape_xml(line))
+            })
+            .collect();
+          writeln!(
+            out,
+            "<text x=\"{}\" y=\"{}\" font-family=\"{}\" font-size=\"{}\" dominant-baseline=\"text-before-edge\" {}{}>{}</text>",
+            num(pos.x), num(pos.y), escape_xml(&options.font_family), num(*size), fill(*color), transform, lines.concat()
+          )
+        }
+      }
+    }
+  };
+}
+
+/// SVG element for a marker of the shader's `marker_type` (see
+/// `primitives.wgsl`): plus, cross, star, diamond, or a circle.
+fn marker(c: Vec2, r: f32, marker_type: u32, color: Vec4) -> String {
+  let polygon = |pts: Vec<Vec2>| {
+    let pts: Vec<String> = pts.iter().map(|p| format!("{},{}", num(p.x), num(p.y))).collect();
+    format!("<polygon points=\"{}\" {}/>", pts.join(" "), fill(color))
+  };
+  // Arms of a plus, 0.2 r thick as in the shader.
+  let plus = |turn: f32| {
+    let t = r * 0.2;
+    let corners = [(t, r), (t, t), (r, t), (r, -t), (t, -t), (t, -r), (-t, -r), (-t, -t), (-r, -t), (-r, t), (-t, t), (-t, r)];
+    let rot = Vec2::from_angle(turn);
+    polygon(corners.iter().map(|&(x, y)| c + rot.rotate(Vec2::new(x, y))).collect())
+  };
+  match marker_type {
+    10 => plus(0.0),
+    11 => plus(std::f32::consts::FRAC_PI_4),
+    12 => polygon(
+      (0..10)
+        .map(|i| {
+          let radius = if i % 2 == 0 { r } else { r * 0.382 };
+          let a = std::f32::consts::PI * i as f32 / 5.0;
+          c + radius * Vec2::new(a.sin(), -a.cos())
+        })
+        .collect(),
+    ),
+    13 => polygon(vec![c - Vec2::Y * r, c + Vec2::X * r, c + Vec2::Y * r, c - Vec2::X * r]),
+    _ => format!("<circle cx=\"{}\" cy=\"{}\" r=\"{}\" {}/>", num(c.x), num(c.y), num(r), fill(color)),
+  }
+}
+
+/// `fill` attributes for `color`.
+fn fill(color: Vec4) -> String {
+  paint("fill", color)
+}
+
+/// `stroke` attributes for `color`.
+fn stroke(color: Vec4) -> String {
+  paint("stroke", color)
+}
+
+fn paint(attr: &str, c: Vec4) -> String {
+  let channel = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+  let rgb = format!("#{:02x}{:02x}{:02x}", channel(c.x), channel(c.y), channel(c.z));
+  if c.w >= 1.0 {
+    format!("{}=\"{}\"", attr, rgb)
+  } else {
+    format!("{}=\"{}\" {}-opacity=\"{}\"", attr, rgb, attr, num(c.w.max(0.0)))
+  }
+}
+
+/// `v` rounded to 1/100 pixel, non-finite values as 0.
+fn num(v: f32) -> f32 {
+  if v.is_finite() { (v * 100.0).round() / 100.0 } else { 0.0 }
+}
+
+/// Escapes text for XML content and attributes.
+fn escape_xml(s: &str) -> String {
+  s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use glam::Vec3;
+
+  fn recording() -> Recording {
+    let mut rec = Recording::new(200, 100);
+    rec.push(RecordedPrimitive::Line {
+      start: Vec3::new(0.0, 0.0, 0.0),
+      end: Vec3::new(10.0, 20.0, 0.0),
+      width: 2.0,
+      color: Vec4::new(1.0, 0.0, 0.0, 0.5),
+      dash_len: 4.0,
+      gap_len: 2.0,
+      dash_offset: 0.0,
+    });
+    rec.push(RecordedPrimitive::Marker { center: Vec3::new(5.0, 5.0, 0.0), radius: 3.0, color: Vec4::ONE, marker_type: 13 });
+    rec.push(RecordedPrimitive::Text {
+      text: "a<b".into(),
+      pos: Vec2::new(50.0, 60.0),
+      size: 12.0,
+      color: Vec4::new(0.0, 0.0, 0.0, 1.0),
+      rotation: 90.0,
+    });
+    rec
+  }
+
+  #[test]
+  fn primitives_become_elements() {
+    let svg = to_svg(&recording(), &SvgOptions::new().background(None), None);
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"200\" height=\"100\""));
+    assert!(!svg.contains("<rect"));
+    assert!(svg.contains("stroke=\"#ff0000\" stroke-opacity=\"0.5\" stroke-width=\"2\" stroke-dasharray=\"4 2\""));
+    assert!(svg.contains("<polygon points=\"5,2 8,5 5,8 2,5\" fill=\"#ffffff\"/>"));
+    assert!(svg.contains(">a&lt;b</tspan></text>"));
+    assert!(svg.contains("transform=\"rotate(-90 50 60)\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+  }
+
+  #[cfg(feature = "embedded-font")]
+  #[test]
+  fn text_can_be_outlined() {
+    let metrics = TextMetrics::embedded();
+    let options = SvgOptions::new().text(SvgText::Paths);
+    let svg = to_svg(&recording(), &options, Some(&metrics));
+    assert!(svg.contains("<path d=\"M "));
+    assert!(!svg.contains("<text"));
+    // Without metrics the text stays text.
+    assert!(to_svg(&recording(), &options, None).contains("<text"));
+  }
+}
@@ -0,0 +1,626 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Date/time tick placement for temporal axes.
+//!
+//! Values are Unix timestamps in seconds (UTC).  [`ticks`] picks a
+//! calendar-aware step (seconds up to years) that yields at most the
+//! requested number of ticks, places them on round boundaries (whole
+//! hours, midnights, first of the month, ...), and formats each label
+//! at the precision the step calls for.  [`ticks_localized`] writes
+//! dates with the month and weekday names of a [`Locale`] instead of
+//! as numbers.
+
+use crate::locale::Locale;
+
+/// Default upper bound on the number of ticks for a temporal axis.
+pub const DEFAULT_MAX_TICKS: usize = 8;
+
+const MINUTE: i64 = 60;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+
+/// Spacing between neighbouring temporal ticks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeStep {
+  /// Every `n` seconds.
+  Seconds(u32),
+  /// Every `n` minutes.
+  Minutes(u32),
+  /// Every `n` hours.
+  Hours(u32),
+  /// Every `n` days.
+  Days(u32),
+  /// Every `n` weeks, on Mondays.
+  Weeks(u32),
+  /// Every `n` calendar months, on the first of the month.
+  Months(u32),
+  /// Every `n` calendar years, on January 1st.
+  Years(u32),
+}
+
+/// Candidate steps, finest first.
+const STEPS: &[TimeStep] = &[
+  TimeStep::Seconds(1),
+  TimeStep::Seconds(2),
+  TimeStep::Seconds(5),
+  TimeStep::Seconds(10),
+  TimeStep::Seconds(15),
+  TimeStep::Seconds(30),
+  TimeStep::Minutes(1),
+  TimeStep::Minutes(2),
+  TimeStep::Minutes(5),
+  TimeStep::Minutes(10),
+  TimeStep::Minutes(15),
+  TimeStep::Minutes(30),
+  TimeStep::Hours(1),
+  TimeStep::Hours(2),
+  TimeStep::Hours(3),
+  TimeStep::Hours(6),
+  TimeStep::Hours(12),
+  TimeStep::Days(1),
+  TimeStep::Days(2),
+  TimeStep::Weeks(1),
+  TimeStep::Weeks(2),
+  TimeStep::Months(1),
+  TimeStep::Months(2),
+  TimeStep::Months(3),
+  TimeStep::Months(6),
+  TimeStep::Years(1),
+  TimeStep::Years(2),
+  TimeStep::Years(5),
+  TimeStep::Years(10),
+  TimeStep::Years(20),
+  TimeStep::Years(50),
+  TimeStep::Years(100),
+];
+
+impl TimeStep {
+  /// Approximate length of the step in seconds.
+  pub fn approx_seconds(self) -> f64 {
+    match self {
+      TimeStep::Seconds(n) => n as f64,
+      TimeStep::Minutes(n) => (n as i64 * MINUTE) as f64,
+      TimeStep::Hours(n) => (n as i64 * HOUR) as f64,
+      TimeStep::Days(n) => (n as i64 * DAY) as f64,
+      TimeStep::Weeks(n) => (n as i64 * 7 * DAY) as f64,
+      TimeStep::Months(n) => n as f64 * 30.44 * DAY as f64,
+      TimeStep::Years(n) => n as f64 * 365.25 * DAY as f64,
+    }
+  }
+
+  /// Fixed length in seconds, or `None` for calendar steps.
+  fn fixed_seconds(self) -> Option<i64> {
+    match self {
+      TimeStep::Seconds(n) => Some(n as i64),
+      TimeStep::Minutes(n) => Some(n as i64 * MINUTE),
+      TimeStep::Hours(n) => Some(n as i64 * HOUR),
+      TimeStep::Days(n) => Some(n as i64 * DAY),
+      TimeStep::Weeks(n) => Some(n as i64 * 7 * DAY),
+      TimeStep::Months(_) | TimeStep::Years(_) => None,
+    }
+  }
+}
+
+/// Picks the finest step that puts at most `max_ticks` ticks on
+/// `[t_min, t_max]`.
+pub fn choose_step(t_min: f64, t_max: f64, max_ticks: usize) -> TimeStep {
+  let span = (t_max - t_min).abs();
+  let max_ticks = max_ticks.max(2) as f64;
+  STEPS
+    .iter()
+    .copied()
+    .find(|s| span / s.approx_seconds() < max_ticks)
+    .unwrap_or(TimeStep::Years(100))
+}
+
+/// Tick positions and labels for a temporal range, in UTC.
+///
+/// Returns `(positions, labels)` of equal length.  An empty or
+/// non-finite range yields no ticks.
+pub fn ticks(
+  t_min: f64,
+  t_max: f64,
+  max_ticks: usize,
+) -> (Vec<f64>, Vec<String>) {
+  ticks_in(t_min, t_max, max_ticks, &Utc)
+}
+
+/// Tick positions and labels for a temporal range, placed on round
+/// wall-clock values of the display time zone `zone`.
+///
+/// Positions stay Unix timestamps.  Across DST transitions, wall-clock
+/// times skipped by the zone get no tick, and repeated ones are ticked
+/// only at their first occurrence, so labels are never duplicated.
+pub fn ticks_in(
+  t_min: f64,
+  t_max: f64,
+  max_ticks: usize,
+  zone: &dyn DisplayZone,
+) -> (Vec<f64>, Vec<String>) {
+  ticks_with(t_min, t_max, max_ticks, zone, format_tick)
+}
+
+/// Like [`ticks_in`], with dates labelled with the names and patterns
+/// of `locale` (see [`format_tick_localized`]).
+pub fn ticks_localized(
+  t_min: f64,
+  t_max: f64,
+  max_ticks: usize,
+  zone: &dyn DisplayZone,
+  locale: &Locale,
+) -> (Vec<f64>, Vec<String>) {
+  ticks_with(t_min, t_max, max_ticks, zone, |t, step| {
+    format_tick_localized(t, step, locale)
+  })
+}
+
+/// Ticks of [`ticks_in`], labelled by `format` from the wall-clock
+/// time and step.
+fn ticks_with(
+  t_min: f64,
+  t_max: f64,
+  max_ticks: usize,
+  zone: &dyn DisplayZone,
+  format: impl Fn(f64, TimeStep) -> String,
+) -> (Vec<f64>, Vec<String>) {
+  if !t_min.is_finite() || !t_max.is_finite() {
+    return (Vec::new(), Vec::new());
+  }
+  let (lo, hi) = if t_min <= t_max {
+    (t_min, t_max)
+  } else {
+    (t_max, t_min)
+  };
+  let step = choose_step(lo, hi, max_ticks);
+  let local_lo = lo + zone.utc_offset(lo.floor() as i64) as f64;
+  let local_hi = hi + zone.utc_offset(hi.floor() as i64) as f64;
+
+  let mut positions = Vec::new();
+  let mut labels = Vec::new();
+  for local in tick_positions(local_lo, local_hi, step) {
+    let Some(t) = local_to_utc(local as i64, zone) else {
+      continue;
+    };
+    let t = t as f64 + local.fract();
+    if t < lo || t > hi || positions.last().is_some_and(|&p| t <= p) {
+      continue;
+    }
+    positions.push(t);
+    labels.push(format(local, step));
+  }
+  (positions, labels)
+}
+
+/// Converts wall-clock times in `data_zone` (seconds since 1970-01-01
+/// local) to Unix timestamps.  Ambiguous times resolve to the earlier
+/// instant; nonexistent ones to the instant just after the gap.
+pub fn from_local(values: &[f64], data_zone: &dyn DisplayZone) -> Vec<f64> {
+  values
+    .iter()
+    .map(|&v| {
+      let secs = v.floor() as i64;
+      let utc = local_to_utc(secs, data_zone)
+        .unwrap_or_else(|| secs - data_zone.utc_offset(secs));
+      utc as f64 + (v - secs as f64)
+    })
+    .collect()
+}
+
+/// A time zone for displaying or interpreting timestamps.
+pub trait DisplayZone {
+  /// Offset of local time from UTC at Unix time `t`, in seconds.
+  fn utc_offset(&self, t: i64) -> i64;
+}
+
+/// Coordinated Universal Time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Utc;
+
+impl DisplayZone for Utc {
+  fn utc_offset(&self, _t: i64) -> i64 {
+    0
+  }
+}
+
+/// A fixed offset from UTC in seconds (east positive), without DST.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FixedOffset(pub i32);
+
+impl DisplayZone for FixedOffset {
+  fn utc_offset(&self, _t: i64) -> i64 {
+    self.0 as i64
+  }
+}
+
+#[cfg(feature = "tz")]
+impl DisplayZone for chrono_tz::Tz {
+  fn utc_offset(&self, t: i64) -> i64 {
+    use chrono::{Offset, TimeZone};
+    let utc = chrono::DateTime::from_timestamp(t, 0).unwrap_or_default();
+    self.offset_from_utc_datetime(&utc.naive_utc())
+      .fix()
+      .local_minus_utc() as i64
+  }
+}
+
+/// Earliest Unix time whose wall clock in `zone` reads `local`, or
+/// `None` if DST skips it.
+fn local_to_utc(local: i64, zone: &dyn DisplayZone) -> Option<i64> {
+  // Offsets just before and after any transition near `local`.
+  let before = zone.utc_offset(local - DAY);
+  let after = zone.utc_offset(local + DAY);
+  let mut candidates = [local - before, local - after];
+  candidates.sort_unstable();
+  candidates
+    .into_iter()
+    .find(|&t| t + zone.utc_offset(t) == local)
+}
+
+/// Tick positions for `step` on `[lo, hi]`, aligned to round values.
+pub fn tick_positions(lo: f64, hi: f64, step: TimeStep) -> Vec<f64> {
+  let mut out = Vec::new();
+  let mut t = floor_boundary(lo.ceil() as i64, step);
+  if (t as f64) < lo {
+    t = next_boundary(t, step);
+  }
+  while (t as f64) <= hi {
+    out.push(t as f64);
+    t = next_boundary(t, step);
+  }
+  out
+}
+
+/// Calendar-aligned histogram bin edges covering `[t_min, t_max]`.
+///
+/// Edges fall on `bin` boundaries of `zone`'s wall clock (midnights,
+/// Mondays, first of the month, ...), so bins can differ in length;
+/// the first edge is at or before `t_min` and the last after `t_max`.
+pub fn bin_edges(
+  t_min: f64,
+  t_max: f64,
+  bin: TimeStep,
+  zone: &dyn DisplayZone,
+) -> Vec<f64> {
+  if !t_min.is_finite() || !t_max.is_finite() || t_min > t_max {
+    return Vec::new();
+  }
+  let local_lo = t_min.floor() as i64 + zone.utc_offset(t_min.floor() as i64);
+  let mut local = floor_boundary(local_lo, bin);
+  let mut edges = Vec::new();
+  loop {
+    let t = local_to_utc(local, zone)
+      .unwrap_or_else(|| local - zone.utc_offset(local)) as f64;
+    if !matches!(edges.last(), Some(&e) if e >= t) {
+      edges.push(t);
+    }
+    if t > t_max {
+      break;
+    }
+    local = next_boundary(local, bin);
+  }
+  edges
+}
+
+/// Counts `values` per calendar-aligned bin.
+///
+/// Returns `(edges, counts)` with `counts.len() + 1 == edges.len()`.
+/// Non-finite values are ignored.
+pub fn histogram(
+  values: &[f64],
+  bin: TimeStep,
+  zone: &dyn DisplayZone,
+) -> (Vec<f64>, Vec<usize>) {
+  let (lo, hi) = values
+    .iter()
+    .filter(|v| v.is_finite())
+    .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+      (lo.min(v), hi.max(v))
+    });
+  let edges = bin_edges(lo, hi, bin, zone);
+  let mut counts = vec![0; edges.len().saturating_sub(1)];
+  for &v in values.iter().filter(|v| v.is_finite()) {
+    let i = edges.partition_point(|&e| e <= v);
+    if i > 0 && i <= counts.len() {
+      counts[i - 1] += 1;
+    }
+  }
+  (edges, counts)
+}
+
+/// Latest `step` boundary at or before `t` (seconds, same clock).
+fn floor_boundary(t: i64, step: TimeStep) -> i64 {
+  match step {
+    TimeStep::Weeks(n) => {
+      // 1970-01-01 was a Thursday; Mondays are 3 days before.
+      let len = n as i64 * 7 * DAY;
+      (t + 3 * DAY).div_euclid(len) * len - 3 * DAY
+    }
+    TimeStep::Months(n) => {
+      let (y, m, _) = civil_from_days(t.div_euclid(DAY));
+      // Align to months 1, 1+n, 1+2n, ... of each year.
+      let m0 = (m - 1) / n * n + 1;
+      days_from_civil(y, m0, 1) * DAY
+    }
+    TimeStep::Years(n) => {
+      let (y, _, _) = civil_from_days(t.div_euclid(DAY));
+      days_from_civil(y.div_euclid(n as i64) * n as i64, 1, 1) * DAY
+    }
+    _ => {
+      // Multiples of the step since the epoch land on round wall-clock
+      // values (midnights for days).
+      let len = step.fixed_seconds().unwrap_or(1);
+      t.div_euclid(len) * len
+    }
+  }
+}
+
+/// The `step` boundary following boundary `t`.
+fn next_boundary(t: i64, step: TimeStep) -> i64 {
+  match step {
+    TimeStep::Months(n) => {
+      let (y, m, _) = civil_from_days(t.div_euclid(DAY));
+      let m = m - 1 + n;
+      days_from_civil(y + (m / 12) as i64, m % 12 + 1, 1) * DAY
+    }
+    TimeStep::Years(n) => {
+      let (y, _, _) = civil_from_days(t.div_euclid(DAY));
+      days_from_civil(y + n as i64, 1, 1) * DAY
+    }
+    _ => t + step.fixed_seconds().unwrap_or(1),
+  }
+}
+
+/// Formats a timestamp at the precision appropriate for `step`.
+///
+/// Seconds steps give `HH:MM:SS`, minute and hour steps `HH:MM` (with
+/// the date prepended at midnight), day steps `YYYY-MM-DD`, month
+/// steps `YYYY-MM`, and year steps `YYYY`.
+pub fn format_tick(t: f64, step: TimeStep) -> String {
+  let secs = t.floor() as i64;
+  let days = secs.div_euclid(DAY);
+  let tod = secs.rem_euclid(DAY);
+  let (y, mo, d) = civil_from_days(days);
+  let (h, mi, s) = (tod / HOUR, tod % HOUR / MINUTE, tod % MINUTE);
+  match step {
+    TimeStep::Seconds(_) => format!("{:02}:{:02}:{:02}", h, mi, s),
+    TimeStep::Minutes(_) | TimeStep::Hours(_) if tod == 0 => {
+      format!("{:04}-{:02}-{:02}", y, mo, d)
+    }
+    TimeStep::Minutes(_) | TimeStep::Hours(_) => {
+      format!("{:02}:{:02}", h, mi)
+    }
+    TimeStep::Days(_) | TimeStep::Weeks(_) => {
+      format!("{:04}-{:02}-{:02}", y, mo, d)
+    }
+    TimeStep::Months(_) => format!("{:04}-{:02}", y, mo),
+    TimeStep::Years(_) => format!("{:04}", y),
+  }
+}
+
+/// Formats a timestamp for `step` with the names and date patterns of
+/// `locale`.
+///
+/// Times are numeric as in [`format_tick`]; daily ticks use the
+/// locale's day pattern, weekly ticks and midnights among hourly ticks
+/// its date pattern, and monthly ticks its month pattern.
+pub fn format_tick_localized(t: f64, step: TimeStep, locale: &Locale) -> String {
+  let secs = t.floor() as i64;
+  let days = secs.div_euclid(DAY);
+  let (y, mo, d) = civil_from_days(days);
+  // 1970-01-01 was a Thursday.
+  let weekday = (days + 3).rem_euclid(7) as u32;
+  let date = |pattern: &str| locale.format_date(pattern, y, mo, d, weekday);
+  match step {
+    TimeStep::Minutes(_) | TimeStep::Hours(_) if secs.rem_euclid(DAY) == 0 => {
+      date(&locale.date_format)
+    }
+    TimeStep::Days(1) => date(&locale.day_format),
+    TimeStep::Days(_) | TimeStep::Weeks(_) => date(&locale.date_format),
+    TimeStep::Months(_) => date(&locale.month_format),
+    _ => format_tick(t, step),
+  }
+}
+
+/// Converts `chrono` datetimes to Unix timestamps in seconds.
+#[cfg(feature = "chrono")]
+pub fn from_datetimes<Tz: chrono::TimeZone>(
+  times: &[chrono::DateTime<Tz>],
+) -> Vec<f64> {
+  times
+    .iter()
+    .map(|t| t.timestamp() as f64 + t.timestamp_subsec_nanos() as f64 * 1e-9)
+    .collect()
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+  let y = if m <= 2 { y - 1 } else { y };
+  let era = y.div_euclid(400);
+  let yoe = y - era * 400;
+  let mp = (m as i64 + 9) % 12;
+  let doy = (153 * mp + 2) / 5 + d as i64 - 1;
+  let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+  era * 146097 + doe - 719468
+}
+
+/// Proleptic Gregorian `(year, month, day)` for days since 1970-01-01.
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+  let z = z + 719468;
+  let era = z.div_euclid(146097);
+  let doe = z - era * 146097;
+  let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+  let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+  let mp = (5 * doy + 2) / 153;
+  let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+  let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+  let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+  (y, m, d)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn civil_round_trip() {
+    assert_eq!(civil_from_days(0), (1970, 1, 1));
+    assert_eq!(days_from_civil(2000, 3, 1), 11017);
+    assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    for z in [-800_000, -1, 0, 59, 11016, 20_000, 800_000] {
+      let (y, m, d) = civil_from_days(z);
+      assert_eq!(days_from_civil(y, m, d), z);
+    }
+  }
+
+  #[test]
+  fn hour_ticks_on_the_hour() {
+    // 2024-01-01 00:20 .. 06:10 UTC.
+    let base = (days_from_civil(2024, 1, 1) * DAY) as f64;
+    let (pos, labels) = ticks(base + 1200.0, base + 22200.0, 8);
+    assert_eq!(choose_step(base, base + 21000.0, 8), TimeStep::Hours(1));
+    assert_eq!(pos.len(), 6);
+    assert_eq!(labels[0], "01:00");
+    assert_eq!(labels[5], "06:00");
+    assert!(pos.iter().all(|p| (*p as i64) % HOUR == 0));
+  }
+
+  #[test]
+  fn month_ticks_on_first_of_month() {
+    let lo = (days_from_civil(2023, 11, 15) * DAY) as f64;
+    let hi = (days_from_civil(2024, 5, 2) * DAY) as f64;
+    let (_, labels) = ticks(lo, hi, 8);
+    assert_eq!(
+      labels,
+      vec!["2023-12", "2024-01", "2024-02", "2024-03", "2024-04", "2024-05"]
+    );
+  }
+
+  #[test]
+  fn week_ticks_on_mondays() {
+    // Wednesday 2024-01-03 to 2024-02-14.
+    let lo = (days_from_civil(2024, 1, 3) * DAY) as f64;
+    let hi = (days_from_civil(2024, 2, 14) * DAY) as f64;
+    assert_eq!(choose_step(lo, hi, 8), TimeStep::Weeks(1));
+    let (pos, labels) = ticks(lo, hi, 8);
+    assert_eq!(labels[0], "2024-01-08");
+    assert!(pos.iter().all(|p| ((*p as i64) / DAY + 3) % 7 == 0));
+  }
+
+  #[test]
+  fn localized_ticks_use_names() {
+    let lo = (days_from_civil(2023, 11, 15) * DAY) as f64;
+    let hi = (days_from_civil(2024, 2, 2) * DAY) as f64;
+    let de = Locale::english().short_months([
+      "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+    ]);
+    let (_, labels) = ticks_localized(lo, hi, 4, &Utc, &de);
+    assert_eq!(labels, vec!["Dez 2023", "Jan 2024", "Feb 2024"]);
+    // 2024-01-01 was a Monday.
+    let day = (days_from_civil(2024, 1, 1) * DAY) as f64;
+    let en = Locale::english();
+    assert_eq!(format_tick_localized(day, TimeStep::Days(1), &en), "Mon 1 Jan");
+    assert_eq!(format_tick_localized(day, TimeStep::Hours(6), &en), "1 Jan");
+    assert_eq!(format_tick_localized(day + 3600.0, TimeStep::Hours(1), &en), "01:00");
+  }
+
+  #[test]
+  fn multi_year_step_is_aligned() {
+    let lo = (days_from_civil(1993, 6, 1) * DAY) as f64;
+    let hi = (days_from_civil(2051, 1, 1) * DAY) as f64;
+    let (_, labels) = ticks(lo, hi, 8);
+    assert_eq!(labels, vec!["2000", "2010", "2020", "2030", "2040", "2050"]);
+  }
+
+  /// Central European time with DST from 2024-03-31 01:00 UTC to
+  /// 2024-10-27 01:00 UTC.
+  struct Cet;
+
+  impl DisplayZone for Cet {
+    fn utc_offset(&self, t: i64) -> i64 {
+      let start = days_from_civil(2024, 3, 31) * DAY + HOUR;
+      let end = days_from_civil(2024, 10, 27) * DAY + HOUR;
+      if (start..end).contains(&t) {
+        2 * HOUR
+      } else {
+        HOUR
+      }
+    }
+  }
+
+  #[test]
+  fn spring_forward_skips_missing_hour() {
+    // 2024-03-31 00:00..05:00 local is 4 real hours; 02:00 is skipped.
+    let lo = (days_from_civil(2024, 3, 31) * DAY - HOUR) as f64;
+    let hi = lo + 4.0 * HOUR as f64;
+    let (pos, labels) = ticks_in(lo, hi, 8, &Cet);
+    assert_eq!(labels, vec!["2024-03-31", "01:00", "03:00", "04:00", "05:00"]);
+    assert!(pos.windows(2).all(|w| w[1] - w[0] == HOUR as f64));
+  }
+
+  #[test]
+  fn fall_back_labels_repeated_hour_once() {
+    // 2024-10-27 00:00..04:00 local spans 5 real hours.
+    let lo = (days_from_civil(2024, 10, 27) * DAY - 2 * HOUR) as f64;
+    let hi = lo + 5.0 * HOUR as f64;
+    let (pos, labels) = ticks_in(lo, hi, 8, &Cet);
+    assert_eq!(labels, vec!["2024-10-27", "01:00", "02:00", "03:00", "04:00"]);
+    // 02:00 is the first (summer time) occurrence.
+    assert_eq!(pos[2] - pos[1], HOUR as f64);
+    assert_eq!(pos[3] - pos[2], 2.0 * HOUR as f64);
+  }
+
+  #[test]
+  fn local_data_round_trips() {
+    let local = (days_from_civil(2024, 7, 1) * DAY + 12 * HOUR) as f64;
+    let utc = from_local(&[local], &Cet)[0];
+    assert_eq!(utc, local - 2.0 * HOUR as f64);
+    assert_eq!(from_local(&[local], &FixedOffset(-3600))[0], local + 3600.0);
+  }
+
+  #[test]
+  fn month_bins_follow_calendar() {
+    let jan15 = (days_from_civil(2024, 1, 15) * DAY) as f64;
+    let mar02 = (days_from_civil(2024, 3, 2) * DAY) as f64;
+    let feb29 = (days_from_civil(2024, 2, 29) * DAY) as f64;
+    let (edges, counts) =
+      histogram(&[jan15, feb29, mar02, f64::NAN], TimeStep::Months(1), &Utc);
+    let days: Vec<i64> =
+      edges.iter().map(|e| *e as i64 / DAY).collect();
+    assert_eq!(
+      days,
+      vec![
+        days_from_civil(2024, 1, 1),
+        days_from_civil(2024, 2, 1),
+        days_from_civil(2024, 3, 1),
+        days_from_civil(2024, 4, 1),
+      ]
+    );
+    assert_eq!(counts, vec![1, 1, 1]);
+  }
+
+  #[test]
+  fn week_bins_start_on_monday() {
+    // 2024-01-03 is a Wednesday; its week starts Monday 2024-01-01.
+    let wed = (days_from_civil(2024, 1, 3) * DAY) as f64;
+    let edges = bin_edges(wed, wed + DAY as f64, TimeStep::Weeks(1), &Utc);
+    assert_eq!(edges[0] as i64, days_from_civil(2024, 1, 1) * DAY);
+    assert_eq!(edges.len(), 2);
+  }
+
+  #[test]
+  fn day_bins_use_local_midnight() {
+    let noon = (days_from_civil(2024, 7, 1) * DAY + 12 * HOUR) as f64;
+    let edges = bin_edges(noon, noon, TimeStep::Days(1), &Cet);
+    // Local midnight in summer is 22:00 UTC the day before.
+    assert_eq!(edges[0] as i64, days_from_civil(2024, 7, 1) * DAY - 2 * HOUR);
+  }
+
+  #[test]
+  fn degenerate_ranges() {
+    assert_eq!(ticks(f64::NAN, 1.0, 8).0.len(), 0);
+    let (pos, labels) = ticks(60.0, 0.0, 8);
+    assert_eq!(pos.first(), Some(&0.0));
+    assert_eq!(labels[0], "00:00:00");
+  }
+}
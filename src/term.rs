@@ -0,0 +1,278 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Terminal previews of captured frames.
+//!
+//! Downsamples tightly-packed RGBA pixels (as returned by
+//! [`HeadlessRenderer::capture`](crate::capture::HeadlessRenderer::capture))
+//! to either sixel graphics or 24-bit colored Unicode half blocks, for
+//! quick looks at plots over SSH without copying PNGs around.
+
+/// Output encoding for terminal previews.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TermFormat {
+  /// `▀` cells with truecolor foreground/background, two pixel rows
+  /// per text row.  Works in nearly every modern terminal.
+  HalfBlocks,
+  /// DEC sixel graphics with a 216-color palette.  Needs a
+  /// sixel-capable terminal (xterm -ti vt340, mlterm, WezTerm, foot).
+  Sixel,
+}
+
+/// Encodes an RGBA frame for display in a terminal.
+///
+/// `columns` is the target width in text cells for half blocks, or in
+/// pixels for sixel.  The aspect ratio is preserved; transparent
+/// pixels are composited over white.  A `pixels` buffer that is not
+/// `width * height * 4` bytes encodes to an empty string.
+pub fn render(
+  pixels: &[u8],
+  width: u32,
+  height: u32,
+  format: TermFormat,
+  columns: u32,
+) -> String {
+  match format {
+    TermFormat::HalfBlocks => half_blocks(pixels, width, height, columns),
+    TermFormat::Sixel => sixel(pixels, width, height, columns),
+  }
+}
+
+/// Encodes an RGBA frame as colored `▀` half-block cells, or returns
+/// an empty string if `pixels` is not `width * height * 4` bytes.
+pub fn half_blocks(
+  pixels: &[u8],
+  width: u32,
+  height: u32,
+  columns: u32,
+) -> String {
+  if !is_frame(pixels, width, height) {
+    return String::new();
+  }
+  let out_w = columns.clamp(1, width.max(1));
+  // Each cell is two pixels tall; cells are roughly twice as tall as
+  // wide, so one pixel per half-cell keeps the aspect ratio.
+  let mut out_h = scaled_height(width, height, out_w);
+  out_h += out_h % 2;
+  let rgb = downsample(pixels, width, height, out_w, out_h);
+
+  let mut s = String::new();
+  for row in (0..out_h as usize).step_by(2) {
+    for col in 0..out_w as usize {
+      let top = rgb[row * out_w as usize + col];
+      let bottom = rgb[(row + 1) * out_w as usize + col];
+      s.push_str(&format!(
+        "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m\u{2580}",
+        top[0], top[1], top[2], bottom[0], bottom[1], bottom[2],
+      ));
+    }
+    s.push_str("\x1b[0m\n");
+  }
+  s
+}
+
+/// Encodes an RGBA frame as a sixel image `columns` pixels wide, or
+/// returns an empty string if `pixels` is not `width * height * 4`
+/// bytes.
+pub fn sixel(
+  pixels: &[u8],
+  width: u32,
+  height: u32,
+  columns: u32,
+) -> String {
+  if !is_frame(pixels, width, height) {
+    return String::new();
+  }
+  let out_w = columns.clamp(1, width.max(1));
+  let out_h = scaled_height(width, height, out_w);
+  let rgb = downsample(pixels, width, height, out_w, out_h);
+  let indices: Vec<u8> = rgb.iter().map(|&c| palette_index(c)).collect();
+
+  let mut s = String::from("\x1bPq");
+  s.push_str(&format!("\"1;1;{};{}", out_w, out_h));
+  for i in 0..216u32 {
+    let (r, g, b) = (i / 36, (i / 6) % 6, i % 6);
+    // Sixel color components are percentages.
+    s.push_str(&format!(
+      "#{};2;{};{};{}",
+      i,
+      r * 100 / 5,
+      g * 100 / 5,
+      b * 100 / 5
+    ));
+  }
+
+  let w = out_w as usize;
+  for band in (0..out_h as usize).step_by(6) {
+    let rows = (out_h as usize - band).min(6);
+    let mut used = [false; 216];
+    for y in band..band + rows {
+      for x in 0..w {
+        used[indices[y * w + x] as usize] = true;
+      }
+    }
+    let mut first = true;
+    for (color, _) in used.iter().enumerate().filter(|(_, u)| **u) {
+      if !first {
+        // Carriage return: overlay the next color on the same band.
+        s.push('$');
+      }
+      first = false;
+      s.push_str(&format!("#{}", color));
+      let mut run_char = 0u8;
+      let mut run_len = 0usize;
+      for x in 0..w {
+        let mut bits = 0u8;
+        for dy in 0..rows {
+          if indices[(band + dy) * w + x] as usize == color {
+            bits |= 1 << dy;
+          }
+        }
+        let ch = 63 + bits;
+        if run_len > 0 && ch == run_char {
+          run_len += 1;
+        } else {
+          push_sixel_run(&mut s, run_char, run_len);
+          run_char = ch;
+          run_len = 1;
+        }
+      }
+      push_sixel_run(&mut s, run_char, run_len);
+    }
+    // Line feed: advance to the next band.
+    s.push('-');
+  }
+  s.push_str("\x1b\\");
+  s
+}
+
+/// Whether `pixels` holds exactly a `width x height` RGBA frame.
+fn is_frame(pixels: &[u8], width: u32, height: u32) -> bool {
+  (width as usize)
+    .checked_mul(height as usize)
+    .and_then(|n| n.checked_mul(4))
+    == Some(pixels.len())
+}
+
+/// Output height that preserves the source aspect ratio.
+fn scaled_height(width: u32, height: u32, out_w: u32) -> u32 {
+  if width == 0 {
+    return 1;
+  }
+  ((height as u64 * out_w as u64 + width as u64 / 2) / width as u64)
+    .max(1) as u32
+}
+
+/// Writes one run of a sixel character, using `!n` repeats.
+fn push_sixel_run(s: &mut String, ch: u8, len: usize) {
+  match len {
+    0 => {}
+    1..=3 => {
+      for _ in 0..len {
+        s.push(ch as char);
+      }
+    }
+    _ => s.push_str(&format!("!{}{}", len, ch as char)),
+  }
+}
+
+/// Maps an RGB color to the nearest entry of the 6x6x6 color cube.
+fn palette_index(c: [u8; 3]) -> u8 {
+  let q = |v: u8| ((v as u32 * 5 + 127) / 255) as u8;
+  q(c[0]) * 36 + q(c[1]) * 6 + q(c[2])
+}
+
+/// Box-filters RGBA pixels down to `out_w x out_h` RGB, compositing
+/// over a white background.  `pixels` must pass [`is_frame`].
+fn downsample(
+  pixels: &[u8],
+  width: u32,
+  height: u32,
+  out_w: u32,
+  out_h: u32,
+) -> Vec<[u8; 3]> {
+  let (w, h) = (width as usize, height as usize);
+  debug_assert!(is_frame(pixels, width, height));
+  let mut out = Vec::with_capacity((out_w * out_h) as usize);
+  for oy in 0..out_h as usize {
+    let y0 = oy * h / out_h as usize;
+    let y1 = ((oy + 1) * h / out_h as usize).max(y0 + 1).min(h);
+    for ox in 0..out_w as usize {
+      let x0 = ox * w / out_w as usize;
+      let x1 = ((ox + 1) * w / out_w as usize).max(x0 + 1).min(w);
+      let mut acc = [0.0f32; 3];
+      let mut n = 0.0f32;
+      for y in y0..y1 {
+        for x in x0..x1 {
+          let p = &pixels[(y * w + x) * 4..(y * w + x) * 4 + 4];
+          let a = p[3] as f32 / 255.0;
+          for c in 0..3 {
+            acc[c] += p[c] as f32 * a + 255.0 * (1.0 - a);
+          }
+          n += 1.0;
+        }
+      }
+      if n == 0.0 {
+        out.push([255, 255, 255]);
+      } else {
+        out.push([
+          (acc[0] / n).round() as u8,
+          (acc[1] / n).round() as u8,
+          (acc[2] / n).round() as u8,
+        ]);
+      }
+    }
+  }
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn solid(w: u32, h: u32, rgba: [u8; 4]) -> Vec<u8> {
+    rgba.iter().copied().cycle().take((w * h * 4) as usize).collect()
+  }
+
+  #[test]
+  fn downsample_averages_and_composites() {
+    // 2x1: black opaque + fully transparent (→ white).
+    let px = vec![0, 0, 0, 255, 0, 0, 0, 0];
+    let out = downsample(&px, 2, 1, 1, 1);
+    assert_eq!(out, vec![[128, 128, 128]]);
+  }
+
+  #[test]
+  fn half_blocks_dimensions() {
+    let px = solid(8, 4, [255, 0, 0, 255]);
+    let s = half_blocks(&px, 8, 4, 4);
+    // 4 columns wide -> 2 pixel rows -> 1 text row.
+    assert_eq!(s.lines().count(), 1);
+    assert_eq!(s.matches('\u{2580}').count(), 4);
+    assert!(s.contains("\x1b[38;2;255;0;0m"));
+  }
+
+  #[test]
+  fn sixel_envelope_and_run_length() {
+    let px = solid(10, 6, [255, 255, 255, 255]);
+    let s = sixel(&px, 10, 6, 10);
+    assert!(s.starts_with("\x1bPq"));
+    assert!(s.ends_with("\x1b\\"));
+    // One band, one color (white = 215), all six bits set, run of 10.
+    assert!(s.contains("#215!10~-"));
+  }
+
+  #[test]
+  fn wrong_size_frames_encode_to_nothing() {
+    let px = solid(4, 4, [0, 0, 0, 255]);
+    assert_eq!(render(&px, 5, 4, TermFormat::HalfBlocks, 4), "");
+    assert_eq!(render(&px[..60], 4, 4, TermFormat::Sixel, 4), "");
+  }
+
+  #[test]
+  fn palette_extremes() {
+    assert_eq!(palette_index([0, 0, 0]), 0);
+    assert_eq!(palette_index([255, 255, 255]), 215);
+    assert_eq!(palette_index([255, 0, 0]), 180);
+  }
+}
@@ -6,39 +6,69 @@
 //! Each test case pairs a name (used as the golden file stem) with a
 //! setup function that configures a [`Figure`].  Both the automated
 //! regression tests and the interactive review GUI import from here
-//! so the definitions stay in sync.
+//! so the definitions stay in sync.  Cases render at [`WIDTH`] x
+//! [`HEIGHT`] unless they ask for another size with
+//! [`TestCase::sized`].
 
 use crate::plotting::{self, linspace};
 
+/// Default width of the visual test renders and goldens, in pixels.
+pub const WIDTH: u32 = 800;
+/// Default height of the visual test renders and goldens, in pixels.
+pub const HEIGHT: u32 = 600;
+
 /// A named visual test case.
 pub struct TestCase {
   /// Display name and golden-file stem.
   pub name: &'static str,
   /// Configures the plot on a [`plotting::Figure`].
-  pub setup: fn(&plotting::Figure),
+  pub setup: fn(&plotting::Figure<'_>),
+  /// Render and golden size as `(width, height)` in pixels.
+  pub size: (u32, u32),
+}
+
+impl TestCase {
+  /// A case rendered at the default [`WIDTH`] x [`HEIGHT`].
+  pub const fn new(name: &'static str, setup: fn(&plotting::Figure<'_>)) -> Self {
+    Self { name, setup, size: (WIDTH, HEIGHT) }
+  }
+
+  /// Renders the case at `width` x `height` instead.
+  pub const fn sized(mut self, width: u32, height: u32) -> Self {
+    self.size = (width, height);
+    self
+  }
 }
 
 /// Returns the full list of visual test cases.
 pub fn all() -> Vec<TestCase> {
   vec![
-    TestCase { name: "line_plot", setup: setup_line_plot },
-    TestCase { name: "scatter_plot", setup: setup_scatter_plot },
-    TestCase { name: "bar_chart", setup: setup_bar_chart },
-    TestCase { name: "multi_line", setup: setup_multi_line },
-    TestCase { name: "histogram", setup: setup_histogram },
-    TestCase {
-      name: "grid_and_labels",
-      setup: setup_grid_and_labels,
-    },
-    TestCase { name: "heatmap", setup: setup_heatmap },
-    TestCase { name: "surface_3d", setup: setup_surface_3d },
-    TestCase { name: "pie_chart", setup: setup_pie_chart },
-    TestCase { name: "box_chart", setup: setup_box_chart },
+    TestCase::new("line_plot", setup_line_plot),
+    TestCase::new("scatter_plot", setup_scatter_plot),
+    TestCase::new("bar_chart", setup_bar_chart),
+    TestCase::new("multi_line", setup_multi_line),
+    TestCase::new("histogram", setup_histogram),
+    TestCase::new("grid_and_labels", setup_grid_and_labels),
+    TestCase::new("heatmap", setup_heatmap),
+    TestCase::new("heatmap_masked", setup_heatmap_masked),
+    TestCase::new("surface_3d", setup_surface_3d),
+    TestCase::new("pie_chart", setup_pie_chart),
+    TestCase::new("box_chart", setup_box_chart),
   ]
 }
 
+/// Names of [`all`] test cases.
+pub fn names() -> Vec<&'static str> {
+  all().iter().map(|tc| tc.name).collect()
+}
+
+/// Smallest size holding every case in `cases`, `(0, 0)` when empty.
+pub fn max_size(cases: &[TestCase]) -> (u32, u32) {
+  cases.iter().fold((0, 0), |(w, h), tc| (w.max(tc.size.0), h.max(tc.size.1)))
+}
+
 /// Sine wave with title and axis labels.
-fn setup_line_plot(fig: &plotting::Figure) {
+fn setup_line_plot(fig: &plotting::Figure<'_>) {
   let ax = fig.current_axes();
   let x = linspace(0.0, 6.283, 100);
   let y: Vec<f64> = x.iter().map(|v| v.sin()).collect();
@@ -49,7 +79,7 @@ fn setup_line_plot(fig: &plotting::Figure) {
 }
 
 /// Scatter plot with deterministic pseudo-random points.
-fn setup_scatter_plot(fig: &plotting::Figure) {
+fn setup_scatter_plot(fig: &plotting::Figure<'_>) {
   let ax = fig.current_axes();
   let x = crate::plotting::randn(80);
   let y = {
@@ -64,14 +94,14 @@ fn setup_scatter_plot(fig: &plotting::Figure) {
 }
 
 /// Bar chart with 5 categories.
-fn setup_bar_chart(fig: &plotting::Figure) {
+fn setup_bar_chart(fig: &plotting::Figure<'_>) {
   let ax = fig.current_axes();
   ax.bar(&[3.0, 7.0, 5.0, 9.0, 2.0]);
   ax.set_title("Bar Chart");
 }
 
 /// Three overlaid curves with different styles.
-fn setup_multi_line(fig: &plotting::Figure) {
+fn setup_multi_line(fig: &plotting::Figure<'_>) {
   let ax = fig.current_axes();
   let x = linspace(0.0, 6.283, 100);
   let y1: Vec<f64> = x.iter().map(|v| v.sin()).collect();
@@ -85,7 +115,7 @@ fn setup_multi_line(fig: &plotting::Figure) {
 }
 
 /// Histogram with deterministic pseudo-random data.
-fn setup_histogram(fig: &plotting::Figure) {
+fn setup_histogram(fig: &plotting::Figure<'_>) {
   let ax = fig.current_axes();
   let data = crate::plotting::randn(200);
   ax.hist(&data, 20);
@@ -93,7 +123,7 @@ fn setup_histogram(fig: &plotting::Figure) {
 }
 
 /// Grid lines and axis labels.
-fn setup_grid_and_labels(fig: &plotting::Figure) {
+fn setup_grid_and_labels(fig: &plotting::Figure<'_>) {
   let ax = fig.current_axes();
   let x = linspace(-5.0, 5.0, 50);
   let y: Vec<f64> = x.iter().map(|v| v * v).collect();
@@ -105,7 +135,7 @@ fn setup_grid_and_labels(fig: &plotting::Figure) {
 }
 
 /// 10x10 heatmap.
-fn setup_heatmap(fig: &plotting::Figure) {
+fn setup_heatmap(fig: &plotting::Figure<'_>) {
   let ax = fig.current_axes();
   let rows = 10usize;
   let cols = 10usize;
@@ -122,15 +152,33 @@ fn setup_heatmap(fig: &plotting::Figure) {
   ax.set_title("Heatmap");
 }
 
+/// 10x10 heatmap with a masked diagonal band next to cells at the
+/// minimum, which must stay visible.
+fn setup_heatmap_masked(fig: &plotting::Figure<'_>) {
+  let ax = fig.current_axes();
+  let rows = 10usize;
+  let cols = 10usize;
+  let mut z = Vec::with_capacity(rows * cols);
+  let mut mask = Vec::with_capacity(rows * cols);
+  for r in 0..rows {
+    for c in 0..cols {
+      z.push(((r * c) % 5) as f64);
+      mask.push((r + c) % 7 == 3);
+    }
+  }
+  ax.heatmap_masked(&z, rows, cols, &mask);
+  ax.set_title("Masked Heatmap");
+}
+
 /// Pie chart with 5 slices.
-fn setup_pie_chart(fig: &plotting::Figure) {
+fn setup_pie_chart(fig: &plotting::Figure<'_>) {
   let ax = fig.current_axes();
   ax.pie(&[30.0, 20.0, 25.0, 15.0, 10.0]);
   ax.set_title("Pie Chart");
 }
 
 /// Box chart (box-and-whisker) with 20 data points.
-fn setup_box_chart(fig: &plotting::Figure) {
+fn setup_box_chart(fig: &plotting::Figure<'_>) {
   let ax = fig.current_axes();
   let data = linspace(-3.0, 3.0, 20);
   ax.boxplot(&data);
@@ -138,7 +186,7 @@ fn setup_box_chart(fig: &plotting::Figure) {
 }
 
 /// 3D surface plot (sinc-like function).
-fn setup_surface_3d(fig: &plotting::Figure) {
+fn setup_surface_3d(fig: &plotting::Figure<'_>) {
   let ax = fig.current_axes();
   let n = 20usize;
   let vals = linspace(-3.0, 3.0, n);
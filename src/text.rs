@@ -1,21 +1,143 @@
-use wgpu_text::glyph_brush::{Section, Text, ab_glyph::FontArc};
-use wgpu_text::{BrushBuilder, TextBrush};
+use wgpu_text::glyph_brush::{FontId, GlyphPositioner, Layout, SectionGeometry, SectionText, ab_glyph::{point, Font, FontArc, PxScale, ScaleFont}};
 use glam::{Vec2, Vec4};
+use std::sync::Arc;
+use crate::emoji::{self, BitmapCache, EmojiLayout};
+use crate::error::Result;
+use crate::glyphs::{GlyphCache, GlyphInstance};
+use crate::mathtext::{self, MathLayout, Metrics};
+use crate::outline::{self, PathCommand};
+use crate::trace::trace_span;
+
+/// DejaVu Sans, embedded with the `embedded-font` feature so rendering
+/// works without system fonts.  See `assets/fonts/LICENSE-DejaVu.txt`.
+#[cfg(feature = "embedded-font")]
+pub const EMBEDDED_FONT: &[u8] = include_bytes!("../assets/fonts/DejaVuSans.ttf");
 
 struct QueuedText {
     text: String,
     pos: Vec2,
     size: f32,
     color: Vec4,
+    /// Counter-clockwise rotation in degrees about `pos`.
+    rotation: f32,
+}
+
+/// How glyphs are placed relative to the pixel grid.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GlyphPositioning {
+    /// Glyphs are rasterized at their fractional position, so text
+    /// moves smoothly when limits animate instead of jumping a pixel.
+    #[default]
+    Subpixel,
+    /// Anchors are rounded to whole pixels and each glyph is rasterized
+    /// once, for crisp, stable static exports.
+    Snap,
+}
+
+impl GlyphPositioning {
+    /// Whole-pixel origin of a glyph at `pos` and the offset from it the
+    /// glyph is rasterized at: quarter pixels when subpixel, none when
+    /// snapped.
+    fn split(self, pos: Vec2) -> (Vec2, Vec2) {
+        match self {
+            GlyphPositioning::Subpixel => {
+                let origin = pos.floor();
+                (origin, ((pos - origin) * 4.0).round() / 4.0)
+            }
+            GlyphPositioning::Snap => (pos.round(), Vec2::ZERO),
+        }
+    }
+}
+
+/// Glyph placement and blending options of a [`TextRenderer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextRendering {
+    /// Subpixel or pixel-snapped glyphs.
+    pub positioning: GlyphPositioning,
+    /// Gamma text colors are decoded with before blending.  sRGB
+    /// targets blend in linear light, so colors given in sRGB (as
+    /// matplot++ colors are) need 2.2 to come out as specified; 1.0
+    /// passes them through unchanged.
+    pub gamma: f32,
+}
+
+impl Default for TextRendering {
+    fn default() -> Self {
+        Self { positioning: GlyphPositioning::default(), gamma: 1.0 }
+    }
+}
+
+impl TextRendering {
+    /// Pixel-snapped glyphs, for static exports.
+    pub fn snapped() -> Self {
+        Self { positioning: GlyphPositioning::Snap, ..Self::default() }
+    }
+
+    /// Sets the color gamma; values that are not positive and finite
+    /// are ignored.
+    pub fn gamma(mut self, gamma: f32) -> Self {
+        if gamma.is_finite() && gamma > 0.0 {
+            self.gamma = gamma;
+        }
+        self
+    }
+
+    /// `color` with its RGB channels decoded with the gamma.  Alpha is
+    /// coverage and stays linear.
+    pub fn decode_color(&self, color: Vec4) -> Vec4 {
+        if self.gamma == 1.0 {
+            return color;
+        }
+        let rgb = color.truncate().max(glam::Vec3::ZERO).powf(self.gamma);
+        rgb.extend(color.w)
+    }
+
+    /// Layout anchor for `pos`, rounded to whole pixels when snapping.
+    pub fn anchor(&self, pos: Vec2) -> Vec2 {
+        match self.positioning {
+            GlyphPositioning::Subpixel => pos,
+            GlyphPositioning::Snap => pos.round(),
+        }
+    }
 }
 
 pub struct TextRenderer {
-    brush: TextBrush<FontArc>,
+    /// Pipeline and glyph atlas, shared by the renderers of a
+    /// [`RenderContext`](crate::context::RenderContext).
+    cache: Arc<GlyphCache>,
+    /// Atlas texture the prepared glyphs are in.
+    atlas: Option<Arc<wgpu::BindGroup>>,
+    /// Glyph quads of the prepared text.
+    instances: Vec<GlyphInstance>,
+    instance_buffer: wgpu::Buffer,
+    /// Primary font followed by the fallbacks, in lookup order.
+    fonts: Vec<FontArc>,
+    /// Decoded color glyphs of `fonts`.
+    emoji_bitmaps: BitmapCache,
     width: u32,
     height: u32,
+    rendering: TextRendering,
+    /// Device pixels per queued pixel.
+    pixel_scale: f32,
     queued_texts: Vec<QueuedText>,
 }
 
+/// `v` turned `degrees` counter-clockwise on a Y-down screen.
+fn turn(v: Vec2, degrees: f32) -> Vec2 {
+    let (s, c) = degrees.to_radians().sin_cos();
+    Vec2::new(v.x * c + v.y * s, v.y * c - v.x * s)
+}
+
+/// Instance buffer for `capacity` glyphs.
+fn instance_buffer(device: &wgpu::Device, capacity: usize) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("GlyphInstances"),
+        size: (capacity * std::mem::size_of::<GlyphInstance>()) as u64,
+        usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
 impl TextRenderer {
     pub fn new(
         device: &wgpu::Device, 
@@ -23,49 +145,163 @@ impl TextRenderer {
         width: u32, 
         height: u32, 
         font_data: &[u8]
+    ) -> Result<Self> {
+        let font = FontArc::try_from_vec(font_data.to_vec())?;
+        let cache = Arc::new(GlyphCache::new(device, format, 1));
+        Ok(Self::with_cache(device, cache, width, height, vec![font]))
+    }
+
+    /// Renderer for already parsed `fonts` (primary first, then
+    /// fallbacks) drawing with the pipeline and glyph atlas of `cache`.
+    /// `fonts` must not be empty.
+    pub(crate) fn with_cache(
+        device: &wgpu::Device,
+        cache: Arc<GlyphCache>,
+        width: u32,
+        height: u32,
+        fonts: Vec<FontArc>,
     ) -> Self {
-        let font = FontArc::try_from_vec(font_data.to_vec()).expect("Failed to parse font");
-        let brush = BrushBuilder::using_font(font)
-            .build(device, width, height, format);
-        
+        let capacity = 256;
         Self {
-            brush,
+            cache,
+            atlas: None,
+            instances: Vec::with_capacity(capacity),
+            instance_buffer: instance_buffer(device, capacity),
+            fonts,
+            emoji_bitmaps: BitmapCache::default(),
             width,
             height,
+            rendering: TextRendering::default(),
+            pixel_scale: 1.0,
             queued_texts: Vec::new(),
         }
     }
 
-    pub fn resize(&mut self, queue: &wgpu::Queue, width: u32, height: u32) {
+    /// The glyph placement and blending options.
+    pub fn rendering(&self) -> TextRendering {
+        self.rendering
+    }
+
+    /// Sets the glyph placement and blending options.  Text queued for
+    /// the current frame is kept.
+    pub fn set_rendering(&mut self, rendering: TextRendering) {
+        self.rendering = rendering;
+    }
+
+    /// Device pixels per pixel of queued text.
+    pub fn pixel_scale(&self) -> f32 {
+        self.pixel_scale
+    }
+
+    /// Multiplies the position and size of queued text by `scale` when
+    /// it is drawn, so a figure laid out in logical pixels is rasterized
+    /// sharply into a target `scale` times larger.  Measurements stay in
+    /// logical pixels.
+    pub fn set_pixel_scale(&mut self, scale: f32) {
+        self.pixel_scale = scale;
+    }
+
+    /// Replaces the primary font, keeping the fallbacks.  Text queued
+    /// for the current frame is kept.
+    pub fn set_font(&mut self, font_data: &[u8]) -> Result<()> {
+        self.fonts[0] = FontArc::try_from_vec(font_data.to_vec())?;
+        self.emoji_bitmaps.clear();
+        Ok(())
+    }
+
+    /// Appends a fallback font.  Characters missing from the primary font
+    /// (Greek, CJK, math symbols, ...) are drawn with the first fallback
+    /// that has them.
+    pub fn add_fallback_font(&mut self, font_data: &[u8]) -> Result<()> {
+        self.fonts.push(FontArc::try_from_vec(font_data.to_vec())?);
+        self.emoji_bitmaps.clear();
+        Ok(())
+    }
+
+    /// Removes all fallback fonts.
+    pub fn clear_fallback_fonts(&mut self) {
+        self.fonts.truncate(1);
+        self.emoji_bitmaps.clear();
+    }
+
+    /// Sets the target size text is prepared for.
+    pub fn resize(&mut self, width: u32, height: u32) {
         self.width = width;
         self.height = height;
-        self.brush.resize_view(width as f32, height as f32, queue);
     }
 
     // Queue text for the current frame
     pub fn draw_text(&mut self, text: &str, pos: Vec2, size: f32, color: Vec4) {
+        self.draw_text_rotated(text, pos, size, color, 0.0);
+    }
+
+    /// Queues text rotated `rotation` degrees counter-clockwise about its
+    /// top-left anchor `pos` (90 reads bottom to top, as Y axis labels).
+    pub fn draw_text_rotated(&mut self, text: &str, pos: Vec2, size: f32, color: Vec4, rotation: f32) {
+        let rotation = if rotation.is_finite() { rotation.rem_euclid(360.0) } else { 0.0 };
         self.queued_texts.push(QueuedText {
             text: text.to_string(),
             pos,
             size,
             color,
+            rotation,
         });
     }
 
     // Process all queued text and upload to GPU
     pub fn prepare(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
-        let sections: Vec<Section> = self.queued_texts.iter().map(|qt| {
-            Section::default()
-                .add_text(
-                    Text::new(&qt.text)
-                        .with_scale(qt.size)
-                        .with_color([qt.color.x, qt.color.y, qt.color.z, qt.color.w])
-                )
-                .with_screen_position((qt.pos.x, qt.pos.y))
-        }).collect();
-
-        self.brush.queue(device, queue, sections).unwrap();
-        
+        let _span = trace_span!("prepare_text", sections = self.queued_texts.len());
+        // Glyphs to rasterize, and for each its whole-pixel origin, the
+        // anchor and rotation of its text and its color.
+        let mut glyphs = Vec::new();
+        let mut placements = Vec::new();
+        for qt in &self.queued_texts {
+            // Rotated text is laid out level from its anchor, then turned
+            // about it.
+            let anchor = self.rendering.anchor(qt.pos * self.pixel_scale);
+            let color = self.rendering.decode_color(qt.color);
+            let scale = PxScale::from(qt.size * self.pixel_scale);
+            let runs: Vec<SectionText> = font_runs(&self.fonts, &qt.text)
+                .into_iter()
+                .map(|(font, text)| SectionText { text, scale, font_id: FontId(font) })
+                .collect();
+            let geometry = SectionGeometry { screen_position: (anchor.x, anchor.y), ..Default::default() };
+            for laid_out in Layout::default().calculate_glyphs(&self.fonts, &geometry, &runs) {
+                let mut glyph = laid_out.glyph;
+                let pos = Vec2::new(glyph.position.x, glyph.position.y);
+                let (origin, offset) = self.rendering.positioning.split(pos);
+                glyph.position = point(offset.x, offset.y);
+                glyphs.push((&self.fonts[laid_out.font_id.0], glyph));
+                placements.push((origin, anchor, qt.rotation, color));
+            }
+        }
+
+        let (placed, atlas) = self.cache.place(device, queue, &glyphs);
+        // Pixels to clip space.
+        let clip = Vec2::new(2.0 / self.width as f32, -2.0 / self.height as f32);
+        self.instances.clear();
+        for ((origin, anchor, degrees, color), glyph) in placements.into_iter().zip(placed) {
+            let Some(glyph) = glyph else { continue };
+            let [_, _, w, h] = glyph.uv;
+            let corner = anchor + turn(origin + glyph.offset - anchor, degrees);
+            self.instances.push(GlyphInstance {
+                origin: (corner * clip + Vec2::new(-1.0, 1.0)).to_array(),
+                axis_x: (turn(Vec2::new(w, 0.0), degrees) * clip).to_array(),
+                axis_y: (turn(Vec2::new(0.0, h), degrees) * clip).to_array(),
+                uv: glyph.uv,
+                color: color.to_array(),
+            });
+        }
+        self.atlas = Some(atlas);
+
+        let size = (self.instances.len() * std::mem::size_of::<GlyphInstance>()) as u64;
+        if size > self.instance_buffer.size() {
+            self.instance_buffer = instance_buffer(device, self.instances.len());
+        }
+        if !self.instances.is_empty() {
+            queue.write_buffer(&self.instance_buffer, 0, bytemuck::cast_slice(&self.instances));
+        }
+
         // Clear for next frame
         self.queued_texts.clear();
     }
@@ -74,15 +310,397 @@ impl TextRenderer {
         self.queued_texts.clear();
     }
 
+    /// Number of text sections queued for the current frame.
+    pub(crate) fn queued_count(&self) -> usize {
+        self.queued_texts.len()
+    }
+
+    /// Drops the text sections queued after the first `n`.
+    pub(crate) fn truncate_queue(&mut self, n: usize) {
+        self.queued_texts.truncate(n);
+    }
+
     pub fn render<'a>(&'a mut self, rpass: &mut wgpu::RenderPass<'a>) {
-        self.brush.draw(rpass);
+        let Some(atlas) = &self.atlas else { return };
+        if self.instances.is_empty() {
+            return;
+        }
+        rpass.set_pipeline(self.cache.pipeline());
+        rpass.set_bind_group(0, atlas, &[]);
+        rpass.set_vertex_buffer(0, self.instance_buffer.slice(..));
+        rpass.draw(0..6, 0..self.instances.len() as u32);
     }
 
+
+    /// Extent of `text` at `size` pixels in the loaded fonts.  Labels
+    /// with `$...$` math are measured as laid out by [`layout_math`](Self::layout_math).
     pub fn measure_text(&self, text: &str, size: f32) -> Vec2 {
-        // Approximate for FiraCode (Monospaced)
-        // Average width approx 0.5 * height (tighter fit)
-        let width = text.len() as f32 * size * 0.5;
-        let height = size;
-        Vec2::new(width, height)
+        measure_text_in(&self.fonts, text, size)
+    }
+
+    /// Queues `text` following the polyline `path` in pixels, each glyph
+    /// centred on the path and turned along it, starting `start` pixels
+    /// along (see [`layout_on_path`]).  Glyphs past the end of the path
+    /// are dropped, and line breaks become spaces.
+    pub fn draw_text_on_path(&mut self, text: &str, path: &[Vec2], size: f32, color: Vec4, start: f32) {
+        let glyphs = glyph_advances(&self.fonts, &text.replace('\n', " "), size);
+        let advances: Vec<f32> = glyphs.iter().map(|g| g.1).collect();
+        let height = measure_with_fonts(&self.fonts, "", size).y;
+        for ((glyph, _), (pos, degrees)) in glyphs.iter().zip(layout_on_path(&advances, height, path, start)) {
+            self.draw_text_rotated(glyph, pos, size, color, degrees);
+        }
+    }
+
+    /// Lays out `text` with [`mathtext`] using the loaded fonts.
+    pub fn layout_math(&self, text: &str, size: f32) -> MathLayout {
+        layout_math_in(&self.fonts, text, size)
+    }
+
+    /// Whether `text` uses color emoji glyphs, which are drawn from an
+    /// [`emoji::layout`] instead of as plain text.
+    pub fn has_color_glyphs(&self, text: &str) -> bool {
+        emoji::has_color_glyphs(&self.fonts, text)
+    }
+
+    /// Lays out `text` with [`emoji::layout`] using the loaded fonts.
+    pub fn layout_emoji(&self, text: &str, size: f32) -> EmojiLayout {
+        emoji::layout(&self.fonts, text, size, &self.emoji_bitmaps)
+    }
+
+    /// GPU-free measurer using the same fonts.
+    pub fn metrics(&self) -> TextMetrics {
+        TextMetrics { fonts: self.fonts.clone(), emoji_bitmaps: self.emoji_bitmaps.clone() }
+    }
+}
+
+/// Text measurement without a GPU device.
+///
+/// Measures exactly like a [`TextRenderer`] with the same fonts, for
+/// layout passes, hit-testing and tests on machines without an adapter.
+#[derive(Clone)]
+pub struct TextMetrics {
+    /// Primary font followed by the fallbacks, in lookup order.
+    fonts: Vec<FontArc>,
+    /// Decoded color glyphs of `fonts`.
+    emoji_bitmaps: BitmapCache,
+}
+
+impl TextMetrics {
+    /// Measurer with `font_data` as the only font.
+    pub fn new(font_data: &[u8]) -> Result<Self> {
+        Ok(Self { fonts: vec![FontArc::try_from_vec(font_data.to_vec())?], emoji_bitmaps: BitmapCache::default() })
+    }
+
+    /// Measurer using the embedded DejaVu Sans font.
+    #[cfg(feature = "embedded-font")]
+    pub fn embedded() -> Self {
+        Self::new(EMBEDDED_FONT).expect("embedded font is valid")
+    }
+
+    /// Appends a fallback font (see [`TextRenderer::add_fallback_font`]).
+    pub fn add_fallback_font(&mut self, font_data: &[u8]) -> Result<()> {
+        self.fonts.push(FontArc::try_from_vec(font_data.to_vec())?);
+        Ok(())
+    }
+
+    /// Same as [`TextRenderer::measure_text`].
+    pub fn measure_text(&self, text: &str, size: f32) -> Vec2 {
+        measure_text_in(&self.fonts, text, size)
+    }
+
+    /// Same as [`TextRenderer::layout_math`].
+    pub fn layout_math(&self, text: &str, size: f32) -> MathLayout {
+        layout_math_in(&self.fonts, text, size)
+    }
+
+    /// Same as [`TextRenderer::has_color_glyphs`].
+    pub fn has_color_glyphs(&self, text: &str) -> bool {
+        emoji::has_color_glyphs(&self.fonts, text)
+    }
+
+    /// Same as [`TextRenderer::layout_emoji`].
+    pub fn layout_emoji(&self, text: &str, size: f32) -> EmojiLayout {
+        emoji::layout(&self.fonts, text, size, &self.emoji_bitmaps)
+    }
+
+    /// Glyph outlines of `text` at `size` pixels drawn at `pos`, for
+    /// vector export (see [`outline`](crate::outline)).
+    pub fn outline_text(&self, text: &str, size: f32, pos: Vec2) -> Vec<PathCommand> {
+        outline::outline_text(&self.fonts, text, size, pos)
+    }
+}
+
+fn measure_text_in(fonts: &[FontArc], text: &str, size: f32) -> Vec2 {
+    if mathtext::has_math(text) {
+        return layout_math_in(fonts, text, size).size;
+    }
+    measure_with_fonts(fonts, text, size)
+}
+
+fn layout_math_in(fonts: &[FontArc], text: &str, size: f32) -> MathLayout {
+    let font = &fonts[0];
+    let height = font.ascent_unscaled() - font.descent_unscaled();
+    let metrics = Metrics {
+        ascent: font.ascent_unscaled() / height,
+        descent: -font.descent_unscaled() / height,
+    };
+    mathtext::layout(text, size, metrics, &|run, size| measure_with_fonts(fonts, run, size).x)
+}
+
+/// Splits `text` into runs drawn with the same font: the first of
+/// `fonts` with a glyph for the character, or the first font if none
+/// has one.  Whitespace stays in the current run.
+pub fn font_runs<'t, F: Font>(fonts: &[F], text: &'t str) -> Vec<(usize, &'t str)> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    let mut current = 0;
+    for (i, c) in text.char_indices() {
+        let font = if c.is_whitespace() {
+            current
+        } else {
+            fonts.iter().position(|f| f.glyph_id(c).0 != 0).unwrap_or(0)
+        };
+        if font != current && i > start {
+            runs.push((current, &text[start..i]));
+            start = i;
+        }
+        current = font;
+    }
+    if start < text.len() {
+        runs.push((current, &text[start..]));
+    }
+    runs
+}
+
+/// Extent of `text` at `size` pixels in `font`, from its glyph advances,
+/// kerning and line metrics.  The width is that of the widest line; the
+/// height spans ascent to descent of every line.
+pub fn measure_with_font<F: Font>(font: &F, text: &str, size: f32) -> Vec2 {
+    measure_with_fonts(std::slice::from_ref(font), text, size)
+}
+
+/// Like [`measure_with_font`], with characters missing from the first
+/// font measured in the first fallback that has them (see
+/// [`font_runs`]).
+pub fn measure_with_fonts<F: Font>(fonts: &[F], text: &str, size: f32) -> Vec2 {
+    let Some(primary) = fonts.first() else { return Vec2::ZERO };
+    let scale = PxScale::from(size);
+    let mut width: f32 = 0.0;
+    let mut lines = 0;
+    for line in text.split('\n') {
+        let mut w = 0.0;
+        for (font, run) in font_runs(fonts, line) {
+            let scaled = fonts[font].as_scaled(scale);
+            let mut prev = None;
+            for c in run.chars() {
+                let id = scaled.glyph_id(c);
+                if let Some(p) = prev {
+                    w += scaled.kern(p, id);
+                }
+                w += scaled.h_advance(id);
+                prev = Some(id);
+            }
+        }
+        width = width.max(w);
+        lines += 1;
+    }
+    let scaled = primary.as_scaled(scale);
+    let line_height = scaled.height() + scaled.line_gap();
+    Vec2::new(width, line_height * lines as f32 - scaled.line_gap())
+}
+
+/// Characters of the single line `text` with their advance at `size`
+/// pixels, kerning against the previous character included.
+fn glyph_advances(fonts: &[FontArc], text: &str, size: f32) -> Vec<(String, f32)> {
+    let mut out = Vec::new();
+    let mut width = 0.0;
+    for (i, c) in text.char_indices() {
+        let end = i + c.len_utf8();
+        let w = measure_with_fonts(fonts, &text[..end], size).x;
+        out.push((c.to_string(), w - width));
+        width = w;
+    }
+    out
+}
+
+/// Length of the polyline `path`.
+pub fn path_length(path: &[Vec2]) -> f32 {
+    path.windows(2).map(|w| w[0].distance(w[1])).sum()
+}
+
+/// Top-left anchors and rotations (degrees counter-clockwise, as
+/// [`TextRenderer::draw_text_rotated`] takes them) of glyphs `advances`
+/// wide and `height` tall laid out along the polyline `path`, starting
+/// `start` pixels along it.  Each glyph is centred on the path at the
+/// middle of its advance and turned to the path's direction there.
+/// Only the glyphs whose middle falls on the path are returned, so the
+/// result may be shorter than `advances`.
+///
+/// To centre a label on the path, start at half of [`path_length`]
+/// minus half the text width.
+pub fn layout_on_path(advances: &[f32], height: f32, path: &[Vec2], start: f32) -> Vec<(Vec2, f32)> {
+    let mut out = Vec::new();
+    let mut segments = path.windows(2).filter(|w| w[0] != w[1]);
+    let Some(mut seg) = segments.next() else { return out };
+    // Distance along the path to the start of `seg`.
+    let mut seg_start = 0.0;
+    let mut along = start.max(0.0);
+    for &advance in advances {
+        let mid = along + advance / 2.0;
+        along += advance;
+        while mid > seg_start + seg[0].distance(seg[1]) {
+            seg_start += seg[0].distance(seg[1]);
+            match segments.next() {
+                Some(next) => seg = next,
+                None => return out,
+            }
+        }
+        let dir = (seg[1] - seg[0]).normalize();
+        let at = seg[0] + dir * (mid - seg_start);
+        // Up in the glyph frame on a Y-down screen.
+        let up = Vec2::new(dir.y, -dir.x);
+        let degrees = (-dir.y).atan2(dir.x).to_degrees();
+        out.push((at - dir * (advance / 2.0) + up * (height / 2.0), degrees));
+    }
+    out
+}
+
+/// Points of a circular arc around `center` in pixels, from `from` to
+/// `to` degrees counter-clockwise from the positive X axis, for text
+/// around polar axes.  Text reads in the direction of the arc, so a
+/// label over the top of a circle runs from the larger angle to the
+/// smaller one.
+pub fn arc_path(center: Vec2, radius: f32, from: f32, to: f32, segments: usize) -> Vec<Vec2> {
+    let segments = segments.max(1);
+    (0..=segments)
+        .map(|i| {
+            let a = (from + (to - from) * i as f32 / segments as f32).to_radians();
+            center + radius * Vec2::new(a.cos(), -a.sin())
+        })
+        .collect()
+}
+
+/// Average character advance in DejaVu Sans, in ems.
+const AVERAGE_ADVANCE: f32 = 0.6;
+
+/// Approximate rendered width of `text` at `size` pixels, for when no
+/// font is loaded: an average advance per character.
+pub fn estimate_text_width(text: &str, size: f32) -> f32 {
+    text.chars().count() as f32 * size * AVERAGE_ADVANCE
+}
+
+/// Width of `text` at `size` pixels in the embedded font, which
+/// renderers use unless given another, or [`estimate_text_width`]
+/// without the `embedded-font` feature.
+pub fn default_text_width(text: &str, size: f32) -> f32 {
+    #[cfg(feature = "embedded-font")]
+    {
+        static FONT: std::sync::OnceLock<FontArc> = std::sync::OnceLock::new();
+        let font = FONT.get_or_init(|| FontArc::try_from_slice(EMBEDDED_FONT).expect("embedded font is valid"));
+        measure_text_in(std::slice::from_ref(font), text, size).x
+    }
+    #[cfg(not(feature = "embedded-font"))]
+    estimate_text_width(text, size)
+}
+
+#[cfg(all(test, feature = "embedded-font"))]
+mod tests {
+    use super::*;
+
+    fn font() -> FontArc {
+        FontArc::try_from_slice(EMBEDDED_FONT).unwrap()
+    }
+
+    #[test]
+    fn measures_proportional_glyphs() {
+        let f = font();
+        let narrow = measure_with_font(&f, "iiii", 20.0);
+        let wide = measure_with_font(&f, "WWWW", 20.0);
+        assert!(wide.x > narrow.x * 2.0);
+        let two_lines = measure_with_font(&f, "a\nbb", 20.0);
+        assert!(two_lines.y > narrow.y * 1.9);
+    }
+
+    #[test]
+    fn default_width_uses_the_glyphs() {
+        assert!(default_text_width("WWWW", 20.0) > default_text_width("iiii", 20.0) * 2.0);
+        assert_eq!(default_text_width("1.5", 10.0), measure_with_font(&font(), "1.5", 10.0).x);
+        assert_eq!(estimate_text_width("\u{b5}s", 10.0), 12.0);
+    }
+
+    #[test]
+    fn missing_glyphs_fall_back() {
+        let f = font();
+        // DejaVu Sans has Greek but no CJK.
+        assert_eq!(font_runs(std::slice::from_ref(&f), "\u{3b1} + \u{4e00}"), vec![(0, "\u{3b1} + \u{4e00}")]);
+        // A fallback without the glyph does not take the character.
+        let fonts = [f.clone(), f];
+        assert_eq!(font_runs(&fonts, "x \u{4e00}"), vec![(0, "x \u{4e00}")]);
+        assert!(font_runs(&fonts, "").is_empty());
+        assert_eq!(measure_with_fonts::<FontArc>(&[], "x", 10.0), Vec2::ZERO);
+    }
+
+    #[test]
+    fn rendering_snaps_and_decodes_colors() {
+        let pos = Vec2::new(10.4, 20.6);
+        assert_eq!(TextRendering::default().anchor(pos), pos);
+        assert_eq!(TextRendering::snapped().anchor(pos), Vec2::new(10.0, 21.0));
+        let gray = Vec4::new(0.5, 0.5, 0.5, 0.25);
+        assert_eq!(TextRendering::default().decode_color(gray), gray);
+        let decoded = TextRendering::default().gamma(2.0).decode_color(gray);
+        assert_eq!(decoded, Vec4::new(0.25, 0.25, 0.25, 0.25));
+        assert_eq!(TextRendering::default().gamma(-1.0).gamma, 1.0);
+    }
+
+    #[test]
+    fn glyphs_follow_the_path() {
+        let path = [Vec2::new(0.0, 100.0), Vec2::new(20.0, 100.0), Vec2::new(20.0, 80.0)];
+        assert_eq!(path_length(&path), 40.0);
+        let glyphs = layout_on_path(&[10.0, 10.0, 10.0, 10.0, 10.0], 4.0, &path, 0.0);
+        // The fifth glyph's middle is past the end.
+        assert_eq!(glyphs.len(), 4);
+        assert_eq!(glyphs[0], (Vec2::new(0.0, 98.0), 0.0));
+        // Up the vertical leg, reading bottom to top.
+        let (pos, degrees) = glyphs[2];
+        assert_eq!(degrees, 90.0);
+        assert!(pos.abs_diff_eq(Vec2::new(18.0, 100.0), 1e-4));
+        assert!(layout_on_path(&[10.0], 4.0, &path[..1], 0.0).is_empty());
+
+        let arc = arc_path(Vec2::ZERO, 10.0, 180.0, 0.0, 2);
+        assert!(arc[1].abs_diff_eq(Vec2::new(0.0, -10.0), 1e-4));
+        let advances: Vec<f32> = glyph_advances(&[font()], "AVA", 20.0).iter().map(|g| g.1).collect();
+        assert_eq!(advances.len(), 3);
+        // Advances include kerning, so they add up to the measured width.
+        let width = measure_with_font(&font(), "AVA", 20.0).x;
+        assert!((advances.iter().sum::<f32>() - width).abs() < 1e-3);
+    }
+
+    #[test]
+    fn renderers_of_a_context_share_glyphs() {
+        let shared = crate::capture::shared_context().expect("headless GPU context");
+        let ctx = crate::context::RenderContext::with_embedded_font(
+            shared.device().clone(),
+            shared.queue().clone(),
+            crate::capture::CAPTURE_FORMAT,
+        );
+        let (device, queue) = (ctx.device(), ctx.queue());
+        let mut a = ctx.text_renderer(200, 100);
+        let mut b = ctx.text_renderer(200, 100);
+        assert!(Arc::ptr_eq(&a.cache, &b.cache));
+
+        a.draw_text("shared", Vec2::new(10.0, 10.0), 14.0, Vec4::ONE);
+        a.prepare(device, queue);
+        let rasterized = a.cache.len();
+        // Same glyphs at the same subpixel offsets, one of them turned.
+        b.draw_text("shared", Vec2::new(50.0, 30.0), 14.0, Vec4::ONE);
+        b.draw_text_rotated("shared", Vec2::new(50.0, 30.0), 14.0, Vec4::ONE, 90.0);
+        b.prepare(device, queue);
+        assert_eq!(b.cache.len(), rasterized);
+        assert_eq!(a.instances.len(), 6);
+        assert_eq!(b.instances.len(), 12);
+        // The turned copy reads bottom to top from its anchor.
+        let [level, turned] = [b.instances[0], b.instances[6]];
+        assert!(level.axis_x[0] > 0.0 && level.axis_x[1] == 0.0);
+        assert!(turned.axis_x[0].abs() < 1e-6 && turned.axis_x[1] > 0.0);
     }
 }
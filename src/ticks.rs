@@ -0,0 +1,502 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Tick placement and pluggable tick label formatting.
+//!
+//! [`nice_ticks`] picks round tick positions (1, 2, 5 x 10^k steps)
+//! for a numeric range, and a [`TickFormatter`] turns each position
+//! into label text.  Built-in formatters cover fixed precision,
+//! percentages, SI prefixes, currency, and powers of ten; any
+//! `Fn(f64) -> String` closure is a formatter too.  [`log_ticks`]
+//! places decade and minor ticks on logarithmic axes, and an
+//! [`OffsetScale`] keeps labels of large-magnitude data short.  A
+//! [`TickAdjuster`] edits the placed positions on every render without
+//! replacing the placement.
+
+/// Default upper bound on the number of ticks per axis.
+pub const DEFAULT_MAX_TICKS: usize = 8;
+
+/// Converts tick values into label text.
+pub trait TickFormatter {
+  /// Formats `value`.  `step` is the spacing between neighbouring
+  /// ticks, for formatters that derive precision from it.
+  fn format(&self, value: f64, step: f64) -> String;
+}
+
+impl<F: Fn(f64) -> String> TickFormatter for F {
+  fn format(&self, value: f64, _step: f64) -> String {
+    self(value)
+  }
+}
+
+/// Plain numbers with just enough decimals to tell ticks apart.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Auto;
+
+impl TickFormatter for Auto {
+  fn format(&self, value: f64, step: f64) -> String {
+    format!("{:.*}", decimals_for(step), clean_zero(value))
+  }
+}
+
+/// Plain numbers with a fixed number of decimals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fixed(pub usize);
+
+impl TickFormatter for Fixed {
+  fn format(&self, value: f64, _step: f64) -> String {
+    format!("{:.*}", self.0, clean_zero(value))
+  }
+}
+
+/// Fractions shown as percentages (`0.25` -> `25%`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Percent {
+  /// Decimals after the percent value.
+  pub decimals: usize,
+}
+
+impl TickFormatter for Percent {
+  fn format(&self, value: f64, _step: f64) -> String {
+    format!("{:.*}%", self.decimals, clean_zero(value * 100.0))
+  }
+}
+
+/// Engineering notation with SI prefixes (`1500` -> `1.5k`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SiPrefix {
+  /// Maximum decimals; trailing zeros are trimmed.
+  pub decimals: usize,
+}
+
+const SI_PREFIXES: [&str; 17] = [
+  "y", "z", "a", "f", "p", "n", "\u{b5}", "m", "", "k", "M", "G", "T",
+  "P", "E", "Z", "Y",
+];
+
+impl TickFormatter for SiPrefix {
+  fn format(&self, value: f64, _step: f64) -> String {
+    if value == 0.0 || !value.is_finite() {
+      return format!("{}", clean_zero(value));
+    }
+    let exp3 = (value.abs().log10() / 3.0).floor() as i32;
+    let exp3 = exp3.clamp(-8, 8);
+    let scaled = value / 10f64.powi(exp3 * 3);
+    let mut s = format!("{:.*}", self.decimals, scaled);
+    if s.contains('.') {
+      s = s.trim_end_matches('0').trim_end_matches('.').to_string();
+    }
+    s.push_str(SI_PREFIXES[(exp3 + 8) as usize]);
+    s
+  }
+}
+
+/// Currency amounts with a leading symbol and thousands separators
+/// (`-1234.5` -> `-$1,234.50`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Currency {
+  /// Symbol placed before the amount.
+  pub symbol: String,
+  /// Decimals after the amount.
+  pub decimals: usize,
+}
+
+impl Currency {
+  /// Currency formatter with two decimals.
+  pub fn new(symbol: &str) -> Self {
+    Self {
+      symbol: symbol.to_string(),
+      decimals: 2,
+    }
+  }
+}
+
+impl TickFormatter for Currency {
+  fn format(&self, value: f64, _step: f64) -> String {
+    let value = clean_zero(value);
+    let digits = format!("{:.*}", self.decimals, value.abs());
+    let (int, frac) = match digits.split_once('.') {
+      Some((i, f)) => (i, Some(f)),
+      None => (digits.as_str(), None),
+    };
+    let mut grouped = String::with_capacity(int.len() + int.len() / 3);
+    for (i, ch) in int.chars().enumerate() {
+      if i > 0 && (int.len() - i) % 3 == 0 {
+        grouped.push(',');
+      }
+      grouped.push(ch);
+    }
+    let sign = if value < 0.0 { "-" } else { "" };
+    match frac {
+      Some(f) => format!("{}{}{}.{}", sign, self.symbol, grouped, f),
+      None => format!("{}{}{}", sign, self.symbol, grouped),
+    }
+  }
+}
+
+/// Scientific notation with Unicode superscripts (`1000` -> `10³`,
+/// `2500` -> `2.5×10³`), as used on logarithmic axes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Scientific {
+  /// Maximum mantissa decimals; trailing zeros are trimmed.
+  pub decimals: usize,
+}
+
+impl TickFormatter for Scientific {
+  fn format(&self, value: f64, _step: f64) -> String {
+    if value == 0.0 || !value.is_finite() {
+      return format!("{}", clean_zero(value));
+    }
+    let mut exp = value.abs().log10().floor() as i32;
+    let mut mantissa = value / 10f64.powi(exp);
+    let mut m = format!("{:.*}", self.decimals, mantissa);
+    // Rounding can carry the mantissa to 10.
+    if m.trim_start_matches('-').starts_with("10") {
+      exp += 1;
+      mantissa /= 10.0;
+      m = format!("{:.*}", self.decimals, mantissa);
+    }
+    if m.contains('.') {
+      m = m.trim_end_matches('0').trim_end_matches('.').to_string();
+    }
+    let power = format!("10{}", superscript(exp));
+    match m.as_str() {
+      "1" => power,
+      "-1" => format!("-{}", power),
+      _ => format!("{}\u{d7}{}", m, power),
+    }
+  }
+}
+
+/// `n` written with Unicode superscript digits.
+fn superscript(n: i32) -> String {
+  const DIGITS: [char; 10] = [
+    '\u{2070}', '\u{b9}', '\u{b2}', '\u{b3}', '\u{2074}', '\u{2075}',
+    '\u{2076}', '\u{2077}', '\u{2078}', '\u{2079}',
+  ];
+  let mut s = String::new();
+  if n < 0 {
+    s.push('\u{207b}');
+  }
+  for c in n.unsigned_abs().to_string().chars() {
+    s.push(DIGITS[c as usize - '0' as usize]);
+  }
+  s
+}
+
+/// Decade and minor ticks for a logarithmic axis over `[min, max]`.
+///
+/// Returns `(major, minor)`: powers of ten inside the range (every
+/// n-th decade when more than `max_ticks` would fit) and the
+/// 2..9 multiples inside the range.  Minor ticks are omitted when the
+/// decades are thinned.  Non-positive or non-finite bounds yield no
+/// ticks.
+pub fn log_ticks(min: f64, max: f64, max_ticks: usize) -> (Vec<f64>, Vec<f64>) {
+  let (lo, hi) = (min.min(max), min.max(max));
+  if lo.is_nan() || lo <= 0.0 || !hi.is_finite() {
+    return (Vec::new(), Vec::new());
+  }
+  let first = lo.log10().floor() as i32;
+  let last = hi.log10().ceil() as i32;
+  let decades = (last - first + 1) as usize;
+  let stride = decades.div_ceil(max_ticks.max(2)).max(1);
+  let inside = |v: f64| v >= lo * (1.0 - 1e-12) && v <= hi * (1.0 + 1e-12);
+  let mut major = Vec::new();
+  let mut minor = Vec::new();
+  for k in first..=last {
+    let decade = 10f64.powi(k);
+    if ((k - first) as usize).is_multiple_of(stride) && inside(decade) {
+      major.push(decade);
+    }
+    if stride == 1 {
+      minor.extend((2..10).map(|m| m as f64 * decade).filter(|&v| inside(v)));
+    }
+  }
+  (major, minor)
+}
+
+/// Round tick positions covering `[min, max]`.
+///
+/// Returns the positions that fall inside the range and the step
+/// between them.  Degenerate or non-finite ranges yield no ticks.
+pub fn nice_ticks(min: f64, max: f64, max_ticks: usize) -> (Vec<f64>, f64) {
+  if !min.is_finite() || !max.is_finite() || min == max {
+    return (Vec::new(), 0.0);
+  }
+  let (lo, hi) = if min < max { (min, max) } else { (max, min) };
+  let raw = (hi - lo) / max_ticks.max(2) as f64;
+  let mag = 10f64.powf(raw.log10().floor());
+  let step = [1.0, 2.0, 5.0, 10.0]
+    .iter()
+    .map(|m| m * mag)
+    .find(|s| (hi - lo) / s <= max_ticks.max(2) as f64)
+    .unwrap_or(10.0 * mag);
+  let first = (lo / step).ceil() as i64;
+  let last = (hi / step).floor() as i64;
+  let ticks = (first..=last).map(|i| i as f64 * step).collect();
+  (ticks, step)
+}
+
+/// Axis whose ticks a [`TickAdjuster`] is adjusting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TickAxis {
+  /// The horizontal axis.
+  X,
+  /// The vertical axis.
+  Y,
+}
+
+/// Adjusts tick positions computed for an axis, e.g. adding a tick at
+/// an event time or dropping ticks near an annotation.  Any
+/// `FnMut(TickAxis, (f64, f64), &mut Vec<f64>)` closure is an adjuster.
+pub trait TickAdjuster: Send {
+  /// Edits `ticks`, the round positions placed over `limits` of
+  /// `axis`, in place.
+  fn adjust(&mut self, axis: TickAxis, limits: (f64, f64), ticks: &mut Vec<f64>);
+}
+
+impl<F: FnMut(TickAxis, (f64, f64), &mut Vec<f64>) + Send> TickAdjuster for F {
+  fn adjust(&mut self, axis: TickAxis, limits: (f64, f64), ticks: &mut Vec<f64>) {
+    self(axis, limits, ticks)
+  }
+}
+
+/// Round ticks over `limits` passed through `adjuster`, then sorted
+/// with duplicates and positions outside the limits removed.
+pub fn adjusted_ticks(
+  axis: TickAxis,
+  limits: (f64, f64),
+  max_ticks: usize,
+  adjuster: &mut dyn TickAdjuster,
+) -> Vec<f64> {
+  adjust_placed_ticks(axis, limits, Vec::new(), max_ticks, adjuster)
+}
+
+/// Like [`adjusted_ticks`], starting from the ticks `placed` by the
+/// plotting library when at least two of them lie within `limits`, and
+/// from round ticks otherwise.
+pub fn adjust_placed_ticks(
+  axis: TickAxis,
+  limits: (f64, f64),
+  placed: Vec<f64>,
+  max_ticks: usize,
+  adjuster: &mut dyn TickAdjuster,
+) -> Vec<f64> {
+  let (lo, hi) = (limits.0.min(limits.1), limits.0.max(limits.1));
+  let mut ticks = if placed.iter().filter(|&&t| t >= lo && t <= hi).count() >= 2 {
+    placed
+  } else {
+    nice_ticks(limits.0, limits.1, max_ticks).0
+  };
+  adjuster.adjust(axis, limits, &mut ticks);
+  ticks.retain(|&t| t >= lo && t <= hi);
+  ticks.sort_by(f64::total_cmp);
+  ticks.dedup();
+  ticks
+}
+
+/// Formats every tick with `formatter`.
+pub fn labels(
+  ticks: &[f64],
+  step: f64,
+  formatter: &dyn TickFormatter,
+) -> Vec<String> {
+  ticks.iter().map(|&t| formatter.format(t, step)).collect()
+}
+
+/// Offset subtracted from, and power of ten divided out of, tick values
+/// so labels of large-magnitude data stay short: ticks at
+/// `1_700_000_200`, `1_700_000_400` read `200`, `400` with `+1.7e9`
+/// shown near the axis.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OffsetScale {
+  /// Value subtracted from every tick.
+  pub offset: f64,
+  /// Power of ten the residuals are divided by.
+  pub exponent: i32,
+}
+
+impl OffsetScale {
+  /// Offset and multiplier for `ticks`: an offset when the values are
+  /// large compared to their spread (see [`crate::origin::axis_origin`]),
+  /// then a multiple-of-three exponent when the residuals are still
+  /// at least 10⁴ or below 10⁻³.
+  pub fn for_ticks(ticks: &[f64]) -> Self {
+    let finite = || ticks.iter().copied().filter(|v| v.is_finite());
+    let (Some(lo), Some(hi)) = (finite().reduce(f64::min), finite().reduce(f64::max)) else {
+      return Self::default();
+    };
+    let offset = if lo == hi || crate::origin::axis_origin(lo, hi) == 0.0 {
+      0.0
+    } else {
+      // Round to the power of ten above the spread, so the offset is short.
+      let unit = 10f64.powf((hi - lo).log10().ceil());
+      (lo / unit).floor() * unit
+    };
+    let largest = finite().map(|v| (v - offset).abs()).fold(0.0, f64::max);
+    let exponent = if largest >= 1e4 || (largest > 0.0 && largest < 1e-3) {
+      (largest.log10().floor() as i32).div_euclid(3) * 3
+    } else {
+      0
+    };
+    Self { offset, exponent }
+  }
+
+  /// Whether labels are unchanged.
+  pub fn is_identity(&self) -> bool {
+    self.offset == 0.0 && self.exponent == 0
+  }
+
+  /// `value` as shown in the tick label.
+  pub fn apply(&self, value: f64) -> f64 {
+    (value - self.offset) / 10f64.powi(self.exponent)
+  }
+
+  /// Tick labels for `ticks` `step` apart: `formatter` applied to the
+  /// scaled residuals.
+  pub fn labels(&self, ticks: &[f64], step: f64, formatter: &dyn TickFormatter) -> Vec<String> {
+    let step = step / 10f64.powi(self.exponent);
+    ticks.iter().map(|&t| formatter.format(clean_zero(self.apply(t)), step)).collect()
+  }
+
+  /// Text shown near the axis, multiplier first (`×10³ +1.7e9`), or
+  /// empty for the identity.
+  pub fn label(&self) -> String {
+    let mut parts = Vec::new();
+    if self.exponent != 0 {
+      parts.push(format!("\u{d7}10{}", superscript(self.exponent)));
+    }
+    if self.offset != 0.0 {
+      parts.push(offset_text(self.offset));
+    }
+    parts.join(" ")
+  }
+}
+
+/// Signed short form of an offset: `+1.7e9`, `-250`.
+fn offset_text(v: f64) -> String {
+  let exp = v.abs().log10().floor() as i32;
+  if (-3..5).contains(&exp) {
+    return format!("{:+}", v);
+  }
+  let mantissa = format!("{:.12}", v / 10f64.powi(exp));
+  let mantissa = mantissa.trim_end_matches('0').trim_end_matches('.');
+  let sign = if v < 0.0 { "" } else { "+" };
+  format!("{}{}e{}", sign, mantissa, exp)
+}
+
+/// Decimals needed to distinguish ticks `step` apart.
+fn decimals_for(step: f64) -> usize {
+  if step <= 0.0 || !step.is_finite() {
+    return 1;
+  }
+  (-step.log10().floor()).max(0.0) as usize
+}
+
+/// Maps `-0.0` (and tiny rounding residue around zero) to `0.0`.
+fn clean_zero(v: f64) -> f64 {
+  if v.abs() < 1e-12 {
+    0.0
+  } else {
+    v
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn offset_and_multiplier() {
+    let ticks = [1_700_000_200.0, 1_700_000_400.0, 1_700_000_600.0];
+    let os = OffsetScale::for_ticks(&ticks);
+    assert_eq!(os, OffsetScale { offset: 1_700_000_000.0, exponent: 0 });
+    assert_eq!(os.labels(&ticks, 200.0, &Auto), ["200", "400", "600"]);
+    assert_eq!(os.label(), "+1.7e9");
+
+    let big = [0.0, 2e6, 4e6];
+    let os = OffsetScale::for_ticks(&big);
+    assert_eq!(os.exponent, 6);
+    assert_eq!(os.labels(&big, 2e6, &Auto), ["0", "2", "4"]);
+    assert_eq!(os.label(), "\u{d7}10\u{2076}");
+
+    assert!(OffsetScale::for_ticks(&[0.0, 0.5, 1.0]).is_identity());
+    assert_eq!(OffsetScale::for_ticks(&[]).label(), "");
+    assert_eq!(offset_text(-250.0), "-250");
+  }
+
+  #[test]
+  fn nice_ticks_uses_round_steps() {
+    let (t, step) = nice_ticks(0.0, 1.0, 8);
+    assert_eq!(step, 0.2);
+    assert_eq!(t.len(), 6);
+    let (t, step) = nice_ticks(-3.0, 47.0, 8);
+    assert_eq!(step, 10.0);
+    assert_eq!(t, vec![0.0, 10.0, 20.0, 30.0, 40.0]);
+    assert!(nice_ticks(1.0, 1.0, 8).0.is_empty());
+  }
+
+  #[test]
+  fn builtin_formatters() {
+    assert_eq!(Auto.format(0.4, 0.2), "0.4");
+    assert_eq!(Auto.format(-1e-17, 0.5), "0.0");
+    assert_eq!(Fixed(3).format(1.5, 1.0), "1.500");
+    assert_eq!(Percent { decimals: 0 }.format(0.25, 0.05), "25%");
+    assert_eq!(SiPrefix { decimals: 2 }.format(1500.0, 500.0), "1.5k");
+    assert_eq!(SiPrefix { decimals: 1 }.format(0.002, 0.001), "2m");
+    assert_eq!(Currency::new("$").format(-1234.5, 1.0), "-$1,234.50");
+  }
+
+  #[test]
+  fn log_ticks_per_decade() {
+    let (major, minor) = log_ticks(0.5, 1000.0, 8);
+    assert_eq!(major, vec![1.0, 10.0, 100.0, 1000.0]);
+    assert_eq!(minor.len(), 5 + 8 * 3);
+    assert_eq!(minor[0], 0.5);
+    let (major, minor) = log_ticks(1e-10, 1e10, 5);
+    assert_eq!(major.len(), 5);
+    assert!(minor.is_empty());
+    assert!(log_ticks(0.0, 10.0, 8).0.is_empty());
+  }
+
+  #[test]
+  fn scientific_labels() {
+    let f = Scientific { decimals: 1 };
+    assert_eq!(f.format(1000.0, 0.0), "10\u{b3}");
+    assert_eq!(f.format(0.01, 0.0), "10\u{207b}\u{b2}");
+    assert_eq!(f.format(2500.0, 0.0), "2.5\u{d7}10\u{b3}");
+    assert_eq!(f.format(9.99, 0.0), "10\u{b9}");
+    assert_eq!(f.format(-100.0, 0.0), "-10\u{b2}");
+  }
+
+  #[test]
+  fn closures_are_formatters() {
+    let f = |v: f64| format!("{}x", v);
+    assert_eq!(labels(&[1.0, 2.0], 1.0, &f), vec!["1x", "2x"]);
+  }
+
+  #[test]
+  fn adjusters_edit_placed_ticks() {
+    // Force a tick at an event time and drop the one next to it.
+    let mut event = |axis: TickAxis, _: (f64, f64), ticks: &mut Vec<f64>| {
+      if axis == TickAxis::X {
+        ticks.retain(|&t| t != 4.0);
+        ticks.extend([4.3, 2.0, 11.0]);
+      }
+    };
+    let x = adjusted_ticks(TickAxis::X, (0.0, 10.0), 5, &mut event);
+    assert_eq!(x, vec![0.0, 2.0, 4.3, 6.0, 8.0, 10.0]);
+    let y = adjusted_ticks(TickAxis::Y, (0.0, 10.0), 5, &mut event);
+    assert_eq!(y, nice_ticks(0.0, 10.0, 5).0);
+  }
+
+  #[test]
+  fn placed_ticks_are_kept_unless_too_few_fall_within_the_limits() {
+    let mut keep = |_: TickAxis, _: (f64, f64), _: &mut Vec<f64>| {};
+    let placed = vec![0.0, 2.5, 5.0, 7.5, 10.0];
+    assert_eq!(adjust_placed_ticks(TickAxis::X, (0.0, 10.0), placed, 5, &mut keep), vec![0.0, 2.5, 5.0, 7.5, 10.0]);
+    // Stale ticks from wider limits.
+    let stale = vec![0.0, 50.0, 100.0];
+    assert_eq!(adjust_placed_ticks(TickAxis::X, (0.0, 10.0), stale, 5, &mut keep), nice_ticks(0.0, 10.0, 5).0);
+  }
+}
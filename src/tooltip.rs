@@ -0,0 +1,140 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Hover tooltips.
+//!
+//! A [`Tooltip`] is a rounded box of text lines pointing at a data
+//! position, typically the point under the cursor found with
+//! [`PlotBackend::pick`](crate::plotting::PlotBackend::pick).  The box
+//! sits above and to the right of its anchor and flips to the other
+//! side of it wherever it would leave the axes.  Show one with
+//! [`PlotBackend::set_tooltip`](crate::plotting::PlotBackend::set_tooltip).
+
+use crate::artist::{Artist, DataRect, DrawContext};
+use glam::{Vec2, Vec4};
+
+/// Gap between the anchor and the box, in pixels.
+const OFFSET: f32 = 10.0;
+/// Box padding, in pixels.
+const PAD: f32 = 6.0;
+/// Corner radius, in pixels.
+const RADIUS: f32 = 4.0;
+
+/// Text box anchored to a data position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tooltip {
+  /// Data position pointed at.
+  pub anchor: (f64, f64),
+  /// Lines of text, top to bottom.
+  pub lines: Vec<String>,
+  /// Text color (RGBA).
+  pub color: Vec4,
+  /// Box fill (RGBA).
+  pub background: Vec4,
+  /// Font size, in pixels.
+  pub font_size: f32,
+}
+
+impl Tooltip {
+  /// Black text on white at `anchor`.
+  pub fn new<S: Into<String>>(anchor: (f64, f64), lines: impl IntoIterator<Item = S>) -> Self {
+    Self {
+      anchor,
+      lines: lines.into_iter().map(Into::into).collect(),
+      color: Vec4::new(0.0, 0.0, 0.0, 1.0),
+      background: Vec4::new(1.0, 1.0, 1.0, 0.95),
+      font_size: 12.0,
+    }
+  }
+
+  /// Sets the text color.
+  pub fn color(mut self, color: Vec4) -> Self {
+    self.color = color;
+    self
+  }
+
+  /// Sets the box fill.
+  pub fn background(mut self, background: Vec4) -> Self {
+    self.background = background;
+    self
+  }
+
+  /// Sets the font size in pixels.
+  pub fn font_size(mut self, size: f32) -> Self {
+    self.font_size = size;
+    self
+  }
+}
+
+/// Top-left corner of a box of `size` pointing at pixel `anchor`:
+/// above and to the right, flipped left or below where that would
+/// leave `pixel_box` (`[left, top, right, bottom]`), then clamped into
+/// it as far as it fits.
+pub fn place(anchor: Vec2, size: Vec2, pixel_box: [f32; 4]) -> Vec2 {
+  let [left, top, right, bottom] = pixel_box;
+  let mut x = anchor.x + OFFSET;
+  if x + size.x > right {
+    x = anchor.x - OFFSET - size.x;
+  }
+  let mut y = anchor.y - OFFSET - size.y;
+  if y < top {
+    y = anchor.y + OFFSET;
+  }
+  Vec2::new(x.min(right - size.x).max(left), y.min(bottom - size.y).max(top))
+}
+
+impl Artist for Tooltip {
+  fn draw(&self, ctx: &mut DrawContext<'_>) {
+    if self.lines.is_empty() {
+      return;
+    }
+    let anchor = ctx.to_pixels(self.anchor.0, self.anchor.1);
+    if !anchor.is_finite() {
+      return;
+    }
+    let line_h = self.font_size + PAD * 0.5;
+    let w = self
+      .lines
+      .iter()
+      .map(|s| ctx.measure_text(s, self.font_size))
+      .fold(0.0, f32::max)
+      + PAD * 2.0;
+    let h = line_h * self.lines.len() as f32 + PAD * 1.5;
+    let size = Vec2::new(w, h);
+    let origin = place(anchor, size, ctx.pixel_box());
+    ctx.rounded_rect_pixels(origin, size, self.background, RADIUS, 0.0);
+    ctx.rounded_rect_pixels(origin, size, Vec4::new(0.0, 0.0, 0.0, 0.6), RADIUS, 0.75);
+    for (i, line) in self.lines.iter().enumerate() {
+      let pos = origin + Vec2::new(PAD, PAD + line_h * i as f32);
+      ctx.text_pixels(pos, line, self.font_size, self.color);
+    }
+  }
+
+  fn extent(&self) -> Option<DataRect> {
+    None
+  }
+
+  fn z_order(&self) -> i32 {
+    i32::MAX
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn boxes_flip_to_stay_inside() {
+    let pixel_box = [0.0, 0.0, 200.0, 100.0];
+    let size = Vec2::new(50.0, 20.0);
+    // Room above and to the right.
+    assert_eq!(place(Vec2::new(50.0, 50.0), size, pixel_box), Vec2::new(60.0, 20.0));
+    // Near the right edge: flipped left.
+    assert_eq!(place(Vec2::new(180.0, 50.0), size, pixel_box), Vec2::new(120.0, 20.0));
+    // Near the top: flipped below.
+    assert_eq!(place(Vec2::new(50.0, 10.0), size, pixel_box), Vec2::new(60.0, 20.0));
+    // Too big to fit either side: clamped to the box.
+    let wide = Vec2::new(150.0, 20.0);
+    assert_eq!(place(Vec2::new(100.0, 50.0), wide, pixel_box), Vec2::new(0.0, 20.0));
+  }
+}
@@ -0,0 +1,43 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! `tracing` instrumentation behind the `tracing` feature.
+//!
+//! [`trace_span!`] enters a debug-level span for the rest of the
+//! enclosing scope and [`trace_event!`] emits a debug event, both with
+//! `name = value` fields.  Without the feature they expand to nothing,
+//! so the hot paths (autoscaling, the matplot++ pipeline, GPU upload and
+//! capture read-back) pay nothing for being instrumented.
+
+/// Stand-in for an entered span without the `tracing` feature.
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct NoSpan;
+
+/// Enters a span named `$name` with fields until the returned guard is
+/// dropped: `let _span = trace_span!("prepare", instances = n);`.
+macro_rules! trace_span {
+  ($name:literal $(, $field:ident = $value:expr)* $(,)?) => {{
+    #[cfg(feature = "tracing")]
+    let span = tracing::debug_span!($name $(, $field = $value)*).entered();
+    #[cfg(not(feature = "tracing"))]
+    let span = {
+      $(let _ = &$value;)*
+      $crate::trace::NoSpan
+    };
+    span
+  }};
+}
+
+/// Emits a debug event with fields in the current span.
+macro_rules! trace_event {
+  ($($field:ident = $value:expr),+ $(,)?) => {{
+    #[cfg(feature = "tracing")]
+    tracing::debug!($($field = $value),+);
+    #[cfg(not(feature = "tracing"))]
+    {
+      $(let _ = &$value;)+
+    }
+  }};
+}
+
+pub(crate) use {trace_event, trace_span};
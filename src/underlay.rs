@@ -0,0 +1,202 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Raster images beneath the data.
+//!
+//! An [`ImageUnderlay`] places an RGBA image (a map tile, microscope
+//! image, floor plan, ...) over a rectangle in data coordinates and
+//! draws it beneath the figure's series, so scatter and line overlays
+//! stay aligned with it through pan and zoom.  The image is uploaded
+//! to a texture once and drawn as a single quad over the part inside
+//! the axis limits.  Recordings have no textures and hold that part as
+//! blocks of image pixels about one screen pixel wide instead.  Add
+//! one with
+//! [`PlotBackend::add_image_underlay`](crate::plotting::PlotBackend::add_image_underlay).
+
+use crate::artist::{Artist, DataRect, DrawContext};
+use crate::error::{Error, Result};
+use glam::Vec4;
+use image::RgbaImage;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+
+/// An image anchored to a data rectangle, drawn under the data.
+pub struct ImageUnderlay {
+  image: Arc<RgbaImage>,
+  extent: DataRect,
+  /// Opacity multiplied into every pixel.
+  pub alpha: f32,
+  /// Whether the extent widens the axis limits.
+  pub autoscale: bool,
+}
+
+impl ImageUnderlay {
+  /// Image from tightly packed RGBA8 rows, top row first, stretched
+  /// over `extent`: the top row at `y_max`, the left column at `x_min`.
+  ///
+  /// Fails with [`Error::FrameSize`] if `data` is not
+  /// `width * height * 4` bytes long.
+  pub fn from_rgba8(width: u32, height: u32, data: &[u8], extent: DataRect) -> Result<Self> {
+    if data.len() as u64 != width as u64 * height as u64 * 4 {
+      return Err(Error::FrameSize(data.len()));
+    }
+    let image = RgbaImage::from_raw(width, height, data.to_vec()).ok_or(Error::FrameSize(data.len()))?;
+    Ok(Self::from_image(image, extent))
+  }
+
+  /// Image from a decoded [`image::RgbaImage`].
+  pub fn from_image(image: RgbaImage, extent: DataRect) -> Self {
+    Self {
+      image: Arc::new(image),
+      extent,
+      alpha: 1.0,
+      autoscale: true,
+    }
+  }
+
+  /// Loads a PNG, JPEG, ... file.
+  pub fn open<P: AsRef<Path>>(path: P, extent: DataRect) -> image::ImageResult<Self> {
+    Ok(Self::from_image(image::open(path)?.to_rgba8(), extent))
+  }
+
+  /// Sets the opacity.
+  pub fn alpha(mut self, alpha: f32) -> Self {
+    self.alpha = alpha;
+    self
+  }
+
+  /// Sets whether the extent widens the axis limits.
+  pub fn autoscale(mut self, autoscale: bool) -> Self {
+    self.autoscale = autoscale;
+    self
+  }
+
+  /// Data rectangle the image covers.
+  pub fn data_extent(&self) -> DataRect {
+    self.extent
+  }
+
+  fn pixel(&self, col: usize, row: usize) -> Vec4 {
+    let [r, g, b, a] = self.image.get_pixel(col as u32, row as u32).0;
+    Vec4::new(r as f32, g as f32, b as f32, a as f32 * self.alpha) / 255.0
+  }
+
+  /// The visible part `[x0, x1] x [y0, y1]` as blocks of image pixels
+  /// about one screen pixel wide, for recordings.
+  fn draw_blocks(&self, ctx: &mut DrawContext<'_>, (vx0, vx1): (f64, f64), (vy0, vy1): (f64, f64)) {
+    let e = self.extent;
+    let (width, height) = (self.image.width() as usize, self.image.height() as usize);
+    let cols = visible_cells(e.x_min, e.x_max, width, vx0, vx1);
+    // Rows count down from the top edge.
+    let rows = visible_cells(e.y_max, e.y_min, height, vy0, vy1);
+    let span = ctx.to_pixels(vx1, vy0) - ctx.to_pixels(vx0, vy1);
+    let step_x = block_size(cols.len(), span.x.abs());
+    let step_y = block_size(rows.len(), span.y.abs());
+    let col_x = |c: usize| (e.x_min + (e.x_max - e.x_min) * c as f64 / width as f64).clamp(vx0, vx1);
+    let row_y = |r: usize| (e.y_max - (e.y_max - e.y_min) * r as f64 / height as f64).clamp(vy0, vy1);
+    for r in rows.clone().step_by(step_y) {
+      let r_end = (r + step_y).min(rows.end);
+      let (y0, y1) = (row_y(r), row_y(r_end));
+      for c in cols.clone().step_by(step_x) {
+        let c_end = (c + step_x).min(cols.end);
+        // Nearest sample at the block center.
+        let color = self.pixel((c + c_end - 1) / 2, (r + r_end - 1) / 2);
+        if color.w <= 0.0 {
+          continue;
+        }
+        let (x0, x1) = (col_x(c), col_x(c_end));
+        let corners = [(x0, y0), (x1, y0), (x1, y1), (x0, y1)].map(|(x, y)| ctx.to_pixels(x, y));
+        ctx.quad_pixels(corners, color);
+      }
+    }
+  }
+}
+
+/// Cells of `n` spread evenly over `[start, end]` that overlap
+/// `[lo, hi]`.  Cells are numbered from `start`.
+pub fn visible_cells(start: f64, end: f64, n: usize, lo: f64, hi: f64) -> Range<usize> {
+  let span = end - start;
+  if n == 0 || span == 0.0 || !span.is_finite() {
+    return 0..0;
+  }
+  let a = ((lo - start) / span * n as f64).floor();
+  let b = ((hi - start) / span * n as f64).ceil();
+  let (a, b) = (a.min(b), a.max(b));
+  let clamp = |v: f64| v.clamp(0.0, n as f64) as usize;
+  clamp(a)..clamp(b)
+}
+
+/// Cells merged into one block so `cells` image pixels spread over
+/// `pixels` screen pixels give blocks about a screen pixel wide.
+pub fn block_size(cells: usize, pixels: f32) -> usize {
+  if pixels < 1.0 {
+    return cells.max(1);
+  }
+  ((cells as f32 / pixels).floor() as usize).max(1)
+}
+
+impl Artist for ImageUnderlay {
+  fn draw(&self, ctx: &mut DrawContext<'_>) {
+    let l = ctx.limits();
+    let e = self.extent;
+    let (vx0, vx1) = (l.x_min.min(l.x_max).max(e.x_min), l.x_max.max(l.x_min).min(e.x_max));
+    let (vy0, vy1) = (l.y_min.min(l.y_max).max(e.y_min), l.y_max.max(l.y_min).min(e.y_max));
+    if vx0 >= vx1 || vy0 >= vy1 || self.image.width() == 0 || self.image.height() == 0 {
+      return;
+    }
+    let corners = [(vx0, vy1), (vx1, vy1), (vx1, vy0), (vx0, vy0)].map(|(x, y)| ctx.to_pixels(x, y));
+    let u = |x: f64| ((x - e.x_min) / (e.x_max - e.x_min)) as f32;
+    let v = |y: f64| ((e.y_max - y) / (e.y_max - e.y_min)) as f32;
+    ctx.image_pixels(&self.image, corners, [u(vx0), v(vy1), u(vx1), v(vy0)], self.alpha);
+    if ctx.recording() {
+      self.draw_blocks(ctx, (vx0, vx1), (vy0, vy1));
+    }
+  }
+
+  fn extent(&self) -> Option<DataRect> {
+    self.autoscale.then_some(self.extent)
+  }
+
+  fn underlay(&self) -> bool {
+    true
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn visible_cells_cover_the_overlap() {
+    assert_eq!(visible_cells(0.0, 10.0, 10, 2.5, 4.0), 2..4);
+    assert_eq!(visible_cells(0.0, 10.0, 10, -5.0, 50.0), 0..10);
+    assert_eq!(visible_cells(0.0, 10.0, 10, 20.0, 30.0), 10..10);
+    // Counting down from the top edge.
+    assert_eq!(visible_cells(10.0, 0.0, 10, 0.0, 3.0), 7..10);
+    assert_eq!(visible_cells(0.0, 0.0, 10, 0.0, 1.0), 0..0);
+  }
+
+  #[test]
+  fn blocks_shrink_to_screen_pixels() {
+    assert_eq!(block_size(1000, 100.0), 10);
+    assert_eq!(block_size(50, 400.0), 1);
+    assert_eq!(block_size(50, 0.0), 50);
+  }
+
+  #[test]
+  fn pixels_and_extent() {
+    let data = [255, 0, 0, 255, 0, 0, 255, 128];
+    let img = ImageUnderlay::from_rgba8(2, 1, &data, DataRect::new((0.0, 2.0), (0.0, 1.0))).unwrap().alpha(0.5);
+    assert_eq!(img.pixel(0, 0), Vec4::new(1.0, 0.0, 0.0, 0.5));
+    assert!(img.underlay());
+    assert_eq!(img.extent(), Some(img.data_extent()));
+    assert!(img.autoscale(false).extent().is_none());
+  }
+
+  #[test]
+  fn mismatched_data_is_an_error() {
+    let extent = DataRect::new((0.0, 1.0), (0.0, 1.0));
+    assert!(matches!(ImageUnderlay::from_rgba8(2, 2, &[0; 12], extent), Err(Error::FrameSize(12))));
+  }
+}
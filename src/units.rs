@@ -0,0 +1,75 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Physical size units for lines, markers, and strokes.
+//!
+//! Widths and marker sizes coming from matplot++ are in typographic
+//! points (1/72 inch).  [`SizeScale`] is the single place where points
+//! become device pixels, combining the figure DPI with the display
+//! scale factor, so a figure keeps its proportions across export sizes
+//! and HiDPI screens.
+
+/// Typographic points per inch.
+pub const POINTS_PER_INCH: f32 = 72.0;
+
+/// Converts point sizes into device pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SizeScale {
+  /// Figure resolution in dots per inch.  The default of 72 maps one
+  /// point to one logical pixel.
+  pub dpi: f32,
+  /// Display scale factor (e.g. 2.0 on HiDPI screens).
+  pub scale_factor: f32,
+}
+
+impl Default for SizeScale {
+  fn default() -> Self {
+    Self {
+      dpi: POINTS_PER_INCH,
+      scale_factor: 1.0,
+    }
+  }
+}
+
+impl SizeScale {
+  /// Device pixels per point.
+  pub fn pixels_per_point(&self) -> f32 {
+    self.dpi / POINTS_PER_INCH * self.scale_factor
+  }
+
+  /// Converts a size in points to device pixels.
+  pub fn points_to_pixels(&self, points: f32) -> f32 {
+    points * self.pixels_per_point()
+  }
+
+  /// Converts a size in device pixels to points.
+  pub fn pixels_to_points(&self, pixels: f32) -> f32 {
+    let ppp = self.pixels_per_point();
+    if ppp > 0.0 {
+      pixels / ppp
+    } else {
+      0.0
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn default_is_one_pixel_per_point() {
+    let s = SizeScale::default();
+    assert_eq!(s.points_to_pixels(6.0), 6.0);
+  }
+
+  #[test]
+  fn dpi_and_scale_factor_compose() {
+    let s = SizeScale {
+      dpi: 144.0,
+      scale_factor: 1.5,
+    };
+    assert_eq!(s.pixels_per_point(), 3.0);
+    assert_eq!(s.pixels_to_points(s.points_to_pixels(2.5)), 2.5);
+  }
+}
@@ -0,0 +1,106 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Shadows of 3D series on the walls of the axes box.
+//!
+//! A 3D trajectory is hard to place in depth from a single view.
+//! Projecting it onto the floor and walls of the box, as matplotlib's
+//! `zdir` plots do, shows where it runs along each pair of axes.  From
+//! the default view (azimuth -37.5°, elevation 30°) the walls behind
+//! the data are the floor at the lowest Z, the back wall at the highest
+//! Y and the side wall at the lowest X.  Draw shadows with
+//! [`Axes::project_onto_walls`](crate::plotting::Axes::project_onto_walls).
+
+/// Wall of the 3D axes box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wall {
+  /// The XY plane at the lowest Z.
+  Floor,
+  /// The XZ plane at the highest Y.
+  Back,
+  /// The YZ plane at the lowest X.
+  Side,
+}
+
+impl Wall {
+  /// Every wall, floor first.
+  pub const ALL: [Wall; 3] = [Wall::Floor, Wall::Back, Wall::Side];
+}
+
+/// Which walls a series is projected onto, and how its shadows look.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WallProjection {
+  /// Walls receiving a shadow.
+  pub walls: Vec<Wall>,
+  /// Shadow color.
+  pub color: [f32; 3],
+  /// Shadow line width.
+  pub width: f32,
+}
+
+impl Default for WallProjection {
+  /// Light gray shadows on every wall.
+  fn default() -> Self {
+    Self::new(&Wall::ALL)
+  }
+}
+
+impl WallProjection {
+  /// Light gray shadows on `walls`.
+  pub fn new(walls: &[Wall]) -> Self {
+    Self {
+      walls: walls.to_vec(),
+      color: [0.7, 0.7, 0.7],
+      width: 1.0,
+    }
+  }
+
+  /// Sets the shadow color.
+  pub fn color(mut self, r: f32, g: f32, b: f32) -> Self {
+    self.color = [r, g, b];
+    self
+  }
+
+  /// Sets the shadow line width.
+  pub fn width(mut self, width: f32) -> Self {
+    self.width = width;
+    self
+  }
+}
+
+/// The points `(x, y, z)` flattened onto `wall` of a box spanning
+/// `limits` (X, Y and Z, in either order), truncated to the shortest
+/// slice.
+pub fn project(
+  wall: Wall,
+  x: &[f64],
+  y: &[f64],
+  z: &[f64],
+  limits: [(f64, f64); 3],
+) -> [Vec<f64>; 3] {
+  let n = x.len().min(y.len()).min(z.len());
+  let [x_lim, y_lim, z_lim] = limits;
+  let flat = |v: f64| vec![v; n];
+  match wall {
+    Wall::Floor => [x[..n].to_vec(), y[..n].to_vec(), flat(z_lim.0.min(z_lim.1))],
+    Wall::Back => [x[..n].to_vec(), flat(y_lim.0.max(y_lim.1)), z[..n].to_vec()],
+    Wall::Side => [flat(x_lim.0.min(x_lim.1)), y[..n].to_vec(), z[..n].to_vec()],
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn points_flatten_onto_the_far_walls() {
+    let (x, y, z) = ([1.0, 2.0], [3.0, 4.0, 9.0], [5.0, 6.0]);
+    let limits = [(0.0, 10.0), (10.0, -10.0), (-1.0, 1.0)];
+    let [fx, fy, fz] = project(Wall::Floor, &x, &y, &z, limits);
+    assert_eq!((fx, fy, fz), (vec![1.0, 2.0], vec![3.0, 4.0], vec![-1.0, -1.0]));
+    let [_, by, bz] = project(Wall::Back, &x, &y, &z, limits);
+    assert_eq!((by, bz), (vec![10.0, 10.0], vec![5.0, 6.0]));
+    let [sx, ..] = project(Wall::Side, &x, &y, &z, limits);
+    assert_eq!(sx, vec![0.0, 0.0]);
+  }
+}
@@ -0,0 +1,113 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Observing the view of an axes.
+//!
+//! Applications persisting the view, driving a minimap or recording
+//! navigation for replay need to know when the limits or the 3D camera
+//! change, whatever changed them: a box zoom, a follow-mode stream or
+//! a call to [`Axes::set_xlim`](crate::plotting::Axes::set_xlim).
+//! [`PlotBackend::watch_limits`](crate::plotting::PlotBackend::watch_limits)
+//! and [`watch_camera`](crate::plotting::PlotBackend::watch_camera)
+//! hand out channel receivers.  Each receives the current state at
+//! once, then every change seen by a render, after autoscaling has
+//! settled the limits.  Receivers can move to other threads, and
+//! dropping one unsubscribes it.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+
+/// Limits of the current axes at one render.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisLimits {
+  /// X limits.
+  pub x: (f64, f64),
+  /// Y limits.
+  pub y: (f64, f64),
+  /// Z limits of 3D axes.
+  pub z: (f64, f64),
+}
+
+/// Camera of 3D axes, in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraView {
+  /// Rotation about the Z axis.
+  pub azimuth: f32,
+  /// Angle above the XY plane.
+  pub elevation: f32,
+}
+
+/// Subscribers to a value, sent each change.
+pub(crate) struct Watch<T> {
+  /// Subscribers with the last value each was sent.
+  senders: Vec<(Sender<T>, T)>,
+}
+
+impl<T> Default for Watch<T> {
+  fn default() -> Self {
+    Self { senders: Vec::new() }
+  }
+}
+
+impl<T: Clone + PartialEq> Watch<T> {
+  /// New subscriber, sent `current` straight away.  Earlier
+  /// subscribers still get `current` at the next publish if they have
+  /// not seen it.
+  pub(crate) fn subscribe(&mut self, current: T) -> Receiver<T> {
+    let (tx, rx) = mpsc::channel();
+    let _ = tx.send(current.clone());
+    self.senders.push((tx, current));
+    rx
+  }
+
+  /// Whether anyone is subscribed, to skip reading the value otherwise.
+  pub(crate) fn is_watched(&self) -> bool {
+    !self.senders.is_empty()
+  }
+
+  /// Sends `value` to every subscriber it is new to, dropping the
+  /// subscribers whose receiver is gone.
+  pub(crate) fn publish(&mut self, value: T) {
+    self.senders.retain_mut(|(tx, last)| {
+      if *last == value {
+        return true;
+      }
+      *last = value.clone();
+      tx.send(value.clone()).is_ok()
+    });
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn subscribers_see_the_current_value_then_changes() {
+    let mut watch = Watch::default();
+    assert!(!watch.is_watched());
+    let a = watch.subscribe(1);
+    watch.publish(1);
+    watch.publish(2);
+    let b = watch.subscribe(2);
+    watch.publish(3);
+    assert_eq!(a.try_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(b.try_iter().collect::<Vec<_>>(), vec![2, 3]);
+
+    drop(a);
+    watch.publish(4);
+    assert_eq!(watch.senders.len(), 1);
+    assert_eq!(b.try_recv(), Ok(4));
+  }
+
+  #[test]
+  fn a_late_subscriber_does_not_hide_a_change_from_earlier_ones() {
+    // A subscribes, the limits change without a render, B subscribes
+    // with the new limits, then a render publishes them.
+    let mut watch = Watch::default();
+    let a = watch.subscribe(1);
+    let b = watch.subscribe(2);
+    watch.publish(2);
+    assert_eq!(a.try_iter().collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(b.try_iter().collect::<Vec<_>>(), vec![2]);
+  }
+}
@@ -0,0 +1,348 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Showing a figure in a window.
+//!
+//! [`show`] opens a winit window and renders a [`FigureHandle`] into it
+//! until the window is closed.  [`WindowOptions`] control the frame
+//! pacing: the swap chain present mode (vsync with
+//! [`Fifo`](wgpu::PresentMode::Fifo), low latency with
+//! [`Mailbox`](wgpu::PresentMode::Mailbox), uncapped with
+//! [`Immediate`](wgpu::PresentMode::Immediate)), an optional frame rate
+//! cap, and whether to redraw continuously or only when the figure
+//! changed.  With [`Redraw::OnChange`] an idle dashboard presents no
+//! frames at all; producer threads edit the figure through
+//! [`FigureHandle::with`] and the window picks the change up on its next
+//! check.
+//!
+//! ```rust,no_run
+//! # fn demo(backend: mpl_wgpu::plotting::PlotBackend) -> mpl_wgpu::Result<()> {
+//! use mpl_wgpu::handle::FigureHandle;
+//! use mpl_wgpu::window::{show, Redraw, WindowOptions};
+//!
+//! let options = WindowOptions::new().max_fps(30.0).redraw(Redraw::OnChange);
+//! show(FigureHandle::new(backend), options)
+//! # }
+//! ```
+
+use crate::capture::load_default_font;
+use crate::context::RenderContext;
+use crate::error::{Error, Result};
+use crate::handle::FigureHandle;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use winit::dpi::PhysicalSize;
+use winit::event::{Event, WindowEvent};
+use winit::event_loop::{ControlFlow, EventLoop};
+use winit::window::WindowBuilder;
+
+/// How often an uncapped [`Redraw::OnChange`] window checks the figure
+/// for changes.
+const CHANGE_POLL: Duration = Duration::from_millis(16);
+
+/// When a window draws a new frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Redraw {
+  /// Every frame, as fast as the present mode and frame cap allow.
+  Continuous,
+  /// Only after the figure changed, the window was resized or exposed.
+  OnChange,
+}
+
+/// Window size, title and frame pacing for [`show`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowOptions {
+  /// Window title.
+  pub title: String,
+  /// Initial inner width, in physical pixels.
+  pub width: u32,
+  /// Initial inner height, in physical pixels.
+  pub height: u32,
+  /// Requested present mode; [`Fifo`](wgpu::PresentMode::Fifo) is used
+  /// when the surface does not support it.
+  pub present_mode: wgpu::PresentMode,
+  /// Frame rate cap, or `None` to leave pacing to the present mode.
+  pub max_fps: Option<f32>,
+  /// When frames are drawn.
+  pub redraw: Redraw,
+}
+
+impl Default for WindowOptions {
+  fn default() -> Self {
+    Self {
+      title: "mpl-wgpu".into(),
+      width: 800,
+      height: 600,
+      present_mode: wgpu::PresentMode::Fifo,
+      max_fps: None,
+      redraw: Redraw::OnChange,
+    }
+  }
+}
+
+impl WindowOptions {
+  /// 800 x 600 window with vsync, redrawing on change.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Sets the window title.
+  pub fn title(mut self, title: &str) -> Self {
+    self.title = title.into();
+    self
+  }
+
+  /// Sets the initial size, in physical pixels.
+  pub fn size(mut self, width: u32, height: u32) -> Self {
+    self.width = width;
+    self.height = height;
+    self
+  }
+
+  /// Sets the requested present mode.
+  pub fn present_mode(mut self, mode: wgpu::PresentMode) -> Self {
+    self.present_mode = mode;
+    self
+  }
+
+  /// Caps the frame rate.
+  pub fn max_fps(mut self, fps: f32) -> Self {
+    self.max_fps = Some(fps);
+    self
+  }
+
+  /// Sets when frames are drawn.
+  pub fn redraw(mut self, redraw: Redraw) -> Self {
+    self.redraw = redraw;
+    self
+  }
+}
+
+/// `requested` if the surface supports it, else
+/// [`Fifo`](wgpu::PresentMode::Fifo), which every surface supports.
+pub fn pick_present_mode(requested: wgpu::PresentMode, supported: &[wgpu::PresentMode]) -> wgpu::PresentMode {
+  if supported.contains(&requested) {
+    requested
+  } else {
+    wgpu::PresentMode::Fifo
+  }
+}
+
+/// Spaces frames at least `1 / max_fps` apart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FramePacer {
+  interval: Option<Duration>,
+  last: Option<Instant>,
+}
+
+impl FramePacer {
+  /// Pacer for at most `max_fps` frames per second; `None`, zero or a
+  /// non-finite rate means no cap.
+  pub fn new(max_fps: Option<f32>) -> Self {
+    let interval = max_fps
+      .filter(|fps| fps.is_finite() && *fps > 0.0)
+      .map(|fps| Duration::from_secs_f64(1.0 / fps as f64));
+    Self { interval, last: None }
+  }
+
+  /// Minimum time between frames.
+  pub fn interval(&self) -> Option<Duration> {
+    self.interval
+  }
+
+  /// Earliest start of the next frame.
+  pub fn next_frame(&self) -> Option<Instant> {
+    Some(self.last? + self.interval?)
+  }
+
+  /// Whether a frame may start at `now`.
+  pub fn ready(&self, now: Instant) -> bool {
+    self.next_frame().is_none_or(|next| now >= next)
+  }
+
+  /// Records a frame started at `now`.
+  pub fn frame(&mut self, now: Instant) {
+    self.last = Some(now);
+  }
+}
+
+/// Opens a window showing `figure` and blocks until it is closed.
+///
+/// Must be called on the main thread on platforms that require it.
+/// The figure is resized with the window; other threads can keep
+/// editing it through clones of the handle.
+pub fn show(figure: FigureHandle, options: WindowOptions) -> Result<()> {
+  let event_loop = EventLoop::new()?;
+  let window = Arc::new(
+    WindowBuilder::new()
+      .with_title(&options.title)
+      .with_inner_size(PhysicalSize::new(options.width, options.height))
+      .build(&event_loop)?,
+  );
+
+  let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+    backends: wgpu::Backends::all(),
+    ..Default::default()
+  });
+  let surface = instance.create_surface(window.clone())?;
+  let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+    power_preference: wgpu::PowerPreference::default(),
+    compatible_surface: Some(&surface),
+    force_fallback_adapter: false,
+  }))
+  .ok_or(Error::AdapterNotFound)?;
+  let (device, queue) = pollster::block_on(adapter.request_device(
+    &wgpu::DeviceDescriptor {
+      label: Some("WindowDevice"),
+      required_features: wgpu::Features::empty(),
+      required_limits: wgpu::Limits::default(),
+    },
+    None,
+  ))?;
+
+  let caps = surface.get_capabilities(&adapter);
+  let format = caps
+    .formats
+    .iter()
+    .copied()
+    .find(|f| f.is_srgb())
+    .or(caps.formats.first().copied())
+    .ok_or(Error::AdapterNotFound)?;
+  let size = window.inner_size();
+  let mut config = wgpu::SurfaceConfiguration {
+    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+    format,
+    width: size.width.max(1),
+    height: size.height.max(1),
+    present_mode: pick_present_mode(options.present_mode, &caps.present_modes),
+    desired_maximum_frame_latency: 2,
+    alpha_mode: caps.alpha_modes[0],
+    view_formats: vec![],
+  };
+  surface.configure(&device, &config);
+
+  let ctx = RenderContext::new(Arc::new(device), Arc::new(queue), format, &load_default_font()?)?;
+  let (mut prim, mut text) = ctx.renderers(config.width, config.height);
+  figure.with(|b| {
+    b.resize(config.width, config.height);
+    b.set_scale_factor(window.scale_factor() as f32);
+  });
+
+  let mut pacer = FramePacer::new(options.max_fps);
+  // Generation drawn last, `None` until the first frame.
+  let mut drawn: Option<u64> = None;
+  let mut exposed = true;
+
+  event_loop.run(move |event, elwt| match event {
+    Event::WindowEvent { event, .. } => match event {
+      WindowEvent::CloseRequested => elwt.exit(),
+      WindowEvent::Resized(size) => {
+        config.width = size.width.max(1);
+        config.height = size.height.max(1);
+        surface.configure(ctx.device(), &config);
+        prim.resize(ctx.queue(), config.width, config.height);
+        text.resize(config.width, config.height);
+        figure.with(|b| b.resize(config.width, config.height));
+        exposed = true;
+      }
+      WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+        figure.with(|b| b.set_scale_factor(scale_factor as f32));
+      }
+      WindowEvent::RedrawRequested => {
+        let frame = match surface.get_current_texture() {
+          Ok(frame) => frame,
+          Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+            surface.configure(ctx.device(), &config);
+            exposed = true;
+            return;
+          }
+          Err(wgpu::SurfaceError::Timeout) => return,
+          Err(wgpu::SurfaceError::OutOfMemory) => return elwt.exit(),
+        };
+        pacer.frame(Instant::now());
+        drawn = Some(figure.generation());
+        exposed = false;
+
+        prim.clear();
+        text.clear();
+        figure.render(&mut prim, &mut text, None);
+        prim.prepare(ctx.device(), ctx.queue());
+        text.prepare(ctx.device(), ctx.queue());
+
+        let view = frame.texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let mut encoder = ctx
+          .device()
+          .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("WindowEncoder") });
+        {
+          let mut rp = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("WindowPass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+              view: &view,
+              resolve_target: None,
+              ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::WHITE),
+                store: wgpu::StoreOp::Store,
+              },
+            })],
+            depth_stencil_attachment: None,
+            ..Default::default()
+          });
+          prim.render(&mut rp);
+          text.render(&mut rp);
+        }
+        ctx.queue().submit(std::iter::once(encoder.finish()));
+        window.pre_present_notify();
+        frame.present();
+      }
+      _ => {}
+    },
+    Event::AboutToWait => {
+      let now = Instant::now();
+      let wanted = match options.redraw {
+        Redraw::Continuous => true,
+        Redraw::OnChange => exposed || drawn != Some(figure.generation()),
+      };
+      if wanted && pacer.ready(now) {
+        window.request_redraw();
+      }
+      let wake = match (wanted, options.redraw) {
+        (true, _) => pacer.next_frame().filter(|t| *t > now),
+        // Changes come from other threads without waking the loop.
+        (false, Redraw::OnChange) => Some(now + pacer.interval().unwrap_or(CHANGE_POLL)),
+        (false, Redraw::Continuous) => None,
+      };
+      elwt.set_control_flow(wake.map_or(ControlFlow::Wait, ControlFlow::WaitUntil));
+    }
+    _ => {}
+  })?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn present_mode_falls_back_to_fifo() {
+    use wgpu::PresentMode::*;
+    assert_eq!(pick_present_mode(Mailbox, &[Fifo, Mailbox]), Mailbox);
+    assert_eq!(pick_present_mode(Immediate, &[Fifo]), Fifo);
+  }
+
+  #[test]
+  fn pacer_spaces_frames() {
+    let t0 = Instant::now();
+    let mut pacer = FramePacer::new(Some(20.0));
+    assert_eq!(pacer.interval(), Some(Duration::from_millis(50)));
+    assert!(pacer.ready(t0));
+    pacer.frame(t0);
+    assert!(!pacer.ready(t0 + Duration::from_millis(10)));
+    assert!(pacer.ready(t0 + Duration::from_millis(50)));
+    assert_eq!(pacer.next_frame(), Some(t0 + Duration::from_millis(50)));
+
+    let mut uncapped = FramePacer::new(Some(0.0));
+    uncapped.frame(t0);
+    assert!(uncapped.ready(t0));
+    assert_eq!(uncapped.next_frame(), None);
+  }
+}
@@ -30,16 +30,17 @@ const DEFAULT_MAX_DIFF_PCT: f64 = 2.0;
 
 /// Orchestrates a visual regression test.
 ///
-/// Creates a [`PlotCapture`] at 800x600, calls `setup_fn` to
+/// Creates a [`PlotCapture`] of `size`, calls `setup_fn` to
 /// configure the plot via a [`plotting::Figure`], captures the
 /// result, and either blesses (when `BLESS=1`) or compares
 /// against the golden reference.  On failure, saves actual + diff
 /// images to `tests/output/`.
-pub fn run_visual_test<F>(name: &str, setup_fn: F)
+pub fn run_visual_test<F>(name: &str, size: (u32, u32), setup_fn: F)
 where
-  F: FnOnce(&plotting::Figure),
+  F: FnOnce(&plotting::Figure<'_>),
 {
-  let cap = PlotCapture::new(800, 600);
+  let cap = PlotCapture::new(size.0, size.1)
+    .expect("headless GPU context");
   let fig = cap.figure();
   setup_fn(&fig);
   run_visual_test_with_capture(name, cap);
@@ -51,7 +52,7 @@ pub fn run_visual_test_with_capture(
   name: &str,
   mut cap: PlotCapture,
 ) {
-  let actual = cap.render_and_capture();
+  let actual = cap.render_and_capture().expect("read back frame");
   let w = cap.width();
   let h = cap.height();
 
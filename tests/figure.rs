@@ -0,0 +1,488 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Figure-building tests that inspect the recorded draw calls of the
+//! matplot++ pipeline instead of comparing pixels.
+
+use mpl_wgpu::artist::{Artist, DataRect, DrawContext};
+use mpl_wgpu::colormaps::{Norm, GREYS};
+use mpl_wgpu::events::PlotEvent;
+use mpl_wgpu::glam::{Vec2, Vec4};
+use mpl_wgpu::layout::LabelStrategy;
+use mpl_wgpu::locale::Locale;
+use mpl_wgpu::plotting::{Axes, FontStyle, PlotBackend};
+use mpl_wgpu::probe::DataProbe;
+use mpl_wgpu::quickplot::Table;
+use mpl_wgpu::recording::{RecordedPrimitive, Recording};
+use mpl_wgpu::spec::{self, PlotSpec};
+use mpl_wgpu::walls::{Wall, WallProjection};
+use serial_test::serial;
+
+/// Colors of the recorded triangles.
+fn triangle_colors(recording: &Recording) -> Vec<[f32; 3]> {
+  recording
+    .primitives
+    .iter()
+    .filter_map(|p| match p {
+      RecordedPrimitive::Triangle { color, .. } => {
+        Some([color.x, color.y, color.z])
+      }
+      _ => None,
+    })
+    .collect()
+}
+
+/// Flat `rows` x `cols` grid at height `z`.
+fn flat_grid(rows: usize, cols: usize, z: f64) -> [Vec<f64>; 3] {
+  let mut grid = [Vec::new(), Vec::new(), Vec::new()];
+  for i in 0..rows {
+    for j in 0..cols {
+      grid[0].push(j as f64);
+      grid[1].push(i as f64);
+      grid[2].push(z);
+    }
+  }
+  grid
+}
+
+#[test]
+#[serial]
+fn held_surfaces_share_color_limits() {
+  let mut backend = PlotBackend::new(400, 300);
+  {
+    let ax = backend.figure().current_axes();
+    ax.hold(true);
+    let [x, y, low] = flat_grid(4, 4, 0.0);
+    ax.surf(&x, &y, &low, 4, 4, false);
+    let [x, y, high] = flat_grid(4, 4, 2.0);
+    ax.surf(&x, &y, &high, 4, 4, false);
+    ax.set_colormap(&GREYS);
+    ax.set_clim(0.0, 2.0);
+  }
+  let colors = triangle_colors(&backend.record(None));
+  let near = |target: f32| {
+    colors
+      .iter()
+      .any(|c| c.iter().all(|v| (v - target).abs() < 0.1))
+  };
+  // The bottom surface sits at the white end of the shared range and
+  // the top one at the black end.
+  assert!(near(1.0), "no surface at vmin: {colors:?}");
+  assert!(near(0.0), "no surface at vmax: {colors:?}");
+}
+
+#[test]
+#[serial]
+fn wall_projections_keep_the_data_series() {
+  let backend = PlotBackend::new(400, 300);
+  let ax = backend.figure().current_axes();
+  let t: Vec<f64> = (0..50).map(|i| i as f64 * 0.2).collect();
+  let (x, y): (Vec<f64>, Vec<f64>) = t.iter().map(|t| (t.cos(), t.sin())).unzip();
+  ax.plot3(&x, &y, &t, "-");
+  let shadows = ax.project_onto_walls(&x, &y, &t, "-", &WallProjection::default());
+  assert_eq!(shadows.len(), Wall::ALL.len());
+  assert_eq!(ax.series_ids().len(), 1 + Wall::ALL.len());
+}
+
+/// Passes values through unchanged, out-of-range ones included.
+struct Identity;
+
+impl Norm for Identity {
+  fn apply(&self, value: f64) -> f64 {
+    value
+  }
+
+  fn range(&self) -> (f64, f64) {
+    (0.0, 1.0)
+  }
+}
+
+#[test]
+#[serial]
+fn mapped_scatter_keeps_every_color_bucket() {
+  let mut backend = PlotBackend::new(400, 300);
+  {
+    let ax = backend.figure().current_axes();
+    let (x, y) = ([0.0, 1.0, 2.0], [0.0, 1.0, 2.0]);
+    let ids = ax.scatter_mapped(&x, &y, &[-1.0, 0.5, 3.0], "o", &GREYS, Some(&Identity));
+    assert_eq!(ids, ax.series_ids());
+  }
+  let markers = backend
+    .record(None)
+    .primitives
+    .iter()
+    .filter(|p| matches!(p, RecordedPrimitive::Marker { .. }))
+    .count();
+  assert_eq!(markers, 3);
+}
+
+#[test]
+#[serial]
+fn series_ids_only_reach_their_own_axes() {
+  let backend = PlotBackend::new(400, 300);
+  let ax = backend.figure().current_axes();
+  let id = ax.plot(&[0.0, 1.0], &[0.0, 1.0], "-").id();
+  let extra = ax.add_y_axis(40.0, [1.0, 0.0, 0.0]).unwrap();
+  assert!(!extra.update_series(id, &[0.0], &[5.0]));
+  assert!(!extra.append_series(id, &[2.0], &[5.0], None));
+  assert!(!extra.set_visible(id, false));
+  assert_eq!(ax.is_visible(id), Some(true));
+  assert_eq!(ax.series_points(id).0, [0.0, 1.0]);
+}
+
+#[test]
+#[serial]
+fn spec_draws_every_series() {
+  let mut spec = PlotSpec::new();
+  spec.data.insert("t".into(), vec![0.0, 1.0, 2.0]);
+  spec.series.push(spec::Series::line("t", vec![1.0, 4.0, 9.0]));
+  spec.series.push(spec::Series::line("t", vec![9.0, 4.0, 1.0]));
+  let backend = PlotBackend::from_spec(&spec).unwrap();
+  let ax = backend.figure().current_axes();
+  assert_eq!(ax.series_ids().len(), 2);
+}
+
+#[test]
+#[serial]
+fn band_is_drawn_behind_its_line() {
+  let mut backend = PlotBackend::new(400, 300);
+  {
+    let ax = backend.figure().current_axes();
+    let x = [0.0, 1.0, 2.0, 3.0];
+    let y = [1.0, 2.0, 1.5, 2.5];
+    let lo: Vec<f64> = y.iter().map(|v| v - 0.5).collect();
+    let hi: Vec<f64> = y.iter().map(|v| v + 0.5).collect();
+    let id = ax.plot_with_band(&x, &y, &lo, &hi, "r-").id();
+    assert!(ax.series_ids().contains(&id));
+  }
+  let recording = backend.record(None);
+  let red = |c: &Vec4| c.x > 0.9 && c.y < 0.1 && c.z < 0.1;
+  let last_band = recording.primitives.iter().rposition(|p| {
+    matches!(p, RecordedPrimitive::Triangle { color, .. } if red(color))
+  });
+  let first_line = recording.primitives.iter().position(|p| {
+    matches!(p, RecordedPrimitive::Line { color, .. } if red(color))
+  });
+  let (Some(band), Some(line)) = (last_band, first_line) else {
+    panic!("band or line missing from the recording");
+  };
+  assert!(band < line, "band drawn over the line");
+}
+
+#[test]
+#[serial]
+fn table_plots_every_column() {
+  let table = Table::parse("t,a,b\n0,1,2\n1,3,4\n");
+  let backend = PlotBackend::new(400, 300);
+  let ax = backend.figure().current_axes();
+  table.plot(&ax, "t", &["a", "b"]).unwrap();
+  assert_eq!(ax.series_ids().len(), 2);
+}
+
+/// Marker at one data point, counted in the autoscaled limits.
+struct Dot(f64, f64);
+
+impl Artist for Dot {
+  fn draw(&self, ctx: &mut DrawContext<'_>) {
+    ctx.marker((self.0, self.1), 3.0, Vec4::ONE);
+  }
+
+  fn extent(&self) -> Option<DataRect> {
+    Some(DataRect::new((self.0, self.0), (self.1, self.1)))
+  }
+}
+
+#[test]
+#[serial]
+fn artists_widen_autoscaled_limits_without_fixing_them() {
+  let mut backend = PlotBackend::new(400, 300);
+  backend.add_artist(Box::new(Dot(5.0, 5.0)));
+  let id = backend.figure().current_axes().plot(&[0.0, 1.0], &[0.0, 1.0], "-").id();
+  backend.record(None);
+  let ax = backend.figure().current_axes();
+  assert!(ax.xlim().1 >= 5.0 && ax.ylim().1 >= 5.0);
+
+  // Data outgrowing the artist is still autoscaled.
+  ax.update_series(id, &[0.0, 20.0], &[0.0, 20.0]);
+  drop(ax);
+  backend.record(None);
+  let ax = backend.figure().current_axes();
+  assert!(ax.xlim().1 >= 20.0 && ax.ylim().1 >= 20.0);
+
+  // Limits set by the user are not widened.
+  ax.set_xlim(-1.0, 2.0);
+  drop(ax);
+  backend.record(None);
+  assert_eq!(backend.figure().current_axes().xlim(), (-1.0, 2.0));
+}
+
+#[test]
+#[serial]
+fn extra_y_axes_follow_the_host_x_range() {
+  let mut backend = PlotBackend::new(400, 300);
+  {
+    let ax = backend.figure().current_axes();
+    ax.plot(&[0.0, 10.0], &[0.0, 1.0], "-");
+    let extra = ax.add_y_axis(40.0, [1.0, 0.0, 0.0]).unwrap();
+    extra.plot(&[0.0, 1.0], &[0.0, 100.0], "o");
+  }
+  let recording = backend.record(None);
+  // The host stays current after add_y_axis.  The extra axes' own data
+  // ends at x = 1, the host's at x = 10, so its last marker sits where
+  // the host draws x = 1.
+  let host = backend.figure().current_axes();
+  assert!(host.xlim().1 >= 10.0, "{:?}", host.xlim());
+  let at = host.viewport().to_pixels(1.0, 0.0).x;
+  let right = recording
+    .primitives
+    .iter()
+    .filter_map(|p| match p {
+      RecordedPrimitive::Marker { center, .. } => Some(center.x),
+      _ => None,
+    })
+    .fold(f32::MIN, f32::max);
+  assert!((right - at).abs() < 1.0, "{right} {at}");
+}
+
+/// Positions of the recorded text in `color`.
+fn text_positions(recording: &Recording, color: Vec4) -> Vec<(f32, f32)> {
+  recording
+    .primitives
+    .iter()
+    .filter_map(|p| match p {
+      RecordedPrimitive::Text { pos, color: c, .. } if (*c - color).abs().max_element() < 0.01 => {
+        Some((pos.x, pos.y))
+      }
+      _ => None,
+    })
+    .collect()
+}
+
+/// Text positions of a host axes (black) and an extra y axis (red),
+/// with `adjust` applied to the host.
+fn host_and_extra_labels(adjust: impl Fn(&Axes<'_>)) -> [Vec<(f32, f32)>; 2] {
+  let mut backend = PlotBackend::new(400, 300);
+  {
+    let ax = backend.figure().current_axes();
+    ax.plot(&[0.0, 10.0], &[0.0, 1.0], "-");
+    ax.set_ylabel("host");
+    adjust(&ax);
+    let extra = ax.add_y_axis(40.0, [1.0, 0.0, 0.0]).unwrap();
+    extra.plot(&[0.0, 10.0], &[0.0, 100.0], "-");
+    extra.set_ylabel("extra");
+  }
+  let recording = backend.record(None);
+  [
+    text_positions(&recording, Vec4::new(0.0, 0.0, 0.0, 1.0)),
+    text_positions(&recording, Vec4::new(1.0, 0.0, 0.0, 1.0)),
+  ]
+}
+
+#[test]
+#[serial]
+fn spine_offsets_and_tick_padding_move_only_their_axes() {
+  let [host, extra] = host_and_extra_labels(|_| {});
+  let [padded, padded_extra] = host_and_extra_labels(|ax| {
+    ax.despine(10.0);
+    ax.set_tick_padding(20.0);
+  });
+  assert!(!extra.is_empty());
+  assert_eq!(padded_extra, extra);
+  assert_ne!(padded, host);
+}
+
+#[test]
+#[serial]
+fn margins_stay_with_the_axes_they_were_set_on() {
+  let mut backend = PlotBackend::new(400, 300);
+  let extra_box = {
+    let extra = backend.figure().current_axes().add_y_axis(40.0, [1.0, 0.0, 0.0]).unwrap();
+    extra.plot(&[0.0, 1.0], &[0.0, 100.0], "-");
+    extra.set_position([0.5, 0.5, 0.25, 0.25]);
+    extra.pixel_box()
+  };
+  // The host stays current and takes the margins.
+  backend.set_margins(10.0, 20.0, 30.0, 40.0);
+  assert_eq!(backend.margins(), Some([10.0, 20.0, 30.0, 40.0]));
+  backend.resize(800, 600);
+  let host = backend.figure().current_axes().pixel_box();
+  for (got, want) in host.into_iter().zip([10.0, 30.0, 780.0, 560.0]) {
+    assert!((got - want).abs() < 1.0, "{host:?}");
+  }
+  // The extra axes is unmoved in figure fractions, so its labels stay
+  // right of its scaled box instead of the host's.
+  let labels = text_positions(&backend.record(None), Vec4::new(1.0, 0.0, 0.0, 1.0));
+  assert!(!labels.is_empty());
+  assert!(labels.iter().all(|&(x, _)| x > extra_box[2] * 2.0 && x < 780.0), "{labels:?}");
+}
+
+#[test]
+#[serial]
+fn toggling_and_picking_report_real_changes() {
+  let mut backend = PlotBackend::new(400, 300);
+  let events = backend.subscribe();
+  let (id, other) = {
+    let ax = backend.figure().current_axes();
+    let id = ax.plot(&[0.0, 1.0], &[0.0, 1.0], "-").id();
+    let other = ax.add_y_axis(40.0, [1.0, 0.0, 0.0]).unwrap().plot(&[0.0, 1.0], &[1.0, 0.0], "-").id();
+    (id, other)
+  };
+  // The host stays current; the extra axes' series is not in it.
+  assert_eq!(backend.toggle_series(other), None);
+  assert_eq!(backend.toggle_series(id), Some(false));
+  assert_eq!(backend.toggle_series(id), Some(true));
+
+  backend.record(None);
+  let at = backend.data_to_screen(1.0, 1.0);
+  assert!(backend.pick(at, 5.0).is_some());
+  assert!(backend.pick(at + Vec2::ONE, 5.0).is_some());
+  let picked = events
+    .try_iter()
+    .filter(|e| matches!(e, PlotEvent::SeriesPicked(_)))
+    .count();
+  assert_eq!(picked, 1);
+}
+
+/// Recorded font size of the text `label`.
+fn text_size(recording: &Recording, label: &str) -> Option<f32> {
+  recording.primitives.iter().find_map(|p| match p {
+    RecordedPrimitive::Text { text, size, .. } if text == label => Some(*size),
+    _ => None,
+  })
+}
+
+#[test]
+#[serial]
+fn shrunk_tick_labels_leave_the_title_and_axis_labels_alone() {
+  // Widen the labels until they just collide.
+  for n in 1..40 {
+    let mut backend = PlotBackend::new(400, 300);
+    {
+      let ax = backend.figure().current_axes();
+      ax.plot(&[0.0, 10.0], &[0.0, 1.0], "-");
+      ax.set_title("Title");
+      ax.set_xlabel("Time");
+      ax.set_xlim(0.0, 10.0);
+    }
+    let before = backend.record(None);
+    let label = "x".repeat(n);
+    let fit = backend.figure().current_axes().set_xticklabels_fitted(&[4.5, 5.5], &[&label, &label]);
+    if fit.strategy != LabelStrategy::Shrink {
+      continue;
+    }
+    let after = backend.record(None);
+    for text in ["Title", "Time"] {
+      assert_eq!(text_size(&after, text), text_size(&before, text), "{text}");
+    }
+    assert!(text_size(&after, &label).unwrap() < text_size(&before, "10").unwrap());
+    return;
+  }
+  panic!("no label width needed shrinking");
+}
+
+#[test]
+#[serial]
+fn tick_label_style_leaves_the_title_and_axis_labels_alone() {
+  let mut backend = PlotBackend::new(400, 300);
+  {
+    let ax = backend.figure().current_axes();
+    ax.plot(&[0.0, 10.0], &[0.0, 1.0], "-");
+    ax.set_title("Title");
+    ax.set_xlabel("Time");
+    ax.set_xlim(0.0, 10.0);
+  }
+  let before = backend.record(None);
+  backend.figure().current_axes().set_tick_label_style(&FontStyle::new(20.0).color(1.0, 0.0, 0.0));
+  let after = backend.record(None);
+  for text in ["Title", "Time"] {
+    assert_eq!(text_size(&after, text), text_size(&before, text), "{text}");
+  }
+  assert!(text_size(&after, "10").unwrap() > text_size(&before, "10").unwrap());
+  // Only the tick labels turn red.
+  let red = text_positions(&after, Vec4::new(1.0, 0.0, 0.0, 1.0));
+  assert!(!red.is_empty());
+  assert!(!text_positions(&after, Vec4::new(0.0, 0.0, 0.0, 1.0)).is_empty());
+}
+
+#[test]
+#[serial]
+fn probe_reads_plotted_series_from_the_axes() {
+  let mut backend = PlotBackend::new(400, 300);
+  let id = {
+    let ax = backend.figure().current_axes();
+    ax.set_xlim(0.0, 10.0);
+    ax.plot(&[0.0, 10.0], &[0.0, 10.0], "-").id()
+  };
+  let mut probe = DataProbe::new();
+  probe.add_plotted("a", id, Vec4::ONE);
+  backend.set_probe(Some(probe));
+  backend.record(None);
+  let [left, _, right, _] = backend.figure().current_axes().pixel_box();
+  let middle = (left + right) / 2.0;
+  assert_eq!(backend.move_probe(middle)[0].value.map(f64::round), Some(5.0));
+
+  backend.figure().current_axes().update_series(id, &[0.0, 10.0], &[10.0, 30.0]);
+  assert_eq!(backend.move_probe(middle)[0].value.map(f64::round), Some(20.0));
+  backend.figure().current_axes().set_visible(id, false);
+  assert!(backend.move_probe(middle).is_empty());
+}
+
+/// Texts of the recorded labels.
+fn texts(recording: &Recording) -> Vec<String> {
+  recording
+    .primitives
+    .iter()
+    .filter_map(|p| match p {
+      RecordedPrimitive::Text { text, .. } => Some(text.clone()),
+      _ => None,
+    })
+    .collect()
+}
+
+#[test]
+#[serial]
+fn temporal_axes_follow_the_figure_locale() {
+  // 2024-01-01 to 2024-07-01, ticked monthly.
+  let (t_min, t_max) = (1_704_067_200.0, 1_719_792_000.0);
+  let mut backend = PlotBackend::new(600, 300);
+  backend.figure().current_axes().plot_time(&[t_min, t_max], &[0.0, 1.0], "-");
+  assert!(texts(&backend.record(None)).iter().any(|t| t == "2024-03"));
+
+  let mut backend = PlotBackend::new(600, 300);
+  backend.set_locale(Locale::english().short_months([
+    "Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez",
+  ]));
+  backend.figure().current_axes().plot_time(&[t_min, t_max], &[0.0, 1.0], "-");
+  assert!(texts(&backend.record(None)).iter().any(|t| t == "Mär 2024"));
+}
+
+#[test]
+#[serial]
+fn formatted_ticks_follow_the_limits() {
+  let mut backend = PlotBackend::new(600, 300);
+  {
+    let ax = backend.figure().current_axes();
+    ax.plot(&[0.0, 1.0], &[0.0, 1.0], "-");
+    ax.format_xticks(|v: f64| format!("{v:.0} m"));
+    ax.set_xlim(0.0, 100.0);
+  }
+  assert!(texts(&backend.record(None)).iter().any(|t| t == "100 m"));
+
+  // Explicit ticks replace the formatter.
+  backend.figure().current_axes().set_xticks(&[0.0, 50.0]);
+  backend.figure().current_axes().set_xlim(0.0, 200.0);
+  assert!(!texts(&backend.record(None)).iter().any(|t| t == "200 m"));
+}
+
+#[test]
+#[serial]
+fn temporal_ticks_follow_the_limits() {
+  // 2024-01-01 to 2024-07-01, then zoomed to the morning of 2024-03-01.
+  let (t_min, t_max) = (1_704_067_200.0, 1_719_792_000.0);
+  let mut backend = PlotBackend::new(600, 300);
+  backend.figure().current_axes().plot_time(&[t_min, t_max], &[0.0, 1.0], "-");
+  assert!(texts(&backend.record(None)).iter().any(|t| t == "2024-03"));
+
+  backend.figure().current_axes().set_xlim(1_709_251_200.0, 1_709_294_400.0);
+  assert!(texts(&backend.record(None)).iter().any(|t| t == "06:00"));
+}
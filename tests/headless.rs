@@ -0,0 +1,40 @@
+// Copyright (c) 2026 Karl Ruskowski
+// SPDX-License-Identifier: MIT
+
+//! Pixel checks of overlays and renderers drawn on the headless GPU
+//! context, for cases a golden image would not pin down.
+
+use mpl_wgpu::capture::{shared_context, PlotCapture};
+use mpl_wgpu::glam::Vec2;
+use mpl_wgpu::plotting::PlotBackend;
+use serial_test::serial;
+
+const SIZE: (u32, u32) = (400, 300);
+
+/// RGBA of pixel (`x`, `y`) of a tightly packed `SIZE` frame.
+fn pixel(frame: &[u8], x: u32, y: u32) -> [u8; 4] {
+  let i = ((y * SIZE.0 + x) * 4) as usize;
+  [frame[i], frame[i + 1], frame[i + 2], frame[i + 3]]
+}
+
+/// Whether `p` has the blue tint of the box-zoom band.
+fn tinted(p: [u8; 4]) -> bool {
+  p[2] as i32 - p[0] as i32 > 10
+}
+
+#[test]
+#[serial]
+fn box_zoom_band_covers_the_dragged_rectangle() {
+  let mut backend = PlotBackend::new(SIZE.0, SIZE.1);
+  backend.begin_box_zoom(Vec2::new(120.0, 100.0));
+  backend.update_box_zoom(Vec2::new(220.0, 160.0));
+  let ctx = shared_context().expect("headless GPU context");
+  let mut cap = PlotCapture::from_backend(ctx, backend);
+  let frame = cap.render_and_capture().expect("read back frame");
+
+  // Inside, near the far corner of the drag.
+  assert!(tinted(pixel(&frame, 210, 150)));
+  // Up and left of the start corner, where a band centered on it
+  // would reach.
+  assert!(!tinted(pixel(&frame, 90, 80)));
+}
@@ -35,7 +35,7 @@ fn run(name: &str) {
     .unwrap_or_else(|| {
       panic!("Unknown test case: {}", name)
     });
-  common::run_visual_test(tc.name, tc.setup);
+  common::run_visual_test(tc.name, tc.size, tc.setup);
 }
 
 #[test]
@@ -80,6 +80,12 @@ fn test_heatmap() {
   run("heatmap");
 }
 
+#[test]
+#[serial]
+fn test_heatmap_masked() {
+  run("heatmap_masked");
+}
+
 #[test]
 #[serial]
 fn test_surface_3d() {